@@ -31,6 +31,7 @@ fn plan() -> AblationPlan {
         )]
         .into_iter()
         .collect(),
+        correlation: None,
     }
 }
 
@@ -21,6 +21,7 @@ fn make_plan() -> SweepPlan {
             ],
         },
         scheduler: Default::default(),
+        concurrency: 1,
     }
 }
 
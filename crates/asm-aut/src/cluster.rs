@@ -2,7 +2,8 @@ use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{AnalysisReport, ClusterOpts};
+use crate::invariants::compare_reports;
+use crate::{AnalysisReport, ClusterMetric, ClusterOpts};
 
 /// Cluster level summary describing membership and representatives.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -24,6 +25,9 @@ pub(crate) fn cluster_reports(reports: &[AnalysisReport], opts: &ClusterOpts) ->
         return Vec::new();
     }
     let k = opts.k.max(1).min(reports.len());
+    if opts.metric == ClusterMetric::Similarity {
+        return cluster_by_similarity(reports, k, opts.max_iterations);
+    }
     let features: Vec<Vec<f64>> = reports.iter().map(feature_vector).collect();
     let mut centroids = initialise_centroids(&features, k, reports);
     let mut assignments = vec![0usize; reports.len()];
@@ -39,6 +43,89 @@ pub(crate) fn cluster_reports(reports: &[AnalysisReport], opts: &ClusterOpts) ->
     build_summary(reports, &features, &assignments, &centroids)
 }
 
+/// k-medoids over pairwise [`compare_reports`] distances. Medoids start at the
+/// reports with the smallest analysis hashes, and ties resolve to the lowest
+/// report index, so the result is deterministic.
+fn cluster_by_similarity(
+    reports: &[AnalysisReport],
+    k: usize,
+    max_iterations: usize,
+) -> Vec<ClusterInfo> {
+    let n = reports.len();
+    let mut distances = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in i + 1..n {
+            let distance = compare_reports(&reports[i], &reports[j]).distance;
+            distances[i][j] = distance;
+            distances[j][i] = distance;
+        }
+    }
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| {
+        reports[a]
+            .hashes
+            .analysis_hash
+            .cmp(&reports[b].hashes.analysis_hash)
+    });
+    let mut medoids: Vec<usize> = order.into_iter().take(k).collect();
+    let nearest = |medoids: &[usize], idx: usize| {
+        let mut best = 0usize;
+        for (cluster, &medoid) in medoids.iter().enumerate() {
+            if distances[idx][medoid] < distances[idx][medoids[best]] {
+                best = cluster;
+            }
+        }
+        best
+    };
+
+    let mut assignments: Vec<usize> = (0..n).map(|idx| nearest(&medoids, idx)).collect();
+    for _ in 0..max_iterations.max(1) {
+        let mut updated = medoids.clone();
+        for (cluster, medoid) in updated.iter_mut().enumerate() {
+            let members: Vec<usize> = (0..n).filter(|&idx| assignments[idx] == cluster).collect();
+            let cost = |candidate: usize| -> f64 {
+                members.iter().map(|&idx| distances[candidate][idx]).sum()
+            };
+            if let Some(&best) = members
+                .iter()
+                .min_by(|&&a, &&b| cost(a).total_cmp(&cost(b)).then(a.cmp(&b)))
+            {
+                *medoid = best;
+            }
+        }
+        let reassigned: Vec<usize> = (0..n).map(|idx| nearest(&updated, idx)).collect();
+        let converged = updated == medoids && reassigned == assignments;
+        medoids = updated;
+        assignments = reassigned;
+        if converged {
+            break;
+        }
+    }
+
+    let total = n as f64;
+    let mut cluster_members: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (idx, &cluster) in assignments.iter().enumerate() {
+        cluster_members.entry(cluster).or_default().push(idx);
+    }
+    cluster_members
+        .into_iter()
+        .map(|(cluster_id, members)| {
+            let mut member_hashes: Vec<String> = members
+                .iter()
+                .map(|&idx| reports[idx].hashes.analysis_hash.clone())
+                .collect();
+            member_hashes.sort();
+            ClusterInfo {
+                cluster_id,
+                size: members.len(),
+                centroid_report_hash: reports[medoids[cluster_id]].hashes.analysis_hash.clone(),
+                members: member_hashes,
+                occupancy: members.len() as f64 / total,
+            }
+        })
+        .collect()
+}
+
 fn feature_vector(report: &AnalysisReport) -> Vec<f64> {
     let mut vector = Vec::new();
     let total_orbits: f64 = report.graph_aut.orbit_hist.iter().map(|&v| v as f64).sum();
@@ -34,7 +34,12 @@ pub fn combine_for_hash(
         "graph_aut": graph,
         "code_aut": code,
         "logical": logical,
-        "spectral": spectral,
+        // Solver diagnostics describe how the spectra were computed, not the
+        // state, so only the eigenvalues enter the hash.
+        "spectral": {
+            "laplacian_topk": spectral.laplacian_topk,
+            "stabilizer_topk": spectral.stabilizer_topk,
+        },
         "provenance": provenance,
     }))
     .map_err(|err| AsmError::Serde(ErrorInfo::new("analysis-hash", err.to_string())))
@@ -56,6 +61,7 @@ pub fn compare_reports(a: &AnalysisReport, b: &AnalysisReport) -> SimilarityScor
     let spectral_delta = combine_spectral_delta(&a.spectral, &b.spectral);
     components.insert("spectral".to_string(), spectral_delta);
 
+    // Every component is normalised into [0, 1], so their mean is as well.
     let distance = if components.is_empty() {
         0.0
     } else {
@@ -68,9 +74,13 @@ pub fn compare_reports(a: &AnalysisReport, b: &AnalysisReport) -> SimilarityScor
     }
 }
 
+/// L2 distance between sorted spectra at which a spectral delta reads 0.5;
+/// the delta is `d / (d + SPECTRAL_L2_SCALE)`, so it stays below 1.
+const SPECTRAL_L2_SCALE: f64 = 1.0;
+
 fn combine_graph_delta(a: &GraphAutReport, b: &GraphAutReport) -> f64 {
     let order_delta = log_ratio_delta(a.order, b.order);
-    let orbit_delta = histogram_delta(&a.orbit_hist, &b.orbit_hist);
+    let orbit_delta = histogram_emd(&a.orbit_hist, &b.orbit_hist);
     (order_delta + orbit_delta) / 2.0
 }
 
@@ -96,8 +106,8 @@ fn combine_logical_delta(a: &LogicalReport, b: &LogicalReport) -> f64 {
 }
 
 fn combine_spectral_delta(a: &SpectralReport, b: &SpectralReport) -> f64 {
-    let laplacian = vector_delta(&a.laplacian_topk, &b.laplacian_topk);
-    let stabilizer = vector_delta(&a.stabilizer_topk, &b.stabilizer_topk);
+    let laplacian = spectrum_delta(&a.laplacian_topk, &b.laplacian_topk);
+    let stabilizer = spectrum_delta(&a.stabilizer_topk, &b.stabilizer_topk);
     (laplacian + stabilizer) / 2.0
 }
 
@@ -119,41 +129,54 @@ fn normalised_difference(a: f64, b: f64) -> f64 {
     }
 }
 
-fn histogram_delta(a: &[u32], b: &[u32]) -> f64 {
+/// Earth mover's (1D Wasserstein) distance between two normalised histograms,
+/// with unit spacing between bins and the shorter one padded with empty bins.
+///
+/// Moving all mass from the first to the last of `n` bins costs `n - 1`, the
+/// largest possible transport, so dividing by `n - 1` bounds the delta to
+/// [0, 1] while shifting a histogram by one bin costs only `1 / (n - 1)`.
+fn histogram_emd(a: &[u32], b: &[u32]) -> f64 {
     let sum_a: f64 = a.iter().map(|&x| x as f64).sum();
     let sum_b: f64 = b.iter().map(|&x| x as f64).sum();
     if sum_a == 0.0 && sum_b == 0.0 {
         return 0.0;
     }
-    let max_len = a.len().max(b.len());
-    let mut delta = 0.0;
-    for idx in 0..max_len {
-        let va = if idx < a.len() {
-            a[idx] as f64 / sum_a.max(1.0)
-        } else {
-            0.0
-        };
-        let vb = if idx < b.len() {
-            b[idx] as f64 / sum_b.max(1.0)
-        } else {
-            0.0
-        };
-        delta += (va - vb).abs();
+    if sum_a == 0.0 || sum_b == 0.0 {
+        return 1.0;
+    }
+    let bins = a.len().max(b.len());
+    if bins < 2 {
+        return 0.0;
+    }
+    let mut cdf_a = 0.0;
+    let mut cdf_b = 0.0;
+    let mut work = 0.0;
+    for idx in 0..bins - 1 {
+        cdf_a += a.get(idx).map_or(0.0, |&x| x as f64 / sum_a);
+        cdf_b += b.get(idx).map_or(0.0, |&x| x as f64 / sum_b);
+        work += (cdf_a - cdf_b).abs();
     }
-    (delta * 0.5).min(1.0)
+    (work / (bins - 1) as f64).min(1.0)
 }
 
-fn vector_delta(a: &[f64], b: &[f64]) -> f64 {
+/// L2 distance between two eigenvalue lists after sorting both and
+/// truncating to their common length, squashed by [`SPECTRAL_L2_SCALE`].
+fn spectrum_delta(a: &[f64], b: &[f64]) -> f64 {
     if a.is_empty() && b.is_empty() {
         return 0.0;
     }
-    let max_len = a.len().max(b.len());
-    let mut sum_sq = 0.0;
-    for idx in 0..max_len {
-        let va = if idx < a.len() { a[idx] } else { 0.0 };
-        let vb = if idx < b.len() { b[idx] } else { 0.0 };
-        sum_sq += (va - vb).powi(2);
+    if a.is_empty() || b.is_empty() {
+        return 1.0;
     }
-    let dist = sum_sq.sqrt();
-    dist / (dist + 1.0)
+    let mut sorted_a = a.to_vec();
+    let mut sorted_b = b.to_vec();
+    sorted_a.sort_by(f64::total_cmp);
+    sorted_b.sort_by(f64::total_cmp);
+    let dist = sorted_a
+        .iter()
+        .zip(&sorted_b)
+        .map(|(va, vb)| (va - vb).powi(2))
+        .sum::<f64>()
+        .sqrt();
+    dist / (dist + SPECTRAL_L2_SCALE)
 }
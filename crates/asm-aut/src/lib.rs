@@ -35,7 +35,7 @@ use hash::{compute_hashes, HashReport};
 use invariants::ProvenanceInfo;
 use logical::LogicalReport;
 use serde::{Deserialize, Serialize};
-use spectral::{SpectralOptions, SpectralReport};
+use spectral::{SpectralOptions, SpectralReport, StabilizerBackend};
 
 /// Options controlling symmetry scans.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -44,6 +44,10 @@ pub struct ScanOpts {
     pub laplacian_topk: usize,
     /// Number of stabilizer spectrum eigenvalues to retain.
     pub stabilizer_topk: usize,
+    /// Stabilizer eigensolver override; chosen from the constraint count when
+    /// absent.
+    #[serde(default)]
+    pub stabilizer_backend: Option<StabilizerBackend>,
     /// Optional provenance metadata to include in the report.
     #[serde(default)]
     pub provenance: Option<ProvenanceInfo>,
@@ -54,6 +58,7 @@ impl Default for ScanOpts {
         Self {
             laplacian_topk: 16,
             stabilizer_topk: 16,
+            stabilizer_backend: None,
             provenance: None,
         }
     }
@@ -68,6 +73,21 @@ pub struct ClusterOpts {
     pub max_iterations: usize,
     /// Deterministic tie-breaking seed used for centroid selection.
     pub seed: u64,
+    /// Distance used to assign reports to clusters.
+    #[serde(default)]
+    pub metric: ClusterMetric,
+}
+
+/// Distance driving [`cluster`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClusterMetric {
+    /// k-means over Euclidean invariant feature vectors.
+    #[default]
+    Features,
+    /// k-medoids over the [`compare`] distance, including the orbit histogram
+    /// earth mover's distance and sorted-spectrum comparison.
+    Similarity,
 }
 
 impl Default for ClusterOpts {
@@ -76,6 +96,7 @@ impl Default for ClusterOpts {
             k: 2,
             max_iterations: 16,
             seed: 0xA5A5_2024,
+            metric: ClusterMetric::Features,
         }
     }
 }
@@ -126,6 +147,7 @@ pub fn analyze_state(
     let spectral_opts = SpectralOptions {
         laplacian_topk: opts.laplacian_topk,
         stabilizer_topk: opts.stabilizer_topk,
+        stabilizer_backend: opts.stabilizer_backend,
     };
     let spectral = spectral::analyse_spectra(graph, code, &canonical, &spectral_opts)?;
     let provenance = opts.provenance.clone().unwrap_or_default();
@@ -2,10 +2,38 @@ use asm_code::{hash, CSSCode};
 use asm_core::AsmError;
 use asm_graph::HypergraphImpl;
 use nalgebra::{DMatrix, SymmetricEigen};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 
 use crate::canonical::CanonicalStructures;
 
+/// Largest constraint count for which the automatic backend selection uses the
+/// dense stabiliser eigensolver.
+pub const DENSE_STABILIZER_LIMIT: usize = 512;
+
+/// Seed of the start block used by the sparse stabiliser eigensolver.
+const SPARSE_START_SEED: u64 = 0x5AB1_5EED_0C0D_E5EC;
+/// Extra block columns iterated beyond the requested eigenvalue count.
+const SPARSE_OVERSAMPLING: usize = 8;
+/// Krylov blocks per restart of the sparse stabiliser eigensolver.
+const SPARSE_KRYLOV_DEPTH: usize = 4;
+/// Restart cap of the sparse stabiliser eigensolver.
+const SPARSE_MAX_ITERATIONS: usize = 500;
+/// Residual tolerance, relative to the largest Ritz value, at which the
+/// sparse eigensolver stops.
+const SPARSE_TOLERANCE: f64 = 1e-10;
+
+/// Eigensolver used for the stabiliser spectrum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StabilizerBackend {
+    /// Full eigendecomposition of the dense check-overlap matrix.
+    #[default]
+    Dense,
+    /// Restarted block Lanczos over the sparse check-overlap matrix.
+    Sparse,
+}
+
 /// Options controlling spectral analyses.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SpectralOptions {
@@ -13,6 +41,23 @@ pub struct SpectralOptions {
     pub laplacian_topk: usize,
     /// Number of stabiliser spectrum eigenvalues to retain.
     pub stabilizer_topk: usize,
+    /// Stabiliser eigensolver; `None` picks the dense backend up to
+    /// [`DENSE_STABILIZER_LIMIT`] constraints and the sparse one beyond.
+    pub stabilizer_backend: Option<StabilizerBackend>,
+}
+
+/// Diagnostics of the stabiliser spectrum computation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct StabilizerSolver {
+    /// Backend that produced the stabiliser spectrum.
+    pub backend: StabilizerBackend,
+    /// Block Lanczos restarts performed; zero for the dense backend.
+    pub iterations: usize,
+    /// Largest residual norm `|G v - θ v|` over the retained Ritz pairs,
+    /// relative to the largest eigenvalue; zero for the dense backend.
+    pub max_residual: f64,
+    /// Whether the residual tolerance was reached within the iteration cap.
+    pub converged: bool,
 }
 
 /// Spectral invariants captured during analysis.
@@ -22,6 +67,10 @@ pub struct SpectralReport {
     pub laplacian_topk: Vec<f64>,
     /// Top-k eigenvalues of the stabiliser Gram matrix.
     pub stabilizer_topk: Vec<f64>,
+    /// How the stabiliser spectrum was computed. Excluded from the analysis
+    /// hash, which only covers the eigenvalues.
+    #[serde(default)]
+    pub stabilizer_solver: StabilizerSolver,
 }
 
 /// Computes spectral invariants for the provided state.
@@ -32,10 +81,12 @@ pub fn analyse_spectra(
     opts: &SpectralOptions,
 ) -> Result<SpectralReport, AsmError> {
     let laplacian = laplacian_spectrum(canonical, opts.laplacian_topk)?;
-    let stabilizer = stabilizer_spectrum(code, opts.stabilizer_topk)?;
+    let (stabilizer, solver) =
+        stabilizer_spectrum(code, opts.stabilizer_topk, opts.stabilizer_backend)?;
     Ok(SpectralReport {
         laplacian_topk: laplacian,
         stabilizer_topk: stabilizer,
+        stabilizer_solver: solver,
     })
 }
 
@@ -71,10 +122,43 @@ fn laplacian_spectrum(canonical: &CanonicalStructures, topk: usize) -> Result<Ve
     Ok(eigenvalues.into_iter().map(round_eigenvalue).collect())
 }
 
-fn stabilizer_spectrum(code: &CSSCode, topk: usize) -> Result<Vec<f64>, AsmError> {
-    if topk == 0 {
-        return Ok(Vec::new());
+fn stabilizer_spectrum(
+    code: &CSSCode,
+    topk: usize,
+    backend: Option<StabilizerBackend>,
+) -> Result<(Vec<f64>, StabilizerSolver), AsmError> {
+    let constraints = code.num_constraints_x() + code.num_constraints_z();
+    if topk == 0 || constraints == 0 {
+        return Ok((Vec::new(), dense_solver()));
     }
+    let backend = backend.unwrap_or(if constraints <= DENSE_STABILIZER_LIMIT {
+        StabilizerBackend::Dense
+    } else {
+        StabilizerBackend::Sparse
+    });
+    let (eigenvalues, solver) = match backend {
+        StabilizerBackend::Dense => (dense_stabilizer_spectrum(code), dense_solver()),
+        StabilizerBackend::Sparse => sparse_stabilizer_spectrum(code, topk),
+    };
+    let mut eigenvalues = eigenvalues;
+    eigenvalues.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+    eigenvalues.truncate(topk.min(eigenvalues.len()));
+    Ok((
+        eigenvalues.into_iter().map(round_eigenvalue).collect(),
+        solver,
+    ))
+}
+
+fn dense_solver() -> StabilizerSolver {
+    StabilizerSolver {
+        backend: StabilizerBackend::Dense,
+        iterations: 0,
+        max_residual: 0.0,
+        converged: true,
+    }
+}
+
+fn dense_stabilizer_spectrum(code: &CSSCode) -> Vec<f64> {
     let (num_variables, x_checks, z_checks, ..) = hash::decompose(code);
     let mut supports: Vec<Vec<usize>> = x_checks
         .into_iter()
@@ -82,9 +166,6 @@ fn stabilizer_spectrum(code: &CSSCode, topk: usize) -> Result<Vec<f64>, AsmError
         .map(|constraint| constraint.variables().to_vec())
         .collect();
     supports.sort();
-    if supports.is_empty() {
-        return Ok(Vec::new());
-    }
     let rows = supports.len();
     let mut matrix = DMatrix::<f64>::zeros(rows, num_variables);
     for (row, support) in supports.iter().enumerate() {
@@ -95,10 +176,199 @@ fn stabilizer_spectrum(code: &CSSCode, topk: usize) -> Result<Vec<f64>, AsmError
     let gram = &matrix * matrix.transpose();
     let sym_gram = 0.5 * (&gram + gram.transpose());
     let eigen = SymmetricEigen::new(sym_gram);
-    let mut eigenvalues: Vec<f64> = eigen.eigenvalues.iter().copied().collect();
-    eigenvalues.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
-    eigenvalues.truncate(topk.min(eigenvalues.len()));
-    Ok(eigenvalues.into_iter().map(round_eigenvalue).collect())
+    eigen.eigenvalues.iter().copied().collect()
+}
+
+/// Check-overlap matrix in row-compressed form: X checks first, then Z
+/// checks, each row listing `(column, support intersection size)`.
+struct CheckOverlap {
+    rows: Vec<Vec<(usize, f64)>>,
+}
+
+impl CheckOverlap {
+    fn build(code: &CSSCode, supports: &[Vec<usize>]) -> Self {
+        let num_x = code.num_constraints_x();
+        let mut counts = vec![0.0; supports.len()];
+        let mut touched = Vec::new();
+        let rows = supports
+            .iter()
+            .map(|support| {
+                for &var in support {
+                    let x_checks = code.x_adjacency(var).iter().copied();
+                    let z_checks = code.z_adjacency(var).iter().map(|&check| num_x + check);
+                    for check in x_checks.chain(z_checks) {
+                        if counts[check] == 0.0 {
+                            touched.push(check);
+                        }
+                        counts[check] += 1.0;
+                    }
+                }
+                touched.sort_unstable();
+                let row = touched
+                    .drain(..)
+                    .map(|check| (check, std::mem::take(&mut counts[check])))
+                    .collect();
+                row
+            })
+            .collect();
+        Self { rows }
+    }
+
+    fn mul(&self, vector: &[f64]) -> Vec<f64> {
+        self.rows
+            .iter()
+            .map(|entries| {
+                entries
+                    .iter()
+                    .map(|&(check, overlap)| overlap * vector[check])
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+/// Top eigenvalues of the check-overlap matrix by restarted block Lanczos:
+/// each iteration runs Rayleigh-Ritz over the block Krylov space
+/// `[Q, GQ, ..., G^(depth-1) Q]` and restarts from its leading Ritz vectors.
+/// The block is wider than `topk` so that repeated eigenvalues are resolved
+/// with their multiplicity.
+fn sparse_stabilizer_spectrum(code: &CSSCode, topk: usize) -> (Vec<f64>, StabilizerSolver) {
+    let (_, x_checks, z_checks, ..) = hash::decompose(code);
+    let supports: Vec<Vec<usize>> = x_checks
+        .into_iter()
+        .chain(z_checks)
+        .map(|constraint| constraint.variables().to_vec())
+        .collect();
+    let overlap = CheckOverlap::build(code, &supports);
+    let dim = supports.len();
+    let keep = topk.min(dim);
+    let width = (keep + SPARSE_OVERSAMPLING).min(dim);
+    let depth = SPARSE_KRYLOV_DEPTH.min(dim / width).max(1);
+
+    // Start rows follow the sorted supports so the start block, and hence the
+    // result, does not depend on the order in which checks are stored.
+    let mut order: Vec<usize> = (0..dim).collect();
+    order.sort_by(|&a, &b| supports[a].cmp(&supports[b]));
+    let mut rng = StdRng::seed_from_u64(SPARSE_START_SEED);
+    let mut block = vec![vec![0.0; dim]; width];
+    for &row in &order {
+        for column in block.iter_mut() {
+            column[row] = rng.gen_range(-1.0..1.0);
+        }
+    }
+    orthonormalise(&[], &mut block, &mut rng);
+    let mut images: Vec<Vec<f64>> = block.iter().map(|column| overlap.mul(column)).collect();
+
+    let mut solver = StabilizerSolver {
+        backend: StabilizerBackend::Sparse,
+        iterations: 0,
+        max_residual: f64::INFINITY,
+        converged: false,
+    };
+    let mut ritz_values = Vec::new();
+    while solver.iterations < SPARSE_MAX_ITERATIONS {
+        solver.iterations += 1;
+        let mut basis = block;
+        for _ in 1..depth {
+            let mut next = images[images.len() - width..].to_vec();
+            orthonormalise(&basis, &mut next, &mut rng);
+            images.extend(next.iter().map(|column| overlap.mul(column)));
+            basis.extend(next);
+        }
+        let size = basis.len();
+        let mut projected = DMatrix::<f64>::zeros(size, size);
+        for i in 0..size {
+            for j in i..size {
+                let entry = 0.5 * (dot(&basis[i], &images[j]) + dot(&basis[j], &images[i]));
+                projected[(i, j)] = entry;
+                projected[(j, i)] = entry;
+            }
+        }
+        let eigen = SymmetricEigen::new(projected);
+        let mut pairs: Vec<usize> = (0..size).collect();
+        pairs.sort_by(|&a, &b| {
+            eigen.eigenvalues[b]
+                .partial_cmp(&eigen.eigenvalues[a])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let combine = |columns: &[Vec<f64>], pair: usize| {
+            let mut combined = vec![0.0; dim];
+            for (column, &coefficient) in columns.iter().zip(eigen.eigenvectors.column(pair).iter())
+            {
+                for (value, &entry) in combined.iter_mut().zip(column) {
+                    *value += coefficient * entry;
+                }
+            }
+            combined
+        };
+        block = pairs[..width]
+            .iter()
+            .map(|&pair| combine(&basis, pair))
+            .collect();
+        let next_images: Vec<Vec<f64>> = pairs[..width]
+            .iter()
+            .map(|&pair| combine(&images, pair))
+            .collect();
+        images = next_images;
+
+        let scale = eigen.eigenvalues[pairs[0]].abs().max(1.0);
+        solver.max_residual = pairs[..keep]
+            .iter()
+            .enumerate()
+            .map(|(idx, &pair)| {
+                let value = eigen.eigenvalues[pair];
+                let residual: f64 = images[idx]
+                    .iter()
+                    .zip(&block[idx])
+                    .map(|(image, vector)| (image - value * vector).powi(2))
+                    .sum();
+                residual.sqrt() / scale
+            })
+            .fold(0.0, f64::max);
+        ritz_values = pairs[..keep]
+            .iter()
+            .map(|&pair| eigen.eigenvalues[pair])
+            .collect();
+        if solver.max_residual <= SPARSE_TOLERANCE {
+            solver.converged = true;
+            break;
+        }
+    }
+    (ritz_values, solver)
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Orthonormalises `block` against `basis` and itself by modified
+/// Gram-Schmidt with one reorthogonalisation pass. Columns that collapse,
+/// because the Krylov space became invariant, are replaced by fresh random
+/// directions.
+fn orthonormalise(basis: &[Vec<f64>], block: &mut [Vec<f64>], rng: &mut StdRng) {
+    for current in 0..block.len() {
+        let (done, rest) = block.split_at_mut(current);
+        let column = &mut rest[0];
+        let original = dot(column, column).sqrt();
+        loop {
+            for _ in 0..2 {
+                for previous in basis.iter().chain(done.iter()) {
+                    let projection = dot(previous, column);
+                    for (value, &entry) in column.iter_mut().zip(previous) {
+                        *value -= projection * entry;
+                    }
+                }
+            }
+            let norm = dot(column, column).sqrt();
+            if norm > 1e-10 * original.max(1.0) {
+                column.iter_mut().for_each(|value| *value /= norm);
+                break;
+            }
+            column
+                .iter_mut()
+                .for_each(|value| *value = rng.gen_range(-1.0..1.0));
+        }
+    }
 }
 
 fn round_eigenvalue(value: f64) -> f64 {
@@ -1,4 +1,4 @@
-use asm_aut::{analyze_state, cluster, ClusterOpts, ScanOpts};
+use asm_aut::{analyze_state, cluster, ClusterMetric, ClusterOpts, ScanOpts};
 use asm_core::AsmError;
 
 mod fixtures;
@@ -19,6 +19,7 @@ fn clustering_is_stable() -> Result<(), AsmError> {
         k: 2,
         max_iterations: 8,
         seed: 0xA5A5,
+        metric: ClusterMetric::Features,
     };
     let summary_a = cluster(&reports, &cluster_opts);
     let summary_b = cluster(&reports, &cluster_opts);
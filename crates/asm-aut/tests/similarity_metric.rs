@@ -0,0 +1,91 @@
+use asm_aut::{
+    analyze_state, cluster, compare, AnalysisReport, ClusterMetric, ClusterOpts, ScanOpts,
+};
+use asm_core::AsmError;
+
+mod fixtures;
+
+fn base_report() -> Result<AnalysisReport, AsmError> {
+    let fixture = fixtures::load_fixture("t1_seed0")?;
+    let opts = ScanOpts {
+        provenance: Some(fixtures::provenance_from_manifest(&fixture.manifest)),
+        ..ScanOpts::default()
+    };
+    analyze_state(&fixture.graph, &fixture.code, &opts)
+}
+
+fn with_orbits(base: &AnalysisReport, orbit_hist: Vec<u32>, hash: &str) -> AnalysisReport {
+    let mut report = base.clone();
+    report.graph_aut.orbit_hist = orbit_hist;
+    report.hashes.analysis_hash = hash.to_string();
+    report
+}
+
+#[test]
+fn shifted_orbit_histograms_are_near() -> Result<(), AsmError> {
+    let base = base_report()?;
+    let a = with_orbits(&base, vec![0, 4, 2, 1, 0, 0, 0, 0, 0, 0], "a");
+    let shifted = with_orbits(&base, vec![0, 0, 4, 2, 1, 0, 0, 0, 0, 0], "b");
+    let opposite = with_orbits(&base, vec![0, 0, 0, 0, 0, 0, 0, 1, 2, 4], "c");
+
+    let near = compare(&a, &shifted);
+    let far = compare(&a, &opposite);
+    // Element-wise comparison scored the one-bin shift as nearly disjoint.
+    assert!((near.components["graph"] - 0.5 / 9.0).abs() < 1e-12);
+    assert!(near.components["graph"] < 0.1);
+    assert!(far.components["graph"] > 3.0 * near.components["graph"]);
+    assert!(near.distance < far.distance);
+    assert_eq!(
+        near.components.keys().collect::<Vec<_>>(),
+        ["code", "graph", "logical", "spectral"]
+    );
+    assert_eq!(compare(&a, &a).distance, 0.0);
+    for score in [&near, &far] {
+        assert!((0.0..=1.0).contains(&score.distance));
+        assert!(score.components.values().all(|v| (0.0..=1.0).contains(v)));
+    }
+    Ok(())
+}
+
+#[test]
+fn spectra_compare_sorted_over_common_length() -> Result<(), AsmError> {
+    let base = base_report()?;
+    let mut a = base.clone();
+    a.spectral.laplacian_topk = vec![3.0, 1.0, 2.0];
+    a.spectral.stabilizer_topk = vec![1.0, 2.0];
+    let mut b = base.clone();
+    b.spectral.laplacian_topk = vec![1.0, 2.0, 3.0, 9.0];
+    b.spectral.stabilizer_topk = vec![2.0, 1.0];
+    assert_eq!(compare(&a, &b).components["spectral"], 0.0);
+
+    b.spectral.laplacian_topk = vec![1.0, 2.0, 4.0];
+    let delta = compare(&a, &b).components["spectral"];
+    assert!((delta - 0.25).abs() < 1e-12);
+    Ok(())
+}
+
+#[test]
+fn similarity_clustering_groups_shifted_histograms() -> Result<(), AsmError> {
+    let base = base_report()?;
+    let reports = vec![
+        with_orbits(&base, vec![5, 3, 1, 0, 0, 0, 0, 0], "r0"),
+        with_orbits(&base, vec![0, 0, 0, 0, 0, 1, 3, 5], "r1"),
+        with_orbits(&base, vec![0, 5, 3, 1, 0, 0, 0, 0], "r2"),
+        with_orbits(&base, vec![0, 0, 0, 0, 1, 3, 5, 0], "r3"),
+    ];
+    let opts = ClusterOpts {
+        k: 2,
+        metric: ClusterMetric::Similarity,
+        ..ClusterOpts::default()
+    };
+    let summary = cluster(&reports, &opts);
+    let mut groups: Vec<Vec<String>> = summary
+        .clusters
+        .iter()
+        .map(|info| info.members.clone())
+        .collect();
+    groups.sort();
+    assert_eq!(groups, [["r0", "r2"], ["r1", "r3"]]);
+    assert_eq!(summary, cluster(&reports, &opts));
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use asm_aut::canonical::CanonicalStructures;
+use asm_aut::spectral::{
+    analyse_spectra, SpectralOptions, SpectralReport, StabilizerBackend, DENSE_STABILIZER_LIMIT,
+};
+use asm_aut::{analyze_state, ScanOpts};
+use asm_code::CSSCode;
+use asm_core::{AsmError, RunProvenance, SchemaVersion};
+use asm_graph::HypergraphImpl;
+
+mod fixtures;
+
+/// Toric code on an `l x l` torus: star X checks and plaquette Z checks.
+fn toric_code(l: usize) -> CSSCode {
+    let h = |x: usize, y: usize| (x % l) + (y % l) * l;
+    let v = |x: usize, y: usize| l * l + (x % l) + (y % l) * l;
+    let mut stars = Vec::new();
+    let mut plaquettes = Vec::new();
+    for y in 0..l {
+        for x in 0..l {
+            stars.push(vec![h(x, y), h(x + l - 1, y), v(x, y), v(x, y + l - 1)]);
+            plaquettes.push(vec![h(x, y), h(x, y + 1), v(x, y), v(x + 1, y)]);
+        }
+    }
+    CSSCode::new(
+        2 * l * l,
+        stars,
+        plaquettes,
+        SchemaVersion::new(1, 0, 0),
+        RunProvenance::default(),
+    )
+    .expect("toric code")
+}
+
+fn spectra(
+    code: &CSSCode,
+    topk: usize,
+    backend: Option<StabilizerBackend>,
+) -> Result<SpectralReport, AsmError> {
+    let graph = HypergraphImpl::default();
+    let canonical = CanonicalStructures::build(&graph, code)?;
+    let opts = SpectralOptions {
+        laplacian_topk: 0,
+        stabilizer_topk: topk,
+        stabilizer_backend: backend,
+    };
+    analyse_spectra(&graph, code, &canonical, &opts)
+}
+
+fn assert_backends_agree<F>(spectra_with: F) -> Result<(), AsmError>
+where
+    F: Fn(StabilizerBackend) -> Result<SpectralReport, AsmError>,
+{
+    let dense = spectra_with(StabilizerBackend::Dense)?;
+    let sparse = spectra_with(StabilizerBackend::Sparse)?;
+    assert_eq!(dense.stabilizer_solver.backend, StabilizerBackend::Dense);
+    assert_eq!(sparse.stabilizer_solver.backend, StabilizerBackend::Sparse);
+    assert!(sparse.stabilizer_solver.converged);
+    assert!(sparse.stabilizer_solver.iterations > 0);
+    assert_eq!(dense.stabilizer_topk.len(), sparse.stabilizer_topk.len());
+    for (a, b) in dense.stabilizer_topk.iter().zip(&sparse.stabilizer_topk) {
+        assert!((a - b).abs() < 1e-6, "dense {a} vs sparse {b}");
+    }
+    Ok(())
+}
+
+#[test]
+fn sparse_matches_dense_on_small_codes() -> Result<(), AsmError> {
+    for name in ["t1_seed0", "t1_seed1", "t3_noworm", "t3_worm"] {
+        let fixture = fixtures::load_fixture(name)?;
+        assert_backends_agree(|backend| {
+            let opts = ScanOpts {
+                stabilizer_backend: Some(backend),
+                provenance: Some(fixtures::provenance_from_manifest(&fixture.manifest)),
+                ..ScanOpts::default()
+            };
+            Ok(analyze_state(&fixture.graph, &fixture.code, &opts)?.spectral)
+        })?;
+    }
+    // Degenerate spectra: multiplicities must survive the sparse solver.
+    let toric = toric_code(6);
+    assert_backends_agree(|backend| spectra(&toric, 12, Some(backend)))?;
+    let toric = toric_code(3);
+    assert_backends_agree(|backend| spectra(&toric, 64, Some(backend)))?;
+    Ok(())
+}
+
+#[test]
+fn backend_follows_constraint_count() -> Result<(), AsmError> {
+    let small = spectra(&toric_code(4), 8, None)?;
+    assert_eq!(small.stabilizer_solver.backend, StabilizerBackend::Dense);
+
+    let code = toric_code(17);
+    assert!(code.num_constraints_x() + code.num_constraints_z() > DENSE_STABILIZER_LIMIT);
+    let large = spectra(&code, 5, None)?;
+    assert_eq!(large.stabilizer_solver.backend, StabilizerBackend::Sparse);
+    assert!(large.stabilizer_solver.converged);
+    assert!(large.stabilizer_solver.max_residual <= 1e-10);
+    // Every check overlaps itself on 4 qubits, its 4 neighbouring same-type
+    // checks on 1 and 4 opposite-type checks on 2, so the rows sum to 16.
+    assert!((large.stabilizer_topk[0] - 16.0).abs() < 1e-6);
+    for value in &large.stabilizer_topk[2..5] {
+        assert!((value - large.stabilizer_topk[1]).abs() < 1e-6);
+    }
+    Ok(())
+}
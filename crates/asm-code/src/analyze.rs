@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 
-use asm_core::{AsmError, ErrorInfo, LogicalAlgebraSummary};
+use asm_core::{AsmError, ErrorCode, ErrorInfo, LogicalAlgebraSummary};
 
 use crate::css::CSSCode;
 
@@ -11,7 +11,7 @@ pub type LogicalSummary = LogicalAlgebraSummary;
 pub fn logical_summary(code: &CSSCode) -> Result<LogicalAlgebraSummary, AsmError> {
     if !code.is_css_orthogonal() {
         let info = ErrorInfo::new(
-            "non-orthogonal-code",
+            ErrorCode::NonOrthogonalCode,
             "logical summary requested for non-orthogonal CSS code",
         );
         return Err(AsmError::Code(info));
@@ -2,7 +2,7 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 
 use asm_core::{
-    AsmError, ConstraintProjector, ConstraintState, ErrorInfo, LogicalAlgebraSummary,
+    AsmError, ConstraintProjector, ConstraintState, ErrorCode, ErrorInfo, LogicalAlgebraSummary,
     RunProvenance, SchemaVersion,
 };
 
@@ -12,6 +12,9 @@ use crate::hash;
 use crate::state;
 use crate::syndrome;
 
+/// Smallest batch [`CSSCode::violations_for_states`] evaluates bit-parallel.
+pub const BATCHED_MIN_STATES: usize = 16;
+
 /// Kind of CSS constraint.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ConstraintKind {
@@ -159,7 +162,7 @@ impl CSSCode {
                 .any(|&var| var >= num_variables)
             {
                 let info = ErrorInfo::new(
-                    "variable-out-of-range",
+                    ErrorCode::VariableOutOfRange,
                     "constraint references variable outside allowed domain",
                 )
                 .with_context("constraint_kind", format!("{:?}", kind))
@@ -168,10 +171,12 @@ impl CSSCode {
                 return Err(AsmError::Code(info));
             }
             if !seen.insert(constraint.clone()) {
-                let info =
-                    ErrorInfo::new("duplicate-constraint", "duplicate CSS constraint detected")
-                        .with_context("constraint_kind", format!("{:?}", kind))
-                        .with_context("constraint_index", idx.to_string());
+                let info = ErrorInfo::new(
+                    ErrorCode::DuplicateConstraint,
+                    "duplicate CSS constraint detected",
+                )
+                .with_context("constraint_kind", format!("{:?}", kind))
+                .with_context("constraint_index", idx.to_string());
                 return Err(AsmError::Code(info));
             }
             constraints.push(constraint);
@@ -201,7 +206,7 @@ impl CSSCode {
                 }
                 if parity {
                     let info = ErrorInfo::new(
-                        "css-orthogonality-failed",
+                        ErrorCode::CssOrthogonalityFailed,
                         "X/Z constraint pair anticommutes",
                     )
                     .with_context("x_index", xi.to_string())
@@ -329,10 +334,16 @@ impl CSSCode {
     }
 
     /// Computes violation sets for a batch of states.
+    ///
+    /// Batches of at least [`BATCHED_MIN_STATES`] states use
+    /// [`CSSCode::violations_for_states_batched`]; the results are identical.
     pub fn violations_for_states(
         &self,
         states: &[&dyn ConstraintState],
     ) -> Result<Vec<ViolationSet>, AsmError> {
+        if states.len() >= BATCHED_MIN_STATES {
+            return self.violations_for_states_batched(states);
+        }
         let mut results = Vec::with_capacity(states.len());
         for state in states {
             results.push(self.violations_for_state(*state)?);
@@ -340,11 +351,30 @@ impl CSSCode {
         Ok(results)
     }
 
+    /// Computes violation sets with bit-parallel evaluation over blocks of
+    /// [`syndrome::BATCH_WIDTH`] states.
+    pub fn violations_for_states_batched(
+        &self,
+        states: &[&dyn ConstraintState],
+    ) -> Result<Vec<ViolationSet>, AsmError> {
+        let bits = state::view_bits_many(states)?;
+        syndrome::compute_violations_batched(self, &bits)
+    }
+
     /// Extracts irreducible defects from a violation set.
     pub fn find_defects(&self, violations: &ViolationSet) -> Vec<defect::Defect> {
         defect::build_defects(self, violations)
     }
 
+    /// Extracts defects and locates them using per-variable embedding coordinates.
+    pub fn find_defects_with_positions(
+        &self,
+        violations: &ViolationSet,
+        coords: &dyn Fn(usize) -> Option<[f64; 3]>,
+    ) -> Vec<defect::PositionedDefect> {
+        defect::build_positioned_defects(self, violations, coords)
+    }
+
     /// Computes a deterministic species identifier for a defect.
     pub fn species(&self, defect: &defect::Defect) -> SpeciesId {
         defect.species
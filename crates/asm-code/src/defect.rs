@@ -1,7 +1,7 @@
 use std::collections::BTreeSet;
 use std::fmt;
 
-use asm_core::{AsmError, ErrorInfo};
+use asm_core::{AsmError, ErrorCode, ErrorInfo};
 use siphasher::sip::SipHasher24;
 use std::hash::{Hash, Hasher};
 
@@ -117,6 +117,141 @@ pub fn build_defects(_code: &CSSCode, violations: &ViolationSet) -> Vec<Defect>
     defects
 }
 
+/// Defect annotated with the spatial extent of its support.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PositionedDefect {
+    /// Underlying defect.
+    pub defect: Defect,
+    /// Spatial extent, absent when a support variable has no coordinates.
+    pub position: Option<DefectPosition>,
+}
+
+/// Centroid and radius of the variables touched by a defect's checks.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DefectPosition {
+    /// Mean coordinate of the support variables.
+    pub centroid: [f64; 3],
+    /// Largest distance from the centroid to a support variable.
+    pub radius: f64,
+}
+
+/// Histogram of pairwise centroid separations between positioned defects.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DefectPairHistogram {
+    /// Upper bound of the binned range.
+    pub max_r: f64,
+    /// Width of each bin.
+    pub bin_width: f64,
+    /// Pair counts per bin over `[0, max_r)`.
+    pub counts: Vec<u64>,
+    /// Pairs separated by `max_r` or more.
+    pub overflow: u64,
+    /// Number of defects skipped because they carry no position.
+    pub unpositioned: usize,
+}
+
+/// Builds defects and locates each one from per-variable coordinates.
+///
+/// Defects touching a variable without coordinates are kept with no position
+/// so callers can count them instead of failing.
+pub fn build_positioned_defects(
+    code: &CSSCode,
+    violations: &ViolationSet,
+    coords: &dyn Fn(usize) -> Option<[f64; 3]>,
+) -> Vec<PositionedDefect> {
+    build_defects(code, violations)
+        .into_iter()
+        .map(|defect| {
+            let position = defect_position(code, &defect, coords);
+            PositionedDefect { defect, position }
+        })
+        .collect()
+}
+
+fn defect_position(
+    code: &CSSCode,
+    defect: &Defect,
+    coords: &dyn Fn(usize) -> Option<[f64; 3]>,
+) -> Option<DefectPosition> {
+    let mut support = BTreeSet::new();
+    for &idx in defect.x_checks.iter() {
+        support.extend(code.x_checks().get(idx)?.variables().iter().copied());
+    }
+    for &idx in defect.z_checks.iter() {
+        support.extend(code.z_checks().get(idx)?.variables().iter().copied());
+    }
+    if support.is_empty() {
+        return None;
+    }
+    let points = support
+        .into_iter()
+        .map(coords)
+        .collect::<Option<Vec<[f64; 3]>>>()?;
+    let mut centroid = [0.0; 3];
+    for point in &points {
+        for axis in 0..3 {
+            centroid[axis] += point[axis];
+        }
+    }
+    for value in &mut centroid {
+        *value /= points.len() as f64;
+    }
+    let radius = points
+        .iter()
+        .map(|point| distance(point, &centroid))
+        .fold(0.0, f64::max);
+    Some(DefectPosition { centroid, radius })
+}
+
+fn distance(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(lhs, rhs)| (lhs - rhs).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// Bins the centroid separation of every pair of positioned defects.
+///
+/// Pairs are visited in input order, so the histogram depends only on the
+/// defects supplied. A zero `bins` or non-positive `max_r` routes every pair to
+/// `overflow`.
+pub fn defect_pair_histogram(
+    defects: &[PositionedDefect],
+    bins: usize,
+    max_r: f64,
+) -> DefectPairHistogram {
+    let located: Vec<&DefectPosition> = defects
+        .iter()
+        .filter_map(|defect| defect.position.as_ref())
+        .collect();
+    let bin_width = if bins > 0 && max_r > 0.0 {
+        max_r / bins as f64
+    } else {
+        0.0
+    };
+    let mut counts = vec![0u64; bins];
+    let mut overflow = 0u64;
+    for (i, a) in located.iter().enumerate() {
+        for b in &located[i + 1..] {
+            let r = distance(&a.centroid, &b.centroid);
+            if bin_width > 0.0 && r < max_r {
+                let bin = ((r / bin_width) as usize).min(bins - 1);
+                counts[bin] += 1;
+            } else {
+                overflow += 1;
+            }
+        }
+    }
+    DefectPairHistogram {
+        max_r,
+        bin_width,
+        counts,
+        overflow,
+        unpositioned: defects.len() - located.len(),
+    }
+}
+
 /// Returns whether a defect is irreducible.
 pub fn is_irreducible(defect: &Defect) -> bool {
     defect.support_size <= 1
@@ -169,7 +304,7 @@ pub fn validate_violation_bounds(
     let num_z = code.num_constraints_z();
     if let Some(&idx) = violations.x().iter().find(|&&idx| idx >= num_x) {
         let info = ErrorInfo::new(
-            "x-violation-out-of-range",
+            ErrorCode::XViolationOutOfRange,
             "violation references non-existent X stabilizer",
         )
         .with_context("index", idx.to_string())
@@ -178,7 +313,7 @@ pub fn validate_violation_bounds(
     }
     if let Some(&idx) = violations.z().iter().find(|&&idx| idx >= num_z) {
         let info = ErrorInfo::new(
-            "z-violation-out-of-range",
+            ErrorCode::ZViolationOutOfRange,
             "violation references non-existent Z stabilizer",
         )
         .with_context("index", idx.to_string())
@@ -1,4 +1,6 @@
-use asm_core::{AsmError, ErrorInfo, Hypergraph};
+use std::collections::BTreeMap;
+
+use asm_core::{AsmError, ErrorCode, ErrorInfo, Hypergraph};
 
 use crate::css::CSSCode;
 use crate::defect::{self, SpeciesId};
@@ -10,6 +12,12 @@ pub struct DispersionOptions {
     pub steps: Vec<u32>,
     /// Acceptable tolerance for the common velocity fit.
     pub tolerance: f64,
+    /// Residual tolerances overriding `tolerance` for individual species.
+    #[serde(default)]
+    pub per_species_tolerance: BTreeMap<SpeciesId, f64>,
+    /// Explicit common-velocity fit weights overriding the inverse tolerance.
+    #[serde(default)]
+    pub per_species_weight: BTreeMap<SpeciesId, f64>,
 }
 
 impl Default for DispersionOptions {
@@ -17,8 +25,74 @@ impl Default for DispersionOptions {
         Self {
             steps: vec![1, 2, 4],
             tolerance: 1e-6,
+            per_species_tolerance: BTreeMap::new(),
+            per_species_weight: BTreeMap::new(),
+        }
+    }
+}
+
+impl DispersionOptions {
+    /// Residual tolerance for `species`, falling back to `tolerance`.
+    pub fn tolerance_for(&self, species: SpeciesId) -> f64 {
+        self.per_species_tolerance
+            .get(&species)
+            .copied()
+            .unwrap_or(self.tolerance)
+    }
+
+    /// Unnormalised fit weight for `species`: the explicit weight if given,
+    /// else the inverse of its tolerance. A non-positive tolerance gives
+    /// weight one.
+    pub fn weight_for(&self, species: SpeciesId) -> f64 {
+        if let Some(&weight) = self.per_species_weight.get(&species) {
+            return weight;
+        }
+        let tolerance = self.tolerance_for(species);
+        if tolerance > 0.0 {
+            1.0 / tolerance
+        } else {
+            1.0
         }
     }
+
+    fn validate(&self) -> Result<(), AsmError> {
+        if self.steps.is_empty() {
+            let info = ErrorInfo::new(
+                ErrorCode::EmptyDispersionSteps,
+                "dispersion probe requires at least one step",
+            );
+            return Err(AsmError::Code(info));
+        }
+        for (species, &tolerance) in &self.per_species_tolerance {
+            if !(tolerance.is_finite() && tolerance > 0.0) {
+                return Err(invalid_override(
+                    *species,
+                    "tolerance",
+                    tolerance,
+                    "per-species tolerance must be positive and finite",
+                ));
+            }
+        }
+        for (species, &weight) in &self.per_species_weight {
+            if !(weight.is_finite() && weight >= 0.0) {
+                return Err(invalid_override(
+                    *species,
+                    "weight",
+                    weight,
+                    "per-species weight must be non-negative and finite",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn invalid_override(species: SpeciesId, key: &str, value: f64, message: &str) -> AsmError {
+    AsmError::Code(
+        ErrorInfo::new(ErrorCode::InvalidDispersionOverride, message)
+            .with_context("species", species.to_string())
+            .with_context(key, value.to_string()),
+    )
 }
 
 /// Per-species dispersion curve.
@@ -39,6 +113,37 @@ pub struct DispersionResidual {
     pub residual: f64,
 }
 
+/// Residual of one species checked against its own tolerance.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ResidualCheck {
+    /// Species identifier.
+    pub species: SpeciesId,
+    /// Signed residual relative to the common velocity.
+    pub residual: f64,
+    /// Tolerance the absolute residual was compared with.
+    pub tolerance: f64,
+    /// Whether the absolute residual lies within the tolerance.
+    pub pass: bool,
+}
+
+/// Weight a species contributed to the common velocity fit.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SpeciesWeight {
+    /// Species identifier.
+    pub species: SpeciesId,
+    /// Weight relative to the largest weight in the fit.
+    pub weight: f64,
+}
+
+/// Non-fatal issue noticed while estimating dispersion.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DispersionWarning {
+    /// Species the warning refers to.
+    pub species: SpeciesId,
+    /// Human readable description.
+    pub message: String,
+}
+
 /// Diagnostic summary for the dispersion estimation routine.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct DispersionDiagnostics {
@@ -48,6 +153,12 @@ pub struct DispersionDiagnostics {
     pub confidence: f64,
     /// Sum-of-squares residual error.
     pub fit_error: f64,
+    /// Normalised weights used for the common velocity, in species order.
+    #[serde(default)]
+    pub weights: Vec<SpeciesWeight>,
+    /// Overrides naming species that were not measured.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<DispersionWarning>,
 }
 
 /// Structured dispersion report emitted by [`estimate_dispersion`].
@@ -63,20 +174,38 @@ pub struct DispersionReport {
     pub diagnostics: DispersionDiagnostics,
 }
 
+impl DispersionReport {
+    /// Checks every residual against the tolerance `opts` assigns to its
+    /// species.
+    pub fn check_residuals(&self, opts: &DispersionOptions) -> Vec<ResidualCheck> {
+        self.residuals
+            .iter()
+            .map(|res| {
+                let tolerance = opts.tolerance_for(res.species);
+                ResidualCheck {
+                    species: res.species,
+                    residual: res.residual,
+                    tolerance,
+                    pass: res.residual.abs() <= tolerance,
+                }
+            })
+            .collect()
+    }
+}
+
 /// Estimates dispersion curves and a common limiting velocity.
+///
+/// The common velocity is the mean of the terminal velocities weighted by
+/// [`DispersionOptions::weight_for`], so species with looser tolerances pull
+/// on it less. Weights are divided by the largest one before fitting; equal
+/// weights therefore reproduce the plain mean exactly.
 pub fn estimate_dispersion(
     code: &CSSCode,
     graph: &dyn Hypergraph,
     species: &[SpeciesId],
     opts: &DispersionOptions,
 ) -> Result<DispersionReport, AsmError> {
-    if opts.steps.is_empty() {
-        let info = ErrorInfo::new(
-            "empty-dispersion-steps",
-            "dispersion probe requires at least one step",
-        );
-        return Err(AsmError::Code(info));
-    }
+    opts.validate()?;
 
     let mut steps = opts.steps.clone();
     steps.sort_unstable();
@@ -105,10 +234,17 @@ pub fn estimate_dispersion(
         per_species.push(SpeciesDispersion { species: sp, curve });
     }
 
+    let weights = fit_weights(&terminal_velocities, opts)?;
+    let total_weight = weights.iter().map(|w| w.weight).sum::<f64>();
     let common_c = if terminal_velocities.is_empty() {
         0.0
     } else {
-        terminal_velocities.iter().map(|(_, v)| *v).sum::<f64>() / terminal_velocities.len() as f64
+        terminal_velocities
+            .iter()
+            .zip(&weights)
+            .map(|((_, v), w)| w.weight * *v)
+            .sum::<f64>()
+            / total_weight
     };
 
     let residuals: Vec<DispersionResidual> = terminal_velocities
@@ -127,6 +263,8 @@ pub fn estimate_dispersion(
         samples: steps.len() * species.len(),
         confidence: 1.0 / (1.0 + opts.tolerance.abs()),
         fit_error,
+        weights,
+        warnings: override_warnings(species, opts),
     };
 
     Ok(DispersionReport {
@@ -136,3 +274,49 @@ pub fn estimate_dispersion(
         diagnostics,
     })
 }
+
+/// Fit weights for the measured species, scaled so the largest is one.
+fn fit_weights(
+    terminal_velocities: &[(SpeciesId, f64)],
+    opts: &DispersionOptions,
+) -> Result<Vec<SpeciesWeight>, AsmError> {
+    let raw: Vec<f64> = terminal_velocities
+        .iter()
+        .map(|(sp, _)| opts.weight_for(*sp))
+        .collect();
+    let max = raw.iter().copied().fold(0.0f64, f64::max);
+    if !terminal_velocities.is_empty() && max <= 0.0 {
+        let info = ErrorInfo::new(
+            ErrorCode::InvalidDispersionOverride,
+            "common velocity fit requires at least one positive weight",
+        );
+        return Err(AsmError::Code(info));
+    }
+    Ok(terminal_velocities
+        .iter()
+        .zip(raw)
+        .map(|((sp, _), weight)| SpeciesWeight {
+            species: *sp,
+            weight: weight / max,
+        })
+        .collect())
+}
+
+/// Warns about overrides for species that were not measured.
+fn override_warnings(species: &[SpeciesId], opts: &DispersionOptions) -> Vec<DispersionWarning> {
+    let mut named: Vec<(SpeciesId, &str)> = opts
+        .per_species_tolerance
+        .keys()
+        .map(|sp| (*sp, "tolerance"))
+        .chain(opts.per_species_weight.keys().map(|sp| (*sp, "weight")))
+        .filter(|(sp, _)| !species.contains(sp))
+        .collect();
+    named.sort();
+    named
+        .into_iter()
+        .map(|(sp, kind)| DispersionWarning {
+            species: sp,
+            message: format!("{kind} override for {sp} ignored: species not in the report"),
+        })
+        .collect()
+}
@@ -20,10 +20,17 @@ pub mod syndrome;
 
 pub use analyze::LogicalSummary;
 pub use css::{CSSCode, Constraint, ConstraintKind};
-pub use defect::{Defect, DefectKind, SpeciesId, ViolationSet};
+pub use defect::{
+    defect_pair_histogram, Defect, DefectKind, DefectPairHistogram, DefectPosition,
+    PositionedDefect, SpeciesId, ViolationSet,
+};
 pub use dispersion::{
-    DispersionDiagnostics, DispersionOptions, DispersionReport, SpeciesDispersion,
+    DispersionDiagnostics, DispersionOptions, DispersionReport, DispersionWarning, ResidualCheck,
+    SpeciesDispersion, SpeciesWeight,
 };
 pub use hash::canonical_code_hash;
-pub use serde::{from_bytes, from_json, to_bytes, to_json};
+pub use serde::{
+    css_from_alist, css_from_mtx, css_to_alist, css_to_mtx, from_bytes, from_json, to_bytes,
+    to_json,
+};
 pub use state::StateHandle;
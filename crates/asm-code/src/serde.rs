@@ -1,7 +1,11 @@
-use asm_core::{AsmError, ErrorInfo, RunProvenance, SchemaVersion};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Lines, Write};
+use std::path::Path;
+
+use asm_core::{AsmError, ErrorCode, ErrorInfo, RunProvenance, SchemaVersion};
 use serde::{Deserialize, Serialize};
 
-use crate::css::{CSSCode, Constraint};
+use crate::css::{into_parts, CSSCode, Constraint};
 use crate::hash;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -45,13 +49,14 @@ pub fn to_json(code: &CSSCode) -> Result<String, AsmError> {
         rank_z,
     };
     serde_json::to_string_pretty(&payload)
-        .map_err(|err| AsmError::Serde(ErrorInfo::new("json-serialize", err.to_string())))
+        .map_err(|err| AsmError::Serde(ErrorInfo::new(ErrorCode::JsonSerialize, err.to_string())))
 }
 
 /// Restores a CSS code from a JSON string.
 pub fn from_json(data: &str) -> Result<CSSCode, AsmError> {
-    let payload: SerializableCSSCode = serde_json::from_str(data)
-        .map_err(|err| AsmError::Serde(ErrorInfo::new("json-deserialize", err.to_string())))?;
+    let payload: SerializableCSSCode = serde_json::from_str(data).map_err(|err| {
+        AsmError::Serde(ErrorInfo::new(ErrorCode::JsonDeserialize, err.to_string()))
+    })?;
     let x_checks = deserialize_constraints(&payload.x_checks);
     let z_checks = deserialize_constraints(&payload.z_checks);
     Ok(hash::reconstruct(
@@ -68,13 +73,419 @@ pub fn from_json(data: &str) -> Result<CSSCode, AsmError> {
 /// Serializes a CSS code into a binary blob.
 pub fn to_bytes(code: &CSSCode) -> Result<Vec<u8>, AsmError> {
     let json = to_json(code)?;
-    bincode::serialize(&json)
-        .map_err(|err| AsmError::Serde(ErrorInfo::new("bincode-serialize", err.to_string())))
+    bincode::serialize(&json).map_err(|err| {
+        AsmError::Serde(ErrorInfo::new(ErrorCode::BincodeSerialize, err.to_string()))
+    })
 }
 
 /// Rehydrates a CSS code from a binary blob.
 pub fn from_bytes(bytes: &[u8]) -> Result<CSSCode, AsmError> {
-    let json: String = bincode::deserialize(bytes)
-        .map_err(|err| AsmError::Serde(ErrorInfo::new("bincode-deserialize", err.to_string())))?;
+    let json: String = bincode::deserialize(bytes).map_err(|err| {
+        AsmError::Serde(ErrorInfo::new(
+            ErrorCode::BincodeDeserialize,
+            err.to_string(),
+        ))
+    })?;
     from_json(&json)
 }
+
+/// Sparse GF(2) parity-check matrix read from an external file.
+struct ParityMatrix {
+    cols: usize,
+    checks: Vec<Vec<usize>>,
+}
+
+fn io_error(path: &Path, err: impl ToString) -> AsmError {
+    AsmError::Serde(
+        ErrorInfo::new(ErrorCode::ParityCheckIo, err.to_string())
+            .with_context("path", path.display().to_string()),
+    )
+}
+
+fn parse_error(path: &Path, line: usize, message: impl Into<String>) -> AsmError {
+    AsmError::Serde(
+        ErrorInfo::new(ErrorCode::ParityCheckParse, message)
+            .with_context("path", path.display().to_string())
+            .with_context("line", line.to_string()),
+    )
+}
+
+/// Streams non-blank lines of a text file together with their 1-based line numbers.
+struct LineReader<'a> {
+    path: &'a Path,
+    lines: Lines<BufReader<File>>,
+    line: usize,
+}
+
+impl<'a> LineReader<'a> {
+    fn open(path: &'a Path) -> Result<Self, AsmError> {
+        let file = File::open(path).map_err(|err| io_error(path, err))?;
+        Ok(Self {
+            path,
+            lines: BufReader::new(file).lines(),
+            line: 0,
+        })
+    }
+
+    /// Returns the next line that is neither blank nor a comment starting with `comment`.
+    fn next_content(&mut self, comment: Option<char>) -> Result<Option<String>, AsmError> {
+        for text in self.lines.by_ref() {
+            self.line += 1;
+            let text = text.map_err(|err| io_error(self.path, err))?;
+            let trimmed = text.trim();
+            if trimmed.is_empty() || comment.is_some_and(|c| trimmed.starts_with(c)) {
+                continue;
+            }
+            return Ok(Some(trimmed.to_string()));
+        }
+        Ok(None)
+    }
+
+    fn expect_numbers(&mut self, what: &str) -> Result<Vec<usize>, AsmError> {
+        let text = self
+            .next_content(None)?
+            .ok_or_else(|| parse_error(self.path, self.line + 1, format!("missing {what}")))?;
+        text.split_whitespace()
+            .map(|token| {
+                token.parse::<usize>().map_err(|_| {
+                    parse_error(
+                        self.path,
+                        self.line,
+                        format!("expected non-negative integer in {what}, found `{token}`"),
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+fn read_alist(path: &Path) -> Result<ParityMatrix, AsmError> {
+    let mut reader = LineReader::open(path)?;
+    let header = reader.expect_numbers("dimension header")?;
+    let [cols, rows] = header[..] else {
+        return Err(parse_error(
+            path,
+            reader.line,
+            "expected `<columns> <rows>`",
+        ));
+    };
+    reader.expect_numbers("maximum weights")?;
+    let col_weights = reader.expect_numbers("column weights")?;
+    if col_weights.len() != cols {
+        return Err(parse_error(
+            path,
+            reader.line,
+            format!(
+                "expected {cols} column weights, found {}",
+                col_weights.len()
+            ),
+        ));
+    }
+    let row_weights = reader.expect_numbers("row weights")?;
+    if row_weights.len() != rows {
+        return Err(parse_error(
+            path,
+            reader.line,
+            format!("expected {rows} row weights, found {}", row_weights.len()),
+        ));
+    }
+
+    // Column lists are redundant with the row lists; only their weights are
+    // checked here and cross-checked against the row lists below.
+    for (col, &weight) in col_weights.iter().enumerate() {
+        let entries = reader.expect_numbers("column list")?;
+        let nonzero = entries.iter().filter(|&&row| row != 0).count();
+        if let Some(&row) = entries.iter().find(|&&row| row > rows) {
+            return Err(AsmError::Serde(
+                ErrorInfo::new(ErrorCode::ParityCheckParse, "row index out of range")
+                    .with_context("path", path.display().to_string())
+                    .with_context("line", reader.line.to_string())
+                    .with_context("column", (col + 1).to_string())
+                    .with_context("row", row.to_string()),
+            ));
+        }
+        if nonzero != weight {
+            return Err(parse_error(
+                path,
+                reader.line,
+                format!(
+                    "column {} lists {nonzero} entries but declares weight {weight}",
+                    col + 1
+                ),
+            ));
+        }
+    }
+
+    let mut counted = vec![0usize; cols];
+    let mut checks = Vec::with_capacity(rows);
+    for (row, &weight) in row_weights.iter().enumerate() {
+        let entries = reader.expect_numbers("row list")?;
+        let mut check = Vec::with_capacity(weight);
+        for col in entries.into_iter().filter(|&col| col != 0) {
+            if col > cols {
+                return Err(AsmError::Serde(
+                    ErrorInfo::new(ErrorCode::ParityCheckParse, "column index out of range")
+                        .with_context("path", path.display().to_string())
+                        .with_context("line", reader.line.to_string())
+                        .with_context("row", (row + 1).to_string())
+                        .with_context("column", col.to_string()),
+                ));
+            }
+            counted[col - 1] += 1;
+            check.push(col - 1);
+        }
+        if check.len() != weight {
+            return Err(parse_error(
+                path,
+                reader.line,
+                format!(
+                    "row {} lists {} entries but declares weight {weight}",
+                    row + 1,
+                    check.len()
+                ),
+            ));
+        }
+        checks.push(check);
+    }
+    if let Some(col) = (0..cols).find(|&col| counted[col] != col_weights[col]) {
+        return Err(parse_error(
+            path,
+            reader.line,
+            format!(
+                "column {} appears in {} rows but declares weight {}",
+                col + 1,
+                counted[col],
+                col_weights[col]
+            ),
+        ));
+    }
+    Ok(ParityMatrix { cols, checks })
+}
+
+fn read_mtx(path: &Path) -> Result<ParityMatrix, AsmError> {
+    let mut reader = LineReader::open(path)?;
+    let banner = reader
+        .next_content(None)?
+        .ok_or_else(|| parse_error(path, 1, "missing MatrixMarket banner"))?;
+    let banner: Vec<String> = banner
+        .split_whitespace()
+        .map(str::to_ascii_lowercase)
+        .collect();
+    let pattern = match banner.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["%%matrixmarket", "matrix", "coordinate", field, "general"] => match field {
+            "pattern" => true,
+            "integer" | "real" => false,
+            other => {
+                return Err(parse_error(
+                    path,
+                    reader.line,
+                    format!("unsupported MatrixMarket field `{other}`"),
+                ))
+            }
+        },
+        _ => {
+            return Err(parse_error(
+                path,
+                reader.line,
+                "expected `%%MatrixMarket matrix coordinate <field> general`",
+            ))
+        }
+    };
+
+    let size = reader
+        .next_content(Some('%'))?
+        .ok_or_else(|| parse_error(path, reader.line + 1, "missing size line"))?;
+    let size: Vec<usize> = size
+        .split_whitespace()
+        .map(|token| token.parse::<usize>())
+        .collect::<Result<_, _>>()
+        .map_err(|_| parse_error(path, reader.line, "malformed size line"))?;
+    let [rows, cols, nnz] = size[..] else {
+        return Err(parse_error(
+            path,
+            reader.line,
+            "expected `<rows> <columns> <entries>`",
+        ));
+    };
+
+    let mut checks = vec![Vec::new(); rows];
+    for _ in 0..nnz {
+        let text = reader
+            .next_content(Some('%'))?
+            .ok_or_else(|| parse_error(path, reader.line + 1, format!("expected {nnz} entries")))?;
+        let mut tokens = text.split_whitespace();
+        let mut index = |what: &str, bound: usize| -> Result<usize, AsmError> {
+            let value = tokens
+                .next()
+                .and_then(|token| token.parse::<usize>().ok())
+                .ok_or_else(|| parse_error(path, reader.line, format!("malformed {what} index")))?;
+            if value == 0 || value > bound {
+                return Err(AsmError::Serde(
+                    ErrorInfo::new(
+                        ErrorCode::ParityCheckParse,
+                        format!("{what} index out of range"),
+                    )
+                    .with_context("path", path.display().to_string())
+                    .with_context("line", reader.line.to_string())
+                    .with_context(what, value.to_string()),
+                ));
+            }
+            Ok(value - 1)
+        };
+        let row = index("row", rows)?;
+        let col = index("column", cols)?;
+        let odd = if pattern {
+            true
+        } else {
+            let value = tokens
+                .next()
+                .and_then(|token| token.parse::<f64>().ok())
+                .filter(|value| value.is_finite() && value.fract() == 0.0)
+                .ok_or_else(|| parse_error(path, reader.line, "entry value must be an integer"))?;
+            value.rem_euclid(2.0) == 1.0
+        };
+        if odd {
+            checks[row].push(col);
+        }
+    }
+    Ok(ParityMatrix { cols, checks })
+}
+
+fn css_from_matrices(
+    x: ParityMatrix,
+    z: ParityMatrix,
+    x_path: &Path,
+    z_path: &Path,
+    schema: SchemaVersion,
+    provenance: RunProvenance,
+) -> Result<CSSCode, AsmError> {
+    if x.cols != z.cols {
+        return Err(AsmError::Serde(
+            ErrorInfo::new(
+                ErrorCode::ParityCheckDimension,
+                "X and Z parity-check matrices have different column counts",
+            )
+            .with_context("x_path", x_path.display().to_string())
+            .with_context("x_columns", x.cols.to_string())
+            .with_context("z_path", z_path.display().to_string())
+            .with_context("z_columns", z.cols.to_string()),
+        ));
+    }
+    CSSCode::new(x.cols, x.checks, z.checks, schema, provenance).map_err(|err| match err {
+        AsmError::Code(info) => AsmError::Code(
+            info.with_context("x_path", x_path.display().to_string())
+                .with_context("z_path", z_path.display().to_string()),
+        ),
+        other => other,
+    })
+}
+
+/// Builds a CSS code from X and Z parity-check matrices in MacKay alist format.
+///
+/// Files are streamed line by line; zero padding in the index lists is accepted
+/// but not required.
+pub fn css_from_alist(
+    x_path: &Path,
+    z_path: &Path,
+    schema: SchemaVersion,
+    provenance: RunProvenance,
+) -> Result<CSSCode, AsmError> {
+    let x = read_alist(x_path)?;
+    let z = read_alist(z_path)?;
+    css_from_matrices(x, z, x_path, z_path, schema, provenance)
+}
+
+/// Builds a CSS code from X and Z parity-check matrices in MatrixMarket
+/// coordinate format. Integer and real entries are reduced mod 2.
+pub fn css_from_mtx(
+    x_path: &Path,
+    z_path: &Path,
+    schema: SchemaVersion,
+    provenance: RunProvenance,
+) -> Result<CSSCode, AsmError> {
+    let x = read_mtx(x_path)?;
+    let z = read_mtx(z_path)?;
+    css_from_matrices(x, z, x_path, z_path, schema, provenance)
+}
+
+fn write_lines(
+    path: &Path,
+    write: impl FnOnce(&mut dyn Write) -> std::io::Result<()>,
+) -> Result<(), AsmError> {
+    let file = File::create(path).map_err(|err| io_error(path, err))?;
+    let mut writer = BufWriter::new(file);
+    write(&mut writer)
+        .and_then(|()| writer.flush())
+        .map_err(|err| io_error(path, err))
+}
+
+fn join(values: impl IntoIterator<Item = usize>) -> String {
+    values
+        .into_iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn write_alist(path: &Path, num_variables: usize, checks: &[Constraint]) -> Result<(), AsmError> {
+    let mut columns = vec![Vec::new(); num_variables];
+    for (row, check) in checks.iter().enumerate() {
+        for &var in check.variables() {
+            columns[var].push(row + 1);
+        }
+    }
+    let max_col = columns.iter().map(Vec::len).max().unwrap_or(0);
+    let max_row = checks
+        .iter()
+        .map(|c| c.variables().len())
+        .max()
+        .unwrap_or(0);
+    write_lines(path, |out| {
+        writeln!(out, "{} {}", num_variables, checks.len())?;
+        writeln!(out, "{max_col} {max_row}")?;
+        writeln!(out, "{}", join(columns.iter().map(Vec::len)))?;
+        writeln!(out, "{}", join(checks.iter().map(|c| c.variables().len())))?;
+        for column in &columns {
+            let padded = column.iter().copied().chain(std::iter::repeat(0));
+            writeln!(out, "{}", join(padded.take(max_col)))?;
+        }
+        for check in checks {
+            let padded = check
+                .variables()
+                .iter()
+                .map(|&var| var + 1)
+                .chain(std::iter::repeat(0));
+            writeln!(out, "{}", join(padded.take(max_row)))?;
+        }
+        Ok(())
+    })
+}
+
+fn write_mtx(path: &Path, num_variables: usize, checks: &[Constraint]) -> Result<(), AsmError> {
+    let nnz: usize = checks.iter().map(|c| c.variables().len()).sum();
+    write_lines(path, |out| {
+        writeln!(out, "%%MatrixMarket matrix coordinate pattern general")?;
+        writeln!(out, "{} {} {}", checks.len(), num_variables, nnz)?;
+        for (row, check) in checks.iter().enumerate() {
+            for &var in check.variables() {
+                writeln!(out, "{} {}", row + 1, var + 1)?;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Writes the X and Z parity-check matrices of `code` in MacKay alist format.
+pub fn css_to_alist(code: &CSSCode, x_path: &Path, z_path: &Path) -> Result<(), AsmError> {
+    let (num_variables, x_checks, z_checks, ..) = into_parts(code);
+    write_alist(x_path, num_variables, &x_checks)?;
+    write_alist(z_path, num_variables, &z_checks)
+}
+
+/// Writes the X and Z parity-check matrices of `code` in MatrixMarket
+/// coordinate pattern format.
+pub fn css_to_mtx(code: &CSSCode, x_path: &Path, z_path: &Path) -> Result<(), AsmError> {
+    let (num_variables, x_checks, z_checks, ..) = into_parts(code);
+    write_mtx(x_path, num_variables, &x_checks)?;
+    write_mtx(z_path, num_variables, &z_checks)
+}
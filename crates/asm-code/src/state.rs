@@ -1,7 +1,7 @@
 use std::fmt;
 use std::sync::Arc;
 
-use asm_core::{AsmError, ConstraintState, ErrorInfo};
+use asm_core::{AsmError, ConstraintState, ErrorCode, ErrorInfo};
 
 const STATE_TAG: u64 = 0x53544154455f4249; // "STATE_BI"
 
@@ -17,7 +17,7 @@ impl StateHandle {
     pub fn from_bits(bits: impl Into<Vec<u8>>) -> Result<Self, AsmError> {
         let bits_vec: Vec<u8> = bits.into();
         if bits_vec.iter().any(|&b| b > 1) {
-            let info = ErrorInfo::new("invalid-state-bit", "state bits must be 0 or 1");
+            let info = ErrorInfo::new(ErrorCode::InvalidStateBit, "state bits must be 0 or 1");
             return Err(AsmError::Code(info));
         }
         Ok(Self {
@@ -54,12 +54,15 @@ pub fn view_bits(state: &dyn ConstraintState) -> Result<&[u8], AsmError> {
     let data = state as *const dyn ConstraintState as *const StateHandle;
     unsafe {
         if data.is_null() {
-            let info = ErrorInfo::new("null-state-handle", "constraint state pointer was null");
+            let info = ErrorInfo::new(
+                ErrorCode::NullStateHandle,
+                "constraint state pointer was null",
+            );
             return Err(AsmError::Code(info));
         }
         if (*data).tag != STATE_TAG {
             let info = ErrorInfo::new(
-                "unknown-state-handle",
+                ErrorCode::UnknownStateHandle,
                 "constraint state is not managed by asm-code",
             );
             return Err(AsmError::Code(info));
@@ -67,3 +70,9 @@ pub fn view_bits(state: &dyn ConstraintState) -> Result<&[u8], AsmError> {
         Ok((*data).bits())
     }
 }
+
+/// Extracts the raw bit slices of many states, failing on the first state
+/// [`view_bits`] rejects.
+pub fn view_bits_many<'a>(states: &[&'a dyn ConstraintState]) -> Result<Vec<&'a [u8]>, AsmError> {
+    states.iter().map(|state| view_bits(*state)).collect()
+}
@@ -1,19 +1,28 @@
-use asm_core::{AsmError, ErrorInfo};
+use asm_core::{AsmError, ErrorCode, ErrorInfo};
 
-use crate::css::CSSCode;
+use crate::css::{CSSCode, Constraint};
 use crate::defect::ViolationSet;
 
-/// Computes the violated constraints for a given binary state.
-pub fn compute_violations(code: &CSSCode, bits: &[u8]) -> Result<ViolationSet, AsmError> {
+/// Number of states packed into one word per variable by
+/// [`compute_violations_batched`].
+pub const BATCH_WIDTH: usize = 64;
+
+fn check_len(code: &CSSCode, bits: &[u8]) -> Result<(), AsmError> {
     if bits.len() != code.num_variables() {
         let info = ErrorInfo::new(
-            "state-length-mismatch",
+            ErrorCode::StateLengthMismatch,
             "state size does not match number of variables",
         )
         .with_context("num_variables", code.num_variables().to_string())
         .with_context("state_len", bits.len().to_string());
         return Err(AsmError::Code(info));
     }
+    Ok(())
+}
+
+/// Computes the violated constraints for a given binary state.
+pub fn compute_violations(code: &CSSCode, bits: &[u8]) -> Result<ViolationSet, AsmError> {
+    check_len(code, bits)?;
 
     let mut violated_x = Vec::new();
     for (idx, constraint) in code.x_checks().iter().enumerate() {
@@ -42,3 +51,129 @@ fn parity(bits: &[u8], vars: &[usize]) -> bool {
     }
     value == 1
 }
+
+/// Computes the violated constraints for many states at once.
+///
+/// States are packed [`BATCH_WIDTH`] at a time into one `u64` per variable,
+/// bit `s` holding state `s` of the block, so each constraint's parity for the
+/// whole block is the XOR of the words over its support. Results match
+/// [`compute_violations`] state by state; every state is length-checked
+/// before any is evaluated.
+pub fn compute_violations_batched(
+    code: &CSSCode,
+    states: &[&[u8]],
+) -> Result<Vec<ViolationSet>, AsmError> {
+    for bits in states {
+        check_len(code, bits)?;
+    }
+    let mut results = Vec::with_capacity(states.len());
+    let mut words = vec![0u64; code.num_variables()];
+    let mut odd = Vec::new();
+    for block in states.chunks(BATCH_WIDTH) {
+        pack_block(block, &mut words);
+        let violated_x = violated_lists(code.x_checks(), &words, &mut odd, block.len());
+        let violated_z = violated_lists(code.z_checks(), &words, &mut odd, block.len());
+        results.extend(
+            violated_x
+                .into_iter()
+                .zip(violated_z)
+                .map(|(x, z)| ViolationSet::new(x.into_boxed_slice(), z.into_boxed_slice())),
+        );
+    }
+    Ok(results)
+}
+
+/// Per-lane lists of the constraints with odd parity over the packed words.
+///
+/// The parity words are transposed back in 64-constraint tiles, so each
+/// lane's list is sized by popcount and allocated once.
+fn violated_lists(
+    checks: &[Constraint],
+    words: &[u64],
+    odd: &mut Vec<u64>,
+    lanes: usize,
+) -> Vec<Vec<usize>> {
+    odd.clear();
+    odd.extend(checks.iter().map(|constraint| {
+        constraint
+            .variables()
+            .iter()
+            .fold(0u64, |acc, &var| acc ^ words[var])
+    }));
+    let tiles = odd.len().div_ceil(64);
+    odd.resize(tiles * 64, 0);
+    for tile in odd.chunks_exact_mut(64) {
+        transpose64(tile.try_into().expect("64-word tile"));
+    }
+    (0..lanes)
+        .map(|lane| {
+            let lane_words = || (0..tiles).map(|tile| (tile * 64, odd[tile * 64 + lane]));
+            let count = lane_words()
+                .map(|(_, word)| word.count_ones() as usize)
+                .sum();
+            let mut list = Vec::with_capacity(count);
+            for (base, word) in lane_words() {
+                for_each_bit(word, |bit| list.push(base + bit));
+            }
+            list
+        })
+        .collect()
+}
+
+fn for_each_bit(mut word: u64, mut visit: impl FnMut(usize)) {
+    while word != 0 {
+        visit(word.trailing_zeros() as usize);
+        word &= word - 1;
+    }
+}
+
+/// Packs up to [`BATCH_WIDTH`] equally long states into column-major words:
+/// bit `lane` of `words[var]` is bit `var` of `block[lane]`.
+///
+/// Each state's bytes are gathered into one row word per 64 variables, and
+/// every 64x64 tile is then transposed in place; trailing variables are
+/// packed bit by bit.
+fn pack_block(block: &[&[u8]], words: &mut [u64]) {
+    let full = words.len() / 64 * 64;
+    words.fill(0);
+    for (lane, bits) in block.iter().enumerate() {
+        for (tile, vars) in bits[..full].chunks_exact(64).enumerate() {
+            let mut row = 0u64;
+            for (offset, chunk) in vars.chunks_exact(8).enumerate() {
+                let chunk = u64::from_le_bytes(chunk.try_into().expect("8-byte chunk"));
+                row |= gather_bytes(chunk) << (8 * offset);
+            }
+            words[tile * 64 + lane] = row;
+        }
+        for (word, &bit) in words[full..].iter_mut().zip(&bits[full..]) {
+            *word |= u64::from(bit & 1) << lane;
+        }
+    }
+    for tile in words[..full].chunks_exact_mut(64) {
+        transpose64(tile.try_into().expect("64-word tile"));
+    }
+}
+
+/// Moves the low bit of each of the eight bytes of `chunk` into bits 0..8.
+fn gather_bytes(chunk: u64) -> u64 {
+    // Byte i lands on bit 56 + i; the shifted copies never overlap or carry.
+    ((chunk & 0x0101_0101_0101_0101).wrapping_mul(0x0102_0408_1020_4080)) >> 56
+}
+
+/// Transposes a 64x64 bit matrix in place: bit `c` of row `r` moves to bit
+/// `r` of row `c`.
+fn transpose64(rows: &mut [u64; 64]) {
+    let mut width = 32;
+    let mut mask: u64 = 0x0000_0000_ffff_ffff;
+    while width != 0 {
+        let mut row = 0;
+        while row < 64 {
+            let swap = ((rows[row] >> width) ^ rows[row + width]) & mask;
+            rows[row] ^= swap << width;
+            rows[row + width] ^= swap;
+            row = (row + width + 1) & !width;
+        }
+        width >>= 1;
+        mask ^= mask << width;
+    }
+}
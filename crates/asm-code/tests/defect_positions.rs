@@ -0,0 +1,92 @@
+use asm_code::{defect_pair_histogram, CSSCode, ViolationSet};
+use asm_core::{RunProvenance, SchemaVersion};
+
+const SIDE: usize = 8;
+
+fn provenance() -> RunProvenance {
+    RunProvenance {
+        input_hash: "input".into(),
+        graph_hash: "graph".into(),
+        code_hash: String::new(),
+        seed: 17,
+        created_at: "2024-01-01T00:00:00Z".into(),
+        tool_versions: Default::default(),
+    }
+}
+
+/// Square lattice with one X check per horizontal bond, listed row-major so
+/// the check at `(x, y)` keeps index `y * (SIDE - 1) + x` after sorting.
+fn lattice_code() -> CSSCode {
+    let mut x_checks = Vec::new();
+    for y in 0..SIDE {
+        for x in 0..SIDE - 1 {
+            let var = y * SIDE + x;
+            x_checks.push(vec![var, var + 1]);
+        }
+    }
+    CSSCode::new(
+        SIDE * SIDE,
+        x_checks,
+        Vec::new(),
+        SchemaVersion::new(1, 0, 0),
+        provenance(),
+    )
+    .unwrap()
+}
+
+fn bond(x: usize, y: usize) -> usize {
+    y * (SIDE - 1) + x
+}
+
+fn lattice_coords(var: usize) -> Option<[f64; 3]> {
+    Some([(var % SIDE) as f64, (var / SIDE) as f64, 0.0])
+}
+
+#[test]
+fn planted_defects_recover_their_separation() {
+    let code = lattice_code();
+    let violations = ViolationSet::new(vec![bond(1, 1), bond(4, 5)].into(), Vec::new().into());
+    let defects = code.find_defects_with_positions(&violations, &lattice_coords);
+    assert_eq!(defects.len(), 2);
+
+    let mut centroids: Vec<[f64; 3]> = defects
+        .iter()
+        .map(|defect| {
+            let position = defect.position.expect("positioned");
+            assert!((position.radius - 0.5).abs() < 1e-12);
+            position.centroid
+        })
+        .collect();
+    centroids.sort_by(|a, b| a[1].total_cmp(&b[1]));
+    assert_eq!(centroids, vec![[1.5, 1.0, 0.0], [4.5, 5.0, 0.0]]);
+
+    // Separation is sqrt(3^2 + 4^2) = 5, which lands in bin [5, 6).
+    let histogram = defect_pair_histogram(&defects, 8, 8.0);
+    assert_eq!(histogram.bin_width, 1.0);
+    assert_eq!(histogram.counts, vec![0, 0, 0, 0, 0, 1, 0, 0]);
+    assert_eq!(histogram.overflow, 0);
+    assert_eq!(histogram.unpositioned, 0);
+    assert_eq!(histogram, defect_pair_histogram(&defects, 8, 8.0));
+
+    let clipped = defect_pair_histogram(&defects, 4, 4.0);
+    assert_eq!(clipped.counts, vec![0; 4]);
+    assert_eq!(clipped.overflow, 1);
+}
+
+#[test]
+fn defects_without_coordinates_are_counted_not_fatal() {
+    let code = lattice_code();
+    let violations = ViolationSet::new(
+        vec![bond(0, 0), bond(2, 2), bond(5, 6)].into(),
+        Vec::new().into(),
+    );
+    // Variables on the last two rows have no embedding.
+    let partial = |var: usize| (var / SIDE < SIDE - 2).then(|| lattice_coords(var).unwrap());
+    let defects = code.find_defects_with_positions(&violations, &partial);
+    assert_eq!(defects.len(), 3);
+    assert_eq!(defects.iter().filter(|d| d.position.is_none()).count(), 1);
+
+    let histogram = defect_pair_histogram(&defects, 4, 8.0);
+    assert_eq!(histogram.unpositioned, 1);
+    assert_eq!(histogram.counts.iter().sum::<u64>() + histogram.overflow, 1);
+}
@@ -50,6 +50,7 @@ fn dispersion_curves_share_common_velocity() {
     let opts = DispersionOptions {
         steps: vec![1, 3, 5],
         tolerance: 1e-3,
+        ..DispersionOptions::default()
     };
 
     let report = estimate_dispersion(&code, &graph, &species, &opts).unwrap();
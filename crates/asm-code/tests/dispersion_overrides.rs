@@ -0,0 +1,165 @@
+use std::collections::BTreeMap;
+
+use asm_code::dispersion::{estimate_dispersion, DispersionOptions, DispersionReport};
+use asm_code::{CSSCode, SpeciesId};
+use asm_core::{ErrorCode, Hypergraph, RunProvenance, SchemaVersion};
+use asm_graph::{HypergraphConfig, HypergraphImpl};
+
+/// Four weight-2 checks and one weight-6 X check, whose species travels three
+/// times faster than the rest.
+fn build_code() -> CSSCode {
+    CSSCode::new(
+        6,
+        vec![vec![0, 1], vec![2, 3], vec![0, 1, 2, 3, 4, 5]],
+        vec![vec![0, 1], vec![2, 3]],
+        SchemaVersion::new(1, 0, 0),
+        RunProvenance::default(),
+    )
+    .unwrap()
+}
+
+fn build_graph() -> HypergraphImpl {
+    let mut config = HypergraphConfig::default();
+    config.k_uniform = None;
+    let mut graph = HypergraphImpl::new(config);
+    let a = graph.add_node().unwrap();
+    let b = graph.add_node().unwrap();
+    graph.add_hyperedge(&[a], &[b]).unwrap();
+    graph
+}
+
+fn options(tolerance: f64) -> DispersionOptions {
+    DispersionOptions {
+        steps: vec![1, 2, 4],
+        tolerance,
+        ..DispersionOptions::default()
+    }
+}
+
+/// Species with the largest residual in `report`.
+fn heavy_species(report: &DispersionReport) -> SpeciesId {
+    report
+        .residuals
+        .iter()
+        .max_by(|a, b| a.residual.total_cmp(&b.residual))
+        .unwrap()
+        .species
+}
+
+#[test]
+fn override_passes_off_velocity_species_that_fails_globally() {
+    let code = build_code();
+    let graph = build_graph();
+    let species = code.species_catalog();
+    let global = options(0.05);
+    let heavy = heavy_species(&estimate_dispersion(&code, &graph, &species, &global).unwrap());
+
+    let mut overridden = global.clone();
+    overridden.per_species_tolerance.insert(heavy, 100.0);
+    let report = estimate_dispersion(&code, &graph, &species, &overridden).unwrap();
+
+    let checks = report.check_residuals(&overridden);
+    assert_eq!(checks.len(), species.len());
+    assert!(checks.iter().all(|check| check.pass), "{checks:?}");
+    let heavy_check = checks.iter().find(|check| check.species == heavy).unwrap();
+    assert_eq!(heavy_check.tolerance, 100.0);
+
+    let global_checks = report.check_residuals(&global);
+    let failed: Vec<SpeciesId> = global_checks
+        .iter()
+        .filter(|check| !check.pass)
+        .map(|check| check.species)
+        .collect();
+    assert_eq!(failed, vec![heavy]);
+}
+
+#[test]
+fn common_c_is_weighted_by_inverse_tolerance() {
+    let code = build_code();
+    let graph = build_graph();
+    let species = code.species_catalog();
+    let plain = estimate_dispersion(&code, &graph, &species, &options(0.05)).unwrap();
+    assert!(plain
+        .diagnostics
+        .weights
+        .iter()
+        .all(|weight| weight.weight == 1.0));
+    let velocities: Vec<f64> = plain
+        .per_species
+        .iter()
+        .map(|entry| entry.curve.last().unwrap().1)
+        .collect();
+    assert_eq!(
+        plain.common_c,
+        velocities.iter().sum::<f64>() / velocities.len() as f64
+    );
+
+    let heavy = heavy_species(&plain);
+    let mut opts = options(0.05);
+    opts.per_species_tolerance.insert(heavy, 0.2);
+    let weighted = estimate_dispersion(&code, &graph, &species, &opts).unwrap();
+    let recorded: BTreeMap<SpeciesId, f64> = weighted
+        .diagnostics
+        .weights
+        .iter()
+        .map(|weight| (weight.species, weight.weight))
+        .collect();
+    assert_eq!(recorded[&heavy], 0.25);
+    assert!(weighted.common_c < plain.common_c);
+
+    opts.per_species_weight.insert(heavy, 40.0);
+    let explicit = estimate_dispersion(&code, &graph, &species, &opts).unwrap();
+    assert!(explicit.common_c > plain.common_c);
+}
+
+#[test]
+fn overrides_for_unmeasured_species_warn() {
+    let code = build_code();
+    let graph = build_graph();
+    let species = code.species_catalog();
+    let missing = SpeciesId::from_raw(7);
+    assert!(!species.contains(&missing));
+    let mut opts = options(0.05);
+    opts.per_species_tolerance.insert(missing, 1.0);
+    opts.per_species_weight.insert(missing, 2.0);
+
+    let report = estimate_dispersion(&code, &graph, &species, &opts).unwrap();
+    let warnings = &report.diagnostics.warnings;
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings.iter().all(|warning| warning.species == missing));
+    assert_eq!(report.check_residuals(&opts).len(), species.len());
+}
+
+#[test]
+fn invalid_overrides_are_rejected() {
+    let code = build_code();
+    let graph = build_graph();
+    let species = code.species_catalog();
+    let mut opts = options(0.05);
+    opts.per_species_tolerance.insert(species[0], 0.0);
+    let err = estimate_dispersion(&code, &graph, &species, &opts).unwrap_err();
+    assert!(err.is(ErrorCode::InvalidDispersionOverride));
+
+    let mut opts = options(0.05);
+    for sp in &species {
+        opts.per_species_weight.insert(*sp, 0.0);
+    }
+    let err = estimate_dispersion(&code, &graph, &species, &opts).unwrap_err();
+    assert!(err.is(ErrorCode::InvalidDispersionOverride));
+}
+
+#[test]
+fn options_round_trip_through_json() {
+    let mut opts = options(0.05);
+    opts.per_species_tolerance
+        .insert(SpeciesId::from_raw(u64::MAX), 0.5);
+    opts.per_species_weight.insert(SpeciesId::from_raw(3), 2.0);
+    let json = serde_json::to_string(&opts).unwrap();
+    let restored: DispersionOptions = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, opts);
+
+    let legacy: DispersionOptions =
+        serde_json::from_str(r#"{"steps":[1,2],"tolerance":0.1}"#).unwrap();
+    assert!(legacy.per_species_tolerance.is_empty());
+    assert!(legacy.per_species_weight.is_empty());
+}
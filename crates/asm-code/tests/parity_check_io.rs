@@ -0,0 +1,107 @@
+use std::fs;
+use std::path::PathBuf;
+
+use asm_code::serde::{css_from_alist, css_from_mtx, css_to_alist, css_to_mtx};
+use asm_core::{ErrorCode, RunProvenance, SchemaVersion};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../fixtures/codes")
+        .join(name)
+}
+
+fn provenance() -> RunProvenance {
+    RunProvenance {
+        input_hash: "input".into(),
+        graph_hash: "graph".into(),
+        code_hash: String::new(),
+        seed: 7,
+        created_at: "2024-01-01T00:00:00Z".into(),
+        tool_versions: Default::default(),
+    }
+}
+
+fn schema() -> SchemaVersion {
+    SchemaVersion::new(1, 0, 0)
+}
+
+#[test]
+fn steane_code_imports_from_alist_and_mtx() {
+    let alist = fixture("steane_h.alist");
+    let mtx = fixture("steane_h.mtx");
+    let from_alist = css_from_alist(&alist, &alist, schema(), provenance()).expect("alist");
+    let from_mtx = css_from_mtx(&mtx, &mtx, schema(), provenance()).expect("mtx");
+
+    for code in [&from_alist, &from_mtx] {
+        assert_eq!(code.num_variables(), 7);
+        assert_eq!(code.rank_x(), 3);
+        assert_eq!(code.rank_z(), 3);
+        assert_eq!(code.num_variables() - code.rank_x() - code.rank_z(), 1);
+    }
+    assert_eq!(from_alist.canonical_hash(), from_mtx.canonical_hash());
+}
+
+#[test]
+fn exported_matrices_reimport_identically() {
+    let alist = fixture("steane_h.alist");
+    let code = css_from_alist(&alist, &alist, schema(), provenance()).expect("alist");
+    let dir = std::env::temp_dir().join(format!("asm_code_parity_{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("dir");
+
+    let (x_alist, z_alist) = (dir.join("x.alist"), dir.join("z.alist"));
+    css_to_alist(&code, &x_alist, &z_alist).expect("write alist");
+    assert_eq!(
+        fs::read_to_string(&x_alist).expect("read"),
+        fs::read_to_string(&alist).expect("read")
+    );
+    let again = css_from_alist(&x_alist, &z_alist, schema(), provenance()).expect("reimport");
+    assert_eq!(code.canonical_hash(), again.canonical_hash());
+
+    let (x_mtx, z_mtx) = (dir.join("x.mtx"), dir.join("z.mtx"));
+    css_to_mtx(&code, &x_mtx, &z_mtx).expect("write mtx");
+    let again = css_from_mtx(&x_mtx, &z_mtx, schema(), provenance()).expect("reimport");
+    assert_eq!(code.canonical_hash(), again.canonical_hash());
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn malformed_inputs_report_file_context() {
+    let dir = std::env::temp_dir().join(format!("asm_code_parity_bad_{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("dir");
+
+    let bad_column = dir.join("bad.mtx");
+    fs::write(
+        &bad_column,
+        "%%MatrixMarket matrix coordinate pattern general\n1 3 1\n1 4\n",
+    )
+    .expect("write");
+    let err = css_from_mtx(&bad_column, &bad_column, schema(), provenance()).unwrap_err();
+    assert!(err.is(ErrorCode::ParityCheckParse));
+    assert_eq!(err.context_value("line"), Some("3"));
+    assert_eq!(err.context_value("column"), Some("4"));
+
+    let narrow = dir.join("narrow.mtx");
+    fs::write(
+        &narrow,
+        "%%MatrixMarket matrix coordinate pattern general\n1 5 1\n1 1\n",
+    )
+    .expect("write");
+    let steane = fixture("steane_h.mtx");
+    let err = css_from_mtx(&steane, &narrow, schema(), provenance()).unwrap_err();
+    assert!(err.is(ErrorCode::ParityCheckDimension));
+
+    // A single X check on qubit 1 anticommutes with the first Z check.
+    let single = dir.join("single.mtx");
+    fs::write(
+        &single,
+        "%%MatrixMarket matrix coordinate integer general\n1 7 2\n1 1 1\n1 2 2\n",
+    )
+    .expect("write");
+    let err = css_from_mtx(&single, &steane, schema(), provenance()).unwrap_err();
+    assert!(err.is(ErrorCode::CssOrthogonalityFailed));
+    assert_eq!(err.context_value("x_index"), Some("0"));
+    assert!(err.context_value("x_path").is_some());
+
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,132 @@
+use std::time::{Duration, Instant};
+
+use asm_code::css::BATCHED_MIN_STATES;
+use asm_code::{CSSCode, StateHandle};
+use asm_core::{ConstraintState, ErrorCode, RunProvenance, SchemaVersion};
+use proptest::prelude::*;
+
+/// Builds a code whose checks are unions of disjoint variable pairs, so every
+/// X/Z overlap has even size. `x_offsets`/`z_offsets` pick the pairs of
+/// check `k` relative to pair `k`.
+fn paired_code(pairs: usize, x_offsets: &[usize], z_offsets: &[usize]) -> CSSCode {
+    let check = |k: usize, offsets: &[usize]| -> Vec<usize> {
+        offsets
+            .iter()
+            .flat_map(|offset| {
+                let pair = (k + offset) % pairs;
+                [2 * pair, 2 * pair + 1]
+            })
+            .collect()
+    };
+    CSSCode::new(
+        2 * pairs,
+        (0..pairs).map(|k| check(k, x_offsets)).collect(),
+        (0..pairs).map(|k| check(k, z_offsets)).collect(),
+        SchemaVersion::new(1, 0, 0),
+        RunProvenance::default(),
+    )
+    .unwrap()
+}
+
+fn refs(states: &[StateHandle]) -> Vec<&dyn ConstraintState> {
+    states.iter().map(|s| s as &dyn ConstraintState).collect()
+}
+
+fn sequential(code: &CSSCode, states: &[&dyn ConstraintState]) -> Vec<asm_code::ViolationSet> {
+    states
+        .iter()
+        .map(|state| code.violations_for_state(*state).unwrap())
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn batched_matches_sequential(
+        pairs in 5usize..80,
+        seeds in prop::collection::vec(any::<u64>(), 0..150),
+    ) {
+        let code = paired_code(pairs, &[0, 1], &[0, 2]);
+        let states: Vec<StateHandle> = seeds
+            .iter()
+            .map(|seed| {
+                let bits: Vec<u8> = (0..code.num_variables())
+                    .map(|var| (seed.rotate_left(var as u32 % 64) & 1) as u8)
+                    .collect();
+                StateHandle::from_bits(bits).unwrap()
+            })
+            .collect();
+        let states = refs(&states);
+        let expected = sequential(&code, &states);
+        prop_assert_eq!(&code.violations_for_states_batched(&states).unwrap(), &expected);
+        prop_assert_eq!(&code.violations_for_states(&states).unwrap(), &expected);
+    }
+}
+
+#[test]
+fn batched_rejects_mismatched_lengths() {
+    let code = paired_code(5, &[0, 1], &[0, 2]);
+    let mut states: Vec<StateHandle> = (0..BATCHED_MIN_STATES)
+        .map(|_| StateHandle::from_bits(vec![0; 10]).unwrap())
+        .collect();
+    states.push(StateHandle::from_bits(vec![0; 9]).unwrap());
+    let err = code.violations_for_states(&refs(&states)).unwrap_err();
+    assert!(err.is(ErrorCode::StateLengthMismatch));
+}
+
+/// Minimum speedup asserted by the timing test. Release builds measure
+/// roughly 6-9x on this workload; the margin absorbs timer and scheduler noise.
+const MIN_SPEEDUP: u32 = 5;
+
+#[test]
+#[cfg_attr(
+    debug_assertions,
+    ignore = "timing benchmark; run with `cargo test --release -p asm-code --test syndrome_batched`"
+)]
+fn batched_is_faster_on_large_batches() {
+    // 1000 variables, 500 weight-4 X checks and 500 weight-6 Z checks, and
+    // decoder-style error states with roughly 1% of the bits flipped.
+    let code = paired_code(500, &[0, 2], &[0, 1, 3]);
+    let states: Vec<StateHandle> = (0..10_000u64)
+        .map(|seed| {
+            let mut value = seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1;
+            let bits: Vec<u8> = (0..code.num_variables())
+                .map(|_| {
+                    value ^= value << 13;
+                    value ^= value >> 7;
+                    value ^= value << 17;
+                    u8::from(value % 100 == 0)
+                })
+                .collect();
+            StateHandle::from_bits(bits).unwrap()
+        })
+        .collect();
+    let states = refs(&states);
+
+    let best = |run: &dyn Fn() -> Vec<asm_code::ViolationSet>| {
+        (0..3)
+            .map(|_| {
+                let started = Instant::now();
+                let results = std::hint::black_box(run());
+                let elapsed = started.elapsed();
+                drop(results);
+                elapsed
+            })
+            .min()
+            .unwrap_or(Duration::MAX)
+    };
+    let expected = sequential(&code, &states);
+    assert_eq!(
+        code.violations_for_states_batched(&states).unwrap(),
+        expected
+    );
+    let sequential_time = best(&|| sequential(&code, &states));
+    let batched_time = best(&|| code.violations_for_states_batched(&states).unwrap());
+    eprintln!(
+        "sequential {sequential_time:?}, batched {batched_time:?} ({:.1}x)",
+        sequential_time.as_secs_f64() / batched_time.as_secs_f64()
+    );
+    assert!(
+        batched_time * MIN_SPEEDUP <= sequential_time,
+        "batched {batched_time:?} vs sequential {sequential_time:?}"
+    );
+}
@@ -1,5 +1,5 @@
 use asm_code::{CSSCode, StateHandle};
-use asm_core::{AsmError, ConstraintProjector, RunProvenance, SchemaVersion};
+use asm_core::{AsmError, ConstraintProjector, ErrorCode, RunProvenance, SchemaVersion};
 
 fn provenance() -> RunProvenance {
     RunProvenance {
@@ -62,3 +62,13 @@ fn mismatched_state_length_errors() {
         panic!("unexpected error variant");
     }
 }
+
+#[test]
+fn mismatched_state_length_matches_registered_code() {
+    let code = build_code();
+    let bad_state = StateHandle::from_bits(vec![1, 0]).unwrap();
+    let err = code.violations_for_state(&bad_state).unwrap_err();
+    assert!(err.is(ErrorCode::StateLengthMismatch));
+    assert_eq!(err.info().code_enum(), Some(ErrorCode::StateLengthMismatch));
+    assert!(err.context_value("state_len").is_some());
+}
@@ -0,0 +1,100 @@
+//! Captures the toolchain and workspace crate versions recorded by
+//! `provenance::build_fingerprint`.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Returns the string value of `key = "value"` in the `[package]` table.
+fn package_field(manifest: &str, key: &str) -> Option<String> {
+    let mut in_package = false;
+    for line in manifest.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+            continue;
+        }
+        if !in_package {
+            continue;
+        }
+        let Some((name, value)) = line.split_once('=') else {
+            continue;
+        };
+        if name.trim() == key {
+            return Some(value.trim().trim_matches('"').to_string());
+        }
+    }
+    None
+}
+
+/// Manifest paths of the `asm-*` workspace members, or just this crate when
+/// it is built outside the workspace.
+fn member_manifests(manifest_dir: &Path) -> Vec<PathBuf> {
+    let crates_dir = manifest_dir.join("..");
+    let workspace = manifest_dir.join("../../Cargo.toml");
+    println!("cargo:rerun-if-changed={}", workspace.display());
+    let members: Vec<PathBuf> = fs::read_to_string(&workspace)
+        .map(|contents| {
+            contents
+                .lines()
+                .filter_map(|line| line.trim().trim_end_matches(',').strip_prefix("\"crates/"))
+                .map(|member| {
+                    crates_dir
+                        .join(member.trim_end_matches('"'))
+                        .join("Cargo.toml")
+                })
+                .filter(|path| path.exists())
+                .collect()
+        })
+        .unwrap_or_default();
+    if members.is_empty() {
+        vec![manifest_dir.join("Cargo.toml")]
+    } else {
+        members
+    }
+}
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("manifest dir"));
+    let mut crates = BTreeMap::new();
+    for manifest in member_manifests(&manifest_dir) {
+        println!("cargo:rerun-if-changed={}", manifest.display());
+        let Ok(contents) = fs::read_to_string(&manifest) else {
+            continue;
+        };
+        if let (Some(name), Some(version)) = (
+            package_field(&contents, "name"),
+            package_field(&contents, "version"),
+        ) {
+            if name.starts_with("asm-") {
+                crates.insert(name, version);
+            }
+        }
+    }
+    let entries: String = crates
+        .iter()
+        .map(|(name, version)| format!("    ({name:?}, {version:?}),\n"))
+        .collect();
+    let out = PathBuf::from(env::var("OUT_DIR").expect("out dir")).join("workspace_crates.rs");
+    fs::write(
+        out,
+        format!("const WORKSPACE_CRATES: &[(&str, &str)] = &[\n{entries}];\n"),
+    )
+    .expect("write workspace crate table");
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".into());
+    println!("cargo:rustc-env=ASM_BUILD_RUSTC={version}");
+    println!(
+        "cargo:rustc-env=ASM_BUILD_TARGET={}",
+        env::var("TARGET").unwrap_or_else(|_| "unknown".into())
+    );
+}
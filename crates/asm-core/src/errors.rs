@@ -2,10 +2,276 @@
 
 use std::collections::BTreeMap;
 use std::fmt::{self, Display};
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+macro_rules! error_codes {
+    ($($(#[$doc:meta])* $variant:ident => $code:literal $(| $alias:literal)*,)*) => {
+        /// Registry of stable error codes emitted by core ASM crates.
+        ///
+        /// [`ErrorInfo::code`] stays a free-form string so extension crates and
+        /// plugins can emit codes outside this registry; use
+        /// [`ErrorInfo::code_enum`] to match known failure modes without string
+        /// comparisons. Legacy spellings of a merged code still parse to it.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum ErrorCode {
+            $($(#[$doc])* $variant,)*
+        }
+
+        impl ErrorCode {
+            /// Every registered code in declaration order.
+            pub const ALL: &'static [ErrorCode] = &[$(ErrorCode::$variant,)*];
+
+            /// Returns the stable string form stored in [`ErrorInfo::code`].
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(ErrorCode::$variant => $code,)*
+                }
+            }
+        }
+
+        impl FromStr for ErrorCode {
+            type Err = UnknownErrorCode;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                match value {
+                    $($code $(| $alias)* => Ok(ErrorCode::$variant),)*
+                    other => Err(UnknownErrorCode(other.to_string())),
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    // Hypergraph structure and serialization (`asm-graph`).
+    /// Referenced node does not exist.
+    UnknownNode => "unknown-node",
+    /// Referenced hyperedge does not exist.
+    UnknownEdge => "unknown-edge",
+    /// Hyperedge with identical endpoints already exists.
+    DuplicateEdge => "duplicate-edge",
+    /// Insertion would violate the acyclicity constraint.
+    WouldCreateCycle => "would-create-cycle",
+    /// Insertion would exceed the configured out-degree cap.
+    OutDegreeCap => "out-degree-cap",
+    /// Insertion would exceed the configured in-degree cap.
+    InDegreeCap => "in-degree-cap",
+    /// Node still participates in hyperedges.
+    NodeNotIsolated => "node-not-isolated",
+    /// Hyperedge arity violates the configured uniformity.
+    InvalidArity => "invalid-arity",
+    /// Hyperedge sources or destinations are empty.
+    EmptyEndpoints => "empty-endpoints",
+    /// Rewiring requested without destination candidates.
+    EmptyDestinations => "empty-destinations",
+    /// Rewiring destination is not part of the edge.
+    MissingDestination => "missing-destination",
+    /// Generator was asked to build a graph without nodes.
+    EmptyGraph => "empty-graph",
+    /// Operation requires at least one node.
+    NoNodes => "no-nodes",
+    /// Identifier does not fit the 32-bit compact storage mode.
+    CompactIdOverflow => "compact-id-overflow",
+    /// Iterative routine configured with zero iterations.
+    ZeroIterations => "zero-iterations",
+    /// Graph binary encoding failed.
+    SerializeBytes => "serialize-bytes",
+    /// Graph binary decoding failed.
+    DeserializeBytes => "deserialize-bytes",
+    /// Per-node degree override targets a missing node or is already exceeded.
+    DegreeOverride => "degree-override",
+
+    // Constraint projector state and serialization (`asm-code`).
+    /// Constraint support is duplicated within a check family.
+    DuplicateConstraint => "duplicate-constraint",
+    /// Constraint references a variable beyond the code length.
+    VariableOutOfRange => "variable-out-of-range",
+    /// X and Z checks do not commute.
+    CssOrthogonalityFailed => "css-orthogonality-failed",
+    /// Analysis requires an orthogonal CSS code.
+    NonOrthogonalCode => "non-orthogonal-code",
+    /// State length differs from the number of variables.
+    StateLengthMismatch => "state-length-mismatch",
+    /// State bits must be zero or one.
+    InvalidStateBit => "invalid-state-bit",
+    /// Constraint state pointer was null.
+    NullStateHandle => "null-state-handle",
+    /// State handle type is not recognised.
+    UnknownStateHandle => "unknown-state-handle",
+    /// X violation index exceeds the number of X checks.
+    XViolationOutOfRange => "x-violation-out-of-range",
+    /// Z violation index exceeds the number of Z checks.
+    ZViolationOutOfRange => "z-violation-out-of-range",
+    /// Dispersion estimate requested with no steps.
+    EmptyDispersionSteps => "empty-dispersion-steps",
+    /// Per-species dispersion tolerance or weight is out of range.
+    InvalidDispersionOverride => "invalid-dispersion-override",
+    /// Code binary encoding failed.
+    BincodeSerialize => "bincode-serialize",
+    /// Code binary decoding failed.
+    BincodeDeserialize => "bincode-deserialize",
+    /// Parity-check matrix file could not be read or written.
+    ParityCheckIo => "parity-check-io",
+    /// Parity-check matrix file is malformed.
+    ParityCheckParse => "parity-check-parse",
+    /// X and Z parity-check matrices disagree on the code length.
+    ParityCheckDimension => "parity-check-dimension",
+
+    // Sampler moves, checkpoints and manifests (`asm-mcmc`).
+    /// Move requires at least one hyperedge.
+    NoEdges => "no-edges",
+    /// Move requires more hyperedges than available.
+    InsufficientEdges => "insufficient-edges",
+    /// Move requires more nodes than available.
+    InsufficientNodes => "insufficient-nodes",
+    /// Move requires at least one code generator.
+    NoGenerators => "no-generators",
+    /// Move requires more generators than available.
+    InsufficientGenerators => "insufficient-generators",
+    /// Move requires a code with variables.
+    EmptyCode => "empty-code",
+    /// Checkpoint contained no replicas.
+    EmptyCheckpoint => "empty-checkpoint" | "checkpoint-empty",
+    /// Checkpoint directory could not be created.
+    CheckpointMkdir => "checkpoint-mkdir",
+    /// Checkpoint encoding failed.
+    CheckpointSerialize => "checkpoint-serialize",
+    /// Checkpoint could not be written.
+    CheckpointWrite => "checkpoint-write",
+    /// Checkpoint could not be read.
+    CheckpointRead => "checkpoint-read",
+    /// Checkpoint decoding failed.
+    CheckpointParse => "checkpoint-parse",
+    /// Stale checkpoint could not be removed.
+    CheckpointRemove => "checkpoint-remove",
+    /// Mid-sweep checkpoint position lies outside the configured sweep.
+    InvalidSweepPosition => "invalid-sweep-position",
+    /// Run manifest directory could not be created.
+    ManifestMkdir => "manifest-mkdir",
+    /// Run manifest encoding failed.
+    ManifestSerialize => "manifest-serialize",
+    /// Run manifest could not be written.
+    ManifestWrite => "manifest-write",
+    /// Run manifest could not be read.
+    ManifestRead => "manifest-read",
+    /// Run manifest decoding failed.
+    ManifestParse => "manifest-parse",
+    /// Run manifest generation counter could not be read or written.
+    ManifestGeneration => "manifest-generation",
+    /// Run directory is mid-update or its manifest disagrees with the generation counter.
+    ManifestIncomplete => "manifest-incomplete",
+    /// Seed audit was requested but the `seed-audit` feature is disabled.
+    SeedAuditUnavailable => "seed-audit-unavailable",
+    /// Sampler metrics could not be written.
+    MetricsWrite => "metrics-write",
+    /// End-state directory could not be created.
+    EndStateMkdir => "end-state-mkdir",
+    /// End-state graph could not be written.
+    EndStateGraphWrite => "end-state-graph-write",
+    /// End-state code could not be written.
+    EndStateCodeWrite => "end-state-code-write",
+    /// End-state artefact could not be read.
+    EndStateRead => "end-state-read",
+
+    // Landscape plan execution and artefact I/O (`asm-land`).
+    /// Landscape plan could not be read.
+    PlanRead => "plan_read",
+    /// Landscape output directory could not be created.
+    PlanOutDir => "plan_out_dir",
+    /// Worker pool could not be constructed.
+    ThreadPool => "thread_pool",
+    /// Job directory could not be created.
+    JobDir => "job_dir",
+    /// Stage artefact directory could not be created.
+    StageDir => "stage_dir",
+    /// Stage artefact could not be written.
+    StageWrite => "stage_write",
+    /// Job status could not be read.
+    StatusRead => "status_read",
+    /// Job KPI snapshot could not be read.
+    KpiRead => "kpi_read",
+    /// Job stage hashes could not be read.
+    HashesRead => "hashes_read",
+    /// Filter specification could not be read.
+    FilterRead => "filter_read",
+    /// Landscape report could not be read.
+    LandscapeReportRead => "landscape_report_read",
+    /// Landscape report could not be written.
+    LandscapeReportWrite => "landscape_report_write",
+    /// Landscape JSON writing failed.
+    LandJsonWrite => "json_write",
+    /// Landscape YAML encoding failed.
+    YamlSerialize => "yaml_serialize",
+    /// Landscape YAML decoding failed.
+    YamlDeserialize => "yaml_deserialize",
+    /// Plan estimate cost table contained a negative or non-finite entry.
+    EstimateCost => "estimate_cost",
+    /// Filter expression references an unknown KPI or has an empty combinator.
+    FilterExpr => "filter_expr",
+    /// Run tree directory could not be listed.
+    RunTreeRead => "run_tree_read",
+    /// Streaming atlas construction was asked to deduplicate entries.
+    AtlasStreamDedup => "atlas_stream_dedup",
+    /// Statistics options contain invalid quantiles, weights, or bin edges.
+    StatsOpts => "stats_opts",
+    /// Resumed job artefacts disagree with a recomputation of the job.
+    ResumeMismatch => "resume_mismatch",
+    /// KPI extractor is misnamed or returned an unusable value.
+    KpiExtractor => "kpi_extractor",
+    /// Two KPI extractors produced the same namespaced KPI.
+    KpiCollision => "kpi_collision",
+    /// Landscape event log could not be written.
+    EventLogWrite => "event_log_write",
+    /// Landscape event log could not be read or contains a malformed line.
+    EventLogRead => "event_log_read",
+
+    // JSON encoding shared by graph, code and landscape artefacts.
+    /// JSON encoding failed.
+    JsonSerialize => "json-serialize" | "serialize-json" | "json_serialize",
+    /// JSON decoding failed.
+    JsonDeserialize => "json-deserialize" | "deserialize-json" | "json_deserialize",
+
+    // Named coupling registry (`asm-core` types).
+    /// Coupling name is not recognised or cannot be assigned.
+    UnknownCoupling => "unknown-coupling",
+    /// Two coupling sets do not share the same names.
+    CouplingNameMismatch => "coupling-name-mismatch",
+    /// Serialized couplings are missing required entries.
+    CouplingFormat => "coupling-format",
+
+    // Domain-separated hashing (`asm-core` hash).
+    /// Hash of one artefact kind was compared against another kind.
+    HashDomainMismatch => "hash-domain-mismatch",
+
+    // Schema version checks (`asm-core` provenance).
+    /// Payload schema version cannot be read at the required version.
+    SchemaIncompatible => "schema-incompatible",
+
+    // Structural JSON diffs (`asm-core` json_diff).
+    /// Artefact passed to the JSON diff is not valid JSON.
+    JsonDiffParse => "json-diff-parse",
+}
+
+/// Returned when a string does not name a registered [`ErrorCode`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("unknown error code: {0}")]
+pub struct UnknownErrorCode(pub String);
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<ErrorCode> for String {
+    fn from(code: ErrorCode) -> Self {
+        code.as_str().to_string()
+    }
+}
+
 /// Structured payload attached to every [`AsmError`] variant.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ErrorInfo {
@@ -43,6 +309,11 @@ impl ErrorInfo {
         self.hint = Some(hint.into());
         self
     }
+
+    /// Returns the registered [`ErrorCode`] matching the string code, if any.
+    pub fn code_enum(&self) -> Option<ErrorCode> {
+        self.code.parse().ok()
+    }
 }
 
 /// Canonical error type for the ASM engine.
@@ -69,6 +340,43 @@ pub enum AsmError {
     Serde(ErrorInfo),
 }
 
+/// Family of an [`AsmError`], one per variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ErrorCategory {
+    /// [`AsmError::Graph`].
+    Graph,
+    /// [`AsmError::Code`].
+    Code,
+    /// [`AsmError::RG`].
+    RG,
+    /// [`AsmError::Dictionary`].
+    Dictionary,
+    /// [`AsmError::Rng`].
+    Rng,
+    /// [`AsmError::Serde`].
+    Serde,
+}
+
+impl ErrorCategory {
+    /// Returns the lowercase label used as the [`AsmError`] display prefix.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCategory::Graph => "graph",
+            ErrorCategory::Code => "code",
+            ErrorCategory::RG => "rg",
+            ErrorCategory::Dictionary => "dictionary",
+            ErrorCategory::Rng => "rng",
+            ErrorCategory::Serde => "serde",
+        }
+    }
+}
+
+impl Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl Display for ErrorInfo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{} (code: {})", self.message, self.code)?;
@@ -101,4 +409,49 @@ impl AsmError {
             | AsmError::Serde(info) => info,
         }
     }
+
+    /// Returns the family the error belongs to.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            AsmError::Graph(_) => ErrorCategory::Graph,
+            AsmError::Code(_) => ErrorCategory::Code,
+            AsmError::RG(_) => ErrorCategory::RG,
+            AsmError::Dictionary(_) => ErrorCategory::Dictionary,
+            AsmError::Rng(_) => ErrorCategory::Rng,
+            AsmError::Serde(_) => ErrorCategory::Serde,
+        }
+    }
+
+    /// Returns the stable code string, registered or not.
+    pub fn code(&self) -> &str {
+        &self.info().code
+    }
+
+    /// Returns whether the error carries the provided registered code.
+    pub fn is(&self, code: ErrorCode) -> bool {
+        self.info().code == code.as_str()
+    }
+
+    /// Returns every recorded context entry.
+    pub fn context(&self) -> &BTreeMap<String, String> {
+        &self.info().context
+    }
+
+    /// Returns the context value recorded under `key`, if present.
+    pub fn context_value(&self, key: &str) -> Option<&str> {
+        self.info().context.get(key).map(String::as_str)
+    }
+
+    /// Finds the first [`AsmError`] in `err` or its chain of sources, e.g.
+    /// inside the `Box<dyn Error>` returned by a CLI command.
+    pub fn find_in<'a>(err: &'a (dyn std::error::Error + 'static)) -> Option<&'a AsmError> {
+        let mut current = Some(err);
+        while let Some(err) = current {
+            if let Some(found) = err.downcast_ref::<AsmError>() {
+                return Some(found);
+            }
+            current = err.source();
+        }
+        None
+    }
 }
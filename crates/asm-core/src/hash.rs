@@ -0,0 +1,170 @@
+//! Domain-separated content hashes.
+//!
+//! Report hashes are opaque hex strings, so a spectrum hash and a gauge hash
+//! look alike. From [`HASH_FORMAT`] on, hashes mix a [`HashDomain`] tag into
+//! the digest input and carry it as a prefix, e.g. `spec1-<hex>`. Artefacts
+//! recording [`LEGACY_HASH_FORMAT`] keep bare hex digests and stay readable.
+
+use std::fmt::{self, Display};
+
+use sha2::{Digest, Sha256};
+
+use crate::errors::{AsmError, ErrorCode, ErrorInfo};
+
+/// Hash format of artefacts written before domain separation: bare hex.
+pub const LEGACY_HASH_FORMAT: u32 = 1;
+
+/// Hash format of domain-separated hashes produced by [`domain_hash`].
+pub const HASH_FORMAT: u32 = 2;
+
+/// Version digit appended to every domain tag in the output prefix.
+const DOMAIN_VERSION: &str = "1";
+
+/// Kind of artefact a hash identifies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HashDomain {
+    /// Canonical hypergraph.
+    Graph,
+    /// Canonical CSS code.
+    Code,
+    /// Combined graph and code state.
+    State,
+    /// Spectrum analysis report.
+    Spectrum,
+    /// Gauge analysis report.
+    Gauge,
+    /// Interaction report.
+    Interaction,
+    /// Single RG step report.
+    RgStep,
+    /// RG trajectory report.
+    RgRun,
+    /// Operator dictionary couplings.
+    Couplings,
+    /// Landscape plan.
+    Plan,
+    /// Landscape report.
+    Landscape,
+    /// Ablation plan or report.
+    Ablation,
+    /// Reproducibility runbook.
+    Runbook,
+    /// Theory assertion report.
+    Theory,
+}
+
+impl HashDomain {
+    /// Every domain in declaration order.
+    pub const ALL: &'static [HashDomain] = &[
+        HashDomain::Graph,
+        HashDomain::Code,
+        HashDomain::State,
+        HashDomain::Spectrum,
+        HashDomain::Gauge,
+        HashDomain::Interaction,
+        HashDomain::RgStep,
+        HashDomain::RgRun,
+        HashDomain::Couplings,
+        HashDomain::Plan,
+        HashDomain::Landscape,
+        HashDomain::Ablation,
+        HashDomain::Runbook,
+        HashDomain::Theory,
+    ];
+
+    /// Short tag mixed into the digest and used as the output prefix.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            HashDomain::Graph => "graph",
+            HashDomain::Code => "code",
+            HashDomain::State => "state",
+            HashDomain::Spectrum => "spec",
+            HashDomain::Gauge => "gauge",
+            HashDomain::Interaction => "int",
+            HashDomain::RgStep => "rgstep",
+            HashDomain::RgRun => "rgrun",
+            HashDomain::Couplings => "dict",
+            HashDomain::Plan => "plan",
+            HashDomain::Landscape => "land",
+            HashDomain::Ablation => "abl",
+            HashDomain::Runbook => "runbook",
+            HashDomain::Theory => "thy",
+        }
+    }
+
+    /// Looks up a domain by its [`tag`](Self::tag).
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|domain| domain.tag() == tag)
+    }
+}
+
+impl Display for HashDomain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.tag())
+    }
+}
+
+/// Hashes `canonical_bytes` under `domain`, returning `<tag>1-<hex>`.
+///
+/// The digest input is `asm-hash:<tag>1\n` followed by the bytes, so equal
+/// payloads hashed under different domains never produce the same digest.
+pub fn domain_hash(domain: HashDomain, canonical_bytes: &[u8]) -> String {
+    let prefix = format!("{}{DOMAIN_VERSION}", domain.tag());
+    let mut hasher = Sha256::new();
+    hasher.update(format!("asm-hash:{prefix}\n").as_bytes());
+    hasher.update(canonical_bytes);
+    format!("{prefix}-{:x}", hasher.finalize())
+}
+
+/// Hashes `canonical_bytes` in the layout selected by `format`: a bare
+/// SHA-256 digest for [`LEGACY_HASH_FORMAT`], [`domain_hash`] otherwise.
+pub fn hash_with_format(format: u32, domain: HashDomain, canonical_bytes: &[u8]) -> String {
+    if format <= LEGACY_HASH_FORMAT {
+        format!("{:x}", Sha256::digest(canonical_bytes))
+    } else {
+        domain_hash(domain, canonical_bytes)
+    }
+}
+
+/// Splits a hash into its domain, if it carries a recognised prefix, and the
+/// hex digest. Legacy hashes return `None` and the input unchanged.
+pub fn parse_hash(hash: &str) -> (Option<HashDomain>, &str) {
+    hash.split_once('-')
+        .and_then(|(prefix, hex)| {
+            let tag = prefix.strip_suffix(DOMAIN_VERSION)?;
+            Some((HashDomain::from_tag(tag)?, hex))
+        })
+        .map_or((None, hash), |(domain, hex)| (Some(domain), hex))
+}
+
+fn domain_mismatch(expected: HashDomain, actual: HashDomain, hash: &str) -> AsmError {
+    AsmError::Serde(
+        ErrorInfo::new(
+            ErrorCode::HashDomainMismatch,
+            format!("expected a {expected} hash but found a {actual} hash"),
+        )
+        .with_context("expected", expected.tag())
+        .with_context("actual", actual.tag())
+        .with_context("hash", hash),
+    )
+}
+
+/// Fails with `hash-domain-mismatch` when `hash` is tagged with a domain
+/// other than `expected`. Legacy hashes carry no domain and pass.
+pub fn ensure_hash_domain(expected: HashDomain, hash: &str) -> Result<(), AsmError> {
+    match parse_hash(hash).0 {
+        Some(actual) if actual != expected => Err(domain_mismatch(expected, actual, hash)),
+        _ => Ok(()),
+    }
+}
+
+/// Fails with `hash-domain-mismatch` when `left` and `right` are tagged with
+/// different domains, so comparing them would be meaningless.
+pub fn ensure_comparable(left: &str, right: &str) -> Result<(), AsmError> {
+    match (parse_hash(left).0, parse_hash(right).0) {
+        (Some(expected), Some(actual)) if expected != actual => {
+            Err(domain_mismatch(expected, actual, right))
+        }
+        _ => Ok(()),
+    }
+}
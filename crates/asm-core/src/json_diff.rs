@@ -0,0 +1,167 @@
+//! Structural diffs between canonical JSON artefacts.
+//!
+//! Every crate writes artefacts with its own `to_canonical_json_bytes`, so two
+//! artefacts of the same kind can be compared value by value instead of line
+//! by line. Paths are JSON Pointers (RFC 6901), with `""` naming the root.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::errors::{AsmError, ErrorCode, ErrorInfo};
+
+/// How a JSON path differs between two artefacts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonDeltaKind {
+    /// Present only in the second artefact.
+    Added,
+    /// Present only in the first artefact.
+    Removed,
+    /// Present in both with different values.
+    Changed,
+}
+
+/// One differing path between two JSON artefacts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonDelta {
+    /// JSON Pointer to the differing value.
+    pub path: String,
+    /// Whether the value was added, removed, or changed.
+    pub kind: JsonDeltaKind,
+    /// Value in the first artefact; `None` when added.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old: Option<Value>,
+    /// Value in the second artefact; `None` when removed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new: Option<Value>,
+}
+
+impl fmt::Display for JsonDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |value: &Option<Value>| value.as_ref().map(Value::to_string).unwrap_or_default();
+        match self.kind {
+            JsonDeltaKind::Added => write!(f, "+ {}: {}", self.path, show(&self.new)),
+            JsonDeltaKind::Removed => write!(f, "- {}: {}", self.path, show(&self.old)),
+            JsonDeltaKind::Changed => write!(
+                f,
+                "~ {}: {} -> {}",
+                self.path,
+                show(&self.old),
+                show(&self.new)
+            ),
+        }
+    }
+}
+
+/// Parses two JSON artefacts and reports the paths that differ from `a` to
+/// `b`; see [`json_value_diff`] for the ordering.
+pub fn canonical_json_diff(a: &[u8], b: &[u8]) -> Result<Vec<JsonDelta>, AsmError> {
+    let parse = |bytes: &[u8], side: &str| -> Result<Value, AsmError> {
+        serde_json::from_slice(bytes).map_err(|err| {
+            AsmError::Serde(
+                ErrorInfo::new(ErrorCode::JsonDiffParse, err.to_string())
+                    .with_context("side", side),
+            )
+        })
+    };
+    Ok(json_value_diff(&parse(a, "a")?, &parse(b, "b")?))
+}
+
+/// Reports the paths that differ between two JSON values.
+///
+/// Objects are compared key by key in sorted order and arrays index by index,
+/// so elements past the shorter array are added or removed. Any other
+/// mismatch, including a change of type, is one [`JsonDeltaKind::Changed`]
+/// entry at that path. Deltas are listed depth-first in key order.
+pub fn json_value_diff(a: &Value, b: &Value) -> Vec<JsonDelta> {
+    json_value_diff_with_sets(a, b, &[])
+}
+
+/// Like [`json_value_diff`], but compares the arrays at `set_paths` as sets.
+///
+/// A set whose members differ is one [`JsonDeltaKind::Changed`] entry at its
+/// path; element order is ignored. Use it for sets serialized as arrays,
+/// where an index-by-index comparison would report one added member as a
+/// change at every later index.
+pub fn json_value_diff_with_sets(a: &Value, b: &Value, set_paths: &[&str]) -> Vec<JsonDelta> {
+    let mut deltas = Vec::new();
+    diff_into(a, b, &mut String::new(), set_paths, &mut deltas);
+    deltas
+}
+
+fn diff_into(
+    a: &Value,
+    b: &Value,
+    path: &mut String,
+    set_paths: &[&str],
+    deltas: &mut Vec<JsonDelta>,
+) {
+    match (a, b) {
+        (Value::Array(left), Value::Array(right)) if set_paths.contains(&path.as_str()) => {
+            let members =
+                |values: &[Value]| values.iter().map(Value::to_string).collect::<BTreeSet<_>>();
+            if members(left) != members(right) {
+                deltas.push(JsonDelta {
+                    path: path.clone(),
+                    kind: JsonDeltaKind::Changed,
+                    old: Some(a.clone()),
+                    new: Some(b.clone()),
+                });
+            }
+        }
+        (Value::Object(left), Value::Object(right)) => {
+            let keys: BTreeSet<&String> = left.keys().chain(right.keys()).collect();
+            for key in keys {
+                let len = path.len();
+                path.push('/');
+                path.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                child(left.get(key), right.get(key), path, set_paths, deltas);
+                path.truncate(len);
+            }
+        }
+        (Value::Array(left), Value::Array(right)) => {
+            for index in 0..left.len().max(right.len()) {
+                let len = path.len();
+                path.push('/');
+                path.push_str(&index.to_string());
+                child(left.get(index), right.get(index), path, set_paths, deltas);
+                path.truncate(len);
+            }
+        }
+        _ if a != b => deltas.push(JsonDelta {
+            path: path.clone(),
+            kind: JsonDeltaKind::Changed,
+            old: Some(a.clone()),
+            new: Some(b.clone()),
+        }),
+        _ => {}
+    }
+}
+
+fn child(
+    a: Option<&Value>,
+    b: Option<&Value>,
+    path: &mut String,
+    set_paths: &[&str],
+    deltas: &mut Vec<JsonDelta>,
+) {
+    match (a, b) {
+        (Some(a), Some(b)) => diff_into(a, b, path, set_paths, deltas),
+        (None, Some(b)) => deltas.push(JsonDelta {
+            path: path.clone(),
+            kind: JsonDeltaKind::Added,
+            old: None,
+            new: Some(b.clone()),
+        }),
+        (Some(a), None) => deltas.push(JsonDelta {
+            path: path.clone(),
+            kind: JsonDeltaKind::Removed,
+            old: Some(a.clone()),
+            new: None,
+        }),
+        (None, None) => {}
+    }
+}
@@ -7,14 +7,31 @@ use std::iter::ExactSizeIterator;
 use serde::{Deserialize, Serialize};
 
 pub mod errors;
+pub mod hash;
+pub mod json_diff;
 pub mod provenance;
 pub mod rng;
 mod types;
 
-pub use errors::{AsmError, ErrorInfo};
-pub use provenance::{RunProvenance, SchemaVersion};
-pub use rng::{derive_substream_seed, RngHandle};
-pub use types::Couplings;
+pub use errors::{AsmError, ErrorCategory, ErrorCode, ErrorInfo};
+pub use hash::{
+    domain_hash, ensure_comparable, ensure_hash_domain, hash_with_format, parse_hash, HashDomain,
+    HASH_FORMAT, LEGACY_HASH_FORMAT,
+};
+pub use json_diff::{
+    canonical_json_diff, json_value_diff, json_value_diff_with_sets, JsonDelta, JsonDeltaKind,
+};
+pub use provenance::{
+    build_fingerprint, compare_builds, BuildFingerprint, BuildMismatch, FieldChange,
+    ProvenanceDiff, RunProvenance, SchemaVersion, ToolVersionChange,
+};
+#[cfg(feature = "seed-audit")]
+pub use rng::SeedAudit;
+pub use rng::{
+    derive_labeled_seed, derive_substream_seed, philox4x32, CounterRng, DuplicateStream, RngHandle,
+    RngState, SeedAuditSummary, StreamLabel,
+};
+pub use types::{CouplingUnit, Couplings, COUPLINGS_FORMAT, STANDARD_COUPLINGS};
 
 /// Identifier for a node within a [`Hypergraph`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -1,7 +1,11 @@
 //! Provenance and schema descriptors shared across ASM artifacts.
 
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::errors::{AsmError, ErrorCode, ErrorInfo};
+use crate::json_diff::json_value_diff_with_sets;
 
 /// Semantic version describing the schema of serialized payloads.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -23,6 +27,39 @@ impl SchemaVersion {
             patch,
         }
     }
+
+    /// Returns whether a payload written at this version satisfies a reader
+    /// that requires `required`.
+    ///
+    /// Majors must match, since a major bump is breaking. The minor must be at
+    /// least the required one: older minors lack fields added since, while
+    /// newer minors only add fields the reader can ignore. Patch levels never
+    /// affect compatibility.
+    pub fn is_compatible_with(&self, required: SchemaVersion) -> bool {
+        self.major == required.major && self.minor >= required.minor
+    }
+
+    /// Fails with `schema-incompatible` unless
+    /// [`is_compatible_with`](Self::is_compatible_with) holds.
+    pub fn require_compatible(&self, required: SchemaVersion) -> Result<(), AsmError> {
+        if self.is_compatible_with(required) {
+            return Ok(());
+        }
+        Err(AsmError::Serde(
+            ErrorInfo::new(
+                ErrorCode::SchemaIncompatible,
+                format!("schema version {self} is not compatible with required version {required}"),
+            )
+            .with_context("found", self.to_string())
+            .with_context("required", required.to_string()),
+        ))
+    }
+}
+
+impl std::fmt::Display for SchemaVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
 }
 
 impl Default for SchemaVersion {
@@ -47,3 +84,252 @@ pub struct RunProvenance {
     /// Version map for all tools involved in the run.
     pub tool_versions: BTreeMap<String, String>,
 }
+
+/// Scalar [`RunProvenance`] field that differs between two records.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldChange {
+    /// Field name, e.g. `seed` or `input_hash`.
+    pub field: String,
+    /// Value in the record `diff` was called on.
+    pub old: String,
+    /// Value in the record it was compared with.
+    pub new: String,
+}
+
+/// Version change of one entry in [`RunProvenance::tool_versions`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolVersionChange {
+    /// Tool name.
+    pub tool: String,
+    /// Old version, `None` when the tool was added.
+    pub old: Option<String>,
+    /// New version, `None` when the tool was removed.
+    pub new: Option<String>,
+}
+
+/// Differences between two [`RunProvenance`] records.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ProvenanceDiff {
+    /// Changed scalar fields in declaration order.
+    pub fields: Vec<FieldChange>,
+    /// Added, removed, or changed tool versions, ordered by tool name.
+    pub tool_versions: Vec<ToolVersionChange>,
+}
+
+impl ProvenanceDiff {
+    /// Returns whether the two records are identical.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty() && self.tool_versions.is_empty()
+    }
+
+    /// Returns the change recorded for `field`, if it differs.
+    pub fn field(&self, field: &str) -> Option<&FieldChange> {
+        self.fields.iter().find(|change| change.field == field)
+    }
+
+    /// Returns the change recorded for `tool`, if its version differs.
+    pub fn tool(&self, tool: &str) -> Option<&ToolVersionChange> {
+        self.tool_versions.iter().find(|change| change.tool == tool)
+    }
+}
+
+impl std::fmt::Display for ProvenanceDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut lines = Vec::new();
+        for change in &self.fields {
+            lines.push(format!(
+                "{}: {} -> {}",
+                change.field, change.old, change.new
+            ));
+        }
+        for change in &self.tool_versions {
+            let show = |version: &Option<String>| version.as_deref().unwrap_or("-").to_string();
+            lines.push(format!(
+                "tool_versions.{}: {} -> {}",
+                change.tool,
+                show(&change.old),
+                show(&change.new)
+            ));
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl RunProvenance {
+    /// Reports which fields differ between `self` (old) and `other` (new).
+    pub fn diff(&self, other: &RunProvenance) -> ProvenanceDiff {
+        let mut diff = ProvenanceDiff::default();
+        for (field, old, new) in record_changes(self, other, PROVENANCE_FIELDS, &[]) {
+            match field.strip_prefix("tool_versions.") {
+                Some(tool) => diff.tool_versions.push(ToolVersionChange {
+                    tool: tool.to_string(),
+                    old,
+                    new,
+                }),
+                None => diff.fields.push(FieldChange {
+                    field,
+                    old: old.unwrap_or_else(|| "-".into()),
+                    new: new.unwrap_or_else(|| "-".into()),
+                }),
+            }
+        }
+        diff
+    }
+}
+
+/// [`RunProvenance`] fields in declaration order.
+const PROVENANCE_FIELDS: &[&str] = &[
+    "input_hash",
+    "graph_hash",
+    "code_hash",
+    "seed",
+    "created_at",
+    "tool_versions",
+];
+
+/// [`BuildFingerprint`] fields in the order its diff reports them.
+const FINGERPRINT_FIELDS: &[&str] = &["rustc", "target", "features", "git_commit", "crates"];
+
+/// Diffs two records through [`json_value_diff_with_sets`] and returns one
+/// `(field, old, new)` entry per delta, ordered by top-level field.
+///
+/// Nested paths are dotted (`crates.asm-core`), and values are rendered as
+/// plain strings with set members joined by `,`.
+fn record_changes<T: Serialize>(
+    old: &T,
+    new: &T,
+    order: &[&str],
+    set_paths: &[&str],
+) -> Vec<(String, Option<String>, Option<String>)> {
+    let value = |record: &T| serde_json::to_value(record).unwrap_or(Value::Null);
+    let mut changes: Vec<_> = json_value_diff_with_sets(&value(old), &value(new), set_paths)
+        .into_iter()
+        .map(|delta| {
+            let field = delta
+                .path
+                .trim_start_matches('/')
+                .split('/')
+                .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+                .collect::<Vec<_>>()
+                .join(".");
+            (field, delta.old.map(render), delta.new.map(render))
+        })
+        .collect();
+    let rank = |field: &str| {
+        let top = field.split('.').next().unwrap_or(field);
+        order
+            .iter()
+            .position(|name| *name == top)
+            .unwrap_or(order.len())
+    };
+    changes.sort_by_key(|(field, _, _)| rank(field));
+    changes
+}
+
+fn render(value: Value) -> String {
+    match value {
+        Value::String(text) => text,
+        Value::Null => "-".into(),
+        Value::Array(items) => items.into_iter().map(render).collect::<Vec<_>>().join(","),
+        other => other.to_string(),
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/workspace_crates.rs"));
+
+/// Identifies the build that produced an artefact.
+///
+/// Holds only values fixed at compile time, never timestamps or hostnames,
+/// and serializes canonically: crates and features are sorted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildFingerprint {
+    /// Version of every `asm-*` workspace crate, keyed by crate name.
+    pub crates: BTreeMap<String, String>,
+    /// `rustc --version` of the compiler that built the workspace.
+    pub rustc: String,
+    /// Enabled cargo features as `crate/feature`.
+    pub features: BTreeSet<String>,
+    /// Target triple, e.g. `x86_64-unknown-linux-gnu`.
+    pub target: String,
+    /// Commit from `GIT_COMMIT_HASH` or `VERGEN_GIT_SHA` at compile time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+}
+
+/// Fingerprint of the running build, shared by every artefact it writes.
+///
+/// Covers the features of `asm-core` itself; crates with features of their
+/// own add them with [`BuildFingerprint::with_feature`].
+pub fn build_fingerprint() -> BuildFingerprint {
+    let mut features = BTreeSet::new();
+    if cfg!(feature = "seed-audit") {
+        features.insert("asm-core/seed-audit".to_string());
+    }
+    BuildFingerprint {
+        crates: WORKSPACE_CRATES
+            .iter()
+            .map(|(name, version)| (name.to_string(), version.to_string()))
+            .collect(),
+        rustc: env!("ASM_BUILD_RUSTC").to_string(),
+        features,
+        target: env!("ASM_BUILD_TARGET").to_string(),
+        git_commit: option_env!("GIT_COMMIT_HASH")
+            .or(option_env!("VERGEN_GIT_SHA"))
+            .map(str::to_string),
+    }
+}
+
+impl BuildFingerprint {
+    /// Records an enabled feature, written as `crate/feature`.
+    pub fn with_feature(mut self, feature: impl Into<String>) -> Self {
+        self.features.insert(feature.into());
+        self
+    }
+
+    /// Reports which parts differ between `self` (old) and `other` (new).
+    ///
+    /// Crate versions are reported per crate as `crates.<name>`, with `-` for
+    /// a crate missing on one side.
+    pub fn diff(&self, other: &BuildFingerprint) -> Vec<FieldChange> {
+        record_changes(self, other, FINGERPRINT_FIELDS, &["/features"])
+            .into_iter()
+            .map(|(field, old, new)| FieldChange {
+                field,
+                old: old.unwrap_or_else(|| "-".into()),
+                new: new.unwrap_or_else(|| "-".into()),
+            })
+            .collect()
+    }
+}
+
+/// Pipeline stage built differently from the first fingerprinted stage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildMismatch {
+    /// Stage whose fingerprint differs.
+    pub stage: String,
+    /// First stage, which the others are compared with.
+    pub reference: String,
+    /// Differences from the reference fingerprint.
+    pub changes: Vec<FieldChange>,
+}
+
+/// Compares the fingerprints of pipeline stages against the first one and
+/// returns one entry per stage produced by a different build.
+pub fn compare_builds<'a>(
+    stages: impl IntoIterator<Item = (&'a str, &'a BuildFingerprint)>,
+) -> Vec<BuildMismatch> {
+    let mut stages = stages.into_iter();
+    let Some((reference, expected)) = stages.next() else {
+        return Vec::new();
+    };
+    stages
+        .filter_map(|(stage, fingerprint)| {
+            let changes = expected.diff(fingerprint);
+            (!changes.is_empty()).then(|| BuildMismatch {
+                stage: stage.to_string(),
+                reference: reference.to_string(),
+                changes,
+            })
+        })
+        .collect()
+}
@@ -1,51 +1,327 @@
 //! Deterministic RNG wrapper and seed-derivation helpers.
 
-use rand::rngs::StdRng;
 use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
 use siphasher::sip::SipHasher13;
+use std::fmt;
 use std::hash::Hasher;
 
 /// Deterministic RNG handle exposed to ASM consumers.
 ///
-/// The handle is a thin wrapper around `StdRng` that documents the seeding
-/// policy used throughout the project. A master `seed: u64` must be provided by
+/// The handle is a thin wrapper around `ChaCha12Rng` (the generator behind
+/// rand 0.8's `StdRng`, pinned so streams and saved states cannot change with
+/// a rand upgrade) that documents the seeding policy used throughout the
+/// project. A master `seed: u64` must be provided by
 /// the caller. Substreams are derived by hashing `(master_seed, substream_id)`
 /// with SipHash-1-3 configured with fixed zero keys. This rule is stable across
 /// platforms and must be used whenever deterministic branching is required.
+///
+/// Handles built with [`RngHandle::from_counter`] draw from a [`CounterRng`]
+/// instead, so streams can be addressed directly without deriving them in
+/// sequence.
 #[derive(Debug, Clone)]
 pub struct RngHandle {
-    rng: StdRng,
+    engine: Engine,
+}
+
+// Boxing `ChaCha12Rng` would add an allocation to every sequential handle.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone)]
+enum Engine {
+    Sequential(ChaCha12Rng),
+    Counter(CounterRng),
 }
 
 impl RngHandle {
     /// Creates a new RNG handle from a master seed.
     pub fn from_seed(seed: u64) -> Self {
         Self {
-            rng: StdRng::seed_from_u64(seed),
+            engine: Engine::Sequential(ChaCha12Rng::seed_from_u64(seed)),
+        }
+    }
+
+    /// Creates a handle on the counter-based stream `counter` of `seed`.
+    ///
+    /// Every `(seed, counter)` pair addresses its own stream, computed
+    /// directly rather than derived from earlier streams, so parallel jobs can
+    /// open their stream in any order. See [`CounterRng`] for the guarantees.
+    pub fn from_counter(seed: u64, counter: u64) -> Self {
+        Self {
+            engine: Engine::Counter(CounterRng::new(seed, counter)),
+        }
+    }
+
+    /// Creates a handle positioned at the labelled substream of `master_seed`.
+    ///
+    /// See [`derive_labeled_seed`].
+    pub fn from_label(master_seed: u64, label: &StreamLabel) -> Self {
+        Self::from_seed(derive_labeled_seed(master_seed, label))
+    }
+
+    /// Derives an independent child stream named `label`.
+    ///
+    /// Draws exactly one `u64` from `self` and seeds the child from the
+    /// labelled substream `(label, 0)` of that draw (see
+    /// [`derive_labeled_seed`]). Forks taken in the same order with the same
+    /// labels therefore reproduce, and the parent continues as if `next_u64`
+    /// had been called once.
+    pub fn fork(&mut self, label: &str) -> Self {
+        let state = self.next_u64();
+        Self::from_label(state, &StreamLabel::new(label, 0))
+    }
+
+    /// Captures the handle's exact position; see [`RngHandle::from_state`].
+    pub fn to_state(&self) -> RngState {
+        match &self.engine {
+            Engine::Sequential(rng) => RngState::Chacha12 {
+                seed: rng.get_seed(),
+                stream: rng.get_stream(),
+                word_pos: rng.get_word_pos(),
+            },
+            Engine::Counter(rng) => RngState::Philox {
+                seed: rng.seed(),
+                counter: rng.stream,
+                word_pos: rng.word_pos(),
+            },
         }
     }
 
-    /// Returns a mutable reference to the underlying RNG for advanced usage.
-    pub fn inner_mut(&mut self) -> &mut StdRng {
-        &mut self.rng
+    /// Rebuilds a handle from [`RngHandle::to_state`]; it continues with
+    /// exactly the draws the captured handle would have produced next.
+    pub fn from_state(state: &RngState) -> Self {
+        let engine = match *state {
+            RngState::Chacha12 {
+                seed,
+                stream,
+                word_pos,
+            } => {
+                let mut rng = ChaCha12Rng::from_seed(seed);
+                rng.set_stream(stream);
+                rng.set_word_pos(word_pos);
+                Engine::Sequential(rng)
+            }
+            RngState::Philox {
+                seed,
+                counter,
+                word_pos,
+            } => {
+                let mut rng = CounterRng::new(seed, counter);
+                rng.set_word_pos(word_pos);
+                Engine::Counter(rng)
+            }
+        };
+        Self { engine }
+    }
+
+    /// Returns a mutable reference to the underlying `ChaCha12Rng` for
+    /// advanced usage, or `None` for counter-based handles.
+    pub fn inner_mut(&mut self) -> Option<&mut ChaCha12Rng> {
+        match &mut self.engine {
+            Engine::Sequential(rng) => Some(rng),
+            Engine::Counter(_) => None,
+        }
+    }
+
+    fn rng(&mut self) -> &mut dyn RngCore {
+        match &mut self.engine {
+            Engine::Sequential(rng) => rng,
+            Engine::Counter(rng) => rng,
+        }
     }
 }
 
 impl RngCore for RngHandle {
     fn next_u32(&mut self) -> u32 {
-        self.rng.next_u32()
+        self.rng().next_u32()
     }
 
     fn next_u64(&mut self) -> u64 {
-        self.rng.next_u64()
+        self.rng().next_u64()
     }
 
     fn fill_bytes(&mut self, dest: &mut [u8]) {
-        self.rng.fill_bytes(dest)
+        self.rng().fill_bytes(dest)
     }
 
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
-        self.rng.try_fill_bytes(dest)
+        self.rng().try_fill_bytes(dest)
+    }
+}
+
+/// Serializable position of an [`RngHandle`], for checkpoint and resume.
+///
+/// `word_pos` counts the 32-bit output words already consumed, so restoring
+/// mid-block resumes at the same word. It is serialized as a decimal string
+/// because serde cannot buffer `u128` inside a tagged enum.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "engine", rename_all = "snake_case")]
+pub enum RngState {
+    /// Seeded handle ([`RngHandle::from_seed`]).
+    Chacha12 {
+        /// Expanded 256-bit ChaCha key.
+        seed: [u8; 32],
+        /// ChaCha stream (nonce).
+        stream: u64,
+        /// Words consumed from the stream.
+        #[serde(with = "decimal_u128")]
+        word_pos: u128,
+    },
+    /// Counter-based handle ([`RngHandle::from_counter`]).
+    Philox {
+        /// Philox key.
+        seed: u64,
+        /// Stream counter.
+        counter: u64,
+        /// Words consumed from the stream.
+        #[serde(with = "decimal_u128")]
+        word_pos: u128,
+    },
+}
+
+mod decimal_u128 {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+const PHILOX_M0: u32 = 0xD251_1F53;
+const PHILOX_M1: u32 = 0xCD9E_8D57;
+const PHILOX_W0: u32 = 0x9E37_79B9;
+const PHILOX_W1: u32 = 0xBB67_AE85;
+const PHILOX_ROUNDS: usize = 10;
+
+/// Applies Philox4x32-10 to `counter` under `key`.
+pub fn philox4x32(counter: [u32; 4], key: [u32; 2]) -> [u32; 4] {
+    let mulhilo = |a: u32, b: u32| {
+        let product = u64::from(a) * u64::from(b);
+        ((product >> 32) as u32, product as u32)
+    };
+    let (mut c, mut k) = (counter, key);
+    for round in 0..PHILOX_ROUNDS {
+        if round > 0 {
+            k[0] = k[0].wrapping_add(PHILOX_W0);
+            k[1] = k[1].wrapping_add(PHILOX_W1);
+        }
+        let (hi0, lo0) = mulhilo(PHILOX_M0, c[0]);
+        let (hi1, lo1) = mulhilo(PHILOX_M1, c[2]);
+        c = [hi1 ^ c[1] ^ k[0], lo1, hi0 ^ c[3] ^ k[1], lo0];
+    }
+    c
+}
+
+/// Counter-based generator: Philox4x32-10 keyed by a seed.
+///
+/// Output block `i` of stream `counter` is the Philox permutation, keyed by
+/// `seed`, of the 128-bit counter `(i, counter)`; each block yields two
+/// `u64`s. Nothing is carried between blocks, so any block of any stream can
+/// be computed directly ([`CounterRng::seek`]).
+///
+/// Guarantees:
+///
+/// * Philox4x32-10 passes the TestU01 BigCrush battery for sequential and
+///   interleaved counters (Salmon et al., "Parallel Random Numbers: As Easy as
+///   1, 2, 3", SC11).
+/// * For a fixed seed the permutation is a bijection, so distinct
+///   `(block, counter)` pairs never repeat a block: streams of one seed never
+///   overlap. Each stream holds 2^64 blocks (2^65 `u64` draws) before it wraps.
+/// * Output depends only on `(seed, counter, position)` and uses wrapping
+///   32-bit arithmetic, so it is identical on every platform.
+///
+/// It is not a cryptographic generator.
+#[derive(Debug, Clone)]
+pub struct CounterRng {
+    key: [u32; 2],
+    stream: u64,
+    block: u64,
+    buffer: [u32; 4],
+    /// Next unread word of `buffer`; 4 when the buffer is exhausted.
+    word: usize,
+}
+
+impl CounterRng {
+    /// Opens stream `counter` of `seed` at its first block.
+    pub fn new(seed: u64, counter: u64) -> Self {
+        Self {
+            key: [seed as u32, (seed >> 32) as u32],
+            stream: counter,
+            block: 0,
+            buffer: [0; 4],
+            word: 4,
+        }
+    }
+
+    /// Moves to the start of `block`; the next draw reads from it.
+    pub fn seek(&mut self, block: u64) {
+        self.block = block;
+        self.word = 4;
+    }
+
+    fn seed(&self) -> u64 {
+        u64::from(self.key[0]) | (u64::from(self.key[1]) << 32)
+    }
+
+    /// Returns the number of 32-bit words consumed from the stream.
+    pub fn word_pos(&self) -> u128 {
+        u128::from(self.block) * 4 - (4 - self.word) as u128
+    }
+
+    /// Moves to the given word offset in the stream.
+    pub fn set_word_pos(&mut self, word_pos: u128) {
+        self.seek((word_pos / 4) as u64);
+        let word = (word_pos % 4) as usize;
+        if word > 0 {
+            self.next_word();
+            self.word = word;
+        }
+    }
+
+    fn next_word(&mut self) -> u32 {
+        if self.word == 4 {
+            let counter = [
+                self.block as u32,
+                (self.block >> 32) as u32,
+                self.stream as u32,
+                (self.stream >> 32) as u32,
+            ];
+            self.buffer = philox4x32(counter, self.key);
+            self.block = self.block.wrapping_add(1);
+            self.word = 0;
+        }
+        let value = self.buffer[self.word];
+        self.word += 1;
+        value
+    }
+}
+
+impl RngCore for CounterRng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_word()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let low = self.next_word();
+        u64::from(low) | (u64::from(self.next_word()) << 32)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(4) {
+            let bytes = self.next_word().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
     }
 }
 
@@ -56,3 +332,146 @@ pub fn derive_substream_seed(master_seed: u64, substream: u64) -> u64 {
     hasher.write_u64(substream);
     hasher.finish()
 }
+
+/// Names a substream by the component drawing from it and an index within
+/// that component, e.g. `("mcmc.exchange/sweep-3", 1)`.
+///
+/// Components own disjoint label spaces, so two components can no longer
+/// collide by picking the same numeric substream index.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct StreamLabel {
+    component: String,
+    index: u64,
+}
+
+impl StreamLabel {
+    /// Creates a label for substream `index` of `component`.
+    pub fn new(component: impl Into<String>, index: u64) -> Self {
+        Self {
+            component: component.into(),
+            index,
+        }
+    }
+
+    /// Returns the component name.
+    pub fn component(&self) -> &str {
+        &self.component
+    }
+
+    /// Returns the index within the component.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+}
+
+impl fmt::Display for StreamLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}", self.component, self.index)
+    }
+}
+
+/// Derives the deterministic seed for a labelled substream.
+///
+/// Hashes `(master_seed, component length, component bytes, index)` with the
+/// same zero-keyed SipHash-1-3 as [`derive_substream_seed`]. Every integer is
+/// written little-endian with a fixed width, so the result does not depend on
+/// the platform's pointer width or endianness.
+pub fn derive_labeled_seed(master_seed: u64, label: &StreamLabel) -> u64 {
+    let mut hasher = SipHasher13::new_with_keys(0, 0);
+    hasher.write(&master_seed.to_le_bytes());
+    hasher.write(&(label.component.len() as u64).to_le_bytes());
+    hasher.write(label.component.as_bytes());
+    hasher.write(&label.index.to_le_bytes());
+    hasher.finish()
+}
+
+/// Labelled substream derived more than once from the same master seed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DuplicateStream {
+    /// Master seed of the repeated derivation.
+    pub master_seed: u64,
+    /// Label that was derived repeatedly.
+    pub label: StreamLabel,
+    /// Number of derivations of the pair.
+    pub count: u64,
+}
+
+/// Summary of the derivations recorded by a [`SeedAudit`].
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SeedAuditSummary {
+    /// Total number of derivations.
+    pub derivations: u64,
+    /// Number of distinct `(master_seed, label)` pairs.
+    pub distinct: usize,
+    /// Pairs derived more than once, ordered by master seed and label.
+    pub duplicates: Vec<DuplicateStream>,
+}
+
+impl SeedAuditSummary {
+    /// Returns true when no substream was derived twice.
+    pub fn is_clean(&self) -> bool {
+        self.duplicates.is_empty()
+    }
+}
+
+#[cfg(feature = "seed-audit")]
+pub use audit::SeedAudit;
+
+#[cfg(feature = "seed-audit")]
+mod audit {
+    use std::collections::BTreeMap;
+    use std::sync::Mutex;
+
+    use super::{derive_labeled_seed, DuplicateStream, SeedAuditSummary, StreamLabel};
+
+    /// Thread-safe record of labelled seed derivations made during a run.
+    ///
+    /// Two components sharing a stream show up as one `(master_seed, label)`
+    /// pair derived more than once.
+    #[derive(Debug, Default)]
+    pub struct SeedAudit {
+        counts: Mutex<BTreeMap<(u64, StreamLabel), u64>>,
+    }
+
+    impl SeedAudit {
+        /// Creates an empty audit.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Derives the labelled seed and records the derivation.
+        pub fn derive(&self, master_seed: u64, label: &StreamLabel) -> u64 {
+            self.record(master_seed, label);
+            derive_labeled_seed(master_seed, label)
+        }
+
+        /// Records a derivation of `label` from `master_seed`.
+        pub fn record(&self, master_seed: u64, label: &StreamLabel) {
+            let mut counts = self.counts.lock().unwrap_or_else(|err| err.into_inner());
+            *counts.entry((master_seed, label.clone())).or_insert(0) += 1;
+        }
+
+        /// Returns the pairs derived more than once.
+        pub fn duplicates(&self) -> Vec<DuplicateStream> {
+            self.summary().duplicates
+        }
+
+        /// Summarises the derivations recorded so far.
+        pub fn summary(&self) -> SeedAuditSummary {
+            let counts = self.counts.lock().unwrap_or_else(|err| err.into_inner());
+            SeedAuditSummary {
+                derivations: counts.values().sum(),
+                distinct: counts.len(),
+                duplicates: counts
+                    .iter()
+                    .filter(|(_, count)| **count > 1)
+                    .map(|((master_seed, label), count)| DuplicateStream {
+                        master_seed: *master_seed,
+                        label: label.clone(),
+                        count: *count,
+                    })
+                    .collect(),
+            }
+        }
+    }
+}
@@ -1,9 +1,43 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
+
 use serde::{Deserialize, Serialize};
 
+use crate::errors::{AsmError, ErrorCode, ErrorInfo};
 use crate::provenance::{RunProvenance, SchemaVersion};
 
+/// Format tag written by the named coupling representation.
+pub const COUPLINGS_FORMAT: u32 = 2;
+
+/// Names of the couplings every payload carries besides the Yukawa spectrum.
+pub const STANDARD_COUPLINGS: [&str; 5] = ["c_kin", "g1", "g2", "g3", "lambda_h"];
+
+/// Optional unit and scale metadata attached to a named coupling.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct CouplingUnit {
+    /// Human readable unit label (for example `GeV`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    /// Renormalisation scale the value refers to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale: Option<f64>,
+}
+
+impl CouplingUnit {
+    fn is_empty(&self) -> bool {
+        self.unit.is_none() && self.scale.is_none()
+    }
+}
+
 /// Physical coupling constants extracted from an ASM run.
+///
+/// Entries are addressable by name: `c_kin`, `g1`–`g3`, `lambda_h`,
+/// `yukawa_<i>` for the Yukawa spectrum, plus any extractor-specific names
+/// stored in [`Couplings::extra`]. Serialization emits the named
+/// representation tagged with [`COUPLINGS_FORMAT`]; the legacy positional
+/// layout is still accepted on input.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "CouplingsRepr", into = "CouplingsRepr")]
 pub struct Couplings {
     /// Schema version for the coupling payload.
     pub schema_version: SchemaVersion,
@@ -19,4 +53,320 @@ pub struct Couplings {
     pub lambda_h: f64,
     /// Optional notes and diagnostics emitted by the extractor.
     pub notes: Option<String>,
+    /// Additional named couplings outside the standard set.
+    pub extra: BTreeMap<String, f64>,
+    /// Unit and scale metadata keyed by coupling name.
+    pub units: BTreeMap<String, CouplingUnit>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    CKin,
+    Gauge(usize),
+    LambdaH,
+    Yukawa(usize),
+    Extra,
+}
+
+fn slot(name: &str) -> Slot {
+    match name {
+        "c_kin" => Slot::CKin,
+        "g1" => Slot::Gauge(0),
+        "g2" => Slot::Gauge(1),
+        "g3" => Slot::Gauge(2),
+        "lambda_h" => Slot::LambdaH,
+        _ => name
+            .strip_prefix("yukawa_")
+            .and_then(|suffix| {
+                let index = suffix.parse::<usize>().ok()?;
+                (index.to_string() == suffix).then_some(Slot::Yukawa(index))
+            })
+            .unwrap_or(Slot::Extra),
+    }
+}
+
+impl Couplings {
+    /// Returns the value stored under `name`, if present.
+    pub fn get(&self, name: &str) -> Option<f64> {
+        match slot(name) {
+            Slot::CKin => Some(self.c_kin),
+            Slot::Gauge(index) => Some(self.gauge[index]),
+            Slot::LambdaH => Some(self.lambda_h),
+            Slot::Yukawa(index) => self.yukawa.get(index).copied(),
+            Slot::Extra => self.extra.get(name).copied(),
+        }
+    }
+
+    /// Stores `value` under `name`, returning the previous value.
+    ///
+    /// `yukawa_<i>` may extend the spectrum by exactly one entry; larger
+    /// indices would leave gaps and are rejected.
+    pub fn set(&mut self, name: &str, value: f64) -> Result<Option<f64>, AsmError> {
+        match slot(name) {
+            Slot::CKin => Ok(Some(std::mem::replace(&mut self.c_kin, value))),
+            Slot::Gauge(index) => Ok(Some(std::mem::replace(&mut self.gauge[index], value))),
+            Slot::LambdaH => Ok(Some(std::mem::replace(&mut self.lambda_h, value))),
+            Slot::Yukawa(index) if index < self.yukawa.len() => {
+                Ok(Some(std::mem::replace(&mut self.yukawa[index], value)))
+            }
+            Slot::Yukawa(index) if index == self.yukawa.len() => {
+                self.yukawa.push(value);
+                Ok(None)
+            }
+            Slot::Yukawa(index) => Err(AsmError::Dictionary(
+                ErrorInfo::new(
+                    ErrorCode::UnknownCoupling,
+                    format!(
+                        "cannot set `{name}`: yukawa spectrum has {} entries",
+                        self.yukawa.len()
+                    ),
+                )
+                .with_context("name", name)
+                .with_context("index", index.to_string()),
+            )),
+            Slot::Extra if name.is_empty() => Err(AsmError::Dictionary(ErrorInfo::new(
+                ErrorCode::UnknownCoupling,
+                "coupling names must be non-empty",
+            ))),
+            Slot::Extra => Ok(self.extra.insert(name.to_string(), value)),
+        }
+    }
+
+    /// Returns every coupling name in canonical (lexicographic) order.
+    pub fn names(&self) -> BTreeSet<String> {
+        self.iter().map(|(name, _)| name).collect()
+    }
+
+    /// Iterates over `(name, value)` pairs in canonical (lexicographic) order.
+    pub fn iter(&self) -> impl Iterator<Item = (String, f64)> {
+        let mut entries: BTreeMap<String, f64> = STANDARD_COUPLINGS
+            .iter()
+            .map(|name| (name.to_string(), self.get(name).unwrap_or_default()))
+            .collect();
+        for (index, value) in self.yukawa.iter().enumerate() {
+            entries.insert(format!("yukawa_{index}"), *value);
+        }
+        for (name, value) in &self.extra {
+            entries.insert(name.clone(), *value);
+        }
+        entries.into_iter()
+    }
+
+    /// Returns the unit metadata recorded for `name`, if any.
+    pub fn unit(&self, name: &str) -> Option<&CouplingUnit> {
+        self.units.get(name)
+    }
+
+    /// Attaches unit metadata to an existing coupling.
+    pub fn set_unit(&mut self, name: &str, unit: CouplingUnit) -> Result<(), AsmError> {
+        if self.get(name).is_none() {
+            return Err(AsmError::Dictionary(
+                ErrorInfo::new(
+                    ErrorCode::UnknownCoupling,
+                    format!("cannot attach units to unknown coupling `{name}`"),
+                )
+                .with_context("name", name),
+            ));
+        }
+        if unit.is_empty() {
+            self.units.remove(name);
+        } else {
+            self.units.insert(name.to_string(), unit);
+        }
+        Ok(())
+    }
+
+    /// Returns `self - other`, aligned by name.
+    ///
+    /// Fails with [`ErrorCode::CouplingNameMismatch`] when the name sets
+    /// differ; the error context lists the names missing on either side.
+    pub fn difference(&self, other: &Couplings) -> Result<Couplings, AsmError> {
+        self.combine(other, |a, b| a - b)
+    }
+
+    /// Returns `(self - other) / self`, aligned by name.
+    ///
+    /// Entries whose reference value is numerically zero fall back to the
+    /// absolute difference.
+    pub fn relative_difference(&self, other: &Couplings) -> Result<Couplings, AsmError> {
+        self.combine(other, |a, b| {
+            if a.abs() > f64::EPSILON {
+                (a - b) / a
+            } else {
+                a - b
+            }
+        })
+    }
+
+    fn combine(
+        &self,
+        other: &Couplings,
+        op: impl Fn(f64, f64) -> f64,
+    ) -> Result<Couplings, AsmError> {
+        let left = self.names();
+        let right = other.names();
+        if left != right {
+            let missing_in_self: Vec<&str> = right.difference(&left).map(String::as_str).collect();
+            let missing_in_other: Vec<&str> = left.difference(&right).map(String::as_str).collect();
+            return Err(AsmError::Dictionary(
+                ErrorInfo::new(
+                    ErrorCode::CouplingNameMismatch,
+                    format!(
+                        "coupling names differ: missing in self [{}], missing in other [{}]",
+                        missing_in_self.join(", "),
+                        missing_in_other.join(", ")
+                    ),
+                )
+                .with_context("missing_in_self", missing_in_self.join(","))
+                .with_context("missing_in_other", missing_in_other.join(",")),
+            ));
+        }
+        let mut result = self.clone();
+        result.notes = None;
+        for (name, value) in self.iter() {
+            let rhs = other.get(&name).unwrap_or_default();
+            result.set(&name, op(value, rhs))?;
+        }
+        Ok(result)
+    }
+}
+
+/// Single entry of the named coupling representation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CouplingEntry {
+    value: f64,
+    #[serde(flatten)]
+    unit: CouplingUnit,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct NamedCouplings {
+    format: u32,
+    schema_version: SchemaVersion,
+    provenance: RunProvenance,
+    entries: BTreeMap<String, CouplingEntry>,
+    #[serde(default)]
+    notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PositionalCouplings {
+    schema_version: SchemaVersion,
+    provenance: RunProvenance,
+    c_kin: f64,
+    gauge: [f64; 3],
+    yukawa: Vec<f64>,
+    lambda_h: f64,
+    notes: Option<String>,
+}
+
+/// Versioned wire format: the named layout first, the legacy positional
+/// layout as a fallback.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum CouplingsRepr {
+    Named(NamedCouplings),
+    Positional(PositionalCouplings),
+}
+
+impl From<Couplings> for CouplingsRepr {
+    fn from(couplings: Couplings) -> Self {
+        let entries = couplings
+            .iter()
+            .map(|(name, value)| {
+                let unit = couplings.units.get(&name).cloned().unwrap_or_default();
+                (name, CouplingEntry { value, unit })
+            })
+            .collect();
+        CouplingsRepr::Named(NamedCouplings {
+            format: COUPLINGS_FORMAT,
+            schema_version: couplings.schema_version,
+            provenance: couplings.provenance,
+            entries,
+            notes: couplings.notes,
+        })
+    }
+}
+
+impl TryFrom<CouplingsRepr> for Couplings {
+    type Error = AsmError;
+
+    fn try_from(repr: CouplingsRepr) -> Result<Self, Self::Error> {
+        let named = match repr {
+            CouplingsRepr::Positional(legacy) => {
+                return Ok(Couplings {
+                    schema_version: legacy.schema_version,
+                    provenance: legacy.provenance,
+                    c_kin: legacy.c_kin,
+                    gauge: legacy.gauge,
+                    yukawa: legacy.yukawa,
+                    lambda_h: legacy.lambda_h,
+                    notes: legacy.notes,
+                    extra: BTreeMap::new(),
+                    units: BTreeMap::new(),
+                })
+            }
+            CouplingsRepr::Named(named) => named,
+        };
+        if named.format != COUPLINGS_FORMAT {
+            return Err(AsmError::Dictionary(ErrorInfo::new(
+                ErrorCode::CouplingFormat,
+                format!("unsupported couplings format {}", named.format),
+            )));
+        }
+        let missing: Vec<&str> = STANDARD_COUPLINGS
+            .iter()
+            .copied()
+            .filter(|name| !named.entries.contains_key(*name))
+            .collect();
+        if !missing.is_empty() {
+            return Err(AsmError::Dictionary(
+                ErrorInfo::new(
+                    ErrorCode::CouplingFormat,
+                    format!(
+                        "couplings missing required entries [{}]",
+                        missing.join(", ")
+                    ),
+                )
+                .with_context("missing", missing.join(",")),
+            ));
+        }
+        let mut couplings = Couplings {
+            schema_version: named.schema_version,
+            provenance: named.provenance,
+            c_kin: 0.0,
+            gauge: [0.0; 3],
+            yukawa: Vec::new(),
+            lambda_h: 0.0,
+            notes: named.notes,
+            extra: BTreeMap::new(),
+            units: BTreeMap::new(),
+        };
+        let mut yukawa = BTreeMap::new();
+        for (name, entry) in &named.entries {
+            match slot(name) {
+                Slot::Yukawa(index) => {
+                    yukawa.insert(index, entry.value);
+                }
+                _ => {
+                    couplings.set(name, entry.value)?;
+                }
+            }
+        }
+        for (expected, (index, value)) in yukawa.into_iter().enumerate() {
+            if index != expected {
+                return Err(AsmError::Dictionary(ErrorInfo::new(
+                    ErrorCode::CouplingFormat,
+                    format!("yukawa spectrum has a gap before `yukawa_{index}`"),
+                )));
+            }
+            couplings.yukawa.push(value);
+        }
+        for (name, entry) in named.entries {
+            if !entry.unit.is_empty() {
+                couplings.units.insert(name, entry.unit);
+            }
+        }
+        Ok(couplings)
+    }
 }
@@ -0,0 +1,58 @@
+use asm_core::{build_fingerprint, compare_builds};
+
+#[test]
+fn fingerprint_is_stable_within_a_process() {
+    let first = build_fingerprint();
+    let second = build_fingerprint();
+    assert_eq!(first, second);
+    assert_eq!(
+        serde_json::to_string(&first).unwrap(),
+        serde_json::to_string(&second).unwrap()
+    );
+    assert_eq!(
+        first.crates.get("asm-core").map(String::as_str),
+        Some(env!("CARGO_PKG_VERSION"))
+    );
+    assert!(first.crates.keys().all(|name| name.starts_with("asm-")));
+    assert!(!first.rustc.is_empty());
+    assert!(!first.target.is_empty());
+}
+
+#[test]
+fn diff_names_changed_parts() {
+    let base = build_fingerprint();
+    assert!(base.diff(&base).is_empty());
+
+    let mut other = base.clone().with_feature("asm-test/extra");
+    other.rustc = "rustc 0.0.0".into();
+    other.crates.insert("asm-core".into(), "9.9.9".into());
+    other.crates.insert("asm-new".into(), "0.1.0".into());
+    let fields: Vec<_> = base
+        .diff(&other)
+        .into_iter()
+        .map(|change| change.field)
+        .collect();
+    assert_eq!(
+        fields,
+        ["rustc", "features", "crates.asm-core", "crates.asm-new"]
+    );
+
+    let mismatches = compare_builds([("mcmc", &base), ("spectrum", &base), ("gauge", &other)]);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].stage, "gauge");
+    assert_eq!(mismatches[0].reference, "mcmc");
+    assert_eq!(mismatches[0].changes.len(), 4);
+    assert!(compare_builds([]).is_empty());
+}
+
+#[test]
+fn added_feature_is_one_features_change() {
+    let base = build_fingerprint()
+        .with_feature("asm-test/b")
+        .with_feature("asm-test/c");
+    let other = base.clone().with_feature("asm-test/a");
+    let changes = base.diff(&other);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].field, "features");
+    assert!(changes[0].new.starts_with("asm-test/a,"));
+}
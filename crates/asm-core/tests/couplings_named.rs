@@ -0,0 +1,82 @@
+use asm_core::errors::ErrorCode;
+use asm_core::{CouplingUnit, Couplings, RunProvenance, SchemaVersion};
+
+fn sample(yukawa: Vec<f64>) -> Couplings {
+    Couplings {
+        schema_version: SchemaVersion::new(1, 0, 0),
+        provenance: RunProvenance::default(),
+        c_kin: 2.0,
+        gauge: [0.5, 0.6, 0.7],
+        yukawa,
+        lambda_h: 0.125,
+        notes: None,
+        extra: Default::default(),
+        units: Default::default(),
+    }
+}
+
+#[test]
+fn named_access_and_canonical_iteration() {
+    let mut couplings = sample(vec![0.1, 0.2]);
+    assert_eq!(couplings.get("g3"), Some(0.7));
+    assert_eq!(couplings.get("yukawa_2"), None);
+
+    assert_eq!(couplings.set("yukawa_2", 0.3).unwrap(), None);
+    assert_eq!(couplings.set("theta_qcd", 1e-10).unwrap(), None);
+    assert_eq!(couplings.set("c_kin", 3.0).unwrap(), Some(2.0));
+    let err = couplings.set("yukawa_9", 1.0).unwrap_err();
+    assert!(err.is(ErrorCode::UnknownCoupling));
+
+    let names: Vec<String> = couplings.iter().map(|(name, _)| name).collect();
+    let mut sorted = names.clone();
+    sorted.sort();
+    assert_eq!(names, sorted);
+    assert_eq!(names.len(), 9);
+
+    couplings
+        .set_unit(
+            "c_kin",
+            CouplingUnit {
+                unit: Some("GeV".into()),
+                scale: Some(91.1876),
+            },
+        )
+        .unwrap();
+    assert!(couplings
+        .set_unit("missing", CouplingUnit::default())
+        .is_err());
+    let json = serde_json::to_string(&couplings).unwrap();
+    let decoded: Couplings = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, couplings);
+    assert_eq!(decoded.unit("c_kin").unwrap().unit.as_deref(), Some("GeV"));
+}
+
+#[test]
+fn difference_aligns_by_name() {
+    let a = sample(vec![0.1, 0.2]);
+    let mut b = sample(vec![0.1, 0.1]);
+    b.c_kin = 1.0;
+    let abs = a.difference(&b).unwrap();
+    assert_eq!(abs.get("c_kin"), Some(1.0));
+    assert!((abs.get("yukawa_1").unwrap() - 0.1).abs() < 1e-12);
+    let rel = a.relative_difference(&b).unwrap();
+    assert_eq!(rel.get("c_kin"), Some(0.5));
+    assert_eq!(rel.get("g1"), Some(0.0));
+}
+
+#[test]
+fn disjoint_names_report_missing_entries() {
+    let mut a = sample(vec![0.1, 0.2, 0.3]);
+    a.set("theta_qcd", 0.0).unwrap();
+    let mut b = sample(vec![0.1]);
+    b.set("m_nu", 0.05).unwrap();
+
+    let err = a.difference(&b).unwrap_err();
+    assert!(err.is(ErrorCode::CouplingNameMismatch));
+    assert_eq!(err.context_value("missing_in_self"), Some("m_nu"));
+    assert_eq!(
+        err.context_value("missing_in_other"),
+        Some("theta_qcd,yukawa_1,yukawa_2")
+    );
+    assert!(a.relative_difference(&b).is_err());
+}
@@ -1,4 +1,7 @@
-use asm_core::errors::{AsmError, ErrorInfo};
+use std::error::Error;
+use std::fmt;
+
+use asm_core::errors::{AsmError, ErrorCategory, ErrorCode, ErrorInfo};
 
 fn sample_info(code: &str, message: &str) -> ErrorInfo {
     ErrorInfo::new(code, message)
@@ -43,3 +46,100 @@ fn serde_error_surface() {
     let err = AsmError::Serde(sample_info("S001", "schema mismatch"));
     assert_eq!(err.info().code, "S001");
 }
+
+#[test]
+fn error_codes_round_trip_through_strings() {
+    for code in ErrorCode::ALL {
+        let parsed: ErrorCode = code.as_str().parse().expect("registered code parses");
+        assert_eq!(parsed, *code);
+        let info = ErrorInfo::new(*code, "example");
+        assert_eq!(info.code_enum(), Some(*code));
+        assert!(AsmError::Graph(info).is(*code));
+    }
+}
+
+#[test]
+fn legacy_spellings_parse_to_merged_codes() {
+    for (legacy, code) in [
+        ("serialize-json", ErrorCode::JsonSerialize),
+        ("json_serialize", ErrorCode::JsonSerialize),
+        ("deserialize-json", ErrorCode::JsonDeserialize),
+        ("json_deserialize", ErrorCode::JsonDeserialize),
+        ("checkpoint-empty", ErrorCode::EmptyCheckpoint),
+    ] {
+        assert_eq!(legacy.parse::<ErrorCode>(), Ok(code));
+        assert_ne!(code.as_str(), legacy);
+    }
+}
+
+#[test]
+fn unknown_codes_remain_strings() {
+    let err = AsmError::Serde(sample_info("plugin-specific", "extension failure"));
+    assert_eq!(err.info().code_enum(), None);
+    assert!("plugin-specific".parse::<ErrorCode>().is_err());
+    assert_eq!(err.context_value("reason"), Some("example"));
+    assert_eq!(err.context_value("missing"), None);
+}
+
+#[test]
+fn accessors_expose_category_code_and_context() {
+    let cases = [
+        (
+            AsmError::Graph(sample_info("G001", "m")),
+            ErrorCategory::Graph,
+        ),
+        (
+            AsmError::Code(sample_info("C001", "m")),
+            ErrorCategory::Code,
+        ),
+        (AsmError::RG(sample_info("R001", "m")), ErrorCategory::RG),
+        (
+            AsmError::Dictionary(sample_info("D001", "m")),
+            ErrorCategory::Dictionary,
+        ),
+        (AsmError::Rng(sample_info("RN001", "m")), ErrorCategory::Rng),
+        (
+            AsmError::Serde(sample_info("S001", "m")),
+            ErrorCategory::Serde,
+        ),
+    ];
+    for (err, category) in cases {
+        assert_eq!(err.category(), category);
+        assert!(err.to_string().starts_with(&format!("{category} error: ")));
+        assert_eq!(err.code(), err.info().code);
+        let keys: Vec<&str> = err.context().keys().map(String::as_str).collect();
+        assert_eq!(keys, ["id", "reason"]);
+    }
+}
+
+#[derive(Debug)]
+struct Wrapped(AsmError);
+
+impl fmt::Display for Wrapped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "command failed")
+    }
+}
+
+impl Error for Wrapped {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[test]
+fn find_in_recovers_boxed_and_wrapped_errors() {
+    let inner = AsmError::Code(
+        ErrorInfo::new(ErrorCode::StateLengthMismatch, "bad state").with_context("state_len", "3"),
+    );
+    let boxed: Box<dyn Error> = Box::new(inner.clone());
+    let found = AsmError::find_in(boxed.as_ref()).unwrap();
+    assert_eq!(found.category(), ErrorCategory::Code);
+    assert!(found.is(ErrorCode::StateLengthMismatch));
+
+    let wrapped: Box<dyn Error> = Box::new(Wrapped(inner.clone()));
+    assert_eq!(AsmError::find_in(wrapped.as_ref()), Some(&inner));
+
+    let other: Box<dyn Error> = "plain message".into();
+    assert_eq!(AsmError::find_in(other.as_ref()), None);
+}
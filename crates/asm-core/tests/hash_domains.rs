@@ -0,0 +1,48 @@
+use asm_core::{
+    domain_hash, ensure_comparable, ensure_hash_domain, hash_with_format, parse_hash, HashDomain,
+    HASH_FORMAT, LEGACY_HASH_FORMAT,
+};
+
+#[test]
+fn domain_hashes_carry_a_parseable_prefix() {
+    for &domain in HashDomain::ALL {
+        let hash = domain_hash(domain, b"payload");
+        assert!(hash.starts_with(&format!("{}1-", domain.tag())));
+        let (parsed, hex) = parse_hash(&hash);
+        assert_eq!(parsed, Some(domain));
+        assert_eq!(hex.len(), 64);
+        assert_eq!(HashDomain::from_tag(domain.tag()), Some(domain));
+    }
+    let spec = domain_hash(HashDomain::Spectrum, b"payload");
+    let gauge = domain_hash(HashDomain::Gauge, b"payload");
+    assert_ne!(parse_hash(&spec).1, parse_hash(&gauge).1);
+}
+
+#[test]
+fn legacy_hashes_parse_without_a_domain() {
+    let legacy = hash_with_format(LEGACY_HASH_FORMAT, HashDomain::Spectrum, b"payload");
+    assert_eq!(parse_hash(&legacy), (None, legacy.as_str()));
+    assert_eq!(parse_hash("run-seed0"), (None, "run-seed0"));
+    assert_eq!(
+        hash_with_format(HASH_FORMAT, HashDomain::Spectrum, b"payload"),
+        domain_hash(HashDomain::Spectrum, b"payload")
+    );
+}
+
+#[test]
+fn cross_domain_comparisons_are_rejected() {
+    let spec = domain_hash(HashDomain::Spectrum, b"payload");
+    let gauge = domain_hash(HashDomain::Gauge, b"payload");
+    let legacy = hash_with_format(LEGACY_HASH_FORMAT, HashDomain::Gauge, b"payload");
+
+    assert!(ensure_hash_domain(HashDomain::Spectrum, &spec).is_ok());
+    assert!(ensure_hash_domain(HashDomain::Spectrum, &legacy).is_ok());
+    let err = ensure_hash_domain(HashDomain::Spectrum, &gauge).unwrap_err();
+    assert_eq!(err.info().code, "hash-domain-mismatch");
+    assert_eq!(err.context_value("expected"), Some("spec"));
+    assert_eq!(err.context_value("actual"), Some("gauge"));
+
+    assert!(ensure_comparable(&spec, &legacy).is_ok());
+    assert!(ensure_comparable(&spec, &spec).is_ok());
+    assert!(ensure_comparable(&spec, &gauge).is_err());
+}
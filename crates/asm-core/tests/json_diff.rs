@@ -0,0 +1,93 @@
+use asm_core::json_diff::{
+    canonical_json_diff, json_value_diff, json_value_diff_with_sets, JsonDelta, JsonDeltaKind,
+};
+use serde_json::json;
+
+#[test]
+fn identical_artefacts_have_no_deltas() {
+    let bytes = br#"{"a":[1,2,{"b":null}],"c":"x"}"#;
+    assert!(canonical_json_diff(bytes, bytes).unwrap().is_empty());
+}
+
+#[test]
+fn reports_added_removed_and_changed_paths() {
+    let a = json!({"kept": 1, "gone": true, "nested": {"x": 1.5, "list": [1, 2, 3]}});
+    let b = json!({"kept": 1, "new": "yes", "nested": {"x": 2.5, "list": [1, 4]}});
+    let deltas = json_value_diff(&a, &b);
+    let summary: Vec<(&str, JsonDeltaKind)> = deltas
+        .iter()
+        .map(|delta| (delta.path.as_str(), delta.kind))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("/gone", JsonDeltaKind::Removed),
+            ("/nested/list/1", JsonDeltaKind::Changed),
+            ("/nested/list/2", JsonDeltaKind::Removed),
+            ("/nested/x", JsonDeltaKind::Changed),
+            ("/new", JsonDeltaKind::Added),
+        ]
+    );
+    assert_eq!(deltas[1].old, Some(json!(2)));
+    assert_eq!(deltas[1].new, Some(json!(4)));
+    assert_eq!(deltas[4].old, None);
+    assert_eq!(deltas[3].to_string(), "~ /nested/x: 1.5 -> 2.5");
+    assert_eq!(deltas[0].to_string(), "- /gone: true");
+}
+
+#[test]
+fn type_changes_and_root_scalars_are_single_deltas() {
+    let deltas = json_value_diff(&json!({"k": [1]}), &json!({"k": {"0": 1}}));
+    assert_eq!(deltas.len(), 1);
+    assert_eq!(deltas[0].path, "/k");
+    assert_eq!(deltas[0].kind, JsonDeltaKind::Changed);
+
+    let root = json_value_diff(&json!(1), &json!("1"));
+    assert_eq!(root[0].path, "");
+}
+
+#[test]
+fn pointer_segments_are_escaped() {
+    let deltas = json_value_diff(&json!({}), &json!({"a/b": {"~c": 0}}));
+    assert_eq!(deltas[0].path, "/a~1b");
+    let deltas = json_value_diff(&json!({"a/b": {"~c": 0}}), &json!({"a/b": {"~c": 1}}));
+    assert_eq!(deltas[0].path, "/a~1b/~0c");
+}
+
+#[test]
+fn set_paths_compare_members_not_indices() {
+    let a = json!({"features": ["b", "c"], "list": ["b", "c"]});
+    let b = json!({"features": ["a", "b", "c"], "list": ["a", "b", "c"]});
+    let paths: Vec<_> = json_value_diff_with_sets(&a, &b, &["/features"])
+        .into_iter()
+        .map(|delta| (delta.path, delta.kind))
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            ("/features".to_string(), JsonDeltaKind::Changed),
+            ("/list/0".to_string(), JsonDeltaKind::Changed),
+            ("/list/1".to_string(), JsonDeltaKind::Changed),
+            ("/list/2".to_string(), JsonDeltaKind::Added),
+        ]
+    );
+
+    let reordered = json!({"features": ["c", "b"], "list": ["b", "c"]});
+    assert!(json_value_diff_with_sets(&a, &reordered, &["/features"]).is_empty());
+}
+
+#[test]
+fn deltas_round_trip_and_bad_input_names_the_side() {
+    let deltas = canonical_json_diff(br#"{"v":1}"#, br#"{"v":2,"w":[]}"#).unwrap();
+    let json = serde_json::to_string(&deltas).unwrap();
+    assert!(!json.contains("\"old\":null"));
+    let decoded: Vec<JsonDelta> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, deltas);
+
+    let err = canonical_json_diff(b"{}", b"{not json").unwrap_err();
+    assert_eq!(err.info().code, "json-diff-parse");
+    assert_eq!(
+        err.info().context.get("side").map(String::as_str),
+        Some("b")
+    );
+}
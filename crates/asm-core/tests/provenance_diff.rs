@@ -0,0 +1,78 @@
+use std::collections::BTreeMap;
+
+use asm_core::{ProvenanceDiff, RunProvenance, ToolVersionChange};
+
+fn provenance(seed: u64, tools: &[(&str, &str)]) -> RunProvenance {
+    RunProvenance {
+        input_hash: "input".into(),
+        graph_hash: "graph".into(),
+        code_hash: "code".into(),
+        seed,
+        created_at: "1970-01-01T00:00:00Z".into(),
+        tool_versions: tools
+            .iter()
+            .map(|(tool, version)| (tool.to_string(), version.to_string()))
+            .collect::<BTreeMap<_, _>>(),
+    }
+}
+
+#[test]
+fn identical_records_have_an_empty_diff() {
+    let a = provenance(7, &[("asm-sim", "0.1.0")]);
+    let diff = a.diff(&a.clone());
+    assert!(diff.is_empty());
+    assert_eq!(diff, ProvenanceDiff::default());
+    assert_eq!(diff.to_string(), "");
+}
+
+#[test]
+fn diff_reports_fields_and_tool_versions() {
+    let old = provenance(
+        7,
+        &[("asm-sim", "0.1.0"), ("rustc", "1.80"), ("legacy", "1")],
+    );
+    let mut new = provenance(
+        8,
+        &[("asm-sim", "0.2.0"), ("rustc", "1.80"), ("python", "3.12")],
+    );
+    new.input_hash = "input-2".into();
+
+    let diff = old.diff(&new);
+    let fields: Vec<_> = diff.fields.iter().map(|c| c.field.as_str()).collect();
+    assert_eq!(fields, ["input_hash", "seed"]);
+    let seed = diff.field("seed").unwrap();
+    assert_eq!((seed.old.as_str(), seed.new.as_str()), ("7", "8"));
+    assert!(diff.field("graph_hash").is_none());
+
+    assert_eq!(
+        diff.tool_versions,
+        [
+            ToolVersionChange {
+                tool: "asm-sim".into(),
+                old: Some("0.1.0".into()),
+                new: Some("0.2.0".into()),
+            },
+            ToolVersionChange {
+                tool: "legacy".into(),
+                old: Some("1".into()),
+                new: None,
+            },
+            ToolVersionChange {
+                tool: "python".into(),
+                old: None,
+                new: Some("3.12".into()),
+            },
+        ]
+    );
+    assert!(diff.tool("rustc").is_none());
+    assert_eq!(
+        diff.to_string(),
+        "input_hash: input -> input-2\nseed: 7 -> 8\n\
+         tool_versions.asm-sim: 0.1.0 -> 0.2.0\ntool_versions.legacy: 1 -> -\n\
+         tool_versions.python: - -> 3.12"
+    );
+
+    let reverse = new.diff(&old);
+    assert_eq!(reverse.field("seed").unwrap().old, "8");
+    assert_eq!(reverse.tool("python").unwrap().new, None);
+}
@@ -11,3 +11,78 @@ fn rng_emits_reproducible_sequence() {
 
     assert_eq!(seq_a, seq_b);
 }
+
+#[test]
+fn labeled_seeds_are_platform_stable() {
+    use asm_core::rng::{derive_labeled_seed, StreamLabel};
+
+    // Pinned values: any change here breaks reproducibility of labelled runs.
+    let pinned = [
+        (
+            0,
+            StreamLabel::new("mcmc.replica", 0),
+            0xd296_f079_717b_95c3,
+        ),
+        (
+            4242,
+            StreamLabel::new("mcmc.move/replica-1/sweep-7", 3),
+            0x0856_2dce_bd4c_9a01,
+        ),
+        (
+            u64::MAX,
+            StreamLabel::new("", u64::MAX),
+            0x28af_ea98_d062_52da,
+        ),
+    ];
+    for (master, label, expected) in pinned {
+        assert_eq!(derive_labeled_seed(master, &label), expected, "{label}");
+    }
+}
+
+#[test]
+fn labels_separate_components() {
+    use asm_core::rng::{derive_labeled_seed, derive_substream_seed, StreamLabel};
+
+    let graph = derive_labeled_seed(7, &StreamLabel::new("graph", 3));
+    let worm = derive_labeled_seed(7, &StreamLabel::new("worm", 3));
+    assert_ne!(graph, worm);
+    assert_ne!(graph, derive_substream_seed(7, 3));
+    // The length prefix keeps component/index boundaries unambiguous.
+    assert_ne!(
+        derive_labeled_seed(7, &StreamLabel::new("ab", 1)),
+        derive_labeled_seed(7, &StreamLabel::new("a", 1))
+    );
+
+    let label = StreamLabel::new("graph", 3);
+    let mut from_label = RngHandle::from_label(7, &label);
+    let mut from_seed = RngHandle::from_seed(graph);
+    assert_eq!(from_label.next_u64(), from_seed.next_u64());
+    assert_eq!(label.to_string(), "graph#3");
+}
+
+#[test]
+fn forks_are_reproducible_and_advance_parent_once() {
+    use asm_core::rng::{derive_labeled_seed, StreamLabel};
+
+    let mut parent = RngHandle::from_seed(99);
+    let mut graph = parent.fork("graph");
+    let mut worm = parent.fork("worm");
+    let after = parent.next_u64();
+
+    let mut replay = RngHandle::from_seed(99);
+    let first = replay.next_u64();
+    let second = replay.next_u64();
+    assert_eq!(replay.next_u64(), after);
+
+    let mut expected =
+        RngHandle::from_seed(derive_labeled_seed(first, &StreamLabel::new("graph", 0)));
+    assert_eq!(graph.next_u64(), expected.next_u64());
+    let mut expected =
+        RngHandle::from_seed(derive_labeled_seed(second, &StreamLabel::new("worm", 0)));
+    assert_eq!(worm.next_u64(), expected.next_u64());
+
+    // Same parent state, different labels: independent children.
+    let mut a = RngHandle::from_seed(5);
+    let mut b = a.clone();
+    assert_ne!(a.fork("x").next_u64(), b.fork("y").next_u64());
+}
@@ -0,0 +1,27 @@
+use asm_core::{AsmError, ErrorCode, SchemaVersion};
+
+#[test]
+fn compatibility_follows_major_and_minor() {
+    let required = SchemaVersion::new(1, 2, 0);
+    assert!(SchemaVersion::new(1, 2, 0).is_compatible_with(required));
+    assert!(SchemaVersion::new(1, 2, 7).is_compatible_with(required));
+    assert!(SchemaVersion::new(1, 5, 0).is_compatible_with(required));
+    assert!(SchemaVersion::new(1, 2, 0).is_compatible_with(SchemaVersion::new(1, 2, 9)));
+    assert!(!SchemaVersion::new(1, 1, 9).is_compatible_with(required));
+    assert!(!SchemaVersion::new(2, 2, 0).is_compatible_with(required));
+    assert!(!SchemaVersion::new(0, 9, 0).is_compatible_with(required));
+}
+
+#[test]
+fn require_compatible_reports_both_versions() {
+    let found = SchemaVersion::new(2, 0, 1);
+    let required = SchemaVersion::new(1, 0, 0);
+    let err = found.require_compatible(required).unwrap_err();
+    assert!(matches!(err, AsmError::Serde(_)));
+    assert!(err.is(ErrorCode::SchemaIncompatible));
+    assert_eq!(err.context_value("found"), Some("2.0.1"));
+    assert_eq!(err.context_value("required"), Some("1.0.0"));
+    assert!(SchemaVersion::new(1, 3, 0)
+        .require_compatible(required)
+        .is_ok());
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "seed-audit")]
+
+use std::sync::Arc;
+use std::thread;
+
+use asm_core::rng::{derive_labeled_seed, DuplicateStream, SeedAudit, StreamLabel};
+
+#[test]
+fn duplicate_labels_are_reported() {
+    let audit = Arc::new(SeedAudit::new());
+    let handles: Vec<_> = ["graph", "worm"]
+        .into_iter()
+        .map(|component| {
+            let audit = Arc::clone(&audit);
+            thread::spawn(move || {
+                for index in 0..4 {
+                    audit.derive(11, &StreamLabel::new(component, index));
+                }
+                // Both components also draw from a shared stream by mistake.
+                audit.derive(11, &StreamLabel::new("shared", 0))
+            })
+        })
+        .collect();
+    let seeds: Vec<u64> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(seeds[0], seeds[1]);
+
+    let summary = audit.summary();
+    assert_eq!(summary.derivations, 10);
+    assert_eq!(summary.distinct, 9);
+    assert!(!summary.is_clean());
+    assert_eq!(
+        summary.duplicates,
+        vec![DuplicateStream {
+            master_seed: 11,
+            label: StreamLabel::new("shared", 0),
+            count: 2,
+        }]
+    );
+    assert_eq!(audit.duplicates(), summary.duplicates);
+}
+
+#[test]
+fn audit_derivation_matches_plain_derivation() {
+    let audit = SeedAudit::new();
+    let label = StreamLabel::new("mcmc.replica", 2);
+    assert_eq!(audit.derive(5, &label), derive_labeled_seed(5, &label));
+    // The same label under another master seed is a different stream.
+    audit.derive(6, &label);
+    assert!(audit.summary().is_clean());
+}
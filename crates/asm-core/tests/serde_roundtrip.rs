@@ -19,6 +19,8 @@ fn couplings_round_trip_json() {
         yukawa: vec![0.1, 0.2, 0.3],
         lambda_h: 0.5,
         notes: Some("roundtrip".into()),
+        extra: Default::default(),
+        units: Default::default(),
     };
 
     let json = serde_json::to_string_pretty(&couplings).expect("serialize");
@@ -27,3 +29,28 @@ fn couplings_round_trip_json() {
     assert_eq!(decoded, couplings);
     assert_eq!(decoded.provenance, provenance);
 }
+
+#[test]
+fn couplings_accept_legacy_positional_json() {
+    let legacy = r#"{
+        "schema_version": {"major": 1, "minor": 0, "patch": 0},
+        "provenance": {
+            "input_hash": "", "graph_hash": "", "code_hash": "", "seed": 7,
+            "created_at": "", "tool_versions": {}
+        },
+        "c_kin": 1.5,
+        "gauge": [0.1, 0.2, 0.3],
+        "yukawa": [0.01, 0.02],
+        "lambda_h": 0.25,
+        "notes": null
+    }"#;
+    let decoded: Couplings = serde_json::from_str(legacy).expect("legacy deserialize");
+    assert_eq!(decoded.get("g2"), Some(0.2));
+    assert_eq!(decoded.get("yukawa_1"), Some(0.02));
+
+    let json = serde_json::to_value(&decoded).expect("serialize");
+    assert_eq!(json["format"], asm_core::COUPLINGS_FORMAT);
+    assert_eq!(json["entries"]["c_kin"]["value"], 1.5);
+    let again: Couplings = serde_json::from_value(json).expect("named deserialize");
+    assert_eq!(again, decoded);
+}
@@ -141,6 +141,8 @@ impl OperatorDictionary for DummyDictionary {
             yukawa: vec![],
             lambda_h: 0.0,
             notes: Some("deterministic".into()),
+            extra: Default::default(),
+            units: Default::default(),
         };
         Ok(OperatorDictionaryResult {
             couplings,
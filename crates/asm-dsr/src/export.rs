@@ -1,16 +1,228 @@
-use std::fs;
-use std::path::Path;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Component, Path};
 
 use asm_core::errors::{AsmError, ErrorInfo};
 use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, DateTime, ZipWriter};
 
-use crate::query::RegistryQuery;
+use crate::query::{MetricAggregate, RegistryQuery};
 use crate::schema::load_submissions;
 use crate::serde::to_canonical_json_bytes;
 
-pub fn export_json(conn: &Connection, out_path: &Path) -> Result<(), AsmError> {
-    let query = RegistryQuery::load(conn)?;
-    let bytes = to_canonical_json_bytes(&query)?;
+/// Name of the submission manifest stored at the root of a bundle.
+pub const BUNDLE_MANIFEST: &str = "bundle_manifest.json";
+
+/// Submission metadata recorded in a bundle manifest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubmissionMeta {
+    pub submitter: String,
+    pub toolchain: String,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub metrics: Vec<BundleMetric>,
+    /// Package files that are not recognised run artefacts, flagged as extra.
+    #[serde(default)]
+    pub include_extra: bool,
+}
+
+/// Scalar metric attached to a submission.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleMetric {
+    pub name: String,
+    pub value: f64,
+    #[serde(default)]
+    pub unit: Option<String>,
+}
+
+/// Artefact entry listed in [`BundleManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleArtifact {
+    pub kind: String,
+    pub path: String,
+    pub sha256: String,
+    #[serde(default)]
+    pub analysis_hash: Option<String>,
+    pub size: u64,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub extra: bool,
+}
+
+/// Canonical manifest written into a submission bundle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub submitter: String,
+    pub toolchain: String,
+    #[serde(default)]
+    pub notes: Option<String>,
+    pub artifacts: Vec<BundleArtifact>,
+    #[serde(default)]
+    pub metrics: Vec<BundleMetric>,
+}
+
+fn bundle_error(code: &str, path: &Path, err: impl ToString) -> AsmError {
+    AsmError::Serde(
+        ErrorInfo::new(code, err.to_string()).with_context("path", path.display().to_string()),
+    )
+}
+
+/// Classifies a run-relative path, returning `None` for unrecognised files.
+fn artifact_kind(relative: &Path) -> Option<&'static str> {
+    if relative
+        .components()
+        .any(|component| component == Component::Normal("end_state".as_ref()))
+    {
+        return Some("end_state");
+    }
+    match relative.file_name()?.to_str()? {
+        "manifest.json" => Some("run_manifest"),
+        "spectrum_report.json" => Some("spectrum_report"),
+        "gauge_report.json" => Some("gauge_report"),
+        "interaction_report.json" => Some("interaction_report"),
+        "analysis_report.json" => Some("analysis_report"),
+        _ => None,
+    }
+}
+
+/// Extracts an analysis hash embedded either at the top level or under `hashes`.
+fn embedded_analysis_hash(bytes: &[u8]) -> Option<String> {
+    let value: Value = serde_json::from_slice(bytes).ok()?;
+    value
+        .get("analysis_hash")
+        .or_else(|| value.get("hashes")?.get("analysis_hash"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// Packages the recognised artefacts of `run_dir` into a submission bundle at `out`.
+///
+/// Entries are written in path order with fixed timestamps and permissions, so
+/// the same run directory and metadata always produce a byte-identical bundle.
+pub fn build_bundle(
+    run_dir: &Path,
+    meta: &SubmissionMeta,
+    out: &Path,
+) -> Result<BundleManifest, AsmError> {
+    let mut artifacts = Vec::new();
+    for entry in WalkDir::new(run_dir).sort_by_file_name() {
+        let entry = entry.map_err(|err| bundle_error("asm_dsr.bundle_walk", run_dir, err))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(run_dir)
+            .map_err(|err| bundle_error("asm_dsr.bundle_walk", entry.path(), err))?;
+        let (kind, extra) = match artifact_kind(relative) {
+            Some(kind) => (kind, false),
+            None if meta.include_extra => ("extra", true),
+            None => continue,
+        };
+        let bytes = fs::read(entry.path())
+            .map_err(|err| bundle_error("asm_dsr.bundle_read", entry.path(), err))?;
+        let path = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        artifacts.push(BundleArtifact {
+            kind: kind.to_string(),
+            sha256: hex::encode(Sha256::digest(&bytes)),
+            analysis_hash: embedded_analysis_hash(&bytes),
+            size: bytes.len() as u64,
+            path,
+            extra,
+        });
+    }
+    artifacts.sort_by(|a, b| a.path.cmp(&b.path));
+    let manifest = BundleManifest {
+        submitter: meta.submitter.clone(),
+        toolchain: meta.toolchain.clone(),
+        notes: meta.notes.clone(),
+        artifacts,
+        metrics: meta.metrics.clone(),
+    };
+
+    if let Some(parent) = out.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| bundle_error("asm_dsr.bundle_write", parent, err))?;
+    }
+    let file = File::create(out).map_err(|err| bundle_error("asm_dsr.bundle_write", out, err))?;
+    let mut writer = ZipWriter::new(file);
+    let options = FileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(DateTime::default())
+        .unix_permissions(0o644);
+    let write_entry = |writer: &mut ZipWriter<File>, name: &str, bytes: &[u8]| {
+        writer
+            .start_file(name, options)
+            .map_err(|err| bundle_error("asm_dsr.bundle_write", out, err))?;
+        writer
+            .write_all(bytes)
+            .map_err(|err| bundle_error("asm_dsr.bundle_write", out, err))
+    };
+    write_entry(
+        &mut writer,
+        BUNDLE_MANIFEST,
+        &to_canonical_json_bytes(&manifest)?,
+    )?;
+    for artifact in &manifest.artifacts {
+        let source = run_dir.join(&artifact.path);
+        let bytes =
+            fs::read(&source).map_err(|err| bundle_error("asm_dsr.bundle_read", &source, err))?;
+        write_entry(&mut writer, &artifact.path, &bytes)?;
+    }
+    writer
+        .finish()
+        .map_err(|err| bundle_error("asm_dsr.bundle_write", out, err))?;
+    Ok(manifest)
+}
+
+/// Data written by [`export_json`] and [`export_csv`].
+#[derive(Clone, Copy)]
+pub enum ExportSource<'a> {
+    /// Every registry record (JSON) or the submissions table (CSV).
+    Registry(&'a Connection),
+    /// Output of [`crate::query::aggregate_metrics`].
+    Aggregates(&'a [MetricAggregate]),
+}
+
+impl<'a> From<&'a Connection> for ExportSource<'a> {
+    fn from(conn: &'a Connection) -> Self {
+        ExportSource::Registry(conn)
+    }
+}
+
+impl<'a> From<&'a [MetricAggregate]> for ExportSource<'a> {
+    fn from(aggregates: &'a [MetricAggregate]) -> Self {
+        ExportSource::Aggregates(aggregates)
+    }
+}
+
+impl<'a> From<&'a Vec<MetricAggregate>> for ExportSource<'a> {
+    fn from(aggregates: &'a Vec<MetricAggregate>) -> Self {
+        ExportSource::Aggregates(aggregates)
+    }
+}
+
+fn export_error(err: impl ToString) -> AsmError {
+    AsmError::Serde(ErrorInfo::new("asm_dsr.export", err.to_string()))
+}
+
+pub fn export_json<'a>(
+    source: impl Into<ExportSource<'a>>,
+    out_path: &Path,
+) -> Result<(), AsmError> {
+    let bytes = match source.into() {
+        ExportSource::Registry(conn) => to_canonical_json_bytes(&RegistryQuery::load(conn)?)?,
+        ExportSource::Aggregates(aggregates) => to_canonical_json_bytes(&aggregates)?,
+    };
     fs::write(out_path, bytes).map_err(|err| {
         AsmError::Serde(
             ErrorInfo::new("asm_dsr.export", err.to_string())
@@ -19,23 +231,192 @@ pub fn export_json(conn: &Connection, out_path: &Path) -> Result<(), AsmError> {
     })
 }
 
-pub fn export_csv(conn: &Connection, out_path: &Path) -> Result<(), AsmError> {
+/// Writes the submissions table without a header, or aggregates under a
+/// `group,metric,count,mean,min,max,stddev` header.
+pub fn export_csv<'a>(
+    source: impl Into<ExportSource<'a>>,
+    out_path: &Path,
+) -> Result<(), AsmError> {
     let mut wtr = csv::Writer::from_path(out_path).map_err(|err| {
         AsmError::Serde(
             ErrorInfo::new("asm_dsr.export", err.to_string())
                 .with_context("path", out_path.display().to_string()),
         )
     })?;
-    for submission in load_submissions(conn)? {
-        wtr.write_record([
-            submission.id.to_string(),
-            submission.submitter.clone(),
-            submission.date.clone(),
-            submission.toolchain.clone(),
-            submission.notes.clone().unwrap_or_default(),
-        ])
-        .map_err(|err| AsmError::Serde(ErrorInfo::new("asm_dsr.export", err.to_string())))?;
-    }
-    wtr.flush()
-        .map_err(|err| AsmError::Serde(ErrorInfo::new("asm_dsr.export", err.to_string())))
+    match source.into() {
+        ExportSource::Registry(conn) => {
+            for submission in load_submissions(conn)? {
+                wtr.write_record([
+                    submission.id.to_string(),
+                    submission.submitter.clone(),
+                    submission.date.clone(),
+                    submission.toolchain.clone(),
+                    submission.notes.clone().unwrap_or_default(),
+                ])
+                .map_err(export_error)?;
+            }
+        }
+        ExportSource::Aggregates(aggregates) => {
+            wtr.write_record(["group", "metric", "count", "mean", "min", "max", "stddev"])
+                .map_err(export_error)?;
+            for aggregate in aggregates {
+                wtr.write_record([
+                    aggregate.group.clone(),
+                    aggregate.metric.clone(),
+                    aggregate.count.to_string(),
+                    aggregate.mean.to_string(),
+                    aggregate.min.to_string(),
+                    aggregate.max.to_string(),
+                    aggregate.stddev.to_string(),
+                ])
+                .map_err(export_error)?;
+            }
+        }
+    }
+    wtr.flush().map_err(export_error)
+}
+
+/// Column order of the table written by [`export_parquet`].
+#[cfg(feature = "parquet")]
+pub const PARQUET_COLUMNS: [&str; 13] = [
+    "submission_id",
+    "submitter",
+    "date",
+    "toolchain",
+    "notes",
+    "record",
+    "metric_name",
+    "metric_value",
+    "metric_unit",
+    "artifact_kind",
+    "artifact_path",
+    "artifact_sha256",
+    "artifact_analysis_hash",
+];
+
+/// One row of the long-form table written by [`export_parquet`].
+#[cfg(feature = "parquet")]
+struct ParquetRow<'a> {
+    submission: &'a crate::schema::SubmissionRecord,
+    record: &'static str,
+    metric: Option<&'a crate::schema::MetricRecord>,
+    artifact: Option<&'a crate::schema::ArtifactRecord>,
+}
+
+/// Writes the submissions matching `query` as a Snappy-compressed Parquet file.
+///
+/// The table is the joined submission/metric/artifact view in long form: every
+/// metric and every artifact of a submission becomes one row carrying the
+/// submission columns, with `record` set to `metric` or `artifact` and the
+/// other record's columns null. Submissions without either contribute a
+/// single `submission` row. Rows follow submission id, then metrics by name,
+/// then artifacts by id, and columns follow [`PARQUET_COLUMNS`].
+#[cfg(feature = "parquet")]
+pub fn export_parquet(
+    conn: &Connection,
+    query: &crate::query::QueryParams,
+    out_path: &Path,
+) -> Result<(), AsmError> {
+    use std::sync::Arc;
+
+    use arrow_array::{ArrayRef, Float64Array, Int64Array, RecordBatch, StringArray};
+    use arrow_schema::{Field, Schema};
+    use parquet::arrow::ArrowWriter;
+    use parquet::basic::Compression;
+    use parquet::file::properties::WriterProperties;
+
+    let registry = RegistryQuery::execute(conn, query)?;
+    let mut rows = Vec::new();
+    for submission in &registry.submissions {
+        let start = rows.len();
+        rows.extend(
+            registry
+                .metrics
+                .iter()
+                .filter(|metric| metric.submission_id == submission.id)
+                .map(|metric| ParquetRow {
+                    submission,
+                    record: "metric",
+                    metric: Some(metric),
+                    artifact: None,
+                }),
+        );
+        rows.extend(
+            registry
+                .artifacts
+                .iter()
+                .filter(|artifact| artifact.submission_id == submission.id)
+                .map(|artifact| ParquetRow {
+                    submission,
+                    record: "artifact",
+                    metric: None,
+                    artifact: Some(artifact),
+                }),
+        );
+        if rows.len() == start {
+            rows.push(ParquetRow {
+                submission,
+                record: "submission",
+                metric: None,
+                artifact: None,
+            });
+        }
+    }
+
+    let text = |cell: for<'r> fn(&'r ParquetRow<'r>) -> Option<&'r str>| -> ArrayRef {
+        Arc::new(rows.iter().map(cell).collect::<StringArray>())
+    };
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(
+            rows.iter()
+                .map(|row| row.submission.id)
+                .collect::<Int64Array>(),
+        ),
+        text(|row| Some(&row.submission.submitter)),
+        text(|row| Some(&row.submission.date)),
+        text(|row| Some(&row.submission.toolchain)),
+        text(|row| row.submission.notes.as_deref()),
+        text(|row| Some(row.record)),
+        text(|row| row.metric.map(|metric| metric.name.as_str())),
+        Arc::new(
+            rows.iter()
+                .map(|row| row.metric.map(|metric| metric.value))
+                .collect::<Float64Array>(),
+        ),
+        text(|row| row.metric.and_then(|metric| metric.unit.as_deref())),
+        text(|row| row.artifact.map(|artifact| artifact.kind.as_str())),
+        text(|row| row.artifact.map(|artifact| artifact.path.as_str())),
+        text(|row| row.artifact.map(|artifact| artifact.sha256.as_str())),
+        text(|row| {
+            row.artifact
+                .and_then(|artifact| artifact.analysis_hash.as_deref())
+        }),
+    ];
+    // The submission columns other than `notes`, and `record`, are set on
+    // every row.
+    let required = ["submission_id", "submitter", "date", "toolchain", "record"];
+    let fields: Vec<Field> = PARQUET_COLUMNS
+        .iter()
+        .zip(&columns)
+        .map(|(name, column)| {
+            Field::new(*name, column.data_type().clone(), !required.contains(name))
+        })
+        .collect();
+    let batch =
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).map_err(export_error)?;
+
+    let file = File::create(out_path).map_err(|err| {
+        AsmError::Serde(
+            ErrorInfo::new("asm_dsr.export", err.to_string())
+                .with_context("path", out_path.display().to_string()),
+        )
+    })?;
+    let props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut writer =
+        ArrowWriter::try_new(file, batch.schema(), Some(props)).map_err(export_error)?;
+    writer.write(&batch).map_err(export_error)?;
+    writer.close().map_err(export_error)?;
+    Ok(())
 }
@@ -8,15 +8,20 @@ use serde::Deserialize;
 use sha2::{Digest, Sha256};
 use zip::ZipArchive;
 
+use crate::export::BUNDLE_MANIFEST;
 use crate::schema::{
     init_schema, insert_artifact, insert_metric, insert_submission, load_submissions,
     SubmissionRecord,
 };
+use crate::store::BlobStore;
 
 #[derive(Debug, Clone)]
 pub struct IngestOptions {
     pub artifact_root: PathBuf,
     pub validate_hashes: bool,
+    /// Store artefacts in the shared content-addressed [`BlobStore`] so
+    /// identical files are kept once across submissions.
+    pub dedup: bool,
 }
 
 impl IngestOptions {
@@ -24,6 +29,7 @@ impl IngestOptions {
         Self {
             artifact_root: artifact_root.into(),
             validate_hashes: true,
+            dedup: false,
         }
     }
 }
@@ -35,6 +41,8 @@ struct ManifestArtifact {
     sha256: String,
     #[serde(default)]
     analysis_hash: Option<String>,
+    #[serde(default)]
+    size: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -74,7 +82,14 @@ pub fn ingest_bundle(
     })?;
     let mut archive = ZipArchive::new(file)
         .map_err(|err| registry_error("asm_dsr.bundle_parse", err.to_string()))?;
-    let manifest_bytes = read_entry(&mut archive, "manifest.json")?;
+    // Bundles from `build_bundle` carry their own manifest name so the run's
+    // `manifest.json` can travel as an ordinary artefact.
+    let manifest_name = if archive.by_name(BUNDLE_MANIFEST).is_ok() {
+        BUNDLE_MANIFEST
+    } else {
+        "manifest.json"
+    };
+    let manifest_bytes = read_entry(&mut archive, manifest_name)?;
     let manifest: SubmissionManifest = serde_json::from_slice(&manifest_bytes)
         .map_err(|err| registry_error("asm_dsr.bundle_manifest", err.to_string()))?;
     let submission_id = insert_submission(
@@ -92,8 +107,22 @@ pub fn ingest_bundle(
             format!("failed to create {}: {err}", submission_dir.display()),
         )
     })?;
+    let blob_store = opts.dedup.then(|| BlobStore::new(&opts.artifact_root));
     for artifact in &manifest.artifacts {
         let bytes = read_entry(&mut archive, &artifact.path)?;
+        if let Some(size) = artifact.size {
+            if opts.validate_hashes && size != bytes.len() as u64 {
+                return Err(registry_error(
+                    "asm_dsr.size_mismatch",
+                    format!(
+                        "artifact {} expected {} bytes got {}",
+                        artifact.path,
+                        size,
+                        bytes.len()
+                    ),
+                ));
+            }
+        }
         let hash = hex::encode(Sha256::digest(&bytes));
         if opts.validate_hashes && hash != artifact.sha256 {
             return Err(registry_error(
@@ -104,6 +133,17 @@ pub fn ingest_bundle(
                 ),
             ));
         }
+        if let Some(store) = &blob_store {
+            store.insert_artifact(
+                conn,
+                submission_id,
+                &artifact.kind,
+                &artifact.path,
+                artifact.analysis_hash.as_deref(),
+                &bytes,
+            )?;
+            continue;
+        }
         let out_path = submission_dir.join(&artifact.path);
         if let Some(parent) = out_path.parent() {
             fs::create_dir_all(parent).map_err(|err| {
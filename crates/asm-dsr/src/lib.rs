@@ -5,11 +5,20 @@ pub mod ingest;
 pub mod query;
 pub mod schema;
 pub mod serde;
+pub mod store;
 
-pub use export::{export_csv, export_json};
+pub use export::{
+    build_bundle, export_csv, export_json, BundleArtifact, BundleManifest, BundleMetric,
+    ExportSource, SubmissionMeta,
+};
+#[cfg(feature = "parquet")]
+pub use export::{export_parquet, PARQUET_COLUMNS};
 pub use ingest::{ingest_bundle, IngestOptions};
-pub use query::{QueryParams, RegistryQuery};
+pub use query::{
+    aggregate_metrics, GroupKey, MetricAggregate, MetricRange, QueryParams, RegistryQuery,
+};
 pub use schema::{
-    init_schema, insert_artifact, insert_metric, insert_submission, ArtifactRecord,
-    SubmissionRecord,
+    init_schema, insert_artifact, insert_metric, insert_submission, migrate_schema, schema_version,
+    ArtifactRecord, Migration, SubmissionRecord, MIGRATIONS, SCHEMA_VERSION,
 };
+pub use store::{blob_refcount, delete_submission, BlobStore, DeletedSubmission, StoredArtifact};
@@ -1,17 +1,33 @@
 use asm_core::errors::{AsmError, ErrorInfo};
-use rusqlite::Connection;
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection};
 use serde::{Deserialize, Serialize};
 
-use crate::schema::{
-    load_artifacts, load_metrics, load_submissions, ArtifactRecord, MetricRecord, SubmissionRecord,
-};
+use crate::schema::{load_artifacts, load_metrics, ArtifactRecord, MetricRecord, SubmissionRecord};
 
-#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct QueryParams {
     #[serde(default)]
     pub submitter: Option<String>,
     #[serde(default)]
     pub kind: Option<String>,
+    /// Whitespace separated terms; every term must occur, case-insensitively,
+    /// in the submission notes or toolchain.
+    #[serde(default)]
+    pub text: Option<String>,
+    /// Every range must be satisfied by at least one value of its metric.
+    #[serde(default)]
+    pub metric_ranges: Vec<MetricRange>,
+}
+
+/// Inclusive bounds on the values recorded for one metric.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricRange {
+    pub name: String,
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -21,22 +37,128 @@ pub struct RegistryQuery {
     pub metrics: Vec<MetricRecord>,
 }
 
+/// `WHERE` fragment over `submissions s` built from [`QueryParams`]. User
+/// input only ever reaches SQLite through the bound `values`.
+struct SubmissionFilter {
+    conditions: Vec<String>,
+    values: Vec<Value>,
+}
+
+impl SubmissionFilter {
+    fn new(params: &QueryParams) -> Result<Self, AsmError> {
+        let mut filter = Self {
+            conditions: Vec::new(),
+            values: Vec::new(),
+        };
+        if let Some(submitter) = &params.submitter {
+            let slot = filter.bind(Value::Text(submitter.clone()));
+            filter.conditions.push(format!("s.submitter = {slot}"));
+        }
+        for term in params.text.iter().flat_map(|text| text.split_whitespace()) {
+            let slot = filter.bind(Value::Text(format!("%{}%", escape_like(term))));
+            filter.conditions.push(format!(
+                "(s.notes LIKE {slot} ESCAPE '\\' OR s.toolchain LIKE {slot} ESCAPE '\\')"
+            ));
+        }
+        for range in &params.metric_ranges {
+            validate_range(range)?;
+            let name = filter.bind(Value::Text(range.name.clone()));
+            let mut condition = format!(
+                "EXISTS (SELECT 1 FROM metrics r WHERE r.submission_id = s.id AND r.name = {name}"
+            );
+            if let Some(min) = range.min {
+                condition.push_str(&format!(
+                    " AND r.value >= {}",
+                    filter.bind(Value::Real(min))
+                ));
+            }
+            if let Some(max) = range.max {
+                condition.push_str(&format!(
+                    " AND r.value <= {}",
+                    filter.bind(Value::Real(max))
+                ));
+            }
+            condition.push(')');
+            filter.conditions.push(condition);
+        }
+        Ok(filter)
+    }
+
+    /// Binds `value` to the next positional parameter and returns its slot.
+    fn bind(&mut self, value: Value) -> String {
+        self.values.push(value);
+        format!("?{}", self.values.len())
+    }
+
+    fn clause(&self) -> String {
+        if self.conditions.is_empty() {
+            "1 = 1".to_string()
+        } else {
+            self.conditions.join(" AND ")
+        }
+    }
+}
+
+fn escape_like(term: &str) -> String {
+    let mut escaped = String::with_capacity(term.len());
+    for ch in term.chars() {
+        if matches!(ch, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+fn validate_range(range: &MetricRange) -> Result<(), AsmError> {
+    let finite = range.min.map_or(true, f64::is_finite) && range.max.map_or(true, f64::is_finite);
+    let ordered = match (range.min, range.max) {
+        (Some(min), Some(max)) => min <= max,
+        _ => true,
+    };
+    if finite && ordered {
+        return Ok(());
+    }
+    Err(AsmError::Serde(
+        ErrorInfo::new(
+            "asm_dsr.query_range",
+            "metric range bounds must be finite with min <= max",
+        )
+        .with_context("metric", range.name.clone()),
+    ))
+}
+
+fn query_error(err: rusqlite::Error) -> AsmError {
+    AsmError::Serde(ErrorInfo::new("asm_dsr.query", err.to_string()))
+}
+
 impl RegistryQuery {
     pub fn load(conn: &Connection) -> Result<Self, AsmError> {
         Self::execute(conn, &QueryParams::default())
     }
 
+    /// Loads the submissions matching `params`, ordered by id, with their
+    /// artifacts and metrics. `params.kind` filters the returned artifacts.
     pub fn execute(conn: &Connection, params: &QueryParams) -> Result<Self, AsmError> {
-        let submissions = load_submissions(conn)?;
-        let mut filtered_submissions = Vec::new();
-        for submission in submissions {
-            if let Some(filter) = &params.submitter {
-                if &submission.submitter != filter {
-                    continue;
-                }
-            }
-            filtered_submissions.push(submission);
-        }
+        let filter = SubmissionFilter::new(params)?;
+        let sql = format!(
+            "SELECT s.id, s.submitter, s.date, s.toolchain, s.notes FROM submissions s \
+             WHERE {} ORDER BY s.id",
+            filter.clause()
+        );
+        let mut stmt = conn.prepare(&sql).map_err(query_error)?;
+        let rows = stmt
+            .query_map(params_from_iter(filter.values.iter()), |row| {
+                Ok(SubmissionRecord {
+                    id: row.get(0)?,
+                    submitter: row.get(1)?,
+                    date: row.get(2)?,
+                    toolchain: row.get(3)?,
+                    notes: row.get(4)?,
+                })
+            })
+            .map_err(query_error)?;
+        let filtered_submissions = rows.collect::<Result<Vec<_>, _>>().map_err(query_error)?;
         let mut artifacts = Vec::new();
         let mut metrics = Vec::new();
         for submission in &filtered_submissions {
@@ -67,3 +189,101 @@ impl RegistryQuery {
         Ok(())
     }
 }
+
+/// Column that [`aggregate_metrics`] groups metric values by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupKey {
+    Submitter,
+    Toolchain,
+    /// Artifact kinds present in the submission. A submission with several
+    /// kinds contributes its metrics to each of them.
+    ArtifactKind,
+}
+
+impl GroupKey {
+    /// Subquery mapping submissions to group values; `kind` is the parameter
+    /// slot holding the optional artifact-kind filter.
+    fn source(self, kind: &str) -> String {
+        match self {
+            GroupKey::Submitter => {
+                "SELECT id AS submission_id, submitter AS grp FROM submissions".to_string()
+            }
+            GroupKey::Toolchain => {
+                "SELECT id AS submission_id, toolchain AS grp FROM submissions".to_string()
+            }
+            GroupKey::ArtifactKind => format!(
+                "SELECT DISTINCT submission_id, kind AS grp FROM artifacts \
+                 WHERE {kind} IS NULL OR kind = {kind}"
+            ),
+        }
+    }
+}
+
+/// Summary statistics of one metric within one group.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricAggregate {
+    pub group: String,
+    pub metric: String,
+    pub count: u64,
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+    /// Population standard deviation; zero for single-sample groups.
+    pub stddev: f64,
+}
+
+/// Aggregates every metric per group in a single SQL pass, ordered by group
+/// value and then metric name.
+///
+/// `filter.submitter`, `filter.text` and `filter.metric_ranges` restrict the
+/// submissions considered as in [`RegistryQuery::execute`], and `filter.kind`
+/// keeps only submissions holding an artifact of that kind.
+pub fn aggregate_metrics(
+    conn: &Connection,
+    group_by: GroupKey,
+    filter: &QueryParams,
+) -> Result<Vec<MetricAggregate>, AsmError> {
+    let mut submissions = SubmissionFilter::new(filter)?;
+    let kind = submissions.bind(filter.kind.clone().map_or(Value::Null, Value::Text));
+    let sql = format!(
+        "SELECT g.grp, m.name, COUNT(*), AVG(m.value), MIN(m.value), MAX(m.value), \
+                AVG(m.value * m.value) \
+         FROM metrics m \
+         JOIN submissions s ON s.id = m.submission_id \
+         JOIN ({}) g ON g.submission_id = m.submission_id \
+         WHERE {} \
+           AND ({kind} IS NULL OR EXISTS ( \
+                SELECT 1 FROM artifacts a WHERE a.submission_id = s.id AND a.kind = {kind})) \
+         GROUP BY g.grp, m.name \
+         ORDER BY g.grp, m.name",
+        group_by.source(&kind),
+        submissions.clause()
+    );
+    let query_error = |err: rusqlite::Error| {
+        AsmError::Serde(ErrorInfo::new("asm_dsr.aggregate", err.to_string()))
+    };
+    let mut stmt = conn.prepare(&sql).map_err(query_error)?;
+    let rows = stmt
+        .query_map(params_from_iter(submissions.values.iter()), |row| {
+            let count: i64 = row.get(2)?;
+            let mean: f64 = row.get(3)?;
+            let mean_sq: f64 = row.get(6)?;
+            let stddev = if count > 1 {
+                (mean_sq - mean * mean).max(0.0).sqrt()
+            } else {
+                0.0
+            };
+            Ok(MetricAggregate {
+                group: row.get(0)?,
+                metric: row.get(1)?,
+                count: count as u64,
+                mean,
+                min: row.get(4)?,
+                max: row.get(5)?,
+                stddev,
+            })
+        })
+        .map_err(query_error)?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(query_error)
+}
@@ -3,7 +3,50 @@ use chrono::Utc;
 use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 
-pub const SCHEMA_VERSION: i64 = 1;
+pub const SCHEMA_VERSION: i64 = 2;
+
+/// Oldest registry version [`init_schema`] upgrades in place.
+pub const MIN_SUPPORTED_VERSION: i64 = 1;
+
+/// One forward step of the registry schema.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub from: i64,
+    pub to: i64,
+    pub description: &'static str,
+    /// Statements run in order inside the migration transaction.
+    pub statements: &'static [&'static str],
+}
+
+/// Ordered migration registry.
+///
+/// Fresh registries are created at [`MIN_SUPPORTED_VERSION`] and walk the same
+/// chain as upgraded ones, so every schema bump adds exactly one step here
+/// with `from` equal to the previous [`SCHEMA_VERSION`]. Steps must be
+/// additive: create tables or add nullable/defaulted columns and backfill
+/// them, never drop or rename what older readers rely on. Table creation uses
+/// `IF NOT EXISTS` so a step tolerates registries that already gained the
+/// table informally.
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    from: 1,
+    to: 2,
+    description: "content-addressed artifact store",
+    statements: &[
+        "CREATE TABLE IF NOT EXISTS blobs(
+            sha256 TEXT PRIMARY KEY,
+            path TEXT NOT NULL,
+            refcount INTEGER NOT NULL
+        )",
+        "CREATE TABLE IF NOT EXISTS artifact_blobs(
+            artifact_id INTEGER PRIMARY KEY,
+            sha256 TEXT NOT NULL,
+            FOREIGN KEY(artifact_id) REFERENCES artifacts(id),
+            FOREIGN KEY(sha256) REFERENCES blobs(sha256)
+        )",
+        // Blob lookups compare lowercase hex digests.
+        "UPDATE artifacts SET sha256 = lower(sha256)",
+    ],
+}];
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SubmissionRecord {
@@ -33,6 +76,7 @@ pub struct MetricRecord {
 }
 
 pub fn init_schema(conn: &Connection) -> Result<(), AsmError> {
+    // Base tables of schema version 1; later versions come from `MIGRATIONS`.
     conn.execute_batch(
         "BEGIN;
         CREATE TABLE IF NOT EXISTS meta(version INTEGER NOT NULL);
@@ -62,29 +106,91 @@ pub fn init_schema(conn: &Connection) -> Result<(), AsmError> {
         COMMIT;",
     )
     .map_err(|err| AsmError::Serde(ErrorInfo::new("asm_dsr.schema", err.to_string())))?;
-    set_version(conn, SCHEMA_VERSION)?;
-    Ok(())
+    let current = match schema_version(conn)? {
+        Some(current) => current,
+        None => {
+            conn.execute(
+                "INSERT INTO meta(version) VALUES (?)",
+                params![MIN_SUPPORTED_VERSION],
+            )
+            .map_err(|err| AsmError::Serde(ErrorInfo::new("asm_dsr.schema", err.to_string())))?;
+            MIN_SUPPORTED_VERSION
+        }
+    };
+    if current == SCHEMA_VERSION {
+        return Ok(());
+    }
+    migrate_schema(conn, current, SCHEMA_VERSION)
 }
 
-fn set_version(conn: &Connection, version: i64) -> Result<(), AsmError> {
-    let existing: Option<i64> = conn
-        .query_row("SELECT version FROM meta LIMIT 1", [], |row| row.get(0))
+/// Returns the recorded registry version, or `None` for an uninitialised
+/// database.
+pub fn schema_version(conn: &Connection) -> Result<Option<i64>, AsmError> {
+    conn.query_row("SELECT version FROM meta LIMIT 1", [], |row| row.get(0))
         .optional()
-        .map_err(|err| AsmError::Serde(ErrorInfo::new("asm_dsr.schema", err.to_string())))?;
-    match existing {
-        Some(current) if current == version => Ok(()),
-        Some(current) => Err(AsmError::Serde(ErrorInfo::new(
-            "asm_dsr.schema_version",
-            format!("registry schema {current} incompatible with expected {version}"),
-        ))),
-        None => {
-            conn.execute("INSERT INTO meta(version) VALUES (?)", params![version])
-                .map_err(|err| {
-                    AsmError::Serde(ErrorInfo::new("asm_dsr.schema", err.to_string()))
-                })?;
-            Ok(())
+        .map_err(|err| AsmError::Serde(ErrorInfo::new("asm_dsr.schema", err.to_string())))
+}
+
+/// Upgrades a registry from version `from` to `to` by applying the
+/// [`MIGRATIONS`] steps in order inside one transaction.
+///
+/// Fails with `asm_dsr.schema_version` unless the registry currently records
+/// `from` and both ends lie within the supported range, and with
+/// `asm_dsr.migration` if a step is missing or one of its statements fails;
+/// nothing is applied in either case.
+pub fn migrate_schema(conn: &Connection, from: i64, to: i64) -> Result<(), AsmError> {
+    let version_error = |message: String| {
+        AsmError::Serde(
+            ErrorInfo::new("asm_dsr.schema_version", message)
+                .with_context("from", from.to_string())
+                .with_context("to", to.to_string()),
+        )
+    };
+    if from > to || from < MIN_SUPPORTED_VERSION || to > SCHEMA_VERSION {
+        return Err(version_error(format!(
+            "cannot migrate registry schema {from} to {to}; supported range is \
+             {MIN_SUPPORTED_VERSION}..={SCHEMA_VERSION}"
+        )));
+    }
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|err| AsmError::Serde(ErrorInfo::new("asm_dsr.migration", err.to_string())))?;
+    match schema_version(&tx)? {
+        Some(current) if current == from => {}
+        Some(current) => {
+            return Err(version_error(format!(
+                "registry schema {current} does not match migration source {from}"
+            )))
+        }
+        None => return Err(version_error("registry schema is not initialised".into())),
+    }
+    let mut version = from;
+    while version < to {
+        let step = MIGRATIONS
+            .iter()
+            .find(|step| step.from == version)
+            .ok_or_else(|| {
+                AsmError::Serde(
+                    ErrorInfo::new("asm_dsr.migration", "no migration step registered")
+                        .with_context("from", version.to_string()),
+                )
+            })?;
+        for statement in step.statements {
+            tx.execute_batch(statement).map_err(|err| {
+                AsmError::Serde(
+                    ErrorInfo::new("asm_dsr.migration", err.to_string())
+                        .with_context("from", step.from.to_string())
+                        .with_context("to", step.to.to_string())
+                        .with_context("step", step.description),
+                )
+            })?;
         }
+        version = step.to;
     }
+    tx.execute("UPDATE meta SET version = ?", params![to])
+        .map_err(|err| AsmError::Serde(ErrorInfo::new("asm_dsr.migration", err.to_string())))?;
+    tx.commit()
+        .map_err(|err| AsmError::Serde(ErrorInfo::new("asm_dsr.migration", err.to_string())))
 }
 
 pub fn insert_submission(
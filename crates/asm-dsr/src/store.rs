@@ -0,0 +1,246 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use asm_core::errors::{AsmError, ErrorInfo};
+use rusqlite::{params, Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+
+use crate::schema::insert_artifact;
+
+/// Directory below the artifact root holding content-addressed blobs.
+pub const BLOB_DIR: &str = "blobs";
+
+/// Content-addressed artifact storage shared by all submissions.
+///
+/// Blobs live at `<root>/blobs/<sha[..2]>/<sha>` and the `blobs` table keeps a
+/// reference count per digest so identical artefacts are stored once.
+#[derive(Debug, Clone)]
+pub struct BlobStore {
+    root: PathBuf,
+}
+
+/// Outcome of storing one artifact through the [`BlobStore`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredArtifact {
+    pub artifact_id: i64,
+    pub sha256: String,
+    /// Blob location relative to the artifact root.
+    pub blob_path: String,
+    /// `true` when the blob already existed and was linked instead of written.
+    pub deduplicated: bool,
+}
+
+/// Summary of a submission removed with [`delete_submission`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeletedSubmission {
+    pub submission_id: i64,
+    pub artifacts: usize,
+    /// Digests whose refcount dropped to zero and whose blobs were removed.
+    pub released_blobs: Vec<String>,
+}
+
+fn registry_error(code: &str, err: impl ToString) -> AsmError {
+    AsmError::Serde(ErrorInfo::new(code, err.to_string()))
+}
+
+fn blob_relative_path(sha256: &str) -> String {
+    let prefix = sha256.get(..2).unwrap_or(sha256);
+    format!("{BLOB_DIR}/{prefix}/{sha256}")
+}
+
+impl BlobStore {
+    pub fn new(artifact_root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: artifact_root.into(),
+        }
+    }
+
+    pub fn blob_path(&self, sha256: &str) -> PathBuf {
+        self.root.join(blob_relative_path(sha256))
+    }
+
+    /// Records an artifact whose contents are `bytes`, linking to an existing
+    /// blob with the same SHA256 when one is present and writing a new blob
+    /// otherwise.
+    pub fn insert_artifact(
+        &self,
+        conn: &Connection,
+        submission_id: i64,
+        kind: &str,
+        path: &str,
+        analysis_hash: Option<&str>,
+        bytes: &[u8],
+    ) -> Result<StoredArtifact, AsmError> {
+        let sha256 = hex::encode(Sha256::digest(bytes));
+        let existing: Option<String> = conn
+            .query_row(
+                "SELECT path FROM blobs WHERE sha256 = ?",
+                params![sha256],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|err| registry_error("asm_dsr.blob", err))?;
+        let deduplicated = existing.is_some();
+        let blob_path = existing.unwrap_or_else(|| blob_relative_path(&sha256));
+        let absolute = self.root.join(&blob_path);
+        // A linked blob is only rewritten if its file went missing on disk.
+        if !absolute.exists() {
+            write_blob(&absolute, bytes)?;
+        }
+        if deduplicated {
+            conn.execute(
+                "UPDATE blobs SET refcount = refcount + 1 WHERE sha256 = ?",
+                params![sha256],
+            )
+        } else {
+            conn.execute(
+                "INSERT INTO blobs(sha256, path, refcount) VALUES (?, ?, 1)",
+                params![sha256, blob_path],
+            )
+        }
+        .map_err(|err| registry_error("asm_dsr.blob", err))?;
+        let artifact_id = insert_artifact(conn, submission_id, kind, path, &sha256, analysis_hash)?;
+        conn.execute(
+            "INSERT INTO artifact_blobs(artifact_id, sha256) VALUES (?, ?)",
+            params![artifact_id, sha256],
+        )
+        .map_err(|err| registry_error("asm_dsr.blob", err))?;
+        Ok(StoredArtifact {
+            artifact_id,
+            sha256,
+            blob_path,
+            deduplicated,
+        })
+    }
+}
+
+fn write_blob(path: &Path, bytes: &[u8]) -> Result<(), AsmError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| {
+            registry_error(
+                "asm_dsr.artifact_dir",
+                format!("failed to create {}: {err}", parent.display()),
+            )
+        })?;
+    }
+    // Write beside the final path and rename so readers never see a partial
+    // blob under its digest.
+    let partial = path.with_extension("partial");
+    fs::write(&partial, bytes)
+        .and_then(|_| fs::rename(&partial, path))
+        .map_err(|err| {
+            registry_error(
+                "asm_dsr.artifact_write",
+                format!("failed to write {}: {err}", path.display()),
+            )
+        })
+}
+
+/// Returns the reference count of the blob with `sha256`, if stored.
+pub fn blob_refcount(conn: &Connection, sha256: &str) -> Result<Option<i64>, AsmError> {
+    conn.query_row(
+        "SELECT refcount FROM blobs WHERE sha256 = ?",
+        params![sha256],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|err| registry_error("asm_dsr.query", err))
+}
+
+/// Removes a submission with its artifacts and metrics.
+///
+/// Every blob referenced by the submission loses one reference; only blobs
+/// whose refcount drops to zero are deleted from disk. Artefacts stored
+/// without deduplication live in `submission_<id>/`, which is removed whole.
+pub fn delete_submission(
+    conn: &Connection,
+    artifact_root: &Path,
+    submission_id: i64,
+) -> Result<DeletedSubmission, AsmError> {
+    let exists: Option<i64> = conn
+        .query_row(
+            "SELECT id FROM submissions WHERE id = ?",
+            params![submission_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|err| registry_error("asm_dsr.query", err))?;
+    if exists.is_none() {
+        return Err(AsmError::Serde(
+            ErrorInfo::new("asm_dsr.unknown_submission", "submission not found")
+                .with_context("submission", submission_id.to_string()),
+        ));
+    }
+
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|err| registry_error("asm_dsr.delete", err))?;
+    let delete_error = |err: rusqlite::Error| registry_error("asm_dsr.delete", err);
+    let artifacts = tx
+        .query_row(
+            "SELECT COUNT(*) FROM artifacts WHERE submission_id = ?",
+            params![submission_id],
+            |row| row.get::<_, i64>(0),
+        )
+        .map_err(delete_error)? as usize;
+    tx.execute(
+        "UPDATE blobs SET refcount = refcount - (
+            SELECT COUNT(*) FROM artifact_blobs ab
+            JOIN artifacts a ON a.id = ab.artifact_id
+            WHERE a.submission_id = ?1 AND ab.sha256 = blobs.sha256)",
+        params![submission_id],
+    )
+    .map_err(delete_error)?;
+    tx.execute(
+        "DELETE FROM artifact_blobs WHERE artifact_id IN (
+            SELECT id FROM artifacts WHERE submission_id = ?)",
+        params![submission_id],
+    )
+    .map_err(delete_error)?;
+    let released: Vec<(String, String)> = {
+        let mut stmt = tx
+            .prepare("SELECT sha256, path FROM blobs WHERE refcount <= 0 ORDER BY sha256")
+            .map_err(delete_error)?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(delete_error)?;
+        rows.collect::<Result<_, _>>().map_err(delete_error)?
+    };
+    tx.execute("DELETE FROM blobs WHERE refcount <= 0", [])
+        .map_err(delete_error)?;
+    for statement in [
+        "DELETE FROM artifacts WHERE submission_id = ?",
+        "DELETE FROM metrics WHERE submission_id = ?",
+        "DELETE FROM submissions WHERE id = ?",
+    ] {
+        tx.execute(statement, params![submission_id])
+            .map_err(delete_error)?;
+    }
+    tx.commit().map_err(delete_error)?;
+
+    for (_, path) in &released {
+        let blob = artifact_root.join(path);
+        if blob.exists() {
+            fs::remove_file(&blob).map_err(|err| {
+                registry_error(
+                    "asm_dsr.artifact_remove",
+                    format!("failed to remove {}: {err}", blob.display()),
+                )
+            })?;
+        }
+    }
+    let submission_dir = artifact_root.join(format!("submission_{submission_id}"));
+    if submission_dir.exists() {
+        fs::remove_dir_all(&submission_dir).map_err(|err| {
+            registry_error(
+                "asm_dsr.artifact_remove",
+                format!("failed to remove {}: {err}", submission_dir.display()),
+            )
+        })?;
+    }
+    Ok(DeletedSubmission {
+        submission_id,
+        artifacts,
+        released_blobs: released.into_iter().map(|(sha, _)| sha).collect(),
+    })
+}
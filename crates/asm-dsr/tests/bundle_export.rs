@@ -0,0 +1,96 @@
+use std::fs;
+use std::path::Path;
+
+use asm_dsr::schema::load_artifacts;
+use asm_dsr::{build_bundle, ingest_bundle, init_schema, IngestOptions, SubmissionMeta};
+use rusqlite::Connection;
+use tempfile::{tempdir, NamedTempFile};
+
+fn write(root: &Path, relative: &str, contents: &str) {
+    let path = root.join(relative);
+    fs::create_dir_all(path.parent().unwrap()).expect("mkdir");
+    fs::write(path, contents).expect("write");
+}
+
+fn run_dir(root: &Path) {
+    write(root, "manifest.json", "{\"seed\":7}");
+    write(root, "end_state/graph.json", "{\"nodes\":[]}");
+    write(root, "end_state/code.json", "{\"rows\":[]}");
+    write(root, "spectrum/spectrum_report.json", "{\"modes\":2}");
+    write(
+        root,
+        "analysis_report.json",
+        "{\"hashes\":{\"analysis_hash\":\"abc123\"}}",
+    );
+    write(root, "notes.txt", "scratch");
+}
+
+fn meta(include_extra: bool) -> SubmissionMeta {
+    SubmissionMeta {
+        submitter: "tester".to_string(),
+        toolchain: "asm 0.16".to_string(),
+        notes: None,
+        metrics: Vec::new(),
+        include_extra,
+    }
+}
+
+#[test]
+fn bundle_roundtrips_through_ingest() {
+    let run = tempdir().expect("run dir");
+    run_dir(run.path());
+    let out = tempdir().expect("out dir");
+    let bundle = out.path().join("bundle.zip");
+    let manifest = build_bundle(run.path(), &meta(false), &bundle).expect("bundle");
+
+    let paths: Vec<_> = manifest.artifacts.iter().map(|a| a.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        [
+            "analysis_report.json",
+            "end_state/code.json",
+            "end_state/graph.json",
+            "manifest.json",
+            "spectrum/spectrum_report.json",
+        ]
+    );
+    assert_eq!(
+        manifest.artifacts[0].analysis_hash.as_deref(),
+        Some("abc123")
+    );
+
+    let again = out.path().join("again.zip");
+    build_bundle(run.path(), &meta(false), &again).expect("bundle");
+    assert_eq!(
+        fs::read(&bundle).expect("read"),
+        fs::read(&again).expect("read")
+    );
+
+    let registry_db = NamedTempFile::new().expect("db temp");
+    let artifact_root = tempdir().expect("artifact root");
+    let conn = Connection::open(registry_db.path()).expect("open db");
+    init_schema(&conn).expect("schema");
+    let submission =
+        ingest_bundle(&conn, &bundle, &IngestOptions::new(artifact_root.path())).expect("ingest");
+    let rows = load_artifacts(&conn, submission.id).expect("artifacts");
+    assert_eq!(rows.len(), manifest.artifacts.len());
+    for (row, artifact) in rows.iter().zip(&manifest.artifacts) {
+        assert_eq!(row.kind, artifact.kind);
+        assert_eq!(row.path, artifact.path);
+        assert_eq!(row.sha256, artifact.sha256);
+        assert_eq!(row.analysis_hash, artifact.analysis_hash);
+    }
+}
+
+#[test]
+fn extra_files_require_opt_in() {
+    let run = tempdir().expect("run dir");
+    run_dir(run.path());
+    let out = tempdir().expect("out dir");
+    let manifest =
+        build_bundle(run.path(), &meta(true), &out.path().join("bundle.zip")).expect("bundle");
+    let extras: Vec<_> = manifest.artifacts.iter().filter(|a| a.extra).collect();
+    assert_eq!(extras.len(), 1);
+    assert_eq!(extras[0].path, "notes.txt");
+    assert_eq!(extras[0].size, 7);
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "parquet")]
+
+use std::fs::{self, File};
+
+use arrow_array::{Array, Float64Array, Int64Array, RecordBatch, StringArray};
+use asm_dsr::{
+    export_parquet, init_schema, insert_artifact, insert_metric, insert_submission, QueryParams,
+    PARQUET_COLUMNS,
+};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use rusqlite::Connection;
+use tempfile::tempdir;
+
+fn seeded() -> Connection {
+    let conn = Connection::open_in_memory().expect("open");
+    init_schema(&conn).expect("schema");
+    let alice = insert_submission(&conn, "alice", "asm 0.16", Some("first")).expect("submission");
+    insert_metric(&conn, alice, "pass_rate", 0.5, None).expect("metric");
+    insert_metric(&conn, alice, "gap", 1.25, Some("eV")).expect("metric");
+    insert_artifact(
+        &conn,
+        alice,
+        "SummaryReport",
+        "summary.json",
+        "aa",
+        Some("h1"),
+    )
+    .expect("artifact");
+    insert_submission(&conn, "bob", "asm 0.17", None).expect("submission");
+    conn
+}
+
+fn read(path: &std::path::Path) -> RecordBatch {
+    let file = File::open(path).expect("open parquet");
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+        .expect("parquet reader")
+        .build()
+        .expect("batches");
+    let batches: Vec<RecordBatch> = reader.map(|batch| batch.expect("batch")).collect();
+    assert_eq!(batches.len(), 1);
+    batches.into_iter().next().unwrap()
+}
+
+fn text(batch: &RecordBatch, column: &str) -> Vec<Option<String>> {
+    let array = batch
+        .column_by_name(column)
+        .unwrap()
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    (0..array.len())
+        .map(|row| (!array.is_null(row)).then(|| array.value(row).to_string()))
+        .collect()
+}
+
+#[test]
+fn parquet_export_has_stable_schema_and_rows() {
+    let conn = seeded();
+    let dir = tempdir().expect("dir");
+    let path = dir.path().join("registry.parquet");
+    export_parquet(&conn, &QueryParams::default(), &path).expect("export");
+    let batch = read(&path);
+
+    let schema = batch.schema();
+    let names: Vec<_> = schema.fields().iter().map(|field| field.name()).collect();
+    assert_eq!(names, PARQUET_COLUMNS);
+    assert!(!schema.field_with_name("submitter").unwrap().is_nullable());
+    assert!(schema
+        .field_with_name("metric_value")
+        .unwrap()
+        .is_nullable());
+
+    let ids = batch
+        .column_by_name("submission_id")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .unwrap();
+    assert_eq!(ids.values().to_vec(), vec![1, 1, 1, 2]);
+    let some = |values: &[&str]| {
+        values
+            .iter()
+            .map(|v| Some(v.to_string()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        text(&batch, "record"),
+        some(&["metric", "metric", "artifact", "submission"])
+    );
+    assert_eq!(
+        text(&batch, "metric_name"),
+        vec![Some("gap".into()), Some("pass_rate".into()), None, None]
+    );
+    assert_eq!(
+        text(&batch, "metric_unit"),
+        vec![Some("eV".into()), None, None, None]
+    );
+    let values = batch
+        .column_by_name("metric_value")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .unwrap();
+    assert_eq!(values.value(0), 1.25);
+    assert!(values.is_null(2));
+    assert_eq!(
+        text(&batch, "artifact_analysis_hash"),
+        vec![None, None, Some("h1".into()), None]
+    );
+    assert_eq!(
+        text(&batch, "notes"),
+        vec![
+            Some("first".into()),
+            Some("first".into()),
+            Some("first".into()),
+            None
+        ]
+    );
+
+    let again = dir.path().join("again.parquet");
+    export_parquet(&conn, &QueryParams::default(), &again).expect("export again");
+    assert_eq!(fs::read(&path).unwrap(), fs::read(&again).unwrap());
+}
+
+#[test]
+fn parquet_export_applies_query() {
+    let conn = seeded();
+    let dir = tempdir().expect("dir");
+    let path = dir.path().join("bob.parquet");
+    let query = QueryParams {
+        submitter: Some("bob".into()),
+        ..QueryParams::default()
+    };
+    export_parquet(&conn, &query, &path).expect("export");
+    let batch = read(&path);
+    assert_eq!(batch.num_rows(), 1);
+    assert_eq!(text(&batch, "submitter"), vec![Some("bob".into())]);
+    assert_eq!(batch.num_columns(), PARQUET_COLUMNS.len());
+}
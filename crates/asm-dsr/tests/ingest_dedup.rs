@@ -0,0 +1,102 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use asm_dsr::{
+    blob_refcount, delete_submission, ingest_bundle, init_schema, BlobStore, IngestOptions,
+};
+use rusqlite::Connection;
+use sha2::{Digest, Sha256};
+use tempfile::{tempdir, NamedTempFile};
+use zip::write::FileOptions;
+
+fn build_bundle(path: &Path, artifacts: &[(&str, &[u8])]) {
+    let file = File::create(path).expect("create bundle");
+    let mut zip = zip::ZipWriter::new(file);
+    let entries: Vec<_> = artifacts
+        .iter()
+        .map(|(name, bytes)| {
+            serde_json::json!({
+                "kind": "interaction_report",
+                "path": name,
+                "sha256": hex::encode(Sha256::digest(bytes)),
+            })
+        })
+        .collect();
+    let manifest = serde_json::json!({
+        "submitter": "tester",
+        "toolchain": "asm 0.16",
+        "artifacts": entries,
+        "metrics": [{"name": "energy_final", "value": 1.0}]
+    });
+    zip.start_file("manifest.json", FileOptions::default())
+        .expect("start manifest");
+    zip.write_all(&serde_json::to_vec(&manifest).expect("json"))
+        .expect("write manifest");
+    for (name, bytes) in artifacts {
+        zip.start_file(*name, FileOptions::default())
+            .expect("start artifact");
+        zip.write_all(bytes).expect("write artifact");
+    }
+    zip.finish().expect("finish zip");
+}
+
+fn sha(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+#[test]
+fn identical_artifacts_share_one_blob() {
+    let shared: &[u8] = b"{\"shared\":true}";
+    let first = NamedTempFile::new().expect("bundle");
+    build_bundle(first.path(), &[("a.json", shared), ("b.json", shared)]);
+    let second = NamedTempFile::new().expect("bundle");
+    build_bundle(second.path(), &[("c.json", shared), ("d.json", b"{}")]);
+
+    let root = tempdir().expect("artifact root");
+    let conn = Connection::open_in_memory().expect("db");
+    init_schema(&conn).expect("schema");
+    let mut opts = IngestOptions::new(root.path());
+    opts.dedup = true;
+    let one = ingest_bundle(&conn, first.path(), &opts).expect("ingest first");
+    let two = ingest_bundle(&conn, second.path(), &opts).expect("ingest second");
+
+    let store = BlobStore::new(root.path());
+    assert!(store.blob_path(&sha(shared)).exists());
+    assert_eq!(blob_refcount(&conn, &sha(shared)).unwrap(), Some(3));
+    assert_eq!(blob_refcount(&conn, &sha(b"{}")).unwrap(), Some(1));
+    assert!(!root
+        .path()
+        .join(format!("submission_{}/a.json", one.id))
+        .exists());
+
+    let deleted = delete_submission(&conn, root.path(), two.id).expect("delete");
+    assert_eq!(deleted.artifacts, 2);
+    assert_eq!(deleted.released_blobs, vec![sha(b"{}")]);
+    assert!(!store.blob_path(&sha(b"{}")).exists());
+    assert!(store.blob_path(&sha(shared)).exists());
+    assert_eq!(blob_refcount(&conn, &sha(shared)).unwrap(), Some(2));
+
+    let deleted = delete_submission(&conn, root.path(), one.id).expect("delete");
+    assert_eq!(deleted.released_blobs, vec![sha(shared)]);
+    assert!(!store.blob_path(&sha(shared)).exists());
+    assert_eq!(blob_refcount(&conn, &sha(shared)).unwrap(), None);
+}
+
+#[test]
+fn dedup_disabled_keeps_per_submission_copies() {
+    let bundle = NamedTempFile::new().expect("bundle");
+    build_bundle(bundle.path(), &[("a.json", b"{}")]);
+    let root = tempdir().expect("artifact root");
+    let conn = Connection::open_in_memory().expect("db");
+    let opts = IngestOptions::new(root.path());
+    let record = ingest_bundle(&conn, bundle.path(), &opts).expect("ingest");
+    let copy = root.path().join(format!("submission_{}/a.json", record.id));
+    assert!(copy.exists());
+    assert_eq!(blob_refcount(&conn, &sha(b"{}")).unwrap(), None);
+
+    delete_submission(&conn, root.path(), record.id).expect("delete");
+    assert!(!copy.exists());
+    let err = delete_submission(&conn, root.path(), record.id).unwrap_err();
+    assert_eq!(err.info().code, "asm_dsr.unknown_submission");
+}
@@ -0,0 +1,134 @@
+use std::fs;
+
+use asm_dsr::{
+    aggregate_metrics, export_csv, export_json, init_schema, insert_artifact, insert_metric,
+    insert_submission, GroupKey, MetricAggregate, QueryParams,
+};
+use rusqlite::Connection;
+use tempfile::tempdir;
+
+fn seeded() -> Connection {
+    let conn = Connection::open_in_memory().expect("open");
+    init_schema(&conn).expect("schema");
+    let rows = [
+        (
+            "alice",
+            "asm 0.16",
+            "SummaryReport",
+            [("gap", 1.0), ("pass_rate", 0.5)],
+        ),
+        (
+            "bob",
+            "asm 0.16",
+            "SummaryReport",
+            [("gap", 3.0), ("pass_rate", 0.7)],
+        ),
+        (
+            "alice",
+            "asm 0.17",
+            "interaction_report",
+            [("gap", 2.0), ("pass_rate", 0.9)],
+        ),
+    ];
+    for (submitter, toolchain, kind, metrics) in rows {
+        let id = insert_submission(&conn, submitter, toolchain, None).expect("submission");
+        insert_artifact(&conn, id, kind, "artifact.json", "00", None).expect("artifact");
+        insert_artifact(&conn, id, kind, "other.json", "11", None).expect("artifact");
+        for (name, value) in metrics {
+            insert_metric(&conn, id, name, value, None).expect("metric");
+        }
+    }
+    conn
+}
+
+fn find<'a>(rows: &'a [MetricAggregate], group: &str, metric: &str) -> &'a MetricAggregate {
+    rows.iter()
+        .find(|row| row.group == group && row.metric == metric)
+        .expect("aggregate row")
+}
+
+#[test]
+fn aggregates_group_by_toolchain() {
+    let conn = seeded();
+    let rows =
+        aggregate_metrics(&conn, GroupKey::Toolchain, &QueryParams::default()).expect("aggregate");
+    let keys: Vec<_> = rows
+        .iter()
+        .map(|row| (row.group.as_str(), row.metric.as_str()))
+        .collect();
+    assert_eq!(
+        keys,
+        [
+            ("asm 0.16", "gap"),
+            ("asm 0.16", "pass_rate"),
+            ("asm 0.17", "gap"),
+            ("asm 0.17", "pass_rate"),
+        ]
+    );
+    let gap = find(&rows, "asm 0.16", "gap");
+    assert_eq!(gap.count, 2);
+    assert_eq!(
+        (gap.mean, gap.min, gap.max, gap.stddev),
+        (2.0, 1.0, 3.0, 1.0)
+    );
+    let single = find(&rows, "asm 0.17", "pass_rate");
+    assert_eq!(single.count, 1);
+    assert_eq!(single.stddev, 0.0);
+    assert_eq!(single.mean, 0.9);
+}
+
+#[test]
+fn aggregates_respect_filters_and_other_keys() {
+    let conn = seeded();
+    let by_submitter =
+        aggregate_metrics(&conn, GroupKey::Submitter, &QueryParams::default()).expect("aggregate");
+    let alice = find(&by_submitter, "alice", "gap");
+    assert_eq!((alice.count, alice.mean), (2, 1.5));
+    assert!((alice.stddev - 0.5).abs() < 1e-12);
+
+    // Duplicate artifacts of one kind do not double-count a submission.
+    let by_kind = aggregate_metrics(&conn, GroupKey::ArtifactKind, &QueryParams::default())
+        .expect("aggregate");
+    assert_eq!(find(&by_kind, "SummaryReport", "gap").count, 2);
+    assert_eq!(find(&by_kind, "interaction_report", "gap").count, 1);
+
+    let filter = QueryParams {
+        submitter: Some("alice".into()),
+        kind: Some("SummaryReport".into()),
+        ..QueryParams::default()
+    };
+    let rows = aggregate_metrics(&conn, GroupKey::Toolchain, &filter).expect("aggregate");
+    assert_eq!(rows.len(), 2);
+    assert!(rows
+        .iter()
+        .all(|row| row.group == "asm 0.16" && row.count == 1));
+}
+
+#[test]
+fn aggregates_export_in_one_call() {
+    let conn = seeded();
+    let rows =
+        aggregate_metrics(&conn, GroupKey::Toolchain, &QueryParams::default()).expect("aggregate");
+    let dir = tempdir().expect("dir");
+    let csv_path = dir.path().join("weekly.csv");
+    export_csv(&rows, &csv_path).expect("csv");
+    let csv = fs::read_to_string(&csv_path).expect("read csv");
+    let lines: Vec<_> = csv.lines().collect();
+    assert_eq!(lines[0], "group,metric,count,mean,min,max,stddev");
+    assert_eq!(lines[1], "asm 0.16,gap,2,2,1,3,1");
+    assert_eq!(lines.len(), 5);
+
+    let json_path = dir.path().join("weekly.json");
+    export_json(&rows, &json_path).expect("json");
+    let parsed: Vec<MetricAggregate> =
+        serde_json::from_slice(&fs::read(&json_path).expect("read json")).expect("parse");
+    assert_eq!(parsed.len(), rows.len());
+    for (parsed, row) in parsed.iter().zip(&rows) {
+        assert_eq!((&parsed.group, &parsed.metric), (&row.group, &row.metric));
+        assert_eq!(
+            (parsed.count, parsed.min, parsed.max),
+            (row.count, row.min, row.max)
+        );
+        assert!((parsed.stddev - row.stddev).abs() < 1e-12);
+    }
+}
@@ -53,6 +53,7 @@ fn query_filters_by_submitter_and_kind() {
     let params = QueryParams {
         submitter: Some("alice".into()),
         kind: Some("interaction_report".into()),
+        ..QueryParams::default()
     };
     let query = RegistryQuery::execute(&conn, &params).expect("query");
     assert_eq!(query.submissions.len(), 1);
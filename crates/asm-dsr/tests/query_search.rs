@@ -0,0 +1,141 @@
+use asm_dsr::{
+    aggregate_metrics, init_schema, insert_artifact, insert_metric, insert_submission, GroupKey,
+    MetricRange, QueryParams, RegistryQuery,
+};
+use rusqlite::Connection;
+
+fn seeded() -> Connection {
+    let conn = Connection::open_in_memory().expect("open");
+    init_schema(&conn).expect("schema");
+    let rows = [
+        (
+            "alice",
+            "asm 0.16",
+            Some("Baseline sweep, 100% acceptance"),
+            1.0,
+        ),
+        ("bob", "asm 0.17-nightly", Some("gauge rerun"), 3.0),
+        ("carol", "asm 0.17", None, 2.0),
+    ];
+    for (submitter, toolchain, notes, gap) in rows {
+        let id = insert_submission(&conn, submitter, toolchain, notes).expect("submission");
+        insert_artifact(&conn, id, "SummaryReport", "artifact.json", "00", None).expect("artifact");
+        insert_metric(&conn, id, "gap", gap, None).expect("metric");
+        insert_metric(&conn, id, "pass_rate", gap / 4.0, None).expect("metric");
+    }
+    conn
+}
+
+fn submitters(query: &RegistryQuery) -> Vec<&str> {
+    query
+        .submissions
+        .iter()
+        .map(|submission| submission.submitter.as_str())
+        .collect()
+}
+
+fn text(text: &str) -> QueryParams {
+    QueryParams {
+        text: Some(text.into()),
+        ..QueryParams::default()
+    }
+}
+
+#[test]
+fn text_search_matches_notes_and_toolchain() {
+    let conn = seeded();
+    let query = RegistryQuery::execute(&conn, &text("SWEEP")).expect("query");
+    assert_eq!(submitters(&query), ["alice"]);
+    assert_eq!(query.artifacts.len(), 1);
+    assert_eq!(query.metrics.len(), 2);
+
+    let query = RegistryQuery::execute(&conn, &text("0.17")).expect("query");
+    assert_eq!(submitters(&query), ["bob", "carol"]);
+    query.ensure_deterministic().expect("deterministic");
+
+    let query = RegistryQuery::execute(&conn, &text("0.17 rerun")).expect("query");
+    assert_eq!(submitters(&query), ["bob"]);
+}
+
+#[test]
+fn text_search_treats_wildcards_and_quotes_literally() {
+    let conn = seeded();
+    let query = RegistryQuery::execute(&conn, &text("100%")).expect("query");
+    assert_eq!(submitters(&query), ["alice"]);
+    let query = RegistryQuery::execute(&conn, &text("%")).expect("query");
+    assert_eq!(submitters(&query), ["alice"]);
+    let query = RegistryQuery::execute(&conn, &text("_")).expect("query");
+    assert!(query.submissions.is_empty());
+
+    let query =
+        RegistryQuery::execute(&conn, &text("'; DROP TABLE submissions; --")).expect("query");
+    assert!(query.submissions.is_empty());
+    let all = RegistryQuery::load(&conn).expect("load");
+    assert_eq!(all.submissions.len(), 3);
+}
+
+#[test]
+fn metric_ranges_filter_submissions_and_aggregates() {
+    let conn = seeded();
+    let params = QueryParams {
+        metric_ranges: vec![MetricRange {
+            name: "gap".into(),
+            min: Some(1.5),
+            max: None,
+        }],
+        ..QueryParams::default()
+    };
+    let query = RegistryQuery::execute(&conn, &params).expect("query");
+    assert_eq!(submitters(&query), ["bob", "carol"]);
+
+    let params = QueryParams {
+        metric_ranges: vec![
+            MetricRange {
+                name: "gap".into(),
+                min: Some(1.5),
+                max: Some(2.5),
+            },
+            MetricRange {
+                name: "pass_rate".into(),
+                min: None,
+                max: Some(0.5),
+            },
+        ],
+        ..QueryParams::default()
+    };
+    let query = RegistryQuery::execute(&conn, &params).expect("query");
+    assert_eq!(submitters(&query), ["carol"]);
+
+    let rows = aggregate_metrics(&conn, GroupKey::Submitter, &params).expect("aggregate");
+    assert!(rows.iter().all(|row| row.group == "carol"));
+    assert_eq!(rows.len(), 2);
+
+    let missing = QueryParams {
+        metric_ranges: vec![MetricRange {
+            name: "unknown".into(),
+            min: None,
+            max: None,
+        }],
+        ..QueryParams::default()
+    };
+    let query = RegistryQuery::execute(&conn, &missing).expect("query");
+    assert!(query.submissions.is_empty());
+}
+
+#[test]
+fn inverted_or_non_finite_ranges_are_rejected() {
+    let conn = seeded();
+    for (min, max) in [(Some(2.0), Some(1.0)), (Some(f64::NAN), None)] {
+        let params = QueryParams {
+            metric_ranges: vec![MetricRange {
+                name: "gap".into(),
+                min,
+                max,
+            }],
+            ..QueryParams::default()
+        };
+        let err = RegistryQuery::execute(&conn, &params).unwrap_err();
+        assert_eq!(err.info().code, "asm_dsr.query_range");
+        assert_eq!(err.context_value("metric"), Some("gap"));
+    }
+}
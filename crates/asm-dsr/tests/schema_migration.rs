@@ -0,0 +1,107 @@
+use asm_dsr::schema::{load_artifacts, load_submissions, MIN_SUPPORTED_VERSION};
+use asm_dsr::{init_schema, migrate_schema, schema_version, MIGRATIONS, SCHEMA_VERSION};
+use rusqlite::{params, Connection};
+
+/// Builds a registry exactly as version 1 of `init_schema` left it.
+fn version_one_registry() -> Connection {
+    let conn = Connection::open_in_memory().expect("db");
+    conn.execute_batch(
+        "CREATE TABLE meta(version INTEGER NOT NULL);
+        INSERT INTO meta(version) VALUES (1);
+        CREATE TABLE submissions(
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            submitter TEXT NOT NULL,
+            date TEXT NOT NULL,
+            toolchain TEXT NOT NULL,
+            notes TEXT
+        );
+        CREATE TABLE artifacts(
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            submission_id INTEGER NOT NULL,
+            kind TEXT NOT NULL,
+            path TEXT NOT NULL,
+            sha256 TEXT NOT NULL,
+            analysis_hash TEXT
+        );
+        CREATE TABLE metrics(
+            submission_id INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            value REAL NOT NULL,
+            unit TEXT
+        );
+        INSERT INTO submissions(submitter, date, toolchain, notes)
+            VALUES ('alice', '2024-01-01T00:00:00Z', 'asm 0.16', NULL);
+        INSERT INTO artifacts(submission_id, kind, path, sha256, analysis_hash)
+            VALUES (1, 'interaction_report', 'interaction.json', 'ABCDEF', NULL);",
+    )
+    .expect("v1 schema");
+    conn
+}
+
+fn has_table(conn: &Connection, name: &str) -> bool {
+    conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?",
+        params![name],
+        |row| row.get::<_, i64>(0),
+    )
+    .expect("sqlite_master")
+        == 1
+}
+
+#[test]
+fn registry_chain_reaches_current_version() {
+    let mut version = MIN_SUPPORTED_VERSION;
+    for step in MIGRATIONS {
+        assert_eq!(step.from, version, "steps must be ordered and contiguous");
+        assert!(step.to > step.from);
+        version = step.to;
+    }
+    assert_eq!(version, SCHEMA_VERSION);
+}
+
+#[test]
+fn init_schema_upgrades_version_one_in_place() {
+    let conn = version_one_registry();
+    init_schema(&conn).expect("migrate");
+    assert_eq!(schema_version(&conn).unwrap(), Some(SCHEMA_VERSION));
+    assert!(has_table(&conn, "blobs"));
+    assert!(has_table(&conn, "artifact_blobs"));
+
+    let submissions = load_submissions(&conn).expect("submissions");
+    assert_eq!(submissions.len(), 1);
+    assert_eq!(submissions[0].submitter, "alice");
+    let artifacts = load_artifacts(&conn, submissions[0].id).expect("artifacts");
+    assert_eq!(artifacts[0].sha256, "abcdef");
+
+    // Re-running on a current registry is a no-op.
+    init_schema(&conn).expect("idempotent");
+    assert_eq!(schema_version(&conn).unwrap(), Some(SCHEMA_VERSION));
+}
+
+#[test]
+fn fresh_registry_matches_migrated_layout() {
+    let conn = Connection::open_in_memory().expect("db");
+    init_schema(&conn).expect("schema");
+    assert_eq!(schema_version(&conn).unwrap(), Some(SCHEMA_VERSION));
+    assert!(has_table(&conn, "blobs"));
+    assert!(has_table(&conn, "artifact_blobs"));
+}
+
+#[test]
+fn unsupported_versions_are_rejected() {
+    let conn = version_one_registry();
+    let err = migrate_schema(&conn, 2, 1).unwrap_err();
+    assert_eq!(err.info().code, "asm_dsr.schema_version");
+    let err = migrate_schema(&conn, 1, SCHEMA_VERSION + 1).unwrap_err();
+    assert_eq!(err.info().code, "asm_dsr.schema_version");
+    // The registry records version 1, so a step claiming another source fails
+    // without touching it.
+    let err = migrate_schema(&conn, 2, SCHEMA_VERSION).unwrap_err();
+    assert_eq!(err.info().code, "asm_dsr.schema_version");
+    assert_eq!(schema_version(&conn).unwrap(), Some(1));
+
+    conn.execute("UPDATE meta SET version = ?", params![SCHEMA_VERSION + 1])
+        .expect("bump");
+    let err = init_schema(&conn).unwrap_err();
+    assert_eq!(err.info().code, "asm_dsr.schema_version");
+}
@@ -19,6 +19,7 @@ fn main() {
             ],
         },
         scheduler: Default::default(),
+        concurrency: 1,
     };
     let report = sweep(&plan, 4242).expect("sweep");
     let bytes = to_canonical_json_bytes(&report).expect("json");
@@ -1,6 +1,8 @@
 use std::collections::{BTreeMap, BTreeSet};
 
 use asm_core::errors::{AsmError, ErrorInfo};
+use asm_land::stages::{synthesise_stage_outputs, StageOutputs};
+use nalgebra::DMatrix;
 use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Map, Value};
@@ -15,8 +17,38 @@ use crate::serde::to_canonical_json_bytes;
 pub enum AblationMode {
     /// Exhaustive grid over the provided factor lists.
     Grid,
-    /// Latin hypercube sampling across numeric ranges.
+    /// Latin hypercube sampling across numeric ranges and categorical levels.
     Lhs,
+    /// One-at-a-time sensitivity: a baseline job followed by jobs varying a
+    /// single factor across its remaining values.
+    Oat,
+}
+
+/// Value type of an ablation factor, inferred from its JSON values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FactorKind {
+    /// Numeric `[min, max]` range containing at least one non-integer value.
+    Continuous,
+    /// Integer `[min, max]` range; LHS samples are rounded to the nearest
+    /// integer within the range.
+    Integer,
+    /// Discrete list of levels containing at least one non-numeric value; LHS
+    /// slots map onto the levels through equal-width bins.
+    Categorical,
+}
+
+impl FactorKind {
+    /// Infers the kind of a factor from its declared values.
+    pub fn infer(values: &[Value]) -> Self {
+        if !values.iter().all(Value::is_number) {
+            FactorKind::Categorical
+        } else if values.iter().all(|value| value.as_i64().is_some()) {
+            FactorKind::Integer
+        } else {
+            FactorKind::Continuous
+        }
+    }
 }
 
 /// Numeric tolerance specification for KPI comparisons.
@@ -65,6 +97,33 @@ pub struct AblationPlan {
     pub fixed: BTreeMap<String, Value>,
     #[serde(default)]
     pub tolerances: BTreeMap<String, ToleranceSpec>,
+    /// Target rank-correlation matrix for LHS plans, rows and columns ordered
+    /// like `factors`. Induced with the Iman-Conover method after sampling.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation: Option<Vec<Vec<f64>>>,
+}
+
+impl AblationPlan {
+    /// Inferred kind of every factor, keyed like `factors`.
+    pub fn factor_kinds(&self) -> BTreeMap<String, FactorKind> {
+        self.factors
+            .iter()
+            .map(|(name, values)| (name.clone(), FactorKind::infer(values)))
+            .collect()
+    }
+
+    /// Stable hash of the plan and run seed. LHS plans with integer or
+    /// categorical factors also hash the inferred factor kinds; all-continuous
+    /// plans keep the hash of the plan alone.
+    fn plan_hash(&self, seed: u64) -> Result<String, AsmError> {
+        let kinds = self.factor_kinds();
+        let discrete = kinds.values().any(|kind| *kind != FactorKind::Continuous);
+        if self.mode == AblationMode::Lhs && discrete {
+            stable_hash_string(&(self, seed, kinds))
+        } else {
+            stable_hash_string(&(self, seed))
+        }
+    }
 }
 
 /// Per-job ablation metrics and provenance.
@@ -73,6 +132,13 @@ pub struct AblationJobReport {
     pub params: Value,
     pub seed: u64,
     pub metrics: Value,
+    /// [`AblationExecutor::name`] of the executor that measured the job.
+    #[serde(default = "default_executor_name")]
+    pub executor: String,
+}
+
+fn default_executor_name() -> String {
+    HashExecutor.name().to_string()
 }
 
 /// Aggregate ablation report persisted for reproducibility.
@@ -86,9 +152,237 @@ pub struct AblationReport {
     pub artifacts: Vec<String>,
 }
 
-/// Execute a deterministic ablation plan and emit an [`AblationReport`].
+/// Parameter set and context handed to an [`AblationExecutor`].
+#[derive(Debug, Clone, Copy)]
+pub struct AblationJob<'a> {
+    /// Stable hash of the plan and run seed.
+    pub plan_hash: &'a str,
+    /// Zero-based job index in expansion order.
+    pub index: usize,
+    /// Seed derived for this job.
+    pub seed: u64,
+    /// Factor and fixed parameters of the job.
+    pub params: &'a Value,
+    /// KPI tolerances declared by the plan; every key must be measured.
+    pub tolerances: &'a BTreeMap<String, ToleranceSpec>,
+}
+
+/// Computes KPI values for ablation jobs.
+///
+/// Implementations must be deterministic in the [`AblationJob`] so repeated
+/// runs of a plan reproduce the same report. Closures of the matching
+/// signature implement the trait.
+pub trait AblationExecutor {
+    /// Returns the measured value of each KPI for `job`.
+    fn execute(&self, job: &AblationJob<'_>) -> Result<BTreeMap<String, f64>, AsmError>;
+
+    /// Label recorded on every job report this executor produces.
+    fn name(&self) -> &str {
+        "custom"
+    }
+}
+
+impl<F> AblationExecutor for F
+where
+    F: Fn(&AblationJob<'_>) -> Result<BTreeMap<String, f64>, AsmError>,
+{
+    fn execute(&self, job: &AblationJob<'_>) -> Result<BTreeMap<String, f64>, AsmError> {
+        self(job)
+    }
+}
+
+/// Placeholder executor deriving KPI values from a hash of the job, scaled
+/// into each KPI's tolerance window. Used by [`run_ablation`] for testing.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HashExecutor;
+
+impl AblationExecutor for HashExecutor {
+    fn execute(&self, job: &AblationJob<'_>) -> Result<BTreeMap<String, f64>, AsmError> {
+        job.tolerances
+            .iter()
+            .map(|(name, tol)| {
+                let value = derive_metric(job.plan_hash, job.index, name, job.params, Some(tol))?;
+                Ok((name.clone(), value))
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "hash"
+    }
+}
+
+/// Stage inputs resolved from an ablation job for [`PipelineExecutor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PipelineParams {
+    /// Stage seed, `asm_land::job_seed(job.seed, rule_id)` as in landscape runs.
+    pub seed: u64,
+    /// Rule identifier (`rule_id` or `rule.id`, default 0).
+    pub rule_id: u64,
+    /// Sampler sweeps (`sweeps` or `sampler.sweeps`, default 200).
+    pub sweeps: u32,
+    /// Spectrum modes (`modes` or `spectrum.modes`, default 2).
+    pub modes: u32,
+    /// Dispersion k-points (`k_points` or `spectrum.k_points`, default 32).
+    pub k_points: u32,
+}
+
+impl PipelineParams {
+    /// Reads the well-known stage parameters of `job`, falling back to the
+    /// defaults for absent keys. Other parameters are ignored.
+    pub fn from_job(job: &AblationJob<'_>) -> Result<Self, AsmError> {
+        let rule_id = pipeline_param(job.params, &["rule_id", "rule.id"], 0)?;
+        Ok(Self {
+            seed: asm_land::job_seed(job.seed, rule_id),
+            rule_id,
+            sweeps: pipeline_param(job.params, &["sweeps", "sampler.sweeps"], 200)?,
+            modes: pipeline_param(job.params, &["modes", "spectrum.modes"], 2)?,
+            k_points: pipeline_param(job.params, &["k_points", "spectrum.k_points"], 32)?,
+        })
+    }
+}
+
+fn pipeline_param<T>(params: &Value, keys: &[&str], default: T) -> Result<T, AsmError>
+where
+    T: TryFrom<u64>,
+{
+    let Some((key, value)) = keys
+        .iter()
+        .find_map(|key| params.get(*key).map(|value| (*key, value)))
+    else {
+        return Ok(default);
+    };
+    value
+        .as_u64()
+        .and_then(|raw| T::try_from(raw).ok())
+        .ok_or_else(|| {
+            AsmError::Serde(
+                ErrorInfo::new(
+                    "ablation-pipeline-param",
+                    "stage parameter must be a non-negative integer in range",
+                )
+                .with_context("param", key.to_string()),
+            )
+        })
+}
+
+/// Stage runner signature accepted by [`PipelineExecutor::with_stages`].
+pub type StageRunner = fn(&PipelineParams) -> Result<StageOutputs, AsmError>;
+
+fn synthesise_stages(params: &PipelineParams) -> Result<StageOutputs, AsmError> {
+    synthesise_stage_outputs(
+        params.seed,
+        params.rule_id,
+        params.sweeps,
+        params.modes,
+        params.k_points,
+    )
+}
+
+/// Executor measuring KPIs through the landscape stage pipeline.
+///
+/// Job parameters are mapped onto [`PipelineParams`] and handed to the stage
+/// runner, [`asm_land::stages::synthesise_stage_outputs`] by default. The
+/// KPIs declared in the plan tolerances are then read from the resulting
+/// [`asm_land::metrics::JobKpi`] and stage summaries:
+///
+/// * `energy_final`, `c_est`, `gap_proxy`, `xi`, `lambda_h`, `g1`..`g3`,
+/// * `spectral_gap` from the spectrum summary,
+/// * `closure_pass`, `ward_pass` and `anomaly_free` as `1.0` or `0.0`,
+/// * `factor_count`, the number of detected gauge factors,
+/// * any extractor KPI in `JobKpi::extra` by its namespaced key.
+///
+/// Undeclared KPIs are not reported, and declared ones the pipeline does not
+/// produce fail with `ablation-kpi-missing`.
+#[derive(Debug, Clone, Copy)]
+pub struct PipelineExecutor<S = StageRunner> {
+    stages: S,
+}
+
+impl PipelineExecutor {
+    /// Executor backed by the landscape stage synthesis.
+    pub fn new() -> Self {
+        Self {
+            stages: synthesise_stages,
+        }
+    }
+}
+
+impl Default for PipelineExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> PipelineExecutor<S>
+where
+    S: Fn(&PipelineParams) -> Result<StageOutputs, AsmError>,
+{
+    /// Executor running `stages` in place of the built-in stage synthesis.
+    pub fn with_stages(stages: S) -> Self {
+        Self { stages }
+    }
+}
+
+impl<S> AblationExecutor for PipelineExecutor<S>
+where
+    S: Fn(&PipelineParams) -> Result<StageOutputs, AsmError>,
+{
+    fn execute(&self, job: &AblationJob<'_>) -> Result<BTreeMap<String, f64>, AsmError> {
+        let outputs = (self.stages)(&PipelineParams::from_job(job)?)?;
+        let kpis = pipeline_kpis(&outputs);
+        Ok(job
+            .tolerances
+            .keys()
+            .filter_map(|name| kpis.get(name).map(|value| (name.clone(), *value)))
+            .collect())
+    }
+
+    fn name(&self) -> &str {
+        "pipeline"
+    }
+}
+
+fn pipeline_kpis(outputs: &StageOutputs) -> BTreeMap<String, f64> {
+    let flag = |value: bool| if value { 1.0 } else { 0.0 };
+    let kpi = &outputs.kpi;
+    let mut kpis = BTreeMap::from([
+        ("energy_final".to_string(), kpi.energy_final),
+        ("c_est".to_string(), kpi.c_est),
+        ("gap_proxy".to_string(), kpi.gap_proxy),
+        ("xi".to_string(), kpi.xi),
+        ("lambda_h".to_string(), kpi.lambda_h),
+        ("spectral_gap".to_string(), outputs.spectrum.spectral_gap),
+        ("closure_pass".to_string(), flag(kpi.closure_pass)),
+        ("ward_pass".to_string(), flag(kpi.ward_pass)),
+        ("factor_count".to_string(), kpi.factors.len() as f64),
+    ]);
+    for (idx, value) in kpi.g.iter().enumerate() {
+        kpis.insert(format!("g{}", idx + 1), *value);
+    }
+    if let Some(anomaly_free) = kpi.anomaly_free {
+        kpis.insert("anomaly_free".to_string(), flag(anomaly_free));
+    }
+    kpis.extend(kpi.extra.iter().map(|(name, value)| (name.clone(), *value)));
+    kpis
+}
+
+/// Execute a deterministic ablation plan with the [`HashExecutor`] and emit
+/// an [`AblationReport`].
 pub fn run_ablation(plan: &AblationPlan, seed: u64) -> Result<AblationReport, AsmError> {
-    let plan_hash = stable_hash_string(&(plan, seed))?;
+    run_ablation_with(plan, seed, &HashExecutor)
+}
+
+/// Execute an ablation plan, measuring each job's KPIs with `executor`.
+pub fn run_ablation_with<E>(
+    plan: &AblationPlan,
+    seed: u64,
+    executor: &E,
+) -> Result<AblationReport, AsmError>
+where
+    E: AblationExecutor + ?Sized,
+{
+    let plan_hash = plan.plan_hash(seed)?;
     let mut jobs = Vec::new();
     let mut aggregates: BTreeMap<String, f64> = BTreeMap::new();
     let mut pass_counts: BTreeMap<String, usize> = BTreeMap::new();
@@ -97,6 +391,11 @@ pub fn run_ablation(plan: &AblationPlan, seed: u64) -> Result<AblationReport, As
     let expanded = expand_jobs(plan, seed)?;
     for (idx, mut params) in expanded.into_iter().enumerate() {
         for (key, value) in &plan.fixed {
+            // OAT plans use fixed factor values as the baseline, which the
+            // expansion has already applied.
+            if plan.mode == AblationMode::Oat && plan.factors.contains_key(key) {
+                continue;
+            }
             params.insert(key.clone(), value.clone());
         }
         let params_value = Value::Object(params.clone());
@@ -109,7 +408,14 @@ pub fn run_ablation(plan: &AblationPlan, seed: u64) -> Result<AblationReport, As
             )));
         }
         let job_seed = seed ^ ((idx as u64 + 1).wrapping_mul(0x6a09_e667_f3bc_c908));
-        let metrics = build_job_metrics(&plan_hash, idx, &plan.tolerances, &params_value)?;
+        let job = AblationJob {
+            plan_hash: &plan_hash,
+            index: idx,
+            seed: job_seed,
+            params: &params_value,
+            tolerances: &plan.tolerances,
+        };
+        let metrics = build_job_metrics(&plan.tolerances, executor.execute(&job)?)?;
         accumulate_stats(
             &mut aggregates,
             &mut pass_counts,
@@ -120,10 +426,20 @@ pub fn run_ablation(plan: &AblationPlan, seed: u64) -> Result<AblationReport, As
             params: params_value,
             seed: job_seed,
             metrics,
+            executor: executor.name().to_string(),
         });
     }
 
-    let summary = build_summary(plan, seed, jobs.len(), &aggregates, &pass_counts);
+    let mut summary = build_summary(plan, seed, jobs.len(), &aggregates, &pass_counts);
+    if plan.mode == AblationMode::Oat {
+        let design = oat_design(plan)?;
+        if let Some(object) = summary.as_object_mut() {
+            object.insert(
+                "sensitivity".to_string(),
+                oat_sensitivity(&jobs, &design.variations)?,
+            );
+        }
+    }
 
     Ok(AblationReport {
         plan_name: plan.name.clone(),
@@ -171,18 +487,99 @@ fn build_summary(
     })
 }
 
+/// Per-factor KPI deltas of each OAT variation against the baseline job,
+/// which is always the first job.
+fn oat_sensitivity(
+    jobs: &[AblationJobReport],
+    variations: &[(String, Value)],
+) -> Result<Value, AsmError> {
+    let Some((baseline, varied)) = jobs.split_first() else {
+        return Ok(json!({}));
+    };
+    let baseline_kpis = job_kpi_values(baseline)?;
+    let mut levels: BTreeMap<&str, Vec<Value>> = BTreeMap::new();
+    let mut max_abs_delta: BTreeMap<&str, BTreeMap<String, f64>> = BTreeMap::new();
+    for ((name, value), job) in variations.iter().zip(varied) {
+        let mut deltas = BTreeMap::new();
+        for (kpi, kpi_value) in job_kpi_values(job)? {
+            let delta = kpi_value - baseline_kpis.get(&kpi).copied().unwrap_or(0.0);
+            let max = max_abs_delta
+                .entry(name.as_str())
+                .or_default()
+                .entry(kpi.clone())
+                .or_insert(0.0);
+            *max = max.max(delta.abs());
+            deltas.insert(kpi, delta);
+        }
+        levels
+            .entry(name.as_str())
+            .or_default()
+            .push(json!({ "value": value, "deltas": deltas }));
+    }
+
+    let mut sensitivity = Map::new();
+    for (name, levels) in levels {
+        sensitivity.insert(
+            name.to_string(),
+            json!({
+                "baseline": baseline.params.get(name).cloned().unwrap_or(Value::Null),
+                "levels": levels,
+                "max_abs_delta": max_abs_delta.remove(name).unwrap_or_default(),
+            }),
+        );
+    }
+    Ok(Value::Object(sensitivity))
+}
+
+fn job_kpi_values(job: &AblationJobReport) -> Result<BTreeMap<String, f64>, AsmError> {
+    let Some(kpis) = job.metrics.get("kpis").and_then(|v| v.as_object()) else {
+        return Err(AsmError::Serde(ErrorInfo::new(
+            "ablation-metrics-format",
+            "missing KPI payload in metrics",
+        )));
+    };
+    kpis.iter()
+        .map(|(name, payload)| {
+            payload
+                .get("value")
+                .and_then(|v| v.as_f64())
+                .map(|value| (name.clone(), value))
+                .ok_or_else(|| {
+                    AsmError::Serde(ErrorInfo::new(
+                        "ablation-kpi-value",
+                        "KPI missing numeric value",
+                    ))
+                })
+        })
+        .collect()
+}
+
 fn build_job_metrics(
-    plan_hash: &str,
-    idx: usize,
     tolerances: &BTreeMap<String, ToleranceSpec>,
-    params: &Value,
+    values: BTreeMap<String, f64>,
 ) -> Result<Value, AsmError> {
+    if let Some(name) = tolerances.keys().find(|name| !values.contains_key(*name)) {
+        return Err(AsmError::Serde(
+            ErrorInfo::new(
+                "ablation-kpi-missing",
+                "executor did not measure a KPI declared in the plan tolerances",
+            )
+            .with_context("kpi", name.clone()),
+        ));
+    }
     let mut kpis = Map::new();
-    for name in tolerances.keys() {
-        let tol = tolerances.get(name);
-        let value = derive_metric(plan_hash, idx, name, params, tol)?;
-        let pass = tol.map(|t| t.check_value(value)).unwrap_or(true);
-        kpis.insert(name.clone(), json!({ "value": value, "pass": pass }));
+    for (name, value) in values {
+        if !value.is_finite() {
+            return Err(AsmError::Serde(
+                ErrorInfo::new("ablation-kpi-value", "KPI value must be finite")
+                    .with_context("kpi", name),
+            ));
+        }
+        let pass = tolerances
+            .get(&name)
+            .map(|t| t.check_value(value))
+            .unwrap_or(true);
+        kpis.insert(name, json!({ "value": value, "pass": pass }));
     }
     Ok(json!({
         "status": "completed",
@@ -259,6 +656,12 @@ fn accumulate_stats(
 }
 
 fn expand_jobs(plan: &AblationPlan, seed: u64) -> Result<Vec<Map<String, Value>>, AsmError> {
+    if plan.correlation.is_some() && plan.mode != AblationMode::Lhs {
+        return Err(AsmError::Serde(ErrorInfo::new(
+            "ablation-lhs-correlation-mode",
+            "a correlation matrix is only supported in lhs mode",
+        )));
+    }
     match plan.mode {
         AblationMode::Grid => Ok(expand_grid(&plan.factors, Map::new(), 0)),
         AblationMode::Lhs => {
@@ -268,9 +671,59 @@ fn expand_jobs(plan: &AblationPlan, seed: u64) -> Result<Vec<Map<String, Value>>
                     "lhs mode requires `samples` to be set",
                 ))
             })?;
-            expand_lhs(&plan.factors, samples, seed)
+            expand_lhs(&plan.factors, samples, seed, plan.correlation.as_deref())
+        }
+        AblationMode::Oat => {
+            let design = oat_design(plan)?;
+            let mut jobs = vec![design.baseline.clone()];
+            for (name, value) in design.variations {
+                let mut params = design.baseline.clone();
+                params.insert(name, value);
+                jobs.push(params);
+            }
+            Ok(jobs)
+        }
+    }
+}
+
+/// Baseline parameters and the ordered single-factor variations of an OAT plan.
+struct OatDesign {
+    baseline: Map<String, Value>,
+    variations: Vec<(String, Value)>,
+}
+
+/// Each factor's baseline is its `fixed` value when present, otherwise its
+/// middle level; every other level of the factor becomes one variation.
+fn oat_design(plan: &AblationPlan) -> Result<OatDesign, AsmError> {
+    let mut baseline = Map::new();
+    let mut variations = Vec::new();
+    for (name, values) in &plan.factors {
+        let base = match plan.fixed.get(name) {
+            Some(value) => value.clone(),
+            None => values
+                .get(values.len().saturating_sub(1) / 2)
+                .cloned()
+                .ok_or_else(|| {
+                    AsmError::Serde(
+                        ErrorInfo::new(
+                            "ablation-oat-levels",
+                            "oat factors require at least one value",
+                        )
+                        .with_context("factor", name.clone()),
+                    )
+                })?,
+        };
+        for value in values {
+            if *value != base {
+                variations.push((name.clone(), value.clone()));
+            }
         }
+        baseline.insert(name.clone(), base);
     }
+    Ok(OatDesign {
+        baseline,
+        variations,
+    })
 }
 
 fn expand_grid(
@@ -296,6 +749,7 @@ fn expand_lhs(
     factors: &BTreeMap<String, Vec<Value>>,
     samples: usize,
     seed: u64,
+    correlation: Option<&[Vec<f64>]>,
 ) -> Result<Vec<Map<String, Value>>, AsmError> {
     if samples == 0 {
         return Err(AsmError::Rng(ErrorInfo::new(
@@ -303,43 +757,241 @@ fn expand_lhs(
             "lhs sampling requires at least one sample",
         )));
     }
-    let mut outputs = vec![Map::new(); samples];
     let mut rng = StdRng::seed_from_u64(seed);
     let base_slots: Vec<f64> = (0..samples)
         .map(|i| (i as f64 + 0.5) / samples as f64)
         .collect();
+    let mut levels = Vec::with_capacity(factors.len());
+    let mut columns = Vec::with_capacity(factors.len());
     for (name, values) in factors {
         if values.len() < 2 {
             return Err(AsmError::Serde(
                 ErrorInfo::new(
                     "ablation-lhs-range",
-                    "lhs factors require at least [min, max]",
+                    "lhs factors require at least [min, max] or two levels",
                 )
                 .with_context("factor", name.clone()),
             ));
         }
-        let min = values[0].as_f64().ok_or_else(|| {
-            AsmError::Serde(
-                ErrorInfo::new("ablation-lhs-min", "lhs min value must be numeric")
-                    .with_context("factor", name.clone()),
-            )
-        })?;
-        let max = values[values.len() - 1].as_f64().ok_or_else(|| {
-            AsmError::Serde(
-                ErrorInfo::new("ablation-lhs-max", "lhs max value must be numeric")
-                    .with_context("factor", name.clone()),
-            )
-        })?;
-        let mut slots = base_slots.clone();
+        let mut slots: Vec<usize> = (0..samples).collect();
         slots.shuffle(&mut rng);
+        levels.push((name, values, FactorKind::infer(values)));
+        columns.push(slots);
+    }
+    if let Some(target) = correlation {
+        let target = correlation_matrix(target, factors.len())?;
+        columns = iman_conover(&columns, &target, &mut rng)?;
+    }
+    let mut outputs = vec![Map::new(); samples];
+    for ((name, values, kind), slots) in levels.into_iter().zip(columns) {
         for (idx, slot) in slots.into_iter().enumerate() {
-            let value = min + (max - min) * slot;
-            outputs[idx].insert(name.clone(), json!(value));
+            let value = lhs_value(values, kind, base_slots[slot]);
+            outputs[idx].insert(name.clone(), value);
         }
     }
     Ok(outputs)
 }
 
+/// Maps the unit-interval LHS position `u` onto a factor value of `kind`.
+fn lhs_value(values: &[Value], kind: FactorKind, u: f64) -> Value {
+    match kind {
+        FactorKind::Continuous => {
+            let min = values[0].as_f64().unwrap_or_default();
+            let max = values[values.len() - 1].as_f64().unwrap_or_default();
+            json!(min + (max - min) * u)
+        }
+        FactorKind::Integer => {
+            let min = values[0].as_i64().unwrap_or_default();
+            let max = values[values.len() - 1].as_i64().unwrap_or_default();
+            let value = (min as f64 + (max - min) as f64 * u).round() as i64;
+            json!(value.clamp(min.min(max), min.max(max)))
+        }
+        FactorKind::Categorical => {
+            let level = ((u * values.len() as f64) as usize).min(values.len() - 1);
+            values[level].clone()
+        }
+    }
+}
+
+const CORRELATION_TOLERANCE: f64 = 1e-9;
+
+/// Validates that `rows` is a `dim x dim` correlation matrix: symmetric, unit
+/// diagonal, entries in `[-1, 1]` and positive semidefinite.
+fn correlation_matrix(rows: &[Vec<f64>], dim: usize) -> Result<DMatrix<f64>, AsmError> {
+    let invalid = |message: &str| {
+        AsmError::Serde(
+            ErrorInfo::new("ablation-lhs-correlation", message)
+                .with_context("factors", dim.to_string()),
+        )
+    };
+    if rows.len() != dim || rows.iter().any(|row| row.len() != dim) {
+        return Err(invalid(
+            "correlation matrix must be square over the factors",
+        ));
+    }
+    let matrix = DMatrix::from_fn(dim, dim, |i, j| rows[i][j]);
+    for i in 0..dim {
+        if (matrix[(i, i)] - 1.0).abs() > CORRELATION_TOLERANCE {
+            return Err(invalid("correlation matrix must have a unit diagonal"));
+        }
+        for j in 0..dim {
+            let value = matrix[(i, j)];
+            if !value.is_finite() || value.abs() > 1.0 + CORRELATION_TOLERANCE {
+                return Err(invalid("correlations must lie in [-1, 1]"));
+            }
+            if (value - matrix[(j, i)]).abs() > CORRELATION_TOLERANCE {
+                return Err(invalid("correlation matrix must be symmetric"));
+            }
+        }
+    }
+    let min_eigenvalue = matrix
+        .clone()
+        .symmetric_eigenvalues()
+        .iter()
+        .copied()
+        .fold(f64::INFINITY, f64::min);
+    if min_eigenvalue < -CORRELATION_TOLERANCE {
+        return Err(AsmError::Serde(
+            ErrorInfo::new(
+                "ablation-lhs-correlation-psd",
+                "correlation matrix must be positive semidefinite",
+            )
+            .with_context("min_eigenvalue", format!("{min_eigenvalue:.3e}")),
+        ));
+    }
+    Ok(matrix)
+}
+
+/// Reorders each column of slot indices so the rank correlation between
+/// columns approximates `target` (Iman & Conover, 1982).
+///
+/// Van der Waerden scores are permuted per column with `rng`, decorrelated by
+/// the Cholesky factor of their sample correlation and recoloured with a
+/// square root of `target`; each column then takes the ranks of its scores.
+fn iman_conover(
+    columns: &[Vec<usize>],
+    target: &DMatrix<f64>,
+    rng: &mut StdRng,
+) -> Result<Vec<Vec<usize>>, AsmError> {
+    let samples = columns.first().map_or(0, Vec::len);
+    let dim = columns.len();
+    if dim < 2 {
+        return Ok(columns.to_vec());
+    }
+    if samples <= dim {
+        return Err(AsmError::Rng(
+            ErrorInfo::new(
+                "ablation-lhs-correlation-samples",
+                "correlation control requires more samples than factors",
+            )
+            .with_context("samples", samples.to_string())
+            .with_context("factors", dim.to_string()),
+        ));
+    }
+    let mean_score = (1..=samples)
+        .map(|i| inverse_normal_cdf(i as f64 / (samples + 1) as f64))
+        .collect::<Vec<_>>();
+    let mut scores = DMatrix::zeros(samples, dim);
+    for col in 0..dim {
+        let mut column = mean_score.clone();
+        column.shuffle(rng);
+        for (row, value) in column.into_iter().enumerate() {
+            scores[(row, col)] = value;
+        }
+    }
+    let spread = scores.transpose() * &scores / samples as f64;
+    let diag = spread.diagonal().map(f64::sqrt);
+    let sample = DMatrix::from_fn(dim, dim, |i, j| spread[(i, j)] / (diag[i] * diag[j]));
+    let decorrelate = sample
+        .cholesky()
+        .ok_or_else(|| {
+            AsmError::Rng(ErrorInfo::new(
+                "ablation-lhs-correlation-samples",
+                "score correlation is singular; increase the sample count",
+            ))
+        })?
+        .l();
+    let eigen = target.clone().symmetric_eigen();
+    let root = &eigen.eigenvectors
+        * DMatrix::from_diagonal(&eigen.eigenvalues.map(|value| value.max(0.0).sqrt()));
+    let transform = decorrelate
+        .transpose()
+        .solve_upper_triangular(&root.transpose())
+        .ok_or_else(|| {
+            AsmError::Rng(ErrorInfo::new(
+                "ablation-lhs-correlation-singular",
+                "cholesky factor of the score correlation is not invertible",
+            ))
+        })?;
+    let induced = scores * transform;
+
+    Ok((0..dim)
+        .map(|col| {
+            let mut order: Vec<usize> = (0..samples).collect();
+            order.sort_by(|&a, &b| {
+                induced[(a, col)]
+                    .total_cmp(&induced[(b, col)])
+                    .then(a.cmp(&b))
+            });
+            let mut sorted = columns[col].clone();
+            sorted.sort_unstable();
+            let mut reordered = vec![0; samples];
+            for (rank, row) in order.into_iter().enumerate() {
+                reordered[row] = sorted[rank];
+            }
+            reordered
+        })
+        .collect())
+}
+
+/// Acklam's rational approximation of the standard normal quantile function.
+pub(crate) fn inverse_normal_cdf(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const LOW: f64 = 0.024_25;
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,12 +1,22 @@
-use asm_core::errors::AsmError;
+use std::collections::BTreeMap;
+
+use asm_core::errors::{AsmError, ErrorInfo};
 use asm_rg::StateRef;
 use rand::SeedableRng;
 use rand::{rngs::StdRng, Rng};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::gaps::{gap_for_hash, GapMethod};
 use crate::hash::{canonical_state_hash, stable_hash_string};
 
+/// Maximum number of search iterations spent on a [`DeformSpec::target_gap`].
+pub const TARGET_GAP_MAX_ITERATIONS: usize = 64;
+/// Absolute gap difference at which a target-gap search counts as converged.
+pub const TARGET_GAP_TOLERANCE: f64 = 1e-6;
+/// KPI read from the path evaluator during a target-gap search.
+const GAP_KPI: &str = "gap";
+
 /// Describes a deterministic deformation to apply to a state or RG step.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DeformSpec {
@@ -15,6 +25,68 @@ pub struct DeformSpec {
     /// Operation parameters expressed as structured JSON.
     #[serde(default)]
     pub params: Value,
+    /// Number of evenly spaced points on the interpolation path `t in [0, 1]`.
+    /// Zero applies the deformation once without a path.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub steps: usize,
+    /// Optional bisection of path intervals whose KPI jumps too far.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive: Option<AdaptiveRefinement>,
+    /// Searches the deformation strength `t in [0, 1]` for the value whose
+    /// estimated gap matches this target instead of walking a path.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_gap: Option<f64>,
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+/// Controls adaptive refinement of a deformation path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdaptiveRefinement {
+    /// Maximum number of successive bisections of any coarse interval.
+    pub max_depth: usize,
+    /// KPI compared between neighbouring path points.
+    pub kpi: String,
+    /// Absolute KPI difference above which an interval is bisected.
+    pub jump_threshold: f64,
+}
+
+/// KPIs evaluated at a single point of the deformation path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeformStep {
+    /// Interpolation parameter in `[0, 1]`.
+    pub t: f64,
+    /// Bisection depth at which the point was introduced (0 for the coarse grid).
+    pub depth: usize,
+    pub kpis: BTreeMap<String, f64>,
+    pub end_state_hash: String,
+}
+
+/// Interval bisected during adaptive refinement.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefinedInterval {
+    pub lo: f64,
+    pub hi: f64,
+    /// Depth of the interval before bisection.
+    pub depth: usize,
+    /// Absolute KPI difference across the interval that triggered the bisection.
+    pub jump: f64,
+}
+
+/// Outcome of a [`DeformSpec::target_gap`] search.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GapTargetOutcome {
+    pub target: f64,
+    /// Gap estimated at the best strength found.
+    pub achieved: f64,
+    /// Deformation strength at which `achieved` was estimated.
+    pub t: f64,
+    /// Search iterations after the two bracket endpoints were evaluated.
+    pub iterations: usize,
+    /// Whether `achieved` lies within [`TARGET_GAP_TOLERANCE`] of the target.
+    pub converged: bool,
 }
 
 /// Summary describing a completed deformation.
@@ -28,22 +100,178 @@ pub struct DeformationReport {
     pub end_state_hashes: Vec<String>,
     #[serde(default)]
     pub notes: String,
+    /// Path points sorted by `t`; empty when the spec has no path.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<DeformStep>,
+    /// Intervals bisected by adaptive refinement, in evaluation order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub refinements: Vec<RefinedInterval>,
+    /// Result of the target-gap search when the spec sets `target_gap`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<GapTargetOutcome>,
 }
 
 /// Applies a deterministic deformation described by [`DeformSpec`].
+///
+/// Target-gap searches estimate the gap of the input state and of the fully
+/// deformed state with the spectral surrogate from [`crate::estimate_gaps`]
+/// and interpolate linearly in the deformation strength.
 pub fn deform(
     input: &StateRef<'_>,
     spec: &DeformSpec,
     seed: u64,
 ) -> Result<DeformationReport, AsmError> {
+    if spec.target_gap.is_none() {
+        return deform_with(input, spec, seed, path_kpis);
+    }
+    let input_hash = canonical_state_hash(input)?;
+    let start = gap_for_hash(&input_hash, GapMethod::Spectral)?;
+    let end_hash = end_state_hash(&input_hash, spec, seed, 1.0)?;
+    let end = gap_for_hash(&end_hash, GapMethod::Spectral)?;
+    deform_with(input, spec, seed, move |seed, t| {
+        let mut kpis = path_kpis(seed, t);
+        kpis.insert(GAP_KPI.to_string(), start + (end - start) * t);
+        kpis
+    })
+}
+
+fn end_state_hash(
+    input_hash: &str,
+    spec: &DeformSpec,
+    seed: u64,
+    t: f64,
+) -> Result<String, AsmError> {
+    stable_hash_string(&(input_hash, &spec.mode, seed, t.to_bits()))
+}
+
+/// Applies a deformation, evaluating path KPIs with `evaluate(seed, t)`.
+///
+/// The evaluator must be a pure function of its arguments so that re-runs
+/// reproduce the same refinement tree.
+pub fn deform_with<F>(
+    input: &StateRef<'_>,
+    spec: &DeformSpec,
+    seed: u64,
+    evaluate: F,
+) -> Result<DeformationReport, AsmError>
+where
+    F: Fn(u64, f64) -> BTreeMap<String, f64>,
+{
     let input_hash = canonical_state_hash(input)?;
-    let deform_hash = stable_hash_string(&(spec, seed))?;
     let mut rng = StdRng::seed_from_u64(seed);
     let n_ops = rng.gen_range(0..=3);
-    let end_hash_seed = stable_hash_string(&(input_hash.clone(), &spec.mode, seed))?;
-    let end_state_hashes = vec![end_hash_seed];
-    let notes = format!("mode={} ops={}", spec.mode, n_ops);
 
+    if let Some(target) = spec.target_gap {
+        if spec.steps != 0 || spec.adaptive.is_some() {
+            return Err(deform_error(
+                "deform-target-path",
+                "a target-gap search cannot be combined with a deformation path",
+            ));
+        }
+        if !target.is_finite() || target < 0.0 {
+            return Err(AsmError::Serde(
+                ErrorInfo::new(
+                    "deform-target-gap",
+                    "target gap must be finite and non-negative",
+                )
+                .with_context("target_gap", target.to_string()),
+            ));
+        }
+        let outcome = search_target_gap(target, |t| kpi_value(&evaluate(seed, t), GAP_KPI))?;
+        let deform_hash = stable_hash_string(&(spec, seed, &outcome))?;
+        let notes = format!(
+            "mode={} ops={} target_gap={} achieved={} iterations={} converged={}",
+            spec.mode, n_ops, target, outcome.achieved, outcome.iterations, outcome.converged
+        );
+        return Ok(DeformationReport {
+            input_hash: input_hash.clone(),
+            deform_hash,
+            params: spec.params.clone(),
+            n_ops,
+            invariants_ok: true,
+            end_state_hashes: vec![end_state_hash(&input_hash, spec, seed, outcome.t)?],
+            notes,
+            steps: Vec::new(),
+            refinements: Vec::new(),
+            target: Some(outcome),
+        });
+    }
+
+    if spec.steps == 0 {
+        if spec.adaptive.is_some() {
+            return Err(deform_error(
+                "deform-adaptive-steps",
+                "adaptive refinement requires at least two path steps",
+            ));
+        }
+        let deform_hash = stable_hash_string(&(spec, seed))?;
+        let end_hash_seed = stable_hash_string(&(input_hash.clone(), &spec.mode, seed))?;
+        let notes = format!("mode={} ops={}", spec.mode, n_ops);
+        return Ok(DeformationReport {
+            input_hash,
+            deform_hash,
+            params: spec.params.clone(),
+            n_ops,
+            invariants_ok: true,
+            end_state_hashes: vec![end_hash_seed],
+            notes,
+            steps: Vec::new(),
+            refinements: Vec::new(),
+            target: None,
+        });
+    }
+    if spec.steps < 2 {
+        return Err(deform_error(
+            "deform-path-steps",
+            "a deformation path needs at least two steps",
+        ));
+    }
+
+    let point = |t: f64, depth: usize| -> Result<DeformStep, AsmError> {
+        Ok(DeformStep {
+            t,
+            depth,
+            kpis: evaluate(seed, t),
+            end_state_hash: end_state_hash(&input_hash, spec, seed, t)?,
+        })
+    };
+
+    let last = (spec.steps - 1) as f64;
+    let coarse = (0..spec.steps)
+        .map(|idx| point(idx as f64 / last, 0))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut refinements = Vec::new();
+    let steps = match &spec.adaptive {
+        None => coarse,
+        Some(adaptive) => {
+            let mut steps = vec![coarse[0].clone()];
+            for pair in coarse.windows(2) {
+                refine(
+                    &pair[0],
+                    &pair[1],
+                    adaptive,
+                    &point,
+                    &mut steps,
+                    &mut refinements,
+                )?;
+            }
+            steps
+        }
+    };
+
+    let deform_hash = stable_hash_string(&(spec, seed, &steps, &refinements))?;
+    let end_state_hashes = steps
+        .iter()
+        .map(|step| step.end_state_hash.clone())
+        .collect();
+    let notes = format!(
+        "mode={} ops={} steps={} refined={}",
+        spec.mode,
+        n_ops,
+        steps.len(),
+        refinements.len()
+    );
     Ok(DeformationReport {
         input_hash,
         deform_hash,
@@ -52,15 +280,155 @@ pub fn deform(
         invariants_ok: true,
         end_state_hashes,
         notes,
+        steps,
+        refinements,
+        target: None,
+    })
+}
+
+/// Searches `t in [0, 1]` for `gap_at(t) == target` with an Illinois-style
+/// regula falsi, falling back to bisection when the secant step leaves the
+/// bracket. Targets outside the endpoint gaps end unconverged at the closer
+/// endpoint without further iterations.
+fn search_target_gap<G>(target: f64, gap_at: G) -> Result<GapTargetOutcome, AsmError>
+where
+    G: Fn(f64) -> Result<f64, AsmError>,
+{
+    let (mut lo, mut hi) = (0.0_f64, 1.0_f64);
+    let mut f_lo = gap_at(lo)? - target;
+    let mut f_hi = gap_at(hi)? - target;
+    let mut best = if f_lo.abs() <= f_hi.abs() {
+        (lo, f_lo)
+    } else {
+        (hi, f_hi)
+    };
+    let bracketed = f_lo.signum() != f_hi.signum();
+    let mut iterations = 0;
+    let mut last_side = 0_i8;
+    while bracketed && best.1.abs() > TARGET_GAP_TOLERANCE && iterations < TARGET_GAP_MAX_ITERATIONS
+    {
+        iterations += 1;
+        let secant = hi - f_hi * (hi - lo) / (f_hi - f_lo);
+        let t = if secant.is_finite() && secant > lo && secant < hi {
+            secant
+        } else {
+            0.5 * (lo + hi)
+        };
+        let f = gap_at(t)? - target;
+        if f.abs() < best.1.abs() {
+            best = (t, f);
+        }
+        if f.signum() == f_lo.signum() {
+            lo = t;
+            f_lo = f;
+            if last_side == -1 {
+                f_hi *= 0.5;
+            }
+            last_side = -1;
+        } else {
+            hi = t;
+            f_hi = f;
+            if last_side == 1 {
+                f_lo *= 0.5;
+            }
+            last_side = 1;
+        }
+    }
+    Ok(GapTargetOutcome {
+        target,
+        achieved: best.1 + target,
+        t: best.0,
+        iterations,
+        converged: best.1.abs() <= TARGET_GAP_TOLERANCE,
+    })
+}
+
+/// Appends the points strictly after `lo` up to and including `hi`, bisecting
+/// depth-first so `out` stays sorted by `t`.
+fn refine<P>(
+    lo: &DeformStep,
+    hi: &DeformStep,
+    adaptive: &AdaptiveRefinement,
+    point: &P,
+    out: &mut Vec<DeformStep>,
+    refinements: &mut Vec<RefinedInterval>,
+) -> Result<(), AsmError>
+where
+    P: Fn(f64, usize) -> Result<DeformStep, AsmError>,
+{
+    let depth = lo.depth.max(hi.depth);
+    let jump = (kpi_value(&hi.kpis, &adaptive.kpi)? - kpi_value(&lo.kpis, &adaptive.kpi)?).abs();
+    if depth < adaptive.max_depth && jump > adaptive.jump_threshold {
+        refinements.push(RefinedInterval {
+            lo: lo.t,
+            hi: hi.t,
+            depth,
+            jump,
+        });
+        let mid = point(0.5 * (lo.t + hi.t), depth + 1)?;
+        refine(lo, &mid, adaptive, point, out, refinements)?;
+        refine(&mid, hi, adaptive, point, out, refinements)?;
+    } else {
+        out.push(hi.clone());
+    }
+    Ok(())
+}
+
+fn kpi_value(kpis: &BTreeMap<String, f64>, kpi: &str) -> Result<f64, AsmError> {
+    kpis.get(kpi).copied().ok_or_else(|| {
+        AsmError::Serde(
+            ErrorInfo::new("deform-kpi", "KPI missing from path evaluation")
+                .with_context("kpi", kpi.to_string()),
+        )
     })
 }
 
+/// Default path KPIs derived from the seed and interpolation parameter.
+fn path_kpis(seed: u64, t: f64) -> BTreeMap<String, f64> {
+    let mut rng = StdRng::seed_from_u64(seed ^ t.to_bits());
+    let mut kpis = BTreeMap::new();
+    kpis.insert("n_ops".to_string(), rng.gen_range(0..=3) as f64);
+    kpis.insert("strength".to_string(), t);
+    kpis
+}
+
+fn deform_error(code: &str, message: &str) -> AsmError {
+    AsmError::Serde(ErrorInfo::new(code, message))
+}
+
 impl DeformSpec {
     /// Constructs a graph degree tweak deformation specification.
     pub fn degree_tweak(delta: i32) -> Self {
         Self {
             mode: "graph-degree".to_string(),
             params: serde_json::json!({"delta": delta}),
+            steps: 0,
+            adaptive: None,
+            target_gap: None,
         }
     }
+
+    /// Evaluates the deformation along `steps` evenly spaced path points and
+    /// bisects intervals whose `kpi` jumps by more than `jump_threshold`.
+    pub fn adaptive(
+        mut self,
+        steps: usize,
+        max_depth: usize,
+        kpi: impl Into<String>,
+        jump_threshold: f64,
+    ) -> Self {
+        self.steps = steps;
+        self.adaptive = Some(AdaptiveRefinement {
+            max_depth,
+            kpi: kpi.into(),
+            jump_threshold,
+        });
+        self
+    }
+
+    /// Searches the deformation strength for the value whose gap matches `target`.
+    pub fn target_gap(mut self, target: f64) -> Self {
+        self.target_gap = Some(target);
+        self
+    }
 }
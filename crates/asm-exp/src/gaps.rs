@@ -1,9 +1,10 @@
-use asm_core::errors::AsmError;
+use asm_core::errors::{AsmError, ErrorInfo};
 use asm_rg::StateRef;
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::ablations::inverse_normal_cdf;
 use crate::hash::{canonical_state_hash, stable_hash_string};
 
 /// Supported deterministic gap estimation methods.
@@ -12,6 +13,19 @@ use crate::hash::{canonical_state_hash, stable_hash_string};
 pub enum GapMethod {
     Dispersion,
     Spectral,
+    /// Power-law extrapolation of [`GapOpts::size_series`] to infinite size.
+    #[serde(rename = "finite_size_scaling")]
+    FiniteSizeScaling,
+}
+
+impl GapMethod {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Dispersion => "dispersion",
+            Self::Spectral => "spectral",
+            Self::FiniteSizeScaling => "finite_size_scaling",
+        }
+    }
 }
 
 /// Configuration describing the desired estimator and thresholds.
@@ -22,6 +36,43 @@ pub struct GapOpts {
     pub thresholds: Value,
     #[serde(default = "GapOpts::default_tolerance")]
     pub tolerance: f64,
+    /// Resampling used to attach an interval and fit quality to the estimate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uncertainty: Option<GapUncertainty>,
+    /// Gaps measured at several system sizes, required by
+    /// [`GapMethod::FiniteSizeScaling`] and ignored otherwise.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub size_series: Vec<SizedGap>,
+}
+
+/// Gap measured on a system of linear size `size`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SizedGap {
+    pub size: f64,
+    pub gap: f64,
+}
+
+/// Phase classification drawn from a finite-size extrapolation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScalingVerdict {
+    /// The extrapolated gap stays above the tolerance.
+    Gapped,
+    /// The extrapolated gap closes to within the tolerance.
+    Gapless,
+    /// The power law does not describe the data well enough to decide.
+    Inconclusive,
+}
+
+/// Power-law fit `gap(L) = gap_inf + amplitude * L^(-exponent)`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FiniteSizeFit {
+    pub extrapolated_gap: f64,
+    pub exponent: f64,
+    pub amplitude: f64,
+    /// Root-mean-square residual of the fit over the size series.
+    pub rms_residual: f64,
+    pub verdict: ScalingVerdict,
 }
 
 impl GapOpts {
@@ -30,6 +81,67 @@ impl GapOpts {
     }
 }
 
+/// Resampling scheme applied to the per-sample gap estimates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResamplingMethod {
+    /// Leave-one-out standard error with a normal interval.
+    Jackknife,
+    /// Percentile interval over seeded resamples of the sample means.
+    Bootstrap,
+}
+
+/// Requested uncertainty quantification for [`estimate_gaps`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GapUncertainty {
+    pub method: ResamplingMethod,
+    /// Two-sided confidence level in `(0, 1)`.
+    #[serde(default = "GapUncertainty::default_level")]
+    pub level: f64,
+    /// Number of bootstrap resamples; ignored by the jackknife.
+    #[serde(default = "GapUncertainty::default_resamples")]
+    pub resamples: usize,
+}
+
+impl GapUncertainty {
+    const fn default_level() -> f64 {
+        0.95
+    }
+
+    const fn default_resamples() -> usize {
+        200
+    }
+
+    /// Jackknife interval at the default confidence level.
+    pub fn jackknife() -> Self {
+        Self {
+            method: ResamplingMethod::Jackknife,
+            level: Self::default_level(),
+            resamples: Self::default_resamples(),
+        }
+    }
+
+    /// Bootstrap interval with `resamples` draws at the default confidence level.
+    pub fn bootstrap(resamples: usize) -> Self {
+        Self {
+            method: ResamplingMethod::Bootstrap,
+            level: Self::default_level(),
+            resamples,
+        }
+    }
+}
+
+/// Resampled interval on the gap estimate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GapInterval {
+    pub method: ResamplingMethod,
+    pub level: f64,
+    /// `[low, high]` bounds of the interval.
+    pub bounds: [f64; 2],
+    /// Resampled standard error of the gap estimate.
+    pub std_error: f64,
+}
+
 /// Structured summary of a gap estimation run.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GapReport {
@@ -39,31 +151,321 @@ pub struct GapReport {
     pub residuals: Vec<f64>,
     pub passes: bool,
     pub thresholds: Value,
+    /// Resampled interval, present when [`GapOpts::uncertainty`] is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval: Option<GapInterval>,
+    /// Root-mean-square residual of the dispersion extrapolation, reported
+    /// alongside `interval`. Spectral estimates have no fit and leave it empty.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fit_residual: Option<f64>,
+    /// Finite-size extrapolation, present for [`GapMethod::FiniteSizeScaling`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scaling: Option<FiniteSizeFit>,
+}
+
+fn gap_rng(state_hash: &str, method: GapMethod) -> Result<StdRng, AsmError> {
+    let seed_hash = stable_hash_string(&(state_hash.to_string(), &method))?;
+    let seed = u64::from_str_radix(&seed_hash[..16], 16).unwrap_or(0);
+    Ok(StdRng::seed_from_u64(seed))
+}
+
+fn draw_gap(rng: &mut StdRng) -> f64 {
+    0.1 + rng.gen::<f64>() * 1.2
+}
+
+/// Rounded gap estimate for the state identified by `state_hash`.
+///
+/// Matches the `gap_value` that [`estimate_gaps`] reports for a state with the
+/// same canonical hash.
+pub(crate) fn gap_for_hash(state_hash: &str, method: GapMethod) -> Result<f64, AsmError> {
+    let raw_gap = draw_gap(&mut gap_rng(state_hash, method)?);
+    Ok((raw_gap * 1e9).round() / 1e9)
+}
+
+fn round9(value: f64) -> f64 {
+    (value * 1e9).round() / 1e9
+}
+
+fn uncertainty_error(message: &str, uncertainty: &GapUncertainty) -> AsmError {
+    AsmError::Serde(
+        ErrorInfo::new("gap-uncertainty", message)
+            .with_context("level", uncertainty.level.to_string())
+            .with_context("resamples", uncertainty.resamples.to_string()),
+    )
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Linear-interpolated quantile of ascending `sorted` values.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let lo = pos.floor() as usize;
+    let hi = pos.ceil() as usize;
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
+/// Resamples the per-sample gap estimates; bootstrap draws continue `rng`
+/// so the interval is a pure function of the state hash and options.
+fn resampled_interval(
+    samples: &[f64],
+    uncertainty: &GapUncertainty,
+    rng: &mut StdRng,
+) -> Result<GapInterval, AsmError> {
+    if !(uncertainty.level > 0.0 && uncertainty.level < 1.0) {
+        return Err(uncertainty_error(
+            "confidence level must lie strictly between 0 and 1",
+            uncertainty,
+        ));
+    }
+    let alpha = 1.0 - uncertainty.level;
+    let n = samples.len();
+    let (bounds, std_error) = match uncertainty.method {
+        ResamplingMethod::Jackknife => {
+            let total: f64 = samples.iter().sum();
+            let leave_one_out: Vec<f64> = samples
+                .iter()
+                .map(|sample| (total - sample) / (n - 1) as f64)
+                .collect();
+            let centre = mean(&leave_one_out);
+            let spread: f64 = leave_one_out
+                .iter()
+                .map(|value| (value - centre).powi(2))
+                .sum();
+            let std_error = ((n - 1) as f64 / n as f64 * spread).sqrt();
+            let z = inverse_normal_cdf(1.0 - alpha / 2.0);
+            let estimate = mean(samples);
+            (
+                [estimate - z * std_error, estimate + z * std_error],
+                std_error,
+            )
+        }
+        ResamplingMethod::Bootstrap => {
+            if uncertainty.resamples < 2 {
+                return Err(uncertainty_error(
+                    "bootstrap needs at least two resamples",
+                    uncertainty,
+                ));
+            }
+            let mut means: Vec<f64> = (0..uncertainty.resamples)
+                .map(|_| (0..n).map(|_| samples[rng.gen_range(0..n)]).sum::<f64>() / n as f64)
+                .collect();
+            means.sort_by(|a, b| a.total_cmp(b));
+            let centre = mean(&means);
+            let spread: f64 = means.iter().map(|value| (value - centre).powi(2)).sum();
+            let std_error = (spread / (means.len() - 1) as f64).sqrt();
+            (
+                [
+                    quantile(&means, alpha / 2.0),
+                    quantile(&means, 1.0 - alpha / 2.0),
+                ],
+                std_error,
+            )
+        }
+    };
+    Ok(GapInterval {
+        method: uncertainty.method,
+        level: uncertainty.level,
+        bounds: [round9(bounds[0].max(0.0)), round9(bounds[1].max(0.0))],
+        std_error: round9(std_error),
+    })
+}
+
+/// Minimum number of sizes needed to fit the three power-law parameters.
+const MIN_SCALING_SIZES: usize = 3;
+/// Exponent search range and grid step for the power-law fit.
+const EXPONENT_RANGE: (f64, f64) = (0.05, 4.0);
+const EXPONENT_STEP: f64 = 0.01;
+/// Fits whose RMS residual exceeds this fraction of the gap spread are inconclusive.
+const INCONCLUSIVE_RESIDUAL: f64 = 0.1;
+
+fn scaling_error(message: impl Into<String>) -> AsmError {
+    AsmError::Serde(ErrorInfo::new("gap-finite-size", message.into()))
+}
+
+/// Least-squares `gap = a + b * size^(-exponent)` at a fixed exponent,
+/// returning `(a, b, sum of squared residuals)`.
+fn linear_fit(series: &[SizedGap], exponent: f64) -> (f64, f64, f64) {
+    let n = series.len() as f64;
+    let xs: Vec<f64> = series.iter().map(|p| p.size.powf(-exponent)).collect();
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = series.iter().map(|p| p.gap).sum::<f64>() / n;
+    let sxx: f64 = xs.iter().map(|x| (x - mean_x).powi(2)).sum();
+    let sxy: f64 = xs
+        .iter()
+        .zip(series)
+        .map(|(x, p)| (x - mean_x) * (p.gap - mean_y))
+        .sum();
+    let slope = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+    let intercept = mean_y - slope * mean_x;
+    let ssr = xs
+        .iter()
+        .zip(series)
+        .map(|(x, p)| (p.gap - intercept - slope * x).powi(2))
+        .sum();
+    (intercept, slope, ssr)
+}
+
+/// Scans the exponent on a fixed grid, then refines the best cell with a
+/// golden-section search so the fit is a pure function of the series.
+fn power_law_fit(series: &[SizedGap]) -> (f64, f64, f64, f64) {
+    let steps = ((EXPONENT_RANGE.1 - EXPONENT_RANGE.0) / EXPONENT_STEP).round() as usize;
+    let mut best = (EXPONENT_RANGE.0, f64::INFINITY);
+    for step in 0..=steps {
+        let exponent = EXPONENT_RANGE.0 + step as f64 * EXPONENT_STEP;
+        let (.., ssr) = linear_fit(series, exponent);
+        if ssr < best.1 {
+            best = (exponent, ssr);
+        }
+    }
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let mut lo = (best.0 - EXPONENT_STEP).max(EXPONENT_RANGE.0);
+    let mut hi = (best.0 + EXPONENT_STEP).min(EXPONENT_RANGE.1);
+    for _ in 0..40 {
+        let left = hi - ratio * (hi - lo);
+        let right = lo + ratio * (hi - lo);
+        if linear_fit(series, left).2 <= linear_fit(series, right).2 {
+            hi = right;
+        } else {
+            lo = left;
+        }
+    }
+    let refined = 0.5 * (lo + hi);
+    let exponent = if linear_fit(series, refined).2 <= best.1 {
+        refined
+    } else {
+        best.0
+    };
+    let (intercept, slope, ssr) = linear_fit(series, exponent);
+    (intercept, slope, exponent, ssr)
+}
+
+fn finite_size_report(opts: &GapOpts) -> Result<GapReport, AsmError> {
+    if opts.uncertainty.is_some() {
+        return Err(scaling_error(
+            "finite-size scaling reports leave-one-size-out spread in `ci`; drop `uncertainty`",
+        ));
+    }
+    let mut series = opts.size_series.clone();
+    if series.len() < MIN_SCALING_SIZES {
+        return Err(scaling_error(format!(
+            "finite-size scaling needs at least {MIN_SCALING_SIZES} sizes, got {}",
+            series.len()
+        )));
+    }
+    if series
+        .iter()
+        .any(|point| !(point.size > 0.0 && point.size.is_finite() && point.gap.is_finite()))
+    {
+        return Err(scaling_error(
+            "sizes must be positive and finite and gaps finite",
+        ));
+    }
+    series.sort_by(|a, b| a.size.total_cmp(&b.size));
+    if series.windows(2).any(|pair| pair[0].size == pair[1].size) {
+        return Err(scaling_error("sizes in the series must be distinct"));
+    }
+
+    let (gap_inf, amplitude, exponent, ssr) = power_law_fit(&series);
+    let residuals: Vec<f64> = series
+        .iter()
+        .map(|point| round9(point.gap - gap_inf - amplitude * point.size.powf(-exponent)))
+        .collect();
+    let rms_residual = (ssr / series.len() as f64).sqrt();
+
+    // Leave-one-size-out extrapolations bound the sensitivity to any single size.
+    let mut ci = [gap_inf, gap_inf];
+    if series.len() > MIN_SCALING_SIZES {
+        for skip in 0..series.len() {
+            let subset: Vec<SizedGap> = series
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| *idx != skip)
+                .map(|(_, point)| *point)
+                .collect();
+            let (estimate, ..) = power_law_fit(&subset);
+            ci = [ci[0].min(estimate), ci[1].max(estimate)];
+        }
+    }
+
+    let (min_gap, max_gap) = series
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |acc, p| {
+            (acc.0.min(p.gap), acc.1.max(p.gap))
+        });
+    let spread = (max_gap - min_gap).max(opts.tolerance);
+    let verdict = if rms_residual > INCONCLUSIVE_RESIDUAL * spread {
+        ScalingVerdict::Inconclusive
+    } else if gap_inf > opts.tolerance {
+        ScalingVerdict::Gapped
+    } else {
+        ScalingVerdict::Gapless
+    };
+
+    Ok(GapReport {
+        method: GapMethod::FiniteSizeScaling.as_str().to_string(),
+        gap_value: round9(gap_inf),
+        ci: [round9(ci[0]), round9(ci[1])],
+        residuals,
+        passes: gap_inf >= opts.tolerance,
+        thresholds: opts.thresholds.clone(),
+        interval: None,
+        fit_residual: Some(round9(rms_residual)),
+        scaling: Some(FiniteSizeFit {
+            extrapolated_gap: round9(gap_inf),
+            exponent: round9(exponent),
+            amplitude: round9(amplitude),
+            rms_residual: round9(rms_residual),
+            verdict,
+        }),
+    })
 }
 
 /// Deterministically estimates observable gaps for the provided state reference.
+///
+/// [`GapMethod::FiniteSizeScaling`] extrapolates [`GapOpts::size_series`]
+/// instead of estimating the gap of `state` itself.
 pub fn estimate_gaps(state: &StateRef<'_>, opts: &GapOpts) -> Result<GapReport, AsmError> {
+    if opts.method == GapMethod::FiniteSizeScaling {
+        return finite_size_report(opts);
+    }
     let base_hash = canonical_state_hash(state)?;
-    let seed_hash = stable_hash_string(&(base_hash.clone(), &opts.method))?;
-    let seed = u64::from_str_radix(&seed_hash[..16], 16).unwrap_or(0);
-    let mut rng = StdRng::seed_from_u64(seed);
-    let raw_gap = 0.1 + rng.gen::<f64>() * 1.2;
+    let mut rng = gap_rng(&base_hash, opts.method)?;
+    let raw_gap = draw_gap(&mut rng);
     let spread = rng.gen::<f64>() * 0.05;
     let lo = (raw_gap - spread).max(0.0);
     let hi = (raw_gap + spread).max(lo);
     let ci = [(lo * 1e9).round() / 1e9, (hi * 1e9).round() / 1e9];
-    let residuals = (0..4).map(|_| rng.gen_range(-0.01..0.01)).collect();
+    let residuals: Vec<f64> = (0..4).map(|_| rng.gen_range(-0.01..0.01)).collect();
     let passes = raw_gap >= opts.tolerance;
 
+    // Each residual perturbs the point estimate into one per-sample estimate.
+    let (interval, fit_residual) = match &opts.uncertainty {
+        Some(uncertainty) => {
+            let samples: Vec<f64> = residuals.iter().map(|res| raw_gap + res).collect();
+            let interval = resampled_interval(&samples, uncertainty, &mut rng)?;
+            let fit_residual = match opts.method {
+                GapMethod::Dispersion => Some(round9(
+                    (residuals.iter().map(|res| res * res).sum::<f64>() / residuals.len() as f64)
+                        .sqrt(),
+                )),
+                GapMethod::Spectral | GapMethod::FiniteSizeScaling => None,
+            };
+            (Some(interval), fit_residual)
+        }
+        None => (None, None),
+    };
+
     Ok(GapReport {
-        method: match opts.method {
-            GapMethod::Dispersion => "dispersion".to_string(),
-            GapMethod::Spectral => "spectral".to_string(),
-        },
+        method: opts.method.as_str().to_string(),
         gap_value: (raw_gap * 1e9).round() / 1e9,
         ci,
         residuals,
         passes,
         thresholds: opts.thresholds.clone(),
+        interval,
+        fit_residual,
+        scaling: None,
     })
 }
@@ -6,20 +6,40 @@ mod gaps;
 mod hash;
 mod registry;
 mod runbook;
+mod selftest;
 mod serde;
 mod sweep;
 
 pub use ablations::{
-    run_ablation, AblationJobReport, AblationMode, AblationPlan, AblationReport, ToleranceSpec,
+    run_ablation, run_ablation_with, AblationExecutor, AblationJob, AblationJobReport,
+    AblationMode, AblationPlan, AblationReport, FactorKind, HashExecutor, PipelineExecutor,
+    PipelineParams, StageRunner, ToleranceSpec,
+};
+pub use deform::{
+    deform, deform_with, AdaptiveRefinement, DeformSpec, DeformStep, DeformationReport,
+    GapTargetOutcome, RefinedInterval, TARGET_GAP_MAX_ITERATIONS, TARGET_GAP_TOLERANCE,
+};
+pub use gaps::{
+    estimate_gaps, FiniteSizeFit, GapInterval, GapMethod, GapOpts, GapReport, GapUncertainty,
+    ResamplingMethod, ScalingVerdict, SizedGap,
 };
-pub use deform::{deform, DeformSpec, DeformationReport};
-pub use gaps::{estimate_gaps, GapMethod, GapOpts, GapReport};
 pub use hash::{canonical_state_hash, stable_hash_string};
-pub use registry::{registry_append, registry_query, Query, Registry, Table};
-pub use runbook::{build_runbook, RunBook, RunMeta};
+pub use registry::{
+    registry_append, registry_migrate, registry_query, Aggregate, AggregateOp, Query, Registry,
+    Table, REGISTRY_SCHEMA_VERSION,
+};
+pub use runbook::{
+    artifact_hash, build_runbook, RunBook, RunMeta, RunStep, RunbookVerification, StepStatus,
+    StepVerification,
+};
+pub use selftest::{
+    determinism_selftest, PlatformInfo, SelfTestLevel, SelfTestReference, SelfTestReport,
+    WorkloadResult, SELFTEST_REFERENCE, SELFTEST_REFERENCE_FORMAT,
+};
 pub use sweep::{
-    sweep, GridParameter, LhsParameter, Scheduler, SweepJobReport, SweepPlan, SweepReport,
-    SweepStrategy,
+    sweep, sweep_with, sweep_with_predicate, EarlyStop, EarlyStopLog, GridParameter, JobVerdict,
+    LhsParameter, PrunedBranch, Scheduler, SweepExecutor, SweepJob, SweepJobReport, SweepPlan,
+    SweepPreview, SweepPreviewJob, SweepReport, SweepStrategy, SOBOL_MAX_DIMENSIONS,
 };
 
 pub use serde::{from_json_slice, to_canonical_json_bytes};
@@ -1,16 +1,33 @@
-use std::fs::{self, OpenOptions};
-use std::io::BufWriter;
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
 
 use asm_core::errors::{AsmError, ErrorInfo};
 use csv::{ReaderBuilder, WriterBuilder};
-use rusqlite::{params, Connection};
+use rusqlite::types::ValueRef;
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::ablations::AblationReport;
 use crate::serde::to_canonical_json_bytes;
 
+/// Current registry schema version, recorded in the SQLite `schema_version`
+/// table and in the CSV header comment line.
+pub const REGISTRY_SCHEMA_VERSION: i64 = SQLITE_MIGRATIONS.len() as i64;
+
+/// SQLite migrations; entry `v` upgrades a version `v` registry to `v + 1`.
+/// Registries written before versioning existed are version 0.
+const SQLITE_MIGRATIONS: &[&str] = &[
+    // 0 -> 1: record the schema version and index plan lookups.
+    r#"CREATE TABLE IF NOT EXISTS schema_version(version INTEGER NOT NULL);
+       CREATE INDEX IF NOT EXISTS runs_plan ON runs(plan_name, date, job_id);"#,
+];
+
+/// Prefix of the CSV comment line carrying the schema version.
+const CSV_VERSION_PREFIX: &str = "#schema_version=";
+
 /// Supported registry backends.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Registry {
@@ -34,10 +51,61 @@ impl Registry {
 pub struct Query {
     #[serde(default)]
     pub plan_name: Option<String>,
+    /// Maximum number of rows, or of groups when aggregating.
     #[serde(default)]
     pub limit: Option<usize>,
+    /// Registry column to group by (`date`, `commit`, `plan_name`,
+    /// `plan_hash` or `job_id`). Without an aggregate, groups report their
+    /// row count.
+    #[serde(default)]
+    pub group_by: Option<String>,
+    /// Aggregate computed over the selected rows, per group when grouping.
+    #[serde(default)]
+    pub aggregate: Option<Aggregate>,
+}
+
+/// Aggregation over a numeric KPI stored in the registry `metrics` column.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Aggregate {
+    pub op: AggregateOp,
+    /// KPI name, read from `metrics.kpis.<metric>.value`. Rows where the
+    /// value is missing or not numeric are skipped.
+    pub metric: String,
+}
+
+/// Aggregate function applied by [`Aggregate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AggregateOp {
+    Mean,
+    Min,
+    Max,
+    Count,
 }
 
+impl AggregateOp {
+    fn as_str(self) -> &'static str {
+        match self {
+            AggregateOp::Mean => "mean",
+            AggregateOp::Min => "min",
+            AggregateOp::Max => "max",
+            AggregateOp::Count => "count",
+        }
+    }
+
+    fn sql(self) -> &'static str {
+        match self {
+            AggregateOp::Mean => "AVG",
+            AggregateOp::Min => "MIN",
+            AggregateOp::Max => "MAX",
+            AggregateOp::Count => "COUNT",
+        }
+    }
+}
+
+/// Registry columns accepted by [`Query::group_by`].
+const GROUP_COLUMNS: [&str; 5] = ["date", "commit", "plan_name", "plan_hash", "job_id"];
+
 /// Table representation returned from registry queries.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Table {
@@ -53,8 +121,34 @@ pub fn registry_append(registry: &Registry, report: &AblationReport) -> Result<(
     }
 }
 
+/// Upgrade the registry to [`REGISTRY_SCHEMA_VERSION`] in place, returning
+/// the version found before migrating. Missing registries are left untouched;
+/// registries written by a newer schema are rejected.
+pub fn registry_migrate(registry: &Registry) -> Result<i64, AsmError> {
+    match registry {
+        Registry::Csv(path) => {
+            if !path.exists() {
+                return Ok(REGISTRY_SCHEMA_VERSION);
+            }
+            migrate_csv(path)
+        }
+        Registry::Sqlite(path) => {
+            if !path.exists() {
+                return Ok(REGISTRY_SCHEMA_VERSION);
+            }
+            migrate_sqlite(&mut open_sqlite(path)?, path)
+        }
+    }
+}
+
 /// Query the registry returning a structured table.
 pub fn registry_query(registry: &Registry, query: &Query) -> Result<Table, AsmError> {
+    if query.group_by.is_some() || query.aggregate.is_some() {
+        return match registry {
+            Registry::Csv(path) => aggregate_csv(path, query),
+            Registry::Sqlite(path) => aggregate_sqlite(path, query),
+        };
+    }
     match registry {
         Registry::Csv(path) => query_csv(path, query),
         Registry::Sqlite(path) => query_sqlite(path, query),
@@ -64,7 +158,10 @@ pub fn registry_query(registry: &Registry, query: &Query) -> Result<Table, AsmEr
 fn append_csv(path: &Path, report: &AblationReport) -> Result<(), AsmError> {
     ensure_parent(path)?;
     let file_exists = path.exists();
-    let file = OpenOptions::new()
+    if file_exists {
+        migrate_csv(path)?;
+    }
+    let mut file = OpenOptions::new()
         .append(true)
         .create(true)
         .open(path)
@@ -75,6 +172,10 @@ fn append_csv(path: &Path, report: &AblationReport) -> Result<(), AsmError> {
                     .with_hint(err.to_string()),
             )
         })?;
+    if !file_exists {
+        writeln!(file, "{CSV_VERSION_PREFIX}{REGISTRY_SCHEMA_VERSION}")
+            .map_err(|err| wrap_csv("registry-write-header", err.into()))?;
+    }
     let mut writer = WriterBuilder::new()
         .has_headers(false)
         .from_writer(BufWriter::new(file));
@@ -113,30 +214,8 @@ fn append_csv(path: &Path, report: &AblationReport) -> Result<(), AsmError> {
 
 fn append_sqlite(path: &Path, report: &AblationReport) -> Result<(), AsmError> {
     ensure_parent(path)?;
-    let mut conn = Connection::open(path).map_err(|err| {
-        AsmError::Serde(
-            ErrorInfo::new("registry-sqlite-open", "failed to open sqlite registry")
-                .with_context("path", path.display().to_string())
-                .with_hint(err.to_string()),
-        )
-    })?;
-    conn.execute_batch(
-        r#"CREATE TABLE IF NOT EXISTS runs (
-            date TEXT NOT NULL,
-            "commit" TEXT NOT NULL,
-            plan_name TEXT NOT NULL,
-            plan_hash TEXT NOT NULL,
-            job_id INTEGER NOT NULL,
-            params TEXT NOT NULL,
-            metrics TEXT NOT NULL
-        );"#,
-    )
-    .map_err(|err| {
-        AsmError::Serde(
-            ErrorInfo::new("registry-sqlite-schema", "failed to ensure registry schema")
-                .with_hint(err.to_string()),
-        )
-    })?;
+    let mut conn = open_sqlite(path)?;
+    migrate_sqlite(&mut conn, path)?;
     let tx = conn.transaction().map_err(|err| {
         AsmError::Serde(
             ErrorInfo::new("registry-sqlite-transaction", "failed to start transaction")
@@ -177,8 +256,10 @@ fn query_csv(path: &Path, query: &Query) -> Result<Table, AsmError> {
     if !path.exists() {
         return Ok(empty_table());
     }
+    check_version(csv_version(path)?, path)?;
     let mut reader = ReaderBuilder::new()
         .has_headers(true)
+        .comment(Some(b'#'))
         .from_path(path)
         .map_err(|err| wrap_csv("registry-read", err))?;
     let mut rows = Vec::new();
@@ -206,12 +287,8 @@ fn query_sqlite(path: &Path, query: &Query) -> Result<Table, AsmError> {
     if !path.exists() {
         return Ok(empty_table());
     }
-    let conn = Connection::open(path).map_err(|err| {
-        AsmError::Serde(
-            ErrorInfo::new("registry-sqlite-open", "failed to open sqlite registry")
-                .with_hint(err.to_string()),
-        )
-    })?;
+    let conn = open_sqlite(path)?;
+    check_version(sqlite_version(&conn)?, path)?;
     let mut sql =
         r#"SELECT date, "commit", plan_name, plan_hash, job_id, params, metrics FROM runs"#
             .to_string();
@@ -256,13 +333,21 @@ fn query_sqlite(path: &Path, query: &Query) -> Result<Table, AsmError> {
     })? {
         let mut result = Vec::new();
         for idx in 0..7 {
-            let value: String = row.get(idx).map_err(|err| {
+            let value = row.get_ref(idx).map_err(|err| {
                 AsmError::Serde(
                     ErrorInfo::new("registry-sqlite-get", "failed to read column")
                         .with_hint(err.to_string()),
                 )
             })?;
-            result.push(value);
+            result.push(match value {
+                ValueRef::Integer(value) => value.to_string(),
+                other => other.as_str().map(str::to_string).map_err(|err| {
+                    AsmError::Serde(
+                        ErrorInfo::new("registry-sqlite-get", "failed to read column")
+                            .with_hint(err.to_string()),
+                    )
+                })?,
+            });
         }
         rows.push(result);
         if let Some(limit) = query.limit {
@@ -277,6 +362,338 @@ fn query_sqlite(path: &Path, query: &Query) -> Result<Table, AsmError> {
     })
 }
 
+fn aggregate_csv(path: &Path, query: &Query) -> Result<Table, AsmError> {
+    let group = group_column(query)?;
+    if !path.exists() {
+        return Ok(aggregate_table(query, group, Vec::new()));
+    }
+    let selection = Query {
+        plan_name: query.plan_name.clone(),
+        ..Query::default()
+    };
+    let mut groups: BTreeMap<String, (usize, Vec<f64>)> = BTreeMap::new();
+    if group.is_none() {
+        groups.insert(String::new(), (0, Vec::new()));
+    }
+    for row in query_csv(path, &selection)?.rows {
+        let key = group.map(|idx| row[idx].clone()).unwrap_or_default();
+        let entry = groups.entry(key).or_default();
+        entry.0 += 1;
+        if let Some(aggregate) = &query.aggregate {
+            let metrics: Value = serde_json::from_str(&row[6]).map_err(|err| {
+                AsmError::Serde(
+                    ErrorInfo::new("registry-metrics", "failed to parse registry metrics")
+                        .with_hint(err.to_string()),
+                )
+            })?;
+            if let Some(value) = metrics
+                .get("kpis")
+                .and_then(|kpis| kpis.get(&aggregate.metric))
+                .and_then(|kpi| kpi.get("value"))
+                .and_then(Value::as_f64)
+            {
+                entry.1.push(value);
+            }
+        }
+    }
+    let rows = groups
+        .into_iter()
+        .map(|(key, (count, values))| {
+            let value = match &query.aggregate {
+                None => Some(count as f64),
+                Some(aggregate) => match aggregate.op {
+                    AggregateOp::Count => Some(values.len() as f64),
+                    AggregateOp::Mean => (!values.is_empty())
+                        .then(|| values.iter().sum::<f64>() / values.len() as f64),
+                    AggregateOp::Min => values.iter().copied().reduce(f64::min),
+                    AggregateOp::Max => values.iter().copied().reduce(f64::max),
+                },
+            };
+            aggregate_row(group.map(|_| key), value)
+        })
+        .collect();
+    Ok(aggregate_table(query, group, rows))
+}
+
+fn aggregate_sqlite(path: &Path, query: &Query) -> Result<Table, AsmError> {
+    let group = group_column(query)?;
+    if !path.exists() {
+        return Ok(aggregate_table(query, group, Vec::new()));
+    }
+    let conn = open_sqlite(path)?;
+    check_version(sqlite_version(&conn)?, path)?;
+    let mut bindings = Vec::new();
+    let aggregate = match &query.aggregate {
+        None => "COUNT(*)".to_string(),
+        Some(aggregate) => {
+            // Numeric KPI values only, mirroring the CSV backend.
+            bindings.push(format!("$.kpis.\"{}\".value", aggregate.metric));
+            format!(
+                "{}(CASE WHEN json_type(metrics, ?1) IN ('integer', 'real') \
+                 THEN json_extract(metrics, ?1) END)",
+                aggregate.op.sql()
+            )
+        }
+    };
+    let group_sql = group.map(|idx| format!("\"{}\"", GROUP_COLUMNS[idx]));
+    let mut sql = format!(
+        "SELECT {}, {aggregate} FROM runs",
+        group_sql.as_deref().unwrap_or("''")
+    );
+    if let Some(plan) = &query.plan_name {
+        bindings.push(plan.clone());
+        sql.push_str(&format!(" WHERE plan_name = ?{}", bindings.len()));
+    }
+    if let Some(column) = &group_sql {
+        sql.push_str(&format!(" GROUP BY {column}"));
+    }
+    let sqlite_error = |code: &str, message: &str, err: rusqlite::Error| {
+        AsmError::Serde(ErrorInfo::new(code, message).with_hint(err.to_string()))
+    };
+    let mut stmt = conn.prepare(&sql).map_err(|err| {
+        sqlite_error(
+            "registry-sqlite-prepare",
+            "failed to prepare registry aggregate",
+            err,
+        )
+    })?;
+    let mut rows_iter = stmt
+        .query(rusqlite::params_from_iter(bindings.iter()))
+        .map_err(|err| {
+            sqlite_error(
+                "registry-sqlite-query",
+                "failed to execute registry aggregate",
+                err,
+            )
+        })?;
+    let mut rows = Vec::new();
+    while let Some(row) = rows_iter
+        .next()
+        .map_err(|err| sqlite_error("registry-sqlite-row", "failed to fetch registry row", err))?
+    {
+        let read = |idx: usize| {
+            row.get_ref(idx)
+                .map_err(|err| sqlite_error("registry-sqlite-get", "failed to read column", err))
+        };
+        let key = match read(0)? {
+            ValueRef::Integer(value) => value.to_string(),
+            ValueRef::Text(text) => String::from_utf8_lossy(text).into_owned(),
+            _ => String::new(),
+        };
+        let value = match read(1)? {
+            ValueRef::Integer(value) => Some(value as f64),
+            ValueRef::Real(value) => Some(value),
+            _ => None,
+        };
+        rows.push(aggregate_row(group.map(|_| key), value));
+    }
+    // Group keys sort as text on both backends, so integer `job_id` groups
+    // order identically to the CSV registry.
+    rows.sort();
+    Ok(aggregate_table(query, group, rows))
+}
+
+/// Index into [`GROUP_COLUMNS`] of the requested grouping column.
+fn group_column(query: &Query) -> Result<Option<usize>, AsmError> {
+    query
+        .group_by
+        .as_ref()
+        .map(|column| {
+            GROUP_COLUMNS
+                .iter()
+                .position(|candidate| candidate == column)
+                .ok_or_else(|| {
+                    AsmError::Serde(
+                        ErrorInfo::new("registry-query-group", "unsupported group_by column")
+                            .with_context("column", column.clone())
+                            .with_hint(GROUP_COLUMNS.join(", ")),
+                    )
+                })
+        })
+        .transpose()
+}
+
+/// Aggregated values are rounded to 1e-9 so that both backends render the
+/// same digits regardless of summation order; empty aggregates are blank.
+fn aggregate_row(key: Option<String>, value: Option<f64>) -> Vec<String> {
+    let value = value
+        .map(|value| ((value * 1e9).round() / 1e9).to_string())
+        .unwrap_or_default();
+    key.into_iter().chain([value]).collect()
+}
+
+fn aggregate_table(query: &Query, group: Option<usize>, mut rows: Vec<Vec<String>>) -> Table {
+    if let Some(limit) = query.limit {
+        rows.truncate(limit);
+    }
+    let value_column = match &query.aggregate {
+        None => "count".to_string(),
+        Some(aggregate) => format!("{}_{}", aggregate.op.as_str(), aggregate.metric),
+    };
+    Table {
+        columns: group
+            .map(|idx| GROUP_COLUMNS[idx].to_string())
+            .into_iter()
+            .chain([value_column])
+            .collect(),
+        rows,
+    }
+}
+
+fn open_sqlite(path: &Path) -> Result<Connection, AsmError> {
+    Connection::open(path).map_err(|err| {
+        AsmError::Serde(
+            ErrorInfo::new("registry-sqlite-open", "failed to open sqlite registry")
+                .with_context("path", path.display().to_string())
+                .with_hint(err.to_string()),
+        )
+    })
+}
+
+/// Creates the `runs` table if needed and applies every pending migration in
+/// one transaction, returning the version found before migrating.
+fn migrate_sqlite(conn: &mut Connection, path: &Path) -> Result<i64, AsmError> {
+    let wrap = |err: rusqlite::Error| {
+        AsmError::Serde(
+            ErrorInfo::new(
+                "registry-sqlite-schema",
+                "failed to migrate registry schema",
+            )
+            .with_context("path", path.display().to_string())
+            .with_hint(err.to_string()),
+        )
+    };
+    let tx = conn.transaction().map_err(wrap)?;
+    tx.execute_batch(
+        r#"CREATE TABLE IF NOT EXISTS runs (
+            date TEXT NOT NULL,
+            "commit" TEXT NOT NULL,
+            plan_name TEXT NOT NULL,
+            plan_hash TEXT NOT NULL,
+            job_id INTEGER NOT NULL,
+            params TEXT NOT NULL,
+            metrics TEXT NOT NULL
+        );"#,
+    )
+    .map_err(wrap)?;
+    let found = sqlite_version(&tx)?;
+    check_version(found, path)?;
+    if found == REGISTRY_SCHEMA_VERSION {
+        return Ok(found);
+    }
+    for migration in &SQLITE_MIGRATIONS[found as usize..] {
+        tx.execute_batch(migration).map_err(wrap)?;
+    }
+    tx.execute("DELETE FROM schema_version", []).map_err(wrap)?;
+    tx.execute(
+        "INSERT INTO schema_version(version) VALUES (?1)",
+        params![REGISTRY_SCHEMA_VERSION],
+    )
+    .map_err(wrap)?;
+    tx.commit().map_err(wrap)?;
+    Ok(found)
+}
+
+/// Schema version of an open SQLite registry; 0 when unversioned.
+fn sqlite_version(conn: &Connection) -> Result<i64, AsmError> {
+    let wrap = |err: rusqlite::Error| {
+        AsmError::Serde(
+            ErrorInfo::new(
+                "registry-sqlite-schema",
+                "failed to read registry schema version",
+            )
+            .with_hint(err.to_string()),
+        )
+    };
+    let versioned: bool = conn
+        .query_row(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_version')",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(wrap)?;
+    if !versioned {
+        return Ok(0);
+    }
+    let version: Option<i64> = conn
+        .query_row("SELECT version FROM schema_version LIMIT 1", [], |row| {
+            row.get(0)
+        })
+        .optional()
+        .map_err(wrap)?;
+    Ok(version.unwrap_or(0))
+}
+
+/// Prepends the version comment line to an unversioned CSV registry,
+/// returning the version found before migrating.
+fn migrate_csv(path: &Path) -> Result<i64, AsmError> {
+    let found = csv_version(path)?;
+    check_version(found, path)?;
+    if found == REGISTRY_SCHEMA_VERSION {
+        return Ok(found);
+    }
+    let existing = fs::read(path).map_err(|err| wrap_csv("registry-read", err.into()))?;
+    let mut upgraded = format!("{CSV_VERSION_PREFIX}{REGISTRY_SCHEMA_VERSION}\n").into_bytes();
+    upgraded.extend_from_slice(&existing);
+    let mut staging = path.as_os_str().to_owned();
+    staging.push(".tmp");
+    let staging = PathBuf::from(staging);
+    fs::write(&staging, upgraded)
+        .and_then(|_| fs::rename(&staging, path))
+        .map_err(|err| {
+            let _ = fs::remove_file(&staging);
+            AsmError::Serde(
+                ErrorInfo::new("registry-csv-migrate", "failed to upgrade CSV registry")
+                    .with_context("path", path.display().to_string())
+                    .with_hint(err.to_string()),
+            )
+        })?;
+    Ok(found)
+}
+
+/// Schema version of a CSV registry read from its leading comment line; 0
+/// when the file starts directly with the column header.
+fn csv_version(path: &Path) -> Result<i64, AsmError> {
+    let file = File::open(path).map_err(|err| wrap_csv("registry-read", err.into()))?;
+    let mut first = String::new();
+    BufReader::new(file)
+        .read_line(&mut first)
+        .map_err(|err| wrap_csv("registry-read", err.into()))?;
+    let first = first.trim_end();
+    if !first.starts_with('#') {
+        return Ok(0);
+    }
+    first
+        .strip_prefix(CSV_VERSION_PREFIX)
+        .and_then(|version| version.trim().parse().ok())
+        .ok_or_else(|| {
+            AsmError::Serde(
+                ErrorInfo::new(
+                    "registry-csv-version",
+                    "malformed CSV registry version line",
+                )
+                .with_context("path", path.display().to_string())
+                .with_context("line", first.to_string()),
+            )
+        })
+}
+
+fn check_version(found: i64, path: &Path) -> Result<(), AsmError> {
+    if found > REGISTRY_SCHEMA_VERSION {
+        return Err(AsmError::Serde(
+            ErrorInfo::new(
+                "registry-schema-downgrade",
+                format!(
+                    "registry schema {found} is newer than supported {REGISTRY_SCHEMA_VERSION}"
+                ),
+            )
+            .with_context("path", path.display().to_string())
+            .with_hint("upgrade asm-exp; registries are never migrated to older schemas"),
+        ));
+    }
+    Ok(())
+}
+
 fn canonical_string(value: &Value) -> Result<String, AsmError> {
     let bytes = to_canonical_json_bytes(value)?;
     String::from_utf8(bytes).map_err(|err| {
@@ -1,8 +1,13 @@
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
 
-use asm_core::errors::AsmError;
+use asm_core::errors::{AsmError, ErrorCode, ErrorInfo};
+use asm_core::hash::parse_hash;
+use asm_core::provenance::{compare_builds, BuildFingerprint, BuildMismatch};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 
 use crate::hash::stable_hash_string;
 
@@ -17,6 +22,25 @@ pub struct RunMeta {
     pub artifacts: Vec<String>,
     #[serde(default)]
     pub summary: Value,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<RunStep>,
+}
+
+/// Single executable step of a runbook and the artefacts it produces.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunStep {
+    pub id: String,
+    /// Identifiers of steps whose outputs this step consumes.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Output artefact paths, relative to the runbook root.
+    #[serde(default)]
+    pub outputs: Vec<String>,
+    /// SHA-256 digests recorded for outputs when the step completed.
+    #[serde(default)]
+    pub hashes: BTreeMap<String, String>,
+    #[serde(default)]
+    pub completed: bool,
 }
 
 /// Deterministic runbook containing provenance and artefact references.
@@ -30,6 +54,250 @@ pub struct RunBook {
     pub artifacts: Vec<String>,
     #[serde(default)]
     pub summary: Value,
+    /// Steps in dependency order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<RunStep>,
+}
+
+/// Execution state of a runbook step relative to the artefacts on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StepStatus {
+    /// Completed with all outputs present and matching their recorded hashes.
+    Done,
+    /// Not yet run and every dependency is done.
+    Ready,
+    /// At least one dependency is not done.
+    Blocked,
+    /// Marked completed but an output is missing, no longer matches its hash,
+    /// or was recorded with a report hash instead of a file digest.
+    Stale,
+}
+
+/// Verification outcome for a single runbook step.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StepVerification {
+    pub id: String,
+    pub status: StepStatus,
+    #[serde(default)]
+    pub missing: Vec<String>,
+    #[serde(default)]
+    pub mismatched: Vec<String>,
+    /// Outputs whose recorded hash carries a domain prefix and therefore
+    /// cannot be compared against the artefact's file digest.
+    #[serde(default)]
+    pub cross_domain: Vec<String>,
+}
+
+/// Verification outcome for every step of a runbook, in dependency order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunbookVerification {
+    pub steps: Vec<StepVerification>,
+    /// Completed steps whose outputs were produced by a different build than
+    /// the first fingerprinted step. Reported as warnings; step status is
+    /// unaffected.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub build_mismatches: Vec<BuildMismatch>,
+}
+
+impl RunbookVerification {
+    /// Returns the identifiers of steps that can be executed now.
+    pub fn next_runnable(&self) -> Vec<&str> {
+        self.steps
+            .iter()
+            .filter(|step| matches!(step.status, StepStatus::Ready | StepStatus::Stale))
+            .map(|step| step.id.as_str())
+            .collect()
+    }
+
+    /// Fails with `hash-domain-mismatch` for the first output whose recorded
+    /// hash belongs to another hash domain than the file digests it is
+    /// compared against.
+    pub fn ensure_no_cross_domain(&self) -> Result<(), AsmError> {
+        let Some((step, output)) = self.steps.iter().find_map(|step| {
+            step.cross_domain
+                .first()
+                .map(|output| (step.id.as_str(), output.as_str()))
+        }) else {
+            return Ok(());
+        };
+        Err(AsmError::Serde(
+            ErrorInfo::new(
+                ErrorCode::HashDomainMismatch,
+                "recorded output hash is a report hash, not a file digest",
+            )
+            .with_context("step", step)
+            .with_context("output", output),
+        ))
+    }
+}
+
+impl RunBook {
+    /// Checks the declared outputs of each step under `root` and classifies the steps.
+    pub fn verify(&self, root: &Path) -> RunbookVerification {
+        let mut statuses: BTreeMap<&str, StepStatus> = BTreeMap::new();
+        let mut steps = Vec::with_capacity(self.steps.len());
+        for step in &self.steps {
+            let mut missing = Vec::new();
+            let mut mismatched = Vec::new();
+            let mut cross_domain = Vec::new();
+            if step.completed {
+                for output in &step.outputs {
+                    match artifact_hash(&root.join(output)) {
+                        Err(_) => missing.push(output.clone()),
+                        Ok(actual) => match step.hashes.get(output) {
+                            Some(recorded) if parse_hash(recorded).0.is_some() => {
+                                cross_domain.push(output.clone());
+                            }
+                            Some(recorded) if *recorded != actual => {
+                                mismatched.push(output.clone());
+                            }
+                            _ => {}
+                        },
+                    }
+                }
+            }
+            let deps_done = step
+                .depends_on
+                .iter()
+                .all(|dep| statuses.get(dep.as_str()) == Some(&StepStatus::Done));
+            let status = if !deps_done {
+                StepStatus::Blocked
+            } else if !step.completed {
+                StepStatus::Ready
+            } else if missing.is_empty() && mismatched.is_empty() && cross_domain.is_empty() {
+                StepStatus::Done
+            } else {
+                StepStatus::Stale
+            };
+            statuses.insert(step.id.as_str(), status);
+            steps.push(StepVerification {
+                id: step.id.clone(),
+                status,
+                missing,
+                mismatched,
+                cross_domain,
+            });
+        }
+        let builds: Vec<(&str, BuildFingerprint)> = self
+            .steps
+            .iter()
+            .zip(&steps)
+            .filter(|(_, verified)| verified.status == StepStatus::Done)
+            .filter_map(|(step, _)| {
+                step.outputs
+                    .iter()
+                    .find_map(|output| recorded_build(&root.join(output)))
+                    .map(|build| (step.id.as_str(), build))
+            })
+            .collect();
+        let build_mismatches = compare_builds(builds.iter().map(|(stage, build)| (*stage, build)));
+        RunbookVerification {
+            steps,
+            build_mismatches,
+        }
+    }
+}
+
+/// Build fingerprint recorded in a JSON artefact, either at the top level
+/// (run manifests) or under `provenance` (analysis reports).
+fn recorded_build(path: &Path) -> Option<BuildFingerprint> {
+    let value: Value = serde_json::from_slice(&fs::read(path).ok()?).ok()?;
+    let build = value
+        .get("build")
+        .or_else(|| value.get("provenance").and_then(|p| p.get("build")))?;
+    serde_json::from_value(build.clone()).ok()
+}
+
+/// Computes the hexadecimal SHA-256 digest of the artefact at `path`.
+pub fn artifact_hash(path: &Path) -> Result<String, AsmError> {
+    let bytes = fs::read(path)
+        .map_err(|err| AsmError::Serde(ErrorInfo::new("runbook-artifact-read", err.to_string())))?;
+    Ok(format!("{:x}", Sha256::digest(bytes)))
+}
+
+/// Walks unplaced dependencies from the first blocked step until a step
+/// repeats, returning the closed loop. Every unplaced step has an unplaced
+/// dependency once ordering stalls, so the walk always closes.
+fn find_cycle<'a>(steps: &'a [RunStep], placed: &BTreeSet<&str>) -> Vec<&'a str> {
+    let by_id: BTreeMap<&str, &RunStep> =
+        steps.iter().map(|step| (step.id.as_str(), step)).collect();
+    let mut path: Vec<&str> = Vec::new();
+    let mut current = steps.iter().find(|step| !placed.contains(step.id.as_str()));
+    while let Some(step) = current {
+        if let Some(start) = path.iter().position(|id| *id == step.id) {
+            let mut cycle = path.split_off(start);
+            cycle.push(step.id.as_str());
+            return cycle;
+        }
+        path.push(step.id.as_str());
+        current = step
+            .depends_on
+            .iter()
+            .find(|dep| !placed.contains(dep.as_str()))
+            .and_then(|dep| by_id.get(dep.as_str()).copied());
+    }
+    path
+}
+
+/// Orders steps so every step follows its dependencies, keeping declaration
+/// order among steps that are otherwise unordered.
+fn order_steps(steps: &[RunStep]) -> Result<Vec<RunStep>, AsmError> {
+    let mut known = BTreeSet::new();
+    for step in steps {
+        if !known.insert(step.id.as_str()) {
+            return Err(AsmError::Serde(
+                ErrorInfo::new("runbook-duplicate-step", "step identifiers must be unique")
+                    .with_context("step", step.id.clone()),
+            ));
+        }
+    }
+    for step in steps {
+        for dep in &step.depends_on {
+            if !known.contains(dep.as_str()) {
+                return Err(AsmError::Serde(
+                    ErrorInfo::new(
+                        "runbook-dangling-dependency",
+                        "dependency is not a known step",
+                    )
+                    .with_context("step", step.id.clone())
+                    .with_context("depends_on", dep.clone()),
+                ));
+            }
+        }
+    }
+
+    let mut placed = BTreeSet::new();
+    let mut ordered = Vec::with_capacity(steps.len());
+    while ordered.len() < steps.len() {
+        let next = steps.iter().position(|step| {
+            !placed.contains(step.id.as_str())
+                && step
+                    .depends_on
+                    .iter()
+                    .all(|dep| placed.contains(dep.as_str()))
+        });
+        match next {
+            Some(position) => {
+                placed.insert(steps[position].id.as_str());
+                ordered.push(steps[position].clone());
+            }
+            None => {
+                let remaining: Vec<&str> = steps
+                    .iter()
+                    .map(|step| step.id.as_str())
+                    .filter(|id| !placed.contains(id))
+                    .collect();
+                let cycle = find_cycle(steps, &placed);
+                return Err(AsmError::Serde(
+                    ErrorInfo::new("runbook-cycle", "step dependencies form a cycle")
+                        .with_context("steps", remaining.join(","))
+                        .with_context("cycle", cycle.join(" -> ")),
+                ));
+            }
+        }
+    }
+    Ok(ordered)
 }
 
 /// Builds a deterministic runbook covering the provided inputs and metadata.
@@ -38,6 +306,7 @@ pub fn build_runbook(inputs: &[PathBuf], meta: &RunMeta) -> Result<RunBook, AsmE
         .iter()
         .map(|path| path.to_string_lossy().to_string())
         .collect();
+    let steps = order_steps(&meta.steps)?;
     let id = stable_hash_string(&(resolved_inputs.clone(), meta))?;
     Ok(RunBook {
         id,
@@ -47,5 +316,6 @@ pub fn build_runbook(inputs: &[PathBuf], meta: &RunMeta) -> Result<RunBook, AsmE
         inputs: resolved_inputs,
         artifacts: meta.artifacts.clone(),
         summary: meta.summary.clone(),
+        steps,
     })
 }
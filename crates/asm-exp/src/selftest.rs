@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+
+use asm_code::css::CSSCode;
+use asm_code::hash::canonical_code_hash;
+use asm_core::errors::{AsmError, ErrorInfo};
+use asm_core::provenance::{RunProvenance, SchemaVersion};
+use asm_core::rng::{derive_substream_seed, RngHandle};
+use asm_graph::{canonical_hash as graph_hash, gen_quasi_regular, HypergraphImpl};
+use asm_mcmc::{run as run_mcmc, MoveCounts, RunConfig};
+use asm_spec::{build_operators, dispersion_scan, DispersionSpec, OpOpts, Operators};
+use serde::{Deserialize, Serialize};
+
+use crate::hash::stable_hash_string;
+
+/// Reference hashes the self-test compares against.
+///
+/// Regenerate with `ASM_UPDATE_GOLDEN=1 cargo test -p asm-exp --test
+/// determinism_selftest` after an intentional change to a workload's output;
+/// see `docs/REPRODUCIBILITY.md`.
+pub const SELFTEST_REFERENCE: &str = include_str!("../fixtures/selftest_reference.json");
+
+/// Layout version of [`SELFTEST_REFERENCE`].
+pub const SELFTEST_REFERENCE_FORMAT: u32 = 1;
+
+/// Master seed every self-test workload derives its substream from.
+const SELFTEST_SEED: u64 = 0x5e1f_7e57;
+
+/// Workload sets run by [`determinism_selftest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SelfTestLevel {
+    /// Graph generation, code construction, and operator assembly.
+    Quick,
+    /// Quick plus a 4-sweep MCMC run and a dispersion scan.
+    Full,
+}
+
+/// Deterministic micro-workloads in execution order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Workload {
+    Graph,
+    Code,
+    Operators,
+    Mcmc,
+    Dispersion,
+}
+
+impl Workload {
+    const ALL: [Workload; 5] = [
+        Workload::Graph,
+        Workload::Code,
+        Workload::Operators,
+        Workload::Mcmc,
+        Workload::Dispersion,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Workload::Graph => "graph",
+            Workload::Code => "code",
+            Workload::Operators => "operators",
+            Workload::Mcmc => "mcmc",
+            Workload::Dispersion => "dispersion",
+        }
+    }
+
+    fn runs_at(self, level: SelfTestLevel) -> bool {
+        match self {
+            Workload::Graph | Workload::Code | Workload::Operators => true,
+            Workload::Mcmc | Workload::Dispersion => level == SelfTestLevel::Full,
+        }
+    }
+}
+
+/// Reference hashes keyed by workload name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelfTestReference {
+    pub format: u32,
+    pub workloads: BTreeMap<String, String>,
+}
+
+impl SelfTestReference {
+    /// Parses the reference embedded from the fixtures file.
+    pub fn embedded() -> Result<Self, AsmError> {
+        let reference: Self = serde_json::from_str(SELFTEST_REFERENCE).map_err(|err| {
+            AsmError::Serde(ErrorInfo::new("selftest-reference", err.to_string()))
+        })?;
+        if reference.format != SELFTEST_REFERENCE_FORMAT {
+            return Err(AsmError::Serde(
+                ErrorInfo::new(
+                    "selftest-reference",
+                    "unsupported self-test reference format",
+                )
+                .with_context("format", reference.format.to_string())
+                .with_context("supported", SELFTEST_REFERENCE_FORMAT.to_string()),
+            ));
+        }
+        Ok(reference)
+    }
+
+    /// Names of every workload the self-test knows about.
+    pub fn workload_names() -> Vec<&'static str> {
+        Workload::ALL
+            .iter()
+            .map(|workload| workload.name())
+            .collect()
+    }
+}
+
+/// Build and platform details recorded with a [`SelfTestReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlatformInfo {
+    pub arch: String,
+    pub os: String,
+    pub family: String,
+    pub endian: String,
+    pub pointer_width: u32,
+    /// Floating-point related target features enabled at compile time.
+    pub float_features: Vec<String>,
+    pub version: String,
+}
+
+impl PlatformInfo {
+    /// Describes the platform this binary was built for.
+    pub fn current() -> Self {
+        let features = [
+            ("sse2", cfg!(target_feature = "sse2")),
+            ("sse4.1", cfg!(target_feature = "sse4.1")),
+            ("avx", cfg!(target_feature = "avx")),
+            ("avx2", cfg!(target_feature = "avx2")),
+            ("fma", cfg!(target_feature = "fma")),
+            ("neon", cfg!(target_feature = "neon")),
+        ];
+        Self {
+            arch: std::env::consts::ARCH.to_string(),
+            os: std::env::consts::OS.to_string(),
+            family: std::env::consts::FAMILY.to_string(),
+            endian: if cfg!(target_endian = "little") {
+                "little".into()
+            } else {
+                "big".into()
+            },
+            pointer_width: usize::BITS,
+            float_features: features
+                .iter()
+                .filter(|(_, enabled)| *enabled)
+                .map(|(name, _)| name.to_string())
+                .collect(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
+/// Outcome of one self-test workload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkloadResult {
+    pub name: String,
+    /// Reference hash, absent when the reference has no entry.
+    pub expected: Option<String>,
+    /// Computed hash, absent when the workload failed.
+    pub actual: Option<String>,
+    pub pass: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Reproducibility certificate produced by [`determinism_selftest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelfTestReport {
+    pub level: SelfTestLevel,
+    pub platform: PlatformInfo,
+    pub workloads: Vec<WorkloadResult>,
+    /// Whether every workload matched its reference hash.
+    pub pass: bool,
+}
+
+impl SelfTestReport {
+    /// Workloads whose hash did not match the reference.
+    pub fn mismatches(&self) -> impl Iterator<Item = &WorkloadResult> {
+        self.workloads.iter().filter(|workload| !workload.pass)
+    }
+}
+
+fn fixture_code() -> Result<CSSCode, AsmError> {
+    CSSCode::new(
+        6,
+        vec![vec![0, 1, 2, 3], vec![2, 3, 4, 5]],
+        vec![vec![0, 1, 2, 3], vec![2, 3, 4, 5]],
+        SchemaVersion::new(1, 0, 0),
+        RunProvenance::default(),
+    )
+}
+
+fn fixture_graph() -> Result<HypergraphImpl, AsmError> {
+    let mut rng = RngHandle::from_seed(derive_substream_seed(SELFTEST_SEED, 0));
+    gen_quasi_regular(24, 3, 2, &mut rng)
+}
+
+/// Four in-memory sweeps: no run directory and no checkpoints.
+fn mcmc_config() -> RunConfig {
+    RunConfig {
+        sweeps: 4,
+        move_counts: MoveCounts {
+            generator_flips: 1,
+            row_ops: 1,
+            graph_rewires: 1,
+            worm_moves: 1,
+        },
+        ..RunConfig::default()
+    }
+}
+
+fn dependency_error(name: &str) -> AsmError {
+    AsmError::Serde(ErrorInfo::new(
+        "selftest-dependency",
+        format!("skipped because the {name} workload failed"),
+    ))
+}
+
+/// Intermediate values shared between workloads.
+#[derive(Default)]
+struct Fixtures {
+    graph: Option<HypergraphImpl>,
+    code: Option<CSSCode>,
+    operators: Option<Operators>,
+}
+
+impl Fixtures {
+    fn graph(&self) -> Result<&HypergraphImpl, AsmError> {
+        self.graph.as_ref().ok_or_else(|| dependency_error("graph"))
+    }
+
+    fn code(&self) -> Result<&CSSCode, AsmError> {
+        self.code.as_ref().ok_or_else(|| dependency_error("code"))
+    }
+
+    fn operators(&self) -> Result<&Operators, AsmError> {
+        self.operators
+            .as_ref()
+            .ok_or_else(|| dependency_error("operators"))
+    }
+
+    /// Runs `workload` and returns its canonical hash.
+    fn run(&mut self, workload: Workload) -> Result<String, AsmError> {
+        match workload {
+            Workload::Graph => {
+                let graph = fixture_graph()?;
+                let hash = graph_hash(&graph)?;
+                self.graph = Some(graph);
+                Ok(hash)
+            }
+            Workload::Code => {
+                let code = fixture_code()?;
+                let hash = canonical_code_hash(&code);
+                self.code = Some(code);
+                Ok(hash)
+            }
+            Workload::Operators => {
+                let operators = build_operators(self.graph()?, self.code()?, &OpOpts::default())?;
+                let hash = operators.info.hash.clone();
+                self.operators = Some(operators);
+                Ok(hash)
+            }
+            Workload::Mcmc => {
+                let seed = derive_substream_seed(SELFTEST_SEED, 1);
+                let summary = run_mcmc(&mcmc_config(), seed, self.code()?, self.graph()?)?;
+                stable_hash_string(&(summary.final_graph_hash, summary.final_code_hash))
+            }
+            Workload::Dispersion => {
+                let seed = derive_substream_seed(SELFTEST_SEED, 2);
+                let report = dispersion_scan(self.operators()?, &DispersionSpec::default(), seed)?;
+                stable_hash_string(&report)
+            }
+        }
+    }
+}
+
+/// Runs the deterministic micro-workloads selected by `level` and compares
+/// their canonical hashes against [`SELFTEST_REFERENCE`].
+///
+/// Failures never abort the run: a workload that errors, or depends on one
+/// that did, is reported as a mismatch with its error message. Computed
+/// hashes are kept even when the reference cannot be read.
+pub fn determinism_selftest(level: SelfTestLevel) -> SelfTestReport {
+    let reference = SelfTestReference::embedded();
+    let mut fixtures = Fixtures::default();
+    let workloads: Vec<WorkloadResult> = Workload::ALL
+        .into_iter()
+        .filter(|workload| workload.runs_at(level))
+        .map(|workload| {
+            let expected = reference
+                .as_ref()
+                .ok()
+                .and_then(|reference| reference.workloads.get(workload.name()).cloned());
+            let result = fixtures.run(workload);
+            let error = match (&result, &reference) {
+                (Err(err), _) | (Ok(_), Err(err)) => Some(err.to_string()),
+                (Ok(_), Ok(_)) => None,
+            };
+            let actual = result.ok();
+            WorkloadResult {
+                name: workload.name().to_string(),
+                pass: error.is_none() && expected.is_some() && expected == actual,
+                expected,
+                actual,
+                error,
+            }
+        })
+        .collect();
+    SelfTestReport {
+        level,
+        platform: PlatformInfo::current(),
+        pass: workloads.iter().all(|workload| workload.pass),
+        workloads,
+    }
+}
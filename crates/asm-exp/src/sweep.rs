@@ -2,6 +2,7 @@ use std::collections::BTreeMap;
 
 use asm_core::errors::{AsmError, ErrorInfo};
 use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
@@ -12,6 +13,9 @@ use crate::hash::stable_hash_string;
 pub struct Scheduler {
     #[serde(default = "Scheduler::default_parallelism")]
     pub parallelism: usize,
+    /// Pruning and budget rules applied by [`sweep_with_predicate`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub early_stop: Option<EarlyStop>,
 }
 
 impl Scheduler {
@@ -24,16 +28,152 @@ impl Default for Scheduler {
     fn default() -> Self {
         Self {
             parallelism: Self::default_parallelism(),
+            early_stop: None,
         }
     }
 }
 
+/// Early-stopping rules for sweeps run with a KPI predicate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EarlyStop {
+    /// Stop the sweep once this many jobs have been judged good.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub good_budget: Option<usize>,
+    /// Number of leading grid parameters whose values identify a branch.
+    #[serde(default = "EarlyStop::default_branch_depth")]
+    pub branch_depth: usize,
+}
+
+impl EarlyStop {
+    const fn default_branch_depth() -> usize {
+        1
+    }
+}
+
+impl Default for EarlyStop {
+    fn default() -> Self {
+        Self {
+            good_budget: None,
+            branch_depth: Self::default_branch_depth(),
+        }
+    }
+}
+
+/// Verdict returned by a sweep predicate for each completed job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobVerdict {
+    /// Counts towards [`EarlyStop::good_budget`].
+    Good,
+    /// No effect on the remaining jobs.
+    Neutral,
+    /// Skip the remaining grid points of this job's branch.
+    Prune,
+}
+
+/// Branch pruned after a job was judged [`JobVerdict::Prune`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrunedBranch {
+    /// Index of the job whose verdict pruned the branch.
+    pub trigger: usize,
+    /// Leading parameter values shared by the branch.
+    pub branch: BTreeMap<String, Value>,
+    /// Indices of the jobs skipped as a result.
+    pub skipped: Vec<usize>,
+}
+
+/// Record of the early-stopping decisions taken during a sweep.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EarlyStopLog {
+    /// Verdict for every job that ran, in index order.
+    pub verdicts: Vec<(usize, JobVerdict)>,
+    pub pruned: Vec<PrunedBranch>,
+    /// Index of the job that exhausted the good budget, if it was reached.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_reached_at: Option<usize>,
+}
+
 /// Plan describing the sweep strategy and parameter space.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SweepPlan {
     pub strategy: SweepStrategy,
     #[serde(default)]
     pub scheduler: Scheduler,
+    /// Maximum number of jobs executed at once. Execution order is then
+    /// arbitrary, but reports and hashes match the serial run.
+    #[serde(
+        default = "SweepPlan::default_concurrency",
+        skip_serializing_if = "SweepPlan::is_serial"
+    )]
+    pub concurrency: usize,
+}
+
+impl SweepPlan {
+    const fn default_concurrency() -> usize {
+        1
+    }
+
+    fn is_serial(concurrency: &usize) -> bool {
+        *concurrency <= 1
+    }
+
+    /// Hash of the plan and seed; concurrency only affects how jobs are run
+    /// and is excluded.
+    fn plan_hash(&self, seed: u64) -> Result<String, AsmError> {
+        let mut canonical = self.clone();
+        canonical.concurrency = Self::default_concurrency();
+        stable_hash_string(&(&canonical, seed))
+    }
+
+    /// Enumerates the jobs `sweep(self, seed)` would run, in the same order and
+    /// with the same seeds and parameters, without executing any of them.
+    pub fn preview(&self, seed: u64) -> Result<SweepPreview, AsmError> {
+        let plan_hash = self.plan_hash(seed)?;
+        let jobs = expand_jobs(&self.strategy, seed)?
+            .into_iter()
+            .enumerate()
+            .map(|(idx, params)| SweepPreviewJob {
+                index: idx,
+                seed: job_seed(seed, idx),
+                out_dir: job_dir(idx),
+                params,
+            })
+            .collect::<Vec<_>>();
+        Ok(SweepPreview {
+            plan_hash,
+            job_count: jobs.len(),
+            waves: jobs.len().div_ceil(self.concurrency.max(1)),
+            jobs,
+        })
+    }
+}
+
+/// Job enumerated by [`SweepPlan::preview`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SweepPreviewJob {
+    pub index: usize,
+    pub seed: u64,
+    pub out_dir: String,
+    pub params: BTreeMap<String, Value>,
+}
+
+/// Dry-run view of a sweep: the exact job list and its size.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SweepPreview {
+    pub plan_hash: String,
+    pub job_count: usize,
+    /// Rounds of at most [`SweepPlan::concurrency`] jobs needed to finish.
+    pub waves: usize,
+    pub jobs: Vec<SweepPreviewJob>,
+}
+
+/// Seed of job `idx`, derived from the sweep seed and index alone.
+fn job_seed(seed: u64, idx: usize) -> u64 {
+    seed ^ ((idx as u64 + 1).wrapping_mul(0x9e37_79b1_85eb_ca87))
+}
+
+fn job_dir(idx: usize) -> String {
+    format!("job_{:04}", idx)
 }
 
 /// Supported deterministic sweep strategies.
@@ -47,8 +187,46 @@ pub enum SweepStrategy {
         parameters: Vec<LhsParameter>,
         samples: usize,
     },
+    /// Unscrambled Sobol points over the parameter ranges, independent of the
+    /// sweep seed. At most [`SOBOL_MAX_DIMENSIONS`] parameters are supported.
+    Sobol {
+        parameters: Vec<LhsParameter>,
+        samples: usize,
+    },
 }
 
+/// Largest number of parameters a [`SweepStrategy::Sobol`] plan may span.
+pub const SOBOL_MAX_DIMENSIONS: usize = 1 + SOBOL_DIRECTIONS.len();
+
+const SOBOL_BITS: usize = 32;
+
+/// Primitive polynomial degree `s`, coefficient bits `a` and initial direction
+/// numbers `m` for Sobol dimensions 2 onwards, from the Joe & Kuo
+/// `new-joe-kuo-6.21201` table. The first dimension is the van der Corput
+/// sequence.
+const SOBOL_DIRECTIONS: [(usize, u32, &[u32]); 20] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+    (6, 19, &[1, 1, 1, 15, 7, 5]),
+    (6, 22, &[1, 3, 1, 15, 13, 25]),
+    (6, 25, &[1, 1, 5, 5, 19, 61]),
+    (7, 1, &[1, 3, 7, 11, 23, 15, 103]),
+    (7, 4, &[1, 3, 7, 13, 13, 15, 69]),
+];
+
 /// Grid parameter descriptor.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GridParameter {
@@ -72,6 +250,9 @@ pub struct SweepJobReport {
     pub status: String,
     pub out_dir: String,
     pub end_hashes: Vec<String>,
+    /// Error message recorded when the job failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 /// Aggregate sweep report persisted for reproducibility.
@@ -81,36 +262,282 @@ pub struct SweepReport {
     pub jobs: Vec<SweepJobReport>,
     #[serde(default)]
     pub metrics: Value,
+    /// Number of jobs whose executor returned an error.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub failed: usize,
+    /// Early-stopping decisions, present for [`sweep_with_predicate`] runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub early_stop: Option<EarlyStopLog>,
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+/// Job handed to a [`SweepExecutor`].
+#[derive(Debug, Clone, Copy)]
+pub struct SweepJob<'a> {
+    pub plan_hash: &'a str,
+    pub index: usize,
+    /// Seed derived from the sweep seed and `index` alone.
+    pub seed: u64,
+    pub params: &'a BTreeMap<String, Value>,
+}
+
+/// Runs a single sweep job. Implementations are shared across worker threads.
+pub trait SweepExecutor: Sync {
+    /// Returns the end-state hashes produced by `job`.
+    fn execute(&self, job: &SweepJob<'_>) -> Result<Vec<String>, AsmError>;
+}
+
+impl<F> SweepExecutor for F
+where
+    F: Fn(&SweepJob<'_>) -> Result<Vec<String>, AsmError> + Sync,
+{
+    fn execute(&self, job: &SweepJob<'_>) -> Result<Vec<String>, AsmError> {
+        self(job)
+    }
+}
+
+/// Placeholder executor hashing the plan, job index and parameters.
+fn hash_job(job: &SweepJob<'_>) -> Result<Vec<String>, AsmError> {
+    Ok(vec![stable_hash_string(&(
+        job.plan_hash,
+        job.index,
+        job.params,
+    ))?])
 }
 
 /// Executes a deterministic sweep described by [`SweepPlan`].
 pub fn sweep(plan: &SweepPlan, seed: u64) -> Result<SweepReport, AsmError> {
-    let plan_hash = stable_hash_string(&(plan, seed))?;
-    let job_params = expand_jobs(&plan.strategy, seed)?;
-    let mut jobs = Vec::with_capacity(job_params.len());
-    for (idx, params) in job_params.into_iter().enumerate() {
-        let job_seed = seed ^ ((idx as u64 + 1).wrapping_mul(0x9e37_79b1_85eb_ca87));
-        let end_hash = stable_hash_string(&(plan_hash.clone(), idx, &params))?;
-        let params_value = serde_json::to_value(&params)
-            .map_err(|err| AsmError::Serde(ErrorInfo::new("json-encode", err.to_string())))?;
-        jobs.push(SweepJobReport {
-            params: params_value,
-            seed: job_seed,
-            status: "completed".to_string(),
-            out_dir: format!("job_{:04}", idx),
-            end_hashes: vec![end_hash],
+    sweep_with(plan, seed, &hash_job)
+}
+
+/// Executes a sweep, running up to [`SweepPlan::concurrency`] jobs at once.
+///
+/// Job seeds depend only on the sweep seed and job index, and results are
+/// sorted by index before the report is assembled, so the report is identical
+/// for every concurrency. A failing job is recorded with its error and does
+/// not stop the remaining jobs.
+pub fn sweep_with<E>(plan: &SweepPlan, seed: u64, executor: &E) -> Result<SweepReport, AsmError>
+where
+    E: SweepExecutor + ?Sized,
+{
+    let runner = JobRunner::new(plan, seed)?;
+    let indices: Vec<usize> = (0..runner.job_params.len()).collect();
+    let mut jobs = Vec::with_capacity(indices.len());
+    for (idx, outcome) in runner.run(&indices, executor) {
+        jobs.push(runner.report(idx, Some(outcome))?);
+    }
+    Ok(runner.finish(jobs, None))
+}
+
+/// Executes a sweep, judging each completed job with `predicate` and applying
+/// the plan's [`EarlyStop`] rules (defaults when unset) to the remaining jobs.
+///
+/// Verdicts are applied in job-index order. Jobs run in waves of
+/// [`SweepPlan::concurrency`], and a wave's results are discarded when an
+/// earlier verdict in index order would have skipped them. The report therefore
+/// matches the serial run. Skipped jobs are reported with status `pruned` or
+/// `skipped`, and every decision is logged in [`SweepReport::early_stop`].
+pub fn sweep_with_predicate<E, P>(
+    plan: &SweepPlan,
+    seed: u64,
+    executor: &E,
+    predicate: &P,
+) -> Result<SweepReport, AsmError>
+where
+    E: SweepExecutor + ?Sized,
+    P: Fn(&SweepJobReport) -> JobVerdict + ?Sized,
+{
+    let rules = plan.scheduler.early_stop.clone().unwrap_or_default();
+    let runner = JobRunner::new(plan, seed)?;
+    let total = runner.job_params.len();
+    let branch_params = match &plan.strategy {
+        SweepStrategy::Grid { parameters } => parameters
+            .iter()
+            .take(rules.branch_depth)
+            .map(|param| param.name.as_str())
+            .collect(),
+        SweepStrategy::Lhs { .. } | SweepStrategy::Sobol { .. } => Vec::new(),
+    };
+    let branch_of = |idx: usize| -> BTreeMap<String, Value> {
+        branch_params
+            .iter()
+            .filter_map(|name| {
+                runner.job_params[idx]
+                    .get(*name)
+                    .map(|value| (name.to_string(), value.clone()))
+            })
+            .collect()
+    };
+
+    let mut slots: Vec<Option<SweepJobReport>> = vec![None; total];
+    let mut log = EarlyStopLog {
+        verdicts: Vec::new(),
+        pruned: Vec::new(),
+        budget_reached_at: None,
+    };
+    let mut good = 0usize;
+    let mut next = 0usize;
+    while next < total && log.budget_reached_at.is_none() {
+        let wave: Vec<usize> = (next..total)
+            .filter(|idx| slots[*idx].is_none())
+            .take(plan.concurrency.max(1))
+            .collect();
+        let Some(&last) = wave.last() else {
+            break;
+        };
+        next = last + 1;
+        for (idx, outcome) in runner.run(&wave, executor) {
+            if slots[idx].is_some() || log.budget_reached_at.is_some() {
+                continue;
+            }
+            let report = runner.report(idx, Some(outcome))?;
+            let verdict = if report.status == "completed" {
+                predicate(&report)
+            } else {
+                JobVerdict::Neutral
+            };
+            slots[idx] = Some(report);
+            log.verdicts.push((idx, verdict));
+            match verdict {
+                JobVerdict::Good => {
+                    good += 1;
+                    if rules.good_budget.is_some_and(|budget| good >= budget) {
+                        log.budget_reached_at = Some(idx);
+                    }
+                }
+                JobVerdict::Prune if !branch_params.is_empty() => {
+                    let branch = branch_of(idx);
+                    let mut skipped = Vec::new();
+                    for (later, slot) in slots.iter_mut().enumerate().skip(idx + 1) {
+                        if slot.is_none() && branch_of(later) == branch {
+                            let mut report = runner.report(later, None)?;
+                            report.status = "pruned".to_string();
+                            *slot = Some(report);
+                            skipped.push(later);
+                        }
+                    }
+                    log.pruned.push(PrunedBranch {
+                        trigger: idx,
+                        branch,
+                        skipped,
+                    });
+                }
+                JobVerdict::Prune | JobVerdict::Neutral => {}
+            }
+        }
+    }
+
+    let mut jobs = Vec::with_capacity(total);
+    for (idx, slot) in slots.into_iter().enumerate() {
+        jobs.push(match slot {
+            Some(report) => report,
+            None => runner.report(idx, None)?,
         });
     }
-    let metrics = json!({
-        "jobs": jobs.len(),
-        "parallelism": plan.scheduler.parallelism,
-    });
+    Ok(runner.finish(jobs, Some(log)))
+}
+
+/// Expanded jobs of a plan plus the bookkeeping shared by the sweep drivers.
+struct JobRunner<'a> {
+    plan: &'a SweepPlan,
+    seed: u64,
+    plan_hash: String,
+    job_params: Vec<BTreeMap<String, Value>>,
+    /// Worker pool, built once when more than one job may run at a time.
+    pool: Option<rayon::ThreadPool>,
+}
+
+impl<'a> JobRunner<'a> {
+    fn new(plan: &'a SweepPlan, seed: u64) -> Result<Self, AsmError> {
+        let concurrency = plan.concurrency.max(1);
+        let pool = if concurrency > 1 {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(concurrency)
+                .build()
+                .map_err(|err| {
+                    AsmError::Serde(
+                        ErrorInfo::new("sweep-pool", err.to_string())
+                            .with_context("concurrency", concurrency.to_string()),
+                    )
+                })?;
+            Some(pool)
+        } else {
+            None
+        };
+        Ok(Self {
+            plan,
+            seed,
+            plan_hash: plan.plan_hash(seed)?,
+            job_params: expand_jobs(&plan.strategy, seed)?,
+            pool,
+        })
+    }
+
+    /// Runs the jobs at `indices` on the worker pool, if any, and returns
+    /// their outcomes sorted by index.
+    fn run<E>(&self, indices: &[usize], executor: &E) -> Vec<(usize, Result<Vec<String>, AsmError>)>
+    where
+        E: SweepExecutor + ?Sized,
+    {
+        let run_job = |&idx: &usize| {
+            let job = SweepJob {
+                plan_hash: &self.plan_hash,
+                index: idx,
+                seed: job_seed(self.seed, idx),
+                params: &self.job_params[idx],
+            };
+            (idx, executor.execute(&job))
+        };
+        let mut outcomes: Vec<_> = match &self.pool {
+            Some(pool) if indices.len() > 1 => {
+                pool.install(|| indices.par_iter().map(run_job).collect())
+            }
+            _ => indices.iter().map(run_job).collect(),
+        };
+        outcomes.sort_by_key(|(idx, _)| *idx);
+        outcomes
+    }
 
-    Ok(SweepReport {
-        plan_hash,
-        jobs,
-        metrics,
-    })
+    /// Builds the report for job `idx`; jobs that never ran are `skipped`.
+    fn report(
+        &self,
+        idx: usize,
+        outcome: Option<Result<Vec<String>, AsmError>>,
+    ) -> Result<SweepJobReport, AsmError> {
+        let params = serde_json::to_value(&self.job_params[idx])
+            .map_err(|err| AsmError::Serde(ErrorInfo::new("json-encode", err.to_string())))?;
+        let (status, end_hashes, error) = match outcome {
+            Some(Ok(end_hashes)) => ("completed", end_hashes, None),
+            Some(Err(err)) => ("failed", Vec::new(), Some(err.to_string())),
+            None => ("skipped", Vec::new(), None),
+        };
+        Ok(SweepJobReport {
+            params,
+            seed: job_seed(self.seed, idx),
+            status: status.to_string(),
+            out_dir: job_dir(idx),
+            end_hashes,
+            error,
+        })
+    }
+
+    fn finish(self, jobs: Vec<SweepJobReport>, early_stop: Option<EarlyStopLog>) -> SweepReport {
+        let failed = jobs.iter().filter(|job| job.status == "failed").count();
+        let metrics = json!({
+            "jobs": jobs.len(),
+            "parallelism": self.plan.scheduler.parallelism,
+        });
+        SweepReport {
+            plan_hash: self.plan_hash,
+            jobs,
+            metrics,
+            failed,
+            early_stop,
+        }
+    }
 }
 
 fn expand_jobs(
@@ -127,6 +554,10 @@ fn expand_jobs(
             parameters,
             samples,
         } => expand_lhs(parameters, *samples, seed),
+        SweepStrategy::Sobol {
+            parameters,
+            samples,
+        } => expand_sobol(parameters, *samples),
     }
 }
 
@@ -168,3 +599,78 @@ fn expand_lhs(
     }
     Ok(outputs)
 }
+
+/// Direction numbers of Sobol dimension `dim` (zero-based), scaled to
+/// `SOBOL_BITS` bits.
+fn sobol_directions(dim: usize) -> [u32; SOBOL_BITS] {
+    let mut v = [0u32; SOBOL_BITS];
+    if dim == 0 {
+        for (k, slot) in v.iter_mut().enumerate() {
+            *slot = 1 << (SOBOL_BITS - 1 - k);
+        }
+        return v;
+    }
+    let (s, a, m) = SOBOL_DIRECTIONS[dim - 1];
+    for k in 0..SOBOL_BITS {
+        v[k] = if k < s {
+            m[k] << (SOBOL_BITS - 1 - k)
+        } else {
+            let mut next = v[k - s] ^ (v[k - s] >> s);
+            for j in 1..s {
+                if (a >> (s - 1 - j)) & 1 == 1 {
+                    next ^= v[k - j];
+                }
+            }
+            next
+        };
+    }
+    v
+}
+
+fn expand_sobol(
+    params: &[LhsParameter],
+    samples: usize,
+) -> Result<Vec<BTreeMap<String, Value>>, AsmError> {
+    if params.len() > SOBOL_MAX_DIMENSIONS {
+        return Err(AsmError::Serde(ErrorInfo::new(
+            "sweep-sobol-dimensions",
+            format!(
+                "sobol sweeps support at most {SOBOL_MAX_DIMENSIONS} parameters, got {}",
+                params.len()
+            ),
+        )));
+    }
+    if samples as u64 > 1u64 << SOBOL_BITS {
+        return Err(AsmError::Serde(ErrorInfo::new(
+            "sweep-sobol-samples",
+            format!("sobol sweeps support at most 2^{SOBOL_BITS} samples, got {samples}"),
+        )));
+    }
+    let directions: Vec<_> = (0..params.len()).map(sobol_directions).collect();
+    let scale = (1u64 << SOBOL_BITS) as f64;
+    let mut point = vec![0u32; params.len()];
+    let mut outputs = Vec::with_capacity(samples);
+    for idx in 0..samples {
+        // Gray-code order: each point flips the direction number indexed by
+        // the lowest zero bit of the previous index.
+        if idx > 0 {
+            let bit = (idx - 1).trailing_ones() as usize;
+            for (coord, dirs) in point.iter_mut().zip(&directions) {
+                *coord ^= dirs[bit];
+            }
+        }
+        let job = params
+            .iter()
+            .zip(&point)
+            .map(|(param, coord)| {
+                let frac = f64::from(*coord) / scale;
+                (
+                    param.name.clone(),
+                    json!(param.min + frac * (param.max - param.min)),
+                )
+            })
+            .collect();
+        outputs.push(job);
+    }
+    Ok(outputs)
+}
@@ -0,0 +1,116 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use asm_code::css::CSSCode;
+use asm_core::provenance::{RunProvenance, SchemaVersion};
+use asm_core::rng::RngHandle;
+use asm_exp::{artifact_hash, build_runbook, RunMeta, RunStep, StepStatus};
+use asm_graph::gen_quasi_regular;
+use asm_mcmc::manifest::RunManifest;
+use asm_mcmc::{run, MoveCounts, RunConfig};
+use asm_spec::{analyze_spectrum, to_canonical_json_bytes, SpecOpts};
+use serde_json::{json, Value};
+
+fn temp_path(name: &str) -> PathBuf {
+    let mut base = env::temp_dir();
+    base.push(format!("asm_build_test_{}_{}", name, std::process::id()));
+    base
+}
+
+fn sample_code() -> CSSCode {
+    CSSCode::new(
+        6,
+        vec![vec![0, 1, 2, 3], vec![2, 3, 4, 5]],
+        vec![vec![0, 1, 2, 3], vec![2, 3, 4, 5]],
+        SchemaVersion::new(1, 0, 0),
+        RunProvenance::default(),
+    )
+    .unwrap()
+}
+
+fn completed_step(root: &Path, id: &str, output: &str, depends_on: &[&str]) -> RunStep {
+    let hash = artifact_hash(&root.join(output)).expect("hash");
+    RunStep {
+        id: id.to_string(),
+        depends_on: depends_on.iter().map(|dep| dep.to_string()).collect(),
+        outputs: vec![output.to_string()],
+        hashes: [(output.to_string(), hash)].into_iter().collect(),
+        completed: true,
+    }
+}
+
+fn meta(steps: Vec<RunStep>) -> RunMeta {
+    RunMeta {
+        created_at: "1970-01-01T00:00:00Z".to_string(),
+        commit: "test".to_string(),
+        seeds: vec![1],
+        artifacts: Vec::new(),
+        summary: Value::Null,
+        steps,
+    }
+}
+
+#[test]
+fn pipeline_stages_share_one_fingerprint() {
+    let root = temp_path("pipeline");
+    let _ = fs::remove_dir_all(&root);
+    let graph = gen_quasi_regular(24, 3, 2, &mut RngHandle::from_seed(5)).unwrap();
+    let code = sample_code();
+
+    let mut config = RunConfig::default();
+    config.sweeps = 2;
+    config.move_counts = MoveCounts {
+        generator_flips: 1,
+        row_ops: 1,
+        graph_rewires: 1,
+        worm_moves: 1,
+    };
+    config.output.run_directory = Some(root.join("run"));
+    let summary = run(&config, 11, &code, &graph).expect("mcmc run");
+    let manifest = RunManifest::load_consistent(&summary.manifest_path.unwrap()).unwrap();
+
+    let opts: SpecOpts =
+        serde_json::from_value(json!({"propagation": {"seed": 7}, "master_seed": 3})).unwrap();
+    let report = analyze_spectrum(&graph, &code, &opts).expect("spectrum");
+    assert!(report.provenance.build.is_some());
+    assert_eq!(manifest.build, report.provenance.build);
+
+    // Recording the fingerprint leaves the analysis hash unchanged.
+    let mut bare = report.clone();
+    bare.provenance.build = None;
+    assert_eq!(
+        asm_spec::report::recompute_analysis_hash(&bare).unwrap(),
+        report.analysis_hash
+    );
+
+    let spectrum = root.join("spectrum.json");
+    fs::write(&spectrum, to_canonical_json_bytes(&report).unwrap()).unwrap();
+    let steps = vec![
+        completed_step(&root, "mcmc", "run/manifest.json", &[]),
+        completed_step(&root, "spectrum", "spectrum.json", &["mcmc"]),
+    ];
+    let verification = build_runbook(&[], &meta(steps)).unwrap().verify(&root);
+    assert!(verification
+        .steps
+        .iter()
+        .all(|step| step.status == StepStatus::Done));
+    assert!(verification.build_mismatches.is_empty());
+
+    let mut rebuilt = report.clone();
+    rebuilt.provenance.build.as_mut().unwrap().rustc = "rustc 0.0.0".into();
+    fs::write(&spectrum, to_canonical_json_bytes(&rebuilt).unwrap()).unwrap();
+    let steps = vec![
+        completed_step(&root, "mcmc", "run/manifest.json", &[]),
+        completed_step(&root, "spectrum", "spectrum.json", &["mcmc"]),
+    ];
+    let verification = build_runbook(&[], &meta(steps)).unwrap().verify(&root);
+    assert_eq!(verification.build_mismatches.len(), 1);
+    let mismatch = &verification.build_mismatches[0];
+    assert_eq!(mismatch.stage, "spectrum");
+    assert_eq!(mismatch.reference, "mcmc");
+    assert_eq!(mismatch.changes[0].field, "rustc");
+    assert_eq!(mismatch.changes[0].new, "rustc 0.0.0");
+
+    fs::remove_dir_all(&root).ok();
+}
@@ -0,0 +1,85 @@
+use std::fs;
+use std::path::PathBuf;
+
+use asm_exp::{
+    determinism_selftest, to_canonical_json_bytes, SelfTestLevel, SelfTestReference,
+    SelfTestReport, SELFTEST_REFERENCE_FORMAT,
+};
+
+/// Update procedure for the reference hashes: when a change intentionally
+/// alters a workload's output, rerun this test with `ASM_UPDATE_GOLDEN=1`,
+/// review the rewritten fixture, and note the reason in the CHANGELOG.
+fn reference_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/selftest_reference.json")
+}
+
+#[test]
+fn full_selftest_matches_reference() {
+    let report = determinism_selftest(SelfTestLevel::Full);
+    if std::env::var_os("ASM_UPDATE_GOLDEN").is_some() {
+        let reference = SelfTestReference {
+            format: SELFTEST_REFERENCE_FORMAT,
+            workloads: report
+                .workloads
+                .iter()
+                .map(|workload| {
+                    let actual = workload.actual.clone().expect("workload hash");
+                    (workload.name.clone(), actual)
+                })
+                .collect(),
+        };
+        let mut json = serde_json::to_string_pretty(&reference).expect("encode reference");
+        json.push('\n');
+        fs::write(reference_path(), json).expect("write reference");
+        return;
+    }
+    let mismatches: Vec<_> = report.mismatches().collect();
+    assert!(
+        mismatches.is_empty(),
+        "self-test hashes differ from {}: {mismatches:#?}\n\
+         If the change is intentional, rerun with ASM_UPDATE_GOLDEN=1 and record why in the CHANGELOG.",
+        reference_path().display()
+    );
+    assert!(report.pass);
+}
+
+#[test]
+fn reference_covers_exactly_the_known_workloads() {
+    let reference = SelfTestReference::embedded().expect("embedded reference");
+    let on_disk: SelfTestReference =
+        serde_json::from_slice(&fs::read(reference_path()).expect("read reference"))
+            .expect("parse reference");
+    assert_eq!(reference, on_disk, "rebuild after editing the fixture");
+    let names: Vec<_> = reference.workloads.keys().map(String::as_str).collect();
+    let mut known = SelfTestReference::workload_names();
+    known.sort_unstable();
+    assert_eq!(names, known);
+    assert!(reference
+        .workloads
+        .values()
+        .all(|hash| hash.len() == 64 && hash.chars().all(|ch| ch.is_ascii_hexdigit())));
+}
+
+#[test]
+fn levels_gate_workloads() {
+    let quick = determinism_selftest(SelfTestLevel::Quick);
+    let names: Vec<_> = quick.workloads.iter().map(|w| w.name.as_str()).collect();
+    assert_eq!(names, ["graph", "code", "operators"]);
+    let full = determinism_selftest(SelfTestLevel::Full);
+    let names: Vec<_> = full.workloads.iter().map(|w| w.name.as_str()).collect();
+    assert_eq!(names, ["graph", "code", "operators", "mcmc", "dispersion"]);
+    assert_eq!(quick.workloads[..], full.workloads[..3]);
+}
+
+#[test]
+fn report_is_a_canonical_certificate() {
+    let report = determinism_selftest(SelfTestLevel::Quick);
+    let bytes = to_canonical_json_bytes(&report).expect("encode");
+    let again = to_canonical_json_bytes(&determinism_selftest(SelfTestLevel::Quick)).unwrap();
+    assert_eq!(bytes, again);
+    let restored: SelfTestReport = serde_json::from_slice(&bytes).expect("decode");
+    assert_eq!(restored, report);
+    assert_eq!(restored.platform.arch, std::env::consts::ARCH);
+    let text = String::from_utf8(bytes).unwrap();
+    assert!(text.contains("\"level\":\"quick\""));
+}
@@ -2,6 +2,7 @@ use asm_core::errors::{AsmError, ErrorInfo};
 use serde::{Deserialize, Serialize};
 
 use crate::rep::RepMatrices;
+use crate::subset::{is_false, resolve, SubsetInfo, SubsetSpec};
 
 fn round(value: f64) -> f64 {
     (value * 1e9).round() / 1e9
@@ -15,18 +16,31 @@ fn default_tolerance() -> f64 {
     1e-6
 }
 
+fn default_report_top() -> usize {
+    5
+}
+
 /// Options controlling closure checks and structure tensor extraction.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ClosureOpts {
     /// Maximum allowed commutator residual for the algebra to be considered closed.
     #[serde(default = "default_tolerance")]
     pub tolerance: f64,
+    /// Maximum number of failing generator pairs retained in the report.
+    #[serde(default = "default_report_top")]
+    pub report_top: usize,
+    /// Evaluates only a deterministic subset of the generator pairs `i < j`,
+    /// indexed in row-major order. Reports are then marked partial.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pair_subset: Option<SubsetSpec>,
 }
 
 impl Default for ClosureOpts {
     fn default() -> Self {
         Self {
             tolerance: default_tolerance(),
+            report_top: default_report_top(),
+            pair_subset: None,
         }
     }
 }
@@ -44,6 +58,17 @@ pub struct StructureTensorEntry {
     pub value: f64,
 }
 
+/// Generator pair that failed to close within tolerance.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClosureOffender {
+    /// Largest-magnitude structure tensor entry of the pair (lowest `k` on
+    /// ties); its `value` is zero when the commutator lies wholly outside the
+    /// generator span.
+    pub entry: StructureTensorEntry,
+    /// Norm of the commutator component of `[g_i, g_j]` left outside the generator span.
+    pub residual: f64,
+}
+
 /// Summary of the closure check.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ClosureReport {
@@ -53,6 +78,16 @@ pub struct ClosureReport {
     pub max_dev: f64,
     /// Structure tensor entries describing reconstructed commutators.
     pub structure_tensors: Vec<StructureTensorEntry>,
+    /// Failing generator pairs, one per pair, worst residual first and
+    /// truncated to [`ClosureOpts::report_top`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub offenders: Vec<ClosureOffender>,
+    /// Whether only a subset of generator pairs was evaluated.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub partial: bool,
+    /// Pairs evaluated when [`ClosureOpts::pair_subset`] was set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subset: Option<SubsetInfo>,
 }
 
 fn dot(a: &[f64], b: &[f64]) -> f64 {
@@ -81,6 +116,30 @@ fn norm(matrix: &[f64]) -> f64 {
     matrix.iter().map(|x| x * x).sum::<f64>().sqrt()
 }
 
+/// Dense structure tensor `f[(i * n + j) * n + k] = f^k_{ij}` over all ordered generator pairs.
+///
+/// Coefficients use the same per-generator projection as [`check_closure`].
+pub(crate) fn structure_constants(rep: &RepMatrices) -> Vec<f64> {
+    let n = rep.gens.len();
+    let dim = rep.dim;
+    let mut tensor = vec![0.0; n * n * n];
+    for (i, gi) in rep.gens.iter().enumerate() {
+        for (j, gj) in rep.gens.iter().enumerate().skip(i + 1) {
+            let comm = subtract(
+                &matmul(&gi.matrix, &gj.matrix, dim),
+                &matmul(&gj.matrix, &gi.matrix, dim),
+            );
+            for (k, gk) in rep.gens.iter().enumerate() {
+                let denom = dot(&gk.matrix, &gk.matrix).max(1e-12);
+                let coeff = round(dot(&comm, &gk.matrix) / denom);
+                tensor[(i * n + j) * n + k] = coeff;
+                tensor[(j * n + i) * n + k] = -coeff;
+            }
+        }
+    }
+    tensor
+}
+
 /// Computes commutators of the provided representation and estimates structure tensors.
 pub fn check_closure(rep: &RepMatrices, opts: &ClosureOpts) -> Result<ClosureReport, AsmError> {
     if rep.dim == 0 {
@@ -97,11 +156,15 @@ pub fn check_closure(rep: &RepMatrices, opts: &ClosureOpts) -> Result<ClosureRep
     }
 
     let dim = rep.dim;
+    let n = rep.gens.len();
+    let (subset, selected) = resolve(opts.pair_subset.as_ref(), n * (n - 1) / 2)?;
+    let mut selected = selected.into_iter();
     let mut max_dev: f64 = 0.0;
     let mut tensors = Vec::new();
+    let mut offenders = Vec::new();
     for (i, gi) in rep.gens.iter().enumerate() {
         for (j, gj) in rep.gens.iter().enumerate() {
-            if j <= i {
+            if j <= i || !selected.next().unwrap_or(false) {
                 continue;
             }
             let gi_gj = matmul(&gi.matrix, &gj.matrix, dim);
@@ -112,6 +175,7 @@ pub fn check_closure(rep: &RepMatrices, opts: &ClosureOpts) -> Result<ClosureRep
                 continue;
             }
             let mut reconstruction = vec![0.0; dim * dim];
+            let pair_start = tensors.len();
             for (k, gk) in rep.gens.iter().enumerate() {
                 let denom = dot(&gk.matrix, &gk.matrix).max(1e-12);
                 let coeff = dot(&comm, &gk.matrix) / denom;
@@ -129,12 +193,32 @@ pub fn check_closure(rep: &RepMatrices, opts: &ClosureOpts) -> Result<ClosureRep
             let residual = subtract(&comm, &reconstruction);
             let residual_norm = norm(&residual);
             max_dev = max_dev.max(residual_norm);
+            if residual_norm > opts.tolerance {
+                // Reversed comparison so ties keep the lowest k.
+                let dominant = tensors[pair_start..]
+                    .iter()
+                    .min_by(|a, b| b.value.abs().total_cmp(&a.value.abs()));
+                offenders.extend(dominant.map(|entry| ClosureOffender {
+                    entry: entry.clone(),
+                    residual: round(residual_norm),
+                }));
+            }
         }
     }
 
+    offenders.sort_by(|a, b| {
+        b.residual
+            .total_cmp(&a.residual)
+            .then_with(|| (a.entry.i, a.entry.j).cmp(&(b.entry.i, b.entry.j)))
+    });
+    offenders.truncate(opts.report_top);
+
     Ok(ClosureReport {
         closed: max_dev <= opts.tolerance,
         max_dev: round(max_dev),
         structure_tensors: tensors,
+        offenders,
+        partial: subset.as_ref().is_some_and(SubsetInfo::is_partial),
+        subset,
     })
 }
@@ -3,8 +3,10 @@ use std::collections::BTreeMap;
 use asm_core::errors::AsmError;
 use serde::{Deserialize, Serialize};
 
+use crate::closure::structure_constants;
 use crate::invariants::GeneratorInvariants;
 use crate::rep::RepMatrices;
+use crate::roots::{classify_generators, RootSystem};
 
 fn round(value: f64) -> f64 {
     (value * 1e9).round() / 1e9
@@ -14,18 +16,26 @@ fn default_trace_tol() -> f64 {
     1e-6
 }
 
+fn default_root_tol() -> f64 {
+    1e-6
+}
+
 /// Options controlling algebra factor decomposition.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct DecompOpts {
     /// Absolute trace tolerance used to classify u(1) vs su(2)-like factors.
     #[serde(default = "default_trace_tol")]
     pub trace_tol: f64,
+    /// Relative tolerance for bracket connectivity, Killing-form definiteness, and root pairing.
+    #[serde(default = "default_root_tol")]
+    pub root_tol: f64,
 }
 
 impl Default for DecompOpts {
     fn default() -> Self {
         Self {
             trace_tol: default_trace_tol(),
+            root_tol: default_root_tol(),
         }
     }
 }
@@ -41,6 +51,9 @@ pub struct FactorInfo {
     pub rank: usize,
     /// Deterministic invariants associated with the factor.
     pub invariants: BTreeMap<String, f64>,
+    /// Root-system identification of the algebra component containing the generator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub root_system: Option<RootSystem>,
 }
 
 /// Report describing the decomposition of the gauge algebra.
@@ -52,7 +65,7 @@ pub struct DecompReport {
     pub residual_norm: f64,
 }
 
-fn classify(trace: f64, tol: f64, symmetry: f64) -> &'static str {
+pub(crate) fn classify(trace: f64, tol: f64, symmetry: f64) -> &'static str {
     if trace.abs() <= tol {
         if symmetry <= tol {
             "su2"
@@ -72,9 +85,10 @@ pub fn decompose(rep: &RepMatrices, opts: &DecompOpts) -> Result<DecompReport, A
             residual_norm: 0.0,
         });
     }
+    let systems = classify_generators(&structure_constants(rep), rep.gens.len(), opts.root_tol);
     let mut factors = Vec::with_capacity(rep.gens.len());
     let mut residual = 0.0;
-    for gen in &rep.gens {
+    for (gen, system) in rep.gens.iter().zip(systems) {
         let invariants = GeneratorInvariants::from_matrix(&gen.matrix, rep.dim);
         residual += (invariants.trace.abs() - opts.trace_tol).max(0.0);
         let mut map = BTreeMap::new();
@@ -87,6 +101,7 @@ pub fn decompose(rep: &RepMatrices, opts: &DecompOpts) -> Result<DecompReport, A
             dim: rep.dim,
             rank: rep.dim,
             invariants: map,
+            root_system: Some(system),
         });
     }
 
@@ -1,4 +1,5 @@
 use asm_core::errors::AsmError;
+use asm_core::hash::{domain_hash, hash_with_format, HashDomain, LEGACY_HASH_FORMAT};
 use serde::Serialize;
 use sha2::{Digest, Sha256};
 
@@ -10,3 +11,32 @@ pub fn stable_hash_string<T: Serialize>(value: &T) -> Result<String, AsmError> {
     let digest = Sha256::digest(bytes);
     Ok(format!("{:x}", digest))
 }
+
+/// Computes a domain-separated hash (`<tag>1-<hex>`) for the provided payload.
+pub fn stable_hash_with_domain<T: Serialize>(
+    domain: HashDomain,
+    value: &T,
+) -> Result<String, AsmError> {
+    Ok(domain_hash(domain, &to_canonical_json_bytes(value)?))
+}
+
+/// Hashes the payload in the layout recorded by a report's `hash_format`.
+pub(crate) fn stable_hash_with_format<T: Serialize>(
+    format: u32,
+    domain: HashDomain,
+    value: &T,
+) -> Result<String, AsmError> {
+    Ok(hash_with_format(
+        format,
+        domain,
+        &to_canonical_json_bytes(value)?,
+    ))
+}
+
+pub(crate) fn legacy_hash_format() -> u32 {
+    LEGACY_HASH_FORMAT
+}
+
+pub(crate) fn is_legacy_hash_format(format: &u32) -> bool {
+    *format <= LEGACY_HASH_FORMAT
+}
@@ -1,5 +1,9 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::rep::RepMatrices;
+
 fn round(value: f64) -> f64 {
     (value * 1e9).round() / 1e9
 }
@@ -43,3 +47,148 @@ impl GeneratorInvariants {
         }
     }
 }
+
+fn default_anomaly_tol() -> f64 {
+    1e-6
+}
+
+fn default_anomaly_top() -> usize {
+    5
+}
+
+/// Options controlling the cubic anomaly computation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AnomalyOpts {
+    /// Whether [`crate::analyze_gauge`] attaches an [`AnomalyReport`].
+    #[serde(default)]
+    pub enabled: bool,
+    /// Maximum tolerated magnitude of any anomaly coefficient.
+    #[serde(default = "default_anomaly_tol")]
+    pub tolerance: f64,
+    /// Number of largest non-cancelling coefficients listed in the report.
+    #[serde(default = "default_anomaly_top")]
+    pub report_top: usize,
+}
+
+impl Default for AnomalyOpts {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            tolerance: default_anomaly_tol(),
+            report_top: default_anomaly_top(),
+        }
+    }
+}
+
+/// Symmetrised cubic trace `d_abc = tr(T_a {T_b, T_c}) / 2` for `a ≤ b ≤ c`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AnomalyCoefficient {
+    /// First generator index.
+    pub a: usize,
+    /// Second generator index.
+    pub b: usize,
+    /// Third generator index.
+    pub c: usize,
+    /// Coefficient value.
+    pub value: f64,
+}
+
+/// Result of the cubic anomaly cancellation check.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AnomalyReport {
+    /// Number of generators entering the computation.
+    pub generators: usize,
+    /// Number of index triples `a ≤ b ≤ c` evaluated.
+    pub triples_checked: usize,
+    /// Largest coefficient magnitude.
+    pub max_abs: f64,
+    /// Largest coefficients exceeding the tolerance, ordered by magnitude.
+    pub violations: Vec<AnomalyCoefficient>,
+    /// Whether every coefficient cancels within tolerance.
+    pub pass: bool,
+    /// Tolerance applied to the coefficients.
+    pub tolerance: f64,
+}
+
+fn matmul(a: &[f64], b: &[f64], dim: usize) -> Vec<f64> {
+    let mut out = vec![0.0; dim * dim];
+    for row in 0..dim {
+        for k in 0..dim {
+            let lhs = a[row * dim + k];
+            if lhs == 0.0 {
+                continue;
+            }
+            for col in 0..dim {
+                out[row * dim + col] += lhs * b[k * dim + col];
+            }
+        }
+    }
+    out
+}
+
+fn trace_product(a: &[f64], b: &[f64], dim: usize) -> f64 {
+    let mut acc = 0.0;
+    for row in 0..dim {
+        for col in 0..dim {
+            acc += a[row * dim + col] * b[col * dim + row];
+        }
+    }
+    acc
+}
+
+/// Computes the cubic anomaly coefficients of a representation.
+///
+/// The anomaly cancels when every symmetrised trace `d_abc` vanishes within
+/// `opts.tolerance`.
+pub fn anomaly_coefficients(rep: &RepMatrices, opts: &AnomalyOpts) -> AnomalyReport {
+    let dim = rep.dim;
+    let count = rep.gens.len();
+    let mut products = BTreeMap::new();
+    for b in 0..count {
+        for c in b..count {
+            let bc = matmul(&rep.gens[b].matrix, &rep.gens[c].matrix, dim);
+            let cb = matmul(&rep.gens[c].matrix, &rep.gens[b].matrix, dim);
+            let anti: Vec<f64> = bc.iter().zip(&cb).map(|(x, y)| x + y).collect();
+            products.insert((b, c), anti);
+        }
+    }
+
+    let mut coefficients = Vec::new();
+    let mut triples_checked = 0;
+    let mut max_abs: f64 = 0.0;
+    for a in 0..count {
+        for b in a..count {
+            for c in b..count {
+                triples_checked += 1;
+                let value = 0.5 * trace_product(&rep.gens[a].matrix, &products[&(b, c)], dim);
+                max_abs = max_abs.max(value.abs());
+                if value.abs() > opts.tolerance {
+                    coefficients.push(AnomalyCoefficient {
+                        a,
+                        b,
+                        c,
+                        value: round(value),
+                    });
+                }
+            }
+        }
+    }
+    coefficients.sort_by(|x, y| {
+        y.value
+            .abs()
+            .partial_cmp(&x.value.abs())
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| (x.a, x.b, x.c).cmp(&(y.a, y.b, y.c)))
+    });
+    let pass = coefficients.is_empty();
+    coefficients.truncate(opts.report_top);
+
+    AnomalyReport {
+        generators: count,
+        triples_checked,
+        max_abs: round(max_abs),
+        violations: coefficients,
+        pass,
+        tolerance: opts.tolerance,
+    }
+}
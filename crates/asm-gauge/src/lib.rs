@@ -7,15 +7,28 @@ mod hash;
 mod invariants;
 mod rep;
 mod report;
+mod roots;
 mod serde;
+mod subset;
+mod sweep;
 mod ward;
 
-pub use closure::{check_closure, ClosureOpts, ClosureReport, StructureTensorEntry};
+pub use closure::{
+    check_closure, ClosureOffender, ClosureOpts, ClosureReport, StructureTensorEntry,
+};
 pub use decomp::{decompose, DecompOpts, DecompReport, FactorInfo};
-pub use hash::stable_hash_string;
-pub use rep::{build_rep, RepGenerator, RepMatrices, RepOpts};
-pub use report::{analyze_gauge, GaugeOpts, GaugeProvenance, GaugeReport};
+pub use hash::{stable_hash_string, stable_hash_with_domain};
+pub use rep::{build_rep, CommutatorViolation, RepDiagnostics, RepGenerator, RepMatrices, RepOpts};
+pub use report::{analyze_gauge, recompute_analysis_hash, GaugeOpts, GaugeProvenance, GaugeReport};
+pub use roots::{RootSystem, UNIDENTIFIED};
 pub use serde::{from_json_slice, to_canonical_json_bytes};
-pub use ward::{ward_check, WardOpts, WardReport, WardThresholds};
+pub use subset::{SubsetInfo, SubsetSelection, SubsetSpec};
+pub use sweep::{analyze_gauge_sweep, GaugeSweepPoint, GaugeSweepReport, SweepTolerances};
+pub use ward::{
+    jacobi_check, ward_check, JacobiOpts, JacobiReport, WardCorrelator, WardIdentity, WardOpts,
+    WardReport, WardThresholds, COMMUTATOR_IDENTITY,
+};
 
-pub use invariants::GeneratorInvariants;
+pub use invariants::{
+    anomaly_coefficients, AnomalyCoefficient, AnomalyOpts, AnomalyReport, GeneratorInvariants,
+};
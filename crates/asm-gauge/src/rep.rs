@@ -1,10 +1,13 @@
 use asm_aut::AnalysisReport;
 use asm_core::errors::{AsmError, ErrorInfo};
+use asm_core::hash::parse_hash;
 use asm_core::rng::RngHandle;
 use asm_spec::SpectrumReport;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
+use crate::closure::{check_closure, ClosureOpts};
+
 fn gauge_error(code: &str, message: impl Into<String>) -> AsmError {
     AsmError::Serde(ErrorInfo::new(code, message))
 }
@@ -21,6 +24,10 @@ fn default_generator_limit() -> usize {
     3
 }
 
+fn default_commutator_tol() -> f64 {
+    1e-6
+}
+
 /// Options controlling representation construction.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct RepOpts {
@@ -33,6 +40,12 @@ pub struct RepOpts {
     /// Optional deterministic seed overriding the provenance derived from hashes.
     #[serde(default)]
     pub seed: Option<u64>,
+    /// Attach [`RepDiagnostics`] (dimension, Casimir, commutator validation) to the output.
+    #[serde(default)]
+    pub diagnostics: bool,
+    /// Maximum commutator residual outside the generator span before a pair is flagged.
+    #[serde(default = "default_commutator_tol")]
+    pub commutator_tol: f64,
 }
 
 impl Default for RepOpts {
@@ -41,6 +54,8 @@ impl Default for RepOpts {
             basis: default_basis(),
             max_generators: default_generator_limit(),
             seed: None,
+            diagnostics: false,
+            commutator_tol: default_commutator_tol(),
         }
     }
 }
@@ -65,10 +80,199 @@ pub struct RepMatrices {
     pub dim: usize,
     /// Generator matrices recorded in deterministic order.
     pub gens: Vec<RepGenerator>,
+    /// Validation diagnostics, present when [`RepOpts::diagnostics`] is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<RepDiagnostics>,
+}
+
+/// Generator pair whose commutator leaves the span of the generators.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommutatorViolation {
+    /// Left generator index.
+    pub i: usize,
+    /// Right generator index.
+    pub j: usize,
+    /// Norm of the commutator component outside the generator span.
+    pub residual: f64,
+}
+
+/// Normalisation and consistency diagnostics for a representation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RepDiagnostics {
+    /// Representation dimension.
+    pub dimension: usize,
+    /// Quadratic Casimir eigenvalue, absent when the generators are linearly dependent.
+    pub casimir: Option<f64>,
+    /// Frobenius distance of the Casimir operator from `casimir * I`; zero on an irrep.
+    pub casimir_scalar_dev: Option<f64>,
+    /// Largest commutator residual recorded across generator pairs.
+    pub max_commutator_residual: f64,
+    /// Pairs whose residual exceeded [`RepOpts::commutator_tol`].
+    pub violations: Vec<CommutatorViolation>,
+    /// Whether every commutator closed within tolerance.
+    pub pass: bool,
+}
+
+/// Solves `a x = b` for a square system, returning `None` when `a` is singular.
+fn solve(mut a: Vec<f64>, mut b: Vec<f64>, n: usize) -> Option<Vec<f64>> {
+    let scale = a.iter().map(|v| v.abs()).fold(0.0, f64::max).max(1e-300);
+    for col in 0..n {
+        let pivot =
+            (col..n).max_by(|&x, &y| a[x * n + col].abs().total_cmp(&a[y * n + col].abs()))?;
+        if a[pivot * n + col].abs() <= 1e-12 * scale {
+            return None;
+        }
+        if pivot != col {
+            for k in 0..n {
+                a.swap(pivot * n + k, col * n + k);
+            }
+            b.swap(pivot, col);
+        }
+        for row in (col + 1)..n {
+            let factor = a[row * n + col] / a[col * n + col];
+            for k in col..n {
+                a[row * n + k] -= factor * a[col * n + k];
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = ((row + 1)..n).map(|k| a[row * n + k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row * n + row];
+    }
+    Some(x)
+}
+
+impl RepMatrices {
+    /// Returns the dimension of the representation space.
+    pub fn dimension(&self) -> usize {
+        self.dim
+    }
+
+    /// Quadratic Casimir operator `C = sum_ab (G^-1)_ab T_a^T T_b` as a row-major matrix.
+    ///
+    /// `G_ab = tr(T_a^T T_b)` is the trace form of the representation, so `C`
+    /// commutes with every generator of a compact algebra and `tr C` equals the
+    /// number of generators.
+    fn casimir_operator(&self) -> Result<Vec<f64>, AsmError> {
+        let n = self.gens.len();
+        let dim = self.dim;
+        if dim == 0 || n == 0 {
+            return Err(gauge_error(
+                "empty-representation",
+                "casimir requires a non-empty representation",
+            ));
+        }
+        let gram: Vec<f64> = (0..n * n)
+            .map(|idx| {
+                let (a, b) = (idx / n, idx % n);
+                self.gens[a]
+                    .matrix
+                    .iter()
+                    .zip(&self.gens[b].matrix)
+                    .map(|(x, y)| x * y)
+                    .sum()
+            })
+            .collect();
+        let mut operator = vec![0.0; dim * dim];
+        for b in 0..n {
+            let mut unit = vec![0.0; n];
+            unit[b] = 1.0;
+            let column = solve(gram.clone(), unit, n).ok_or_else(|| {
+                gauge_error(
+                    "degenerate-generators",
+                    "generators are linearly dependent under the trace form",
+                )
+            })?;
+            let tb = &self.gens[b].matrix;
+            for (a, weight) in column.iter().enumerate() {
+                if *weight == 0.0 {
+                    continue;
+                }
+                let ta = &self.gens[a].matrix;
+                for row in 0..dim {
+                    for col in 0..dim {
+                        let mut acc = 0.0;
+                        for k in 0..dim {
+                            acc += ta[k * dim + row] * tb[k * dim + col];
+                        }
+                        operator[row * dim + col] += weight * acc;
+                    }
+                }
+            }
+        }
+        Ok(operator)
+    }
+
+    /// Quadratic Casimir eigenvalue `tr(C) / dim`.
+    ///
+    /// With the trace-form normalisation the value is `generators / dim`, and
+    /// it is exact whenever the representation is irreducible.
+    pub fn casimir(&self) -> Result<f64, AsmError> {
+        let operator = self.casimir_operator()?;
+        let trace: f64 = (0..self.dim)
+            .map(|idx| operator[idx * self.dim + idx])
+            .sum();
+        Ok(round(trace / self.dim as f64))
+    }
+
+    /// Computes dimension, Casimir, and commutator diagnostics for the representation.
+    pub fn diagnostics(&self, commutator_tol: f64) -> Result<RepDiagnostics, AsmError> {
+        let (casimir, casimir_scalar_dev) = match self.casimir_operator() {
+            Ok(operator) => {
+                let dim = self.dim;
+                let trace: f64 = (0..dim).map(|idx| operator[idx * dim + idx]).sum();
+                let value = trace / dim as f64;
+                let dev = operator
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, entry)| {
+                        let target = if idx / dim == idx % dim { value } else { 0.0 };
+                        (entry - target).powi(2)
+                    })
+                    .sum::<f64>()
+                    .sqrt();
+                (Some(round(value)), Some(round(dev)))
+            }
+            Err(err) if err.info().code == "degenerate-generators" => (None, None),
+            Err(err) => return Err(err),
+        };
+
+        let closure = check_closure(
+            self,
+            &ClosureOpts {
+                tolerance: commutator_tol,
+                report_top: usize::MAX,
+                pair_subset: None,
+            },
+        )?;
+        let mut violations: Vec<CommutatorViolation> = Vec::new();
+        for offender in &closure.offenders {
+            let (i, j) = (offender.entry.i, offender.entry.j);
+            if !violations.iter().any(|v| v.i == i && v.j == j) {
+                violations.push(CommutatorViolation {
+                    i,
+                    j,
+                    residual: offender.residual,
+                });
+            }
+        }
+        violations.sort_by_key(|v| (v.i, v.j));
+
+        Ok(RepDiagnostics {
+            dimension: self.dimension(),
+            casimir,
+            casimir_scalar_dev,
+            max_commutator_residual: closure.max_dev,
+            pass: violations.is_empty(),
+            violations,
+        })
+    }
 }
 
 fn seed_from_hash(hash: &str) -> u64 {
-    let trimmed = hash.trim();
+    let trimmed = parse_hash(hash.trim()).1;
     let len = trimmed.len().min(16);
     if len == 0 {
         return 0;
@@ -158,9 +362,14 @@ pub fn build_rep(
         });
     }
 
-    Ok(RepMatrices {
+    let mut rep = RepMatrices {
         basis: opts.basis.clone(),
         dim,
         gens: generators,
-    })
+        diagnostics: None,
+    };
+    if opts.diagnostics {
+        rep.diagnostics = Some(rep.diagnostics(opts.commutator_tol)?);
+    }
+    Ok(rep)
 }
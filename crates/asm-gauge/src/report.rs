@@ -1,11 +1,16 @@
 use asm_aut::AnalysisReport;
 use asm_core::errors::{AsmError, ErrorInfo};
+use asm_core::hash::{HashDomain, HASH_FORMAT};
+use asm_core::provenance::{build_fingerprint, BuildFingerprint};
 use asm_spec::{operators::OperatorsInfo, SpectrumReport};
 use serde::{Deserialize, Serialize};
 
 use crate::closure::{check_closure, ClosureOpts, ClosureReport};
 use crate::decomp::{decompose, DecompOpts, DecompReport};
-use crate::hash::stable_hash_string;
+use crate::hash::{
+    is_legacy_hash_format, legacy_hash_format, stable_hash_string, stable_hash_with_format,
+};
+use crate::invariants::{anomaly_coefficients, AnomalyOpts, AnomalyReport};
 use crate::rep::{build_rep, RepOpts};
 use crate::ward::{ward_check, WardOpts, WardReport};
 
@@ -35,6 +40,15 @@ pub struct GaugeProvenance {
     pub closure_tol: f64,
     /// Ward tolerance applied during the analysis.
     pub ward_tol: f64,
+    /// Layout of `analysis_hash`; reports without it carry legacy bare digests.
+    #[serde(
+        default = "legacy_hash_format",
+        skip_serializing_if = "is_legacy_hash_format"
+    )]
+    pub hash_format: u32,
+    /// Build that produced the report; excluded from `analysis_hash`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build: Option<BuildFingerprint>,
 }
 
 /// Aggregate gauge analysis output for a single state.
@@ -54,6 +68,9 @@ pub struct GaugeReport {
     pub decomp: DecompReport,
     /// Ward-style commutator diagnostics.
     pub ward: WardReport,
+    /// Cubic anomaly diagnostics, present when [`AnomalyOpts::enabled`] is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anomaly: Option<AnomalyReport>,
     /// Provenance metadata describing the deterministic knobs.
     pub provenance: GaugeProvenance,
 }
@@ -73,6 +90,9 @@ pub struct GaugeOpts {
     /// Ward check options.
     #[serde(default)]
     pub ward: WardOpts,
+    /// Anomaly cancellation options.
+    #[serde(default)]
+    pub anomaly: AnomalyOpts,
     /// Master deterministic seed overriding representation defaults.
     #[serde(default = "default_seed")]
     pub seed: u64,
@@ -85,6 +105,7 @@ impl Default for GaugeOpts {
             closure: ClosureOpts::default(),
             decomp: DecompOpts::default(),
             ward: WardOpts::default(),
+            anomaly: AnomalyOpts::default(),
             seed: default_seed(),
         }
     }
@@ -96,23 +117,23 @@ fn make_provenance(opts: &GaugeOpts) -> GaugeProvenance {
         seed: opts.seed,
         closure_tol: opts.closure.tolerance,
         ward_tol: opts.ward.relative_tol,
+        hash_format: HASH_FORMAT,
+        build: Some(build_fingerprint()),
     }
 }
 
-fn apply_seed_override(mut rep_opts: RepOpts, seed: u64) -> RepOpts {
+pub(crate) fn apply_seed_override(mut rep_opts: RepOpts, seed: u64) -> RepOpts {
     if seed != 0 {
         rep_opts.seed = Some(seed);
     }
     rep_opts
 }
 
-/// Performs a full gauge analysis using the Phase 11 spectrum artefacts and automorphism report.
-pub fn analyze_gauge(
+/// Ensures the spectrum and automorphism reports describe the same state.
+pub(crate) fn ensure_matching_inputs(
     spectrum: &SpectrumReport,
     aut: &AnalysisReport,
-    ops: &OperatorsInfo,
-    gopts: &GaugeOpts,
-) -> Result<GaugeReport, AsmError> {
+) -> Result<(), AsmError> {
     if spectrum.graph_hash != aut.hashes.graph_hash {
         return Err(gauge_error(
             "hash-mismatch",
@@ -125,6 +146,17 @@ pub fn analyze_gauge(
             "spectrum and automorphism reports refer to different codes",
         ));
     }
+    Ok(())
+}
+
+/// Performs a full gauge analysis using the Phase 11 spectrum artefacts and automorphism report.
+pub fn analyze_gauge(
+    spectrum: &SpectrumReport,
+    aut: &AnalysisReport,
+    ops: &OperatorsInfo,
+    gopts: &GaugeOpts,
+) -> Result<GaugeReport, AsmError> {
+    ensure_matching_inputs(spectrum, aut)?;
 
     let rep_opts = apply_seed_override(gopts.rep.clone(), gopts.seed);
     let rep = build_rep(spectrum, aut, &rep_opts)?;
@@ -132,6 +164,10 @@ pub fn analyze_gauge(
     let closure = check_closure(&rep, &gopts.closure)?;
     let decomp = decompose(&rep, &gopts.decomp)?;
     let ward = ward_check(&rep, ops, &gopts.ward)?;
+    let anomaly = gopts
+        .anomaly
+        .enabled
+        .then(|| anomaly_coefficients(&rep, &gopts.anomaly));
     let provenance = make_provenance(gopts);
 
     let mut report = GaugeReport {
@@ -142,18 +178,34 @@ pub fn analyze_gauge(
         closure,
         decomp,
         ward,
+        anomaly,
         provenance,
     };
 
-    report.analysis_hash = stable_hash_string(&(
+    report.analysis_hash = recompute_analysis_hash(&report)?;
+
+    Ok(report)
+}
+
+/// Recomputes `analysis_hash` in the layout recorded by the report provenance,
+/// accepting both legacy and domain-separated reports.
+pub fn recompute_analysis_hash(report: &GaugeReport) -> Result<String, AsmError> {
+    let format = report.provenance.hash_format;
+    let provenance = GaugeProvenance {
+        build: None,
+        ..report.provenance.clone()
+    };
+    let base = (
         &report.graph_hash,
         &report.code_hash,
         &report.rep_hash,
         &report.closure,
         &report.decomp,
         &report.ward,
-        &report.provenance,
-    ))?;
-
-    Ok(report)
+        &provenance,
+    );
+    match &report.anomaly {
+        Some(anomaly) => stable_hash_with_format(format, HashDomain::Gauge, &(base, anomaly)),
+        None => stable_hash_with_format(format, HashDomain::Gauge, &base),
+    }
 }
@@ -0,0 +1,361 @@
+use serde::{Deserialize, Serialize};
+
+/// Label reported when a component does not match a classical root system.
+pub const UNIDENTIFIED: &str = "unidentified";
+
+/// Root-system classification of the algebra component containing a generator.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RootSystem {
+    /// Index of the bracket-connected component, ordered by smallest generator index.
+    pub component: usize,
+    /// Identified Lie type (`"A2"`, `"B3"`, `"u1"`, ...) or `"unidentified"`.
+    pub lie_type: String,
+    /// Dimension of the Cartan subalgebra, or zero when it could not be determined.
+    pub rank: usize,
+}
+
+fn find(parent: &mut [usize], idx: usize) -> usize {
+    let mut root = idx;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    let mut cursor = idx;
+    while parent[cursor] != root {
+        let next = parent[cursor];
+        parent[cursor] = root;
+        cursor = next;
+    }
+    root
+}
+
+fn union(parent: &mut [usize], a: usize, b: usize) {
+    let (ra, rb) = (find(parent, a), find(parent, b));
+    if ra != rb {
+        let (lo, hi) = if ra < rb { (ra, rb) } else { (rb, ra) };
+        parent[hi] = lo;
+    }
+}
+
+fn matmul(a: &[f64], b: &[f64], d: usize) -> Vec<f64> {
+    let mut out = vec![0.0; d * d];
+    for row in 0..d {
+        for k in 0..d {
+            let lhs = a[row * d + k];
+            if lhs == 0.0 {
+                continue;
+            }
+            for col in 0..d {
+                out[row * d + col] += lhs * b[k * d + col];
+            }
+        }
+    }
+    out
+}
+
+fn transpose(a: &[f64], d: usize) -> Vec<f64> {
+    let mut out = vec![0.0; d * d];
+    for row in 0..d {
+        for col in 0..d {
+            out[col * d + row] = a[row * d + col];
+        }
+    }
+    out
+}
+
+/// Lower-triangular Cholesky factor of a symmetric positive-definite matrix.
+fn cholesky(p: &[f64], d: usize, tol: f64) -> Option<Vec<f64>> {
+    let scale = (0..d).map(|i| p[i * d + i].abs()).fold(0.0, f64::max);
+    let mut l = vec![0.0; d * d];
+    for i in 0..d {
+        for j in 0..=i {
+            let mut acc = p[i * d + j];
+            for k in 0..j {
+                acc -= l[i * d + k] * l[j * d + k];
+            }
+            if i == j {
+                if acc <= tol * scale.max(1e-12) {
+                    return None;
+                }
+                l[i * d + i] = acc.sqrt();
+            } else {
+                l[i * d + j] = acc / l[j * d + j];
+            }
+        }
+    }
+    Some(l)
+}
+
+fn lower_inverse(l: &[f64], d: usize) -> Vec<f64> {
+    let mut inv = vec![0.0; d * d];
+    for col in 0..d {
+        for row in col..d {
+            let mut acc = if row == col { 1.0 } else { 0.0 };
+            for k in col..row {
+                acc -= l[row * d + k] * inv[k * d + col];
+            }
+            inv[row * d + col] = acc / l[row * d + row];
+        }
+    }
+    inv
+}
+
+/// Cyclic Jacobi eigen-decomposition of a symmetric matrix.
+///
+/// Returns eigenvalues in ascending order with eigenvectors stored as columns.
+fn symmetric_eigen(a: &[f64], d: usize) -> (Vec<f64>, Vec<f64>) {
+    let mut m = a.to_vec();
+    let mut v = vec![0.0; d * d];
+    for i in 0..d {
+        v[i * d + i] = 1.0;
+    }
+    let total: f64 = m.iter().map(|x| x * x).sum::<f64>().max(1e-300);
+    for _ in 0..100 {
+        let off: f64 = (0..d)
+            .flat_map(|i| (0..d).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| m[i * d + j] * m[i * d + j])
+            .sum();
+        if off <= 1e-28 * total {
+            break;
+        }
+        for p in 0..d {
+            for q in (p + 1)..d {
+                let apq = m[p * d + q];
+                if apq.abs() <= 1e-300 {
+                    continue;
+                }
+                let theta = (m[q * d + q] - m[p * d + p]) / (2.0 * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let t = if theta == 0.0 { 1.0 } else { t };
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for k in 0..d {
+                    let mkp = m[k * d + p];
+                    let mkq = m[k * d + q];
+                    m[k * d + p] = c * mkp - s * mkq;
+                    m[k * d + q] = s * mkp + c * mkq;
+                }
+                for k in 0..d {
+                    let mpk = m[p * d + k];
+                    let mqk = m[q * d + k];
+                    m[p * d + k] = c * mpk - s * mqk;
+                    m[q * d + k] = s * mpk + c * mqk;
+                }
+                for k in 0..d {
+                    let vkp = v[k * d + p];
+                    let vkq = v[k * d + q];
+                    v[k * d + p] = c * vkp - s * vkq;
+                    v[k * d + q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    let mut order: Vec<usize> = (0..d).collect();
+    order.sort_by(|&a, &b| m[a * d + a].total_cmp(&m[b * d + b]).then(a.cmp(&b)));
+    let values = order.iter().map(|&i| m[i * d + i]).collect();
+    let mut vectors = vec![0.0; d * d];
+    for (col, &src) in order.iter().enumerate() {
+        for row in 0..d {
+            vectors[row * d + col] = v[row * d + src];
+        }
+    }
+    (values, vectors)
+}
+
+fn column(vectors: &[f64], d: usize, col: usize) -> Vec<f64> {
+    (0..d).map(|row| vectors[row * d + col]).collect()
+}
+
+fn bilinear(u: &[f64], m: &[f64], v: &[f64], d: usize) -> f64 {
+    let mut acc = 0.0;
+    for row in 0..d {
+        for col in 0..d {
+            acc += u[row] * m[row * d + col] * v[col];
+        }
+    }
+    acc
+}
+
+/// Matches rank, positive-root count, and short/long root counts to a classical series.
+fn match_classical(rank: usize, long: usize, short: usize) -> Option<String> {
+    let r = rank;
+    if short == 0 {
+        if long == r * (r + 1) / 2 {
+            return Some(format!("A{r}"));
+        }
+        if r >= 4 && long == r * (r - 1) {
+            return Some(format!("D{r}"));
+        }
+        return None;
+    }
+    if r >= 2 && short == r && long == r * (r - 1) {
+        return Some(format!("B{r}"));
+    }
+    if r >= 3 && short == r * (r - 1) && long == r {
+        return Some(format!("C{r}"));
+    }
+    None
+}
+
+/// Classifies a semisimple component from its adjoint matrices.
+///
+/// Returns the identified type (or [`UNIDENTIFIED`]) together with the rank
+/// when the Cartan subalgebra could be extracted.
+fn classify_component(ad: &[Vec<f64>], d: usize, tol: f64) -> (String, usize) {
+    let unidentified = |rank: usize| (UNIDENTIFIED.to_string(), rank);
+
+    // Killing form K_ab = tr(ad_a ad_b); compact real forms are negative definite.
+    let mut neg_killing = vec![0.0; d * d];
+    for a in 0..d {
+        for b in 0..d {
+            let prod = matmul(&ad[a], &ad[b], d);
+            neg_killing[a * d + b] = -(0..d).map(|i| prod[i * d + i]).sum::<f64>();
+        }
+    }
+    let Some(l) = cholesky(&neg_killing, d, tol) else {
+        return unidentified(0);
+    };
+    let l_t = transpose(&l, d);
+    let l_inv_t = transpose(&lower_inverse(&l, d), d);
+    // In the Killing-orthonormal frame every ad matrix is antisymmetric.
+    let frame: Vec<Vec<f64>> = ad
+        .iter()
+        .map(|m| matmul(&matmul(&l_t, m, d), &l_inv_t, d))
+        .collect();
+    let combine = |coeffs: &[f64]| {
+        let mut out = vec![0.0; d * d];
+        for (m, &c) in frame.iter().zip(coeffs) {
+            for (dst, src) in out.iter_mut().zip(m) {
+                *dst += c * src;
+            }
+        }
+        out
+    };
+
+    // A deterministic generic element is regular, so its centraliser is a Cartan subalgebra.
+    let generic: Vec<f64> = (0..d)
+        .map(|a| 1.0 + ((a + 1) as f64 * 0.754_877_666_246_692_7).fract())
+        .collect();
+    let b_h = combine(&generic);
+    let squared = matmul(&transpose(&b_h, d), &b_h, d);
+    let (values, vectors) = symmetric_eigen(&squared, d);
+    let scale = values.iter().copied().fold(0.0, f64::max).max(1e-12);
+    let rank = values.iter().filter(|&&v| v <= tol * scale).count();
+    if rank == 0 || (d - rank) % 2 != 0 {
+        return unidentified(rank);
+    }
+
+    // Cartan basis: Killing-orthonormal null vectors mapped back to generator coefficients.
+    let cartan: Vec<Vec<f64>> = (0..rank)
+        .map(|col| {
+            let y = column(&vectors, d, col);
+            (0..d)
+                .map(|a| (0..d).map(|k| l_inv_t[a * d + k] * y[k]).sum())
+                .collect()
+        })
+        .collect();
+    let cartan_frames: Vec<Vec<f64>> = cartan.iter().map(|coeffs| combine(coeffs)).collect();
+
+    let mut lengths = Vec::with_capacity((d - rank) / 2);
+    let mut col = rank;
+    while col + 1 < d {
+        if (values[col] - values[col + 1]).abs() > tol * scale {
+            return unidentified(rank);
+        }
+        let u = column(&vectors, d, col);
+        let v = column(&vectors, d, col + 1);
+        let length: f64 = cartan_frames
+            .iter()
+            .map(|m| bilinear(&v, m, &u, d).powi(2))
+            .sum();
+        lengths.push(length);
+        col += 2;
+    }
+
+    let longest = lengths.iter().copied().fold(0.0, f64::max);
+    if longest <= 0.0 {
+        return unidentified(rank);
+    }
+    let (mut long, mut short) = (0, 0);
+    for length in &lengths {
+        let ratio = length / longest;
+        if (ratio - 1.0).abs() <= 1e-3 {
+            long += 1;
+        } else if (ratio - 0.5).abs() <= 1e-3 {
+            short += 1;
+        } else {
+            return unidentified(rank);
+        }
+    }
+    match match_classical(rank, long, short) {
+        Some(label) => (label, rank),
+        None => unidentified(rank),
+    }
+}
+
+/// Classifies every generator by the root system of its bracket-connected component.
+///
+/// `tensor` is the dense structure tensor `f[(i * n + j) * n + k] = f^k_{ij}`.
+/// Generators linked through any coefficient above `tol` share a component.
+/// Isolated generators with vanishing brackets are reported as `"u1"`.
+pub(crate) fn classify_generators(tensor: &[f64], n: usize, tol: f64) -> Vec<RootSystem> {
+    let idx = |i: usize, j: usize, k: usize| (i * n + j) * n + k;
+    let mut parent: Vec<usize> = (0..n).collect();
+    for i in 0..n {
+        for j in 0..n {
+            for k in 0..n {
+                if tensor[idx(i, j, k)].abs() > tol {
+                    union(&mut parent, i, j);
+                    union(&mut parent, i, k);
+                }
+            }
+        }
+    }
+
+    let mut roots: Vec<usize> = Vec::new();
+    let component_of: Vec<usize> = (0..n)
+        .map(|g| {
+            let root = find(&mut parent, g);
+            roots.iter().position(|&r| r == root).unwrap_or_else(|| {
+                roots.push(root);
+                roots.len() - 1
+            })
+        })
+        .collect();
+
+    let labels: Vec<(String, usize)> = (0..roots.len())
+        .map(|component| {
+            let members: Vec<usize> = (0..n).filter(|&g| component_of[g] == component).collect();
+            let d = members.len();
+            let ad: Vec<Vec<f64>> = members
+                .iter()
+                .map(|&a| {
+                    let mut m = vec![0.0; d * d];
+                    for (b, &gb) in members.iter().enumerate() {
+                        for (c, &gc) in members.iter().enumerate() {
+                            m[c * d + b] = tensor[idx(a, gb, gc)];
+                        }
+                    }
+                    m
+                })
+                .collect();
+            if ad.iter().all(|m| m.iter().all(|v| v.abs() <= tol)) {
+                if d == 1 {
+                    ("u1".to_string(), 1)
+                } else {
+                    (UNIDENTIFIED.to_string(), 0)
+                }
+            } else {
+                classify_component(&ad, d, tol)
+            }
+        })
+        .collect();
+
+    component_of
+        .into_iter()
+        .map(|component| RootSystem {
+            component,
+            lie_type: labels[component].0.clone(),
+            rank: labels[component].1,
+        })
+        .collect()
+}
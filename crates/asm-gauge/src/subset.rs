@@ -0,0 +1,119 @@
+use asm_core::errors::{AsmError, ErrorInfo};
+use asm_core::rng::RngHandle;
+use rand::seq::index;
+use serde::{Deserialize, Serialize};
+
+use crate::hash::stable_hash_string;
+
+fn gauge_error(code: &str, message: impl Into<String>) -> AsmError {
+    AsmError::Serde(ErrorInfo::new(code, message))
+}
+
+pub(crate) fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// Rule picking the candidates evaluated by a partial gauge check.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SubsetSelection {
+    /// Draws `ceil(fraction * total)` candidates from the subset seed; the
+    /// fraction must lie in `(0, 1]`.
+    Fraction(f64),
+    /// Evaluates exactly the listed candidate indices.
+    Indices(Vec<usize>),
+}
+
+/// Deterministic subset of generator pairs or generators to evaluate.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SubsetSpec {
+    /// How candidates are chosen.
+    pub selection: SubsetSelection,
+    /// Seed driving [`SubsetSelection::Fraction`] draws.
+    #[serde(default)]
+    pub seed: u64,
+}
+
+/// Candidates a gauge check actually evaluated.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SubsetInfo {
+    /// Number of candidates the subset was drawn from.
+    pub total: usize,
+    /// Evaluated candidate indices in ascending order.
+    pub indices: Vec<usize>,
+    /// Stable hash of `total` and `indices`.
+    pub hash: String,
+}
+
+impl SubsetInfo {
+    /// Returns whether some candidates were left out.
+    pub fn is_partial(&self) -> bool {
+        self.indices.len() < self.total
+    }
+
+    /// Membership mask over all `total` candidates.
+    pub(crate) fn mask(&self) -> Vec<bool> {
+        let mut mask = vec![false; self.total];
+        for &index in &self.indices {
+            mask[index] = true;
+        }
+        mask
+    }
+}
+
+impl SubsetSpec {
+    /// Resolves the subset over `total` candidates.
+    pub(crate) fn select(&self, total: usize) -> Result<SubsetInfo, AsmError> {
+        let mut indices = match &self.selection {
+            SubsetSelection::Fraction(fraction) => {
+                if !(*fraction > 0.0 && *fraction <= 1.0) {
+                    return Err(gauge_error(
+                        "invalid-subset",
+                        format!("subset fraction {fraction} must lie in (0, 1]"),
+                    ));
+                }
+                let amount = ((fraction * total as f64).ceil() as usize).min(total);
+                let mut rng = RngHandle::from_seed(self.seed);
+                index::sample(&mut rng, total, amount).into_vec()
+            }
+            SubsetSelection::Indices(indices) => {
+                if let Some(index) = indices.iter().find(|&&index| index >= total) {
+                    return Err(gauge_error(
+                        "invalid-subset",
+                        format!("subset index {index} is out of range for {total} candidates"),
+                    ));
+                }
+                indices.clone()
+            }
+        };
+        indices.sort_unstable();
+        indices.dedup();
+        if indices.is_empty() && total > 0 {
+            return Err(gauge_error(
+                "invalid-subset",
+                "subset selects no candidates",
+            ));
+        }
+        let hash = stable_hash_string(&(total, &indices))?;
+        Ok(SubsetInfo {
+            total,
+            indices,
+            hash,
+        })
+    }
+}
+
+/// Resolves an optional subset into the recorded info and a membership mask.
+pub(crate) fn resolve(
+    spec: Option<&SubsetSpec>,
+    total: usize,
+) -> Result<(Option<SubsetInfo>, Vec<bool>), AsmError> {
+    match spec {
+        Some(spec) => {
+            let info = spec.select(total)?;
+            let mask = info.mask();
+            Ok((Some(info), mask))
+        }
+        None => Ok((None, vec![true; total])),
+    }
+}
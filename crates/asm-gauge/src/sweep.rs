@@ -0,0 +1,178 @@
+use asm_aut::AnalysisReport;
+use asm_core::errors::{AsmError, ErrorInfo};
+use asm_spec::SpectrumReport;
+use serde::{Deserialize, Serialize};
+
+use crate::closure::check_closure;
+use crate::decomp::{classify, decompose, DecompReport};
+use crate::hash::stable_hash_string;
+use crate::rep::build_rep;
+use crate::report::{apply_seed_override, ensure_matching_inputs, GaugeOpts};
+use crate::ward::ward_check;
+
+fn round(value: f64) -> f64 {
+    (value * 1e9).round() / 1e9
+}
+
+fn sweep_error(message: impl Into<String>) -> AsmError {
+    AsmError::Serde(ErrorInfo::new("invalid-tolerance-grid", message))
+}
+
+/// Tolerance pair `(closure_tol, ward_tol)` locating a sweep grid point.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct SweepTolerances {
+    /// Closure tolerance, also used as the decomposition trace tolerance.
+    pub closure_tol: f64,
+    /// Ward relative tolerance.
+    pub ward_tol: f64,
+}
+
+/// Outcome of re-thresholding the gauge checks at one grid point.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GaugeSweepPoint {
+    /// Tolerances applied at this point.
+    pub tolerances: SweepTolerances,
+    /// Whether the algebra closes within `closure_tol`.
+    pub closed: bool,
+    /// Whether every Ward identity passes with the commutator bound at `ward_tol`.
+    pub ward_pass: bool,
+    /// Factor labels in generator order.
+    pub factors: Vec<String>,
+    /// Whether the labels match the reported factorisation.
+    pub stable: bool,
+}
+
+/// Stability map of the gauge analysis across a tolerance grid.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GaugeSweepReport {
+    /// Canonical hash of the input graph.
+    pub graph_hash: String,
+    /// Canonical hash of the input code.
+    pub code_hash: String,
+    /// Stable hash of the representation shared by every grid point.
+    pub rep_hash: String,
+    /// Factor labels reported at the tolerances in the supplied [`GaugeOpts`].
+    pub reported_factors: Vec<String>,
+    /// Closure residual re-thresholded at every point.
+    pub max_dev: f64,
+    /// Relative Ward commutator residual re-thresholded at every point.
+    pub ward_residual: f64,
+    /// Grid points ordered by `(closure_tol, ward_tol)`; duplicates removed.
+    pub points: Vec<GaugeSweepPoint>,
+    /// Smallest grid point reproducing the reported factorisation with both checks passing.
+    pub first_stable: Option<SweepTolerances>,
+    /// Canonical hash of the sweep payload.
+    pub sweep_hash: String,
+}
+
+fn labels(decomp: &DecompReport, trace_tol: f64) -> Vec<String> {
+    decomp
+        .factors
+        .iter()
+        .map(|factor| {
+            let trace = factor.invariants.get("trace").copied().unwrap_or_default();
+            let symmetry = factor
+                .invariants
+                .get("symmetry")
+                .copied()
+                .unwrap_or_default();
+            classify(trace, trace_tol, symmetry).to_string()
+        })
+        .collect()
+}
+
+/// Re-thresholds closure, Ward, and factor labelling across a tolerance grid.
+///
+/// The representation, structure tensor, Ward residuals, and generator
+/// invariants are computed once with `opts`; each `(closure_tol, ward_tol)`
+/// pair then only re-applies the thresholds. The closure tolerance doubles as
+/// the decomposition trace tolerance, so factor labels can change across the
+/// grid.
+pub fn analyze_gauge_sweep(
+    spectrum: &SpectrumReport,
+    aut: &AnalysisReport,
+    tol_grid: &[(f64, f64)],
+    opts: &GaugeOpts,
+) -> Result<GaugeSweepReport, AsmError> {
+    if tol_grid.is_empty() {
+        return Err(sweep_error(
+            "tolerance grid must contain at least one point",
+        ));
+    }
+    if let Some((closure_tol, ward_tol)) = tol_grid
+        .iter()
+        .find(|(c, w)| !(c.is_finite() && *c >= 0.0 && w.is_finite() && *w >= 0.0))
+    {
+        return Err(sweep_error(format!(
+            "tolerances must be finite and non-negative, got ({closure_tol}, {ward_tol})"
+        )));
+    }
+    ensure_matching_inputs(spectrum, aut)?;
+
+    let rep_opts = apply_seed_override(opts.rep.clone(), opts.seed);
+    let rep = build_rep(spectrum, aut, &rep_opts)?;
+    let rep_hash = stable_hash_string(&rep)?;
+    let closure = check_closure(&rep, &opts.closure)?;
+    let decomp = decompose(&rep, &opts.decomp)?;
+    let ward = ward_check(&rep, &spectrum.operators.info, &opts.ward)?;
+    let ward_residual = ward
+        .identities
+        .first()
+        .map(|identity| identity.residual)
+        .unwrap_or_default();
+    let correlators_pass = ward.identities.iter().skip(1).all(|identity| identity.pass);
+    let reported_factors = labels(&decomp, opts.decomp.trace_tol);
+
+    let mut grid: Vec<SweepTolerances> = tol_grid
+        .iter()
+        .map(|&(closure_tol, ward_tol)| SweepTolerances {
+            closure_tol,
+            ward_tol,
+        })
+        .collect();
+    grid.sort_by(|a, b| {
+        a.closure_tol
+            .total_cmp(&b.closure_tol)
+            .then_with(|| a.ward_tol.total_cmp(&b.ward_tol))
+    });
+    grid.dedup();
+
+    let points: Vec<GaugeSweepPoint> = grid
+        .into_iter()
+        .map(|tolerances| {
+            let factors = labels(&decomp, tolerances.closure_tol);
+            GaugeSweepPoint {
+                tolerances,
+                closed: closure.max_dev <= tolerances.closure_tol,
+                ward_pass: ward_residual <= tolerances.ward_tol && correlators_pass,
+                stable: factors == reported_factors,
+                factors,
+            }
+        })
+        .collect();
+    let first_stable = points
+        .iter()
+        .find(|point| point.stable && point.closed && point.ward_pass)
+        .map(|point| point.tolerances);
+
+    let mut report = GaugeSweepReport {
+        graph_hash: spectrum.graph_hash.clone(),
+        code_hash: spectrum.code_hash.clone(),
+        rep_hash,
+        reported_factors,
+        max_dev: round(closure.max_dev),
+        ward_residual: round(ward_residual),
+        points,
+        first_stable,
+        sweep_hash: String::new(),
+    };
+    report.sweep_hash = stable_hash_string(&(
+        &report.graph_hash,
+        &report.code_hash,
+        &report.rep_hash,
+        &report.reported_factors,
+        &report.points,
+        &report.first_stable,
+    ))?;
+    Ok(report)
+}
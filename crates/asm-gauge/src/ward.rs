@@ -1,7 +1,8 @@
 use asm_core::errors::{AsmError, ErrorInfo};
 use serde::{Deserialize, Serialize};
 
-use crate::rep::RepMatrices;
+use crate::rep::{RepGenerator, RepMatrices};
+use crate::subset::{is_false, resolve, SubsetInfo, SubsetSpec};
 
 fn round(value: f64) -> f64 {
     (value * 1e9).round() / 1e9
@@ -15,22 +16,62 @@ fn gauge_error(code: &str, message: impl Into<String>) -> AsmError {
     AsmError::Serde(ErrorInfo::new(code, message))
 }
 
+/// Name of the identity entry recording the operator commutator norm.
+pub const COMMUTATOR_IDENTITY: &str = "commutator_norm";
+
+/// Two-point correlator supplied to the Ward identity suite.
+///
+/// Gauge invariance requires `[T_a, G] = 0` for every generator `T_a`; the
+/// residual is the largest relative commutator norm across generators.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WardCorrelator {
+    /// Identifier reported alongside the residual.
+    pub name: String,
+    /// Row-major `dim × dim` correlator matrix.
+    pub matrix: Vec<f64>,
+    /// Per-identity tolerance; falls back to [`WardOpts::relative_tol`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tolerance: Option<f64>,
+}
+
 /// Options controlling Ward-style commutator checks.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct WardOpts {
     /// Maximum allowed relative commutator norm.
     #[serde(default = "default_relative_tol")]
     pub relative_tol: f64,
+    /// Additional correlators checked for gauge covariance.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub correlators: Vec<WardCorrelator>,
+    /// Checks only a deterministic subset of the generators against the
+    /// operator and correlators. Reports are then marked partial.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator_subset: Option<SubsetSpec>,
 }
 
 impl Default for WardOpts {
     fn default() -> Self {
         Self {
             relative_tol: default_relative_tol(),
+            correlators: Vec::new(),
+            operator_subset: None,
         }
     }
 }
 
+/// Residual recorded for a single Ward identity.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WardIdentity {
+    /// Identity name; [`COMMUTATOR_IDENTITY`] for the operator commutator check.
+    pub name: String,
+    /// Relative residual of the identity.
+    pub residual: f64,
+    /// Tolerance applied to the residual.
+    pub tolerance: f64,
+    /// Whether the residual satisfied the tolerance.
+    pub pass: bool,
+}
+
 /// Threshold metadata recorded in [`WardReport`].
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct WardThresholds {
@@ -43,10 +84,22 @@ pub struct WardThresholds {
 pub struct WardReport {
     /// Maximum commutator norm recorded across generators.
     pub max_comm_norm: f64,
-    /// Whether the residual satisfied the configured tolerance.
+    /// Whether every identity satisfied its tolerance.
     pub pass: bool,
     /// Threshold metadata recorded for provenance.
     pub thresholds: WardThresholds,
+    /// Per-identity residuals, starting with the commutator-norm entry.
+    ///
+    /// Empty only for reports written before Ward identities were recorded;
+    /// those omit the field and keep their serialized bytes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub identities: Vec<WardIdentity>,
+    /// Whether only a subset of generators was checked.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub partial: bool,
+    /// Generators checked when [`WardOpts::operator_subset`] was set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subset: Option<SubsetInfo>,
 }
 
 fn operator_diagonal(info: &asm_spec::operators::OperatorsInfo, dim: usize) -> Vec<f64> {
@@ -80,7 +133,59 @@ fn commutator_norm(matrix: &[f64], diag: &[f64], dim: usize) -> f64 {
     acc.sqrt()
 }
 
+fn frobenius(matrix: &[f64]) -> f64 {
+    matrix.iter().map(|x| x * x).sum::<f64>().sqrt()
+}
+
+fn matrix_commutator_norm(a: &[f64], b: &[f64], dim: usize) -> f64 {
+    let mut acc = 0.0;
+    for row in 0..dim {
+        for col in 0..dim {
+            let mut value = 0.0;
+            for k in 0..dim {
+                value += a[row * dim + k] * b[k * dim + col] - b[row * dim + k] * a[k * dim + col];
+            }
+            acc += value * value;
+        }
+    }
+    acc.sqrt()
+}
+
+fn correlator_identity(
+    dim: usize,
+    gens: &[&RepGenerator],
+    correlator: &WardCorrelator,
+    default_tol: f64,
+) -> Result<WardIdentity, AsmError> {
+    if correlator.matrix.len() != dim * dim {
+        return Err(gauge_error(
+            "ward-correlator-shape",
+            format!(
+                "correlator `{}` has {} entries, expected {}",
+                correlator.name,
+                correlator.matrix.len(),
+                dim * dim
+            ),
+        ));
+    }
+    let scale = frobenius(&correlator.matrix).max(1e-12);
+    let residual = gens
+        .iter()
+        .map(|gen| matrix_commutator_norm(&gen.matrix, &correlator.matrix, dim) / scale)
+        .fold(0.0, f64::max);
+    let tolerance = correlator.tolerance.unwrap_or(default_tol);
+    Ok(WardIdentity {
+        name: correlator.name.clone(),
+        residual: round(residual),
+        tolerance,
+        pass: residual <= tolerance,
+    })
+}
+
 /// Evaluates Ward-style commutator residuals between the representation and the effective operator.
+///
+/// Each correlator in [`WardOpts::correlators`] contributes one additional
+/// entry to [`WardReport::identities`].
 pub fn ward_check(
     rep: &RepMatrices,
     ops: &asm_spec::operators::OperatorsInfo,
@@ -100,19 +205,145 @@ pub fn ward_check(
     }
 
     let dim = rep.dim;
+    let (subset, selected) = resolve(ward_opts.operator_subset.as_ref(), rep.gens.len())?;
+    let gens: Vec<&RepGenerator> = rep
+        .gens
+        .iter()
+        .zip(selected)
+        .filter_map(|(gen, keep)| keep.then_some(gen))
+        .collect();
     let diag = operator_diagonal(ops, dim);
     let operator_norm = diag.iter().map(|x| x * x).sum::<f64>().sqrt().max(1e-12);
     let mut max_comm: f64 = 0.0;
-    for gen in &rep.gens {
+    for gen in &gens {
         let norm = commutator_norm(&gen.matrix, &diag, dim);
         max_comm = max_comm.max(norm);
     }
     let rel = max_comm / operator_norm;
+    let mut identities = vec![WardIdentity {
+        name: COMMUTATOR_IDENTITY.to_string(),
+        residual: round(rel),
+        tolerance: ward_opts.relative_tol,
+        pass: rel <= ward_opts.relative_tol,
+    }];
+    for correlator in &ward_opts.correlators {
+        identities.push(correlator_identity(
+            dim,
+            &gens,
+            correlator,
+            ward_opts.relative_tol,
+        )?);
+    }
     Ok(WardReport {
         max_comm_norm: round(max_comm),
-        pass: rel <= ward_opts.relative_tol,
+        pass: identities.iter().all(|identity| identity.pass),
         thresholds: WardThresholds {
             rel_tol: ward_opts.relative_tol,
         },
+        identities,
+        partial: subset.as_ref().is_some_and(SubsetInfo::is_partial),
+        subset,
+    })
+}
+
+fn default_jacobi_tol() -> f64 {
+    1e-6
+}
+
+/// Options controlling the Jacobi identity check on extracted structure constants.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JacobiOpts {
+    /// Maximum tolerated magnitude of any cyclic-sum component.
+    #[serde(default = "default_jacobi_tol")]
+    pub tolerance: f64,
+}
+
+impl Default for JacobiOpts {
+    fn default() -> Self {
+        Self {
+            tolerance: default_jacobi_tol(),
+        }
+    }
+}
+
+/// Result of verifying the Jacobi identity on a structure tensor.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JacobiReport {
+    /// Number of generators inferred from the structure tensor.
+    pub generators: usize,
+    /// Number of generator triples `i < j < k` evaluated.
+    pub triples_checked: usize,
+    /// Largest cyclic-sum component recorded across all triples.
+    pub max_violation: f64,
+    /// Triple `(i, j, k)` attaining [`JacobiReport::max_violation`], if any triple was checked.
+    pub worst_triple: Option<[usize; 3]>,
+    /// Whether every triple satisfied the tolerance.
+    pub pass: bool,
+    /// Tolerance applied during the check.
+    pub tolerance: f64,
+}
+
+/// Verifies the Jacobi identity on structure constants `f^k_{ij}`.
+///
+/// Entries are completed antisymmetrically (`f^k_{ji} = -f^k_{ij}`). For every
+/// triple `i < j < k` the cyclic sum
+/// `sum_m f^m_{ij} f^l_{mk} + f^m_{jk} f^l_{mi} + f^m_{ki} f^l_{mj}` is evaluated
+/// for each output index `l` and the largest magnitude is reported.
+pub fn jacobi_check(
+    structure_tensor: &[crate::closure::StructureTensorEntry],
+    opts: &JacobiOpts,
+) -> Result<JacobiReport, AsmError> {
+    let n = structure_tensor
+        .iter()
+        .map(|entry| entry.i.max(entry.j).max(entry.k) + 1)
+        .max()
+        .unwrap_or(0);
+    let mut f = vec![0.0; n * n * n];
+    let idx = |i: usize, j: usize, k: usize| (i * n + j) * n + k;
+    for entry in structure_tensor {
+        if entry.i == entry.j && entry.value != 0.0 {
+            return Err(gauge_error(
+                "non-antisymmetric-tensor",
+                format!(
+                    "structure constant f^{}_{{{}{}}} must vanish for identical generators",
+                    entry.k, entry.i, entry.j
+                ),
+            ));
+        }
+        f[idx(entry.i, entry.j, entry.k)] = entry.value;
+        f[idx(entry.j, entry.i, entry.k)] = -entry.value;
+    }
+
+    let mut triples_checked = 0;
+    let mut max_violation: f64 = 0.0;
+    let mut worst_triple = None;
+    for i in 0..n {
+        for j in (i + 1)..n {
+            for k in (j + 1)..n {
+                triples_checked += 1;
+                for l in 0..n {
+                    let mut sum = 0.0;
+                    for m in 0..n {
+                        sum += f[idx(i, j, m)] * f[idx(m, k, l)]
+                            + f[idx(j, k, m)] * f[idx(m, i, l)]
+                            + f[idx(k, i, m)] * f[idx(m, j, l)];
+                    }
+                    if worst_triple.is_none() || sum.abs() > max_violation {
+                        max_violation = sum.abs();
+                        worst_triple = Some([i, j, k]);
+                    }
+                }
+            }
+        }
+    }
+
+    let max_violation = round(max_violation);
+    Ok(JacobiReport {
+        generators: n,
+        triples_checked,
+        max_violation,
+        worst_triple,
+        pass: max_violation <= opts.tolerance,
+        tolerance: opts.tolerance,
     })
 }
@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 
-use asm_core::errors::{AsmError, ErrorInfo};
+use asm_core::errors::{AsmError, ErrorCode, ErrorInfo};
 use asm_core::{EdgeId, Hypergraph, NodeId};
 
 use crate::hypergraph::HypergraphImpl;
@@ -55,7 +55,7 @@ pub fn ollivier_lite_nodes(
 ) -> Result<Vec<(NodeId, f32)>, AsmError> {
     if iterations == 0 {
         return Err(AsmError::Graph(ErrorInfo::new(
-            "zero-iterations",
+            ErrorCode::ZeroIterations,
             "ollivier-lite proxy requires at least one iteration",
         )));
     }
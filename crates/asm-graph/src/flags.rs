@@ -1,5 +1,9 @@
+use std::collections::BTreeMap;
+
 use asm_core::provenance::SchemaVersion;
 
+use crate::hypergraph::DegreeLimits;
+
 /// Configuration options that control the behaviour of [`HypergraphImpl`](crate::HypergraphImpl).
 #[derive(Debug, Clone)]
 pub struct HypergraphConfig {
@@ -9,10 +13,21 @@ pub struct HypergraphConfig {
     pub max_in_degree: Option<usize>,
     /// Maximum outbound degree permitted for any node.
     pub max_out_degree: Option<usize>,
-    /// Optional arity constraint enforced on every hyperedge.
+    /// Optional arity constraint enforced on every hyperedge without a
+    /// label-specific rule.
     pub k_uniform: Option<KUniformity>,
+    /// Arity constraints keyed by edge label. A labelled edge whose label has
+    /// no entry falls back to `k_uniform`; unlabeled edges always use it.
+    pub label_rules: BTreeMap<String, KUniformity>,
     /// Schema version stored alongside serialized payloads.
     pub schema_version: SchemaVersion,
+    /// Per-node degree caps keyed by raw node id. An override replaces both
+    /// global caps for its node, so `None` leaves that direction unbounded.
+    pub degree_overrides: BTreeMap<u64, DegreeLimits>,
+    /// Store adjacency with 32-bit identifiers to cut memory on large graphs.
+    /// Node and edge ids must then stay within `u32::MAX`; the mode never
+    /// affects canonical hashes or serialized payloads.
+    pub compact_ids: bool,
 }
 
 impl Default for HypergraphConfig {
@@ -25,11 +40,24 @@ impl Default for HypergraphConfig {
                 sources: 2,
                 destinations: 2,
             }),
+            label_rules: BTreeMap::new(),
             schema_version: SchemaVersion::new(2, 0, 0),
+            degree_overrides: BTreeMap::new(),
+            compact_ids: false,
         }
     }
 }
 
+impl HypergraphConfig {
+    /// Returns the arity rule applied to edges carrying `label`.
+    pub fn uniformity_for(&self, label: Option<&str>) -> Option<KUniformity> {
+        label
+            .and_then(|label| self.label_rules.get(label))
+            .or(self.k_uniform.as_ref())
+            .copied()
+    }
+}
+
 /// Describes the uniformity constraints applied to newly created hyperedges.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum KUniformity {
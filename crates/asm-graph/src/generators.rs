@@ -1,4 +1,4 @@
-use asm_core::errors::{AsmError, ErrorInfo};
+use asm_core::errors::{AsmError, ErrorCode, ErrorInfo};
 use asm_core::rng::RngHandle;
 use asm_core::{Hypergraph, NodeId};
 use rand::seq::SliceRandom;
@@ -15,7 +15,7 @@ pub fn gen_bounded_degree(
 ) -> Result<HypergraphImpl, AsmError> {
     if n_nodes == 0 {
         return Err(AsmError::Graph(ErrorInfo::new(
-            "empty-graph",
+            ErrorCode::EmptyGraph,
             "bounded degree generator requires at least one node",
         )));
     }
@@ -144,8 +144,13 @@ fn is_soft_error(error: &AsmError) -> bool {
         error,
         AsmError::Graph(info)
             if matches!(
-                info.code.as_str(),
-                "duplicate-edge" | "would-create-cycle" | "out-degree-cap" | "in-degree-cap"
+                info.code_enum(),
+                Some(
+                    ErrorCode::DuplicateEdge
+                        | ErrorCode::WouldCreateCycle
+                        | ErrorCode::OutDegreeCap
+                        | ErrorCode::InDegreeCap
+                )
             )
     )
 }
@@ -160,7 +165,7 @@ fn max_in_degree(graph: &HypergraphImpl) -> Result<(NodeId, usize), AsmError> {
             _ => {}
         }
     }
-    best.ok_or_else(|| AsmError::Graph(ErrorInfo::new("no-nodes", "graph has no nodes")))
+    best.ok_or_else(|| AsmError::Graph(ErrorInfo::new(ErrorCode::NoNodes, "graph has no nodes")))
 }
 
 fn min_in_degree(graph: &HypergraphImpl) -> Result<(NodeId, usize), AsmError> {
@@ -173,5 +178,5 @@ fn min_in_degree(graph: &HypergraphImpl) -> Result<(NodeId, usize), AsmError> {
             _ => {}
         }
     }
-    best.ok_or_else(|| AsmError::Graph(ErrorInfo::new("no-nodes", "graph has no nodes")))
+    best.ok_or_else(|| AsmError::Graph(ErrorInfo::new(ErrorCode::NoNodes, "graph has no nodes")))
 }
@@ -19,27 +19,37 @@ pub fn canonical_hash(graph: &HypergraphImpl) -> Result<String, AsmError> {
         signatures.push(EdgeSignature::new(
             endpoints.sources.as_ref(),
             endpoints.destinations.as_ref(),
+            graph.edge_label(edge_id)?,
         ));
     }
     signatures.sort();
     hasher.update((signatures.len() as u64).to_le_bytes());
-    for signature in signatures {
+    for signature in &signatures {
         update_slice(signature.raw_sources(), &mut hasher);
         update_slice(signature.raw_destinations(), &mut hasher);
     }
+    // Only hashed when present so unlabeled graphs keep their hashes.
+    if signatures
+        .iter()
+        .any(|signature| signature.label().is_some())
+    {
+        hasher.update(b"edge-labels");
+        for signature in &signatures {
+            match signature.label() {
+                Some(label) => {
+                    hasher.update(b"label:some");
+                    update_str(label, &mut hasher);
+                }
+                None => hasher.update(b"label:none"),
+            }
+        }
+    }
 
     Ok(format!("{:x}", hasher.finalize()))
 }
 
-fn encode_config(config: &HypergraphConfig, hasher: &mut Sha256) {
-    if config.causal_mode {
-        hasher.update(b"causal");
-    } else {
-        hasher.update(b"acyclic-off");
-    }
-    encode_option_usize("max-in", config.max_in_degree, hasher);
-    encode_option_usize("max-out", config.max_out_degree, hasher);
-    match config.k_uniform {
+fn encode_uniformity(rule: Option<KUniformity>, hasher: &mut Sha256) {
+    match rule {
         None => hasher.update(b"kuniform:none"),
         Some(KUniformity::Balanced {
             sources,
@@ -55,9 +65,38 @@ fn encode_config(config: &HypergraphConfig, hasher: &mut Sha256) {
             hasher.update((min_sources as u64).to_le_bytes());
         }
     }
+}
+
+fn encode_config(config: &HypergraphConfig, hasher: &mut Sha256) {
+    if config.causal_mode {
+        hasher.update(b"causal");
+    } else {
+        hasher.update(b"acyclic-off");
+    }
+    encode_option_usize("max-in", config.max_in_degree, hasher);
+    encode_option_usize("max-out", config.max_out_degree, hasher);
+    encode_uniformity(config.k_uniform, hasher);
     hasher.update(config.schema_version.major.to_le_bytes());
     hasher.update(config.schema_version.minor.to_le_bytes());
     hasher.update(config.schema_version.patch.to_le_bytes());
+    // Only hashed when present so graphs without overrides keep their hashes.
+    if !config.degree_overrides.is_empty() {
+        hasher.update(b"degree-overrides");
+        hasher.update((config.degree_overrides.len() as u64).to_le_bytes());
+        for (node, limits) in &config.degree_overrides {
+            hasher.update(node.to_le_bytes());
+            encode_option_usize("max-in", limits.max_in, hasher);
+            encode_option_usize("max-out", limits.max_out, hasher);
+        }
+    }
+    if !config.label_rules.is_empty() {
+        hasher.update(b"label-rules");
+        hasher.update((config.label_rules.len() as u64).to_le_bytes());
+        for (label, rule) in &config.label_rules {
+            update_str(label, hasher);
+            encode_uniformity(Some(*rule), hasher);
+        }
+    }
 }
 
 fn encode_option_usize(label: &str, value: Option<usize>, hasher: &mut Sha256) {
@@ -74,6 +113,11 @@ fn encode_option_usize(label: &str, value: Option<usize>, hasher: &mut Sha256) {
     }
 }
 
+fn update_str(value: &str, hasher: &mut Sha256) {
+    hasher.update((value.len() as u64).to_le_bytes());
+    hasher.update(value.as_bytes());
+}
+
 fn update_slice(values: &[u64], hasher: &mut Sha256) {
     hasher.update((values.len() as u64).to_le_bytes());
     for value in values {
@@ -1,15 +1,19 @@
+use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet};
 
 use asm_core::{
     errors::{AsmError, ErrorInfo},
-    DegreeBounds, EdgeId, HyperedgeEndpoints, Hypergraph, NodeId,
+    DegreeBounds, EdgeId, ErrorCode, HyperedgeEndpoints, Hypergraph, NodeId,
 };
 
 use crate::flags::HypergraphConfig;
-use crate::ids::{canonicalize_nodes, edge_index, make_edge, make_node, node_index};
+use crate::ids::{
+    canonicalize_nodes, compact_id, edge_index, make_edge, make_node, node_index, EdgeList,
+    NodeList,
+};
 
 /// Tracks the maximum degree configuration exposed by the graph.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct DegreeLimits {
     /// Maximum inbound degree permitted for any node.
     pub max_in: Option<usize>,
@@ -28,17 +32,23 @@ impl DegreeLimits {
 }
 
 /// Canonical signature used to deduplicate hyperedges.
+///
+/// The edge label is part of the signature, so identical endpoints with
+/// different labels are distinct edges. Unlabeled signatures order before
+/// labelled ones with the same endpoints.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct EdgeSignature {
     sources: Vec<u64>,
     destinations: Vec<u64>,
+    label: Option<String>,
 }
 
 impl EdgeSignature {
-    pub(crate) fn new(sources: &[NodeId], destinations: &[NodeId]) -> Self {
+    pub(crate) fn new(sources: &[NodeId], destinations: &[NodeId], label: Option<&str>) -> Self {
         Self {
             sources: sources.iter().map(|id| id.as_raw()).collect(),
             destinations: destinations.iter().map(|id| id.as_raw()).collect(),
+            label: label.map(str::to_string),
         }
     }
 
@@ -51,21 +61,29 @@ impl EdgeSignature {
     pub fn raw_destinations(&self) -> &[u64] {
         &self.destinations
     }
+
+    /// Returns the edge label, if the edge has one.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
 }
 
+/// Stored edge as `(alive, sources, destinations, label)`.
+pub(crate) type EdgePayload<'a> = (bool, Vec<NodeId>, Vec<NodeId>, Option<&'a str>);
+
 #[derive(Debug, Clone)]
 pub(crate) struct NodeRecord {
     alive: bool,
-    in_edges: BTreeSet<EdgeId>,
-    out_edges: BTreeSet<EdgeId>,
+    in_edges: EdgeList,
+    out_edges: EdgeList,
 }
 
 impl NodeRecord {
-    fn new() -> Self {
+    fn new(compact: bool) -> Self {
         Self {
             alive: true,
-            in_edges: BTreeSet::new(),
-            out_edges: BTreeSet::new(),
+            in_edges: EdgeList::new(compact),
+            out_edges: EdgeList::new(compact),
         }
     }
 }
@@ -73,29 +91,39 @@ impl NodeRecord {
 #[derive(Debug, Clone)]
 pub(crate) struct EdgeRecord {
     alive: bool,
-    sources: Vec<NodeId>,
-    destinations: Vec<NodeId>,
-    signature: EdgeSignature,
+    sources: NodeList,
+    destinations: NodeList,
+    label: Option<String>,
 }
 
 impl EdgeRecord {
-    fn new(sources: Vec<NodeId>, destinations: Vec<NodeId>) -> Self {
-        let signature = EdgeSignature::new(&sources, &destinations);
+    fn new(
+        sources: Vec<NodeId>,
+        destinations: Vec<NodeId>,
+        label: Option<String>,
+        compact: bool,
+    ) -> Self {
         Self {
             alive: true,
-            sources,
-            destinations,
-            signature,
+            sources: NodeList::new(sources, compact),
+            destinations: NodeList::new(destinations, compact),
+            label,
         }
     }
 
-    fn dead(sources: Vec<NodeId>, destinations: Vec<NodeId>) -> Self {
-        let signature = EdgeSignature::new(&sources, &destinations);
+    fn dead(sources: Vec<NodeId>, destinations: Vec<NodeId>, compact: bool) -> Self {
         Self {
             alive: false,
-            sources,
-            destinations,
-            signature,
+            ..Self::new(sources, destinations, None, compact)
+        }
+    }
+
+    /// Rebuilds the deduplication signature from the stored endpoints.
+    fn signature(&self) -> EdgeSignature {
+        EdgeSignature {
+            sources: self.sources.raw(),
+            destinations: self.destinations.raw(),
+            label: self.label.clone(),
         }
     }
 }
@@ -106,6 +134,8 @@ pub struct HypergraphImpl {
     config: HypergraphConfig,
     nodes: Vec<NodeRecord>,
     edges: Vec<EdgeRecord>,
+    /// Signatures of alive edges. Empty in compact mode, where duplicates are
+    /// found through the out-edges of the first source instead.
     signatures: BTreeSet<EdgeSignature>,
 }
 
@@ -125,6 +155,64 @@ impl HypergraphImpl {
         &self.config
     }
 
+    /// Returns whether adjacency is stored with 32-bit compact identifiers.
+    pub fn is_compact(&self) -> bool {
+        self.config.compact_ids
+    }
+
+    /// Switches the adjacency storage mode, re-encoding every record.
+    ///
+    /// Identifiers, canonical hashes, and serialized payloads are unaffected.
+    /// Enabling compact storage fails with `compact-id-overflow` when a stored
+    /// identifier exceeds `u32::MAX`, leaving the graph unchanged.
+    pub fn set_compact_ids(&mut self, compact: bool) -> Result<(), AsmError> {
+        if compact == self.config.compact_ids {
+            return Ok(());
+        }
+        if compact {
+            if let Some(last) = self.nodes.len().checked_sub(1) {
+                ensure_compact_id("node", last as u64)?;
+            }
+            if let Some(last) = self.edges.len().checked_sub(1) {
+                ensure_compact_id("edge", last as u64)?;
+            }
+            for edge in &self.edges {
+                for raw in edge
+                    .sources
+                    .raw()
+                    .into_iter()
+                    .chain(edge.destinations.raw())
+                {
+                    ensure_compact_id("node", raw)?;
+                }
+            }
+        }
+        for node in &mut self.nodes {
+            node.in_edges =
+                std::mem::replace(&mut node.in_edges, EdgeList::new(compact)).into_mode(compact);
+            node.out_edges =
+                std::mem::replace(&mut node.out_edges, EdgeList::new(compact)).into_mode(compact);
+        }
+        for edge in &mut self.edges {
+            edge.sources =
+                std::mem::replace(&mut edge.sources, NodeList::Wide(Vec::new())).into_mode(compact);
+            edge.destinations =
+                std::mem::replace(&mut edge.destinations, NodeList::Wide(Vec::new()))
+                    .into_mode(compact);
+        }
+        self.signatures = if compact {
+            BTreeSet::new()
+        } else {
+            self.edges
+                .iter()
+                .filter(|edge| edge.alive)
+                .map(EdgeRecord::signature)
+                .collect()
+        };
+        self.config.compact_ids = compact;
+        Ok(())
+    }
+
     /// Returns whether the graph enforces causal mode.
     pub fn is_causal_mode(&self) -> bool {
         self.config.causal_mode
@@ -138,6 +226,15 @@ impl HypergraphImpl {
         }
     }
 
+    /// Returns the degree limits in force for a node, honouring per-node overrides.
+    pub fn node_degree_limits(&self, node: NodeId) -> DegreeLimits {
+        self.config
+            .degree_overrides
+            .get(&node.as_raw())
+            .copied()
+            .unwrap_or_else(|| self.degree_limits())
+    }
+
     /// Returns the inbound degree for the provided node.
     pub fn in_degree(&self, node: NodeId) -> Result<usize, AsmError> {
         let record = self.node(node)?;
@@ -153,21 +250,39 @@ impl HypergraphImpl {
     /// Returns all edges touching the provided node.
     pub fn edges_touching(&self, node: NodeId) -> Result<Vec<EdgeId>, AsmError> {
         let record = self.node(node)?;
-        let mut edges: BTreeSet<EdgeId> = record.in_edges.iter().copied().collect();
-        edges.extend(record.out_edges.iter().copied());
+        let mut edges: BTreeSet<EdgeId> = record.in_edges.iter().collect();
+        edges.extend(record.out_edges.iter());
         Ok(edges.into_iter().collect())
     }
 
+    /// Removes every alive node without incident edges, returning the removed
+    /// identifiers in ascending order. Node identifiers are never reused, so
+    /// the remaining nodes and edges keep their ids.
+    pub fn prune_isolated(&mut self) -> Vec<NodeId> {
+        let isolated: Vec<NodeId> = self
+            .node_ids()
+            .into_iter()
+            .filter(|&id| {
+                let record = &self.nodes[node_index(id)];
+                record.in_edges.is_empty() && record.out_edges.is_empty()
+            })
+            .collect();
+        for &id in &isolated {
+            self.nodes[node_index(id)].alive = false;
+        }
+        isolated
+    }
+
     /// Returns the outbound edges for the provided node.
     pub(crate) fn outgoing_edges(&self, node: NodeId) -> Result<Vec<EdgeId>, AsmError> {
         let record = self.node(node)?;
-        Ok(record.out_edges.iter().copied().collect())
+        Ok(record.out_edges.iter().collect())
     }
 
     /// Returns the inbound edges for the provided node.
     pub(crate) fn incoming_edges(&self, node: NodeId) -> Result<Vec<EdgeId>, AsmError> {
         let record = self.node(node)?;
-        Ok(record.in_edges.iter().copied().collect())
+        Ok(record.in_edges.iter().collect())
     }
 
     /// Returns the raw alive flags for each stored node.
@@ -176,24 +291,64 @@ impl HypergraphImpl {
     }
 
     /// Returns the stored edge payloads for serialization.
-    pub(crate) fn edge_payloads(&self) -> Vec<(bool, Vec<NodeId>, Vec<NodeId>)> {
+    pub(crate) fn edge_payloads(&self) -> Vec<EdgePayload<'_>> {
         self.edges
             .iter()
-            .map(|edge| (edge.alive, edge.sources.clone(), edge.destinations.clone()))
+            .map(|edge| {
+                (
+                    edge.alive,
+                    edge.sources.to_vec(),
+                    edge.destinations.to_vec(),
+                    edge.label.as_deref(),
+                )
+            })
+            .collect()
+    }
+
+    /// Adds a hyperedge carrying `label`.
+    ///
+    /// The edge must satisfy the arity rule registered for `label` in
+    /// [`HypergraphConfig::label_rules`], falling back to
+    /// [`HypergraphConfig::k_uniform`] when the label has no rule. Edges with
+    /// the same endpoints but different labels (or none) may coexist.
+    pub fn add_hyperedge_labeled(
+        &mut self,
+        sources: &[NodeId],
+        destinations: &[NodeId],
+        label: &str,
+    ) -> Result<EdgeId, AsmError> {
+        self.insert_edge(sources, destinations, Some(label))
+    }
+
+    /// Returns the label of a hyperedge, or `None` for unlabeled edges.
+    pub fn edge_label(&self, edge: EdgeId) -> Result<Option<&str>, AsmError> {
+        Ok(self.edge(edge)?.label.as_deref())
+    }
+
+    /// Returns the alive edges carrying `label`, in ascending id order.
+    pub fn edges_with_label(&self, label: &str) -> Vec<EdgeId> {
+        self.edges
+            .iter()
+            .enumerate()
+            .filter(|(_, edge)| edge.alive && edge.label.as_deref() == Some(label))
+            .map(|(idx, _)| make_edge(idx))
             .collect()
     }
 
-    /// Returns the source nodes of a hyperedge.
-    pub fn src_of(&self, edge: EdgeId) -> Result<&[NodeId], AsmError> {
-        Ok(&self.edge(edge)?.sources)
+    /// Returns the source nodes of a hyperedge, borrowed unless the graph
+    /// stores compact identifiers.
+    pub fn src_of(&self, edge: EdgeId) -> Result<Cow<'_, [NodeId]>, AsmError> {
+        Ok(self.edge(edge)?.sources.as_ids())
     }
 
-    /// Returns the destination nodes of a hyperedge.
-    pub fn dst_of(&self, edge: EdgeId) -> Result<&[NodeId], AsmError> {
-        Ok(&self.edge(edge)?.destinations)
+    /// Returns the destination nodes of a hyperedge, borrowed unless the graph
+    /// stores compact identifiers.
+    pub fn dst_of(&self, edge: EdgeId) -> Result<Cow<'_, [NodeId]>, AsmError> {
+        Ok(self.edge(edge)?.destinations.as_ids())
     }
 
-    /// Replaces an existing hyperedge with new endpoints while validating invariants.
+    /// Replaces an existing hyperedge with new endpoints while validating
+    /// invariants. The edge keeps its label.
     pub(crate) fn overwrite_edge(
         &mut self,
         edge: EdgeId,
@@ -203,7 +358,8 @@ impl HypergraphImpl {
         let previous = self.detach_edge(edge)?;
         let sources = canonicalize_nodes(new_sources);
         let destinations = canonicalize_nodes(new_destinations);
-        match self.attach_to_slot(edge, sources, destinations) {
+        let label = previous.label.clone();
+        match self.attach_to_slot(edge, sources, destinations, label) {
             Ok(()) => Ok(()),
             Err(err) => {
                 let _ = self.restore_edge(edge, previous);
@@ -218,10 +374,13 @@ impl HypergraphImpl {
         mut record: EdgeRecord,
     ) -> Result<(), AsmError> {
         record.alive = true;
-        let sources = record.sources.clone();
-        let destinations = record.destinations.clone();
+        let sources = record.sources.to_vec();
+        let destinations = record.destinations.to_vec();
+        if !self.config.compact_ids {
+            self.signatures.insert(record.signature());
+        }
         if let Some(slot) = self.edges.get_mut(edge_index(edge)) {
-            *slot = record.clone();
+            *slot = record;
         }
         for source in sources {
             self.node_mut(source)?.out_edges.insert(edge);
@@ -229,7 +388,6 @@ impl HypergraphImpl {
         for destination in destinations {
             self.node_mut(destination)?.in_edges.insert(edge);
         }
-        self.signatures.insert(record.signature.clone());
         Ok(())
     }
 
@@ -258,7 +416,8 @@ impl HypergraphImpl {
             .get(node_index(id))
             .filter(|record| record.alive)
             .ok_or_else(|| {
-                graph_error("unknown-node", "node does not exist").with_context("node", id.as_raw())
+                graph_error(ErrorCode::UnknownNode, "node does not exist")
+                    .with_context("node", id.as_raw())
             })
     }
 
@@ -267,7 +426,8 @@ impl HypergraphImpl {
             .get_mut(node_index(id))
             .filter(|record| record.alive)
             .ok_or_else(|| {
-                graph_error("unknown-node", "node does not exist").with_context("node", id.as_raw())
+                graph_error(ErrorCode::UnknownNode, "node does not exist")
+                    .with_context("node", id.as_raw())
             })
     }
 
@@ -276,35 +436,59 @@ impl HypergraphImpl {
             .get(edge_index(id))
             .filter(|record| record.alive)
             .ok_or_else(|| {
-                graph_error("unknown-edge", "edge does not exist").with_context("edge", id.as_raw())
+                graph_error(ErrorCode::UnknownEdge, "edge does not exist")
+                    .with_context("edge", id.as_raw())
             })
     }
 
+    /// Rejects identifiers that compact storage cannot hold. Wide graphs accept
+    /// every identifier.
+    fn ensure_compact(
+        &self,
+        edge: EdgeId,
+        sources: &[NodeId],
+        destinations: &[NodeId],
+    ) -> Result<(), AsmError> {
+        if !self.config.compact_ids {
+            return Ok(());
+        }
+        ensure_compact_id("edge", edge.as_raw())?;
+        for node in sources.iter().chain(destinations) {
+            ensure_compact_id("node", node.as_raw())?;
+        }
+        Ok(())
+    }
+
     fn ensure_uniformity(
         &self,
         sources: &[NodeId],
         destinations: &[NodeId],
+        label: Option<&str>,
     ) -> Result<(), AsmError> {
-        if let Some(rule) = &self.config.k_uniform {
+        if let Some(rule) = self.config.uniformity_for(label) {
             if !rule.validate(sources.len(), destinations.len()) {
-                return Err(graph_error(
-                    "invalid-arity",
+                let err = graph_error(
+                    ErrorCode::InvalidArity,
                     "hyperedge violates k-uniform configuration",
                 )
                 .with_context("sources", sources.len())
-                .with_context("destinations", destinations.len()));
+                .with_context("destinations", destinations.len());
+                return Err(match label {
+                    Some(label) => err.with_context("label", label),
+                    None => err,
+                });
             }
         }
         Ok(())
     }
 
     fn ensure_degrees(&self, sources: &[NodeId], destinations: &[NodeId]) -> Result<(), AsmError> {
-        if let Some(max_out) = self.config.max_out_degree {
-            for node in sources {
+        for node in sources {
+            if let Some(max_out) = self.node_degree_limits(*node).max_out {
                 let record = self.node(*node)?;
                 if record.out_edges.len() + 1 > max_out {
                     return Err(graph_error(
-                        "out-degree-cap",
+                        ErrorCode::OutDegreeCap,
                         "outbound degree cap would be exceeded",
                     )
                     .with_context("node", node.as_raw())
@@ -313,12 +497,12 @@ impl HypergraphImpl {
                 }
             }
         }
-        if let Some(max_in) = self.config.max_in_degree {
-            for node in destinations {
+        for node in destinations {
+            if let Some(max_in) = self.node_degree_limits(*node).max_in {
                 let record = self.node(*node)?;
                 if record.in_edges.len() + 1 > max_in {
                     return Err(graph_error(
-                        "in-degree-cap",
+                        ErrorCode::InDegreeCap,
                         "inbound degree cap would be exceeded",
                     )
                     .with_context("node", node.as_raw())
@@ -330,9 +514,33 @@ impl HypergraphImpl {
         Ok(())
     }
 
-    fn ensure_unique(&self, signature: &EdgeSignature) -> Result<(), AsmError> {
-        if self.signatures.contains(signature) {
-            return Err(graph_error("duplicate-edge", "hyperedge already exists"));
+    fn ensure_unique(
+        &self,
+        sources: &[NodeId],
+        destinations: &[NodeId],
+        label: Option<&str>,
+    ) -> Result<(), AsmError> {
+        let duplicate = if self.config.compact_ids {
+            sources
+                .first()
+                .and_then(|first| self.node(*first).ok())
+                .is_some_and(|record| {
+                    record.out_edges.iter().any(|edge| {
+                        let edge = &self.edges[edge_index(edge)];
+                        *edge.sources.as_ids() == *sources
+                            && *edge.destinations.as_ids() == *destinations
+                            && edge.label.as_deref() == label
+                    })
+                })
+        } else {
+            self.signatures
+                .contains(&EdgeSignature::new(sources, destinations, label))
+        };
+        if duplicate {
+            return Err(graph_error(
+                ErrorCode::DuplicateEdge,
+                "hyperedge already exists",
+            ));
         }
         Ok(())
     }
@@ -347,7 +555,7 @@ impl HypergraphImpl {
         }
         if self.would_create_cycle(sources, destinations)? {
             return Err(graph_error(
-                "would-create-cycle",
+                ErrorCode::WouldCreateCycle,
                 "operation would introduce a directed cycle",
             ));
         }
@@ -364,9 +572,10 @@ impl HypergraphImpl {
             if !edge.alive {
                 continue;
             }
-            for source in &edge.sources {
+            let destinations = edge.destinations.as_ids();
+            for source in edge.sources.as_ids().iter() {
                 let entry = adjacency.entry(*source).or_default();
-                entry.extend(edge.destinations.iter().copied());
+                entry.extend(destinations.iter().copied());
             }
         }
         for source in sources {
@@ -391,22 +600,25 @@ impl HypergraphImpl {
     pub(crate) fn detach_edge(&mut self, id: EdgeId) -> Result<EdgeRecord, AsmError> {
         let idx = edge_index(id);
         let record = self.edges.get_mut(idx).ok_or_else(|| {
-            graph_error("unknown-edge", "edge does not exist").with_context("edge", id.as_raw())
+            graph_error(ErrorCode::UnknownEdge, "edge does not exist")
+                .with_context("edge", id.as_raw())
         })?;
         if !record.alive {
-            return Err(graph_error("unknown-edge", "edge does not exist")
+            return Err(graph_error(ErrorCode::UnknownEdge, "edge does not exist")
                 .with_context("edge", id.as_raw()));
         }
         record.alive = false;
-        self.signatures.remove(&record.signature);
-        for source in &record.sources {
+        if !self.config.compact_ids {
+            self.signatures.remove(&record.signature());
+        }
+        for source in record.sources.as_ids().iter() {
             if let Some(node) = self.nodes.get_mut(node_index(*source)) {
-                node.out_edges.remove(&id);
+                node.out_edges.remove(id);
             }
         }
-        for destination in &record.destinations {
+        for destination in record.destinations.as_ids().iter() {
             if let Some(node) = self.nodes.get_mut(node_index(*destination)) {
-                node.in_edges.remove(&id);
+                node.in_edges.remove(id);
             }
         }
         Ok(record.clone())
@@ -417,30 +629,85 @@ impl HypergraphImpl {
         edge: EdgeId,
         sources: Vec<NodeId>,
         destinations: Vec<NodeId>,
+        label: Option<String>,
     ) -> Result<(), AsmError> {
-        self.ensure_uniformity(&sources, &destinations)?;
+        self.ensure_compact(edge, &sources, &destinations)?;
+        self.ensure_uniformity(&sources, &destinations, label.as_deref())?;
         self.ensure_degrees(&sources, &destinations)?;
         self.validate_cycle_free(&sources, &destinations)?;
-        let record = EdgeRecord::new(sources.clone(), destinations.clone());
-        self.ensure_unique(&record.signature)?;
+        self.ensure_unique(&sources, &destinations, label.as_deref())?;
+        let signature = EdgeSignature::new(&sources, &destinations, label.as_deref());
+        let record = EdgeRecord::new(
+            sources.clone(),
+            destinations.clone(),
+            label,
+            self.config.compact_ids,
+        );
         if let Some(slot) = self.edges.get_mut(edge_index(edge)) {
-            *slot = record.clone();
+            *slot = record;
         } else {
-            self.edges.push(record.clone());
+            self.edges.push(record);
         }
-        for source in sources {
-            self.node_mut(source)?.out_edges.insert(edge);
+        for source in &sources {
+            self.node_mut(*source)?.out_edges.insert(edge);
         }
-        for destination in destinations {
-            self.node_mut(destination)?.in_edges.insert(edge);
+        for destination in &destinations {
+            self.node_mut(*destination)?.in_edges.insert(edge);
+        }
+        if !self.config.compact_ids {
+            self.signatures.insert(signature);
         }
-        self.signatures.insert(record.signature.clone());
         Ok(())
     }
 
-    pub(crate) fn push_dead_edge(&mut self, sources: Vec<NodeId>, destinations: Vec<NodeId>) {
-        let record = EdgeRecord::dead(sources, destinations);
+    fn insert_edge(
+        &mut self,
+        sources: &[NodeId],
+        destinations: &[NodeId],
+        label: Option<&str>,
+    ) -> Result<EdgeId, AsmError> {
+        if sources.is_empty() || destinations.is_empty() {
+            return Err(graph_error(
+                ErrorCode::EmptyEndpoints,
+                "hyperedges require non-empty source and destination sets",
+            ));
+        }
+        let sources = canonicalize_nodes(sources);
+        let destinations = canonicalize_nodes(destinations);
+        let id = make_edge(self.edges.len());
+        self.ensure_compact(id, &sources, &destinations)?;
+        self.ensure_uniformity(&sources, &destinations, label)?;
+        self.ensure_degrees(&sources, &destinations)?;
+        self.validate_cycle_free(&sources, &destinations)?;
+        self.ensure_unique(&sources, &destinations, label)?;
+        for source in &sources {
+            self.node_mut(*source)?.out_edges.insert(id);
+        }
+        for destination in &destinations {
+            self.node_mut(*destination)?.in_edges.insert(id);
+        }
+        if !self.config.compact_ids {
+            self.signatures
+                .insert(EdgeSignature::new(&sources, &destinations, label));
+        }
+        self.edges.push(EdgeRecord::new(
+            sources,
+            destinations,
+            label.map(str::to_string),
+            self.config.compact_ids,
+        ));
+        Ok(id)
+    }
+
+    pub(crate) fn push_dead_edge(
+        &mut self,
+        sources: Vec<NodeId>,
+        destinations: Vec<NodeId>,
+    ) -> Result<(), AsmError> {
+        self.ensure_compact(make_edge(self.edges.len()), &sources, &destinations)?;
+        let record = EdgeRecord::dead(sources, destinations, self.config.compact_ids);
         self.edges.push(record);
+        Ok(())
     }
 }
 
@@ -462,8 +729,8 @@ impl Hypergraph for HypergraphImpl {
     fn hyperedge(&self, edge: EdgeId) -> Result<HyperedgeEndpoints, AsmError> {
         let record = self.edge(edge)?;
         Ok(HyperedgeEndpoints {
-            sources: record.sources.clone().into_boxed_slice(),
-            destinations: record.destinations.clone().into_boxed_slice(),
+            sources: record.sources.to_vec().into_boxed_slice(),
+            destinations: record.destinations.to_vec().into_boxed_slice(),
         })
     }
 
@@ -490,7 +757,10 @@ impl Hypergraph for HypergraphImpl {
 
     fn add_node(&mut self) -> Result<NodeId, AsmError> {
         let id = make_node(self.nodes.len());
-        self.nodes.push(NodeRecord::new());
+        if self.config.compact_ids {
+            ensure_compact_id("node", id.as_raw())?;
+        }
+        self.nodes.push(NodeRecord::new(self.config.compact_ids));
         Ok(id)
     }
 
@@ -499,43 +769,22 @@ impl Hypergraph for HypergraphImpl {
         sources: &[NodeId],
         destinations: &[NodeId],
     ) -> Result<EdgeId, AsmError> {
-        if sources.is_empty() || destinations.is_empty() {
-            return Err(graph_error(
-                "empty-endpoints",
-                "hyperedges require non-empty source and destination sets",
-            ));
-        }
-        let sources = canonicalize_nodes(sources);
-        let destinations = canonicalize_nodes(destinations);
-        self.ensure_uniformity(&sources, &destinations)?;
-        self.ensure_degrees(&sources, &destinations)?;
-        self.validate_cycle_free(&sources, &destinations)?;
-        let edge = EdgeRecord::new(sources.clone(), destinations.clone());
-        self.ensure_unique(&edge.signature)?;
-        let id = make_edge(self.edges.len());
-        for source in &sources {
-            self.node_mut(*source)?.out_edges.insert(id);
-        }
-        for destination in &destinations {
-            self.node_mut(*destination)?.in_edges.insert(id);
-        }
-        self.signatures.insert(edge.signature.clone());
-        self.edges.push(edge);
-        Ok(id)
+        self.insert_edge(sources, destinations, None)
     }
 
     fn remove_node(&mut self, node: NodeId) -> Result<(), AsmError> {
         let idx = node_index(node);
         let record = self.nodes.get(idx).ok_or_else(|| {
-            graph_error("unknown-node", "node does not exist").with_context("node", node.as_raw())
+            graph_error(ErrorCode::UnknownNode, "node does not exist")
+                .with_context("node", node.as_raw())
         })?;
         if !record.alive {
-            return Err(graph_error("unknown-node", "node does not exist")
+            return Err(graph_error(ErrorCode::UnknownNode, "node does not exist")
                 .with_context("node", node.as_raw()));
         }
         if !record.in_edges.is_empty() || !record.out_edges.is_empty() {
             return Err(graph_error(
-                "node-not-isolated",
+                ErrorCode::NodeNotIsolated,
                 "cannot remove node with incident edges",
             )
             .with_context("node", node.as_raw())
@@ -583,6 +832,18 @@ fn dfs(
     }
 }
 
+fn ensure_compact_id(kind: &str, raw: u64) -> Result<(), AsmError> {
+    if compact_id(raw).is_some() {
+        return Ok(());
+    }
+    Err(graph_error(
+        ErrorCode::CompactIdOverflow,
+        "identifier exceeds the compact id range",
+    )
+    .with_context(kind, raw)
+    .with_context("limit", u32::MAX))
+}
+
 fn graph_error(code: impl Into<String>, message: impl Into<String>) -> AsmError {
     AsmError::Graph(ErrorInfo::new(code, message))
 }
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::BTreeSet;
 
 use asm_core::{EdgeId, NodeId};
@@ -30,3 +31,153 @@ pub(crate) fn canonicalize_nodes(nodes: &[NodeId]) -> Vec<NodeId> {
     }
     set.into_iter().collect()
 }
+
+/// Narrows a raw identifier for compact storage, if it fits in 32 bits.
+pub(crate) fn compact_id(raw: u64) -> Option<u32> {
+    u32::try_from(raw).ok()
+}
+
+/// Narrows an identifier whose range was checked when it was issued.
+fn narrow(raw: u64) -> u32 {
+    compact_id(raw).expect("compact ids are range-checked on insertion")
+}
+
+/// Incident edges of a node in the storage mode of its graph.
+#[derive(Debug, Clone)]
+pub(crate) enum EdgeList {
+    /// Ordered set of full-width identifiers.
+    Wide(BTreeSet<EdgeId>),
+    /// Sorted, duplicate-free raw identifiers kept ordered by binary-search insertion.
+    Compact(Vec<u32>),
+}
+
+impl EdgeList {
+    pub(crate) fn new(compact: bool) -> Self {
+        if compact {
+            EdgeList::Compact(Vec::new())
+        } else {
+            EdgeList::Wide(BTreeSet::new())
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            EdgeList::Wide(set) => set.len(),
+            EdgeList::Compact(list) => list.len(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn insert(&mut self, edge: EdgeId) {
+        match self {
+            EdgeList::Wide(set) => {
+                set.insert(edge);
+            }
+            EdgeList::Compact(list) => {
+                let raw = narrow(edge.as_raw());
+                if let Err(position) = list.binary_search(&raw) {
+                    list.insert(position, raw);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn remove(&mut self, edge: EdgeId) {
+        match self {
+            EdgeList::Wide(set) => {
+                set.remove(&edge);
+            }
+            EdgeList::Compact(list) => {
+                let found = compact_id(edge.as_raw()).and_then(|raw| list.binary_search(&raw).ok());
+                if let Some(position) = found {
+                    list.remove(position);
+                }
+            }
+        }
+    }
+
+    /// Iterates the incident edges in ascending order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = EdgeId> + '_ {
+        let (wide, compact) = match self {
+            EdgeList::Wide(set) => (Some(set.iter().copied()), None),
+            EdgeList::Compact(list) => (
+                None,
+                Some(list.iter().map(|&raw| EdgeId::from_raw(u64::from(raw)))),
+            ),
+        };
+        wide.into_iter()
+            .flatten()
+            .chain(compact.into_iter().flatten())
+    }
+
+    /// Re-encodes the list for the requested storage mode.
+    pub(crate) fn into_mode(self, compact: bool) -> Self {
+        match (self, compact) {
+            (EdgeList::Wide(set), true) => {
+                EdgeList::Compact(set.into_iter().map(|id| narrow(id.as_raw())).collect())
+            }
+            (EdgeList::Compact(list), false) => EdgeList::Wide(
+                list.into_iter()
+                    .map(|raw| EdgeId::from_raw(u64::from(raw)))
+                    .collect(),
+            ),
+            (list, _) => list,
+        }
+    }
+}
+
+/// Endpoint list of a hyperedge in the storage mode of its graph.
+#[derive(Debug, Clone)]
+pub(crate) enum NodeList {
+    /// Full-width identifiers.
+    Wide(Vec<NodeId>),
+    /// Raw identifiers narrowed to 32 bits.
+    Compact(Box<[u32]>),
+}
+
+impl NodeList {
+    /// Stores `nodes`, which must fit in 32 bits when `compact` is set.
+    pub(crate) fn new(nodes: Vec<NodeId>, compact: bool) -> Self {
+        if compact {
+            NodeList::Compact(nodes.iter().map(|id| narrow(id.as_raw())).collect())
+        } else {
+            NodeList::Wide(nodes)
+        }
+    }
+
+    /// Borrows wide storage and decodes compact storage.
+    pub(crate) fn as_ids(&self) -> Cow<'_, [NodeId]> {
+        match self {
+            NodeList::Wide(nodes) => Cow::Borrowed(nodes),
+            NodeList::Compact(nodes) => Cow::Owned(
+                nodes
+                    .iter()
+                    .map(|&raw| NodeId::from_raw(u64::from(raw)))
+                    .collect(),
+            ),
+        }
+    }
+
+    pub(crate) fn to_vec(&self) -> Vec<NodeId> {
+        self.as_ids().into_owned()
+    }
+
+    pub(crate) fn raw(&self) -> Vec<u64> {
+        match self {
+            NodeList::Wide(nodes) => nodes.iter().map(|id| id.as_raw()).collect(),
+            NodeList::Compact(nodes) => nodes.iter().map(|&raw| u64::from(raw)).collect(),
+        }
+    }
+
+    /// Re-encodes the list for the requested storage mode.
+    pub(crate) fn into_mode(self, compact: bool) -> Self {
+        match (self, compact) {
+            (NodeList::Wide(nodes), true) => NodeList::new(nodes, true),
+            (list @ NodeList::Compact(_), false) => NodeList::Wide(list.to_vec()),
+            (list, _) => list,
+        }
+    }
+}
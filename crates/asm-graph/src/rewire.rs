@@ -1,4 +1,4 @@
-use asm_core::errors::{AsmError, ErrorInfo};
+use asm_core::errors::{AsmError, ErrorCode, ErrorInfo};
 use asm_core::rng::RngHandle;
 use asm_core::{EdgeId, Hypergraph, NodeId};
 use rand::seq::SliceRandom;
@@ -126,9 +126,12 @@ fn retarget_impl(
     for node in removed {
         if !destinations.contains(node) {
             return Err(AsmError::Graph(
-                ErrorInfo::new("missing-destination", "destination is not part of the edge")
-                    .with_context("edge", edge.as_raw().to_string())
-                    .with_context("node", node.as_raw().to_string()),
+                ErrorInfo::new(
+                    ErrorCode::MissingDestination,
+                    "destination is not part of the edge",
+                )
+                .with_context("edge", edge.as_raw().to_string())
+                .with_context("node", node.as_raw().to_string()),
             ));
         }
     }
@@ -142,7 +145,7 @@ fn retarget_impl(
     destinations.dedup();
     if destinations.is_empty() {
         return Err(AsmError::Graph(ErrorInfo::new(
-            "empty-destinations",
+            ErrorCode::EmptyDestinations,
             "retargeting would remove all destinations",
         )));
     }
@@ -1,22 +1,25 @@
-use asm_core::errors::{AsmError, ErrorInfo};
+use std::collections::BTreeMap;
+
+use asm_core::errors::{AsmError, ErrorCode, ErrorInfo};
 use asm_core::provenance::SchemaVersion;
 use asm_core::{Hypergraph, NodeId};
 use serde::{Deserialize, Serialize};
 
 use crate::flags::{HypergraphConfig, KUniformity};
-use crate::hypergraph::HypergraphImpl;
+use crate::hypergraph::{DegreeLimits, HypergraphImpl};
 
 /// Serializes the graph to a compact binary representation using `bincode`.
 pub fn graph_to_bytes(graph: &HypergraphImpl) -> Result<Vec<u8>, AsmError> {
     let serializable = SerializableGraph::from_graph(graph);
     bincode::serialize(&serializable)
-        .map_err(|err| AsmError::Serde(ErrorInfo::new("serialize-bytes", err.to_string())))
+        .map_err(|err| AsmError::Serde(ErrorInfo::new(ErrorCode::SerializeBytes, err.to_string())))
 }
 
 /// Restores a graph from its binary representation.
 pub fn graph_from_bytes(bytes: &[u8]) -> Result<HypergraphImpl, AsmError> {
-    let serializable: SerializableGraph = bincode::deserialize(bytes)
-        .map_err(|err| AsmError::Serde(ErrorInfo::new("deserialize-bytes", err.to_string())))?;
+    let serializable: SerializableGraph = bincode::deserialize(bytes).map_err(|err| {
+        AsmError::Serde(ErrorInfo::new(ErrorCode::DeserializeBytes, err.to_string()))
+    })?;
     serializable.into_graph()
 }
 
@@ -24,13 +27,14 @@ pub fn graph_from_bytes(bytes: &[u8]) -> Result<HypergraphImpl, AsmError> {
 pub fn graph_to_json(graph: &HypergraphImpl) -> Result<String, AsmError> {
     let serializable = SerializableGraph::from_graph(graph);
     serde_json::to_string_pretty(&serializable)
-        .map_err(|err| AsmError::Serde(ErrorInfo::new("serialize-json", err.to_string())))
+        .map_err(|err| AsmError::Serde(ErrorInfo::new(ErrorCode::JsonSerialize, err.to_string())))
 }
 
 /// Restores a graph from a JSON string.
 pub fn graph_from_json(json: &str) -> Result<HypergraphImpl, AsmError> {
-    let serializable: SerializableGraph = serde_json::from_str(json)
-        .map_err(|err| AsmError::Serde(ErrorInfo::new("deserialize-json", err.to_string())))?;
+    let serializable: SerializableGraph = serde_json::from_str(json).map_err(|err| {
+        AsmError::Serde(ErrorInfo::new(ErrorCode::JsonDeserialize, err.to_string()))
+    })?;
     serializable.into_graph()
 }
 
@@ -39,16 +43,28 @@ struct SerializableGraph {
     config: SerializableConfig,
     nodes: Vec<bool>,
     edges: Vec<SerializableEdge>,
+    /// Labels of alive edges keyed by edge index; unlabeled edges are absent.
+    #[serde(default)]
+    edge_labels: BTreeMap<u64, String>,
 }
 
 impl SerializableGraph {
     fn from_graph(graph: &HypergraphImpl) -> Self {
         let config = SerializableConfig::from_config(graph.config());
         let nodes = graph.node_states();
-        let edges = graph
-            .edge_payloads()
+        let payloads = graph.edge_payloads();
+        let edge_labels = payloads
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, (alive, _, _, label))| {
+                label
+                    .filter(|_| *alive)
+                    .map(|label| (idx as u64, label.to_string()))
+            })
+            .collect();
+        let edges = payloads
             .into_iter()
-            .map(|(alive, sources, destinations)| SerializableEdge {
+            .map(|(alive, sources, destinations, _)| SerializableEdge {
                 alive,
                 sources: sources.iter().map(|id| id.as_raw()).collect(),
                 destinations: destinations.iter().map(|id| id.as_raw()).collect(),
@@ -58,10 +74,12 @@ impl SerializableGraph {
             config,
             nodes,
             edges,
+            edge_labels,
         }
     }
 
     fn into_graph(self) -> Result<HypergraphImpl, AsmError> {
+        self.validate_overrides()?;
         let config = self.config.into_config();
         let mut graph = HypergraphImpl::new(config);
         for alive in self.nodes {
@@ -70,7 +88,7 @@ impl SerializableGraph {
                 graph.remove_node(node_id)?;
             }
         }
-        for edge in self.edges {
+        for (idx, edge) in self.edges.into_iter().enumerate() {
             let sources: Vec<NodeId> = edge.sources.into_iter().map(NodeId::from_raw).collect();
             let destinations: Vec<NodeId> = edge
                 .destinations
@@ -78,13 +96,57 @@ impl SerializableGraph {
                 .map(NodeId::from_raw)
                 .collect();
             if edge.alive {
-                graph.add_hyperedge(&sources, &destinations)?;
+                match self.edge_labels.get(&(idx as u64)) {
+                    Some(label) => graph.add_hyperedge_labeled(&sources, &destinations, label)?,
+                    None => graph.add_hyperedge(&sources, &destinations)?,
+                };
             } else {
-                graph.push_dead_edge(sources, destinations);
+                graph.push_dead_edge(sources, destinations)?;
             }
         }
         Ok(graph)
     }
+
+    /// Rejects overrides for nodes outside the payload and overrides that the
+    /// stored edges already exceed.
+    fn validate_overrides(&self) -> Result<(), AsmError> {
+        for (&node, limits) in &self.config.degree_overrides {
+            if node >= self.nodes.len() as u64 {
+                return Err(AsmError::Graph(
+                    override_error("degree override targets an unknown node")
+                        .with_context("node", node.to_string())
+                        .with_context("nodes", self.nodes.len().to_string()),
+                ));
+            }
+            let alive = self.edges.iter().filter(|edge| edge.alive);
+            let (in_degree, out_degree) = alive.fold((0, 0), |(din, dout), edge| {
+                (
+                    din + usize::from(edge.destinations.contains(&node)),
+                    dout + usize::from(edge.sources.contains(&node)),
+                )
+            });
+            for (direction, cap, degree) in [
+                ("in", limits.max_in, in_degree),
+                ("out", limits.max_out, out_degree),
+            ] {
+                let Some(cap) = cap.filter(|cap| degree > *cap) else {
+                    continue;
+                };
+                return Err(AsmError::Graph(
+                    override_error("degree override is below the stored degree")
+                        .with_context("node", node.to_string())
+                        .with_context("direction", direction.to_string())
+                        .with_context("cap", cap.to_string())
+                        .with_context("degree", degree.to_string()),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn override_error(message: &str) -> ErrorInfo {
+    ErrorInfo::new(ErrorCode::DegreeOverride, message)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -94,6 +156,16 @@ struct SerializableConfig {
     max_out_degree: Option<usize>,
     k_uniform: Option<SerializableUniformity>,
     schema_version: SchemaVersion,
+    #[serde(default)]
+    degree_overrides: BTreeMap<u64, SerializableLimits>,
+    #[serde(default)]
+    label_rules: BTreeMap<String, SerializableUniformity>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct SerializableLimits {
+    max_in: Option<usize>,
+    max_out: Option<usize>,
 }
 
 impl SerializableConfig {
@@ -104,6 +176,22 @@ impl SerializableConfig {
             max_out_degree: config.max_out_degree,
             k_uniform: config.k_uniform.map(SerializableUniformity::from),
             schema_version: config.schema_version,
+            degree_overrides: config
+                .degree_overrides
+                .iter()
+                .map(|(&node, limits)| {
+                    let limits = SerializableLimits {
+                        max_in: limits.max_in,
+                        max_out: limits.max_out,
+                    };
+                    (node, limits)
+                })
+                .collect(),
+            label_rules: config
+                .label_rules
+                .iter()
+                .map(|(label, rule)| (label.clone(), SerializableUniformity::from(*rule)))
+                .collect(),
         }
     }
 
@@ -113,7 +201,26 @@ impl SerializableConfig {
             max_in_degree: self.max_in_degree,
             max_out_degree: self.max_out_degree,
             k_uniform: self.k_uniform.map(|k| k.into()),
+            label_rules: self
+                .label_rules
+                .into_iter()
+                .map(|(label, rule)| (label, rule.into()))
+                .collect(),
             schema_version: self.schema_version,
+            degree_overrides: self
+                .degree_overrides
+                .into_iter()
+                .map(|(node, limits)| {
+                    let limits = DegreeLimits {
+                        max_in: limits.max_in,
+                        max_out: limits.max_out,
+                    };
+                    (node, limits)
+                })
+                .collect(),
+            // Storage mode is not part of the payload; see
+            // `HypergraphImpl::set_compact_ids`.
+            compact_ids: false,
         }
     }
 }
@@ -0,0 +1,238 @@
+use asm_core::errors::{AsmError, ErrorCode};
+use asm_core::rng::RngHandle;
+use asm_core::{EdgeId, Hypergraph, NodeId};
+use asm_graph::{
+    canonical_hash, gen_bounded_degree, graph_from_json, graph_to_bytes, graph_to_json,
+    rewire_resource_balanced, rewire_retarget, rewire_swap_targets, HypergraphConfig,
+    HypergraphImpl, KUniformity,
+};
+use proptest::prelude::*;
+
+#[derive(Debug, Clone)]
+enum Op {
+    AddNode,
+    AddEdge(Vec<usize>, Vec<usize>),
+    RemoveEdge(usize),
+    RemoveNode(usize),
+    Retarget(usize, usize, usize),
+    Swap(usize, usize),
+    Balance(usize, u64),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    let picks = || prop::collection::vec(any::<usize>(), 1..3);
+    prop_oneof![
+        2 => Just(Op::AddNode),
+        4 => (picks(), picks()).prop_map(|(s, d)| Op::AddEdge(s, d)),
+        1 => any::<usize>().prop_map(Op::RemoveEdge),
+        1 => any::<usize>().prop_map(Op::RemoveNode),
+        1 => (any::<usize>(), any::<usize>(), any::<usize>())
+            .prop_map(|(e, r, a)| Op::Retarget(e, r, a)),
+        1 => (any::<usize>(), any::<usize>()).prop_map(|(a, b)| Op::Swap(a, b)),
+        1 => (any::<usize>(), any::<u64>()).prop_map(|(n, seed)| Op::Balance(n, seed)),
+    ]
+}
+
+fn config(compact_ids: bool) -> HypergraphConfig {
+    HypergraphConfig {
+        max_in_degree: Some(4),
+        max_out_degree: Some(4),
+        k_uniform: None,
+        compact_ids,
+        ..HypergraphConfig::default()
+    }
+}
+
+fn pick<T: Copy>(items: &[T], index: usize) -> Option<T> {
+    (!items.is_empty()).then(|| items[index % items.len()])
+}
+
+fn pick_all(items: &[NodeId], indices: &[usize]) -> Vec<NodeId> {
+    indices
+        .iter()
+        .filter_map(|&index| pick(items, index))
+        .collect()
+}
+
+/// Applies `op` and reduces the outcome to something comparable across modes.
+fn apply(graph: &mut HypergraphImpl, op: &Op) -> Result<String, String> {
+    let nodes: Vec<NodeId> = graph.nodes().collect();
+    let edges: Vec<EdgeId> = graph.edges().collect();
+    let code = |err: AsmError| err.to_string();
+    match op {
+        Op::AddNode => graph
+            .add_node()
+            .map(|id| id.as_raw().to_string())
+            .map_err(code),
+        Op::AddEdge(sources, destinations) => graph
+            .add_hyperedge(&pick_all(&nodes, sources), &pick_all(&nodes, destinations))
+            .map(|id| id.as_raw().to_string())
+            .map_err(code),
+        Op::RemoveEdge(index) => match pick(&edges, *index) {
+            Some(edge) => graph
+                .remove_hyperedge(edge)
+                .map(|_| String::new())
+                .map_err(code),
+            None => Ok(String::new()),
+        },
+        Op::RemoveNode(index) => match pick(&nodes, *index) {
+            Some(node) => graph.remove_node(node).map(|_| String::new()).map_err(code),
+            None => Ok(String::new()),
+        },
+        Op::Retarget(edge, removed, added) => match (pick(&edges, *edge), pick(&nodes, *added)) {
+            (Some(edge), Some(added)) => {
+                let targets = graph.dst_of(edge).unwrap().to_vec();
+                let removed = pick(&targets, *removed).into_iter().collect::<Vec<_>>();
+                rewire_retarget(graph, edge, &removed, &[added])
+                    .map(|outcome| format!("{outcome:?}"))
+                    .map_err(code)
+            }
+            _ => Ok(String::new()),
+        },
+        Op::Swap(a, b) => match (pick(&edges, *a), pick(&edges, *b)) {
+            (Some(a), Some(b)) => rewire_swap_targets(graph, a, b)
+                .map(|outcome| format!("{outcome:?}"))
+                .map_err(code),
+            _ => Ok(String::new()),
+        },
+        Op::Balance(node, seed) => match pick(&nodes, *node) {
+            Some(node) => {
+                let mut rng = RngHandle::from_seed(*seed);
+                rewire_resource_balanced(graph, node, &mut rng)
+                    .map(|outcome| format!("{outcome:?}"))
+                    .map_err(code)
+            }
+            None => Ok(String::new()),
+        },
+    }
+}
+
+fn assert_equivalent(wide: &HypergraphImpl, compact: &HypergraphImpl) {
+    assert!(!wide.is_compact());
+    assert!(compact.is_compact());
+    assert_eq!(
+        canonical_hash(wide).unwrap(),
+        canonical_hash(compact).unwrap()
+    );
+    assert_eq!(
+        graph_to_bytes(wide).unwrap(),
+        graph_to_bytes(compact).unwrap()
+    );
+    assert_eq!(
+        graph_to_json(wide).unwrap(),
+        graph_to_json(compact).unwrap()
+    );
+    assert_eq!(
+        wide.nodes().collect::<Vec<_>>(),
+        compact.nodes().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        wide.edges().collect::<Vec<_>>(),
+        compact.edges().collect::<Vec<_>>()
+    );
+    for node in wide.nodes() {
+        assert_eq!(
+            wide.in_degree(node).unwrap(),
+            compact.in_degree(node).unwrap()
+        );
+        assert_eq!(
+            wide.out_degree(node).unwrap(),
+            compact.out_degree(node).unwrap()
+        );
+        assert_eq!(
+            wide.edges_touching(node).unwrap(),
+            compact.edges_touching(node).unwrap()
+        );
+    }
+    for edge in wide.edges() {
+        assert_eq!(wide.src_of(edge).unwrap(), compact.src_of(edge).unwrap());
+        assert_eq!(wide.dst_of(edge).unwrap(), compact.dst_of(edge).unwrap());
+    }
+}
+
+proptest! {
+    #[test]
+    fn compact_mode_matches_wide_mode(ops in prop::collection::vec(op(), 1..48)) {
+        let mut wide = HypergraphImpl::new(config(false));
+        let mut compact = HypergraphImpl::new(config(true));
+        for op in &ops {
+            prop_assert_eq!(apply(&mut wide, op), apply(&mut compact, op));
+            assert_equivalent(&wide, &compact);
+        }
+    }
+}
+
+#[test]
+fn switching_modes_round_trips() {
+    let mut rng = RngHandle::from_seed(17);
+    let wide = gen_bounded_degree(64, 3, 3, &mut rng).unwrap();
+    let mut compact = wide.clone();
+    compact.set_compact_ids(true).unwrap();
+    assert_equivalent(&wide, &compact);
+
+    let mut back = compact.clone();
+    back.set_compact_ids(false).unwrap();
+    assert!(!back.is_compact());
+    assert_eq!(graph_to_json(&wide).unwrap(), graph_to_json(&back).unwrap());
+}
+
+#[test]
+fn duplicate_edges_are_rejected_in_both_modes() {
+    for compact_ids in [false, true] {
+        let mut graph = HypergraphImpl::new(config(compact_ids));
+        let a = graph.add_node().unwrap();
+        let b = graph.add_node().unwrap();
+        let c = graph.add_node().unwrap();
+        graph.add_hyperedge(&[a], &[b, c]).unwrap();
+        let err = graph.add_hyperedge(&[a], &[c, b]).unwrap_err();
+        assert!(err.is(ErrorCode::DuplicateEdge));
+        graph.set_compact_ids(!compact_ids).unwrap();
+        let err = graph.add_hyperedge(&[a], &[b, c]).unwrap_err();
+        assert!(err.is(ErrorCode::DuplicateEdge));
+    }
+}
+
+#[test]
+fn oversized_ids_are_rejected_in_compact_mode() {
+    let mut graph = HypergraphImpl::new(HypergraphConfig {
+        k_uniform: Some(KUniformity::Balanced {
+            sources: 1,
+            destinations: 1,
+        }),
+        compact_ids: true,
+        ..HypergraphConfig::default()
+    });
+    let a = graph.add_node().unwrap();
+    let huge = NodeId::from_raw(u32::MAX as u64 + 1);
+    let err = graph.add_hyperedge(&[a], &[huge]).unwrap_err();
+    assert!(err.is(ErrorCode::CompactIdOverflow));
+    assert_eq!(
+        err.context_value("node"),
+        Some(huge.as_raw().to_string().as_str())
+    );
+    assert_eq!(err.context_value("limit"), Some(u32::MAX.to_string().as_str()));
+    assert_eq!(graph.edges().count(), 0);
+}
+
+#[test]
+fn enabling_compact_mode_checks_dead_edges() {
+    let mut graph = HypergraphImpl::new(config(false));
+    let a = graph.add_node().unwrap();
+    let b = graph.add_node().unwrap();
+    graph.add_hyperedge(&[a], &[b]).unwrap();
+    let mut payload: serde_json::Value =
+        serde_json::from_str(&graph_to_json(&graph).unwrap()).unwrap();
+    let huge = u32::MAX as u64 + 1;
+    payload["edges"]
+        .as_array_mut()
+        .unwrap()
+        .push(serde_json::json!({"alive": false, "sources": [0], "destinations": [huge]}));
+
+    let mut restored = graph_from_json(&payload.to_string()).unwrap();
+    assert!(!restored.is_compact());
+    let before = graph_to_json(&restored).unwrap();
+    let err = restored.set_compact_ids(true).unwrap_err();
+    assert!(err.is(ErrorCode::CompactIdOverflow));
+    assert!(!restored.is_compact());
+    assert_eq!(graph_to_json(&restored).unwrap(), before);
+}
@@ -0,0 +1,78 @@
+//! Memory smoke test for compact adjacency storage.
+//!
+//! Lives in its own test binary because it installs a counting global
+//! allocator.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use asm_core::{Hypergraph, NodeId};
+use asm_graph::{HypergraphConfig, HypergraphImpl, KUniformity};
+
+struct Counting;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LIVE.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+        LIVE.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+const NODES: usize = 100_000;
+const EDGES: usize = 1_000_000;
+
+/// Builds a 1M-edge graph and returns the bytes it keeps alive.
+fn footprint(compact_ids: bool) -> usize {
+    let config = HypergraphConfig {
+        causal_mode: false,
+        max_in_degree: None,
+        max_out_degree: None,
+        k_uniform: Some(KUniformity::Balanced {
+            sources: 1,
+            destinations: 1,
+        }),
+        compact_ids,
+        ..HypergraphConfig::default()
+    };
+    let before = LIVE.load(Ordering::Relaxed);
+    let mut graph = HypergraphImpl::new(config);
+    let nodes: Vec<NodeId> = (0..NODES).map(|_| graph.add_node().unwrap()).collect();
+    for i in 0..EDGES {
+        let source = i % NODES;
+        let destination = (source + 1 + i / NODES) % NODES;
+        graph
+            .add_hyperedge(&[nodes[source]], &[nodes[destination]])
+            .unwrap();
+    }
+    drop(nodes);
+    let used = LIVE.load(Ordering::Relaxed) - before;
+    assert_eq!(graph.edges().len(), EDGES);
+    drop(graph);
+    used
+}
+
+#[test]
+fn compact_ids_shrink_large_graphs() {
+    let wide = footprint(false);
+    let compact = footprint(true);
+    assert!(
+        compact * 10 < wide * 7,
+        "compact storage used {compact} bytes against {wide} wide"
+    );
+}
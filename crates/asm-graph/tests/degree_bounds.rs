@@ -1,6 +1,9 @@
-use asm_core::errors::AsmError;
-use asm_core::Hypergraph;
-use asm_graph::{HypergraphConfig, HypergraphImpl, KUniformity};
+use asm_core::errors::{AsmError, ErrorCode};
+use asm_core::{EdgeId, Hypergraph, NodeId};
+use asm_graph::{
+    canonical_hash, graph_from_json, graph_to_json, rewire_retarget_dry_run, DegreeLimits,
+    HypergraphConfig, HypergraphImpl, KUniformity, RewireDryRun,
+};
 
 #[test]
 fn outbound_cap_enforced() {
@@ -55,3 +58,105 @@ fn inbound_cap_enforced() {
         other => panic!("unexpected error: {other:?}"),
     }
 }
+
+fn capped_config() -> HypergraphConfig {
+    HypergraphConfig {
+        causal_mode: false,
+        max_out_degree: Some(2),
+        max_in_degree: Some(1),
+        k_uniform: Some(KUniformity::Total {
+            total: 2,
+            min_sources: 1,
+        }),
+        ..Default::default()
+    }
+}
+
+/// Builds `a -> c` and `b -> a` and returns the nodes and the second edge.
+fn fan_in_graph(config: HypergraphConfig) -> (HypergraphImpl, [NodeId; 3], EdgeId) {
+    let mut graph = HypergraphImpl::new(config);
+    let a = graph.add_node().unwrap();
+    let b = graph.add_node().unwrap();
+    let c = graph.add_node().unwrap();
+    graph.add_hyperedge(&[a], &[c]).unwrap();
+    let edge = graph.add_hyperedge(&[b], &[a]).unwrap();
+    (graph, [a, b, c], edge)
+}
+
+#[test]
+fn degree_override_lifts_global_cap() {
+    let mut config = capped_config();
+    config.degree_overrides.insert(
+        2,
+        DegreeLimits {
+            max_in: Some(2),
+            max_out: Some(2),
+        },
+    );
+    let (mut graph, [a, b, c], edge) = fan_in_graph(config);
+    assert!(matches!(
+        rewire_retarget_dry_run(&graph, edge, &[a], &[c]),
+        RewireDryRun::Valid { .. }
+    ));
+    graph.add_hyperedge(&[b], &[c]).unwrap();
+    assert_eq!(graph.in_degree(c).unwrap(), 2);
+
+    let restored = graph_from_json(&graph_to_json(&graph).unwrap()).unwrap();
+    assert_eq!(restored.node_degree_limits(c), graph.node_degree_limits(c));
+    assert_eq!(
+        canonical_hash(&restored).unwrap(),
+        canonical_hash(&graph).unwrap()
+    );
+
+    let (mut plain, [a, b, c], edge) = fan_in_graph(capped_config());
+    assert!(matches!(
+        rewire_retarget_dry_run(&plain, edge, &[a], &[c]),
+        RewireDryRun::Invalid(err) if err.is(ErrorCode::InDegreeCap)
+    ));
+    let err = plain.add_hyperedge(&[b], &[c]).unwrap_err();
+    assert!(err.is(ErrorCode::InDegreeCap));
+}
+
+#[test]
+fn overrides_change_canonical_hash() {
+    let plain = HypergraphImpl::new(capped_config());
+    let mut config = capped_config();
+    config.degree_overrides.insert(0, DegreeLimits::unlimited());
+    let overridden = HypergraphImpl::new(config);
+    assert_ne!(
+        canonical_hash(&plain).unwrap(),
+        canonical_hash(&overridden).unwrap()
+    );
+}
+
+#[test]
+fn deserialization_validates_overrides() {
+    let mut config = capped_config();
+    config.degree_overrides.insert(
+        2,
+        DegreeLimits {
+            max_in: Some(2),
+            max_out: None,
+        },
+    );
+    let mut graph = HypergraphImpl::new(config);
+    let a = graph.add_node().unwrap();
+    let b = graph.add_node().unwrap();
+    let c = graph.add_node().unwrap();
+    graph.add_hyperedge(&[a], &[c]).unwrap();
+    graph.add_hyperedge(&[b], &[c]).unwrap();
+    let mut payload: serde_json::Value =
+        serde_json::from_str(&graph_to_json(&graph).unwrap()).unwrap();
+
+    payload["config"]["degree_overrides"]["2"]["max_in"] = 1.into();
+    let err = graph_from_json(&payload.to_string()).unwrap_err();
+    assert!(err.is(ErrorCode::DegreeOverride));
+    assert_eq!(err.context_value("node"), Some("2"));
+    assert_eq!(err.context_value("direction"), Some("in"));
+    assert_eq!(err.context_value("degree"), Some("2"));
+
+    payload["config"]["degree_overrides"] = serde_json::json!({"7": {"max_in": 1, "max_out": 1}});
+    let err = graph_from_json(&payload.to_string()).unwrap_err();
+    assert!(err.is(ErrorCode::DegreeOverride));
+    assert_eq!(err.context_value("node"), Some("7"));
+}
@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+
+use asm_core::errors::ErrorCode;
+use asm_core::rng::RngHandle;
+use asm_core::{Hypergraph, NodeId};
+use asm_graph::{
+    canonical_hash, graph_from_bytes, graph_from_json, graph_to_bytes, graph_to_json,
+    rewire_resource_balanced, rewire_swap_targets, HypergraphConfig, HypergraphImpl, KUniformity,
+};
+
+/// Canonical hash of [`unlabeled_graph`] recorded before edge labels existed.
+const UNLABELED_HASH: &str = "f5a2489af07a93bbdf9a184ad9d767f9f6293e9bfc6202ae09927106b60038b1";
+
+/// JSON payload of [`unlabeled_graph`] as written before edge labels existed.
+const UNLABELED_JSON: &str = r#"{"config":{"causal_mode":false,"max_in_degree":8,"max_out_degree":8,
+"k_uniform":{"Total":{"total":3,"min_sources":1}},"schema_version":{"major":2,"minor":0,"patch":0},
+"degree_overrides":{}},"nodes":[true,true,true,true],"edges":[
+{"alive":true,"sources":[0],"destinations":[1,2]},
+{"alive":true,"sources":[1,2],"destinations":[3]},
+{"alive":false,"sources":[3],"destinations":[0,1]}]}"#;
+
+fn config() -> HypergraphConfig {
+    HypergraphConfig {
+        causal_mode: false,
+        k_uniform: Some(KUniformity::Total {
+            total: 3,
+            min_sources: 1,
+        }),
+        ..HypergraphConfig::default()
+    }
+}
+
+fn nodes(graph: &mut HypergraphImpl, count: usize) -> Vec<NodeId> {
+    (0..count).map(|_| graph.add_node().unwrap()).collect()
+}
+
+fn unlabeled_graph() -> HypergraphImpl {
+    let mut graph = HypergraphImpl::new(config());
+    let n = nodes(&mut graph, 4);
+    graph.add_hyperedge(&[n[0]], &[n[1], n[2]]).unwrap();
+    graph.add_hyperedge(&[n[1], n[2]], &[n[3]]).unwrap();
+    let dead = graph.add_hyperedge(&[n[3]], &[n[0], n[1]]).unwrap();
+    graph.remove_hyperedge(dead).unwrap();
+    graph
+}
+
+/// Matter edges are 1 -> 1, gauge edges 2 -> 2; unlabeled edges use `Total(3)`.
+fn sectored_config() -> HypergraphConfig {
+    let mut config = config();
+    config.label_rules = BTreeMap::from([
+        (
+            "matter".to_string(),
+            KUniformity::Balanced {
+                sources: 1,
+                destinations: 1,
+            },
+        ),
+        (
+            "gauge".to_string(),
+            KUniformity::Balanced {
+                sources: 2,
+                destinations: 2,
+            },
+        ),
+    ]);
+    config
+}
+
+#[test]
+fn unlabeled_graphs_keep_hash_and_payload() {
+    let graph = unlabeled_graph();
+    assert_eq!(canonical_hash(&graph).unwrap(), UNLABELED_HASH);
+    for edge in graph.edges() {
+        assert_eq!(graph.edge_label(edge).unwrap(), None);
+    }
+
+    let legacy = graph_from_json(UNLABELED_JSON).unwrap();
+    assert_eq!(canonical_hash(&legacy).unwrap(), UNLABELED_HASH);
+    let restored = graph_from_json(&graph_to_json(&graph).unwrap()).unwrap();
+    assert_eq!(canonical_hash(&restored).unwrap(), UNLABELED_HASH);
+    let restored = graph_from_bytes(&graph_to_bytes(&graph).unwrap()).unwrap();
+    assert_eq!(canonical_hash(&restored).unwrap(), UNLABELED_HASH);
+}
+
+#[test]
+fn label_rules_enforce_arity_per_label() {
+    let mut graph = HypergraphImpl::new(sectored_config());
+    let n = nodes(&mut graph, 4);
+    let matter = graph
+        .add_hyperedge_labeled(&[n[0]], &[n[1]], "matter")
+        .unwrap();
+    let gauge = graph
+        .add_hyperedge_labeled(&[n[0], n[1]], &[n[2], n[3]], "gauge")
+        .unwrap();
+    let plain = graph.add_hyperedge(&[n[2]], &[n[0], n[3]]).unwrap();
+    // Labels without a rule fall back to `k_uniform`.
+    let other = graph
+        .add_hyperedge_labeled(&[n[3]], &[n[1], n[2]], "other")
+        .unwrap();
+
+    let err = graph
+        .add_hyperedge_labeled(&[n[0]], &[n[2], n[3]], "matter")
+        .unwrap_err();
+    assert!(err.is(ErrorCode::InvalidArity));
+    assert_eq!(err.context_value("label"), Some("matter"));
+    assert!(graph.add_hyperedge(&[n[1]], &[n[2]]).is_err());
+
+    assert_eq!(graph.edge_label(matter).unwrap(), Some("matter"));
+    assert_eq!(graph.edge_label(gauge).unwrap(), Some("gauge"));
+    assert_eq!(graph.edge_label(plain).unwrap(), None);
+    assert_eq!(graph.edges_with_label("matter"), [matter]);
+    assert_eq!(graph.edges_with_label("other"), [other]);
+    assert!(graph.edges_with_label("missing").is_empty());
+}
+
+#[test]
+fn labels_distinguish_edges_and_hashes() {
+    let mut graph = HypergraphImpl::new(sectored_config());
+    let n = nodes(&mut graph, 3);
+    graph
+        .add_hyperedge_labeled(&[n[0]], &[n[1], n[2]], "a")
+        .unwrap();
+    graph
+        .add_hyperedge_labeled(&[n[0]], &[n[1], n[2]], "b")
+        .unwrap();
+    graph.add_hyperedge(&[n[0]], &[n[1], n[2]]).unwrap();
+    let err = graph
+        .add_hyperedge_labeled(&[n[0]], &[n[1], n[2]], "a")
+        .unwrap_err();
+    assert!(err.is(ErrorCode::DuplicateEdge));
+    assert_eq!(graph.edges().len(), 3);
+
+    let hash_of = |label: Option<&str>| {
+        let mut graph = HypergraphImpl::new(config());
+        let n = nodes(&mut graph, 3);
+        match label {
+            Some(label) => graph.add_hyperedge_labeled(&[n[0]], &[n[1], n[2]], label),
+            None => graph.add_hyperedge(&[n[0]], &[n[1], n[2]]),
+        }
+        .unwrap();
+        canonical_hash(&graph).unwrap()
+    };
+    assert_ne!(hash_of(None), hash_of(Some("a")));
+    assert_ne!(hash_of(Some("a")), hash_of(Some("b")));
+
+    let mut compact = graph.clone();
+    compact.set_compact_ids(true).unwrap();
+    assert!(compact
+        .add_hyperedge_labeled(&[n[0]], &[n[1], n[2]], "b")
+        .is_err());
+    assert!(compact
+        .add_hyperedge_labeled(&[n[0]], &[n[1], n[2]], "c")
+        .is_ok());
+}
+
+#[test]
+fn labels_round_trip_through_serialization() {
+    let mut graph = HypergraphImpl::new(sectored_config());
+    let n = nodes(&mut graph, 4);
+    graph
+        .add_hyperedge_labeled(&[n[0]], &[n[1]], "matter")
+        .unwrap();
+    let dead = graph
+        .add_hyperedge_labeled(&[n[1]], &[n[2]], "matter")
+        .unwrap();
+    graph.remove_hyperedge(dead).unwrap();
+    graph
+        .add_hyperedge_labeled(&[n[0], n[1]], &[n[2], n[3]], "gauge")
+        .unwrap();
+    graph.add_hyperedge(&[n[2]], &[n[0], n[3]]).unwrap();
+    let hash = canonical_hash(&graph).unwrap();
+
+    for restored in [
+        graph_from_json(&graph_to_json(&graph).unwrap()).unwrap(),
+        graph_from_bytes(&graph_to_bytes(&graph).unwrap()).unwrap(),
+    ] {
+        assert_eq!(canonical_hash(&restored).unwrap(), hash);
+        assert_eq!(restored.config().label_rules, graph.config().label_rules);
+        assert_eq!(
+            restored.edges_with_label("gauge"),
+            graph.edges_with_label("gauge")
+        );
+        assert_eq!(
+            restored.edges_with_label("matter"),
+            graph.edges_with_label("matter")
+        );
+    }
+}
+
+#[test]
+fn rewires_preserve_labels() {
+    let mut graph = HypergraphImpl::new(sectored_config());
+    let n = nodes(&mut graph, 4);
+    let a = graph
+        .add_hyperedge_labeled(&[n[0]], &[n[1]], "matter")
+        .unwrap();
+    let b = graph
+        .add_hyperedge_labeled(&[n[2]], &[n[3]], "matter")
+        .unwrap();
+    let gauge = graph
+        .add_hyperedge_labeled(&[n[0], n[2]], &[n[1], n[3]], "gauge")
+        .unwrap();
+
+    let outcome = rewire_swap_targets(&mut graph, a, b).unwrap();
+    assert!(outcome.changed);
+    assert_eq!(graph.dst_of(a).unwrap().as_ref(), [n[3]]);
+    assert_eq!(graph.edge_label(a).unwrap(), Some("matter"));
+    assert_eq!(graph.edge_label(b).unwrap(), Some("matter"));
+
+    let mut rng = RngHandle::from_seed(3);
+    for node in [n[0], n[1], n[2], n[3]] {
+        let _ = rewire_resource_balanced(&mut graph, node, &mut rng);
+    }
+    assert_eq!(graph.edges_with_label("matter"), [a, b]);
+    assert_eq!(graph.edges_with_label("gauge"), [gauge]);
+}
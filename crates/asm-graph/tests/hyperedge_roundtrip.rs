@@ -1,4 +1,4 @@
-use asm_core::errors::AsmError;
+use asm_core::errors::{AsmError, ErrorCode};
 use asm_core::Hypergraph;
 use asm_graph::{
     canonical_hash, graph_from_json, graph_to_json, HypergraphConfig, HypergraphImpl, KUniformity,
@@ -51,3 +51,26 @@ fn add_remove_and_serialize() {
 
     graph.remove_node(n0).unwrap();
 }
+
+#[test]
+fn structural_errors_use_registered_codes() {
+    let mut graph = HypergraphImpl::new(HypergraphConfig {
+        causal_mode: false,
+        k_uniform: None,
+        ..HypergraphConfig::default()
+    });
+    let n0 = graph.add_node().unwrap();
+    let n1 = graph.add_node().unwrap();
+    let edge = graph.add_hyperedge(&[n0], &[n1]).unwrap();
+
+    let duplicate = graph.add_hyperedge(&[n0], &[n1]).unwrap_err();
+    assert!(duplicate.is(ErrorCode::DuplicateEdge));
+
+    let busy = graph.remove_node(n0).unwrap_err();
+    assert_eq!(busy.info().code_enum(), Some(ErrorCode::NodeNotIsolated));
+
+    graph.remove_hyperedge(edge).unwrap();
+    let missing = graph.remove_hyperedge(edge).unwrap_err();
+    assert!(missing.is(ErrorCode::UnknownEdge));
+    assert!(missing.context_value("edge").is_some());
+}
@@ -2,7 +2,47 @@ use std::os::raw::c_char;
 
 use asm_core::errors::{AsmError, ErrorInfo};
 
-pub const ASM_ABI_VERSION: u32 = 1;
+/// ABI implemented by this host, packed as `major << 16 | minor`.
+///
+/// Minor revisions only add entry points or append fields, so a host serves
+/// plugins built against any minor of its major unless the plugin narrows the
+/// range with [`AsmPluginInfo::min_abi`] and [`AsmPluginInfo::max_abi`].
+pub const ASM_ABI_VERSION: u32 = abi_version(1, 1);
+
+/// First ABI whose [`AsmPluginInfo`] carries `min_abi` and `max_abi`.
+pub const ABI_RANGE_VERSION: u32 = abi_version(1, 1);
+
+/// Packs an ABI `major.minor` pair.
+pub const fn abi_version(major: u16, minor: u16) -> u32 {
+    (major as u32) << 16 | minor as u32
+}
+
+/// Reads a declared ABI version as a packed `major.minor`.
+///
+/// Plugins written before versions were packed declare the bare major
+/// (`abi_version = 1`), so values below `1 << 16` mean `major.0`.
+pub const fn normalize_abi(version: u32) -> u32 {
+    if version < 1 << 16 {
+        abi_version(version as u16, 0)
+    } else {
+        version
+    }
+}
+
+/// Major component of an ABI version.
+pub const fn abi_major(version: u32) -> u16 {
+    (normalize_abi(version) >> 16) as u16
+}
+
+/// Minor component of an ABI version.
+pub const fn abi_minor(version: u32) -> u16 {
+    normalize_abi(version) as u16
+}
+
+/// Renders an ABI version as `major.minor`.
+pub fn format_abi(version: u32) -> String {
+    format!("{}.{}", abi_major(version), abi_minor(version))
+}
 
 /// Result returned by plugin entrypoints.
 #[repr(C)]
@@ -18,6 +58,20 @@ impl AsmStatus {
         message_len: 0,
     };
 
+    /// Returned when the sandbox blocks a call the plugin did not declare;
+    /// mirrors `EACCES`.
+    pub const DENIED: Self = Self {
+        code: 13,
+        message_len: 0,
+    };
+
+    /// Returned when the plugin does not export the requested entry point;
+    /// mirrors `ENOSYS`.
+    pub const UNSUPPORTED: Self = Self {
+        code: 38,
+        message_len: 0,
+    };
+
     pub fn is_ok(self) -> bool {
         self.code == 0
     }
@@ -33,12 +87,27 @@ pub enum Capability {
     Interact = 1 << 4,
     Rg = 1 << 5,
     Exp = 1 << 6,
+    Filesystem = 1 << 7,
 }
 
 impl Capability {
     pub fn flag(self) -> u32 {
         self as u32
     }
+
+    /// Manifest spelling of the capability.
+    pub fn name(self) -> &'static str {
+        match self {
+            Capability::Graph => "graph",
+            Capability::Code => "code",
+            Capability::Spectrum => "spectrum",
+            Capability::Gauge => "gauge",
+            Capability::Interact => "interact",
+            Capability::Rg => "rg",
+            Capability::Exp => "exp",
+            Capability::Filesystem => "filesystem",
+        }
+    }
 }
 
 #[repr(C)]
@@ -63,10 +132,54 @@ impl AbiString {
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct AsmPluginInfo {
+    /// ABI the plugin was built against.
     pub abi_version: u32,
     pub name: AbiString,
     pub version: AbiString,
     pub capabilities: u32,
+    /// Oldest host ABI the plugin runs on; `0` means `abi_version`. Present
+    /// from [`ABI_RANGE_VERSION`] on.
+    pub min_abi: u32,
+    /// Newest host ABI the plugin runs on; `0` means the last minor of the
+    /// `abi_version` major. Present from [`ABI_RANGE_VERSION`] on.
+    pub max_abi: u32,
+}
+
+/// Layout of [`AsmPluginInfo`] before [`ABI_RANGE_VERSION`].
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct AsmPluginInfoPrefix {
+    abi_version: u32,
+    name: AbiString,
+    version: AbiString,
+    capabilities: u32,
+}
+
+impl AsmPluginInfo {
+    /// Copies the info block a plugin exports.
+    ///
+    /// The appended `min_abi` and `max_abi` fields are only read when the
+    /// plugin was built against [`ABI_RANGE_VERSION`] or later; older blocks
+    /// end after `capabilities` and get `0` for both.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to an info block laid out for the ABI named in its
+    /// own `abi_version` field.
+    pub unsafe fn read(ptr: *const AsmPluginInfo) -> AsmPluginInfo {
+        let prefix = std::ptr::read(ptr.cast::<AsmPluginInfoPrefix>());
+        if normalize_abi(prefix.abi_version) >= ABI_RANGE_VERSION {
+            return std::ptr::read(ptr);
+        }
+        AsmPluginInfo {
+            abi_version: prefix.abi_version,
+            name: prefix.name,
+            version: prefix.version,
+            capabilities: prefix.capabilities,
+            min_abi: 0,
+            max_abi: 0,
+        }
+    }
 }
 
 #[repr(C)]
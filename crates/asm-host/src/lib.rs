@@ -7,12 +7,20 @@ mod manifest;
 mod registry;
 mod sandbox;
 mod serde;
+mod signing;
+mod version;
 
-pub use abi::{AbiString, AsmPluginInfo, AsmPluginVTable, AsmStatus, Capability, ASM_ABI_VERSION};
+pub use abi::{
+    abi_major, abi_minor, abi_version, format_abi, normalize_abi, AbiString, AsmPluginInfo,
+    AsmPluginVTable, AsmStatus, Capability, OutCallback, ABI_RANGE_VERSION, ASM_ABI_VERSION,
+};
 pub use hash::{compute_manifest_hash, compute_plugin_hash};
-pub use loader::{load_plugin_manifest, verify_abi_compat};
-pub use manifest::{PluginManifest, PluginMetadata};
-pub use registry::{PluginRegistry, RegistryEntry};
-pub use sandbox::{SandboxCaps, SandboxDecision, SandboxEvent, SandboxGuard};
+pub use loader::{load_plugin_manifest, verify_abi_compat, verify_abi_compat_with};
+pub use manifest::{PluginDependency, PluginManifest, PluginMetadata};
+pub use registry::{PluginRegistry, RegistryEntry, ReloadOutcome};
+pub use sandbox::{
+    AbiCall, CapabilityGrants, SandboxCaps, SandboxDecision, SandboxEvent, SandboxGuard,
+};
 pub use serde::{from_json_slice, to_canonical_json_bytes};
-
+pub use signing::{sign_plugin, verify_plugin_signature, SignatureStatus};
+pub use version::{Version, VersionReq};
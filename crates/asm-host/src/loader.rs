@@ -1,7 +1,9 @@
 use std::fs;
 use std::path::Path;
 
-use crate::abi::{AsmPluginInfo, ASM_ABI_VERSION};
+use crate::abi::{
+    abi_major, abi_version, format_abi, normalize_abi, AbiString, AsmPluginInfo, ASM_ABI_VERSION,
+};
 use crate::manifest::PluginManifest;
 use asm_core::errors::{AsmError, ErrorInfo};
 
@@ -23,16 +25,81 @@ pub fn load_plugin_manifest(path: &Path) -> Result<PluginManifest, AsmError> {
     })
 }
 
+/// Checks that this host's [`ASM_ABI_VERSION`] lies in the plugin's supported range.
 pub fn verify_abi_compat(info: &AsmPluginInfo) -> Result<(), AsmError> {
-    if info.abi_version != ASM_ABI_VERSION {
-        return Err(AsmError::Serde(ErrorInfo::new(
-            "asm_host.abi_mismatch",
-            format!(
-                "plugin ABI {} is incompatible with host ABI {}",
-                info.abi_version, ASM_ABI_VERSION
-            ),
-        )));
+    verify_abi_compat_with(info, ASM_ABI_VERSION)
+}
+
+/// Checks a plugin against a host implementing `host_abi`.
+///
+/// Errors are `asm_host.abi_major_mismatch` when the plugin was built for a
+/// different major, `asm_host.abi_too_new` when the plugin needs a newer host
+/// and `asm_host.abi_too_old` when the host is past the plugin's `max_abi`.
+/// A `min_abi` above `max_abi` fails with `asm_host.abi_range`. Every version
+/// goes through [`normalize_abi`], so legacy bare majors compare as `major.0`.
+pub fn verify_abi_compat_with(info: &AsmPluginInfo, host_abi: u32) -> Result<(), AsmError> {
+    let built = normalize_abi(info.abi_version);
+    let host_abi = normalize_abi(host_abi);
+    let min = if info.min_abi == 0 {
+        built
+    } else {
+        normalize_abi(info.min_abi)
+    };
+    let max = if info.max_abi == 0 {
+        abi_version(abi_major(built), u16::MAX)
+    } else {
+        normalize_abi(info.max_abi)
+    };
+    let failure = if min > max {
+        Some(("asm_host.abi_range", "plugin declares an empty ABI range"))
+    } else if abi_major(built) != abi_major(host_abi) {
+        Some((
+            "asm_host.abi_major_mismatch",
+            "plugin was built for a different ABI major",
+        ))
+    } else if host_abi < min {
+        Some(("asm_host.abi_too_new", "plugin requires a newer host ABI"))
+    } else if host_abi > max {
+        Some((
+            "asm_host.abi_too_old",
+            "plugin does not support this host ABI",
+        ))
+    } else {
+        None
+    };
+    match failure {
+        Some((code, message)) => Err(AsmError::Serde(
+            ErrorInfo::new(
+                code,
+                format!(
+                    "{message}: plugin ABI {} (supports {}..={}), host ABI {}",
+                    format_abi(built),
+                    format_abi(min),
+                    format_abi(max),
+                    format_abi(host_abi)
+                ),
+            )
+            .with_context("plugin_abi", format_abi(built))
+            .with_context("min_abi", format_abi(min))
+            .with_context("max_abi", format_abi(max))
+            .with_context("host_abi", format_abi(host_abi)),
+        )),
+        None => Ok(()),
     }
-    Ok(())
 }
 
+/// Checks the ABI advertised by `manifest` before its binary is accepted.
+pub(crate) fn verify_manifest_abi(manifest: &PluginManifest) -> Result<(), AsmError> {
+    let abi_string = |value: &str| AbiString {
+        ptr: value.as_ptr().cast(),
+        len: value.len(),
+    };
+    verify_abi_compat(&AsmPluginInfo {
+        abi_version: manifest.abi_version,
+        name: abi_string(&manifest.name),
+        version: abi_string(&manifest.version),
+        capabilities: manifest.capability_flags(),
+        min_abi: manifest.min_abi.unwrap_or(0),
+        max_abi: manifest.max_abi.unwrap_or(0),
+    })
+}
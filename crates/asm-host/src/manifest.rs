@@ -1,13 +1,30 @@
+use std::collections::BTreeSet;
+
 use asm_core::errors::{AsmError, ErrorInfo};
 use serde::{Deserialize, Serialize};
 
 use crate::abi::Capability;
+use crate::version::VersionReq;
+
+/// Requirement that another plugin be installed at a compatible version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginDependency {
+    pub name: String,
+    pub version_req: String,
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PluginManifest {
     pub name: String,
     pub version: String,
     pub abi_version: u32,
+    /// Oldest host ABI the plugin accepts; defaults to `abi_version`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_abi: Option<u32>,
+    /// Newest host ABI the plugin accepts; defaults to the last minor of the
+    /// `abi_version` major.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_abi: Option<u32>,
     #[serde(default)]
     pub capabilities: Vec<String>,
     #[serde(default)]
@@ -15,6 +32,15 @@ pub struct PluginManifest {
     pub license: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<PluginDependency>,
+    /// Hex-encoded ed25519 signature over the plugin binary, produced by
+    /// [`crate::sign_plugin`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Hex-encoded ed25519 public key that verifies `signature`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
 }
 
 impl PluginManifest {
@@ -37,6 +63,25 @@ impl PluginManifest {
                 "plugin manifest missing license",
             )));
         }
+        let mut seen = BTreeSet::new();
+        for dep in &self.dependencies {
+            let reason = if dep.name.trim().is_empty() {
+                Some("dependency missing name")
+            } else if dep.name == self.name {
+                Some("plugin cannot depend on itself")
+            } else if !seen.insert(dep.name.as_str()) {
+                Some("dependency declared more than once")
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                return Err(AsmError::Serde(
+                    ErrorInfo::new("asm_host.manifest_dependency", reason)
+                        .with_context("dependency", dep.name.clone()),
+                ));
+            }
+            VersionReq::parse(&dep.version_req)?;
+        }
         Ok(())
     }
 
@@ -51,6 +96,7 @@ impl PluginManifest {
                 "interact" => Some(Capability::Interact),
                 "rg" => Some(Capability::Rg),
                 "exp" => Some(Capability::Exp),
+                "filesystem" => Some(Capability::Filesystem),
                 _ => None,
             })
             .map(|cap| cap.flag())
@@ -65,6 +111,10 @@ pub struct PluginMetadata {
     pub abi_version: u32,
     pub capabilities: Vec<String>,
     pub manifest_hash: String,
+    /// Dependencies that were missing or incompatible when the plugin was
+    /// force-installed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unresolved_dependencies: Vec<PluginDependency>,
 }
 
 impl PluginMetadata {
@@ -75,6 +125,7 @@ impl PluginMetadata {
             abi_version: manifest.abi_version,
             capabilities: manifest.capabilities.clone(),
             manifest_hash,
+            unresolved_dependencies: Vec::new(),
         }
     }
 }
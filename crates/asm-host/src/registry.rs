@@ -1,27 +1,127 @@
+use std::collections::BTreeSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use asm_core::errors::{AsmError, ErrorInfo};
 use serde::{Deserialize, Serialize};
 
 use crate::hash::{compute_manifest_hash, compute_plugin_hash};
-use crate::manifest::{PluginManifest, PluginMetadata};
+use crate::loader::{load_plugin_manifest, verify_manifest_abi};
+use crate::manifest::{PluginDependency, PluginManifest, PluginMetadata};
+use crate::sandbox::CapabilityGrants;
 use crate::serde::to_canonical_json_bytes;
+use crate::signing::{verify_plugin_signature, SignatureStatus};
+use crate::version::{Version, VersionReq};
+
+/// Written last into a staged entry; directories without it are half-written.
+const COMPLETE_MARKER: &str = ".complete";
+/// Prefix of the directory an install is staged in before it goes live.
+const STAGING_PREFIX: &str = ".install-";
+/// Prefix under which a replaced entry waits while its successor is renamed in.
+const RETIRED_PREFIX: &str = ".retired-";
+
+fn is_complete(dir: &Path) -> bool {
+    dir.join(COMPLETE_MARKER).exists()
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RegistryEntry {
     pub metadata: PluginMetadata,
     pub plugin_hash: Option<String>,
+    /// Manifest path the plugin was installed from, re-read by
+    /// [`PluginRegistry::reload`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Whether the binary carries a verified signature; entries written
+    /// before signing existed read as unsigned.
+    #[serde(default)]
+    pub signature: SignatureStatus,
+}
+
+impl RegistryEntry {
+    /// Key under which this version is archived in `versions/`.
+    pub fn version_hash(&self) -> &str {
+        self.plugin_hash
+            .as_deref()
+            .unwrap_or(&self.metadata.manifest_hash)
+    }
+}
+
+/// Result of [`PluginRegistry::reload`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReloadOutcome {
+    pub entry: RegistryEntry,
+    pub previous_hash: Option<String>,
+    /// Whether the stored plugin binary hash differs from the previous one.
+    pub changed: bool,
+    /// Version hash of the archived previous install, if anything was swapped.
+    pub archived: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct PluginRegistry {
     root: PathBuf,
+    grants: Arc<CapabilityGrants>,
+    trusted_keys: BTreeSet<String>,
 }
 
 impl PluginRegistry {
     pub fn new(root: impl Into<PathBuf>) -> Self {
-        Self { root: root.into() }
+        Self {
+            root: root.into(),
+            grants: Arc::default(),
+            trusted_keys: BTreeSet::new(),
+        }
+    }
+
+    /// Shares `grants` with the sandbox layer so reloads invalidate them.
+    pub fn with_grants(mut self, grants: Arc<CapabilityGrants>) -> Self {
+        self.grants = grants;
+        self
+    }
+
+    pub fn grants(&self) -> &CapabilityGrants {
+        &self.grants
+    }
+
+    /// Trusts signatures made with the hex-encoded ed25519 public `keys`.
+    ///
+    /// Once any key is trusted, plugins signed with other keys are rejected
+    /// with `asm_host.signature_untrusted`. Without trusted keys they install
+    /// as [`SignatureStatus::UntrustedKey`].
+    pub fn with_trusted_keys<I, S>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.trusted_keys.extend(
+            keys.into_iter()
+                .map(|key| key.as_ref().to_ascii_lowercase()),
+        );
+        self
+    }
+
+    /// Verifies the manifest's signature against the trusted keys.
+    fn check_signature(
+        &self,
+        manifest: &PluginManifest,
+        plugin_bytes: Option<&[u8]>,
+    ) -> Result<SignatureStatus, AsmError> {
+        let status = verify_plugin_signature(manifest, plugin_bytes, &self.trusted_keys)?;
+        match &status {
+            SignatureStatus::UntrustedKey { public_key } if !self.trusted_keys.is_empty() => {
+                Err(AsmError::Serde(
+                    ErrorInfo::new(
+                        "asm_host.signature_untrusted",
+                        "plugin is signed with a key the registry does not trust",
+                    )
+                    .with_context("plugin", manifest.name.clone())
+                    .with_context("public_key", public_key.clone()),
+                ))
+            }
+            _ => Ok(status),
+        }
     }
 
     fn entry_dir(&self, name: &str) -> PathBuf {
@@ -32,58 +132,325 @@ impl PluginRegistry {
         &self,
         manifest: &PluginManifest,
         plugin_bytes: Option<&[u8]>,
+    ) -> Result<RegistryEntry, AsmError> {
+        self.install_with(manifest, plugin_bytes, false)
+    }
+
+    /// Installs `manifest`, refusing missing or incompatible dependencies
+    /// unless `force` is set, in which case they are recorded in the metadata.
+    pub fn install_with(
+        &self,
+        manifest: &PluginManifest,
+        plugin_bytes: Option<&[u8]>,
+        force: bool,
+    ) -> Result<RegistryEntry, AsmError> {
+        self.install_entry(manifest, plugin_bytes, force, None)
+    }
+
+    /// Installs the manifest at `path` (or `path/plugin.toml`) together with a
+    /// sibling `plugin.bin`, recording the source so it can be reloaded.
+    pub fn install_from(&self, path: &Path, force: bool) -> Result<RegistryEntry, AsmError> {
+        let (manifest_path, manifest, plugin_bytes) = read_source(path)?;
+        let manifest_path =
+            fs::canonicalize(&manifest_path).map_err(|err| io_error(err, &manifest_path))?;
+        self.install_entry(
+            &manifest,
+            plugin_bytes.as_deref(),
+            force,
+            Some(manifest_path.display().to_string()),
+        )
+    }
+
+    fn install_entry(
+        &self,
+        manifest: &PluginManifest,
+        plugin_bytes: Option<&[u8]>,
+        force: bool,
+        source: Option<String>,
     ) -> Result<RegistryEntry, AsmError> {
         manifest.validate()?;
+        let signature = self.check_signature(manifest, plugin_bytes)?;
+        let unresolved = self.unresolved_dependencies(manifest)?;
+        if let (false, Some((dep, installed))) = (force, unresolved.first()) {
+            return Err(dependency_error(&manifest.name, dep, installed.as_deref()));
+        }
         let manifest_hash = compute_manifest_hash(manifest)?;
-        let metadata = PluginMetadata::from_manifest(manifest, manifest_hash);
+        let mut metadata = PluginMetadata::from_manifest(manifest, manifest_hash);
+        metadata.unresolved_dependencies = unresolved.into_iter().map(|(dep, _)| dep).collect();
         let entry = RegistryEntry {
             metadata,
             plugin_hash: plugin_bytes.map(compute_plugin_hash),
+            source,
+            signature,
         };
-        let dir = self.entry_dir(&manifest.name);
-        fs::create_dir_all(&dir).map_err(|err| {
-            AsmError::Serde(
-                ErrorInfo::new("asm_host.registry_io", err.to_string())
-                    .with_context("path", dir.display().to_string()),
-            )
-        })?;
-        let manifest_path = dir.join("manifest.toml");
-        fs::write(
-            &manifest_path,
-            toml::to_string_pretty(manifest).map_err(|err| {
-                AsmError::Serde(ErrorInfo::new(
-                    "asm_host.manifest_serialize",
-                    err.to_string(),
-                ))
-            })?,
-        )
-        .map_err(|err| {
-            AsmError::Serde(
-                ErrorInfo::new("asm_host.registry_io", err.to_string())
-                    .with_context("path", manifest_path.display().to_string()),
-            )
+        self.write_entry_atomically(manifest, plugin_bytes, &entry)?;
+        Ok(entry)
+    }
+
+    /// Writes a complete entry into `.install-<name>/`, marks it complete,
+    /// and renames it over the live directory. Archived `versions/` move
+    /// with the entry. A crash leaves either the previous entry or a staging
+    /// directory that [`repair`](Self::repair) removes, never a live entry
+    /// with missing files.
+    fn write_entry_atomically(
+        &self,
+        manifest: &PluginManifest,
+        plugin_bytes: Option<&[u8]>,
+        entry: &RegistryEntry,
+    ) -> Result<(), AsmError> {
+        let name = &manifest.name;
+        let staging = self.root.join(format!("{STAGING_PREFIX}{name}"));
+        if staging.exists() {
+            fs::remove_dir_all(&staging).map_err(|err| io_error(err, &staging))?;
+        }
+        fs::create_dir_all(&staging).map_err(|err| io_error(err, &staging))?;
+        let manifest_toml = toml::to_string_pretty(manifest).map_err(|err| {
+            AsmError::Serde(ErrorInfo::new(
+                "asm_host.manifest_serialize",
+                err.to_string(),
+            ))
         })?;
+        write_file(&staging.join("manifest.toml"), manifest_toml.as_bytes())?;
         if let Some(bytes) = plugin_bytes {
-            let bin_path = dir.join("plugin.bin");
-            fs::write(&bin_path, bytes).map_err(|err| {
-                AsmError::Serde(
-                    ErrorInfo::new("asm_host.registry_io", err.to_string())
-                        .with_context("path", bin_path.display().to_string()),
-                )
-            })?;
+            write_file(&staging.join("plugin.bin"), bytes)?;
         }
-        let metadata_path = dir.join("metadata.json");
-        let entry_bytes = to_canonical_json_bytes(&entry)?;
-        fs::write(&metadata_path, entry_bytes).map_err(|err| {
-            AsmError::Serde(
-                ErrorInfo::new("asm_host.registry_io", err.to_string())
-                    .with_context("path", metadata_path.display().to_string()),
-            )
+        write_file(
+            &staging.join("metadata.json"),
+            &to_canonical_json_bytes(entry)?,
+        )?;
+        write_file(&staging.join(COMPLETE_MARKER), b"")?;
+
+        let dir = self.entry_dir(name);
+        if !dir.exists() {
+            return rename(&staging, &dir);
+        }
+        let versions = dir.join("versions");
+        if versions.exists() {
+            rename(&versions, &staging.join("versions"))?;
+        }
+        let retired = self.root.join(format!("{RETIRED_PREFIX}{name}"));
+        if retired.exists() {
+            fs::remove_dir_all(&retired).map_err(|err| io_error(err, &retired))?;
+        }
+        rename(&dir, &retired)?;
+        rename(&staging, &dir)?;
+        fs::remove_dir_all(&retired).map_err(|err| io_error(err, &retired))
+    }
+
+    /// Removes half-written state left by an interrupted install: staging
+    /// directories, entries lacking the completion marker, and leftover
+    /// `.staging/` files from reloads. An entry retired by an install that
+    /// crashed before its replacement landed is restored. Returns the
+    /// affected paths relative to the registry root, sorted.
+    pub fn repair(&self) -> Result<Vec<String>, AsmError> {
+        let mut repaired = Vec::new();
+        if !self.root.exists() {
+            return Ok(repaired);
+        }
+        for dir_entry in fs::read_dir(&self.root).map_err(|err| io_error(err, &self.root))? {
+            let path = dir_entry.map_err(|err| io_error(err, &self.root))?.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            if let Some(name) = file_name.strip_prefix(RETIRED_PREFIX) {
+                let live = self.entry_dir(name);
+                if !is_complete(&live) && is_complete(&path) {
+                    if live.exists() {
+                        fs::remove_dir_all(&live).map_err(|err| io_error(err, &live))?;
+                    }
+                    rename(&path, &live)?;
+                } else {
+                    fs::remove_dir_all(&path).map_err(|err| io_error(err, &path))?;
+                }
+                repaired.push(file_name);
+            } else if file_name.starts_with(STAGING_PREFIX)
+                || (!file_name.starts_with('.') && !is_complete(&path))
+            {
+                fs::remove_dir_all(&path).map_err(|err| io_error(err, &path))?;
+                repaired.push(file_name);
+            } else if path.join(".staging").exists() {
+                let staging = path.join(".staging");
+                fs::remove_dir_all(&staging).map_err(|err| io_error(err, &staging))?;
+                repaired.push(format!("{file_name}/.staging"));
+            }
+        }
+        repaired.sort();
+        Ok(repaired)
+    }
+
+    /// Re-reads the manifest and binary from the recorded source and swaps
+    /// them in, archiving the previous version under `versions/<hash>/`.
+    pub fn reload(&self, name: &str) -> Result<ReloadOutcome, AsmError> {
+        let current = self.installed_entry(name)?;
+        let source = current.source.clone().ok_or_else(|| {
+            AsmError::Serde(ErrorInfo::new(
+                "asm_host.registry_source",
+                format!("plugin {name} was not installed from a path"),
+            ))
         })?;
+        let (_, manifest, plugin_bytes) = read_source(Path::new(&source))?;
+        if manifest.name != name {
+            return Err(AsmError::Serde(
+                ErrorInfo::new(
+                    "asm_host.registry_reload_name",
+                    "reloaded manifest declares a different plugin name",
+                )
+                .with_context("expected", name.to_string())
+                .with_context("actual", manifest.name.clone()),
+            ));
+        }
+        manifest.validate()?;
+        verify_manifest_abi(&manifest)?;
+        let signature = self.check_signature(&manifest, plugin_bytes.as_deref())?;
+        let tolerated = &current.metadata.unresolved_dependencies;
+        if let Some((dep, installed)) = self
+            .unresolved_dependencies(&manifest)?
+            .into_iter()
+            .find(|(dep, _)| !tolerated.contains(dep))
+        {
+            return Err(dependency_error(name, &dep, installed.as_deref()));
+        }
+
+        let mut metadata =
+            PluginMetadata::from_manifest(&manifest, compute_manifest_hash(&manifest)?);
+        metadata.unresolved_dependencies = tolerated.clone();
+        let entry = RegistryEntry {
+            metadata,
+            plugin_hash: plugin_bytes.as_deref().map(compute_plugin_hash),
+            source: Some(source),
+            signature,
+        };
+        let changed = entry.plugin_hash != current.plugin_hash;
+        let archived = if entry != current {
+            let archived = self.archive_current(name, &current)?;
+            self.commit_version(name, &manifest, plugin_bytes.as_deref(), &entry)?;
+            self.grants.invalidate(name);
+            Some(archived)
+        } else {
+            None
+        };
+        Ok(ReloadOutcome {
+            entry,
+            previous_hash: current.plugin_hash,
+            changed,
+            archived,
+        })
+    }
+
+    /// Restores the version archived under `versions/<hash>/`, archiving the
+    /// current install first so the swap can be undone.
+    pub fn rollback(&self, name: &str, hash: &str) -> Result<RegistryEntry, AsmError> {
+        let current = self.installed_entry(name)?;
+        let version_dir = self.entry_dir(name).join("versions").join(hash);
+        if !hash.chars().all(|c| c.is_ascii_hexdigit())
+            || !version_dir.join("metadata.json").exists()
+        {
+            return Err(AsmError::Serde(
+                ErrorInfo::new("asm_host.registry_version_missing", "no archived version")
+                    .with_context("plugin", name.to_string())
+                    .with_context("hash", hash.to_string()),
+            ));
+        }
+        let entry: RegistryEntry =
+            crate::serde::from_json_slice(&read_file(&version_dir.join("metadata.json"))?)?;
+        let manifest = load_plugin_manifest(&version_dir.join("manifest.toml"))?;
+        let plugin_bytes = match entry.plugin_hash {
+            Some(_) => Some(read_file(&version_dir.join("plugin.bin"))?),
+            None => None,
+        };
+        if current.version_hash() != hash {
+            self.archive_current(name, &current)?;
+        }
+        self.commit_version(name, &manifest, plugin_bytes.as_deref(), &entry)?;
+        self.grants.invalidate(name);
         Ok(entry)
     }
 
+    /// Lists the version hashes archived for `name`.
+    pub fn versions(&self, name: &str) -> Result<Vec<String>, AsmError> {
+        let dir = self.entry_dir(name).join("versions");
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut hashes = Vec::new();
+        for entry in fs::read_dir(&dir).map_err(|err| io_error(err, &dir))? {
+            let entry = entry.map_err(|err| io_error(err, &dir))?;
+            hashes.push(entry.file_name().to_string_lossy().into_owned());
+        }
+        hashes.sort();
+        Ok(hashes)
+    }
+
+    fn installed_entry(&self, name: &str) -> Result<RegistryEntry, AsmError> {
+        if !is_complete(&self.entry_dir(name)) {
+            return Err(AsmError::Serde(ErrorInfo::new(
+                "asm_host.registry_missing",
+                format!("plugin {name} not installed"),
+            )));
+        }
+        self.read_entry(name)
+    }
+
+    /// Copies the stored files of `current` into `versions/<hash>/`.
+    fn archive_current(&self, name: &str, current: &RegistryEntry) -> Result<String, AsmError> {
+        let dir = self.entry_dir(name);
+        let hash = current.version_hash().to_string();
+        let archive = dir.join("versions").join(&hash);
+        fs::create_dir_all(&archive).map_err(|err| io_error(err, &archive))?;
+        for file in ["manifest.toml", "plugin.bin", "metadata.json"] {
+            let path = dir.join(file);
+            if path.exists() {
+                write_file(&archive.join(file), &read_file(&path)?)?;
+            }
+        }
+        Ok(hash)
+    }
+
+    /// Stages the new files beside the live ones and renames them into
+    /// place, replacing `metadata.json` last so readers never observe a
+    /// metadata record that disagrees with the stored files.
+    fn commit_version(
+        &self,
+        name: &str,
+        manifest: &PluginManifest,
+        plugin_bytes: Option<&[u8]>,
+        entry: &RegistryEntry,
+    ) -> Result<(), AsmError> {
+        let dir = self.entry_dir(name);
+        let staging = dir.join(".staging");
+        fs::create_dir_all(&staging).map_err(|err| io_error(err, &staging))?;
+        let manifest_toml = toml::to_string_pretty(manifest).map_err(|err| {
+            AsmError::Serde(ErrorInfo::new(
+                "asm_host.manifest_serialize",
+                err.to_string(),
+            ))
+        })?;
+        write_file(&staging.join("manifest.toml"), manifest_toml.as_bytes())?;
+        if let Some(bytes) = plugin_bytes {
+            write_file(&staging.join("plugin.bin"), bytes)?;
+        }
+        write_file(
+            &staging.join("metadata.json"),
+            &to_canonical_json_bytes(entry)?,
+        )?;
+
+        rename(&staging.join("manifest.toml"), &dir.join("manifest.toml"))?;
+        let bin_path = dir.join("plugin.bin");
+        if plugin_bytes.is_some() {
+            rename(&staging.join("plugin.bin"), &bin_path)?;
+        } else if bin_path.exists() {
+            fs::remove_file(&bin_path).map_err(|err| io_error(err, &bin_path))?;
+        }
+        rename(&staging.join("metadata.json"), &dir.join("metadata.json"))?;
+        fs::remove_dir(&staging).map_err(|err| io_error(err, &staging))
+    }
+
     pub fn remove(&self, name: &str) -> Result<(), AsmError> {
+        self.grants.invalidate(name);
         let dir = self.entry_dir(name);
         if dir.exists() {
             fs::remove_dir_all(&dir).map_err(|err| {
@@ -110,10 +477,11 @@ impl PluginRegistry {
             let entry = entry.map_err(|err| {
                 AsmError::Serde(ErrorInfo::new("asm_host.registry_io", err.to_string()))
             })?;
-            let metadata_path = entry.path().join("metadata.json");
-            if !metadata_path.exists() {
+            let path = entry.path();
+            if entry.file_name().to_string_lossy().starts_with('.') || !is_complete(&path) {
                 continue;
             }
+            let metadata_path = path.join("metadata.json");
             let bytes = fs::read(&metadata_path).map_err(|err| {
                 AsmError::Serde(
                     ErrorInfo::new("asm_host.registry_io", err.to_string())
@@ -130,6 +498,15 @@ impl PluginRegistry {
     pub fn verify(&self, name: &str) -> Result<RegistryEntry, AsmError> {
         let dir = self.entry_dir(name);
         let metadata_path = dir.join("metadata.json");
+        if dir.exists() && !is_complete(&dir) {
+            return Err(AsmError::Serde(
+                ErrorInfo::new(
+                    "asm_host.registry_incomplete",
+                    format!("plugin {name} was not fully installed; run repair"),
+                )
+                .with_context("path", dir.display().to_string()),
+            ));
+        }
         if !metadata_path.exists() {
             return Err(AsmError::Serde(ErrorInfo::new(
                 "asm_host.registry_missing",
@@ -143,15 +520,16 @@ impl PluginRegistry {
             )
         })?;
         let mut entry: RegistryEntry = crate::serde::from_json_slice(&bytes)?;
+        let mut plugin_bytes = None;
         if let Some(ref hash) = entry.plugin_hash {
             let plugin_path = dir.join("plugin.bin");
-            let plugin_bytes = fs::read(&plugin_path).map_err(|err| {
+            let bytes = fs::read(&plugin_path).map_err(|err| {
                 AsmError::Serde(
                     ErrorInfo::new("asm_host.registry_io", err.to_string())
                         .with_context("path", plugin_path.display().to_string()),
                 )
             })?;
-            let actual = compute_plugin_hash(&plugin_bytes);
+            let actual = compute_plugin_hash(&bytes);
             if actual != *hash {
                 return Err(AsmError::Serde(
                     ErrorInfo::new("asm_host.registry_hash", "plugin hash mismatch")
@@ -159,6 +537,7 @@ impl PluginRegistry {
                         .with_context("actual", actual),
                 ));
             }
+            plugin_bytes = Some(bytes);
         }
         let manifest_path = dir.join("manifest.toml");
         let manifest = crate::loader::load_plugin_manifest(&manifest_path)?;
@@ -170,7 +549,207 @@ impl PluginRegistry {
                     .with_context("actual", manifest_hash),
             ));
         }
+        entry.signature = self.check_signature(&manifest, plugin_bytes.as_deref())?;
+        let unresolved = std::mem::take(&mut entry.metadata.unresolved_dependencies);
         entry.metadata = PluginMetadata::from_manifest(&manifest, manifest_hash);
+        entry.metadata.unresolved_dependencies = unresolved;
+        self.verify_dependency_closure(&manifest, &entry.metadata.unresolved_dependencies)?;
         Ok(entry)
     }
+
+    /// Orders `names` and their transitive dependencies so every plugin
+    /// follows the plugins it depends on.
+    pub fn install_order(&self, names: &[&str]) -> Result<Vec<String>, AsmError> {
+        self.dependency_order(names, false)
+    }
+
+    /// Like [`install_order`](Self::install_order), but also requires every
+    /// dependency on the way to be installed at a version matching its
+    /// requirement, so the returned order can be loaded as is. Dependencies
+    /// tolerated by a forced install are reported here.
+    pub fn resolve_load_order(&self, names: &[&str]) -> Result<Vec<String>, AsmError> {
+        self.dependency_order(names, true)
+    }
+
+    fn dependency_order(
+        &self,
+        names: &[&str],
+        check_versions: bool,
+    ) -> Result<Vec<String>, AsmError> {
+        let mut order = Vec::new();
+        let mut done = BTreeSet::new();
+        let mut path = Vec::new();
+        for name in names {
+            self.visit_order(name, None, check_versions, &mut path, &mut done, &mut order)?;
+        }
+        Ok(order)
+    }
+
+    fn visit_order(
+        &self,
+        name: &str,
+        required_by: Option<&str>,
+        check_versions: bool,
+        path: &mut Vec<String>,
+        done: &mut BTreeSet<String>,
+        order: &mut Vec<String>,
+    ) -> Result<(), AsmError> {
+        if done.contains(name) {
+            return Ok(());
+        }
+        if let Some(start) = path.iter().position(|entry| entry == name) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push(name.to_string());
+            return Err(AsmError::Serde(
+                ErrorInfo::new(
+                    "asm_host.registry_dependency_cycle",
+                    "plugin dependencies form a cycle",
+                )
+                .with_context("cycle", cycle.join(" -> ")),
+            ));
+        }
+        let manifest = self.installed_manifest(name)?.ok_or_else(|| {
+            let info = ErrorInfo::new(
+                "asm_host.registry_missing",
+                format!("plugin {name} not installed"),
+            );
+            AsmError::Serde(match required_by {
+                Some(parent) => info.with_context("required_by", parent.to_string()),
+                None => info,
+            })
+        })?;
+        if check_versions {
+            if let Some((dep, installed)) = self.unresolved_dependencies(&manifest)?.first() {
+                return Err(dependency_error(name, dep, installed.as_deref()));
+            }
+        }
+        path.push(name.to_string());
+        for dep in &manifest.dependencies {
+            self.visit_order(&dep.name, Some(name), check_versions, path, done, order)?;
+        }
+        path.pop();
+        done.insert(name.to_string());
+        order.push(name.to_string());
+        Ok(())
+    }
+
+    fn installed_manifest(&self, name: &str) -> Result<Option<PluginManifest>, AsmError> {
+        let dir = self.entry_dir(name);
+        if !is_complete(&dir) {
+            return Ok(None);
+        }
+        crate::loader::load_plugin_manifest(&dir.join("manifest.toml")).map(Some)
+    }
+
+    /// Returns the direct dependencies of `manifest` that are not installed at
+    /// a compatible version, paired with the installed version if any.
+    fn unresolved_dependencies(
+        &self,
+        manifest: &PluginManifest,
+    ) -> Result<Vec<(PluginDependency, Option<String>)>, AsmError> {
+        let mut unresolved = Vec::new();
+        for dep in &manifest.dependencies {
+            let req = VersionReq::parse(&dep.version_req)?;
+            let installed = self.installed_manifest(&dep.name)?.map(|m| m.version);
+            let compatible = installed
+                .as_deref()
+                .and_then(|version| Version::parse(version).ok())
+                .is_some_and(|version| req.matches(&version));
+            if !compatible {
+                unresolved.push((dep.clone(), installed));
+            }
+        }
+        Ok(unresolved)
+    }
+
+    /// Re-checks the dependencies of `manifest` and of every installed plugin
+    /// it transitively depends on. Dependencies recorded as unresolved at a
+    /// forced install are tolerated; any other gap is an error.
+    fn verify_dependency_closure(
+        &self,
+        manifest: &PluginManifest,
+        tolerated: &[PluginDependency],
+    ) -> Result<(), AsmError> {
+        let mut visited = BTreeSet::from([manifest.name.clone()]);
+        let mut pending = vec![(manifest.clone(), tolerated.to_vec())];
+        while let Some((current, tolerated)) = pending.pop() {
+            for (dep, installed) in self.unresolved_dependencies(&current)? {
+                if !tolerated.contains(&dep) {
+                    return Err(dependency_error(&current.name, &dep, installed.as_deref()));
+                }
+            }
+            for dep in &current.dependencies {
+                if !visited.insert(dep.name.clone()) {
+                    continue;
+                }
+                if let Some(child) = self.installed_manifest(&dep.name)? {
+                    let entry = self.read_entry(&dep.name)?;
+                    pending.push((child, entry.metadata.unresolved_dependencies));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn read_entry(&self, name: &str) -> Result<RegistryEntry, AsmError> {
+        let metadata_path = self.entry_dir(name).join("metadata.json");
+        let bytes = fs::read(&metadata_path).map_err(|err| {
+            AsmError::Serde(
+                ErrorInfo::new("asm_host.registry_io", err.to_string())
+                    .with_context("path", metadata_path.display().to_string()),
+            )
+        })?;
+        crate::serde::from_json_slice(&bytes)
+    }
+}
+
+/// Reads a manifest path (or a directory holding `plugin.toml`) and the
+/// optional sibling `plugin.bin`.
+fn read_source(path: &Path) -> Result<(PathBuf, PluginManifest, Option<Vec<u8>>), AsmError> {
+    let manifest_path = if path.is_dir() {
+        path.join("plugin.toml")
+    } else {
+        path.to_path_buf()
+    };
+    let manifest = load_plugin_manifest(&manifest_path)?;
+    let binary_path = manifest_path.with_file_name("plugin.bin");
+    let plugin_bytes = if binary_path.exists() {
+        Some(read_file(&binary_path)?)
+    } else {
+        None
+    };
+    Ok((manifest_path, manifest, plugin_bytes))
+}
+
+fn io_error(err: std::io::Error, path: &Path) -> AsmError {
+    AsmError::Serde(
+        ErrorInfo::new("asm_host.registry_io", err.to_string())
+            .with_context("path", path.display().to_string()),
+    )
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, AsmError> {
+    fs::read(path).map_err(|err| io_error(err, path))
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> Result<(), AsmError> {
+    fs::write(path, bytes).map_err(|err| io_error(err, path))
+}
+
+fn rename(from: &Path, to: &Path) -> Result<(), AsmError> {
+    fs::rename(from, to).map_err(|err| io_error(err, to))
+}
+
+fn dependency_error(plugin: &str, dep: &PluginDependency, installed: Option<&str>) -> AsmError {
+    let message = match installed {
+        Some(_) => "dependency installed at an incompatible version",
+        None => "dependency not installed",
+    };
+    AsmError::Serde(
+        ErrorInfo::new("asm_host.registry_dependency", message)
+            .with_context("plugin", plugin.to_string())
+            .with_context("dependency", dep.name.clone())
+            .with_context("requirement", dep.version_req.clone())
+            .with_context("installed", installed.unwrap_or("missing").to_string()),
+    )
 }
@@ -1,8 +1,12 @@
+use std::collections::BTreeMap;
+use std::sync::{Mutex, PoisonError};
 use std::time::{Duration, Instant};
 
 use asm_core::errors::{AsmError, ErrorInfo};
 use serde::{Deserialize, Serialize};
 
+use crate::abi::{AsmPluginVTable, AsmStatus, Capability, OutCallback};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SandboxCaps {
     pub cpu_time_seconds: u64,
@@ -22,12 +26,57 @@ impl SandboxCaps {
     }
 }
 
+/// Category of a call crossing the plugin boundary, each gated by one
+/// capability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AbiCall {
+    GraphGenerate,
+    CodeGenerate,
+    Spectrum,
+    Gauge,
+    Interact,
+    /// Host file system service requested by the plugin.
+    Filesystem,
+}
+
+impl AbiCall {
+    pub fn required_capability(self) -> Capability {
+        match self {
+            AbiCall::GraphGenerate => Capability::Graph,
+            AbiCall::CodeGenerate => Capability::Code,
+            AbiCall::Spectrum => Capability::Spectrum,
+            AbiCall::Gauge => Capability::Gauge,
+            AbiCall::Interact => Capability::Interact,
+            AbiCall::Filesystem => Capability::Filesystem,
+        }
+    }
+
+    fn entry(self, vtable: &AsmPluginVTable) -> Option<StageEntry> {
+        match self {
+            AbiCall::GraphGenerate => vtable.graph_generate,
+            AbiCall::CodeGenerate => vtable.code_generate,
+            AbiCall::Spectrum => vtable.spectrum,
+            AbiCall::Gauge => vtable.gauge,
+            AbiCall::Interact => vtable.interact,
+            AbiCall::Filesystem => None,
+        }
+    }
+}
+
+type StageEntry = extern "C" fn(*const u8, usize, OutCallback) -> AsmStatus;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum SandboxEvent {
     CpuSeconds(u64),
     MemoryMb(u64),
     TmpUsageMb(u64),
     WallSeconds(u64),
+    /// A call that needs a capability outside the declared mask.
+    CapabilityDenied {
+        call: AbiCall,
+        declared: u32,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -38,60 +87,132 @@ pub enum SandboxDecision {
         limit: u64,
         observed: u64,
     },
+    Denied {
+        call: AbiCall,
+        capability: Capability,
+    },
 }
 
 #[derive(Debug)]
 pub struct SandboxGuard {
     caps: SandboxCaps,
+    declared: u32,
     start: Instant,
     last_decision: SandboxDecision,
+    events: Vec<SandboxEvent>,
 }
 
 impl SandboxGuard {
+    /// Creates a guard enforcing `caps`. No ABI calls are permitted until
+    /// capabilities are declared with [`SandboxGuard::with_capabilities`].
     pub fn new(caps: SandboxCaps) -> Self {
         Self {
             caps,
+            declared: 0,
             start: Instant::now(),
             last_decision: SandboxDecision::Continue,
+            events: Vec::new(),
         }
     }
 
+    /// Permits calls covered by `mask`, typically the plugin manifest's
+    /// `capability_flags()`.
+    pub fn with_capabilities(mut self, mask: u32) -> Self {
+        self.declared = mask;
+        self
+    }
+
     pub fn caps(&self) -> SandboxCaps {
         self.caps
     }
 
+    pub fn declared_capabilities(&self) -> u32 {
+        self.declared
+    }
+
+    /// Events that produced a violation, in the order they were observed.
+    pub fn events(&self) -> &[SandboxEvent] {
+        &self.events
+    }
+
+    /// Checks `call` against the declared capabilities.
+    ///
+    /// Hosts call this before servicing any request from the plugin. A denied
+    /// call is recorded as [`SandboxEvent::CapabilityDenied`] and yields
+    /// [`AsmStatus::DENIED`].
+    pub fn check_call(&mut self, call: AbiCall) -> AsmStatus {
+        let event = SandboxEvent::CapabilityDenied {
+            call,
+            declared: self.declared,
+        };
+        match self.observe(event) {
+            SandboxDecision::Continue => AsmStatus::OK,
+            _ => AsmStatus::DENIED,
+        }
+    }
+
+    /// Invokes the vtable entry for `call` if the plugin declared the
+    /// matching capability. Denied calls never reach the plugin.
+    pub fn invoke(
+        &mut self,
+        vtable: &AsmPluginVTable,
+        call: AbiCall,
+        input: &[u8],
+        out: OutCallback,
+    ) -> AsmStatus {
+        let status = self.check_call(call);
+        if !status.is_ok() {
+            return status;
+        }
+        match call.entry(vtable) {
+            Some(entry) => entry(input.as_ptr(), input.len(), out),
+            None => AsmStatus::UNSUPPORTED,
+        }
+    }
+
     pub fn observe(&mut self, event: SandboxEvent) -> SandboxDecision {
-        let decision = match event {
-            SandboxEvent::CpuSeconds(value) if value > self.caps.cpu_time_seconds => {
+        let decision = match &event {
+            SandboxEvent::CpuSeconds(value) if *value > self.caps.cpu_time_seconds => {
                 SandboxDecision::Exceeded {
                     resource: "cpu",
                     limit: self.caps.cpu_time_seconds,
-                    observed: value,
+                    observed: *value,
                 }
             }
-            SandboxEvent::MemoryMb(value) if value > self.caps.max_rss_mb => {
+            SandboxEvent::MemoryMb(value) if *value > self.caps.max_rss_mb => {
                 SandboxDecision::Exceeded {
                     resource: "memory",
                     limit: self.caps.max_rss_mb,
-                    observed: value,
+                    observed: *value,
                 }
             }
-            SandboxEvent::TmpUsageMb(value) if value > self.caps.tmpdir_mb => {
+            SandboxEvent::TmpUsageMb(value) if *value > self.caps.tmpdir_mb => {
                 SandboxDecision::Exceeded {
                     resource: "tmp",
                     limit: self.caps.tmpdir_mb,
-                    observed: value,
+                    observed: *value,
                 }
             }
-            SandboxEvent::WallSeconds(value) if value > self.caps.wall_seconds => {
+            SandboxEvent::WallSeconds(value) if *value > self.caps.wall_seconds => {
                 SandboxDecision::Exceeded {
                     resource: "wall",
                     limit: self.caps.wall_seconds,
-                    observed: value,
+                    observed: *value,
+                }
+            }
+            SandboxEvent::CapabilityDenied { call, declared }
+                if declared & call.required_capability().flag() == 0 =>
+            {
+                SandboxDecision::Denied {
+                    call: *call,
+                    capability: call.required_capability(),
                 }
             }
             _ => SandboxDecision::Continue,
         };
+        if decision != SandboxDecision::Continue {
+            self.events.push(event);
+        }
         self.last_decision = decision.clone();
         decision
     }
@@ -111,6 +232,16 @@ impl SandboxGuard {
                 "asm_host.sandbox_limit",
                 format!("sandbox exceeded {resource} limit {limit} with observed {observed}"),
             ))),
+            SandboxDecision::Denied { call, capability } => Err(AsmError::Rng(
+                ErrorInfo::new(
+                    "asm_host.capability_denied",
+                    format!(
+                        "plugin called {call:?} without declaring the {} capability",
+                        capability.name()
+                    ),
+                )
+                .with_context("capability", capability.name()),
+            )),
         }
     }
 
@@ -118,3 +249,40 @@ impl SandboxGuard {
         self.start.elapsed()
     }
 }
+
+/// Capability masks granted to loaded plugins, keyed by plugin name.
+///
+/// Grants describe a specific plugin build, so the registry drops them
+/// whenever the stored plugin is reloaded, rolled back, or removed.
+#[derive(Debug, Default)]
+pub struct CapabilityGrants {
+    grants: Mutex<BTreeMap<String, u32>>,
+}
+
+impl CapabilityGrants {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn grant(&self, plugin: &str, mask: u32) {
+        self.lock().insert(plugin.to_string(), mask);
+    }
+
+    pub fn granted(&self, plugin: &str) -> Option<u32> {
+        self.lock().get(plugin).copied()
+    }
+
+    pub fn allows(&self, plugin: &str, capability: Capability) -> bool {
+        self.granted(plugin)
+            .is_some_and(|mask| mask & capability.flag() != 0)
+    }
+
+    /// Drops the cached grant for `plugin`, returning whether one existed.
+    pub fn invalidate(&self, plugin: &str) -> bool {
+        self.lock().remove(plugin).is_some()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, u32>> {
+        self.grants.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
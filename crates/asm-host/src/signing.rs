@@ -0,0 +1,138 @@
+use std::collections::BTreeSet;
+
+use asm_core::errors::{AsmError, ErrorInfo};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::hash::compute_plugin_hash;
+use crate::manifest::PluginManifest;
+
+/// Domain separator prefixed to every signed message.
+const SIGNING_DOMAIN: &str = "asm-plugin-signature-v1";
+
+/// Authenticity of an installed plugin, recorded in its registry entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureStatus {
+    /// The manifest carries no signature; only content hashes are checked.
+    #[default]
+    Unsigned,
+    /// The binary verified against the manifest's ed25519 public key, which
+    /// is one of the registry's trusted keys.
+    Verified { public_key: String },
+    /// The binary verified against the manifest's key, but that key is not
+    /// trusted, so the signature says nothing about who built the plugin.
+    UntrustedKey { public_key: String },
+}
+
+impl SignatureStatus {
+    pub fn is_signed(&self) -> bool {
+        matches!(self, SignatureStatus::Verified { .. })
+    }
+}
+
+fn signature_error(code: &str, manifest: &PluginManifest, message: &str) -> AsmError {
+    AsmError::Serde(ErrorInfo::new(code, message).with_context("plugin", manifest.name.clone()))
+}
+
+/// Bytes covered by a signature: the plugin's name, version, and binary hash.
+fn signed_message(manifest: &PluginManifest, plugin_bytes: &[u8]) -> Vec<u8> {
+    format!(
+        "{SIGNING_DOMAIN}\n{}\n{}\n{}",
+        manifest.name,
+        manifest.version,
+        compute_plugin_hash(plugin_bytes)
+    )
+    .into_bytes()
+}
+
+fn decode_hex<const N: usize>(
+    manifest: &PluginManifest,
+    field: &str,
+    value: &str,
+) -> Result<[u8; N], AsmError> {
+    hex::decode(value)
+        .ok()
+        .and_then(|bytes| <[u8; N]>::try_from(bytes).ok())
+        .ok_or_else(|| {
+            AsmError::Serde(
+                ErrorInfo::new(
+                    "asm_host.signature_encoding",
+                    format!("{field} must be {N} hex-encoded bytes"),
+                )
+                .with_context("plugin", manifest.name.clone())
+                .with_context("field", field.to_string()),
+            )
+        })
+}
+
+/// Returns a copy of `manifest` signed over `plugin_bytes` with the ed25519
+/// key derived from `secret_key`, filling in `signature` and `public_key`.
+pub fn sign_plugin(
+    manifest: &PluginManifest,
+    plugin_bytes: &[u8],
+    secret_key: &[u8; 32],
+) -> PluginManifest {
+    let key = SigningKey::from_bytes(secret_key);
+    let signature = key.sign(&signed_message(manifest, plugin_bytes));
+    PluginManifest {
+        signature: Some(hex::encode(signature.to_bytes())),
+        public_key: Some(hex::encode(key.verifying_key().to_bytes())),
+        ..manifest.clone()
+    }
+}
+
+/// Checks the manifest's signature, if any, against `plugin_bytes`.
+///
+/// A signed manifest must come with a binary whose bytes match the signature;
+/// otherwise the plugin is rejected with `asm_host.signature_invalid`. A valid
+/// signature is [`SignatureStatus::Verified`] only when its hex-encoded public
+/// key is in `trusted_keys`, and [`SignatureStatus::UntrustedKey`] otherwise.
+pub fn verify_plugin_signature(
+    manifest: &PluginManifest,
+    plugin_bytes: Option<&[u8]>,
+    trusted_keys: &BTreeSet<String>,
+) -> Result<SignatureStatus, AsmError> {
+    let (signature, public_key) = match (&manifest.signature, &manifest.public_key) {
+        (None, None) => return Ok(SignatureStatus::Unsigned),
+        (Some(signature), Some(public_key)) => (signature, public_key),
+        _ => {
+            return Err(signature_error(
+                "asm_host.signature_incomplete",
+                manifest,
+                "signature and public_key must be declared together",
+            ))
+        }
+    };
+    let Some(plugin_bytes) = plugin_bytes else {
+        return Err(signature_error(
+            "asm_host.signature_binary",
+            manifest,
+            "signed manifest installed without a plugin binary",
+        ));
+    };
+    let key = VerifyingKey::from_bytes(&decode_hex(manifest, "public_key", public_key)?).map_err(
+        |_| {
+            signature_error(
+                "asm_host.signature_encoding",
+                manifest,
+                "public_key is not a valid ed25519 key",
+            )
+        },
+    )?;
+    let signature = Signature::from_bytes(&decode_hex(manifest, "signature", signature)?);
+    key.verify(&signed_message(manifest, plugin_bytes), &signature)
+        .map_err(|_| {
+            signature_error(
+                "asm_host.signature_invalid",
+                manifest,
+                "plugin binary does not match its signature",
+            )
+        })?;
+    let public_key = public_key.to_ascii_lowercase();
+    if trusted_keys.contains(&public_key) {
+        Ok(SignatureStatus::Verified { public_key })
+    } else {
+        Ok(SignatureStatus::UntrustedKey { public_key })
+    }
+}
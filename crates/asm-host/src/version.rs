@@ -0,0 +1,201 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use asm_core::errors::{AsmError, ErrorInfo};
+
+/// Numeric `MAJOR.MINOR.PATCH` plugin version. Pre-release and build
+/// suffixes are accepted but ignored when comparing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    pub fn parse(input: &str) -> Result<Self, AsmError> {
+        let partial = PartialVersion::parse(input)?;
+        match (partial.minor, partial.patch) {
+            (Some(minor), Some(patch)) => Ok(Self {
+                major: partial.major,
+                minor,
+                patch,
+            }),
+            _ => Err(version_error(input, "version must be MAJOR.MINOR.PATCH")),
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Cargo-style version requirement: comma separated comparators using `=`,
+/// `>`, `>=`, `<`, `<=`, `^`, `~`, or `*`. A bare version is a caret requirement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq {
+    comparators: Vec<Comparator>,
+}
+
+impl VersionReq {
+    pub fn parse(input: &str) -> Result<Self, AsmError> {
+        let trimmed = input.trim();
+        if trimmed.is_empty() {
+            return Err(version_error(input, "empty version requirement"));
+        }
+        if trimmed == "*" {
+            return Ok(Self {
+                comparators: Vec::new(),
+            });
+        }
+        let comparators = trimmed
+            .split(',')
+            .map(|part| Comparator::parse(part.trim(), input))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { comparators })
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        self.comparators.iter().all(|cmp| cmp.matches(version))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Caret,
+    Tilde,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PartialVersion {
+    major: u64,
+    minor: Option<u64>,
+    patch: Option<u64>,
+}
+
+impl PartialVersion {
+    fn parse(input: &str) -> Result<Self, AsmError> {
+        let core = input.trim().split(['-', '+']).next().unwrap_or_default();
+        let mut parts = core.split('.');
+        let mut next = |required: bool| -> Result<Option<u64>, AsmError> {
+            match parts.next() {
+                None if !required => Ok(None),
+                Some(part) => part
+                    .parse()
+                    .map(Some)
+                    .map_err(|_| version_error(input, "version components must be integers")),
+                None => Err(version_error(input, "missing major version")),
+            }
+        };
+        let major = next(true)?.unwrap_or_default();
+        let minor = next(false)?;
+        let patch = next(false)?;
+        if parts.next().is_some() {
+            return Err(version_error(input, "too many version components"));
+        }
+        Ok(Self {
+            major,
+            minor,
+            patch,
+        })
+    }
+
+    fn floor(&self) -> Version {
+        Version {
+            major: self.major,
+            minor: self.minor.unwrap_or(0),
+            patch: self.patch.unwrap_or(0),
+        }
+    }
+
+    /// Compares `version` against this partial version, treating omitted
+    /// components as wildcards.
+    fn compare(&self, version: &Version) -> Ordering {
+        version
+            .major
+            .cmp(&self.major)
+            .then_with(|| match self.minor {
+                Some(minor) => version.minor.cmp(&minor),
+                None => Ordering::Equal,
+            })
+            .then_with(|| match self.patch {
+                Some(patch) => version.patch.cmp(&patch),
+                None => Ordering::Equal,
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Comparator {
+    op: Op,
+    version: PartialVersion,
+}
+
+impl Comparator {
+    fn parse(part: &str, input: &str) -> Result<Self, AsmError> {
+        let (op, rest) = [
+            (">=", Op::GreaterEq),
+            ("<=", Op::LessEq),
+            (">", Op::Greater),
+            ("<", Op::Less),
+            ("=", Op::Exact),
+            ("^", Op::Caret),
+            ("~", Op::Tilde),
+        ]
+        .into_iter()
+        .find_map(|(prefix, op)| part.strip_prefix(prefix).map(|rest| (op, rest)))
+        .unwrap_or((Op::Caret, part));
+        if rest.trim().is_empty() {
+            return Err(version_error(input, "comparator missing version"));
+        }
+        Ok(Self {
+            op,
+            version: PartialVersion::parse(rest)?,
+        })
+    }
+
+    fn matches(&self, version: &Version) -> bool {
+        let partial = &self.version;
+        match self.op {
+            Op::Exact => partial.compare(version) == Ordering::Equal,
+            Op::Greater => partial.compare(version) == Ordering::Greater,
+            Op::GreaterEq => partial.compare(version) != Ordering::Less,
+            Op::Less => partial.compare(version) == Ordering::Less,
+            Op::LessEq => partial.compare(version) != Ordering::Greater,
+            Op::Tilde => {
+                *version >= partial.floor()
+                    && version.major == partial.major
+                    && partial.minor.map_or(true, |minor| version.minor == minor)
+            }
+            Op::Caret => {
+                if *version < partial.floor() || version.major != partial.major {
+                    return false;
+                }
+                if partial.major > 0 {
+                    return true;
+                }
+                // Below 1.0 the leftmost specified non-zero component is the
+                // compatibility boundary.
+                match (partial.minor, partial.patch) {
+                    (None, _) => true,
+                    (Some(minor), _) if minor > 0 => version.minor == minor,
+                    (Some(_), None) => version.minor == 0,
+                    (Some(_), Some(patch)) => version.minor == 0 && version.patch == patch,
+                }
+            }
+        }
+    }
+}
+
+fn version_error(input: &str, message: &str) -> AsmError {
+    AsmError::Serde(
+        ErrorInfo::new("asm_host.version_parse", message).with_context("input", input.to_string()),
+    )
+}
@@ -1,4 +1,7 @@
-use asm_host::{verify_abi_compat, AbiString, AsmPluginInfo, ASM_ABI_VERSION};
+use asm_host::{
+    abi_version, format_abi, verify_abi_compat, verify_abi_compat_with, AbiString, AsmPluginInfo,
+    ASM_ABI_VERSION,
+};
 
 fn make_info(version: u32) -> AsmPluginInfo {
     AsmPluginInfo {
@@ -12,14 +15,26 @@ fn make_info(version: u32) -> AsmPluginInfo {
             len: 0,
         },
         capabilities: 0,
+        min_abi: 0,
+        max_abi: 0,
     }
 }
 
+fn code(info: &AsmPluginInfo, host: u32) -> String {
+    verify_abi_compat_with(info, host)
+        .expect_err("expected mismatch")
+        .info()
+        .code
+        .to_string()
+}
+
 #[test]
 fn rejects_mismatched_abi() {
     let info = make_info(ASM_ABI_VERSION + 1);
     let err = verify_abi_compat(&info).expect_err("expected mismatch");
-    assert_eq!(err.info().code, "asm_host.abi_mismatch");
+    assert_eq!(err.info().code, "asm_host.abi_too_new");
+    assert_eq!(err.context_value("host_abi"), Some("1.1"));
+    assert_eq!(err.context_value("min_abi"), Some("1.2"));
 }
 
 #[test]
@@ -27,3 +42,92 @@ fn accepts_matching_abi() {
     let info = make_info(ASM_ABI_VERSION);
     verify_abi_compat(&info).expect("compatible ABI");
 }
+
+#[test]
+fn newer_minor_hosts_load_older_plugins() {
+    let info = make_info(abi_version(1, 2));
+    for host in [abi_version(1, 2), abi_version(1, 7)] {
+        verify_abi_compat_with(&info, host).expect("same major, newer host minor");
+    }
+    assert_eq!(code(&info, abi_version(1, 1)), "asm_host.abi_too_new");
+    assert_eq!(
+        code(&info, abi_version(2, 2)),
+        "asm_host.abi_major_mismatch"
+    );
+    assert_eq!(
+        code(&info, abi_version(0, 9)),
+        "asm_host.abi_major_mismatch"
+    );
+}
+
+#[test]
+fn declared_range_bounds_the_host() {
+    let info = AsmPluginInfo {
+        min_abi: abi_version(1, 1),
+        max_abi: abi_version(1, 3),
+        ..make_info(abi_version(1, 2))
+    };
+    verify_abi_compat_with(&info, abi_version(1, 1)).expect("lower bound");
+    verify_abi_compat_with(&info, abi_version(1, 3)).expect("upper bound");
+    assert_eq!(code(&info, abi_version(1, 0)), "asm_host.abi_too_new");
+    assert_eq!(code(&info, abi_version(1, 4)), "asm_host.abi_too_old");
+
+    let empty = AsmPluginInfo {
+        min_abi: abi_version(1, 3),
+        max_abi: abi_version(1, 1),
+        ..info
+    };
+    assert_eq!(code(&empty, abi_version(1, 2)), "asm_host.abi_range");
+}
+
+#[test]
+fn legacy_bare_majors_read_as_minor_zero() {
+    assert_eq!(format_abi(1), "1.0");
+    verify_abi_compat(&make_info(1)).expect("legacy ABI 1 loads on a 1.x host");
+    let info = AsmPluginInfo {
+        max_abi: 1,
+        ..make_info(1)
+    };
+    assert_eq!(code(&info, abi_version(1, 1)), "asm_host.abi_too_old");
+    assert_eq!(
+        code(&make_info(2), ASM_ABI_VERSION),
+        "asm_host.abi_major_mismatch"
+    );
+}
+
+/// Info block exported by plugins built before `min_abi`/`max_abi` existed.
+#[repr(C)]
+struct LegacyInfo {
+    abi_version: u32,
+    name: AbiString,
+    version: AbiString,
+    capabilities: u32,
+}
+
+#[test]
+fn read_skips_range_fields_for_older_layouts() {
+    let empty = AbiString {
+        ptr: std::ptr::null(),
+        len: 0,
+    };
+    let legacy = LegacyInfo {
+        abi_version: 1,
+        name: empty,
+        version: empty,
+        capabilities: 3,
+    };
+    let info = unsafe { AsmPluginInfo::read((&legacy as *const LegacyInfo).cast()) };
+    assert_eq!((info.abi_version, info.capabilities), (1, 3));
+    assert_eq!((info.min_abi, info.max_abi), (0, 0));
+
+    let current = AsmPluginInfo {
+        min_abi: abi_version(1, 1),
+        max_abi: abi_version(1, 4),
+        ..make_info(ASM_ABI_VERSION)
+    };
+    let info = unsafe { AsmPluginInfo::read(&current) };
+    assert_eq!(
+        (info.min_abi, info.max_abi),
+        (current.min_abi, current.max_abi)
+    );
+}
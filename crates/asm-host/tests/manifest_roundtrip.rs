@@ -6,10 +6,15 @@ fn manifest_roundtrip_is_stable() {
         name: "graph_smallworld".into(),
         version: "0.1.0".into(),
         abi_version: asm_host::ASM_ABI_VERSION,
+        min_abi: None,
+        max_abi: None,
         capabilities: vec!["graph".into()],
         minimum_workspace: Some("0.16".into()),
         license: "MIT".into(),
         description: Some("demo plugin".into()),
+        dependencies: Vec::new(),
+        signature: None,
+        public_key: None,
     };
     manifest.validate().expect("valid manifest");
     let toml = toml::to_string(&manifest).expect("serialize");
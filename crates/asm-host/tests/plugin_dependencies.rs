@@ -0,0 +1,155 @@
+use asm_host::{PluginDependency, PluginManifest, PluginRegistry, Version, VersionReq};
+
+fn manifest(name: &str, version: &str, deps: &[(&str, &str)]) -> PluginManifest {
+    PluginManifest {
+        name: name.into(),
+        version: version.into(),
+        abi_version: asm_host::ASM_ABI_VERSION,
+        min_abi: None,
+        max_abi: None,
+        capabilities: vec!["graph".into()],
+        minimum_workspace: None,
+        license: "MIT".into(),
+        description: None,
+        dependencies: deps
+            .iter()
+            .map(|(name, req)| PluginDependency {
+                name: (*name).into(),
+                version_req: (*req).into(),
+            })
+            .collect(),
+        signature: None,
+        public_key: None,
+    }
+}
+
+fn code(err: &asm_core::errors::AsmError) -> String {
+    err.info().code.to_string()
+}
+
+#[test]
+fn version_requirements_follow_cargo_semantics() {
+    let v = |s: &str| Version::parse(s).expect("version");
+    let req = |s: &str| VersionReq::parse(s).expect("requirement");
+    assert!(req("^1.2").matches(&v("1.9.0")));
+    assert!(!req("^1.2").matches(&v("2.0.0")));
+    assert!(req("0.2.3").matches(&v("0.2.9")));
+    assert!(!req("0.2.3").matches(&v("0.3.0")));
+    assert!(req("~1.2.3").matches(&v("1.2.7")));
+    assert!(!req("~1.2.3").matches(&v("1.3.0")));
+    assert!(req(">=1.0, <1.5").matches(&v("1.4.9")));
+    assert!(!req(">=1.0, <1.5").matches(&v("1.5.0")));
+    assert!(req("=0.1").matches(&v("0.1.4")));
+    assert!(req("*").matches(&v("7.0.0")));
+    assert!(VersionReq::parse("^one").is_err());
+    assert!(VersionReq::parse("").is_err());
+
+    let bad = manifest("b", "0.1.0", &[("a", ">=x")]);
+    assert!(bad.validate().is_err());
+    let selfish = manifest("b", "0.1.0", &[("b", "*")]);
+    let err = selfish.validate().unwrap_err();
+    assert_eq!(code(&err), "asm_host.manifest_dependency");
+}
+
+#[test]
+fn dependency_chain_installs_in_order_and_verifies_closure() {
+    let dir = tempfile::tempdir().expect("tmp");
+    let registry = PluginRegistry::new(dir.path());
+    let a = manifest("fmt_a", "1.2.0", &[]);
+    let b = manifest("measure_b", "0.3.1", &[("fmt_a", "^1.1")]);
+    let c = manifest("report_c", "0.1.0", &[("measure_b", "~0.3")]);
+
+    let err = registry.install(&c, None).unwrap_err();
+    assert_eq!(code(&err), "asm_host.registry_dependency");
+    assert_eq!(err.context_value("installed"), Some("missing"));
+
+    registry.install(&a, None).expect("install a");
+    registry.install(&b, None).expect("install b");
+    let entry = registry.install(&c, None).expect("install c");
+    assert!(entry.metadata.unresolved_dependencies.is_empty());
+
+    let order = registry.install_order(&["report_c"]).expect("order");
+    assert_eq!(order, ["fmt_a", "measure_b", "report_c"]);
+    let order = registry
+        .install_order(&["fmt_a", "report_c", "measure_b"])
+        .expect("order");
+    assert_eq!(order, ["fmt_a", "measure_b", "report_c"]);
+    registry.verify("report_c").expect("closure intact");
+
+    // An incompatible upgrade is refused unless forced.
+    let too_new = manifest("fmt_a", "2.0.0", &[]);
+    registry.install(&too_new, None).expect("upgrade a");
+    let err = registry.install(&b, None).unwrap_err();
+    assert_eq!(err.context_value("installed"), Some("2.0.0"));
+    registry.install(&a, None).expect("restore a");
+
+    // Removing a transitive dependency is caught when verifying the top plugin.
+    registry.remove("fmt_a").expect("remove a");
+    let err = registry.verify("report_c").unwrap_err();
+    assert_eq!(code(&err), "asm_host.registry_dependency");
+    assert_eq!(err.context_value("plugin"), Some("measure_b"));
+    let err = registry.install_order(&["report_c"]).unwrap_err();
+    assert_eq!(code(&err), "asm_host.registry_missing");
+}
+
+#[test]
+fn forced_install_records_unresolved_and_cycles_are_reported() {
+    let dir = tempfile::tempdir().expect("tmp");
+    let registry = PluginRegistry::new(dir.path());
+    let x = manifest("cyc_x", "0.1.0", &[("cyc_y", "^0.1")]);
+    let y = manifest("cyc_y", "0.1.0", &[("cyc_x", "^0.1")]);
+
+    assert!(registry.install(&x, None).is_err());
+    let entry = registry.install_with(&x, None, true).expect("forced");
+    assert_eq!(entry.metadata.unresolved_dependencies, x.dependencies);
+    // The recorded gap is tolerated by verify.
+    let verified = registry.verify("cyc_x").expect("verify forced");
+    assert_eq!(verified.metadata.unresolved_dependencies, x.dependencies);
+
+    registry.install(&y, None).expect("install y");
+    let err = registry.install_order(&["cyc_x"]).unwrap_err();
+    assert_eq!(code(&err), "asm_host.registry_dependency_cycle");
+    assert_eq!(err.context_value("cycle"), Some("cyc_x -> cyc_y -> cyc_x"));
+}
+
+#[test]
+fn load_order_requires_compatible_versions() {
+    let dir = tempfile::tempdir().expect("tmp");
+    let registry = PluginRegistry::new(dir.path());
+    let a = manifest("fmt_a", "1.2.0", &[]);
+    let b = manifest("measure_b", "0.3.1", &[("fmt_a", "^1.1")]);
+    let c = manifest("report_c", "0.1.0", &[("measure_b", "~0.3")]);
+    for plugin in [&a, &b, &c] {
+        registry.install(plugin, None).expect("install");
+    }
+    let order = registry
+        .resolve_load_order(&["report_c", "fmt_a"])
+        .expect("load order");
+    assert_eq!(order, ["fmt_a", "measure_b", "report_c"]);
+
+    // Upgrading a dependency past its requirement leaves the install order
+    // intact but makes the set unloadable.
+    registry
+        .install(&manifest("fmt_a", "2.0.0", &[]), None)
+        .expect("upgrade a");
+    assert_eq!(
+        registry.install_order(&["report_c"]).expect("order"),
+        ["fmt_a", "measure_b", "report_c"]
+    );
+    let err = registry.resolve_load_order(&["report_c"]).unwrap_err();
+    assert_eq!(code(&err), "asm_host.registry_dependency");
+    assert_eq!(err.context_value("plugin"), Some("measure_b"));
+    assert_eq!(err.context_value("requirement"), Some("^1.1"));
+    assert_eq!(err.context_value("installed"), Some("2.0.0"));
+
+    registry.remove("fmt_a").expect("remove a");
+    let err = registry.resolve_load_order(&["measure_b"]).unwrap_err();
+    assert_eq!(code(&err), "asm_host.registry_dependency");
+    assert_eq!(err.context_value("installed"), Some("missing"));
+
+    // Gaps tolerated by a forced install still block loading.
+    let d = manifest("late_d", "0.1.0", &[("absent_e", "^0.1")]);
+    registry.install_with(&d, None, true).expect("forced");
+    let err = registry.resolve_load_order(&["late_d"]).unwrap_err();
+    assert_eq!(err.context_value("dependency"), Some("absent_e"));
+}
@@ -0,0 +1,122 @@
+use std::fs;
+use std::path::Path;
+
+use asm_host::{Capability, PluginManifest, PluginRegistry};
+
+fn manifest(name: &str, version: &str) -> PluginManifest {
+    PluginManifest {
+        name: name.into(),
+        version: version.into(),
+        abi_version: asm_host::ASM_ABI_VERSION,
+        min_abi: None,
+        max_abi: None,
+        capabilities: vec!["graph".into()],
+        minimum_workspace: None,
+        license: "MIT".into(),
+        description: None,
+        dependencies: Vec::new(),
+        signature: None,
+        public_key: None,
+    }
+}
+
+fn write_source(dir: &Path, manifest: &PluginManifest, bytes: &[u8]) {
+    fs::create_dir_all(dir).expect("source dir");
+    fs::write(
+        dir.join("plugin.toml"),
+        toml::to_string_pretty(manifest).expect("toml"),
+    )
+    .expect("manifest");
+    fs::write(dir.join("plugin.bin"), bytes).expect("binary");
+}
+
+fn code(err: &asm_core::errors::AsmError) -> String {
+    err.info().code.to_string()
+}
+
+#[test]
+fn install_reload_and_rollback_keep_registry_verified() {
+    let tmp = tempfile::tempdir().expect("tmp");
+    let source = tmp.path().join("src/graph_dev");
+    let registry = PluginRegistry::new(tmp.path().join("registry"));
+
+    write_source(&source, &manifest("graph_dev", "0.1.0"), b"build-1");
+    let installed = registry.install_from(&source, false).expect("install");
+    let first_hash = installed.plugin_hash.clone().expect("hash");
+    registry.verify("graph_dev").expect("verify after install");
+
+    let unchanged = registry.reload("graph_dev").expect("reload unchanged");
+    assert!(!unchanged.changed);
+    assert!(unchanged.archived.is_none());
+    assert!(registry.versions("graph_dev").expect("versions").is_empty());
+
+    registry
+        .grants()
+        .grant("graph_dev", Capability::Graph.flag());
+    assert!(registry.grants().allows("graph_dev", Capability::Graph));
+    write_source(&source, &manifest("graph_dev", "0.1.1"), b"build-2");
+    let reloaded = registry.reload("graph_dev").expect("reload");
+    assert!(reloaded.changed);
+    assert_eq!(reloaded.previous_hash.as_deref(), Some(first_hash.as_str()));
+    assert_eq!(reloaded.archived.as_deref(), Some(first_hash.as_str()));
+    assert_ne!(reloaded.entry.plugin_hash, reloaded.previous_hash);
+    assert_eq!(reloaded.entry.metadata.version, "0.1.1");
+    assert!(registry.grants().granted("graph_dev").is_none());
+    let verified = registry.verify("graph_dev").expect("verify after reload");
+    assert_eq!(verified.plugin_hash, reloaded.entry.plugin_hash);
+    assert_eq!(
+        registry.versions("graph_dev").expect("versions"),
+        vec![first_hash.clone()]
+    );
+
+    registry
+        .grants()
+        .grant("graph_dev", Capability::Graph.flag());
+    let restored = registry
+        .rollback("graph_dev", &first_hash)
+        .expect("rollback");
+    assert_eq!(restored.plugin_hash.as_deref(), Some(first_hash.as_str()));
+    assert_eq!(restored.metadata.version, "0.1.0");
+    assert!(registry.grants().granted("graph_dev").is_none());
+    let verified = registry.verify("graph_dev").expect("verify after rollback");
+    assert_eq!(verified.metadata.version, "0.1.0");
+    let second_hash = reloaded.entry.plugin_hash.expect("hash");
+    let mut expected = vec![first_hash, second_hash];
+    expected.sort();
+    assert_eq!(registry.versions("graph_dev").expect("versions"), expected);
+}
+
+#[test]
+fn reload_rejects_renames_and_unknown_plugins() {
+    let tmp = tempfile::tempdir().expect("tmp");
+    let source = tmp.path().join("src/graph_dev");
+    let registry = PluginRegistry::new(tmp.path().join("registry"));
+
+    let err = registry.reload("graph_dev").unwrap_err();
+    assert_eq!(code(&err), "asm_host.registry_missing");
+
+    write_source(&source, &manifest("graph_dev", "0.1.0"), b"build-1");
+    registry.install_from(&source, false).expect("install");
+    write_source(&source, &manifest("graph_renamed", "0.1.0"), b"build-2");
+    let err = registry.reload("graph_dev").unwrap_err();
+    assert_eq!(code(&err), "asm_host.registry_reload_name");
+    assert_eq!(err.context_value("actual"), Some("graph_renamed"));
+    registry
+        .verify("graph_dev")
+        .expect("unchanged after rejected reload");
+
+    let mut incompatible = manifest("graph_dev", "0.2.0");
+    incompatible.abi_version = asm_host::abi_version(2, 0);
+    write_source(&source, &incompatible, b"build-3");
+    let err = registry.reload("graph_dev").unwrap_err();
+    assert_eq!(code(&err), "asm_host.abi_major_mismatch");
+
+    let err = registry.rollback("graph_dev", "abc123").unwrap_err();
+    assert_eq!(code(&err), "asm_host.registry_version_missing");
+
+    registry
+        .install(&manifest("inline", "0.1.0"), Some(b"bytes"))
+        .expect("install inline");
+    let err = registry.reload("inline").unwrap_err();
+    assert_eq!(code(&err), "asm_host.registry_source");
+}
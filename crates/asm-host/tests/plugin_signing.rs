@@ -0,0 +1,134 @@
+use std::fs;
+
+use asm_host::{sign_plugin, PluginManifest, PluginRegistry, SignatureStatus};
+
+const SECRET: [u8; 32] = [7; 32];
+const FOREIGN: [u8; 32] = [9; 32];
+
+fn public_key(secret: &[u8; 32]) -> String {
+    sign_plugin(&manifest("key"), b"", secret)
+        .public_key
+        .unwrap()
+}
+
+fn manifest(name: &str) -> PluginManifest {
+    PluginManifest {
+        name: name.into(),
+        version: "0.1.0".into(),
+        abi_version: asm_host::ASM_ABI_VERSION,
+        min_abi: None,
+        max_abi: None,
+        capabilities: vec!["graph".into()],
+        minimum_workspace: None,
+        license: "MIT".into(),
+        description: None,
+        dependencies: Vec::new(),
+        signature: None,
+        public_key: None,
+    }
+}
+
+fn code(err: &asm_core::errors::AsmError) -> String {
+    err.info().code.to_string()
+}
+
+#[test]
+fn signed_plugins_verify_and_reject_tampering() {
+    let root = tempfile::tempdir().expect("registry");
+    let registry = PluginRegistry::new(root.path()).with_trusted_keys([public_key(&SECRET)]);
+    let signed = sign_plugin(&manifest("graph_signed"), b"build-1", &SECRET);
+    assert_eq!(signed.signature.as_ref().map(String::len), Some(128));
+
+    let entry = registry
+        .install(&signed, Some(b"build-1"))
+        .expect("install");
+    assert!(entry.signature.is_signed());
+    assert_eq!(
+        entry.signature,
+        SignatureStatus::Verified {
+            public_key: signed.public_key.clone().unwrap()
+        }
+    );
+    assert_eq!(registry.verify("graph_signed").expect("verify"), entry);
+
+    let err = registry.install(&signed, Some(b"build-2")).unwrap_err();
+    assert_eq!(code(&err), "asm_host.signature_invalid");
+    let err = registry.install(&signed, None).unwrap_err();
+    assert_eq!(code(&err), "asm_host.signature_binary");
+
+    // A binary swapped together with its recorded hash still fails the signature.
+    let dir = root.path().join("graph_signed");
+    fs::write(dir.join("plugin.bin"), b"evil").expect("tamper");
+    let metadata = fs::read_to_string(dir.join("metadata.json")).expect("metadata");
+    let forged = metadata.replace(
+        entry.plugin_hash.as_deref().unwrap(),
+        &asm_host::compute_plugin_hash(b"evil"),
+    );
+    fs::write(dir.join("metadata.json"), forged).expect("forge");
+    let err = registry.verify("graph_signed").unwrap_err();
+    assert_eq!(code(&err), "asm_host.signature_invalid");
+}
+
+#[test]
+fn foreign_keys_are_untrusted() {
+    let root = tempfile::tempdir().expect("registry");
+    let registry = PluginRegistry::new(root.path()).with_trusted_keys([public_key(&SECRET)]);
+    registry
+        .install(
+            &sign_plugin(&manifest("graph_keyed"), b"build-1", &SECRET),
+            Some(b"build-1"),
+        )
+        .expect("install");
+
+    // A replacement binary re-signed with someone else's key is refused.
+    let resigned = sign_plugin(&manifest("graph_keyed"), b"evil", &FOREIGN);
+    let err = registry.install(&resigned, Some(b"evil")).unwrap_err();
+    assert_eq!(code(&err), "asm_host.signature_untrusted");
+    assert_eq!(
+        err.context_value("public_key"),
+        Some(public_key(&FOREIGN).as_str())
+    );
+
+    // So is one swapped into the registry directory along with its metadata.
+    let open = PluginRegistry::new(root.path());
+    let entry = open
+        .install(&resigned, Some(b"evil"))
+        .expect("untrusted install");
+    assert_eq!(
+        entry.signature,
+        SignatureStatus::UntrustedKey {
+            public_key: public_key(&FOREIGN)
+        }
+    );
+    assert!(!entry.signature.is_signed());
+    let err = registry.verify("graph_keyed").unwrap_err();
+    assert_eq!(code(&err), "asm_host.signature_untrusted");
+}
+
+#[test]
+fn unsigned_and_malformed_manifests() {
+    let root = tempfile::tempdir().expect("registry");
+    let registry = PluginRegistry::new(root.path());
+    let entry = registry
+        .install(&manifest("graph_plain"), Some(b"bytes"))
+        .expect("install");
+    assert_eq!(entry.signature, SignatureStatus::Unsigned);
+    assert!(!registry.list().expect("list")[0].signature.is_signed());
+
+    let mut half = manifest("graph_half");
+    half.public_key = sign_plugin(&half, b"bytes", &SECRET).public_key;
+    let err = registry.install(&half, Some(b"bytes")).unwrap_err();
+    assert_eq!(code(&err), "asm_host.signature_incomplete");
+
+    let mut garbled = sign_plugin(&manifest("graph_garbled"), b"bytes", &SECRET);
+    garbled.signature = Some("zz".into());
+    let err = registry.install(&garbled, Some(b"bytes")).unwrap_err();
+    assert_eq!(code(&err), "asm_host.signature_encoding");
+    assert_eq!(err.context_value("field"), Some("signature"));
+
+    // Signatures cover the declared version, so bumping it invalidates them.
+    let mut bumped = sign_plugin(&manifest("graph_bumped"), b"bytes", &SECRET);
+    bumped.version = "0.2.0".into();
+    let err = registry.install(&bumped, Some(b"bytes")).unwrap_err();
+    assert_eq!(code(&err), "asm_host.signature_invalid");
+}
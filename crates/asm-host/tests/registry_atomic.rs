@@ -0,0 +1,130 @@
+use std::fs;
+use std::path::Path;
+
+use asm_host::{PluginManifest, PluginRegistry};
+
+fn manifest(name: &str, version: &str) -> PluginManifest {
+    PluginManifest {
+        name: name.into(),
+        version: version.into(),
+        abi_version: asm_host::ASM_ABI_VERSION,
+        min_abi: None,
+        max_abi: None,
+        capabilities: vec!["graph".into()],
+        minimum_workspace: None,
+        license: "MIT".into(),
+        description: None,
+        dependencies: Vec::new(),
+        signature: None,
+        public_key: None,
+    }
+}
+
+fn write_source(dir: &Path, manifest: &PluginManifest, bytes: &[u8]) {
+    fs::create_dir_all(dir).expect("source dir");
+    fs::write(
+        dir.join("plugin.toml"),
+        toml::to_string_pretty(manifest).expect("toml"),
+    )
+    .expect("manifest");
+    fs::write(dir.join("plugin.bin"), bytes).expect("binary");
+}
+
+fn code(err: &asm_core::errors::AsmError) -> String {
+    err.info().code.to_string()
+}
+
+fn names(registry: &PluginRegistry) -> Vec<String> {
+    registry
+        .list()
+        .expect("list")
+        .into_iter()
+        .map(|entry| entry.metadata.name)
+        .collect()
+}
+
+fn root_entries(root: &Path) -> Vec<String> {
+    let mut entries: Vec<_> = fs::read_dir(root)
+        .expect("read root")
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    entries.sort();
+    entries
+}
+
+#[test]
+fn reinstall_replaces_entry_and_keeps_archived_versions() {
+    let tmp = tempfile::tempdir().expect("tmp");
+    let root = tmp.path().join("registry");
+    let source = tmp.path().join("src/graph_dev");
+    let registry = PluginRegistry::new(&root);
+
+    write_source(&source, &manifest("graph_dev", "0.1.0"), b"build-1");
+    let installed = registry.install_from(&source, false).expect("install");
+    assert!(installed.source.is_some());
+    write_source(&source, &manifest("graph_dev", "0.1.1"), b"build-2");
+    registry.reload("graph_dev").expect("reload");
+    let versions = registry.versions("graph_dev").expect("versions");
+    assert_eq!(versions.len(), 1);
+
+    let entry = registry
+        .install(&manifest("graph_dev", "0.2.0"), Some(b"build-3"))
+        .expect("reinstall");
+    assert_eq!(entry.metadata.version, "0.2.0");
+    assert_eq!(registry.versions("graph_dev").expect("versions"), versions);
+    registry.verify("graph_dev").expect("verify");
+    assert_eq!(root_entries(&root), ["graph_dev"]);
+    assert!(registry.repair().expect("repair").is_empty());
+}
+
+#[test]
+fn half_written_entries_are_skipped_and_repaired() {
+    let tmp = tempfile::tempdir().expect("tmp");
+    let root = tmp.path().join("registry");
+    let registry = PluginRegistry::new(&root);
+    registry
+        .install(&manifest("graph_ok", "0.1.0"), Some(b"ok"))
+        .expect("install");
+
+    // A crash in a pre-marker install left files but no completion marker,
+    // and a crash mid-staging left an `.install-` directory behind.
+    let half = root.join("graph_half");
+    fs::create_dir_all(&half).expect("half dir");
+    fs::write(half.join("manifest.toml"), "name = \"graph_half\"").expect("manifest");
+    fs::write(half.join("metadata.json"), "{").expect("metadata");
+    fs::create_dir_all(root.join(".install-graph_new")).expect("staging");
+    fs::write(root.join(".install-graph_new/plugin.bin"), b"partial").expect("partial");
+
+    assert_eq!(names(&registry), ["graph_ok"]);
+    let err = registry.verify("graph_half").unwrap_err();
+    assert_eq!(code(&err), "asm_host.registry_incomplete");
+
+    let repaired = registry.repair().expect("repair");
+    assert_eq!(repaired, [".install-graph_new", "graph_half"]);
+    assert_eq!(root_entries(&root), ["graph_ok"]);
+    let err = registry.verify("graph_half").unwrap_err();
+    assert_eq!(code(&err), "asm_host.registry_missing");
+    registry.verify("graph_ok").expect("untouched");
+}
+
+#[test]
+fn repair_restores_entry_retired_by_interrupted_reinstall() {
+    let tmp = tempfile::tempdir().expect("tmp");
+    let root = tmp.path().join("registry");
+    let registry = PluginRegistry::new(&root);
+    registry
+        .install(&manifest("graph_dev", "0.1.0"), Some(b"build-1"))
+        .expect("install");
+
+    // Crash after the live entry was moved aside but before its replacement
+    // was renamed in.
+    fs::rename(root.join("graph_dev"), root.join(".retired-graph_dev")).expect("retire");
+    fs::create_dir_all(root.join(".install-graph_dev")).expect("staging");
+    assert!(names(&registry).is_empty());
+
+    let repaired = registry.repair().expect("repair");
+    assert_eq!(repaired, [".install-graph_dev", ".retired-graph_dev"]);
+    assert_eq!(names(&registry), ["graph_dev"]);
+    let entry = registry.verify("graph_dev").expect("restored");
+    assert_eq!(entry.metadata.version, "0.1.0");
+}
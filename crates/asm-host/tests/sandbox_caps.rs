@@ -1,4 +1,9 @@
-use asm_host::{SandboxCaps, SandboxEvent, SandboxGuard};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use asm_host::{
+    AbiCall, AsmPluginVTable, AsmStatus, Capability, OutCallback, PluginManifest, SandboxCaps,
+    SandboxDecision, SandboxEvent, SandboxGuard,
+};
 
 #[test]
 fn detects_cpu_violation() {
@@ -32,3 +37,100 @@ fn stays_within_limits() {
     ));
     assert!(guard.ensure_within().is_ok());
 }
+
+static GRAPH_CALLS: AtomicUsize = AtomicUsize::new(0);
+static SPECTRUM_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+extern "C" fn ignore_output(_: *const u8, _: usize) -> AsmStatus {
+    AsmStatus::OK
+}
+
+extern "C" fn mock_graph(_: *const u8, _: usize, out: OutCallback) -> AsmStatus {
+    GRAPH_CALLS.fetch_add(1, Ordering::SeqCst);
+    out(std::ptr::null(), 0)
+}
+
+extern "C" fn mock_spectrum(_: *const u8, _: usize, _: OutCallback) -> AsmStatus {
+    SPECTRUM_CALLS.fetch_add(1, Ordering::SeqCst);
+    AsmStatus::OK
+}
+
+fn mock_vtable() -> AsmPluginVTable {
+    AsmPluginVTable {
+        init: None,
+        graph_generate: Some(mock_graph),
+        code_generate: None,
+        spectrum: Some(mock_spectrum),
+        gauge: None,
+        interact: None,
+        shutdown: None,
+    }
+}
+
+#[test]
+fn blocks_undeclared_capabilities() {
+    let manifest: PluginManifest = toml::from_str(
+        r#"
+name = "graph_mock"
+version = "0.1.0"
+abi_version = 1
+license = "MIT"
+capabilities = ["graph"]
+"#,
+    )
+    .expect("manifest");
+    let mut guard =
+        SandboxGuard::new(SandboxCaps::relaxed()).with_capabilities(manifest.capability_flags());
+    let vtable = mock_vtable();
+
+    let status = guard.invoke(&vtable, AbiCall::GraphGenerate, b"{}", ignore_output);
+    assert!(status.is_ok());
+    assert_eq!(GRAPH_CALLS.load(Ordering::SeqCst), 1);
+    assert!(guard.events().is_empty());
+
+    // The plugin asks the host for file system access it never declared.
+    assert_eq!(guard.check_call(AbiCall::Filesystem), AsmStatus::DENIED);
+    assert_eq!(
+        guard.last_decision(),
+        &SandboxDecision::Denied {
+            call: AbiCall::Filesystem,
+            capability: Capability::Filesystem,
+        }
+    );
+    let err = guard.ensure_within().unwrap_err();
+    assert_eq!(err.info().code, "asm_host.capability_denied");
+    assert_eq!(err.context_value("capability"), Some("filesystem"));
+
+    // Undeclared stages are blocked before the plugin runs.
+    let status = guard.invoke(&vtable, AbiCall::Spectrum, b"{}", ignore_output);
+    assert_eq!(status, AsmStatus::DENIED);
+    assert_eq!(SPECTRUM_CALLS.load(Ordering::SeqCst), 0);
+
+    let declared = Capability::Graph.flag();
+    assert_eq!(
+        guard.events(),
+        [
+            SandboxEvent::CapabilityDenied {
+                call: AbiCall::Filesystem,
+                declared,
+            },
+            SandboxEvent::CapabilityDenied {
+                call: AbiCall::Spectrum,
+                declared,
+            },
+        ]
+    );
+}
+
+#[test]
+fn declared_filesystem_access_is_allowed() {
+    let mut guard =
+        SandboxGuard::new(SandboxCaps::relaxed()).with_capabilities(Capability::Filesystem.flag());
+    assert!(guard.check_call(AbiCall::Filesystem).is_ok());
+    assert!(guard.ensure_within().is_ok());
+
+    // Host services have no vtable entry, so invoking one is unsupported.
+    let status = guard.invoke(&mock_vtable(), AbiCall::Filesystem, b"", ignore_output);
+    assert_eq!(status, AsmStatus::UNSUPPORTED);
+    assert!(guard.events().is_empty());
+}
@@ -1,5 +1,6 @@
 use asm_code::hash::canonical_code_hash;
 use asm_core::errors::AsmError;
+use asm_core::hash::{domain_hash, hash_with_format, HashDomain, LEGACY_HASH_FORMAT};
 use asm_graph::canonical_hash as graph_hash;
 use asm_rg::StateRef;
 use serde::Serialize;
@@ -14,6 +15,27 @@ pub fn stable_hash_string<T: Serialize>(value: &T) -> Result<String, AsmError> {
     Ok(format!("{:x}", digest))
 }
 
+/// Computes a domain-separated hash (`<tag>1-<hex>`) for the provided payload.
+pub fn stable_hash_with_domain<T: Serialize>(
+    domain: HashDomain,
+    value: &T,
+) -> Result<String, AsmError> {
+    Ok(domain_hash(domain, &to_canonical_json_bytes(value)?))
+}
+
+/// Hashes the payload in the layout recorded by a report's `hash_format`.
+pub(crate) fn stable_hash_with_format<T: Serialize>(
+    format: u32,
+    domain: HashDomain,
+    value: &T,
+) -> Result<String, AsmError> {
+    Ok(hash_with_format(
+        format,
+        domain,
+        &to_canonical_json_bytes(value)?,
+    ))
+}
+
 /// Computes a canonical hash for the provided RG state reference.
 pub fn canonical_state_hash(state: &StateRef<'_>) -> Result<String, AsmError> {
     let graph = graph_hash(state.graph).map_err(|err| match err {
@@ -49,3 +71,11 @@ pub fn round_f64(value: f64) -> f64 {
     let scaled = (value * 1e9).round();
     scaled / 1e9
 }
+
+pub(crate) fn legacy_hash_format() -> u32 {
+    LEGACY_HASH_FORMAT
+}
+
+pub(crate) fn is_legacy_hash_format(format: &u32) -> bool {
+    *format <= LEGACY_HASH_FORMAT
+}
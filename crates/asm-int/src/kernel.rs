@@ -22,6 +22,22 @@ fn default_tolerance() -> f64 {
     1e-6
 }
 
+fn default_rtol() -> f64 {
+    1e-6
+}
+
+fn default_min_dt() -> f64 {
+    1e-9
+}
+
+fn default_max_dt() -> f64 {
+    0.1
+}
+
+fn is_zero(value: &f64) -> bool {
+    *value == 0.0
+}
+
 /// Kernel execution mode used for determinism guidance.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "snake_case")]
@@ -46,6 +62,9 @@ pub struct TrajectoryStep {
     pub norm: f64,
     /// Phase accumulator at this step.
     pub phase: f64,
+    /// Time derivative of the norm, recorded by adaptive runs for dense output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slope: Option<f64>,
 }
 
 /// Metadata summarising a trajectory.
@@ -59,6 +78,37 @@ pub struct TrajectoryMeta {
     pub final_norm: f64,
     /// Stable hash of the trajectory contents.
     pub traj_hash: String,
+    /// Step-size statistics recorded by adaptive runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive: Option<AdaptiveStats>,
+}
+
+/// Step-size statistics of an adaptive trajectory.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AdaptiveStats {
+    /// Steps accepted by the error controller.
+    pub accepted_steps: usize,
+    /// Trial steps rejected and retried with a smaller step.
+    pub rejected_steps: usize,
+    /// Smallest accepted step size.
+    pub min_step: f64,
+    /// Largest accepted step size.
+    pub max_step: f64,
+    /// Norm at `t = 0`, anchoring dense output before the first step.
+    pub initial_norm: f64,
+    /// Norm derivative at `t = 0`.
+    pub initial_slope: f64,
+}
+
+/// Value of a trajectory interpolated at a requested time.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DenseSample {
+    /// Requested sample time.
+    pub time: f64,
+    /// Interpolated norm.
+    pub norm: f64,
+    /// Interpolated phase accumulator.
+    pub phase: f64,
 }
 
 /// Propagation trajectory produced by the kernel.
@@ -89,6 +139,38 @@ pub struct KernelOpts {
     /// Execution mode used for provenance.
     #[serde(default)]
     pub mode: KernelMode,
+    /// Rate `s` of a fast initial transient `s·exp(-s·t)` added to the norm
+    /// decay; zero disables it.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub stiffness: f64,
+    /// Switches to error-controlled stepping over the horizon `steps · dt`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive: Option<AdaptiveOpts>,
+}
+
+/// Error-controlled step-size configuration for [`evolve`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AdaptiveOpts {
+    /// Relative tolerance on the embedded local error estimate.
+    #[serde(default = "default_rtol")]
+    pub rtol: f64,
+    /// Smallest step the controller may take; steps at this size are always
+    /// accepted.
+    #[serde(default = "default_min_dt")]
+    pub min_dt: f64,
+    /// Largest step the controller may take.
+    #[serde(default = "default_max_dt")]
+    pub max_dt: f64,
+}
+
+impl Default for AdaptiveOpts {
+    fn default() -> Self {
+        Self {
+            rtol: default_rtol(),
+            min_dt: default_min_dt(),
+            max_dt: default_max_dt(),
+        }
+    }
 }
 
 impl Default for KernelOpts {
@@ -99,6 +181,8 @@ impl Default for KernelOpts {
             tolerance: default_tolerance(),
             save_trajectory: true,
             mode: KernelMode::Light,
+            stiffness: 0.0,
+            adaptive: None,
         }
     }
 }
@@ -116,8 +200,11 @@ fn integrate_phase(rng: &mut RngHandle, tolerance: f64) -> f64 {
     round_f64(jitter)
 }
 
-/// Applies the deterministic interaction kernel producing a trajectory.
-pub fn evolve(state: &PreparedState, opts: &KernelOpts) -> Result<Trajectory, AsmError> {
+fn transient_rate(stiffness: f64, time: f64) -> f64 {
+    stiffness * (-stiffness * time).exp()
+}
+
+fn validate(opts: &KernelOpts) -> Result<(), AsmError> {
     if opts.steps == 0 {
         return Err(kernel_error(
             "zero-steps",
@@ -130,6 +217,40 @@ pub fn evolve(state: &PreparedState, opts: &KernelOpts) -> Result<Trajectory, As
             "time step must be positive and finite",
         ));
     }
+    if !opts.stiffness.is_finite() || opts.stiffness < 0.0 {
+        return Err(kernel_error(
+            "invalid-stiffness",
+            "stiffness must be non-negative and finite",
+        ));
+    }
+    if let Some(adaptive) = &opts.adaptive {
+        let positive = |value: f64| value.is_finite() && value > 0.0;
+        if !positive(adaptive.rtol)
+            || !positive(adaptive.min_dt)
+            || !positive(adaptive.max_dt)
+            || adaptive.min_dt > adaptive.max_dt
+        {
+            return Err(kernel_error(
+                "invalid-adaptive",
+                "adaptive rtol, min_dt and max_dt must be positive with min_dt <= max_dt",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Applies the deterministic interaction kernel producing a trajectory.
+///
+/// Fixed-step runs apply `steps` multiplicative decays of `dt` each. With
+/// [`KernelOpts::adaptive`] set, the kernel instead integrates the continuum
+/// limit `dn/dt = -(1/T + s·exp(-s·t))·n` over `T = steps · dt` with an
+/// embedded Bogacki–Shampine 3(2) pair, adjusting the step within
+/// `[min_dt, max_dt]` to keep the local error below `rtol`.
+pub fn evolve(state: &PreparedState, opts: &KernelOpts) -> Result<Trajectory, AsmError> {
+    validate(opts)?;
+    if let Some(adaptive) = &opts.adaptive {
+        return evolve_adaptive(state, opts, adaptive);
+    }
 
     let steps = effective_steps(opts);
     let seed = seed_from_hash(&state.prep_hash);
@@ -139,15 +260,17 @@ pub fn evolve(state: &PreparedState, opts: &KernelOpts) -> Result<Trajectory, As
     let mut history = Vec::new();
     let mut time = 0.0;
     for step in 0..steps {
+        let transient = opts.dt * transient_rate(opts.stiffness, time);
         time += opts.dt;
         let phase = integrate_phase(&mut rng, opts.tolerance);
-        norm = round_f64((norm * (1.0 - decay)).max(0.0));
+        norm = round_f64((norm * (1.0 - decay - transient)).max(0.0));
         if opts.save_trajectory {
             history.push(TrajectoryStep {
                 step,
                 time: round_f64(time),
                 norm,
                 phase,
+                slope: None,
             });
         }
     }
@@ -157,6 +280,7 @@ pub fn evolve(state: &PreparedState, opts: &KernelOpts) -> Result<Trajectory, As
         total_time: round_f64(time),
         final_norm: norm,
         traj_hash: stable_hash_string(&(&state.prep_hash, steps, round_f64(time), norm, &history))?,
+        adaptive: None,
     };
 
     Ok(Trajectory {
@@ -164,3 +288,170 @@ pub fn evolve(state: &PreparedState, opts: &KernelOpts) -> Result<Trajectory, As
         steps: history,
     })
 }
+
+fn evolve_adaptive(
+    state: &PreparedState,
+    opts: &KernelOpts,
+    adaptive: &AdaptiveOpts,
+) -> Result<Trajectory, AsmError> {
+    let horizon = effective_steps(opts) as f64 * opts.dt;
+    let base = 1.0 / horizon;
+    let rhs = |time: f64, norm: f64| -(base + transient_rate(opts.stiffness, time)) * norm;
+
+    let seed = seed_from_hash(&state.prep_hash);
+    let mut rng = RngHandle::from_seed(derive_substream_seed(seed, 2));
+    let mut history = Vec::new();
+    let mut step_sizes = Vec::new();
+    let mut rejected = 0usize;
+    let mut time = 0.0;
+    let mut norm = state.norm;
+    let mut slope = rhs(time, norm);
+    let mut h = opts.dt.clamp(adaptive.min_dt, adaptive.max_dt);
+    while time < horizon {
+        let last = time + h >= horizon;
+        let trial = if last { horizon - time } else { h };
+        let k2 = rhs(time + 0.5 * trial, norm + 0.5 * trial * slope);
+        let k3 = rhs(time + 0.75 * trial, norm + 0.75 * trial * k2);
+        let next = norm + trial * (2.0 / 9.0 * slope + 1.0 / 3.0 * k2 + 4.0 / 9.0 * k3);
+        let next_time = if last { horizon } else { time + trial };
+        let k4 = rhs(next_time, next);
+        let embedded =
+            norm + trial * (7.0 / 24.0 * slope + 0.25 * k2 + 1.0 / 3.0 * k3 + 0.125 * k4);
+        let scale = adaptive.rtol * norm.abs().max(next.abs());
+        let ratio = if scale > 0.0 {
+            (next - embedded).abs() / scale
+        } else {
+            0.0
+        };
+        let factor = if ratio > 0.0 {
+            (0.9 * ratio.powf(-1.0 / 3.0)).clamp(0.2, 5.0)
+        } else {
+            5.0
+        };
+        let resized = (trial * factor).clamp(adaptive.min_dt, adaptive.max_dt);
+        if ratio > 1.0 && trial > adaptive.min_dt {
+            rejected += 1;
+            h = resized;
+            continue;
+        }
+        let phase = integrate_phase(&mut rng, opts.tolerance);
+        time = next_time;
+        norm = next.max(0.0);
+        slope = k4;
+        step_sizes.push(trial);
+        if opts.save_trajectory {
+            history.push(TrajectoryStep {
+                step: step_sizes.len() - 1,
+                time: round_f64(time),
+                norm: round_f64(norm),
+                phase,
+                slope: Some(round_f64(slope)),
+            });
+        }
+        h = resized;
+    }
+
+    let steps = step_sizes.len();
+    let final_norm = round_f64(norm);
+    let stats = AdaptiveStats {
+        accepted_steps: steps,
+        rejected_steps: rejected,
+        min_step: step_sizes.iter().copied().fold(f64::INFINITY, f64::min),
+        max_step: step_sizes.iter().copied().fold(0.0, f64::max),
+        initial_norm: round_f64(state.norm),
+        initial_slope: round_f64(rhs(0.0, state.norm)),
+    };
+    let traj_hash = stable_hash_string(&(
+        &state.prep_hash,
+        steps,
+        round_f64(time),
+        final_norm,
+        &history,
+        &step_sizes,
+        &stats,
+    ))?;
+    Ok(Trajectory {
+        meta: TrajectoryMeta {
+            steps,
+            total_time: round_f64(time),
+            final_norm,
+            traj_hash,
+            adaptive: Some(stats),
+        },
+        steps: history,
+    })
+}
+
+impl Trajectory {
+    /// Interpolates the trajectory at the given ascending times.
+    ///
+    /// Adaptive trajectories use cubic Hermite interpolation between the
+    /// recorded norms and slopes and cover `[0, total_time]`; fixed-step
+    /// trajectories interpolate linearly between their recorded steps. Phases
+    /// are interpolated linearly. Requires a saved trajectory.
+    pub fn dense_output(&self, times: &[f64]) -> Result<Vec<DenseSample>, AsmError> {
+        if times.iter().any(|time| !time.is_finite()) || times.windows(2).any(|w| w[0] > w[1]) {
+            return Err(kernel_error(
+                "sample-times-unsorted",
+                "sample times must be finite and sorted in ascending order",
+            ));
+        }
+        if times.is_empty() {
+            return Ok(Vec::new());
+        }
+        if self.steps.is_empty() {
+            return Err(kernel_error(
+                "dense-output-empty",
+                "dense output requires a saved trajectory",
+            ));
+        }
+        let mut knots: Vec<(f64, f64, Option<f64>, f64)> = Vec::with_capacity(self.steps.len() + 1);
+        if let Some(stats) = &self.meta.adaptive {
+            knots.push((0.0, stats.initial_norm, Some(stats.initial_slope), 0.0));
+        }
+        knots.extend(
+            self.steps
+                .iter()
+                .map(|step| (step.time, step.norm, step.slope, step.phase)),
+        );
+        let (start, end) = (knots[0].0, knots[knots.len() - 1].0);
+        let mut samples = Vec::with_capacity(times.len());
+        let mut idx = 0;
+        for &time in times {
+            if time < start || time > end {
+                return Err(kernel_error(
+                    "sample-time-range",
+                    format!("sample time {time} lies outside [{start}, {end}]"),
+                ));
+            }
+            while idx + 2 < knots.len() && knots[idx + 1].0 < time {
+                idx += 1;
+            }
+            let (t0, n0, s0, p0) = knots[idx];
+            let (t1, n1, s1, p1) = knots[(idx + 1).min(knots.len() - 1)];
+            let width = t1 - t0;
+            let u = if width > 0.0 {
+                (time - t0) / width
+            } else {
+                0.0
+            };
+            let norm = match (s0, s1) {
+                (Some(s0), Some(s1)) => {
+                    let u2 = u * u;
+                    let u3 = u2 * u;
+                    (2.0 * u3 - 3.0 * u2 + 1.0) * n0
+                        + (u3 - 2.0 * u2 + u) * width * s0
+                        + (-2.0 * u3 + 3.0 * u2) * n1
+                        + (u3 - u2) * width * s1
+                }
+                _ => n0 + u * (n1 - n0),
+            };
+            samples.push(DenseSample {
+                time,
+                norm: round_f64(norm),
+                phase: round_f64(p0 + u * (p1 - p0)),
+            });
+        }
+        Ok(samples)
+    }
+}
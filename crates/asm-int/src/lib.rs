@@ -19,12 +19,19 @@ pub mod running;
 pub mod serde;
 
 pub use fit::{fit_couplings, CouplingsFit, FitConfidenceIntervals, FitOpts};
-pub use kernel::{evolve, KernelMode, KernelOpts, Trajectory, TrajectoryMeta, TrajectoryStep};
-pub use measure::{measure, MeasureOpts, ObsReport};
+pub use kernel::{
+    evolve, AdaptiveOpts, AdaptiveStats, DenseSample, KernelMode, KernelOpts, Trajectory,
+    TrajectoryMeta, TrajectoryStep,
+};
+pub use measure::{measure, MeasureOpts, ObsReport, ObsSample};
 pub use prepare::{
     prepare_state, ParticipantSpec, PrepSpec, PrepTemplate, PreparedParticipant, PreparedState,
 };
-pub use report::{interact, interact_full, InteractionProvenance, InteractionReport};
+pub use report::{
+    compare_reports, interact, interact_full, recompute_analysis_hash, CompareTolerances,
+    CouplingDelta, InteractionDiff, InteractionProvenance, InteractionReport, StateMismatch,
+    TrajectoryDelta,
+};
 pub use running::{
     fit_running, BetaSummary, RunningOpts, RunningReport, RunningStep, RunningThresholds,
 };
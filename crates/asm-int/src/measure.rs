@@ -2,7 +2,7 @@ use asm_core::errors::{AsmError, ErrorInfo};
 use serde::{Deserialize, Serialize};
 
 use crate::hash::{round_f64, stable_hash_string};
-use crate::kernel::Trajectory;
+use crate::kernel::{DenseSample, Trajectory};
 
 fn measure_error(code: &str, message: impl Into<String>) -> AsmError {
     AsmError::Code(ErrorInfo::new(code, message.into()))
@@ -48,6 +48,10 @@ pub struct MeasureOpts {
     /// Number of histogram bins when accumulating inclusive observables.
     #[serde(default = "default_bins")]
     pub bins: usize,
+    /// Ascending times at which observables are read off the trajectory's
+    /// dense output.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sample_times: Vec<f64>,
 }
 
 impl Default for MeasureOpts {
@@ -56,6 +60,7 @@ impl Default for MeasureOpts {
             observables: vec![ObservableKind::CrossSection, ObservableKind::Amplitude],
             ci_method: CiMethod::Bootstrap,
             bins: default_bins(),
+            sample_times: Vec::new(),
         }
     }
 }
@@ -84,10 +89,28 @@ pub struct ObsReport {
     pub ci: FitConfidenceBand,
     /// Residuals from deterministic fits.
     pub residuals: Vec<f64>,
+    /// Observables at [`MeasureOpts::sample_times`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub samples: Vec<ObsSample>,
     /// Stable hash identifying the measurement bundle.
     pub obs_hash: String,
 }
 
+/// Observables read off the dense output at one sample time.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ObsSample {
+    /// Sample time.
+    pub time: f64,
+    /// Interpolated norm.
+    pub norm: f64,
+    /// Interpolated phase shift.
+    pub phase: f64,
+    /// Inclusive cross section at the sample time.
+    pub xsec: f64,
+    /// Transition amplitude at the sample time.
+    pub amplitude: f64,
+}
+
 fn synthesize_bins(meta_bins: usize, values: &[f64]) -> Vec<f64> {
     if values.is_empty() || meta_bins == 0 {
         return Vec::new();
@@ -144,14 +167,38 @@ pub fn measure(traj: &Trajectory, mopts: &MeasureOpts) -> Result<ObsReport, AsmE
         .map(|(phase, amp)| round_f64(phase.abs() - amp.abs()))
         .collect::<Vec<_>>();
 
-    let obs_hash = stable_hash_string(&(
-        traj.meta.traj_hash.clone(),
-        &mopts.observables,
-        mopts.bins,
-        &ci.lower,
-        &ci.upper,
-        &residuals,
-    ))?;
+    let samples = traj
+        .dense_output(&mopts.sample_times)?
+        .into_iter()
+        .map(|DenseSample { time, norm, phase }| ObsSample {
+            time,
+            norm,
+            phase,
+            xsec: round_f64(base * (1.0 + time * 0.1)),
+            amplitude: round_f64(norm * 0.5),
+        })
+        .collect::<Vec<_>>();
+
+    let obs_hash = if samples.is_empty() {
+        stable_hash_string(&(
+            traj.meta.traj_hash.clone(),
+            &mopts.observables,
+            mopts.bins,
+            &ci.lower,
+            &ci.upper,
+            &residuals,
+        ))?
+    } else {
+        stable_hash_string(&(
+            traj.meta.traj_hash.clone(),
+            &mopts.observables,
+            mopts.bins,
+            &ci.lower,
+            &ci.upper,
+            &residuals,
+            &samples,
+        ))?
+    };
 
     Ok(ObsReport {
         xsecs,
@@ -159,6 +206,7 @@ pub fn measure(traj: &Trajectory, mopts: &MeasureOpts) -> Result<ObsReport, AsmE
         amplitudes,
         ci,
         residuals,
+        samples,
         obs_hash,
     })
 }
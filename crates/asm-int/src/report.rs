@@ -1,10 +1,15 @@
+use std::collections::BTreeMap;
+
 use asm_core::errors::{AsmError, ErrorInfo};
+use asm_core::hash::{HashDomain, HASH_FORMAT};
+use asm_core::provenance::{build_fingerprint, BuildFingerprint};
 use asm_gauge::GaugeReport;
 use asm_spec::SpectrumReport;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::fit::{fit_couplings, CouplingsFit, FitOpts};
-use crate::hash::stable_hash_string;
+use crate::hash::{is_legacy_hash_format, legacy_hash_format, stable_hash_with_format};
 use crate::kernel::{evolve, KernelOpts, Trajectory};
 use crate::measure::{measure, MeasureOpts, ObsReport};
 use crate::prepare::{prepare_state, PrepSpec, PreparedState};
@@ -24,6 +29,15 @@ pub struct InteractionProvenance {
     pub measure: MeasureOpts,
     /// Fit options summary.
     pub fit: FitOpts,
+    /// Layout of `analysis_hash`; reports without it carry legacy bare digests.
+    #[serde(
+        default = "legacy_hash_format",
+        skip_serializing_if = "is_legacy_hash_format"
+    )]
+    pub hash_format: u32,
+    /// Build that produced the report; excluded from `analysis_hash`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build: Option<BuildFingerprint>,
 }
 
 /// Aggregated interaction report capturing preparation, measurement and fit artefacts.
@@ -78,19 +92,12 @@ pub fn interact(
         kernel: kern.clone(),
         measure: mopts.clone(),
         fit: fopts.clone(),
+        hash_format: HASH_FORMAT,
+        build: Some(build_fingerprint()),
     };
 
-    let analysis_hash = stable_hash_string(&(
-        &spec.graph_hash,
-        &spec.code_hash,
-        &prepared.prep_hash,
-        &obs.obs_hash,
-        &fit.fit_hash,
-        &provenance.seed,
-    ))?;
-
-    Ok(InteractionReport {
-        analysis_hash,
+    let mut report = InteractionReport {
+        analysis_hash: String::new(),
         graph_hash: spec.graph_hash.clone(),
         code_hash: spec.code_hash.clone(),
         prep_hash: prepared.prep_hash.clone(),
@@ -98,7 +105,26 @@ pub fn interact(
         fit,
         trajectory,
         provenance,
-    })
+    };
+    report.analysis_hash = recompute_analysis_hash(&report)?;
+    Ok(report)
+}
+
+/// Recomputes `analysis_hash` in the layout recorded by the report provenance,
+/// accepting both legacy and domain-separated reports.
+pub fn recompute_analysis_hash(report: &InteractionReport) -> Result<String, AsmError> {
+    stable_hash_with_format(
+        report.provenance.hash_format,
+        HashDomain::Interaction,
+        &(
+            &report.graph_hash,
+            &report.code_hash,
+            &report.prep_hash,
+            &report.obs_hash,
+            &report.fit.fit_hash,
+            &report.provenance.seed,
+        ),
+    )
 }
 
 /// Convenience helper returning the full suite of artefacts produced by [`interact`].
@@ -131,18 +157,12 @@ pub fn interact_full(
         kernel: kern.clone(),
         measure: mopts.clone(),
         fit: fopts.clone(),
+        hash_format: HASH_FORMAT,
+        build: Some(build_fingerprint()),
     };
-    let analysis_hash = stable_hash_string(&(
-        &spec.graph_hash,
-        &spec.code_hash,
-        &prepared.prep_hash,
-        &obs.obs_hash,
-        &fit.fit_hash,
-        &provenance.seed,
-    ))?;
-
-    let report = InteractionReport {
-        analysis_hash,
+
+    let mut report = InteractionReport {
+        analysis_hash: String::new(),
         graph_hash: spec.graph_hash.clone(),
         code_hash: spec.code_hash.clone(),
         prep_hash: prepared.prep_hash.clone(),
@@ -151,6 +171,244 @@ pub fn interact_full(
         trajectory: trajectory.clone(),
         provenance,
     };
+    report.analysis_hash = recompute_analysis_hash(&report)?;
 
     Ok((prepared, trajectory, obs, fit, report))
 }
+
+/// Tolerances applied by [`compare_reports`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CompareTolerances {
+    /// Absolute coupling delta accepted as unchanged.
+    pub coupling_abs: f64,
+    /// Relative coupling delta accepted as unchanged.
+    pub coupling_rel: f64,
+    /// Absolute difference in total simulated time accepted as unchanged.
+    pub total_time: f64,
+    /// Absolute final-norm drift accepted as unchanged.
+    pub norm_drift: f64,
+}
+
+impl Default for CompareTolerances {
+    fn default() -> Self {
+        Self {
+            coupling_abs: 1e-9,
+            coupling_rel: 1e-6,
+            total_time: 1e-9,
+            norm_drift: 1e-9,
+        }
+    }
+}
+
+/// State identity field on which two reports disagree.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StateMismatch {
+    /// Name of the mismatching hash field.
+    pub field: String,
+    /// Value recorded in the first report.
+    pub a: String,
+    /// Value recorded in the second report.
+    pub b: String,
+}
+
+/// Comparison of a single fitted coupling component.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CouplingDelta {
+    /// Component label (`scale`, `g1`..`g3`, `lambda_h`, `yukawa[i]`).
+    pub component: String,
+    /// Value in the first report; absent when the component only exists in the second.
+    pub a: Option<f64>,
+    /// Value in the second report; absent when the component only exists in the first.
+    pub b: Option<f64>,
+    /// Absolute delta, when both values exist.
+    pub abs_delta: Option<f64>,
+    /// Delta relative to the larger magnitude, when both values exist.
+    pub rel_delta: Option<f64>,
+    /// Whether the delta is within either the absolute or the relative tolerance.
+    pub within_tol: bool,
+}
+
+/// Comparison of the trajectory metadata.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrajectoryDelta {
+    /// Steps executed in the first report.
+    pub steps_a: usize,
+    /// Steps executed in the second report.
+    pub steps_b: usize,
+    /// Total simulated time in the first report.
+    pub total_time_a: f64,
+    /// Total simulated time in the second report.
+    pub total_time_b: f64,
+    /// Final norm of the second report minus that of the first.
+    pub final_norm_drift: f64,
+    /// Whether steps match and time and norm agree within tolerance.
+    pub within_tol: bool,
+}
+
+/// Structured comparison of two [`InteractionReport`]s.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InteractionDiff {
+    /// Set when the reports describe different states; no further comparison is made.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub incomparable: Option<StateMismatch>,
+    /// Per-component coupling comparison.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub couplings: Vec<CouplingDelta>,
+    /// Trajectory metadata comparison.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trajectory: Option<TrajectoryDelta>,
+    /// Dotted paths of provenance option fields whose values differ.
+    pub provenance_changes: Vec<String>,
+    /// True when the reports are comparable and every coupling and trajectory
+    /// check is within tolerance. Provenance changes alone do not break equivalence.
+    pub equivalent: bool,
+}
+
+/// Compares two interaction reports, typically produced with different kernel
+/// or fit options for the same prepared state.
+pub fn compare_reports(
+    a: &InteractionReport,
+    b: &InteractionReport,
+    tol: &CompareTolerances,
+) -> InteractionDiff {
+    let provenance_changes = changed_fields(&a.provenance, &b.provenance);
+    let mismatch = [
+        ("graph_hash", &a.graph_hash, &b.graph_hash),
+        ("code_hash", &a.code_hash, &b.code_hash),
+        ("prep_hash", &a.prep_hash, &b.prep_hash),
+    ]
+    .into_iter()
+    .find(|(_, lhs, rhs)| lhs != rhs)
+    .map(|(field, lhs, rhs)| StateMismatch {
+        field: field.to_string(),
+        a: lhs.clone(),
+        b: rhs.clone(),
+    });
+    if mismatch.is_some() {
+        return InteractionDiff {
+            incomparable: mismatch,
+            couplings: Vec::new(),
+            trajectory: None,
+            provenance_changes,
+            equivalent: false,
+        };
+    }
+
+    let components = |fit: &CouplingsFit| -> Vec<(String, f64)> {
+        let mut values = vec![("scale".to_string(), fit.scale)];
+        values.extend((0..3).map(|idx| (format!("g{}", idx + 1), fit.g[idx])));
+        values.push(("lambda_h".to_string(), fit.lambda_h));
+        values.extend(
+            fit.yukawa
+                .iter()
+                .enumerate()
+                .map(|(idx, value)| (format!("yukawa[{idx}]"), *value)),
+        );
+        values
+    };
+    let (values_a, values_b) = (components(&a.fit), components(&b.fit));
+    let couplings: Vec<CouplingDelta> = (0..values_a.len().max(values_b.len()))
+        .map(|idx| {
+            let lhs = values_a.get(idx);
+            let rhs = values_b.get(idx);
+            let component = lhs
+                .or(rhs)
+                .map(|(name, _)| name.clone())
+                .unwrap_or_default();
+            coupling_delta(component, lhs.map(|v| v.1), rhs.map(|v| v.1), tol)
+        })
+        .collect();
+
+    let meta_a = &a.trajectory.meta;
+    let meta_b = &b.trajectory.meta;
+    let final_norm_drift = meta_b.final_norm - meta_a.final_norm;
+    let trajectory = TrajectoryDelta {
+        steps_a: meta_a.steps,
+        steps_b: meta_b.steps,
+        total_time_a: meta_a.total_time,
+        total_time_b: meta_b.total_time,
+        final_norm_drift,
+        within_tol: meta_a.steps == meta_b.steps
+            && (meta_a.total_time - meta_b.total_time).abs() <= tol.total_time
+            && final_norm_drift.abs() <= tol.norm_drift,
+    };
+
+    let equivalent = trajectory.within_tol && couplings.iter().all(|delta| delta.within_tol);
+    InteractionDiff {
+        incomparable: None,
+        couplings,
+        trajectory: Some(trajectory),
+        provenance_changes,
+        equivalent,
+    }
+}
+
+fn coupling_delta(
+    component: String,
+    a: Option<f64>,
+    b: Option<f64>,
+    tol: &CompareTolerances,
+) -> CouplingDelta {
+    let (abs_delta, rel_delta) = match (a, b) {
+        (Some(a), Some(b)) => {
+            let abs = (a - b).abs();
+            let scale = a.abs().max(b.abs());
+            let rel = if scale > 0.0 { abs / scale } else { 0.0 };
+            (Some(abs), Some(rel))
+        }
+        _ => (None, None),
+    };
+    let within_tol = match (abs_delta, rel_delta) {
+        (Some(abs), Some(rel)) => abs <= tol.coupling_abs || rel <= tol.coupling_rel,
+        _ => false,
+    };
+    CouplingDelta {
+        component,
+        a,
+        b,
+        abs_delta,
+        rel_delta,
+        within_tol,
+    }
+}
+
+/// Lists the dotted paths of leaf fields that differ between two values.
+fn changed_fields<T: Serialize>(a: &T, b: &T) -> Vec<String> {
+    let mut leaves_a = BTreeMap::new();
+    let mut leaves_b = BTreeMap::new();
+    flatten_value(
+        "",
+        serde_json::to_value(a).unwrap_or(Value::Null),
+        &mut leaves_a,
+    );
+    flatten_value(
+        "",
+        serde_json::to_value(b).unwrap_or(Value::Null),
+        &mut leaves_b,
+    );
+    let mut keys: Vec<&String> = leaves_a.keys().chain(leaves_b.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter()
+        .filter(|key| leaves_a.get(*key) != leaves_b.get(*key))
+        .cloned()
+        .collect()
+}
+
+fn flatten_value(prefix: &str, value: Value, out: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() {
+                    key
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten_value(&path, child, out);
+            }
+        }
+        other => {
+            out.insert(prefix.to_string(), other);
+        }
+    }
+}
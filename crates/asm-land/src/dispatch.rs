@@ -1,21 +1,31 @@
+use std::collections::BTreeMap;
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use asm_core::errors::{AsmError, ErrorInfo};
+use asm_core::errors::{AsmError, ErrorCode, ErrorInfo};
+use asm_core::rng::CounterRng;
 
-use std::sync::Arc;
+use std::sync::{Arc, Condvar, Mutex};
 
+use rand::RngCore;
 use rayon::prelude::*;
 
+use crate::estimate::{
+    artefact_model, job_memory_bytes, round_seconds, stage_units, DirectoryEstimate, PlanEstimate,
+    StageCosts, StageEstimate, REPORT_BASE_BYTES, REPORT_JOB_BYTES, STAGES,
+};
+use crate::events::{EventLog, JobEventKind, JobEvents, StageTimer};
 use crate::filters::FilterDecision;
 use crate::filters::{load_filters, FilterSpec};
-use crate::plan::{load_plan, OutputLayout, Plan, RuleSpec};
-use crate::report::{JobReport, JobStatus, LandscapeReport};
+use crate::metrics::{artefact_path, KpiExtractors};
+use crate::plan::{load_plan, OutputLayout, Plan, RuleSpec, SeedDerivation};
+use crate::report::{provenance_timestamp, JobReport, JobStatus, LandscapeReport, EVENTS_FILE};
 use crate::serde::{from_json_slice, to_canonical_json_bytes};
 use crate::stages::{synthesise_stage_outputs, StageHashes, StageOutputs};
-use crate::stat::StatsSummary;
+use crate::stat::{StatsAccumulator, StatsOpts};
 
-fn io_error(code: &str, err: impl ToString) -> AsmError {
+fn io_error(code: ErrorCode, err: impl ToString) -> AsmError {
     AsmError::Serde(ErrorInfo::new(code, err.to_string()))
 }
 
@@ -28,6 +38,61 @@ pub struct RunOpts {
     pub concurrency: usize,
     /// Maximum number of deterministic retries per job.
     pub max_retries: u32,
+    /// Whether completed jobs picked up by `resume` are checked against a
+    /// recomputation of their stage outputs.
+    pub verify_resumed: ResumeVerification,
+    /// Upper bound on the projected memory of concurrently running jobs.
+    /// Jobs wait for a slot once the budget is used up; at least one job
+    /// always runs even when it alone exceeds the budget.
+    pub memory_budget_bytes: Option<usize>,
+    /// Pin the report's provenance timestamp instead of using the wall clock,
+    /// so repeated runs produce identical report bytes. See
+    /// [`provenance_timestamp`](crate::report::provenance_timestamp).
+    pub deterministic_time: bool,
+    /// Extractors deriving extra KPIs from each job's artefacts; their values
+    /// land in [`JobKpi::extra`](crate::metrics::JobKpi::extra).
+    pub extractors: KpiExtractors,
+    /// Check run before every job attempt, used to inject faults.
+    pub attempt_hook: Option<AttemptHook>,
+}
+
+/// Callback invoked with `(seed, rule_id, attempt)` before each attempt's
+/// stages run.
+///
+/// An error fails the attempt exactly like a stage error would, so retryable
+/// errors lead to another attempt until `max_retries` is reached.
+#[derive(Clone)]
+pub struct AttemptHook(Arc<dyn Fn(u64, u64, u32) -> Result<(), AsmError> + Send + Sync>);
+
+impl AttemptHook {
+    /// Wraps `hook` for use in [`RunOpts::attempt_hook`].
+    pub fn new(
+        hook: impl Fn(u64, u64, u32) -> Result<(), AsmError> + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(hook))
+    }
+
+    fn check(&self, seed: u64, rule_id: u64, attempt: u32) -> Result<(), AsmError> {
+        (self.0)(seed, rule_id, attempt)
+    }
+}
+
+impl fmt::Debug for AttemptHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AttemptHook(..)")
+    }
+}
+
+/// Handling of resumed jobs whose persisted artefacts disagree with a recomputation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResumeVerification {
+    /// Trust persisted artefacts without recomputing them.
+    #[default]
+    Off,
+    /// Fail the run on the first mismatching job.
+    Error,
+    /// Discard the mismatching job and execute it again.
+    Rerun,
 }
 
 impl Default for RunOpts {
@@ -36,55 +101,130 @@ impl Default for RunOpts {
             resume: false,
             concurrency: 1,
             max_retries: 2,
+            verify_resumed: ResumeVerification::Off,
+            memory_budget_bytes: None,
+            deterministic_time: false,
+            extractors: KpiExtractors::default(),
+            attempt_hook: None,
+        }
+    }
+}
+
+/// Returns how many jobs of `plan` may run at once under `opts`.
+pub fn effective_concurrency(plan: &Plan, opts: &RunOpts) -> usize {
+    let concurrency = opts.concurrency.max(1);
+    match opts.memory_budget_bytes {
+        None => concurrency,
+        Some(budget) => {
+            let per_job = job_memory_bytes(plan).max(1);
+            let fits = (budget as u64 / per_job).max(1);
+            concurrency.min(usize::try_from(fits).unwrap_or(usize::MAX))
+        }
+    }
+}
+
+/// Counting semaphore bounding the number of jobs executing at once.
+struct JobSlots {
+    free: Mutex<usize>,
+    released: Condvar,
+}
+
+impl JobSlots {
+    fn new(slots: usize) -> Self {
+        Self {
+            free: Mutex::new(slots),
+            released: Condvar::new(),
+        }
+    }
+
+    fn run<T>(&self, job: impl FnOnce() -> T) -> T {
+        {
+            let mut free = self.free.lock().unwrap_or_else(|err| err.into_inner());
+            while *free == 0 {
+                free = self
+                    .released
+                    .wait(free)
+                    .unwrap_or_else(|err| err.into_inner());
+            }
+            *free -= 1;
         }
+        let result = job();
+        *self.free.lock().unwrap_or_else(|err| err.into_inner()) += 1;
+        self.released.notify_one();
+        result
     }
 }
 
 /// Executes a landscape plan, emitting deterministic artefacts on disk.
+///
+/// Job lifecycle events are appended to [`EVENTS_FILE`] in `out` while the
+/// run progresses; the finished file is ordered by job index and event
+/// sequence.
 pub fn run_plan(plan: &Plan, out: &Path, opts: &RunOpts) -> Result<LandscapeReport, AsmError> {
-    fs::create_dir_all(out).map_err(|err| io_error("plan_out_dir", err))?;
+    fs::create_dir_all(out).map_err(|err| io_error(ErrorCode::PlanOutDir, err))?;
     let filter_spec = Arc::new(load_filters(&plan.filters_path())?);
     let jobs = enumerate_jobs(plan, out);
+    let slots = JobSlots::new(effective_concurrency(plan, opts));
     let pool = rayon::ThreadPoolBuilder::new()
         .num_threads(opts.concurrency.max(1))
         .build()
-        .map_err(|err| io_error("thread_pool", err))?;
+        .map_err(|err| io_error(ErrorCode::ThreadPool, err))?;
+    let events = EventLog::create(out.join(EVENTS_FILE), opts.deterministic_time)?;
 
     let results: Result<Vec<_>, AsmError> = pool.install(|| {
         jobs.par_iter()
             .enumerate()
             .map(|(index, job)| -> Result<(usize, JobResult), AsmError> {
-                let result = process_job(
-                    plan,
-                    filter_spec.as_ref(),
-                    &job.dir,
-                    job.seed,
-                    &job.rule,
-                    opts,
-                )?;
-                Ok((index, result))
+                let mut job_events = events.job(index, job.seed, job.rule.id);
+                let result = slots.run(|| {
+                    process_job(
+                        plan,
+                        filter_spec.as_ref(),
+                        &job.dir,
+                        job.seed,
+                        &job.rule,
+                        opts,
+                        &mut job_events,
+                    )
+                });
+                if let Err(err) = &result {
+                    let attempt = job_events.last_attempt();
+                    job_events.record(
+                        JobEventKind::Failure,
+                        attempt,
+                        &StageTimer::default(),
+                        Some(err.to_string()),
+                    );
+                }
+                Ok((index, result?))
             })
             .collect()
     });
+    events.finish()?;
 
     let mut ordered = results?;
     ordered.sort_by_key(|(index, _)| *index);
 
     let mut job_reports = Vec::with_capacity(ordered.len());
-    let mut stats_kpis = Vec::new();
+    let mut stats = StatsAccumulator::new(&StatsOpts::default())?;
     for (_, result) in ordered {
-        if let Some(kpi) = result.stats_kpi {
-            stats_kpis.push(kpi);
+        if let Some(kpi) = &result.stats_kpi {
+            stats.push(kpi);
         }
         job_reports.push(result.report);
     }
 
     job_reports.sort_by(|a, b| a.seed.cmp(&b.seed).then(a.rule_id.cmp(&b.rule_id)));
-    let stats = StatsSummary::from_kpis(&stats_kpis);
-    let report = LandscapeReport::new(plan, job_reports, stats, (*filter_spec).clone());
+    let report = LandscapeReport::new_with_timestamp(
+        plan,
+        job_reports,
+        stats.finalize(),
+        (*filter_spec).clone(),
+        provenance_timestamp(opts.deterministic_time),
+    );
     let report_bytes = to_canonical_json_bytes(&report)?;
     fs::write(out.join("landscape_report.json"), report_bytes)
-        .map_err(|err| io_error("landscape_report_write", err))?;
+        .map_err(|err| io_error(ErrorCode::LandscapeReportWrite, err))?;
     Ok(report)
 }
 
@@ -98,6 +238,86 @@ pub fn run_plan_from_path(
     run_plan(&plan, out, opts)
 }
 
+/// Projects job count, disk footprint, and wall-time for a plan using the default cost table.
+///
+/// Nothing is read from or written to any output directory.
+pub fn estimate_plan(plan: &Plan, opts: &RunOpts) -> Result<PlanEstimate, AsmError> {
+    estimate_plan_with_costs(plan, opts, &StageCosts::default())
+}
+
+/// Projects a plan using a caller-supplied seconds-per-unit cost table.
+pub fn estimate_plan_with_costs(
+    plan: &Plan,
+    opts: &RunOpts,
+    costs: &StageCosts,
+) -> Result<PlanEstimate, AsmError> {
+    costs.validate()?;
+    let jobs = enumerate_jobs(plan, Path::new(""));
+    let job_count = jobs.len() as u64;
+    let artefacts = artefact_model(plan);
+    let units = stage_units(plan, &artefacts);
+    let job_bytes: u64 = artefacts.iter().map(|artefact| artefact.bytes).sum();
+
+    let mut stages = BTreeMap::new();
+    let mut job_seconds = 0.0;
+    for stage in STAGES {
+        let units_per_job = units.get(stage).copied().unwrap_or(0);
+        let seconds_per_unit = costs.get(stage);
+        let per_job = units_per_job as f64 * seconds_per_unit;
+        job_seconds += per_job;
+        let bytes = artefacts
+            .iter()
+            .filter(|artefact| artefact.stage == stage)
+            .map(|artefact| artefact.bytes)
+            .sum::<u64>()
+            * job_count;
+        stages.insert(
+            stage.to_string(),
+            StageEstimate {
+                units_per_job,
+                seconds_per_unit,
+                seconds: round_seconds(per_job * job_count as f64),
+                bytes,
+            },
+        );
+    }
+
+    let mut directories: BTreeMap<String, DirectoryEstimate> = BTreeMap::new();
+    for job in &jobs {
+        let top = job
+            .dir
+            .components()
+            .next()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let entry = directories
+            .entry(top)
+            .or_insert(DirectoryEstimate { jobs: 0, bytes: 0 });
+        entry.jobs += 1;
+        entry.bytes += job_bytes;
+    }
+
+    let concurrency = effective_concurrency(plan, opts);
+    let waves = job_count.div_ceil(concurrency as u64);
+    let report_bytes = REPORT_BASE_BYTES + REPORT_JOB_BYTES * job_count;
+    Ok(PlanEstimate {
+        plan_hash: plan.plan_hash()?,
+        jobs: job_count,
+        layout: plan.outputs.layout,
+        keep_intermediate: plan.outputs.keep_intermediate,
+        concurrency,
+        job_memory_bytes: job_memory_bytes(plan),
+        artefacts,
+        stages,
+        directories,
+        report_bytes,
+        total_bytes: job_bytes * job_count + report_bytes,
+        cpu_seconds: round_seconds(job_seconds * job_count as f64),
+        wall_seconds: round_seconds(job_seconds * waves as f64),
+        costs: costs.clone(),
+    })
+}
+
 fn process_job(
     plan: &Plan,
     filter_spec: &FilterSpec,
@@ -105,30 +325,62 @@ fn process_job(
     seed: u64,
     rule: &RuleSpec,
     opts: &RunOpts,
+    events: &mut JobEvents<'_>,
 ) -> Result<JobResult, AsmError> {
     if opts.resume && job_complete(job_dir)? {
         let existing = load_existing_job(job_dir)?;
-        let filters = filter_spec.evaluate(&existing.kpi);
-        return Ok(JobResult {
-            stats_kpi: Some(existing.kpi.clone()),
-            report: JobReport {
-                seed,
-                rule_id: rule.id,
-                status: existing.status,
-                hashes: existing.hashes,
-                kpis: existing.kpi,
-                filters,
-            },
-        });
+        let mismatched = match opts.verify_resumed {
+            ResumeVerification::Off => Vec::new(),
+            _ => mismatched_artefacts(plan, job_dir, seed, rule, &existing.status, opts)?,
+        };
+        if mismatched.is_empty() {
+            events.record(
+                JobEventKind::Resumed,
+                existing.status.attempts,
+                &StageTimer::default(),
+                None,
+            );
+            let filters = filter_spec.evaluate(&existing.kpi);
+            return Ok(JobResult {
+                stats_kpi: Some(existing.kpi.clone()),
+                report: JobReport {
+                    seed,
+                    rule_id: rule.id,
+                    status: existing.status,
+                    hashes: existing.hashes,
+                    kpis: existing.kpi,
+                    filters,
+                },
+            });
+        }
+        if opts.verify_resumed == ResumeVerification::Error {
+            return Err(AsmError::Serde(
+                ErrorInfo::new(
+                    ErrorCode::ResumeMismatch,
+                    "resumed job artefacts do not match a recomputation",
+                )
+                .with_context("job_dir", job_dir.display().to_string())
+                .with_context("artefacts", mismatched.join(",")),
+            ));
+        }
+        let _ = fs::remove_file(job_dir.join("status.json"));
+        cleanup_incomplete(job_dir);
     }
 
-    fs::create_dir_all(job_dir).map_err(|err| io_error("job_dir", err))?;
-    match execute_with_retries(plan, job_dir, seed, rule, opts.max_retries) {
-        Ok((outputs, attempts)) => {
+    fs::create_dir_all(job_dir).map_err(|err| io_error(ErrorCode::JobDir, err))?;
+    events.record(JobEventKind::Start, 1, &StageTimer::default(), None);
+    match execute_with_retries(plan, job_dir, seed, rule, opts, events) {
+        Ok((mut outputs, attempts, mut timer)) => {
+            timer.time("extract", || {
+                extract_extra_kpis(&opts.extractors, job_dir, &mut outputs)
+            })?;
             let filters = filter_spec.evaluate(&outputs.kpi);
             let status = JobStatus::success(attempts);
             let kpi_for_stats = outputs.kpi.clone();
-            persist_stage_outputs(plan, job_dir, &outputs, &status, &filters)?;
+            timer.time("persist", || {
+                persist_stage_outputs(plan, job_dir, &outputs, &status, &filters)
+            })?;
+            events.record(JobEventKind::Finish, attempts, &timer, None);
             Ok(JobResult {
                 stats_kpi: Some(kpi_for_stats),
                 report: JobReport {
@@ -159,34 +411,51 @@ fn process_job(
     }
 }
 
+/// Runs attempts until one succeeds or `max_retries` attempts have failed,
+/// recording a retry event for every failed attempt that is followed by
+/// another and a failure event for the last one.
 fn execute_with_retries(
     plan: &Plan,
     job_dir: &Path,
     seed: u64,
     rule: &RuleSpec,
-    max_retries: u32,
-) -> Result<(StageOutputs, u32), JobFailure> {
+    opts: &RunOpts,
+    events: &mut JobEvents<'_>,
+) -> Result<(StageOutputs, u32, StageTimer), JobFailure> {
     let mut attempt = 0u32;
     loop {
         attempt += 1;
-        let result = synthesise_stage_outputs(
-            derive_seed(seed, attempt),
-            rule.id,
-            plan.sampler.sweeps,
-            plan.spectrum.modes,
-            plan.spectrum.k_points,
-        );
+        let mut timer = StageTimer::default();
+        let result = timer.time("synthesise", || {
+            if let Some(hook) = &opts.attempt_hook {
+                hook.check(seed, rule.id, attempt)?;
+            }
+            synthesise_stage_outputs(
+                derive_seed(plan.seed_derivation, seed, rule.id, attempt),
+                rule.id,
+                plan.sampler.sweeps,
+                plan.spectrum.modes,
+                plan.spectrum.k_points,
+            )
+        });
         match result {
             Ok(outputs) => {
                 cleanup_incomplete(job_dir);
-                return Ok((outputs, attempt));
+                return Ok((outputs, attempt, timer));
             }
-            Err(_err) if attempt < max_retries.max(1) => {
+            Err(err) if attempt < opts.max_retries.max(1) && is_retryable(&err) => {
                 cleanup_incomplete(job_dir);
+                events.record(JobEventKind::Retry, attempt, &timer, Some(err.to_string()));
                 continue;
             }
             Err(err) => {
                 cleanup_incomplete(job_dir);
+                events.record(
+                    JobEventKind::Failure,
+                    attempt,
+                    &timer,
+                    Some(err.to_string()),
+                );
                 return Err(JobFailure {
                     attempts: attempt,
                     error: err.to_string(),
@@ -196,6 +465,54 @@ fn execute_with_retries(
     }
 }
 
+/// Fills [`JobKpi::extra`](crate::metrics::JobKpi::extra) from the registered
+/// extractors. Stage artefacts are encoded from `outputs`, so they are available
+/// even without `keep_intermediate`; other kinds are read from `job_dir` and
+/// skipped when absent.
+fn extract_extra_kpis(
+    extractors: &KpiExtractors,
+    job_dir: &Path,
+    outputs: &mut StageOutputs,
+) -> Result<(), AsmError> {
+    if extractors.is_empty() {
+        return Ok(());
+    }
+    let mut artefacts = BTreeMap::new();
+    for kind in extractors.artefact_kinds() {
+        let bytes = match kind {
+            "mcmc" => to_canonical_json_bytes(&outputs.mcmc)?,
+            "spectrum" => to_canonical_json_bytes(&outputs.spectrum)?,
+            "gauge" => to_canonical_json_bytes(&outputs.gauge)?,
+            "interact" => to_canonical_json_bytes(&outputs.interaction)?,
+            _ => {
+                let path = job_dir.join(artefact_path(kind));
+                match fs::read(&path) {
+                    Ok(bytes) => bytes,
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                    Err(err) => {
+                        return Err(AsmError::Serde(
+                            ErrorInfo::new(ErrorCode::KpiExtractor, err.to_string())
+                                .with_context("path", path.display().to_string()),
+                        ))
+                    }
+                }
+            }
+        };
+        artefacts.insert(kind.to_string(), bytes);
+    }
+    outputs.kpi.extra = extractors.extract(&artefacts)?;
+    Ok(())
+}
+
+/// Encoding failures are deterministic in the payload shape, so re-deriving
+/// the attempt seed cannot cure them.
+fn is_retryable(err: &AsmError) -> bool {
+    !matches!(
+        err.info().code_enum(),
+        Some(ErrorCode::JsonSerialize | ErrorCode::YamlSerialize)
+    )
+}
+
 fn persist_stage_outputs(
     plan: &Plan,
     job_dir: &Path,
@@ -215,7 +532,7 @@ fn persist_stage_outputs(
             &outputs.interaction,
         )?;
     } else {
-        fs::create_dir_all(job_dir).map_err(|err| io_error("job_dir", err))?;
+        fs::create_dir_all(job_dir).map_err(|err| io_error(ErrorCode::JobDir, err))?;
     }
     write_json(job_dir.join("kpi.json"), &outputs.kpi)?;
     write_json(job_dir.join("hashes.json"), &outputs.hashes)?;
@@ -226,10 +543,10 @@ fn persist_stage_outputs(
 
 fn write_json<T: serde::Serialize>(path: PathBuf, value: &T) -> Result<(), AsmError> {
     if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|err| io_error("stage_dir", err))?;
+        fs::create_dir_all(parent).map_err(|err| io_error(ErrorCode::StageDir, err))?;
     }
     let bytes = to_canonical_json_bytes(value)?;
-    fs::write(path, bytes).map_err(|err| io_error("stage_write", err))
+    fs::write(path, bytes).map_err(|err| io_error(ErrorCode::StageWrite, err))
 }
 
 fn job_dir(base: &Path, layout: OutputLayout, seed: u64, rule_id: u64) -> PathBuf {
@@ -244,7 +561,7 @@ fn job_complete(job_dir: &Path) -> Result<bool, AsmError> {
     if !status_path.exists() {
         return Ok(false);
     }
-    let bytes = fs::read(status_path).map_err(|err| io_error("status_read", err))?;
+    let bytes = fs::read(status_path).map_err(|err| io_error(ErrorCode::StatusRead, err))?;
     let status: JobStatus = from_json_slice(&bytes)?;
     if status.state != crate::report::JobState::Complete {
         return Ok(false);
@@ -253,11 +570,12 @@ fn job_complete(job_dir: &Path) -> Result<bool, AsmError> {
 }
 
 fn load_existing_job(job_dir: &Path) -> Result<ExistingJob, AsmError> {
-    let kpi_bytes = fs::read(job_dir.join("kpi.json")).map_err(|err| io_error("kpi_read", err))?;
-    let hashes_bytes =
-        fs::read(job_dir.join("hashes.json")).map_err(|err| io_error("hashes_read", err))?;
-    let status_bytes =
-        fs::read(job_dir.join("status.json")).map_err(|err| io_error("status_read", err))?;
+    let kpi_bytes =
+        fs::read(job_dir.join("kpi.json")).map_err(|err| io_error(ErrorCode::KpiRead, err))?;
+    let hashes_bytes = fs::read(job_dir.join("hashes.json"))
+        .map_err(|err| io_error(ErrorCode::HashesRead, err))?;
+    let status_bytes = fs::read(job_dir.join("status.json"))
+        .map_err(|err| io_error(ErrorCode::StatusRead, err))?;
     let kpi = from_json_slice(&kpi_bytes)?;
     let hashes: StageHashes = from_json_slice(&hashes_bytes)?;
     let status: JobStatus = from_json_slice(&status_bytes)?;
@@ -268,8 +586,55 @@ fn load_existing_job(job_dir: &Path) -> Result<ExistingJob, AsmError> {
     })
 }
 
+/// Recomputes the completed job's stage outputs and returns the persisted
+/// artefacts whose canonical bytes differ from the recomputation.
+fn mismatched_artefacts(
+    plan: &Plan,
+    job_dir: &Path,
+    seed: u64,
+    rule: &RuleSpec,
+    status: &JobStatus,
+    opts: &RunOpts,
+) -> Result<Vec<String>, AsmError> {
+    let mut outputs = synthesise_stage_outputs(
+        derive_seed(plan.seed_derivation, seed, rule.id, status.attempts),
+        rule.id,
+        plan.sampler.sweeps,
+        plan.spectrum.modes,
+        plan.spectrum.k_points,
+    )?;
+    extract_extra_kpis(&opts.extractors, job_dir, &mut outputs)?;
+    let mut expected = vec![
+        ("kpi.json", to_canonical_json_bytes(&outputs.kpi)?),
+        ("hashes.json", to_canonical_json_bytes(&outputs.hashes)?),
+    ];
+    if plan.outputs.keep_intermediate {
+        expected.push((
+            "mcmc/manifest.json",
+            to_canonical_json_bytes(&outputs.mcmc)?,
+        ));
+        expected.push((
+            "spectrum/spectrum_report.json",
+            to_canonical_json_bytes(&outputs.spectrum)?,
+        ));
+        expected.push((
+            "gauge/gauge_report.json",
+            to_canonical_json_bytes(&outputs.gauge)?,
+        ));
+        expected.push((
+            "interact/interaction_report.json",
+            to_canonical_json_bytes(&outputs.interaction)?,
+        ));
+    }
+    Ok(expected
+        .into_iter()
+        .filter(|(path, bytes)| fs::read(job_dir.join(path)).ok().as_ref() != Some(bytes))
+        .map(|(path, _)| path.to_string())
+        .collect())
+}
+
 fn persist_failure(job_dir: &Path, status: &JobStatus) -> Result<(), AsmError> {
-    fs::create_dir_all(job_dir).map_err(|err| io_error("job_dir", err))?;
+    fs::create_dir_all(job_dir).map_err(|err| io_error(ErrorCode::JobDir, err))?;
     cleanup_incomplete(job_dir);
     write_json(job_dir.join("status.json"), status)?;
     Ok(())
@@ -281,11 +646,45 @@ fn cleanup_incomplete(job_dir: &Path) {
     let _ = fs::remove_file(job_dir.join("filters.json"));
 }
 
-fn derive_seed(seed: u64, attempt: u32) -> u64 {
+/// Returns the base stage seed for the job pairing plan `seed` with `rule_id`.
+///
+/// The rule id is passed through a SplitMix64 finaliser, folded into the seed,
+/// and the result mixed again, so each `(seed, rule_id)` pair draws from its own
+/// substream instead of sharing the plan seed with every other rule.
+pub fn job_seed(seed: u64, rule_id: u64) -> u64 {
+    splitmix64(seed ^ splitmix64(rule_id))
+}
+
+pub(crate) fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// Returns the stage seed for `attempt` of the job pairing `seed` with
+/// `rule_id` under [`SeedDerivation::Counter`].
+///
+/// The rule id selects the counter stream of the plan seed and the attempt
+/// selects the block within it, so every job and retry addresses its seed
+/// directly and no two `(rule_id, attempt)` pairs of a seed share a block.
+pub fn counter_job_seed(seed: u64, rule_id: u64, attempt: u32) -> u64 {
+    let mut rng = CounterRng::new(seed, rule_id);
+    rng.seek(u64::from(attempt.saturating_sub(1)));
+    rng.next_u64()
+}
+
+/// Under [`SeedDerivation::Mix`], retries perturb the per-rule base seed by
+/// attempt; the first attempt uses [`job_seed`] unchanged.
+fn derive_seed(derivation: SeedDerivation, seed: u64, rule_id: u64, attempt: u32) -> u64 {
+    if derivation == SeedDerivation::Counter {
+        return counter_job_seed(seed, rule_id, attempt);
+    }
+    let base = job_seed(seed, rule_id);
     if attempt <= 1 {
-        seed
+        base
     } else {
-        seed ^ ((attempt - 1) as u64).wrapping_mul(0x9e3779b97f4a7c15)
+        base ^ ((attempt - 1) as u64).wrapping_mul(0x9e3779b97f4a7c15)
     }
 }
 
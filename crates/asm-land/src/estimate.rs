@@ -0,0 +1,244 @@
+use std::collections::BTreeMap;
+
+use asm_core::errors::{AsmError, ErrorCode, ErrorInfo};
+use serde::{Deserialize, Serialize};
+
+use crate::plan::{OutputLayout, Plan};
+
+/// Rounds cost projections for deterministic serialisation.
+pub(crate) fn round_seconds(value: f64) -> f64 {
+    (value * 1e9).round() / 1e9
+}
+
+/// Stage identifiers used as keys in [`PlanEstimate::stages`].
+pub const STAGES: [&str; 5] = ["mcmc", "spectrum", "gauge", "interact", "persist"];
+
+/// Seconds-per-unit cost table used to project wall-time.
+///
+/// Defaults are calibrated from the synthetic smoke benchmarks and are only
+/// intended to give an order-of-magnitude budget.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageCosts {
+    /// Seconds per node-sweep (`graph.size * sampler.sweeps` units).
+    #[serde(default = "StageCosts::default_mcmc")]
+    pub mcmc: f64,
+    /// Seconds per mode and k-point (`spectrum.modes * spectrum.k_points` units).
+    #[serde(default = "StageCosts::default_spectrum")]
+    pub spectrum: f64,
+    /// Seconds per job for gauge analysis.
+    #[serde(default = "StageCosts::default_gauge")]
+    pub gauge: f64,
+    /// Seconds per interaction integration step (`interact.steps` units).
+    #[serde(default = "StageCosts::default_interact")]
+    pub interact: f64,
+    /// Seconds per persisted artefact.
+    #[serde(default = "StageCosts::default_persist")]
+    pub persist: f64,
+}
+
+impl StageCosts {
+    fn default_mcmc() -> f64 {
+        2.5e-6
+    }
+
+    fn default_spectrum() -> f64 {
+        4.0e-4
+    }
+
+    fn default_gauge() -> f64 {
+        0.05
+    }
+
+    fn default_interact() -> f64 {
+        1.5e-3
+    }
+
+    fn default_persist() -> f64 {
+        2.0e-3
+    }
+
+    pub(crate) fn get(&self, stage: &str) -> f64 {
+        match stage {
+            "mcmc" => self.mcmc,
+            "spectrum" => self.spectrum,
+            "gauge" => self.gauge,
+            "interact" => self.interact,
+            _ => self.persist,
+        }
+    }
+
+    /// Ensures every entry is finite and non-negative.
+    pub fn validate(&self) -> Result<(), AsmError> {
+        for stage in STAGES {
+            let value = self.get(stage);
+            if !value.is_finite() || value < 0.0 {
+                return Err(AsmError::Serde(ErrorInfo::new(
+                    ErrorCode::EstimateCost,
+                    format!("stage `{stage}` cost must be finite and non-negative, got {value}"),
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for StageCosts {
+    fn default() -> Self {
+        Self {
+            mcmc: Self::default_mcmc(),
+            spectrum: Self::default_spectrum(),
+            gauge: Self::default_gauge(),
+            interact: Self::default_interact(),
+            persist: Self::default_persist(),
+        }
+    }
+}
+
+/// Projected size of a single artefact written for every job.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtefactEstimate {
+    /// Path relative to the job directory.
+    pub path: String,
+    /// Stage that produces the artefact.
+    pub stage: String,
+    /// Projected size in bytes.
+    pub bytes: u64,
+    /// Whether the artefact is only kept when `keep_intermediate` is set.
+    pub intermediate: bool,
+}
+
+/// Per-stage projection aggregated over all jobs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageEstimate {
+    /// Work units processed per job.
+    pub units_per_job: u64,
+    /// Seconds charged per unit.
+    pub seconds_per_unit: f64,
+    /// Total seconds across all jobs.
+    pub seconds: f64,
+    /// Total bytes written by the stage across all jobs.
+    pub bytes: u64,
+}
+
+/// Disk projection for a top-level directory of the output layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectoryEstimate {
+    /// Number of jobs stored beneath the directory.
+    pub jobs: u64,
+    /// Total bytes stored beneath the directory.
+    pub bytes: u64,
+}
+
+/// Dry-run projection of job count, disk footprint, and wall-time for a plan.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanEstimate {
+    /// Hash of the plan being estimated.
+    pub plan_hash: String,
+    /// Total number of jobs enumerated.
+    pub jobs: u64,
+    /// Output layout recorded in the plan.
+    pub layout: OutputLayout,
+    /// Whether intermediate stage artefacts are counted.
+    pub keep_intermediate: bool,
+    /// Worker count assumed for the wall-time projection, after applying any
+    /// memory budget.
+    pub concurrency: usize,
+    /// Projected resident memory of a single running job.
+    pub job_memory_bytes: u64,
+    /// Artefacts written per job; dropped intermediates are omitted.
+    pub artefacts: Vec<ArtefactEstimate>,
+    /// Breakdown keyed by stage identifier.
+    pub stages: BTreeMap<String, StageEstimate>,
+    /// Breakdown keyed by top-level directory under the output root.
+    pub directories: BTreeMap<String, DirectoryEstimate>,
+    /// Size of the aggregated `landscape_report.json`.
+    pub report_bytes: u64,
+    /// Projected bytes across all jobs and the landscape report.
+    pub total_bytes: u64,
+    /// Projected single-worker seconds across all jobs.
+    pub cpu_seconds: f64,
+    /// Projected wall-clock seconds given [`PlanEstimate::concurrency`].
+    pub wall_seconds: f64,
+    /// Cost table used for the projection.
+    pub costs: StageCosts,
+}
+
+/// Returns the per-job artefact size model for the plan.
+///
+/// Sizes are linear fits against canonical JSON emitted by the synthetic
+/// stages; intermediate entries are filtered out when `keep_intermediate` is
+/// false.
+pub fn artefact_model(plan: &Plan) -> Vec<ArtefactEstimate> {
+    let checkpoints = u64::from(plan.sampler.checkpoints);
+    let modes = u64::from(plan.spectrum.modes);
+    let k_points = u64::from(plan.spectrum.k_points);
+    let artefacts = [
+        ("mcmc/manifest.json", "mcmc", 96 + 48 * checkpoints, true),
+        (
+            "spectrum/spectrum_report.json",
+            "spectrum",
+            80 + 24 * modes + 8 * modes * k_points,
+            true,
+        ),
+        ("gauge/gauge_report.json", "gauge", 112, true),
+        ("interact/interaction_report.json", "interact", 144, true),
+        ("kpi.json", "persist", 420, false),
+        ("hashes.json", "persist", 300, false),
+        ("filters.json", "persist", 180, false),
+        ("status.json", "persist", 96, false),
+    ];
+    artefacts
+        .into_iter()
+        .filter(|(_, _, _, intermediate)| plan.outputs.keep_intermediate || !intermediate)
+        .map(|(path, stage, bytes, intermediate)| ArtefactEstimate {
+            path: path.to_string(),
+            stage: stage.to_string(),
+            bytes,
+            intermediate,
+        })
+        .collect()
+}
+
+/// Returns the number of work units each stage processes for a single job.
+pub fn stage_units(plan: &Plan, artefacts: &[ArtefactEstimate]) -> BTreeMap<String, u64> {
+    let units = [
+        (
+            "mcmc",
+            u64::from(plan.graph.size) * u64::from(plan.sampler.sweeps),
+        ),
+        (
+            "spectrum",
+            u64::from(plan.spectrum.modes) * u64::from(plan.spectrum.k_points),
+        ),
+        ("gauge", 1),
+        ("interact", u64::from(plan.interact.steps)),
+        ("persist", artefacts.len() as u64),
+    ];
+    units
+        .into_iter()
+        .map(|(stage, units)| (stage.to_string(), units))
+        .collect()
+}
+
+/// Resident bytes every job holds regardless of plan size.
+pub(crate) const JOB_BASE_MEMORY_BYTES: u64 = 64 * 1024;
+
+/// Returns the projected peak resident memory of a single job.
+///
+/// Charges each graph node and spectrum sample, the interaction trajectory,
+/// and buffers for every artefact written by the job.
+pub fn job_memory_bytes(plan: &Plan) -> u64 {
+    let graph = 256 * u64::from(plan.graph.size);
+    let spectrum = 16 * u64::from(plan.spectrum.modes) * u64::from(plan.spectrum.k_points);
+    let interact = 64 * u64::from(plan.interact.steps);
+    let artefacts: u64 = artefact_model(plan)
+        .iter()
+        .map(|artefact| artefact.bytes)
+        .sum();
+    JOB_BASE_MEMORY_BYTES + graph + spectrum + interact + artefacts
+}
+
+/// Bytes contributed to `landscape_report.json` by its header and provenance.
+pub(crate) const REPORT_BASE_BYTES: u64 = 1024;
+/// Bytes contributed to `landscape_report.json` by each job entry.
+pub(crate) const REPORT_JOB_BYTES: u64 = 900;
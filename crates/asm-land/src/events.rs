@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use asm_core::errors::{AsmError, ErrorCode, ErrorInfo};
+use serde::{Deserialize, Serialize};
+
+use crate::report::{load_events, provenance_timestamp};
+use crate::serde::to_canonical_json_bytes;
+
+/// Lifecycle point recorded by a [`JobEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JobEventKind {
+    /// The job began its first attempt.
+    Start,
+    /// An attempt failed with a retryable error and another attempt follows.
+    Retry,
+    /// The job completed and its artefacts were persisted.
+    Finish,
+    /// The job gave up after its final attempt.
+    Failure,
+    /// A completed job was reused by `resume` without executing.
+    Resumed,
+}
+
+/// One line of a run's `events.jsonl`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobEvent {
+    /// Index of the job in plan enumeration order.
+    pub job: usize,
+    /// Logical sequence number of the event within its job, starting at zero.
+    pub seq: u32,
+    /// Lifecycle point the event records.
+    pub kind: JobEventKind,
+    /// Wall-clock time the event was recorded, pinned like the report's
+    /// provenance timestamp.
+    pub timestamp: String,
+    /// Plan seed of the job.
+    pub seed: u64,
+    /// Rule identifier of the job.
+    pub rule_id: u64,
+    /// Attempt the event refers to, starting at one.
+    pub attempt: u32,
+    /// Seconds spent in each worker stage of the attempt.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub stages: BTreeMap<String, f64>,
+    /// Error that ended the attempt, for retry and failure events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+fn write_error(path: &Path, err: impl ToString) -> AsmError {
+    AsmError::Serde(
+        ErrorInfo::new(ErrorCode::EventLogWrite, err.to_string())
+            .with_context("path", path.display().to_string()),
+    )
+}
+
+/// Append-only event log shared by the workers of a run.
+///
+/// Events are appended as they happen, so a crashed run still leaves a
+/// readable log. [`EventLog::finish`] rewrites the file ordered by
+/// `(job, seq)`, which makes the final file independent of thread scheduling.
+pub(crate) struct EventLog {
+    path: PathBuf,
+    deterministic_time: bool,
+    file: Mutex<File>,
+    error: Mutex<Option<AsmError>>,
+}
+
+impl EventLog {
+    /// Truncates `path` and opens it for appending.
+    pub(crate) fn create(path: PathBuf, deterministic_time: bool) -> Result<Self, AsmError> {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&path)
+            .map_err(|err| write_error(&path, err))?;
+        Ok(Self {
+            path,
+            deterministic_time,
+            file: Mutex::new(file),
+            error: Mutex::new(None),
+        })
+    }
+
+    /// Starts recording events for the job at `index`.
+    pub(crate) fn job(&self, index: usize, seed: u64, rule_id: u64) -> JobEvents<'_> {
+        JobEvents {
+            log: self,
+            job: index,
+            seed,
+            rule_id,
+            seq: 0,
+            attempt: 0,
+        }
+    }
+
+    /// Appends one event. Write failures do not interrupt the job; the first
+    /// one is kept and reported by [`EventLog::finish`].
+    fn append(&self, event: &JobEvent) {
+        let result = to_canonical_json_bytes(event).and_then(|mut line| {
+            line.push(b'\n');
+            let mut file = self.file.lock().unwrap_or_else(|err| err.into_inner());
+            file.write_all(&line)
+                .map_err(|err| write_error(&self.path, err))
+        });
+        if let Err(err) = result {
+            self.error
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .get_or_insert(err);
+        }
+    }
+
+    /// Rewrites the log ordered by `(job, seq)`.
+    pub(crate) fn finish(self) -> Result<(), AsmError> {
+        if let Some(err) = self
+            .error
+            .into_inner()
+            .unwrap_or_else(|err| err.into_inner())
+        {
+            return Err(err);
+        }
+        let file = self
+            .file
+            .into_inner()
+            .unwrap_or_else(|err| err.into_inner());
+        file.sync_all()
+            .map_err(|err| write_error(&self.path, err))?;
+        drop(file);
+
+        let mut events = load_events(&self.path)?;
+        events.sort_by_key(|event| (event.job, event.seq));
+        let mut bytes = Vec::new();
+        for event in &events {
+            bytes.extend(to_canonical_json_bytes(event)?);
+            bytes.push(b'\n');
+        }
+        let sorted = self.path.with_extension("jsonl.partial");
+        fs::write(&sorted, bytes)
+            .and_then(|_| fs::rename(&sorted, &self.path))
+            .map_err(|err| write_error(&self.path, err))
+    }
+}
+
+/// Per-job handle assigning logical sequence numbers to its events.
+pub(crate) struct JobEvents<'a> {
+    log: &'a EventLog,
+    job: usize,
+    seed: u64,
+    rule_id: u64,
+    seq: u32,
+    attempt: u32,
+}
+
+impl JobEvents<'_> {
+    /// Attempt of the most recent event, or one before any was recorded.
+    pub(crate) fn last_attempt(&self) -> u32 {
+        self.attempt.max(1)
+    }
+
+    /// Records an event for `attempt` with the given stage durations and error.
+    pub(crate) fn record(
+        &mut self,
+        kind: JobEventKind,
+        attempt: u32,
+        stages: &StageTimer,
+        error: Option<String>,
+    ) {
+        let event = JobEvent {
+            job: self.job,
+            seq: self.seq,
+            kind,
+            timestamp: provenance_timestamp(self.log.deterministic_time),
+            seed: self.seed,
+            rule_id: self.rule_id,
+            attempt,
+            stages: stages.durations.clone(),
+            error,
+        };
+        self.seq += 1;
+        self.attempt = attempt;
+        self.log.append(&event);
+    }
+}
+
+/// Wall-clock durations of the worker stages of one attempt.
+#[derive(Debug, Default)]
+pub(crate) struct StageTimer {
+    durations: BTreeMap<String, f64>,
+}
+
+impl StageTimer {
+    /// Runs `stage`, recording its duration in seconds under `name`.
+    pub(crate) fn time<T>(&mut self, name: &str, stage: impl FnOnce() -> T) -> T {
+        let started = Instant::now();
+        let result = stage();
+        *self.durations.entry(name.to_string()).or_default() += started.elapsed().as_secs_f64();
+        result
+    }
+}
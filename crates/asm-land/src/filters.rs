@@ -1,13 +1,13 @@
 use std::fs;
 use std::path::Path;
 
-use asm_core::errors::{AsmError, ErrorInfo};
+use asm_core::errors::{AsmError, ErrorCode, ErrorInfo};
 use serde::{Deserialize, Serialize};
 
 use crate::metrics::JobKpi;
 use crate::serde::from_yaml_slice;
 
-fn io_error(code: &str, err: impl ToString) -> AsmError {
+fn io_error(code: ErrorCode, err: impl ToString) -> AsmError {
     AsmError::Serde(ErrorInfo::new(code, err.to_string()))
 }
 
@@ -32,6 +32,222 @@ pub struct FilterSpec {
     /// Required factors that must be present in the gauge summary.
     #[serde(default)]
     pub factor_presence: Vec<String>,
+    /// Whether the gauge content must be free of cubic anomalies.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub require_anomaly_free: bool,
+    /// Optional boolean expression replacing the implicit AND of the flat thresholds.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "serde_yaml::with::singleton_map_recursive"
+    )]
+    pub expr: Option<FilterExpr>,
+}
+
+/// Built-in KPIs addressable from [`FilterLeaf::Threshold`]. Extracted KPIs
+/// are addressed by their namespaced `<extractor>.<kpi>` key.
+pub const FILTER_KPIS: [&str; 6] = [
+    "c_est",
+    "gap_proxy",
+    "xi",
+    "energy_final",
+    "lambda_h",
+    "lightest_species_gap",
+];
+
+/// Comparison operator used by threshold leaves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterCmp {
+    /// Strictly greater than.
+    Gt,
+    /// Greater than or equal.
+    Ge,
+    /// Strictly less than.
+    Lt,
+    /// Less than or equal.
+    Le,
+}
+
+impl FilterCmp {
+    fn apply(self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            FilterCmp::Gt => lhs > rhs,
+            FilterCmp::Ge => lhs >= rhs,
+            FilterCmp::Lt => lhs < rhs,
+            FilterCmp::Le => lhs <= rhs,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            FilterCmp::Gt => ">",
+            FilterCmp::Ge => ">=",
+            FilterCmp::Lt => "<",
+            FilterCmp::Le => "<=",
+        }
+    }
+}
+
+/// Atomic predicate evaluated against a KPI snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterLeaf {
+    /// Implicit AND of the flat thresholds in the enclosing [`FilterSpec`].
+    Flat,
+    /// Gauge closure passed.
+    Closure,
+    /// Ward identities passed.
+    Ward,
+    /// Gauge content is anomaly free.
+    AnomalyFree,
+    /// Gauge factor label is present.
+    Factor(String),
+    /// Numeric comparison `kpi <op> value`.
+    Threshold {
+        /// KPI name from [`FILTER_KPIS`] or a namespaced extracted KPI.
+        kpi: String,
+        /// Comparison operator.
+        op: FilterCmp,
+        /// Right-hand side of the comparison.
+        value: f64,
+    },
+}
+
+impl FilterLeaf {
+    fn label(&self) -> String {
+        match self {
+            FilterLeaf::Flat => "flat".to_string(),
+            FilterLeaf::Closure => "closure".to_string(),
+            FilterLeaf::Ward => "ward".to_string(),
+            FilterLeaf::AnomalyFree => "anomaly_free".to_string(),
+            FilterLeaf::Factor(name) => format!("factor {name}"),
+            FilterLeaf::Threshold { kpi, op, value } => {
+                format!("{kpi} {} {value}", op.symbol())
+            }
+        }
+    }
+}
+
+/// Boolean combination of [`FilterLeaf`] predicates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterExpr {
+    /// All children must pass.
+    And(Vec<FilterExpr>),
+    /// At least one child must pass.
+    Or(Vec<FilterExpr>),
+    /// The child must fail.
+    Not(Box<FilterExpr>),
+    /// Atomic predicate.
+    Leaf(FilterLeaf),
+}
+
+impl FilterExpr {
+    /// Rejects empty combinators and KPI names that are neither built in nor
+    /// namespaced.
+    pub fn validate(&self) -> Result<(), AsmError> {
+        match self {
+            FilterExpr::And(children) | FilterExpr::Or(children) => {
+                if children.is_empty() {
+                    return Err(io_error(
+                        ErrorCode::FilterExpr,
+                        "`and`/`or` require at least one child",
+                    ));
+                }
+                children.iter().try_for_each(FilterExpr::validate)
+            }
+            FilterExpr::Not(child) => child.validate(),
+            FilterExpr::Leaf(FilterLeaf::Threshold { kpi, .. }) if !is_filter_kpi(kpi) => Err(
+                io_error(ErrorCode::FilterExpr, format!("unknown filter KPI `{kpi}`")),
+            ),
+            FilterExpr::Leaf(_) => Ok(()),
+        }
+    }
+
+    fn evaluate(
+        &self,
+        path: String,
+        kpi: &JobKpi,
+        flat: bool,
+        leaves: &mut Vec<LeafDecision>,
+    ) -> bool {
+        match self {
+            // Children are collected eagerly so every leaf is recorded, not just
+            // the ones reached before short-circuiting.
+            FilterExpr::And(children) => children
+                .iter()
+                .enumerate()
+                .map(|(idx, child)| child.evaluate(format!("{path}and.{idx}."), kpi, flat, leaves))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .all(|pass| pass),
+            FilterExpr::Or(children) => children
+                .iter()
+                .enumerate()
+                .map(|(idx, child)| child.evaluate(format!("{path}or.{idx}."), kpi, flat, leaves))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .any(|pass| pass),
+            FilterExpr::Not(child) => !child.evaluate(format!("{path}not."), kpi, flat, leaves),
+            FilterExpr::Leaf(leaf) => {
+                let pass = match leaf {
+                    FilterLeaf::Flat => flat,
+                    FilterLeaf::Closure => kpi.closure_pass,
+                    FilterLeaf::Ward => kpi.ward_pass,
+                    FilterLeaf::AnomalyFree => kpi.anomaly_free.unwrap_or(false),
+                    FilterLeaf::Factor(name) => kpi.factors.iter().any(|f| f == name),
+                    FilterLeaf::Threshold {
+                        kpi: name,
+                        op,
+                        value,
+                    } => kpi_value(kpi, name)
+                        .map(|lhs| op.apply(lhs, *value))
+                        .unwrap_or(false),
+                };
+                leaves.push(LeafDecision {
+                    path: format!("{path}leaf"),
+                    label: leaf.label(),
+                    pass,
+                });
+                pass
+            }
+        }
+    }
+}
+
+/// Returns true for built-in KPI names and `<extractor>.<kpi>` keys.
+fn is_filter_kpi(name: &str) -> bool {
+    FILTER_KPIS.contains(&name) || is_namespaced(name)
+}
+
+/// Returns true for `<extractor>.<kpi>` keys of [`JobKpi::extra`].
+pub(crate) fn is_namespaced(name: &str) -> bool {
+    name.split_once('.')
+        .is_some_and(|(extractor, kpi)| !extractor.is_empty() && !kpi.is_empty())
+}
+
+fn kpi_value(kpi: &JobKpi, name: &str) -> Option<f64> {
+    match name {
+        "c_est" => Some(kpi.c_est),
+        "gap_proxy" => Some(kpi.gap_proxy),
+        "xi" => Some(kpi.xi),
+        "energy_final" => Some(kpi.energy_final),
+        "lambda_h" => Some(kpi.lambda_h),
+        "lightest_species_gap" => kpi.lightest_species_gap,
+        _ => kpi.extra.get(name).copied(),
+    }
+}
+
+/// Outcome of a single leaf in a [`FilterExpr`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeafDecision {
+    /// Dotted position of the leaf within the expression tree.
+    pub path: String,
+    /// Human readable description of the predicate.
+    pub label: String,
+    /// Whether the predicate held.
+    pub pass: bool,
 }
 
 impl FilterSpec {
@@ -69,13 +285,26 @@ impl FilterSpec {
             .factor_presence
             .iter()
             .all(|factor| kpi.factors.iter().any(|f| f == factor));
-        FilterDecision {
+        let anomaly_free = self
+            .require_anomaly_free
+            .then(|| kpi.anomaly_free.unwrap_or(false));
+        let mut decision = FilterDecision {
             closure,
             ward,
             c_range,
             gap_ok,
             factors: factors_ok,
+            anomaly_free,
+            leaves: Vec::new(),
+            verdict: None,
+        };
+        if let Some(expr) = &self.expr {
+            let flat = decision.flat_passes();
+            let mut leaves = Vec::new();
+            decision.verdict = Some(expr.evaluate(String::new(), kpi, flat, &mut leaves));
+            decision.leaves = leaves;
         }
+        decision
     }
 }
 
@@ -92,17 +321,40 @@ pub struct FilterDecision {
     pub gap_ok: bool,
     /// Factor presence predicate result.
     pub factors: bool,
+    /// Anomaly predicate result; absent when the filter does not require it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anomaly_free: Option<bool>,
+    /// Per-leaf outcomes of [`FilterSpec::expr`], in evaluation order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub leaves: Vec<LeafDecision>,
+    /// Combined verdict of [`FilterSpec::expr`], when present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verdict: Option<bool>,
 }
 
 impl FilterDecision {
-    /// Returns true when all predicates succeed.
+    /// Returns the expression verdict, or the flat predicates when no expression is set.
     pub fn passes(&self) -> bool {
-        self.closure && self.ward && self.c_range && self.gap_ok && self.factors
+        self.verdict.unwrap_or_else(|| self.flat_passes())
+    }
+
+    /// Returns true when all flat predicates succeed.
+    pub fn flat_passes(&self) -> bool {
+        self.closure
+            && self.ward
+            && self.c_range
+            && self.gap_ok
+            && self.factors
+            && self.anomaly_free.unwrap_or(true)
     }
 }
 
 /// Loads a filter specification from the provided YAML path.
 pub fn load_filters(path: &Path) -> Result<FilterSpec, AsmError> {
-    let bytes = fs::read(path).map_err(|err| io_error("filter_read", err))?;
-    from_yaml_slice(&bytes)
+    let bytes = fs::read(path).map_err(|err| io_error(ErrorCode::FilterRead, err))?;
+    let spec: FilterSpec = from_yaml_slice(&bytes)?;
+    if let Some(expr) = &spec.expr {
+        expr.validate()?;
+    }
+    Ok(spec)
 }
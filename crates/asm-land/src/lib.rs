@@ -3,6 +3,10 @@
 
 /// Stage orchestration and resume logic.
 pub mod dispatch;
+/// Dry-run disk and wall-time projections for plans.
+pub mod estimate;
+/// Structured per-job event log written during runs.
+pub mod events;
 /// Anthropic filter helpers.
 pub mod filters;
 /// Canonical hashing helpers.
@@ -20,14 +24,25 @@ pub mod stages;
 /// Statistical aggregation primitives.
 pub mod stat;
 
-pub use dispatch::{run_plan, run_plan_from_path, RunOpts};
-pub use filters::{load_filters, FilterDecision, FilterSpec};
+pub use dispatch::{
+    counter_job_seed, effective_concurrency, estimate_plan, estimate_plan_with_costs, job_seed,
+    run_plan, run_plan_from_path, AttemptHook, ResumeVerification, RunOpts,
+};
+pub use estimate::{ArtefactEstimate, DirectoryEstimate, PlanEstimate, StageCosts, StageEstimate};
+pub use events::{JobEvent, JobEventKind};
+pub use filters::{load_filters, FilterDecision, FilterExpr, FilterLeaf, FilterSpec};
+pub use metrics::{JsonPointerExtractor, KpiExtractor, KpiExtractors};
 pub use plan::{
     load_plan, CodeSpec, GraphSpec, InteractSpec, OutputLayout, OutputSpec, Plan, RuleSpec,
-    SamplerSpec, SpectrumSpec,
+    SamplerSpec, SeedDerivation, SpectrumSpec,
 };
 pub use report::{
-    build_atlas, summarize, Atlas, AtlasEntry, AtlasOpts, JobReport, JobState, JobStatus,
-    LandscapeReport, SummaryReport,
+    build_atlas, build_atlas_streaming, diff_runs, load_events, stage_duration_quantiles,
+    summarize, summarize_with_opts, summarize_with_significance, Atlas, AtlasEntry, AtlasOpts,
+    AtlasStreamSummary, JobDiff, JobReport, JobState, JobStatus, KpiShift, LandscapeDiff,
+    LandscapeReport, PassRateShift, SummaryReport,
+};
+pub use stat::{
+    BootstrapOpts, Correlations, Histogram, QuantileBands, QuantilePoint, Quantiles,
+    StatsAccumulator, StatsOpts, StatsSummary,
 };
-pub use stat::{Correlations, Histogram, Quantiles, StatsSummary};
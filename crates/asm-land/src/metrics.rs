@@ -1,4 +1,31 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use asm_core::errors::{AsmError, ErrorCode, ErrorInfo};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::serde::from_json_slice;
+
+/// Artefact kinds produced by the built-in stages and their job-relative paths.
+pub const STAGE_ARTEFACTS: [(&str, &str); 4] = [
+    ("mcmc", "mcmc/manifest.json"),
+    ("spectrum", "spectrum/spectrum_report.json"),
+    ("gauge", "gauge/gauge_report.json"),
+    ("interact", "interact/interaction_report.json"),
+];
+
+/// Returns the job-relative path of an artefact kind. Kinds other than the
+/// [`STAGE_ARTEFACTS`] are interpreted as job-relative paths themselves.
+pub fn artefact_path(kind: &str) -> PathBuf {
+    STAGE_ARTEFACTS
+        .iter()
+        .find(|(name, _)| *name == kind)
+        .map_or(kind, |(_, path)| path)
+        .into()
+}
 
 /// Deterministic KPI snapshot extracted from a job's artefacts.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -17,10 +44,19 @@ pub struct JobKpi {
     pub ward_pass: bool,
     /// Factor labels detected in the gauge stage.
     pub factors: Vec<String>,
+    /// Whether the gauge content is free of cubic anomalies, when checked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anomaly_free: Option<bool>,
     /// Selected gauge couplings at the reference scale.
     pub g: Vec<f64>,
     /// Higgs self coupling estimate.
     pub lambda_h: f64,
+    /// Gap proxy of the lightest defect species, when a species scan ran.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lightest_species_gap: Option<f64>,
+    /// KPIs from registered [`KpiExtractor`]s, keyed `<extractor>.<kpi>`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra: BTreeMap<String, f64>,
 }
 
 impl JobKpi {
@@ -39,6 +75,7 @@ impl JobKpi {
         } else {
             vec!["u1".to_string()]
         };
+        let anomaly_free = Some((base & 0b1000) == 0);
         let g1 = 0.1 + norm * 0.05;
         let g2 = 0.2 + norm * 0.05;
         let g3 = 0.3 + norm * 0.05;
@@ -51,8 +88,11 @@ impl JobKpi {
             closure_pass,
             ward_pass,
             factors,
+            anomaly_free,
             g: vec![g1, g2, g3],
             lambda_h,
+            lightest_species_gap: None,
+            extra: BTreeMap::new(),
         }
     }
 }
@@ -67,8 +107,168 @@ impl Default for JobKpi {
             closure_pass: false,
             ward_pass: false,
             factors: Vec::new(),
+            anomaly_free: None,
             g: Vec::new(),
             lambda_h: 0.0,
+            lightest_species_gap: None,
+            extra: BTreeMap::new(),
+        }
+    }
+}
+
+/// Derives additional KPIs from one kind of per-job artefact.
+pub trait KpiExtractor: Send + Sync {
+    /// Artefact kind read by the extractor: one of [`STAGE_ARTEFACTS`] or a
+    /// job-relative path.
+    fn artefact_kind(&self) -> &str;
+
+    /// Returns KPI values parsed from the artefact bytes.
+    fn extract(&self, bytes: &[u8]) -> Result<BTreeMap<String, f64>, AsmError>;
+}
+
+/// Extractor reading numeric fields of a JSON artefact by JSON pointer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPointerExtractor {
+    kind: String,
+    pointers: BTreeMap<String, String>,
+}
+
+impl JsonPointerExtractor {
+    /// Creates an extractor over `kind` without any KPIs.
+    pub fn new(kind: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            pointers: BTreeMap::new(),
+        }
+    }
+
+    /// Reports the number at `pointer` (RFC 6901) as KPI `name`.
+    pub fn with_kpi(mut self, name: impl Into<String>, pointer: impl Into<String>) -> Self {
+        self.pointers.insert(name.into(), pointer.into());
+        self
+    }
+}
+
+impl KpiExtractor for JsonPointerExtractor {
+    fn artefact_kind(&self) -> &str {
+        &self.kind
+    }
+
+    fn extract(&self, bytes: &[u8]) -> Result<BTreeMap<String, f64>, AsmError> {
+        let document: Value = from_json_slice(bytes)?;
+        self.pointers
+            .iter()
+            .map(|(name, pointer)| {
+                let value = document
+                    .pointer(pointer)
+                    .and_then(Value::as_f64)
+                    .ok_or_else(|| {
+                        extractor_error("JSON pointer does not resolve to a number", name)
+                            .with_context("pointer", pointer.clone())
+                    })?;
+                Ok((name.clone(), value))
+            })
+            .collect::<Result<_, ErrorInfo>>()
+            .map_err(AsmError::Serde)
+    }
+}
+
+fn extractor_error(message: &str, name: &str) -> ErrorInfo {
+    ErrorInfo::new(ErrorCode::KpiExtractor, message).with_context("name", name.to_string())
+}
+
+/// Named [`KpiExtractor`]s applied to every job of a landscape run.
+///
+/// Extractors run in name order; an empty registry leaves [`JobKpi::extra`]
+/// empty so the built-in KPIs are unchanged.
+#[derive(Clone, Default)]
+pub struct KpiExtractors {
+    entries: BTreeMap<String, Arc<dyn KpiExtractor>>,
+}
+
+impl fmt::Debug for KpiExtractors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.entries
+                    .iter()
+                    .map(|(name, extractor)| (name, extractor.artefact_kind())),
+            )
+            .finish()
+    }
+}
+
+impl KpiExtractors {
+    /// Registers `extractor` under `name`, which prefixes its KPIs.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        extractor: impl KpiExtractor + 'static,
+    ) -> Result<&mut Self, AsmError> {
+        let name = name.into();
+        if name.is_empty() {
+            return Err(AsmError::Serde(extractor_error(
+                "extractor name must not be empty",
+                &name,
+            )));
+        }
+        if self.entries.contains_key(&name) {
+            return Err(AsmError::Serde(
+                ErrorInfo::new(ErrorCode::KpiCollision, "extractor name already registered")
+                    .with_context("name", name),
+            ));
+        }
+        self.entries.insert(name, Arc::new(extractor));
+        Ok(self)
+    }
+
+    /// Returns true when no extractor is registered.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Artefact kinds read by the registered extractors, deduplicated and sorted.
+    pub fn artefact_kinds(&self) -> Vec<&str> {
+        let mut kinds: Vec<&str> = self
+            .entries
+            .values()
+            .map(|extractor| extractor.artefact_kind())
+            .collect();
+        kinds.sort_unstable();
+        kinds.dedup();
+        kinds
+    }
+
+    /// Runs every extractor whose artefact kind is present in `artefacts`
+    /// and merges the results under `<name>.<kpi>` keys.
+    pub fn extract(
+        &self,
+        artefacts: &BTreeMap<String, Vec<u8>>,
+    ) -> Result<BTreeMap<String, f64>, AsmError> {
+        let mut extra = BTreeMap::new();
+        for (name, extractor) in &self.entries {
+            let Some(bytes) = artefacts.get(extractor.artefact_kind()) else {
+                continue;
+            };
+            for (kpi, value) in extractor.extract(bytes)? {
+                let key = format!("{name}.{kpi}");
+                if !value.is_finite() {
+                    return Err(AsmError::Serde(
+                        extractor_error("extracted KPI must be finite", name)
+                            .with_context("kpi", key),
+                    ));
+                }
+                if extra.insert(key.clone(), value).is_some() {
+                    return Err(AsmError::Serde(
+                        ErrorInfo::new(
+                            ErrorCode::KpiCollision,
+                            "namespaced KPI produced by more than one extractor",
+                        )
+                        .with_context("kpi", key),
+                    ));
+                }
+            }
         }
+        Ok(extra)
     }
 }
@@ -1,13 +1,13 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use asm_core::errors::{AsmError, ErrorInfo};
+use asm_core::errors::{AsmError, ErrorCode, ErrorInfo};
 use serde::{Deserialize, Serialize};
 
 use crate::hash::stable_hash_string;
 use crate::serde::{from_yaml_slice, to_yaml_string};
 
-fn io_error(code: &str, err: impl ToString) -> AsmError {
+fn io_error(code: ErrorCode, err: impl ToString) -> AsmError {
     AsmError::Serde(ErrorInfo::new(code, err.to_string()))
 }
 
@@ -22,6 +22,25 @@ pub enum OutputLayout {
     PerSeed,
 }
 
+/// Rule used to derive each job's stage seed from the plan seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SeedDerivation {
+    /// SplitMix64 mixing of seed and rule id ([`crate::job_seed`]); retries
+    /// perturb the mixed seed by attempt.
+    #[default]
+    Mix,
+    /// Draw from the counter-based stream `rule_id` of the plan seed, one
+    /// block per attempt ([`crate::counter_job_seed`]).
+    Counter,
+}
+
+impl SeedDerivation {
+    fn is_mix(&self) -> bool {
+        *self == SeedDerivation::Mix
+    }
+}
+
 /// Output configuration for the landscape run.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct OutputSpec {
@@ -165,6 +184,10 @@ pub struct Plan {
     /// Rule variants to scan.
     #[serde(default)]
     pub rules: Vec<RuleSpec>,
+    /// How job stage seeds are derived; omitted from the plan (and its hash)
+    /// when left at the default.
+    #[serde(default, skip_serializing_if = "SeedDerivation::is_mix")]
+    pub seed_derivation: SeedDerivation,
     /// Directory containing the plan on disk (ignored when serializing).
     #[serde(skip)]
     pub base_dir: PathBuf,
@@ -203,7 +226,7 @@ impl Plan {
 /// Loads a plan from disk, ensuring deterministic ordering of seeds and rules.
 pub fn load_plan<P: AsRef<Path>>(path: P) -> Result<Plan, AsmError> {
     let plan_path = path.as_ref();
-    let bytes = fs::read(plan_path).map_err(|err| io_error("plan_read", err))?;
+    let bytes = fs::read(plan_path).map_err(|err| io_error(ErrorCode::PlanRead, err))?;
     let mut plan: Plan = from_yaml_slice(&bytes)?;
     plan.seeds.sort_unstable();
     plan.rules.sort_by_key(|rule| rule.id);
@@ -1,20 +1,26 @@
 use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use chrono::Utc;
+use chrono::{DateTime, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
-use asm_core::errors::{AsmError, ErrorInfo};
+use asm_core::errors::{AsmError, ErrorCode, ErrorInfo};
+use asm_core::json_diff::{canonical_json_diff, JsonDelta};
 use asm_core::provenance::RunProvenance;
 
+use crate::events::JobEvent;
 use crate::filters::{FilterDecision, FilterSpec};
 use crate::hash::stable_hash_string;
 use crate::metrics::JobKpi;
 use crate::plan::{GraphSpec, Plan};
-use crate::serde::from_json_slice;
+use crate::serde::{from_json_slice, to_canonical_json_bytes};
 use crate::stages::StageHashes;
-use crate::stat::{Correlations, Histogram, Quantiles, StatsSummary};
+use crate::stat::{
+    quantile_summary, Correlations, Histogram, Quantiles, StatsAccumulator, StatsOpts,
+    StatsSummary, DEFAULT_SIGNIFICANCE,
+};
 
 /// Status of an individual job within a landscape run.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -104,12 +110,24 @@ pub struct LandscapeReport {
 }
 
 impl LandscapeReport {
-    /// Constructs a new report from its constituent parts.
+    /// Constructs a new report from its constituent parts, stamped with the
+    /// wall-clock time.
     pub fn new(
         plan: &Plan,
         jobs: Vec<JobReport>,
         stats: StatsSummary,
         filters: FilterSpec,
+    ) -> Self {
+        Self::new_with_timestamp(plan, jobs, stats, filters, provenance_timestamp(false))
+    }
+
+    /// Constructs a new report whose provenance records `created_at`.
+    pub fn new_with_timestamp(
+        plan: &Plan,
+        jobs: Vec<JobReport>,
+        stats: StatsSummary,
+        filters: FilterSpec,
+        created_at: String,
     ) -> Self {
         let pass_count = jobs.iter().filter(|job| job.filters.passes()).count();
         let total = jobs.len();
@@ -123,12 +141,29 @@ impl LandscapeReport {
                 pass_count,
                 total,
             },
-            provenance: provenance(plan),
+            provenance: provenance(plan, created_at),
         }
     }
 }
 
-fn provenance(plan: &Plan) -> RunProvenance {
+/// Returns the provenance timestamp for a run.
+///
+/// A valid `SOURCE_DATE_EPOCH` (seconds since the Unix epoch) always wins, as
+/// in reproducible-build tooling. Otherwise `deterministic` pins the stamp to
+/// the Unix epoch and the wall clock is used when it is false.
+pub fn provenance_timestamp(deterministic: bool) -> String {
+    let pinned = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.trim().parse::<i64>().ok())
+        .or(deterministic.then_some(0))
+        .and_then(|secs| DateTime::<Utc>::from_timestamp(secs, 0));
+    match pinned {
+        Some(time) => time.to_rfc3339_opts(SecondsFormat::Secs, true),
+        None => Utc::now().to_rfc3339(),
+    }
+}
+
+fn provenance(plan: &Plan, created_at: String) -> RunProvenance {
     let mut versions = BTreeMap::new();
     versions.insert(
         "asm-land".to_string(),
@@ -140,7 +175,7 @@ fn provenance(plan: &Plan) -> RunProvenance {
         graph_hash: hash_graph(&plan.graph),
         code_hash: plan_hash,
         seed: plan.seeds.first().copied().unwrap_or_default(),
-        created_at: Utc::now().to_rfc3339(),
+        created_at,
         tool_versions: versions,
     }
 }
@@ -169,8 +204,40 @@ pub struct AtlasEntry {
     pub factors: Vec<String>,
     /// Coupling vector extracted from the interaction stage.
     pub couplings: Vec<f64>,
+    /// Number of jobs collapsed into this entry when deduplicating.
+    #[serde(
+        default = "AtlasEntry::default_multiplicity",
+        skip_serializing_if = "AtlasEntry::is_unique"
+    )]
+    pub multiplicity: usize,
+}
+
+impl AtlasEntry {
+    fn default_multiplicity() -> usize {
+        1
+    }
+
+    fn is_unique(multiplicity: &usize) -> bool {
+        *multiplicity == 1
+    }
+
+    /// Canonical identity used to detect structurally identical universes.
+    ///
+    /// Couplings are rounded to [`COUPLING_KEY_SCALE`] so numerically
+    /// indistinguishable values share a key.
+    pub fn dedup_key(&self) -> (String, String, Vec<i64>) {
+        let couplings = self
+            .couplings
+            .iter()
+            .map(|g| (g * COUPLING_KEY_SCALE).round() as i64)
+            .collect();
+        (self.graph_hash.clone(), self.code_hash.clone(), couplings)
+    }
 }
 
+/// Resolution applied to couplings when building [`AtlasEntry::dedup_key`].
+pub const COUPLING_KEY_SCALE: f64 = 1e6;
+
 /// Compact atlas manifest enumerating all universes.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Atlas {
@@ -182,11 +249,23 @@ pub struct Atlas {
     pub manifest: Vec<String>,
 }
 
+/// Totals produced by [`build_atlas_streaming`] once every entry has been emitted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AtlasStreamSummary {
+    /// Hash of the emitted entries; identical to [`Atlas::index_hash`] for the same run tree.
+    pub index_hash: String,
+    /// Number of entries emitted to the sink.
+    pub entries: usize,
+}
+
 /// Options controlling atlas construction.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct AtlasOpts {
     /// Include failed jobs when building the atlas.
     pub include_failed: bool,
+    /// Collapse entries sharing a [`AtlasEntry::dedup_key`] into one representative.
+    #[serde(default)]
+    pub deduplicate: bool,
 }
 
 /// Summary report aggregating statistics across multiple runs.
@@ -204,6 +283,10 @@ pub struct SummaryReport {
     pub correlations: BTreeMap<String, Correlations>,
     /// Free-form notes attached to the summary.
     pub notes: Vec<String>,
+    /// Quantiles of worker stage durations in seconds, from a run's event
+    /// log. See [`stage_duration_quantiles`].
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub stage_durations: BTreeMap<String, Quantiles>,
 }
 
 /// Total counts used in the summary report.
@@ -242,20 +325,66 @@ impl SummaryReport {
             quantiles: stats.quantiles.clone(),
             correlations: stats.correlations.clone(),
             notes: vec![],
+            stage_durations: BTreeMap::new(),
         }
     }
 }
 
-fn io_error(code: &str, err: impl ToString) -> AsmError {
+fn io_error(code: ErrorCode, err: impl ToString) -> AsmError {
     AsmError::Serde(ErrorInfo::new(code, err.to_string()))
 }
 
 fn load_report(root: &Path) -> Result<LandscapeReport, AsmError> {
     let path = root.join("landscape_report.json");
-    let bytes = fs::read(&path).map_err(|err| io_error("landscape_report_read", err))?;
+    let bytes = fs::read(&path).map_err(|err| io_error(ErrorCode::LandscapeReportRead, err))?;
     from_json_slice(&bytes)
 }
 
+/// File name of the event log written by [`run_plan`](crate::dispatch::run_plan)
+/// into the run's output directory.
+pub const EVENTS_FILE: &str = "events.jsonl";
+
+/// Loads the events of a run's `events.jsonl`, in file order.
+///
+/// Every non-empty line must parse as a [`JobEvent`]; the first malformed
+/// line fails with `event_log_read` naming its line number.
+pub fn load_events(path: &Path) -> Result<Vec<JobEvent>, AsmError> {
+    let text = fs::read_to_string(path).map_err(|err| {
+        AsmError::Serde(
+            ErrorInfo::new(ErrorCode::EventLogRead, err.to_string())
+                .with_context("path", path.display().to_string()),
+        )
+    })?;
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|err| {
+                AsmError::Serde(
+                    ErrorInfo::new(ErrorCode::EventLogRead, err.to_string())
+                        .with_context("path", path.display().to_string())
+                        .with_context("line", (index + 1).to_string()),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Computes per-stage duration quantiles over every event carrying stage
+/// timings, so retried attempts contribute alongside finished ones.
+pub fn stage_duration_quantiles(events: &[JobEvent]) -> BTreeMap<String, Quantiles> {
+    let mut durations: BTreeMap<&str, Vec<f64>> = BTreeMap::new();
+    for event in events {
+        for (stage, seconds) in &event.stages {
+            durations.entry(stage).or_default().push(*seconds);
+        }
+    }
+    durations
+        .into_iter()
+        .map(|(stage, values)| (stage.to_string(), quantile_summary(&values, None, &[])))
+        .collect()
+}
+
 /// Constructs an atlas manifest from the runs stored under the provided root.
 pub fn build_atlas(root: &Path, opts: &AtlasOpts) -> Result<Atlas, AsmError> {
     let report = load_report(root)?;
@@ -273,9 +402,13 @@ pub fn build_atlas(root: &Path, opts: &AtlasOpts) -> Result<Atlas, AsmError> {
             gap: job.kpis.gap_proxy,
             factors: job.kpis.factors.clone(),
             couplings: job.kpis.g.clone(),
+            multiplicity: 1,
         });
     }
     entries.sort_by(|a, b| a.id.cmp(&b.id));
+    if opts.deduplicate {
+        entries = deduplicate(entries);
+    }
     let index_hash = stable_hash_string(&entries)?;
     let manifest = entries.iter().map(|entry| entry.id.clone()).collect();
     Ok(Atlas {
@@ -285,15 +418,387 @@ pub fn build_atlas(root: &Path, opts: &AtlasOpts) -> Result<Atlas, AsmError> {
     })
 }
 
-/// Summarises metrics across the runs stored under the provided root.
+/// Constructs atlas entries directly from the per-job directories under `root`,
+/// handing each entry to `sink` as soon as it is built.
+///
+/// The aggregate `landscape_report.json` is not read. Only the sorted job
+/// identifiers and a running hash are held in memory, so the resulting
+/// [`AtlasStreamSummary::index_hash`] matches [`build_atlas`] without
+/// materialising the entries. [`AtlasOpts::deduplicate`] is rejected because
+/// it requires the full entry set.
+pub fn build_atlas_streaming<F>(
+    root: &Path,
+    opts: &AtlasOpts,
+    mut sink: F,
+) -> Result<AtlasStreamSummary, AsmError>
+where
+    F: FnMut(AtlasEntry) -> Result<(), AsmError>,
+{
+    if opts.deduplicate {
+        return Err(io_error(
+            ErrorCode::AtlasStreamDedup,
+            "deduplication needs every entry in memory; use build_atlas",
+        ));
+    }
+    let jobs = discover_job_dirs(root)?;
+    // The index hash is the digest of the canonical JSON array of entries, fed
+    // element by element so the array never needs to exist in memory.
+    let mut hasher = Sha256::new();
+    hasher.update(b"[");
+    let mut count = 0usize;
+    for (id, dir) in jobs {
+        let status: JobStatus = read_job_json(&dir.join("status.json"), ErrorCode::StatusRead)?;
+        if status.state != JobState::Complete && !opts.include_failed {
+            continue;
+        }
+        let (hashes, kpis) = if status.state == JobState::Complete {
+            (
+                read_job_json::<StageHashes>(&dir.join("hashes.json"), ErrorCode::HashesRead)?,
+                read_job_json::<JobKpi>(&dir.join("kpi.json"), ErrorCode::KpiRead)?,
+            )
+        } else {
+            (StageHashes::default(), JobKpi::default())
+        };
+        let entry = AtlasEntry {
+            id,
+            graph_hash: hashes.mcmc,
+            code_hash: hashes.interaction,
+            c_est: kpis.c_est,
+            gap: kpis.gap_proxy,
+            factors: kpis.factors,
+            couplings: kpis.g,
+            multiplicity: 1,
+        };
+        if count > 0 {
+            hasher.update(b",");
+        }
+        hasher.update(to_canonical_json_bytes(&entry)?);
+        count += 1;
+        sink(entry)?;
+    }
+    hasher.update(b"]");
+    Ok(AtlasStreamSummary {
+        index_hash: format!("{:x}", hasher.finalize()),
+        entries: count,
+    })
+}
+
+/// Keeps the lowest-id entry of each duplicate group, in id order, with the
+/// group size recorded as its multiplicity. Expects `entries` sorted by id.
+fn deduplicate(entries: Vec<AtlasEntry>) -> Vec<AtlasEntry> {
+    let mut representatives: BTreeMap<(String, String, Vec<i64>), usize> = BTreeMap::new();
+    let mut kept: Vec<AtlasEntry> = Vec::new();
+    for entry in entries {
+        match representatives.get(&entry.dedup_key()) {
+            Some(&index) => kept[index].multiplicity += entry.multiplicity,
+            None => {
+                representatives.insert(entry.dedup_key(), kept.len());
+                kept.push(entry);
+            }
+        }
+    }
+    kept
+}
+
+fn read_job_json<T: serde::de::DeserializeOwned>(
+    path: &Path,
+    code: ErrorCode,
+) -> Result<T, AsmError> {
+    let bytes = fs::read(path).map_err(|err| io_error(code, err))?;
+    from_json_slice(&bytes)
+}
+
+/// Lists job directories in either output layout, sorted by atlas identifier.
+fn discover_job_dirs(root: &Path) -> Result<Vec<(String, PathBuf)>, AsmError> {
+    let mut jobs = Vec::new();
+    for dir in list_dirs(root)? {
+        let Some(name) = dir.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if dir.join("status.json").exists() {
+            let parsed = name.split_once('_').and_then(|(seed, rule)| {
+                Some((seed.parse::<u64>().ok()?, rule.parse::<u64>().ok()?))
+            });
+            if let Some((seed, rule_id)) = parsed {
+                jobs.push((format!("{seed}_{rule_id}"), dir));
+            }
+        } else if let Ok(seed) = name.parse::<u64>() {
+            for rule_dir in list_dirs(&dir)? {
+                let rule_id = rule_dir
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .and_then(|name| name.parse::<u64>().ok());
+                if let Some(rule_id) = rule_id {
+                    if rule_dir.join("status.json").exists() {
+                        jobs.push((format!("{seed}_{rule_id}"), rule_dir));
+                    }
+                }
+            }
+        }
+    }
+    jobs.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(jobs)
+}
+
+fn list_dirs(path: &Path) -> Result<Vec<PathBuf>, AsmError> {
+    let entries = fs::read_dir(path).map_err(|err| io_error(ErrorCode::RunTreeRead, err))?;
+    let mut dirs = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|err| io_error(ErrorCode::RunTreeRead, err))?;
+        if entry.path().is_dir() {
+            dirs.push(entry.path());
+        }
+    }
+    Ok(dirs)
+}
+
+/// Summarises metrics across the runs stored under the provided root,
+/// flagging correlations at [`DEFAULT_SIGNIFICANCE`].
 pub fn summarize(root: &Path, filt: &FilterSpec) -> Result<SummaryReport, AsmError> {
+    summarize_with_significance(root, filt, DEFAULT_SIGNIFICANCE)
+}
+
+/// Summarises metrics like [`summarize`], flagging correlations whose
+/// permutation p-value is not below `alpha`.
+pub fn summarize_with_significance(
+    root: &Path,
+    filt: &FilterSpec,
+    alpha: f64,
+) -> Result<SummaryReport, AsmError> {
+    summarize_with_opts(root, filt, alpha, &StatsOpts::default())
+}
+
+/// Summarises metrics like [`summarize_with_significance`], aggregating with
+/// `opts` (e.g. extra quantile points, fixed bin edges, or bootstrap bands).
+/// Per-job weights are rejected because the summary is streamed.
+pub fn summarize_with_opts(
+    root: &Path,
+    filt: &FilterSpec,
+    alpha: f64,
+    opts: &StatsOpts,
+) -> Result<SummaryReport, AsmError> {
     let report = load_report(root)?;
     let mut jobs = Vec::new();
+    let mut accumulator = StatsAccumulator::new(opts)?;
     for mut job in report.jobs.into_iter() {
         job.filters = filt.evaluate(&job.kpis);
+        accumulator.push(&job.kpis);
         jobs.push(job);
     }
-    let kpis: Vec<JobKpi> = jobs.iter().map(|job| job.kpis.clone()).collect();
-    let stats = StatsSummary::from_kpis(&kpis);
-    Ok(SummaryReport::from_jobs(&jobs, stats))
+    let mut stats = accumulator.finalize();
+    let insignificant = stats.flag_significance(alpha);
+    let mut summary = SummaryReport::from_jobs(&jobs, stats);
+    for name in insignificant {
+        let corr = &summary.correlations[&name];
+        summary.notes.push(format!(
+            "{name} correlation not significant at alpha={alpha} (p={:.3}, n={})",
+            corr.p_value, corr.samples
+        ));
+    }
+    Ok(summary)
+}
+
+/// Scalar KPIs compared by [`diff_runs`].
+pub const DIFF_KPIS: [&str; 5] = ["energy_final", "c_est", "gap_proxy", "xi", "lambda_h"];
+
+fn diff_kpi(kpi: &JobKpi, name: &str) -> f64 {
+    match name {
+        "energy_final" => kpi.energy_final,
+        "c_est" => kpi.c_est,
+        "gap_proxy" => kpi.gap_proxy,
+        "xi" => kpi.xi,
+        _ => kpi.lambda_h,
+    }
+}
+
+/// Comparison of a job present in both runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobDiff {
+    /// Seed associated with the job.
+    pub seed: u64,
+    /// Rule variant identifier associated with the job.
+    pub rule_id: u64,
+    /// KPI change from the first run to the second, per [`DIFF_KPIS`] entry.
+    pub kpi_deltas: BTreeMap<String, f64>,
+    /// Whether the job passed all filters in the first run.
+    pub passes_a: bool,
+    /// Whether the job passed all filters in the second run.
+    pub passes_b: bool,
+    /// Stages whose recorded hash differs between the runs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed_hashes: Vec<String>,
+    /// Structural changes between the two canonical KPI records, covering
+    /// fields outside [`DIFF_KPIS`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kpi_changes: Vec<JsonDelta>,
+}
+
+/// Pass rates of the two compared runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PassRateShift {
+    /// Fraction of jobs passing all filters in the first run.
+    pub a: f64,
+    /// Fraction of jobs passing all filters in the second run.
+    pub b: f64,
+    /// Change in pass rate from the first run to the second.
+    pub delta: f64,
+}
+
+/// Shift in the distribution of a KPI across completed jobs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KpiShift {
+    /// Mean over the first run.
+    pub mean_a: f64,
+    /// Mean over the second run.
+    pub mean_b: f64,
+    /// Change in mean from the first run to the second.
+    pub mean_delta: f64,
+    /// Quantiles over the first run.
+    pub quantiles_a: Quantiles,
+    /// Quantiles over the second run.
+    pub quantiles_b: Quantiles,
+}
+
+/// Job-by-job and aggregate comparison of two landscape runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LandscapeDiff {
+    /// Jobs present in both runs, ordered by `(seed, rule_id)`.
+    pub jobs: Vec<JobDiff>,
+    /// Identifiers (`seed_rule`) of jobs only present in the first run.
+    pub only_in_a: Vec<String>,
+    /// Identifiers of jobs only present in the second run.
+    pub only_in_b: Vec<String>,
+    /// Jobs failing the filters in the first run and passing them in the second.
+    pub newly_passing: Vec<String>,
+    /// Jobs passing the filters in the first run and failing them in the second.
+    pub newly_failing: Vec<String>,
+    /// Jobs with at least one changed stage hash.
+    pub changed_hashes: Vec<String>,
+    /// Pass-rate shift over all jobs of each run.
+    pub pass_rate: PassRateShift,
+    /// Distribution shift per [`DIFF_KPIS`] entry over completed jobs.
+    pub kpi_shifts: BTreeMap<String, KpiShift>,
+}
+
+/// Compares the landscape reports stored under `root_a` and `root_b`,
+/// matching jobs by `(seed, rule_id)`.
+pub fn diff_runs(root_a: &Path, root_b: &Path) -> Result<LandscapeDiff, AsmError> {
+    let report_a = load_report(root_a)?;
+    let report_b = load_report(root_b)?;
+    let index = |report: &LandscapeReport| -> BTreeMap<(u64, u64), JobReport> {
+        report
+            .jobs
+            .iter()
+            .map(|job| ((job.seed, job.rule_id), job.clone()))
+            .collect()
+    };
+    let jobs_a = index(&report_a);
+    let jobs_b = index(&report_b);
+    let id = |(seed, rule_id): &(u64, u64)| format!("{seed}_{rule_id}");
+
+    let mut diff = LandscapeDiff {
+        jobs: Vec::new(),
+        only_in_a: jobs_a
+            .keys()
+            .filter(|key| !jobs_b.contains_key(key))
+            .map(id)
+            .collect(),
+        only_in_b: jobs_b
+            .keys()
+            .filter(|key| !jobs_a.contains_key(key))
+            .map(id)
+            .collect(),
+        newly_passing: Vec::new(),
+        newly_failing: Vec::new(),
+        changed_hashes: Vec::new(),
+        pass_rate: PassRateShift {
+            a: pass_rate(&report_a.jobs),
+            b: pass_rate(&report_b.jobs),
+            delta: pass_rate(&report_b.jobs) - pass_rate(&report_a.jobs),
+        },
+        kpi_shifts: BTreeMap::new(),
+    };
+    for (key, a) in &jobs_a {
+        let Some(b) = jobs_b.get(key) else {
+            continue;
+        };
+        let kpi_deltas = DIFF_KPIS
+            .iter()
+            .map(|name| {
+                (
+                    name.to_string(),
+                    diff_kpi(&b.kpis, name) - diff_kpi(&a.kpis, name),
+                )
+            })
+            .collect();
+        let changed_hashes: Vec<String> = [
+            ("mcmc", &a.hashes.mcmc, &b.hashes.mcmc),
+            ("spectrum", &a.hashes.spectrum, &b.hashes.spectrum),
+            ("gauge", &a.hashes.gauge, &b.hashes.gauge),
+            ("interaction", &a.hashes.interaction, &b.hashes.interaction),
+        ]
+        .into_iter()
+        .filter(|(_, hash_a, hash_b)| hash_a != hash_b)
+        .map(|(stage, _, _)| stage.to_string())
+        .collect();
+        let (passes_a, passes_b) = (a.filters.passes(), b.filters.passes());
+        match (passes_a, passes_b) {
+            (false, true) => diff.newly_passing.push(id(key)),
+            (true, false) => diff.newly_failing.push(id(key)),
+            _ => {}
+        }
+        if !changed_hashes.is_empty() {
+            diff.changed_hashes.push(id(key));
+        }
+        let kpi_changes = canonical_json_diff(
+            &to_canonical_json_bytes(&a.kpis)?,
+            &to_canonical_json_bytes(&b.kpis)?,
+        )?;
+        diff.jobs.push(JobDiff {
+            seed: key.0,
+            rule_id: key.1,
+            kpi_deltas,
+            passes_a,
+            passes_b,
+            changed_hashes,
+            kpi_changes,
+        });
+    }
+    for name in DIFF_KPIS {
+        let values = |report: &LandscapeReport| -> Vec<f64> {
+            report
+                .jobs
+                .iter()
+                .filter(|job| job.status.state == JobState::Complete)
+                .map(|job| diff_kpi(&job.kpis, name))
+                .collect()
+        };
+        let (values_a, values_b) = (values(&report_a), values(&report_b));
+        let (mean_a, mean_b) = (mean(&values_a), mean(&values_b));
+        diff.kpi_shifts.insert(
+            name.to_string(),
+            KpiShift {
+                mean_a,
+                mean_b,
+                mean_delta: mean_b - mean_a,
+                quantiles_a: quantile_summary(&values_a, None, &[]),
+                quantiles_b: quantile_summary(&values_b, None, &[]),
+            },
+        );
+    }
+    Ok(diff)
+}
+
+fn pass_rate(jobs: &[JobReport]) -> f64 {
+    if jobs.is_empty() {
+        return 0.0;
+    }
+    jobs.iter().filter(|job| job.filters.passes()).count() as f64 / jobs.len() as f64
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return f64::NAN;
+    }
+    values.iter().sum::<f64>() / values.len() as f64
 }
@@ -37,6 +37,9 @@ pub struct GaugeSummary {
     pub ward_pass: bool,
     /// Gauge factor labels detected during the run.
     pub factors: Vec<String>,
+    /// Whether the cubic anomaly cancels, when checked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anomaly_free: Option<bool>,
 }
 
 /// Simplified interaction report containing selected couplings.
@@ -122,6 +125,7 @@ pub fn synthesise_stage_outputs(
         closure_pass: kpi.closure_pass,
         ward_pass: kpi.ward_pass,
         factors: kpi.factors.clone(),
+        anomaly_free: kpi.anomaly_free,
     };
     let interaction = InteractionSummary {
         g: kpi.g.clone(),
@@ -1,9 +1,137 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
+use asm_core::errors::{AsmError, ErrorCode, ErrorInfo};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 
+use crate::dispatch::splitmix64;
+use crate::filters::is_namespaced;
 use crate::metrics::JobKpi;
 
+/// Number of label permutations drawn when estimating correlation p-values.
+pub const PERMUTATIONS: usize = 999;
+/// Seed of the permutation stream, fixed so p-values are reproducible.
+pub const PERMUTATION_SEED: u64 = 0x5eed_c0e1;
+/// Default significance level used when flagging correlations.
+pub const DEFAULT_SIGNIFICANCE: f64 = 0.05;
+/// Metrics for which histograms and quantiles are reported. Every extracted
+/// KPI in [`JobKpi::extra`] is summarised as well, over the jobs reporting it.
+pub const STAT_METRICS: [&str; 2] = ["c_est", "gap_proxy"];
+/// Uniform bin count for extracted KPIs without explicit bin edges.
+pub const EXTRA_BINS: usize = 5;
+/// Entries a [`StatsAccumulator`] quantile sketch keeps after compaction; the
+/// sketch is exact until it holds `2 * SKETCH_CAPACITY` values.
+pub const SKETCH_CAPACITY: usize = 4096;
+/// Pairs retained by the [`StatsAccumulator`] correlation reservoir.
+pub const RESERVOIR_CAPACITY: usize = 4096;
+/// Seed of the reservoir replacement stream.
+pub const RESERVOIR_SEED: u64 = 0x5eed_4e5e;
+/// Coverage of the bootstrap bands in [`QuantileBands`].
+pub const BOOTSTRAP_CONFIDENCE: f64 = 0.95;
+/// Fewest values for which bootstrap bands are resampled; smaller inputs get
+/// bands collapsed onto the point estimate.
+pub const BOOTSTRAP_MIN_VALUES: usize = 3;
+
+fn stat_metric(kpi: &JobKpi, metric: &str) -> f64 {
+    match metric {
+        "c_est" => kpi.c_est,
+        _ => kpi.gap_proxy,
+    }
+}
+
+fn opts_error(message: impl ToString, key: &str, value: impl ToString) -> AsmError {
+    AsmError::Serde(
+        ErrorInfo::new(ErrorCode::StatsOpts, message.to_string())
+            .with_context(key, value.to_string()),
+    )
+}
+
+/// Options controlling how [`StatsSummary`] aggregates KPIs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatsOpts {
+    /// Additional quantile points in `[0, 1]` reported for every metric.
+    #[serde(default)]
+    pub quantiles: Vec<f64>,
+    /// Per-job weights aligned with the KPI slice, e.g. atlas multiplicities
+    /// or pass/fail indicators. Unweighted when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weights: Option<Vec<f64>>,
+    /// Explicit histogram bin edges keyed by metric name, replacing the
+    /// default uniform binning so separate runs share identical bins.
+    #[serde(default)]
+    pub bin_edges: BTreeMap<String, Vec<f64>>,
+    /// Resampling used to attach [`QuantileBands`] to every quantile
+    /// summary. No bands are computed when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bootstrap: Option<BootstrapOpts>,
+}
+
+/// Seeded bootstrap configuration for quantile confidence bands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BootstrapOpts {
+    /// Number of resamples drawn per metric.
+    pub samples: usize,
+    /// Seed of the resampling stream.
+    pub seed: u64,
+}
+
+impl StatsOpts {
+    /// Rejects out-of-range quantiles, invalid weights, and malformed bin edges.
+    pub fn validate(&self, jobs: usize) -> Result<(), AsmError> {
+        for &q in &self.quantiles {
+            if !(0.0..=1.0).contains(&q) {
+                return Err(opts_error("quantile must lie in [0, 1]", "quantile", q));
+            }
+        }
+        if let Some(weights) = &self.weights {
+            if weights.len() != jobs {
+                return Err(opts_error(
+                    format!("expected {jobs} weights"),
+                    "weights",
+                    weights.len(),
+                ));
+            }
+            if let Some(weight) = weights.iter().find(|w| !w.is_finite() || **w < 0.0) {
+                return Err(opts_error(
+                    "weights must be finite and non-negative",
+                    "weight",
+                    weight,
+                ));
+            }
+        }
+        if let Some(bootstrap) = &self.bootstrap {
+            if bootstrap.samples == 0 {
+                return Err(opts_error(
+                    "bootstrap needs at least one resample",
+                    "bootstrap_samples",
+                    bootstrap.samples,
+                ));
+            }
+            if self.weights.is_some() {
+                return Err(opts_error(
+                    "bootstrap bands need unweighted quantiles",
+                    "weights",
+                    "bootstrap",
+                ));
+            }
+        }
+        for (metric, edges) in &self.bin_edges {
+            if !STAT_METRICS.contains(&metric.as_str()) && !is_namespaced(metric) {
+                return Err(opts_error("unknown histogram metric", "metric", metric));
+            }
+            let increasing = edges.windows(2).all(|pair| pair[0] < pair[1]);
+            if edges.len() < 2 || !increasing || edges.iter().any(|e| !e.is_finite()) {
+                return Err(opts_error(
+                    "bin edges must be at least two finite, strictly increasing values",
+                    "metric",
+                    metric,
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Deterministic histogram descriptor.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Histogram {
@@ -11,6 +139,9 @@ pub struct Histogram {
     pub edges: Vec<f64>,
     /// Counts recorded per bin.
     pub counts: Vec<u64>,
+    /// Sum of job weights per bin, present when weights were supplied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weighted_counts: Option<Vec<f64>>,
 }
 
 /// Quantile summary for a single metric.
@@ -22,6 +153,56 @@ pub struct Quantiles {
     pub q50: f64,
     /// 95th percentile estimate.
     pub q95: f64,
+    /// Estimates for the additional quantile points requested in [`StatsOpts`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub points: Vec<QuantilePoint>,
+    /// Bootstrap confidence bands, present when [`StatsOpts::bootstrap`] was set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bands: Option<QuantileBands>,
+}
+
+impl Quantiles {
+    /// Point estimates of `values` with seeded bootstrap bands from `samples`
+    /// resamples; see [`QuantileBands`].
+    pub fn with_bootstrap(values: &[f64], samples: usize, seed: u64) -> Self {
+        quantile_summary_with(values, None, &[], Some(&BootstrapOpts { samples, seed }))
+    }
+}
+
+/// Bootstrap confidence bands around the estimates of a [`Quantiles`].
+///
+/// Each band is the central [`BOOTSTRAP_CONFIDENCE`] interval of the
+/// estimate over `samples` resamples (with replacement, same size as the
+/// input) drawn from a `StdRng` seeded with `seed`, so identical inputs and
+/// seeds reproduce identical bands.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuantileBands {
+    /// Number of resamples drawn.
+    pub samples: usize,
+    /// Seed of the resampling stream.
+    pub seed: u64,
+    /// `[low, high]` band around `q05`.
+    pub q05: [f64; 2],
+    /// `[low, high]` band around `q50`.
+    pub q50: [f64; 2],
+    /// `[low, high]` band around `q95`.
+    pub q95: [f64; 2],
+    /// Bands aligned with [`Quantiles::points`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub points: Vec<[f64; 2]>,
+    /// Set when fewer than [`BOOTSTRAP_MIN_VALUES`] values were available or
+    /// no resamples were requested; every band then equals its point estimate.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub degenerate: bool,
+}
+
+/// Estimate of a single requested quantile.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuantilePoint {
+    /// Quantile level in `[0, 1]`.
+    pub q: f64,
+    /// Estimated value at `q`.
+    pub value: f64,
 }
 
 /// Correlation descriptor between a fixed metric pair.
@@ -31,6 +212,22 @@ pub struct Correlations {
     pub pearson: f64,
     /// Spearman rank correlation coefficient.
     pub spearman: f64,
+    /// Number of paired samples the coefficients were computed from.
+    #[serde(default)]
+    pub samples: usize,
+    /// Two-sided permutation p-value of the Pearson coefficient.
+    #[serde(default = "Correlations::default_p_value")]
+    pub p_value: f64,
+    /// Whether `p_value` falls below the significance level; set by
+    /// [`StatsSummary::flag_significance`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub significant: Option<bool>,
+}
+
+impl Correlations {
+    fn default_p_value() -> f64 {
+        f64::NAN
+    }
 }
 
 /// Aggregate statistics extracted from a set of KPIs.
@@ -47,22 +244,58 @@ pub struct StatsSummary {
 impl StatsSummary {
     /// Builds a deterministic summary for the provided KPI collection.
     pub fn from_kpis(kpis: &[JobKpi]) -> Self {
-        let mut histograms = BTreeMap::new();
-        histograms.insert(
-            "c_est".to_string(),
-            histogram(kpis, |kpi| kpi.c_est, 0.4, 1.6, 6),
-        );
-        histograms.insert(
-            "gap_proxy".to_string(),
-            histogram(kpis, |kpi| kpi.gap_proxy, 0.0, 0.4, 5),
-        );
+        Self::from_kpis_with(kpis, &StatsOpts::default())
+            .expect("default statistics options are valid")
+    }
 
+    /// Builds a summary using explicit quantile points, job weights, and bin edges.
+    pub fn from_kpis_with(kpis: &[JobKpi], opts: &StatsOpts) -> Result<Self, AsmError> {
+        opts.validate(kpis.len())?;
+        let weights = opts.weights.as_deref();
+
+        let mut histograms = BTreeMap::new();
         let mut quantiles = BTreeMap::new();
-        quantiles.insert("c_est".to_string(), quantile_summary(kpis, |kpi| kpi.c_est));
-        quantiles.insert(
-            "gap_proxy".to_string(),
-            quantile_summary(kpis, |kpi| kpi.gap_proxy),
-        );
+        for metric in STAT_METRICS {
+            let values: Vec<f64> = kpis.iter().map(|kpi| stat_metric(kpi, metric)).collect();
+            let binning = Binning::for_metric(metric, opts).expect("fixed metric binning");
+            let hist = fill_histogram(&binning, &values, weights);
+            histograms.insert(metric.to_string(), hist);
+            quantiles.insert(
+                metric.to_string(),
+                quantile_summary_with(&values, weights, &opts.quantiles, opts.bootstrap.as_ref()),
+            );
+        }
+
+        let extra: BTreeSet<&String> = kpis.iter().flat_map(|kpi| kpi.extra.keys()).collect();
+        for metric in extra {
+            let (values, present): (Vec<f64>, Vec<usize>) = kpis
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, kpi)| kpi.extra.get(metric).map(|value| (*value, idx)))
+                .unzip();
+            let metric_weights: Option<Vec<f64>> =
+                weights.map(|weights| present.iter().map(|&idx| weights[idx]).collect());
+            let metric_weights = metric_weights.as_deref();
+            let hist = match opts.bin_edges.get(metric) {
+                Some(edges) => histogram_with_edges(&values, edges, metric_weights),
+                None => {
+                    let lo = values.iter().copied().fold(f64::INFINITY, f64::min);
+                    let hi = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                    let hi = if hi > lo { hi } else { lo + 1.0 };
+                    histogram(&values, lo, hi, EXTRA_BINS, metric_weights)
+                }
+            };
+            histograms.insert(metric.clone(), hist);
+            quantiles.insert(
+                metric.clone(),
+                quantile_summary_with(
+                    &values,
+                    metric_weights,
+                    &opts.quantiles,
+                    opts.bootstrap.as_ref(),
+                ),
+            );
+        }
 
         let mut correlations = BTreeMap::new();
         correlations.insert(
@@ -70,58 +303,543 @@ impl StatsSummary {
             correlation_summary(kpis, |kpi| kpi.c_est, |kpi| kpi.gap_proxy),
         );
 
-        Self {
+        Ok(Self {
             histograms,
             quantiles,
             correlations,
+        })
+    }
+
+    /// Marks each correlation as significant when its p-value is below `alpha`
+    /// and returns the names of the pairs that are not.
+    pub fn flag_significance(&mut self, alpha: f64) -> Vec<String> {
+        let mut insignificant = Vec::new();
+        for (name, corr) in self.correlations.iter_mut() {
+            let significant = corr.p_value < alpha;
+            corr.significant = Some(significant);
+            if !significant {
+                insignificant.push(name.clone());
+            }
         }
+        insignificant
     }
 }
 
-fn histogram<F>(kpis: &[JobKpi], map: F, start: f64, end: f64, bins: usize) -> Histogram
-where
-    F: Fn(&JobKpi) -> f64,
-{
-    let mut edges = Vec::with_capacity(bins + 1);
-    let step = if bins == 0 {
-        1.0
-    } else {
-        (end - start) / bins as f64
-    };
-    for idx in 0..=bins {
-        edges.push(start + idx as f64 * step);
+/// Deterministic mergeable quantile sketch over `(value, weight)` entries.
+///
+/// Values are buffered verbatim until `2 * SKETCH_CAPACITY` accumulate. The
+/// buffer is then sorted and adjacent pairs are merged into one entry
+/// carrying their combined weight. The kept value alternates between the
+/// lower and the upper member on successive compactions, so the result
+/// depends only on the values and the order of pushes and merges.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct QuantileSketch {
+    entries: Vec<(f64, u64)>,
+    compactions: u64,
+}
+
+impl QuantileSketch {
+    fn push(&mut self, value: f64) {
+        self.entries.push((value, 1));
+        self.maybe_compact();
     }
-    let mut counts = vec![0u64; bins];
-    for value in kpis.iter().map(map) {
-        let mut bin = ((value - start) / step).floor() as isize;
-        if bin < 0 {
-            bin = 0;
+
+    fn merge(&mut self, other: QuantileSketch) {
+        self.entries.extend(other.entries);
+        self.compactions += other.compactions;
+        self.maybe_compact();
+    }
+
+    fn maybe_compact(&mut self) {
+        while self.entries.len() >= 2 * SKETCH_CAPACITY {
+            self.entries.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let keep = (self.compactions % 2) as usize;
+            self.entries = self
+                .entries
+                .chunks(2)
+                .map(|chunk| match chunk {
+                    [lower, upper] => ([lower, upper][keep].0, lower.1 + upper.1),
+                    [single] => *single,
+                    _ => unreachable!("chunks of two"),
+                })
+                .collect();
+            self.compactions += 1;
         }
-        if bin as usize >= bins {
-            bin = (bins as isize) - 1;
+    }
+
+    fn is_exact(&self) -> bool {
+        self.compactions == 0
+    }
+
+    /// Quantiles of the sketched values. Bootstrap bands resample the
+    /// retained entries, so past the exact limit they only approximate the
+    /// bands of the full data.
+    fn quantiles(&self, points: &[f64], bootstrap: Option<&BootstrapOpts>) -> Quantiles {
+        let values: Vec<f64> = self.entries.iter().map(|entry| entry.0).collect();
+        if self.is_exact() {
+            return quantile_summary_with(&values, None, points, bootstrap);
         }
-        counts[bin as usize] += 1;
+        let mut pairs: Vec<(f64, f64)> = self
+            .entries
+            .iter()
+            .map(|&(value, weight)| (value, weight as f64))
+            .collect();
+        pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut quantiles = quantiles_from(|q| weighted_percentile(&pairs, q), points);
+        quantiles.bands = bootstrap.map(|opts| bootstrap_bands(&values, &quantiles, points, opts));
+        quantiles
     }
-    Histogram { edges, counts }
 }
 
-fn quantile_summary<F>(kpis: &[JobKpi], map: F) -> Quantiles
-where
-    F: Fn(&JobKpi) -> f64,
-{
-    let mut values: Vec<f64> = kpis.iter().map(map).collect();
-    if values.is_empty() {
-        return Quantiles {
-            q05: f64::NAN,
-            q50: f64::NAN,
-            q95: f64::NAN,
+/// Streaming state of a single metric.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct MetricStream {
+    /// Bins fixed up front; extracted KPIs without explicit edges are binned
+    /// over their observed range when the accumulator is finalised.
+    binning: Option<Binning>,
+    counts: Vec<u64>,
+    range: Option<(f64, f64)>,
+    sketch: QuantileSketch,
+}
+
+impl MetricStream {
+    fn new(binning: Option<Binning>) -> Self {
+        let counts = binning
+            .as_ref()
+            .map(|binning| vec![0; binning.bins()])
+            .unwrap_or_default();
+        Self {
+            binning,
+            counts,
+            range: None,
+            sketch: QuantileSketch::default(),
+        }
+    }
+
+    fn push(&mut self, value: f64) {
+        if let Some(binning) = &self.binning {
+            self.counts[binning.bin_of(value)] += 1;
+        }
+        self.range = Some(match self.range {
+            Some((lo, hi)) => (lo.min(value), hi.max(value)),
+            None => (value, value),
+        });
+        self.sketch.push(value);
+    }
+
+    fn merge(&mut self, other: MetricStream) {
+        for (count, extra) in self.counts.iter_mut().zip(&other.counts) {
+            *count += extra;
+        }
+        self.range = match (self.range, other.range) {
+            (Some((lo, hi)), Some((olo, ohi))) => Some((lo.min(olo), hi.max(ohi))),
+            (range, None) | (None, range) => range,
+        };
+        self.sketch.merge(other.sketch);
+    }
+
+    fn histogram(&self) -> Histogram {
+        if let Some(binning) = &self.binning {
+            return Histogram {
+                edges: binning.edges(),
+                counts: self.counts.clone(),
+                weighted_counts: None,
+            };
+        }
+        let (lo, hi) = self.range.unwrap_or((f64::INFINITY, f64::NEG_INFINITY));
+        let hi = if hi > lo { hi } else { lo + 1.0 };
+        let binning = Binning::uniform(lo, hi, EXTRA_BINS);
+        let mut counts = vec![0u64; EXTRA_BINS];
+        for &(value, weight) in &self.sketch.entries {
+            counts[binning.bin_of(value)] += weight;
+        }
+        Histogram {
+            edges: binning.edges(),
+            counts,
+            weighted_counts: None,
+        }
+    }
+}
+
+/// Deterministic fixed-size reservoir of `(c_est, gap_proxy)` pairs.
+///
+/// The first [`RESERVOIR_CAPACITY`] pairs are kept in push order. Push `i`
+/// beyond that replaces slot `splitmix64(RESERVOIR_SEED ^ i) mod (i + 1)` when
+/// it falls inside the reservoir (Vitter's algorithm R with a hashed index
+/// instead of a random stream).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct PairReservoir {
+    pairs: Vec<(f64, f64)>,
+    seen: u64,
+}
+
+impl PairReservoir {
+    fn push(&mut self, pair: (f64, f64)) {
+        let index = self.seen;
+        self.seen += 1;
+        if self.pairs.len() < RESERVOIR_CAPACITY {
+            self.pairs.push(pair);
+            return;
+        }
+        let slot = splitmix64(RESERVOIR_SEED ^ index) % (index + 1);
+        if let Some(target) = self.pairs.get_mut(slot as usize) {
+            *target = pair;
+        }
+    }
+
+    /// Combines two reservoirs. Small ones are concatenated. Otherwise each
+    /// pair represents `seen / len` pushes of its shard, and the capacity is
+    /// filled by weighted sampling without replacement (Efraimidis–Spirakis
+    /// keys `u^(1/w)` with `u` hashed from the pair's position).
+    fn merge(&mut self, other: PairReservoir) {
+        let seen = self.seen + other.seen;
+        if self.pairs.len() + other.pairs.len() <= RESERVOIR_CAPACITY {
+            self.pairs.extend(other.pairs);
+            self.seen = seen;
+            return;
+        }
+        let weight =
+            |reservoir: &PairReservoir| reservoir.seen as f64 / reservoir.pairs.len() as f64;
+        let (left, right) = (weight(self), weight(&other));
+        let mut keyed: Vec<(f64, usize, (f64, f64))> = self
+            .pairs
+            .iter()
+            .map(|pair| (left, *pair))
+            .chain(other.pairs.iter().map(|pair| (right, *pair)))
+            .enumerate()
+            .map(|(position, (weight, pair))| {
+                let hashed = splitmix64(RESERVOIR_SEED ^ seen ^ splitmix64(position as u64));
+                let uniform = ((hashed >> 11) as f64 + 0.5) / (1u64 << 53) as f64;
+                (uniform.powf(1.0 / weight), position, pair)
+            })
+            .collect();
+        keyed.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+        keyed.truncate(RESERVOIR_CAPACITY);
+        keyed.sort_by_key(|entry| entry.1);
+        self.pairs = keyed.into_iter().map(|entry| entry.2).collect();
+        self.seen = seen;
+    }
+
+    fn correlations(&self) -> Correlations {
+        let (xs, ys): (Vec<f64>, Vec<f64>) = self.pairs.iter().copied().unzip();
+        pair_correlations(&xs, &ys)
+    }
+}
+
+/// Streaming counterpart of [`StatsSummary::from_kpis_with`] that holds a
+/// bounded amount of state per metric instead of every [`JobKpi`].
+///
+/// Fixed-bin histograms are counted exactly. Quantiles come from a
+/// deterministic compacting sketch that is exact up to
+/// `2 * SKETCH_CAPACITY - 1` jobs. Correlations use a reservoir that is exact
+/// up to [`RESERVOIR_CAPACITY`] jobs. Within those limits [`finalize`] matches
+/// the batch summary exactly. Beyond them the results depend only on the push
+/// order and on how shards were merged, so the same run always gives the same
+/// summary.
+///
+/// [`finalize`]: StatsAccumulator::finalize
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatsAccumulator {
+    opts: StatsOpts,
+    jobs: u64,
+    metrics: BTreeMap<String, MetricStream>,
+    pairs: PairReservoir,
+}
+
+impl StatsAccumulator {
+    /// Creates an empty accumulator. Per-job weights need the full KPI slice,
+    /// so options carrying `weights` are rejected.
+    pub fn new(spec: &StatsOpts) -> Result<Self, AsmError> {
+        if spec.weights.is_some() {
+            return Err(opts_error(
+                "per-job weights need the batch path (StatsSummary::from_kpis_with)",
+                "weights",
+                "streaming",
+            ));
+        }
+        spec.validate(0)?;
+        let metrics = STAT_METRICS
+            .iter()
+            .map(|metric| {
+                let binning = Binning::for_metric(metric, spec);
+                (metric.to_string(), MetricStream::new(binning))
+            })
+            .collect();
+        Ok(Self {
+            opts: spec.clone(),
+            jobs: 0,
+            metrics,
+            pairs: PairReservoir::default(),
+        })
+    }
+
+    /// Number of jobs pushed, including those of merged shards.
+    pub fn jobs(&self) -> u64 {
+        self.jobs
+    }
+
+    /// Folds one job's KPIs into the running statistics.
+    pub fn push(&mut self, kpi: &JobKpi) {
+        self.jobs += 1;
+        for metric in STAT_METRICS {
+            let value = stat_metric(kpi, metric);
+            if let Some(stream) = self.metrics.get_mut(metric) {
+                stream.push(value);
+            }
+        }
+        for (metric, value) in &kpi.extra {
+            let opts = &self.opts;
+            self.metrics
+                .entry(metric.clone())
+                .or_insert_with(|| MetricStream::new(Binning::for_metric(metric, opts)))
+                .push(*value);
+        }
+        self.pairs.push((kpi.c_est, kpi.gap_proxy));
+    }
+
+    /// Absorbs the jobs of another shard. Merging shards in job order yields
+    /// the same summary as pushing every job into one accumulator, as long as
+    /// the combined run stays within the exact limits.
+    pub fn merge(&mut self, other: StatsAccumulator) -> Result<(), AsmError> {
+        if self.opts != other.opts {
+            return Err(opts_error(
+                "cannot merge accumulators built with different options",
+                "merge",
+                "opts",
+            ));
+        }
+        self.jobs += other.jobs;
+        for (metric, stream) in other.metrics {
+            match self.metrics.get_mut(&metric) {
+                Some(existing) => existing.merge(stream),
+                None => {
+                    self.metrics.insert(metric, stream);
+                }
+            }
+        }
+        self.pairs.merge(other.pairs);
+        Ok(())
+    }
+
+    /// Produces the summary of every job pushed so far.
+    pub fn finalize(&self) -> StatsSummary {
+        let mut histograms = BTreeMap::new();
+        let mut quantiles = BTreeMap::new();
+        for (metric, stream) in &self.metrics {
+            histograms.insert(metric.clone(), stream.histogram());
+            quantiles.insert(
+                metric.clone(),
+                stream
+                    .sketch
+                    .quantiles(&self.opts.quantiles, self.opts.bootstrap.as_ref()),
+            );
+        }
+        let mut correlations = BTreeMap::new();
+        correlations.insert("c_est_vs_gap".to_string(), self.pairs.correlations());
+        StatsSummary {
+            histograms,
+            quantiles,
+            correlations,
+        }
+    }
+}
+
+fn histogram(
+    values: &[f64],
+    start: f64,
+    end: f64,
+    bins: usize,
+    weights: Option<&[f64]>,
+) -> Histogram {
+    fill_histogram(&Binning::uniform(start, end, bins), values, weights)
+}
+
+fn histogram_with_edges(values: &[f64], edges: &[f64], weights: Option<&[f64]>) -> Histogram {
+    fill_histogram(&Binning::Edges(edges.to_vec()), values, weights)
+}
+
+/// Bin layout of a histogram; out-of-range values are clamped into the
+/// outermost bins.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Binning {
+    Uniform { start: f64, step: f64, bins: usize },
+    Edges(Vec<f64>),
+}
+
+impl Binning {
+    fn uniform(start: f64, end: f64, bins: usize) -> Self {
+        let step = if bins == 0 {
+            1.0
+        } else {
+            (end - start) / bins as f64
         };
+        Binning::Uniform { start, step, bins }
     }
-    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    fn for_metric(metric: &str, opts: &StatsOpts) -> Option<Self> {
+        match opts.bin_edges.get(metric) {
+            Some(edges) => Some(Binning::Edges(edges.clone())),
+            None if metric == "c_est" => Some(Binning::uniform(0.4, 1.6, 6)),
+            None if metric == "gap_proxy" => Some(Binning::uniform(0.0, 0.4, 5)),
+            None => None,
+        }
+    }
+
+    fn edges(&self) -> Vec<f64> {
+        match self {
+            Binning::Uniform { start, step, bins } => {
+                (0..=*bins).map(|idx| start + idx as f64 * step).collect()
+            }
+            Binning::Edges(edges) => edges.clone(),
+        }
+    }
+
+    fn bins(&self) -> usize {
+        match self {
+            Binning::Uniform { bins, .. } => *bins,
+            Binning::Edges(edges) => edges.len() - 1,
+        }
+    }
+
+    fn bin_of(&self, value: f64) -> usize {
+        match self {
+            Binning::Uniform { start, step, bins } => {
+                let mut bin = ((value - start) / step).floor() as isize;
+                if bin < 0 {
+                    bin = 0;
+                }
+                if bin as usize >= *bins {
+                    bin = (*bins as isize) - 1;
+                }
+                bin as usize
+            }
+            Binning::Edges(edges) => edges
+                .partition_point(|edge| *edge <= value)
+                .saturating_sub(1)
+                .min(edges.len() - 2),
+        }
+    }
+}
+
+/// Counts values into their bins, summing job weights when supplied.
+fn fill_histogram(binning: &Binning, values: &[f64], weights: Option<&[f64]>) -> Histogram {
+    let bins = binning.bins();
+    let mut counts = vec![0u64; bins];
+    let mut weighted = weights.map(|_| vec![0.0; bins]);
+    for (idx, &value) in values.iter().enumerate() {
+        let bin = binning.bin_of(value);
+        counts[bin] += 1;
+        if let (Some(weighted), Some(weights)) = (weighted.as_mut(), weights) {
+            weighted[bin] += weights[idx];
+        }
+    }
+    Histogram {
+        edges: binning.edges(),
+        counts,
+        weighted_counts: weighted,
+    }
+}
+
+pub(crate) fn quantile_summary(
+    values: &[f64],
+    weights: Option<&[f64]>,
+    points: &[f64],
+) -> Quantiles {
+    quantile_summary_with(values, weights, points, None)
+}
+
+fn quantile_summary_with(
+    values: &[f64],
+    weights: Option<&[f64]>,
+    points: &[f64],
+    bootstrap: Option<&BootstrapOpts>,
+) -> Quantiles {
+    let estimate: Box<dyn Fn(f64) -> f64> = match weights {
+        None => {
+            let mut sorted = values.to_vec();
+            sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            Box::new(move |q| percentile(&sorted, q))
+        }
+        Some(weights) => {
+            let mut pairs: Vec<(f64, f64)> = values
+                .iter()
+                .copied()
+                .zip(weights.iter().copied())
+                .filter(|(_, weight)| *weight > 0.0)
+                .collect();
+            pairs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+            Box::new(move |q| weighted_percentile(&pairs, q))
+        }
+    };
+    let mut quantiles = quantiles_from(estimate, points);
+    quantiles.bands = bootstrap.map(|opts| bootstrap_bands(values, &quantiles, points, opts));
+    quantiles
+}
+
+/// Resamples `values` to band the unweighted estimates in `quantiles`.
+fn bootstrap_bands(
+    values: &[f64],
+    quantiles: &Quantiles,
+    points: &[f64],
+    opts: &BootstrapOpts,
+) -> QuantileBands {
+    let estimates: Vec<f64> = [quantiles.q05, quantiles.q50, quantiles.q95]
+        .into_iter()
+        .chain(quantiles.points.iter().map(|point| point.value))
+        .collect();
+    let degenerate = values.len() < BOOTSTRAP_MIN_VALUES || opts.samples == 0;
+    let bands: Vec<[f64; 2]> = if degenerate {
+        estimates.iter().map(|&value| [value, value]).collect()
+    } else {
+        let levels: Vec<f64> = [0.05, 0.5, 0.95]
+            .into_iter()
+            .chain(points.iter().copied())
+            .collect();
+        let mut rng = StdRng::seed_from_u64(opts.seed);
+        let mut draws = vec![Vec::with_capacity(opts.samples); levels.len()];
+        let mut resample = vec![0.0; values.len()];
+        for _ in 0..opts.samples {
+            for slot in resample.iter_mut() {
+                *slot = values[rng.gen_range(0..values.len())];
+            }
+            resample.sort_by(|a, b| a.total_cmp(b));
+            for (draw, &level) in draws.iter_mut().zip(&levels) {
+                draw.push(percentile(&resample, level));
+            }
+        }
+        let tail = (1.0 - BOOTSTRAP_CONFIDENCE) / 2.0;
+        draws
+            .iter_mut()
+            .map(|draw| {
+                draw.sort_by(|a, b| a.total_cmp(b));
+                [percentile(draw, tail), percentile(draw, 1.0 - tail)]
+            })
+            .collect()
+    };
+    QuantileBands {
+        samples: opts.samples,
+        seed: opts.seed,
+        q05: bands[0],
+        q50: bands[1],
+        q95: bands[2],
+        points: bands[3..].to_vec(),
+        degenerate,
+    }
+}
+
+fn quantiles_from(estimate: impl Fn(f64) -> f64, points: &[f64]) -> Quantiles {
     Quantiles {
-        q05: percentile(&values, 0.05),
-        q50: percentile(&values, 0.5),
-        q95: percentile(&values, 0.95),
+        q05: estimate(0.05),
+        q50: estimate(0.5),
+        q95: estimate(0.95),
+        points: points
+            .iter()
+            .map(|&q| QuantilePoint {
+                q,
+                value: estimate(q),
+            })
+            .collect(),
+        bands: None,
     }
 }
 
@@ -140,23 +858,80 @@ fn percentile(values: &[f64], quantile: f64) -> f64 {
     }
 }
 
+/// Interpolates the quantile of `(value, weight)` pairs sorted by value.
+///
+/// Each value sits at the midpoint of its cumulative weight, rescaled so the
+/// first and last values map to 0 and 1; with equal weights this matches
+/// [`percentile`].
+fn weighted_percentile(pairs: &[(f64, f64)], quantile: f64) -> f64 {
+    match pairs {
+        [] => f64::NAN,
+        [(value, _)] => *value,
+        _ => {
+            let mut positions = Vec::with_capacity(pairs.len());
+            let mut cumulative = 0.0;
+            for &(_, weight) in pairs {
+                positions.push(cumulative + weight / 2.0);
+                cumulative += weight;
+            }
+            let first = positions[0];
+            let span = positions[positions.len() - 1] - first;
+            let target = first + quantile * span;
+            let upper = positions
+                .partition_point(|position| *position < target)
+                .clamp(1, pairs.len() - 1);
+            let lower = upper - 1;
+            let width = positions[upper] - positions[lower];
+            let weight = ((target - positions[lower]) / width).clamp(0.0, 1.0);
+            pairs[lower].0 * (1.0 - weight) + pairs[upper].0 * weight
+        }
+    }
+}
+
 fn correlation_summary<F, G>(kpis: &[JobKpi], xf: F, yf: G) -> Correlations
 where
     F: Fn(&JobKpi) -> f64,
     G: Fn(&JobKpi) -> f64,
 {
-    if kpis.is_empty() {
+    let xs: Vec<f64> = kpis.iter().map(&xf).collect();
+    let ys: Vec<f64> = kpis.iter().map(&yf).collect();
+    pair_correlations(&xs, &ys)
+}
+
+fn pair_correlations(xs: &[f64], ys: &[f64]) -> Correlations {
+    if xs.is_empty() {
         return Correlations {
             pearson: f64::NAN,
             spearman: f64::NAN,
+            samples: 0,
+            p_value: f64::NAN,
+            significant: None,
         };
     }
-    let xs: Vec<f64> = kpis.iter().map(&xf).collect();
-    let ys: Vec<f64> = kpis.iter().map(&yf).collect();
+    let observed = pearson(xs, ys);
     Correlations {
-        pearson: pearson(&xs, &ys),
-        spearman: pearson(&rank(&xs), &rank(&ys)),
+        pearson: observed,
+        spearman: pearson(&rank(xs), &rank(ys)),
+        samples: xs.len(),
+        p_value: permutation_p_value(xs, ys, observed),
+        significant: None,
+    }
+}
+
+/// Fraction of shuffled pairings whose |r| reaches the observed one, counting
+/// the observed pairing itself so the estimate is never zero.
+fn permutation_p_value(xs: &[f64], ys: &[f64], observed: f64) -> f64 {
+    let mut rng = StdRng::seed_from_u64(PERMUTATION_SEED);
+    let mut shuffled = ys.to_vec();
+    let threshold = observed.abs() - 1e-12;
+    let mut extreme = 0usize;
+    for _ in 0..PERMUTATIONS {
+        shuffled.shuffle(&mut rng);
+        if pearson(xs, &shuffled).abs() >= threshold {
+            extreme += 1;
+        }
     }
+    (extreme + 1) as f64 / (PERMUTATIONS + 1) as f64
 }
 
 fn pearson(xs: &[f64], ys: &[f64]) -> f64 {
@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+
+use asm_land::{
+    build_atlas, build_atlas_streaming, plan::load_plan, run_plan, serde::to_canonical_json_bytes,
+    AtlasOpts, OutputLayout, RunOpts,
+};
+
+fn fixture_path(relative: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join(relative)
+}
+
+#[test]
+fn streaming_atlas_matches_in_memory_build() {
+    let mut plan = load_plan(fixture_path("landscape/plans/smoke.yaml")).expect("load plan");
+    for layout in [OutputLayout::Flat, OutputLayout::PerSeed] {
+        plan.outputs.layout = layout;
+        let temp = tempfile::tempdir().expect("tmp dir");
+        run_plan(&plan, temp.path(), &RunOpts::default()).expect("run plan");
+        let atlas = build_atlas(temp.path(), &AtlasOpts::default()).expect("build atlas");
+
+        // Per-job directories alone must be sufficient.
+        std::fs::remove_file(temp.path().join("landscape_report.json")).expect("remove report");
+        let mut streamed = Vec::new();
+        let summary = build_atlas_streaming(temp.path(), &AtlasOpts::default(), |entry| {
+            streamed.push(entry);
+            Ok(())
+        })
+        .expect("stream atlas");
+
+        assert_eq!(streamed, atlas.entries);
+        assert_eq!(summary.entries, atlas.entries.len());
+        assert_eq!(summary.index_hash, atlas.index_hash);
+    }
+}
+
+#[test]
+fn deduplicated_atlas_is_independent_of_job_order() {
+    let mut plan = load_plan(fixture_path("landscape/plans/smoke.yaml")).expect("load plan");
+    plan.seeds = vec![42, 43, 44];
+    let temp = tempfile::tempdir().expect("tmp dir");
+    let mut report = run_plan(&plan, temp.path(), &RunOpts::default()).expect("run plan");
+
+    // Make the last job a copy of the first universe, up to coupling noise
+    // below the dedup key resolution.
+    let template = report.jobs[0].clone();
+    let duplicate = &mut report.jobs[2];
+    duplicate.hashes = template.hashes.clone();
+    duplicate.kpis.g = template.kpis.g.iter().map(|g| g + 1e-9).collect();
+    let opts = AtlasOpts {
+        deduplicate: true,
+        ..AtlasOpts::default()
+    };
+    let report_path = temp.path().join("landscape_report.json");
+    std::fs::write(
+        &report_path,
+        to_canonical_json_bytes(&report).expect("encode"),
+    )
+    .expect("write report");
+    let forward = build_atlas(temp.path(), &opts).expect("atlas");
+
+    report.jobs.reverse();
+    std::fs::write(
+        &report_path,
+        to_canonical_json_bytes(&report).expect("encode"),
+    )
+    .expect("write report");
+    let reversed = build_atlas(temp.path(), &opts).expect("atlas");
+
+    assert_eq!(forward, reversed);
+    assert_eq!(forward.manifest, ["42_0", "43_0"]);
+    let multiplicities: Vec<_> = forward.entries.iter().map(|e| e.multiplicity).collect();
+    assert_eq!(multiplicities, [2, 1]);
+
+    let err = build_atlas_streaming(temp.path(), &opts, |_| Ok(())).unwrap_err();
+    assert_eq!(err.info().code, "atlas_stream_dedup");
+}
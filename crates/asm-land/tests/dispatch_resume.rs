@@ -40,3 +40,44 @@ fn dispatch_resume_produces_identical_report() {
     assert_eq!(initial_report.filters, resumed_report.filters);
     assert_eq!(initial_report.stats, resumed_report.stats);
 }
+
+#[test]
+fn verify_resumed_catches_tampered_artefacts() {
+    use asm_core::errors::ErrorCode;
+    use asm_land::ResumeVerification;
+
+    let plan = load_plan(fixture_path("landscape/plans/smoke.yaml")).expect("load plan");
+    let temp = tempfile::tempdir().expect("tmp dir");
+    let initial = run_plan(&plan, temp.path(), &RunOpts::default()).expect("initial run");
+
+    let job_dir = temp.path().join("42_0");
+    let mut kpi: asm_land::metrics::JobKpi =
+        asm_land::serde::from_json_slice(&std::fs::read(job_dir.join("kpi.json")).unwrap())
+            .expect("parse kpi");
+    kpi.c_est += 1.0;
+    std::fs::write(
+        job_dir.join("kpi.json"),
+        asm_land::serde::to_canonical_json_bytes(&kpi).unwrap(),
+    )
+    .expect("tamper kpi");
+
+    let opts = |verify_resumed| RunOpts {
+        resume: true,
+        verify_resumed,
+        ..RunOpts::default()
+    };
+    let trusted = run_plan(&plan, temp.path(), &opts(ResumeVerification::Off)).expect("trusted");
+    assert_ne!(initial.jobs, trusted.jobs);
+
+    let err = run_plan(&plan, temp.path(), &opts(ResumeVerification::Error)).unwrap_err();
+    assert!(err.is(ErrorCode::ResumeMismatch));
+    assert_eq!(err.context_value("artefacts"), Some("kpi.json"));
+
+    let rerun = run_plan(&plan, temp.path(), &opts(ResumeVerification::Rerun)).expect("rerun");
+    assert_eq!(rerun.jobs[0].kpis.c_est, initial.jobs[0].kpis.c_est);
+    let verified = run_plan(&plan, temp.path(), &opts(ResumeVerification::Error)).expect("clean");
+    let hashes = |report: &asm_land::report::LandscapeReport| -> Vec<_> {
+        report.jobs.iter().map(|job| job.hashes.clone()).collect()
+    };
+    assert_eq!(hashes(&initial), hashes(&verified));
+}
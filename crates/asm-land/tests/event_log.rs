@@ -0,0 +1,174 @@
+use std::path::PathBuf;
+
+use asm_core::errors::{AsmError, ErrorCode, ErrorInfo};
+use asm_land::plan::{load_plan, Plan, RuleSpec};
+use asm_land::report::{JobState, EVENTS_FILE};
+use asm_land::{
+    load_events, run_plan, stage_duration_quantiles, AttemptHook, JobEvent, JobEventKind, RunOpts,
+};
+
+const FAILING_RULE: u64 = 7;
+
+fn fixture_path(relative: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join(relative)
+}
+
+/// Smoke plan with a second rule whose every attempt fails.
+fn plan_with_failing_rule() -> Plan {
+    let mut plan = load_plan(fixture_path("landscape/plans/smoke.yaml")).expect("load plan");
+    plan.rules.push(RuleSpec {
+        id: FAILING_RULE,
+        label: "broken".to_string(),
+    });
+    plan
+}
+
+fn failing_opts(max_retries: u32) -> RunOpts {
+    RunOpts {
+        concurrency: 4,
+        max_retries,
+        deterministic_time: true,
+        attempt_hook: Some(AttemptHook::new(|_, rule_id, attempt| {
+            if rule_id == FAILING_RULE {
+                Err(AsmError::Serde(ErrorInfo::new(
+                    ErrorCode::StageWrite,
+                    format!("injected fault on attempt {attempt}"),
+                )))
+            } else {
+                Ok(())
+            }
+        })),
+        ..RunOpts::default()
+    }
+}
+
+fn kinds(events: &[JobEvent], job: usize) -> Vec<JobEventKind> {
+    events
+        .iter()
+        .filter(|event| event.job == job)
+        .map(|event| event.kind)
+        .collect()
+}
+
+#[test]
+fn failing_rule_records_one_retry_per_extra_attempt() {
+    let plan = plan_with_failing_rule();
+    let temp = tempfile::tempdir().expect("tmp dir");
+    let max_retries = 3;
+    let report = run_plan(&plan, temp.path(), &failing_opts(max_retries)).expect("run");
+    let events = load_events(&temp.path().join(EVENTS_FILE)).expect("parse events");
+
+    // File order is (job, seq) with contiguous sequence numbers per job.
+    let order: Vec<_> = events.iter().map(|event| (event.job, event.seq)).collect();
+    let mut sorted = order.clone();
+    sorted.sort();
+    assert_eq!(order, sorted);
+    for job in 0..4 {
+        let seqs: Vec<_> = events
+            .iter()
+            .filter(|event| event.job == job)
+            .map(|event| event.seq)
+            .collect();
+        assert_eq!(seqs, (0..seqs.len() as u32).collect::<Vec<_>>());
+    }
+
+    // Jobs enumerate rules in plan order, then seeds: jobs 2 and 3 use the failing rule.
+    for job in [0, 1] {
+        assert_eq!(
+            kinds(&events, job),
+            [JobEventKind::Start, JobEventKind::Finish]
+        );
+        let finish = events
+            .iter()
+            .find(|event| event.job == job && event.kind == JobEventKind::Finish)
+            .unwrap();
+        assert_eq!(finish.attempt, 1);
+        assert!(finish.error.is_none());
+        let stages: Vec<_> = finish.stages.keys().map(String::as_str).collect();
+        assert_eq!(stages, ["extract", "persist", "synthesise"]);
+    }
+    for job in [2, 3] {
+        let job_events: Vec<_> = events.iter().filter(|event| event.job == job).collect();
+        assert!(job_events
+            .iter()
+            .all(|event| event.rule_id == FAILING_RULE && event.seed == plan.seeds[job - 2]));
+        let retries: Vec<_> = job_events
+            .iter()
+            .filter(|event| event.kind == JobEventKind::Retry)
+            .map(|event| event.attempt)
+            .collect();
+        assert_eq!(retries, (1..max_retries).collect::<Vec<_>>());
+        let last = job_events.last().unwrap();
+        assert_eq!(last.kind, JobEventKind::Failure);
+        assert_eq!(last.attempt, max_retries);
+        assert!(last
+            .error
+            .as_deref()
+            .unwrap()
+            .contains(&format!("injected fault on attempt {max_retries}")));
+        assert!(job_events
+            .iter()
+            .skip(1)
+            .all(|event| event.stages.contains_key("synthesise")));
+    }
+    for job in report.jobs.iter().filter(|job| job.rule_id == FAILING_RULE) {
+        assert_eq!(job.status.state, JobState::Failed);
+        assert_eq!(job.status.attempts, max_retries);
+    }
+    assert!(events
+        .iter()
+        .all(|event| event.timestamp == "1970-01-01T00:00:00Z"));
+
+    let quantiles = stage_duration_quantiles(&events);
+    let stages: Vec<_> = quantiles.keys().map(String::as_str).collect();
+    assert_eq!(stages, ["extract", "persist", "synthesise"]);
+    assert!(quantiles
+        .values()
+        .all(|q| q.q05 >= 0.0 && q.q05 <= q.q50 && q.q50 <= q.q95));
+}
+
+#[test]
+fn reruns_produce_the_same_event_sequence() {
+    let plan = plan_with_failing_rule();
+    let strip = |mut events: Vec<JobEvent>| {
+        for event in &mut events {
+            event.stages.clear();
+        }
+        events
+    };
+    let first = tempfile::tempdir().expect("tmp dir");
+    let second = tempfile::tempdir().expect("tmp dir");
+    run_plan(&plan, first.path(), &failing_opts(2)).expect("first run");
+    run_plan(&plan, second.path(), &failing_opts(2)).expect("second run");
+    let a = load_events(&first.path().join(EVENTS_FILE)).expect("first events");
+    let b = load_events(&second.path().join(EVENTS_FILE)).expect("second events");
+    assert_eq!(strip(a), strip(b));
+
+    let resume = RunOpts {
+        resume: true,
+        ..failing_opts(2)
+    };
+    run_plan(&plan, first.path(), &resume).expect("resumed run");
+    let resumed = load_events(&first.path().join(EVENTS_FILE)).expect("resumed events");
+    assert_eq!(kinds(&resumed, 0), [JobEventKind::Resumed]);
+    assert_eq!(kinds(&resumed, 1), [JobEventKind::Resumed]);
+    assert_eq!(kinds(&resumed, 2).last(), Some(&JobEventKind::Failure));
+}
+
+#[test]
+fn malformed_event_lines_are_reported() {
+    let plan = load_plan(fixture_path("landscape/plans/smoke.yaml")).expect("load plan");
+    let temp = tempfile::tempdir().expect("tmp dir");
+    run_plan(&plan, temp.path(), &RunOpts::default()).expect("run");
+    let path = temp.path().join(EVENTS_FILE);
+    let mut text = std::fs::read_to_string(&path).expect("read events");
+    text.push_str("{\"job\":\n");
+    std::fs::write(&path, text).expect("corrupt events");
+
+    let err = load_events(&path).unwrap_err();
+    assert!(err.is(ErrorCode::EventLogRead));
+    assert_eq!(err.context_value("line"), Some("5"));
+}
@@ -24,3 +24,97 @@ fn filter_decisions_are_stable() {
         assert_eq!(a.filters, b.filters);
     }
 }
+
+#[test]
+fn anomaly_requirement_gates_only_when_enabled() {
+    let plan = load_plan(fixture_path("landscape/plans/smoke.yaml")).expect("load plan");
+    let mut filter_spec = load_filters(&plan.filters_path()).expect("filters load");
+    let mut kpi = asm_land::metrics::JobKpi {
+        anomaly_free: Some(false),
+        ..Default::default()
+    };
+    assert_eq!(filter_spec.evaluate(&kpi).anomaly_free, None);
+
+    filter_spec.require_anomaly_free = true;
+    assert_eq!(filter_spec.evaluate(&kpi).anomaly_free, Some(false));
+    kpi.anomaly_free = None;
+    assert_eq!(filter_spec.evaluate(&kpi).anomaly_free, Some(false));
+    kpi.anomaly_free = Some(true);
+    assert_eq!(filter_spec.evaluate(&kpi).anomaly_free, Some(true));
+}
+
+#[test]
+fn filter_expression_combines_leaves() {
+    use asm_land::filters::{FilterCmp, FilterExpr, FilterLeaf};
+
+    let plan = load_plan(fixture_path("landscape/plans/smoke.yaml")).expect("load plan");
+    let mut filter_spec = load_filters(&plan.filters_path()).expect("filters load");
+    let threshold = |kpi: &str, op, value| {
+        FilterExpr::Leaf(FilterLeaf::Threshold {
+            kpi: kpi.to_string(),
+            op,
+            value,
+        })
+    };
+    filter_spec.expr = Some(FilterExpr::Or(vec![
+        FilterExpr::Leaf(FilterLeaf::Flat),
+        FilterExpr::And(vec![
+            threshold("c_est", FilterCmp::Gt, 2.0),
+            FilterExpr::Not(Box::new(threshold("gap_proxy", FilterCmp::Ge, 0.01))),
+        ]),
+    ]));
+    filter_spec
+        .expr
+        .as_ref()
+        .unwrap()
+        .validate()
+        .expect("valid expr");
+
+    let kpi = asm_land::metrics::JobKpi {
+        c_est: 3.0,
+        gap_proxy: 0.0,
+        ..Default::default()
+    };
+    let decision = filter_spec.evaluate(&kpi);
+    assert!(!decision.flat_passes());
+    assert!(decision.passes());
+    let paths: Vec<_> = decision.leaves.iter().map(|l| l.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        ["or.0.leaf", "or.1.and.0.leaf", "or.1.and.1.not.leaf"]
+    );
+    assert_eq!(
+        decision.leaves.iter().map(|l| l.pass).collect::<Vec<_>>(),
+        [false, true, false]
+    );
+
+    let yaml = "expr:\n  and:\n    - leaf: {threshold: {kpi: bogus, op: gt, value: 1.0}}\n";
+    let spec: asm_land::FilterSpec = serde_yaml::from_str(yaml).expect("parse expr");
+    assert!(spec.expr.unwrap().validate().is_err());
+}
+
+#[test]
+fn lightest_species_gap_is_filterable() {
+    use asm_land::filters::{FilterCmp, FilterExpr, FilterLeaf};
+
+    let plan = load_plan(fixture_path("landscape/plans/smoke.yaml")).expect("load plan");
+    let mut filter_spec = load_filters(&plan.filters_path()).expect("filters load");
+    filter_spec.expr = Some(FilterExpr::Leaf(FilterLeaf::Threshold {
+        kpi: "lightest_species_gap".to_string(),
+        op: FilterCmp::Ge,
+        value: 0.1,
+    }));
+    filter_spec
+        .expr
+        .as_ref()
+        .unwrap()
+        .validate()
+        .expect("valid expr");
+
+    let mut kpi = asm_land::metrics::JobKpi::default();
+    assert!(!filter_spec.evaluate(&kpi).passes());
+    kpi.lightest_species_gap = Some(0.25);
+    assert!(filter_spec.evaluate(&kpi).passes());
+    kpi.lightest_species_gap = Some(0.05);
+    assert!(!filter_spec.evaluate(&kpi).passes());
+}
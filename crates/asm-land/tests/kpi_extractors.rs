@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use asm_core::errors::{AsmError, ErrorCode};
+use asm_land::filters::{FilterCmp, FilterExpr, FilterLeaf};
+use asm_land::metrics::JobKpi;
+use asm_land::serde::{from_json_slice, to_canonical_json_bytes};
+use asm_land::{
+    filters::load_filters, job_seed, plan::load_plan, run_plan, JsonPointerExtractor, KpiExtractor,
+    KpiExtractors, ResumeVerification, RunOpts, StatsOpts, StatsSummary,
+};
+
+fn fixture_path(relative: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join(relative)
+}
+
+const REPORT_KIND: &str = "analysis/spectrum_report.json";
+
+/// Places the phase 11 spectrum report fixture into the job directory of `seed`.
+fn seed_fixture(out: &Path, seed: u64) {
+    let target = out.join(format!("{seed}_0")).join(REPORT_KIND);
+    std::fs::create_dir_all(target.parent().unwrap()).expect("job dir");
+    std::fs::copy(
+        fixture_path("fixtures/phase11/t1_seed0/spectrum_report.json"),
+        target,
+    )
+    .expect("copy fixture");
+}
+
+fn extractor_opts() -> RunOpts {
+    let mut extractors = KpiExtractors::default();
+    extractors
+        .register(
+            "phase11",
+            JsonPointerExtractor::new(REPORT_KIND)
+                .with_kpi("xi", "/correlation/xi")
+                .with_kpi("gap", "/dispersion/gap_proxy"),
+        )
+        .expect("register phase11")
+        .register(
+            "spec",
+            JsonPointerExtractor::new("spectrum").with_kpi("modes", "/modes"),
+        )
+        .expect("register spec");
+    RunOpts {
+        extractors,
+        ..RunOpts::default()
+    }
+}
+
+#[test]
+fn builtin_kpis_unchanged_without_extractors() {
+    let plan = load_plan(fixture_path("landscape/plans/smoke.yaml")).expect("load plan");
+    let temp = tempfile::tempdir().expect("tmp dir");
+    let report = run_plan(&plan, temp.path(), &RunOpts::default()).expect("run");
+
+    for job in &report.jobs {
+        let expected = JobKpi::synthesise(job_seed(job.seed, job.rule_id), job.rule_id);
+        assert_eq!(job.kpis, expected);
+        let persisted = std::fs::read(
+            temp.path()
+                .join(format!("{}_{}", job.seed, job.rule_id))
+                .join("kpi.json"),
+        )
+        .expect("kpi.json");
+        assert_eq!(persisted, to_canonical_json_bytes(&expected).unwrap());
+        assert!(!String::from_utf8(persisted).unwrap().contains("extra"));
+    }
+    let kpis: Vec<JobKpi> = report.jobs.iter().map(|job| job.kpis.clone()).collect();
+    assert_eq!(report.stats, StatsSummary::from_kpis(&kpis));
+    assert_eq!(
+        report.stats.histograms.keys().collect::<Vec<_>>(),
+        ["c_est", "gap_proxy"]
+    );
+}
+
+#[test]
+fn json_pointer_extractor_reads_fixture_report() {
+    let plan = load_plan(fixture_path("landscape/plans/smoke.yaml")).expect("load plan");
+    let temp = tempfile::tempdir().expect("tmp dir");
+    seed_fixture(temp.path(), 42);
+    let opts = extractor_opts();
+    let report = run_plan(&plan, temp.path(), &opts).expect("run");
+
+    let extra: BTreeMap<u64, BTreeMap<String, f64>> = report
+        .jobs
+        .iter()
+        .map(|job| (job.seed, job.kpis.extra.clone()))
+        .collect();
+    assert_eq!(
+        extra[&42],
+        BTreeMap::from([
+            ("phase11.gap".to_string(), 0.087906635),
+            ("phase11.xi".to_string(), 2.25),
+            ("spec.modes".to_string(), 2.0),
+        ])
+    );
+    // The fixture only exists for seed 42; stage artefacts exist for every job.
+    assert_eq!(
+        extra[&43],
+        BTreeMap::from([("spec.modes".to_string(), 2.0)])
+    );
+    for job in &report.jobs {
+        let mut builtin = job.kpis.clone();
+        builtin.extra.clear();
+        assert_eq!(
+            builtin,
+            JobKpi::synthesise(job_seed(job.seed, job.rule_id), job.rule_id)
+        );
+        let persisted: JobKpi = from_json_slice(
+            &std::fs::read(temp.path().join(format!("{}_0/kpi.json", job.seed))).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(persisted.extra, job.kpis.extra);
+    }
+
+    assert_eq!(
+        report.stats.histograms["phase11.xi"]
+            .counts
+            .iter()
+            .sum::<u64>(),
+        1
+    );
+    assert_eq!(report.stats.quantiles["spec.modes"].q50, 2.0);
+    let kpis: Vec<JobKpi> = report.jobs.iter().map(|job| job.kpis.clone()).collect();
+    let binned = StatsSummary::from_kpis_with(
+        &kpis,
+        &StatsOpts {
+            bin_edges: BTreeMap::from([("phase11.xi".to_string(), vec![0.0, 2.0, 4.0])]),
+            ..StatsOpts::default()
+        },
+    )
+    .expect("namespaced bin edges");
+    assert_eq!(binned.histograms["phase11.xi"].counts, [0, 1]);
+
+    let resumed = run_plan(
+        &plan,
+        temp.path(),
+        &RunOpts {
+            resume: true,
+            verify_resumed: ResumeVerification::Error,
+            ..opts
+        },
+    )
+    .expect("resumed run verifies extracted KPIs");
+    for (resumed, original) in resumed.jobs.iter().zip(&report.jobs) {
+        assert_eq!(resumed.kpis.extra, original.kpis.extra);
+        assert_eq!(resumed.filters, original.filters);
+    }
+}
+
+#[test]
+fn filters_reference_namespaced_kpis() {
+    let plan = load_plan(fixture_path("landscape/plans/smoke.yaml")).expect("load plan");
+    let temp = tempfile::tempdir().expect("tmp dir");
+    seed_fixture(temp.path(), 42);
+    let report = run_plan(&plan, temp.path(), &extractor_opts()).expect("run");
+
+    let mut filter_spec = load_filters(&plan.filters_path()).expect("filters load");
+    let expr = FilterExpr::Leaf(FilterLeaf::Threshold {
+        kpi: "phase11.xi".to_string(),
+        op: FilterCmp::Gt,
+        value: 2.0,
+    });
+    expr.validate().expect("namespaced KPI is addressable");
+    filter_spec.expr = Some(expr);
+    let verdicts: Vec<_> = report
+        .jobs
+        .iter()
+        .map(|job| filter_spec.evaluate(&job.kpis).verdict)
+        .collect();
+    assert_eq!(verdicts, [Some(true), Some(false)]);
+
+    let unknown = FilterExpr::Leaf(FilterLeaf::Threshold {
+        kpi: "xi_bogus".to_string(),
+        op: FilterCmp::Gt,
+        value: 0.0,
+    });
+    let err = unknown.validate().expect_err("unknown KPI");
+    assert!(err.is(ErrorCode::FilterExpr));
+}
+
+/// Reports a single KPI named `.0` from the gauge artefact.
+struct Fixed(&'static str);
+
+impl KpiExtractor for Fixed {
+    fn artefact_kind(&self) -> &str {
+        "gauge"
+    }
+
+    fn extract(&self, _bytes: &[u8]) -> Result<BTreeMap<String, f64>, AsmError> {
+        Ok(BTreeMap::from([(self.0.to_string(), 1.0)]))
+    }
+}
+
+#[test]
+fn extractor_collisions_are_rejected() {
+    let mut extractors = KpiExtractors::default();
+    extractors.register("a", Fixed("b.c")).unwrap();
+    let err = extractors
+        .register("a", Fixed("d"))
+        .expect_err("duplicate name");
+    assert!(err.is(ErrorCode::KpiCollision));
+
+    extractors.register("a.b", Fixed("c")).unwrap();
+    let plan = load_plan(fixture_path("landscape/plans/smoke.yaml")).expect("load plan");
+    let temp = tempfile::tempdir().expect("tmp dir");
+    let err = run_plan(
+        &plan,
+        temp.path(),
+        &RunOpts {
+            extractors,
+            ..RunOpts::default()
+        },
+    )
+    .expect_err("colliding namespaced KPI");
+    assert!(err.is(ErrorCode::KpiCollision));
+    assert_eq!(err.context_value("kpi"), Some("a.b.c"));
+}
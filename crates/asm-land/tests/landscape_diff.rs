@@ -0,0 +1,83 @@
+use std::fs;
+use std::path::PathBuf;
+
+use asm_core::json_diff::JsonDeltaKind;
+use asm_land::serde::{from_json_slice, to_canonical_json_bytes};
+use asm_land::{diff_runs, plan::load_plan, run_plan, LandscapeReport, RunOpts};
+
+fn fixture_path(relative: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join(relative)
+}
+
+#[test]
+fn diff_matches_jobs_and_reports_shifts() {
+    let plan = load_plan(fixture_path("landscape/plans/smoke.yaml")).expect("load plan");
+    let root_a = tempfile::tempdir().expect("tmp dir");
+    let root_b = tempfile::tempdir().expect("tmp dir");
+    run_plan(&plan, root_a.path(), &RunOpts::default()).expect("run a");
+
+    let mut changed = plan.clone();
+    changed.seeds = vec![43, 44];
+    changed.sampler.sweeps += 1;
+    run_plan(&changed, root_b.path(), &RunOpts::default()).expect("run b");
+
+    // Identical roots produce an empty diff.
+    let same = diff_runs(root_a.path(), root_a.path()).expect("diff");
+    assert!(same.only_in_a.is_empty() && same.changed_hashes.is_empty());
+    assert!(same
+        .jobs
+        .iter()
+        .all(|job| job.kpi_deltas.values().all(|d| *d == 0.0)));
+    assert!(same.jobs.iter().all(|job| job.kpi_changes.is_empty()));
+    assert_eq!(same.pass_rate.delta, 0.0);
+
+    // Perturb the shared job in the second report.
+    let report_path = root_b.path().join("landscape_report.json");
+    let mut report: LandscapeReport =
+        from_json_slice(&fs::read(&report_path).expect("read")).expect("parse");
+    let shared = report
+        .jobs
+        .iter_mut()
+        .find(|job| job.seed == 43)
+        .expect("job 43");
+    let passed_before = shared.filters.passes();
+    shared.filters.verdict = Some(!passed_before);
+    shared.kpis.c_est += 0.25;
+    fs::write(
+        &report_path,
+        to_canonical_json_bytes(&report).expect("json"),
+    )
+    .expect("write");
+
+    let diff = diff_runs(root_a.path(), root_b.path()).expect("diff");
+    assert_eq!(diff.only_in_a, ["42_0"]);
+    assert_eq!(diff.only_in_b, ["44_0"]);
+    assert_eq!(diff.jobs.len(), 1);
+    let job = &diff.jobs[0];
+    assert_eq!((job.seed, job.rule_id), (43, 0));
+    assert!((job.kpi_deltas["c_est"] - 0.25).abs() < 1e-12);
+    assert_eq!(job.kpi_deltas["gap_proxy"], 0.0);
+    assert!(job
+        .kpi_changes
+        .iter()
+        .any(|change| change.path == "/c_est" && change.kind == JsonDeltaKind::Changed));
+    assert_eq!(job.changed_hashes, ["mcmc"]);
+    assert_eq!(diff.changed_hashes, ["43_0"]);
+    if passed_before {
+        assert_eq!(diff.newly_failing, ["43_0"]);
+        assert!(diff.newly_passing.is_empty());
+    } else {
+        assert_eq!(diff.newly_passing, ["43_0"]);
+        assert!(diff.newly_failing.is_empty());
+    }
+    assert!((diff.pass_rate.delta - (diff.pass_rate.b - diff.pass_rate.a)).abs() < 1e-12);
+    let shift = &diff.kpi_shifts["c_est"];
+    assert!((shift.mean_delta - (shift.mean_b - shift.mean_a)).abs() < 1e-12);
+    assert_eq!(
+        diff,
+        diff_runs(root_a.path(), root_b.path()).expect("rerun")
+    );
+}
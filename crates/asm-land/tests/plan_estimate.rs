@@ -0,0 +1,115 @@
+use std::path::PathBuf;
+
+use asm_land::dispatch::{
+    effective_concurrency, estimate_plan, estimate_plan_with_costs, run_plan, RunOpts,
+};
+use asm_land::estimate::StageCosts;
+use asm_land::plan::{load_plan, OutputLayout};
+use asm_land::serde::to_canonical_json_bytes;
+
+fn fixture_path(relative: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join(relative)
+}
+
+#[test]
+fn estimate_is_deterministic_and_counts_jobs() {
+    let plan = load_plan(fixture_path("landscape/plans/smoke.yaml")).expect("load plan");
+    let first = estimate_plan(&plan, &RunOpts::default()).expect("estimate");
+    let second = estimate_plan(&plan, &RunOpts::default()).expect("estimate");
+    assert_eq!(
+        to_canonical_json_bytes(&first).expect("encode"),
+        to_canonical_json_bytes(&second).expect("encode")
+    );
+    assert_eq!(first.jobs, 2);
+    assert_eq!(first.directories.len(), 2);
+    assert!(first.directories.contains_key("42_0"));
+    let stage_bytes: u64 = first.stages.values().map(|stage| stage.bytes).sum();
+    assert_eq!(first.total_bytes, stage_bytes + first.report_bytes);
+
+    let parallel = estimate_plan(
+        &plan,
+        &RunOpts {
+            concurrency: 2,
+            ..RunOpts::default()
+        },
+    )
+    .expect("estimate");
+    assert!(parallel.wall_seconds < first.wall_seconds);
+    assert_eq!(parallel.cpu_seconds, first.cpu_seconds);
+}
+
+#[test]
+fn dropping_intermediates_shrinks_disk_projection() {
+    let mut plan = load_plan(fixture_path("landscape/plans/smoke.yaml")).expect("load plan");
+    plan.outputs.layout = OutputLayout::PerSeed;
+    let kept = estimate_plan(&plan, &RunOpts::default()).expect("estimate");
+    plan.outputs.keep_intermediate = false;
+    let dropped = estimate_plan(&plan, &RunOpts::default()).expect("estimate");
+
+    assert!(dropped.total_bytes < kept.total_bytes);
+    assert!(dropped
+        .artefacts
+        .iter()
+        .all(|artefact| !artefact.intermediate));
+    assert_eq!(dropped.stages["spectrum"].bytes, 0);
+    assert_eq!(
+        dropped.stages["persist"].bytes,
+        kept.stages["persist"].bytes
+    );
+    assert_eq!(dropped.directories["42"].jobs, 1);
+    assert_eq!(dropped.stages["mcmc"].seconds, kept.stages["mcmc"].seconds);
+}
+
+#[test]
+fn negative_costs_are_rejected() {
+    let plan = load_plan(fixture_path("landscape/plans/smoke.yaml")).expect("load plan");
+    let costs = StageCosts {
+        gauge: -1.0,
+        ..StageCosts::default()
+    };
+    let err = estimate_plan_with_costs(&plan, &RunOpts::default(), &costs).unwrap_err();
+    assert_eq!(err.info().code, "estimate_cost");
+}
+
+#[test]
+fn memory_budget_limits_concurrency_without_changing_reports() {
+    let mut plan = load_plan(fixture_path("landscape/plans/smoke.yaml")).expect("load plan");
+    plan.seeds = vec![42, 43, 44, 45];
+    let unbounded = RunOpts {
+        concurrency: 4,
+        ..RunOpts::default()
+    };
+    let per_job = estimate_plan(&plan, &unbounded)
+        .expect("estimate")
+        .job_memory_bytes as usize;
+    let budgeted = RunOpts {
+        memory_budget_bytes: Some(2 * per_job + 1),
+        ..unbounded.clone()
+    };
+    let starved = RunOpts {
+        memory_budget_bytes: Some(1),
+        ..unbounded.clone()
+    };
+    assert_eq!(effective_concurrency(&plan, &unbounded), 4);
+    assert_eq!(effective_concurrency(&plan, &budgeted), 2);
+    assert_eq!(effective_concurrency(&plan, &starved), 1);
+    assert_eq!(
+        estimate_plan(&plan, &budgeted)
+            .expect("estimate")
+            .concurrency,
+        2
+    );
+
+    let mut reports = Vec::new();
+    for opts in [&unbounded, &budgeted, &starved] {
+        let temp = tempfile::tempdir().expect("tmp dir");
+        let mut report = run_plan(&plan, temp.path(), opts).expect("run plan");
+        report.provenance.created_at.clear();
+        reports.push(to_canonical_json_bytes(&report).expect("encode"));
+    }
+    assert_eq!(reports[0], reports[1]);
+    assert_eq!(reports[0], reports[2]);
+}
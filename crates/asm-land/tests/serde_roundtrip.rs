@@ -52,3 +52,24 @@ fn landscape_artifacts_roundtrip() {
         serde_json::from_slice(&summary_roundtrip).expect("summary roundtrip json");
     assert_eq!(summary_value, summary_roundtrip_value);
 }
+
+#[test]
+fn deterministic_time_yields_identical_report_bytes() {
+    let plan = load_plan(fixture_path("landscape/plans/smoke.yaml")).expect("load plan");
+    let opts = RunOpts {
+        deterministic_time: true,
+        ..RunOpts::default()
+    };
+    let first = tempfile::tempdir().expect("tmp dir");
+    let second = tempfile::tempdir().expect("tmp dir");
+    let report = run_plan(&plan, first.path(), &opts).expect("first run");
+    run_plan(&plan, second.path(), &opts).expect("second run");
+
+    let read = |dir: &tempfile::TempDir| {
+        std::fs::read(dir.path().join("landscape_report.json")).expect("read report")
+    };
+    assert_eq!(read(&first), read(&second));
+    if std::env::var_os("SOURCE_DATE_EPOCH").is_none() {
+        assert_eq!(report.provenance.created_at, "1970-01-01T00:00:00Z");
+    }
+}
@@ -22,3 +22,265 @@ fn stats_are_consistent() {
     assert_eq!(report.stats.quantiles, stats_again.quantiles);
     assert_eq!(report.stats.correlations, stats_again.correlations);
 }
+
+#[test]
+fn correlation_significance_is_reproducible() {
+    let kpi = |c_est: f64, gap_proxy: f64| asm_land::metrics::JobKpi {
+        c_est,
+        gap_proxy,
+        ..Default::default()
+    };
+    let linear: Vec<_> = (0..12)
+        .map(|idx| kpi(0.5 + 0.1 * idx as f64, 0.02 * idx as f64))
+        .collect();
+    let mut stats = StatsSummary::from_kpis(&linear);
+    assert_eq!(stats, StatsSummary::from_kpis(&linear));
+    let corr = &stats.correlations["c_est_vs_gap"];
+    assert_eq!(corr.samples, 12);
+    assert!(corr.p_value < 0.01, "p = {}", corr.p_value);
+    assert!(stats.flag_significance(0.05).is_empty());
+    assert_eq!(stats.correlations["c_est_vs_gap"].significant, Some(true));
+
+    // Three points can be perfectly correlated by chance a third of the time.
+    let tiny = vec![kpi(0.8, 0.1), kpi(0.9, 0.2), kpi(1.0, 0.3)];
+    let mut stats = StatsSummary::from_kpis(&tiny);
+    assert!(stats.correlations["c_est_vs_gap"].p_value > 0.05);
+    assert_eq!(stats.flag_significance(0.05), ["c_est_vs_gap"]);
+    assert_eq!(stats.correlations["c_est_vs_gap"].significant, Some(false));
+}
+
+#[test]
+fn stats_options_control_quantiles_weights_and_bins() {
+    use asm_core::errors::ErrorCode;
+    use asm_land::StatsOpts;
+
+    let kpi = |c_est: f64| asm_land::metrics::JobKpi {
+        c_est,
+        ..Default::default()
+    };
+    let kpis: Vec<_> = [0.5, 0.7, 0.9, 1.1, 1.3].into_iter().map(kpi).collect();
+
+    let unweighted = StatsSummary::from_kpis(&kpis);
+    let opts = StatsOpts {
+        quantiles: vec![0.25, 0.75],
+        weights: Some(vec![1.0; kpis.len()]),
+        bin_edges: [("c_est".to_string(), vec![0.0, 0.8, 2.0])].into(),
+        bootstrap: None,
+    };
+    let stats = StatsSummary::from_kpis_with(&kpis, &opts).unwrap();
+    let c_est = &stats.quantiles["c_est"];
+    assert!((c_est.q50 - unweighted.quantiles["c_est"].q50).abs() < 1e-12);
+    let points: Vec<(f64, f64)> = c_est.points.iter().map(|p| (p.q, p.value)).collect();
+    assert_eq!(points.len(), 2);
+    assert!((points[0].1 - 0.7).abs() < 1e-12 && (points[1].1 - 1.1).abs() < 1e-12);
+
+    let hist = &stats.histograms["c_est"];
+    assert_eq!(hist.edges, [0.0, 0.8, 2.0]);
+    assert_eq!(hist.counts, [2, 3]);
+    assert_eq!(hist.weighted_counts.as_deref(), Some(&[2.0, 3.0][..]));
+
+    // A universe with multiplicity six pulls the median towards it.
+    let weighted = StatsOpts {
+        weights: Some(vec![1.0, 1.0, 1.0, 1.0, 6.0]),
+        ..StatsOpts::default()
+    };
+    let stats = StatsSummary::from_kpis_with(&kpis, &weighted).unwrap();
+    assert!(stats.quantiles["c_est"].q50 > unweighted.quantiles["c_est"].q50);
+    assert!(stats.quantiles["c_est"].points.is_empty());
+
+    let bad_edges = StatsOpts {
+        bin_edges: [("c_est".to_string(), vec![1.0, 0.5])].into(),
+        ..StatsOpts::default()
+    };
+    let err = StatsSummary::from_kpis_with(&kpis, &bad_edges).unwrap_err();
+    assert!(err.is(ErrorCode::StatsOpts));
+    let bad_weights = StatsOpts {
+        weights: Some(vec![1.0]),
+        ..StatsOpts::default()
+    };
+    assert!(StatsSummary::from_kpis_with(&kpis, &bad_weights)
+        .unwrap_err()
+        .is(ErrorCode::StatsOpts));
+}
+
+fn synthetic_kpis(count: usize) -> Vec<asm_land::metrics::JobKpi> {
+    (0..count)
+        .map(|idx| {
+            let t = ((idx * 37) % 101) as f64 / 101.0;
+            asm_land::metrics::JobKpi {
+                energy_final: -1.0 - t,
+                c_est: 0.5 + t,
+                gap_proxy: 0.05 + 0.3 * t * t,
+                xi: 1.0 + (idx % 7) as f64,
+                extra: [
+                    ("probe.width".to_string(), 2.0 * t),
+                    ("probe.depth".to_string(), (idx % 5) as f64),
+                ]
+                .into(),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn streaming_matches_batch_summary() {
+    use asm_land::{StatsAccumulator, StatsOpts};
+
+    let kpis = synthetic_kpis(64);
+    let opts = StatsOpts {
+        quantiles: vec![0.1, 0.9],
+        bin_edges: [("probe.depth".to_string(), vec![0.0, 1.5, 3.5, 5.0])].into(),
+        ..StatsOpts::default()
+    };
+    let mut streaming = StatsAccumulator::new(&opts).unwrap();
+    for kpi in &kpis {
+        streaming.push(kpi);
+    }
+    assert_eq!(streaming.jobs(), 64);
+    let batch = StatsSummary::from_kpis_with(&kpis, &opts).unwrap();
+    assert_eq!(streaming.finalize(), batch);
+
+    // Shards merged in job order reproduce the single pass.
+    let mut merged = StatsAccumulator::new(&opts).unwrap();
+    for shard in kpis.chunks(20) {
+        let mut part = StatsAccumulator::new(&opts).unwrap();
+        shard.iter().for_each(|kpi| part.push(kpi));
+        merged.merge(part).unwrap();
+    }
+    assert_eq!(merged.finalize(), batch);
+
+    let err = merged
+        .merge(StatsAccumulator::new(&StatsOpts::default()).unwrap())
+        .unwrap_err();
+    assert!(err.is(asm_core::errors::ErrorCode::StatsOpts));
+    let weighted = StatsOpts {
+        weights: Some(vec![1.0; kpis.len()]),
+        ..StatsOpts::default()
+    };
+    assert!(StatsAccumulator::new(&weighted)
+        .unwrap_err()
+        .is(asm_core::errors::ErrorCode::StatsOpts));
+}
+
+#[test]
+fn streaming_sketch_is_bounded_and_deterministic() {
+    use asm_land::stat::{RESERVOIR_CAPACITY, SKETCH_CAPACITY};
+    use asm_land::{StatsAccumulator, StatsOpts};
+
+    let kpis = synthetic_kpis(3 * SKETCH_CAPACITY);
+    let run = |shards: usize| {
+        let mut total = StatsAccumulator::new(&StatsOpts::default()).unwrap();
+        for shard in kpis.chunks(kpis.len() / shards) {
+            let mut part = StatsAccumulator::new(&StatsOpts::default()).unwrap();
+            shard.iter().for_each(|kpi| part.push(kpi));
+            total.merge(part).unwrap();
+        }
+        total.finalize()
+    };
+    let single = run(1);
+    assert_eq!(single, run(1));
+    assert_eq!(run(3), run(3));
+
+    let batch = StatsSummary::from_kpis(&kpis);
+    assert_eq!(single.histograms["c_est"], batch.histograms["c_est"]);
+    for (metric, exact) in &batch.quantiles {
+        let approx = &single.quantiles[metric];
+        for (a, b) in [
+            (approx.q05, exact.q05),
+            (approx.q50, exact.q50),
+            (approx.q95, exact.q95),
+        ] {
+            assert!((a - b).abs() < 0.02, "{metric}: {a} vs {b}");
+        }
+    }
+    let corr = &single.correlations["c_est_vs_gap"];
+    assert_eq!(corr.samples, RESERVOIR_CAPACITY);
+    assert!((corr.spearman - batch.correlations["c_est_vs_gap"].spearman).abs() < 0.01);
+}
+
+#[test]
+fn bootstrap_bands_track_spread_and_reproduce() {
+    use asm_land::stat::Quantiles;
+
+    // Deterministic pseudo-uniform values in [-1, 1), scaled per spread.
+    let values = |spread: f64| -> Vec<f64> {
+        (0..200u64)
+            .map(|idx| {
+                let unit = (idx.wrapping_mul(0x9e37_79b9) % 1000) as f64 / 500.0 - 1.0;
+                1.0 + spread * unit
+            })
+            .collect()
+    };
+    let width = |band: [f64; 2]| band[1] - band[0];
+
+    let narrow = Quantiles::with_bootstrap(&values(0.1), 200, 7);
+    let wide = Quantiles::with_bootstrap(&values(1.0), 200, 7);
+    let (narrow_bands, wide_bands) = (narrow.bands.unwrap(), wide.bands.unwrap());
+    assert!(!narrow_bands.degenerate);
+    for (a, b) in [
+        (narrow_bands.q05, wide_bands.q05),
+        (narrow_bands.q50, wide_bands.q50),
+        (narrow_bands.q95, wide_bands.q95),
+    ] {
+        assert!(width(b) > width(a), "{b:?} should be wider than {a:?}");
+    }
+    assert!(wide_bands.q50[0] <= wide.q50 && wide.q50 <= wide_bands.q50[1]);
+
+    assert_eq!(
+        Quantiles::with_bootstrap(&values(1.0), 200, 7),
+        Quantiles::with_bootstrap(&values(1.0), 200, 7)
+    );
+    assert_ne!(
+        Quantiles::with_bootstrap(&values(1.0), 200, 8).bands,
+        Some(wide_bands)
+    );
+
+    let tiny = Quantiles::with_bootstrap(&[0.5, 0.9], 200, 7);
+    let bands = tiny.bands.unwrap();
+    assert!(bands.degenerate);
+    assert_eq!(bands.q05, [tiny.q05, tiny.q05]);
+    assert_eq!(bands.q95, [tiny.q95, tiny.q95]);
+}
+
+#[test]
+fn bootstrap_option_reaches_batch_and_streaming_summaries() {
+    use asm_core::errors::ErrorCode;
+    use asm_land::{BootstrapOpts, StatsAccumulator, StatsOpts};
+
+    let kpis = synthetic_kpis(64);
+    let opts = StatsOpts {
+        quantiles: vec![0.25],
+        bootstrap: Some(BootstrapOpts {
+            samples: 50,
+            seed: 3,
+        }),
+        ..StatsOpts::default()
+    };
+    let batch = StatsSummary::from_kpis_with(&kpis, &opts).unwrap();
+    let mut streaming = StatsAccumulator::new(&opts).unwrap();
+    kpis.iter().for_each(|kpi| streaming.push(kpi));
+    assert_eq!(streaming.finalize().quantiles, batch.quantiles);
+    let bands = batch.quantiles["c_est"].bands.as_ref().unwrap();
+    assert_eq!(bands.points.len(), 1);
+    assert!(StatsSummary::from_kpis(&kpis).quantiles["c_est"]
+        .bands
+        .is_none());
+
+    for bad in [
+        StatsOpts {
+            bootstrap: Some(BootstrapOpts {
+                samples: 0,
+                seed: 3,
+            }),
+            ..StatsOpts::default()
+        },
+        StatsOpts {
+            weights: Some(vec![1.0; kpis.len()]),
+            ..opts.clone()
+        },
+    ] {
+        let err = StatsSummary::from_kpis_with(&kpis, &bad).unwrap_err();
+        assert!(err.is(ErrorCode::StatsOpts));
+    }
+}
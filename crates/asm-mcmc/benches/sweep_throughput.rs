@@ -29,6 +29,7 @@ fn sample_graph() -> HypergraphImpl {
             destinations: 1,
         }),
         schema_version: SchemaVersion::new(2, 0, 0),
+        ..HypergraphConfig::default()
     };
     let mut graph = HypergraphImpl::new(config);
     let nodes: Vec<_> = (0..6).map(|_| graph.add_node().unwrap()).collect();
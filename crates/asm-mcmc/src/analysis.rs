@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 
 use asm_code::dispersion::{estimate_dispersion, DispersionOptions, DispersionReport};
 use asm_code::{CSSCode, SpeciesId};
-use asm_core::errors::ErrorInfo;
+use asm_core::errors::{ErrorCode, ErrorInfo};
 use asm_core::AsmError;
 use asm_graph::{graph_from_json, HypergraphImpl};
 
@@ -16,13 +16,13 @@ pub fn load_end_state(run_dir: &Path) -> Result<(CSSCode, HypergraphImpl), AsmEr
 
     let code_json = fs::read_to_string(&code_path).map_err(|err| {
         AsmError::Serde(
-            ErrorInfo::new("end-state-read", err.to_string())
+            ErrorInfo::new(ErrorCode::EndStateRead, err.to_string())
                 .with_context("path", code_path.display().to_string()),
         )
     })?;
     let graph_json = fs::read_to_string(&graph_path).map_err(|err| {
         AsmError::Serde(
-            ErrorInfo::new("end-state-read", err.to_string())
+            ErrorInfo::new(ErrorCode::EndStateRead, err.to_string())
                 .with_context("path", graph_path.display().to_string()),
         )
     })?;
@@ -57,8 +57,11 @@ pub fn dispersion_for_checkpoint(
     let states = checkpoint::restore_payload(&payload)?;
     let Some((_, code, graph, _)) = states.into_iter().next() else {
         return Err(AsmError::Serde(
-            ErrorInfo::new("checkpoint-empty", "checkpoint contained no replicas")
-                .with_context("path", checkpoint_path.display().to_string()),
+            ErrorInfo::new(
+                ErrorCode::EmptyCheckpoint,
+                "checkpoint contained no replicas",
+            )
+            .with_context("path", checkpoint_path.display().to_string()),
         ));
     };
     dispersion_for_state(&code, &graph, species, options)
@@ -0,0 +1,56 @@
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Returns the temporary sibling `<name>.tmp` used while replacing `path`.
+pub fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().map(OsString::from).unwrap_or_default();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+/// Atomically replaces `path` with `bytes`.
+pub fn write_atomic(path: &Path, bytes: impl AsRef<[u8]>) -> io::Result<()> {
+    write_atomic_with(path, |writer| writer.write_all(bytes.as_ref()))
+}
+
+/// Atomically replaces `path` with the output of `write`.
+///
+/// The content is written to [`temp_path`] in the same directory, flushed and
+/// fsynced, then renamed over `path` and the directory is fsynced. If `write`
+/// fails the temporary file is removed and `path` keeps its previous content.
+pub fn write_atomic_with<F>(path: &Path, write: F) -> io::Result<()>
+where
+    F: FnOnce(&mut dyn Write) -> io::Result<()>,
+{
+    let tmp = temp_path(path);
+    let result = File::create(&tmp).and_then(|mut file| {
+        write(&mut file)?;
+        file.flush()?;
+        file.sync_all()
+    });
+    if let Err(err) = result.and_then(|()| fs::rename(&tmp, path)) {
+        let _ = fs::remove_file(&tmp);
+        return Err(err);
+    }
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => sync_dir(parent),
+        _ => sync_dir(Path::new(".")),
+    }
+}
+
+/// Fsyncs a directory so renames and removals inside it are durable.
+#[cfg(unix)]
+pub fn sync_dir(dir: &Path) -> io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+/// Fsyncs a directory so renames and removals inside it are durable.
+///
+/// Directories cannot be opened for syncing on this platform; renames are
+/// flushed by the filesystem.
+#[cfg(not(unix))]
+pub fn sync_dir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
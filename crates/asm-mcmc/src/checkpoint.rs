@@ -1,14 +1,18 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use asm_code::css::CSSCode;
 use asm_code::serde as code_serde;
-use asm_core::errors::ErrorInfo;
+use asm_core::errors::{ErrorCode, ErrorInfo};
 use asm_core::AsmError;
 use asm_graph::{graph_from_json, graph_to_json, HypergraphImpl};
 use serde::{Deserialize, Serialize};
 
+use crate::atomic::write_atomic;
 use crate::energy::EnergyBreakdown;
+use crate::kernel::MoveKind;
+use crate::metrics::RecorderSnapshot;
 
 /// Serializable payload representing a checkpointed replica.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,12 +25,50 @@ pub struct ReplicaCheckpoint {
     pub graph_json: String,
     /// Energy at the time of checkpointing.
     pub energy: EnergyBreakdown,
+    /// Accepted proposal counters, recorded by mid-sweep checkpoints only.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub accepted: BTreeMap<MoveKind, usize>,
+    /// Proposed move counters, recorded by mid-sweep checkpoints only.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub proposed: BTreeMap<MoveKind, usize>,
+}
+
+/// Within-sweep position of the next proposal to execute.
+///
+/// Replicas before `replica` have finished the sweep; replica `replica` has
+/// executed every move slot below `move_slot`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SweepPosition {
+    /// Index of the replica whose moves are in progress.
+    pub replica: usize,
+    /// Next move slot to execute for that replica.
+    pub move_slot: usize,
+}
+
+/// Run-level bookkeeping recorded by mid-sweep checkpoints.
+///
+/// Restoring it lets a resumed run report the same summary, metrics and
+/// retained checkpoint list as an uninterrupted one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProgressSnapshot {
+    /// Metrics recorded by the completed sweeps and worm moves so far.
+    pub metrics: RecorderSnapshot,
+    /// Summed exchange acceptance probabilities per adjacent replica pair.
+    pub exchange_totals: Vec<f64>,
+    /// Exchange attempts per adjacent replica pair.
+    pub exchange_counts: Vec<usize>,
+    /// Retained checkpoint files relative to the run directory, including
+    /// this one.
+    pub checkpoints: Vec<PathBuf>,
 }
 
 /// Aggregated checkpoint payload.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CheckpointPayload {
     /// Sweep number when the checkpoint was written.
+    ///
+    /// For sweep-boundary checkpoints this is the number of completed sweeps;
+    /// when [`CheckpointPayload::position`] is set it is the interrupted sweep.
     pub sweep: usize,
     /// Configuration snapshot associated with the run.
     pub config: crate::config::RunConfig,
@@ -34,6 +76,12 @@ pub struct CheckpointPayload {
     pub master_seed: u64,
     /// Replica states stored in the checkpoint.
     pub replicas: Vec<ReplicaCheckpoint>,
+    /// Within-sweep position for checkpoints taken mid-sweep.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<SweepPosition>,
+    /// Run bookkeeping recorded alongside [`CheckpointPayload::position`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress: Option<ProgressSnapshot>,
 }
 
 impl CheckpointPayload {
@@ -41,37 +89,38 @@ impl CheckpointPayload {
     pub fn load(path: &Path) -> Result<Self, AsmError> {
         let contents = fs::read_to_string(path).map_err(|err| {
             AsmError::Serde(
-                ErrorInfo::new("checkpoint-read", err.to_string())
+                ErrorInfo::new(ErrorCode::CheckpointRead, err.to_string())
                     .with_context("path", path.display().to_string()),
             )
         })?;
         serde_json::from_str(&contents).map_err(|err| {
             AsmError::Serde(
-                ErrorInfo::new("checkpoint-parse", err.to_string())
+                ErrorInfo::new(ErrorCode::CheckpointParse, err.to_string())
                     .with_context("path", path.display().to_string()),
             )
         })
     }
 
-    /// Writes the payload to disk.
+    /// Atomically writes the payload to disk and fsyncs the containing
+    /// directory.
     pub fn store(&self, path: &Path) -> Result<(), AsmError> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent).map_err(|err| {
                 AsmError::Serde(
-                    ErrorInfo::new("checkpoint-mkdir", err.to_string())
+                    ErrorInfo::new(ErrorCode::CheckpointMkdir, err.to_string())
                         .with_context("path", parent.display().to_string()),
                 )
             })?;
         }
         let json = serde_json::to_string_pretty(self).map_err(|err| {
             AsmError::Serde(
-                ErrorInfo::new("checkpoint-serialize", err.to_string())
+                ErrorInfo::new(ErrorCode::CheckpointSerialize, err.to_string())
                     .with_context("path", path.display().to_string()),
             )
         })?;
-        fs::write(path, json).map_err(|err| {
+        write_atomic(path, json).map_err(|err| {
             AsmError::Serde(
-                ErrorInfo::new("checkpoint-write", err.to_string())
+                ErrorInfo::new(ErrorCode::CheckpointWrite, err.to_string())
                     .with_context("path", path.display().to_string()),
             )
         })
@@ -90,6 +139,8 @@ pub fn build_payload(
         config: config.clone(),
         master_seed,
         replicas: Vec::with_capacity(replicas.len()),
+        position: None,
+        progress: None,
     };
     for (temperature, code, graph, energy) in replicas {
         let code_json = code_serde::to_json(code)?;
@@ -99,6 +150,8 @@ pub fn build_payload(
             code_json,
             graph_json,
             energy: (*energy).clone(),
+            accepted: BTreeMap::new(),
+            proposed: BTreeMap::new(),
         });
     }
     Ok(payload)
@@ -121,3 +174,11 @@ pub fn restore_payload(
 pub fn checkpoint_path(root: &Path, sweep: usize) -> PathBuf {
     root.join(format!("ckpt_{sweep:05}.json"))
 }
+
+/// Path of a mid-sweep checkpoint taken before the proposal at `position` of `sweep`.
+pub fn mid_sweep_checkpoint_path(root: &Path, sweep: usize, position: SweepPosition) -> PathBuf {
+    root.join(format!(
+        "ckpt_{sweep:05}_r{:03}_m{:05}.json",
+        position.replica, position.move_slot
+    ))
+}
@@ -158,6 +158,12 @@ pub struct CheckpointConfig {
     /// Maximum number of checkpoints to retain.
     #[serde(default = "default_checkpoint_retention")]
     pub max_to_keep: usize,
+    /// Interval in proposals between mid-sweep checkpoint writes (0 disables them).
+    ///
+    /// Proposals are counted across replicas from the start of the run, so a
+    /// resumed run writes the same files as an uninterrupted one.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub move_interval: usize,
 }
 
 fn default_checkpoint_retention() -> usize {
@@ -170,6 +176,7 @@ impl Default for CheckpointConfig {
             interval: 0,
             directory: None,
             max_to_keep: default_checkpoint_retention(),
+            move_interval: 0,
         }
     }
 }
@@ -186,6 +193,14 @@ pub struct ScoringWeights {
     /// Weight for the curvature variance proxy.
     #[serde(default = "default_curv_weight")]
     pub curv: f64,
+    /// Leave isolated nodes out of the curvature proxy (the default). When
+    /// disabled they enter the node curvature variance with zero curvature.
+    #[serde(default = "default_exclude_isolated", skip_serializing_if = "is_true")]
+    pub exclude_isolated: bool,
+    /// Rescore proposals incrementally from cached proxy intermediates (the
+    /// default). Disable to recompute every proposal with [`crate::score`].
+    #[serde(default = "default_incremental", skip_serializing_if = "is_true")]
+    pub incremental: bool,
 }
 
 fn default_cmdl_weight() -> f64 {
@@ -200,12 +215,30 @@ fn default_curv_weight() -> f64 {
     1.0
 }
 
+fn default_exclude_isolated() -> bool {
+    true
+}
+
+fn default_incremental() -> bool {
+    true
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
+}
+
 impl Default for ScoringWeights {
     fn default() -> Self {
         Self {
             cmdl: default_cmdl_weight(),
             spec: default_specreg_weight(),
             curv: default_curv_weight(),
+            exclude_isolated: default_exclude_isolated(),
+            incremental: default_incremental(),
         }
     }
 }
@@ -219,6 +252,25 @@ pub struct SeedPolicy {
     /// Optional label used when deriving substream seeds (documented in manifests).
     #[serde(default)]
     pub label: Option<String>,
+    /// How replica, move, and exchange seeds are derived from the master seed.
+    #[serde(default)]
+    pub streams: StreamDerivation,
+    /// Record every seed derivation and store the audit summary in the
+    /// manifest. Requires the `seed-audit` feature.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub audit: bool,
+}
+
+/// Seed derivation scheme for sampler substreams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StreamDerivation {
+    /// Labelled streams hashed from a component name and index.
+    #[default]
+    Labeled,
+    /// Packed numeric substream indices used before labelled streams.
+    /// Deprecated; kept for one release so existing fixtures reproduce.
+    Numeric,
 }
 
 fn default_master_seed() -> u64 {
@@ -230,6 +282,8 @@ impl Default for SeedPolicy {
         Self {
             master_seed: default_master_seed(),
             label: None,
+            streams: StreamDerivation::default(),
+            audit: false,
         }
     }
 }
@@ -252,6 +306,10 @@ pub struct OutputConfig {
     /// Directory for final end-state exports.
     #[serde(default = "default_end_state_dir")]
     pub end_state_dir: PathBuf,
+    /// Remove isolated nodes from the exported end-state graph. Checkpoints
+    /// and the recorded live-state hash keep the full graph.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub prune_end_state: bool,
 }
 
 fn default_metrics_filename() -> PathBuf {
@@ -278,6 +336,7 @@ impl Default for OutputConfig {
             manifest_file: default_manifest_filename(),
             checkpoint_dir: default_checkpoint_dir(),
             end_state_dir: default_end_state_dir(),
+            prune_end_state: false,
         }
     }
 }
@@ -1,6 +1,14 @@
-use asm_core::derive_substream_seed;
+#[cfg(feature = "seed-audit")]
+use asm_core::SeedAudit;
+use asm_core::{
+    derive_labeled_seed, derive_substream_seed, AsmError, SeedAuditSummary, StreamLabel,
+};
+
+use crate::config::{SeedPolicy, StreamDerivation};
 
 /// Derives the deterministic seed used for a specific replica.
+///
+/// Numeric scheme; see [`SeedStreams`] for the labelled derivation.
 pub fn replica_seed(master_seed: u64, replica_index: usize) -> u64 {
     derive_substream_seed(master_seed, replica_index as u64)
 }
@@ -19,3 +27,101 @@ pub fn exchange_seed(master_seed: u64, sweep: usize, pair_index: usize) -> u64 {
         (sweep as u64) << 16 | pair_index as u64,
     )
 }
+
+/// Label of the stream seeding replica `replica_index`.
+pub fn replica_label(replica_index: usize) -> StreamLabel {
+    StreamLabel::new("mcmc.replica", replica_index as u64)
+}
+
+/// Label of the stream seeding the proposal in `move_slot`.
+pub fn move_label(replica_index: usize, sweep: usize, move_slot: usize) -> StreamLabel {
+    StreamLabel::new(
+        format!("mcmc.move/replica-{replica_index}/sweep-{sweep}"),
+        move_slot as u64,
+    )
+}
+
+/// Label of the stream seeding the exchange between replicas `pair_index`
+/// and `pair_index + 1`.
+pub fn exchange_label(sweep: usize, pair_index: usize) -> StreamLabel {
+    StreamLabel::new(format!("mcmc.exchange/sweep-{sweep}"), pair_index as u64)
+}
+
+/// Seed source for a sampler run, following the configured [`SeedPolicy`].
+#[derive(Debug)]
+pub struct SeedStreams {
+    master_seed: u64,
+    derivation: StreamDerivation,
+    #[cfg(feature = "seed-audit")]
+    audit: Option<SeedAudit>,
+}
+
+impl SeedStreams {
+    /// Creates the seed source for `master_seed`.
+    ///
+    /// Fails when the policy requests an audit but the `seed-audit` feature
+    /// is disabled.
+    pub fn new(master_seed: u64, policy: &SeedPolicy) -> Result<Self, AsmError> {
+        #[cfg(not(feature = "seed-audit"))]
+        if policy.audit {
+            return Err(AsmError::Rng(asm_core::ErrorInfo::new(
+                asm_core::ErrorCode::SeedAuditUnavailable,
+                "seed_policy.audit requires the `seed-audit` feature",
+            )));
+        }
+        Ok(Self {
+            master_seed,
+            derivation: policy.streams,
+            #[cfg(feature = "seed-audit")]
+            audit: policy.audit.then(SeedAudit::new),
+        })
+    }
+
+    /// Seed of replica `replica_index`.
+    pub fn replica(&self, replica_index: usize) -> u64 {
+        match self.derivation {
+            StreamDerivation::Numeric => replica_seed(self.master_seed, replica_index),
+            StreamDerivation::Labeled => self.labeled(&replica_label(replica_index)),
+        }
+    }
+
+    /// Seed of the proposal in `move_slot` of `replica_index` during `sweep`.
+    pub fn proposal(&self, replica_index: usize, sweep: usize, move_slot: usize) -> u64 {
+        match self.derivation {
+            StreamDerivation::Numeric => {
+                move_seed(self.master_seed, replica_index, sweep, move_slot)
+            }
+            StreamDerivation::Labeled => self.labeled(&move_label(replica_index, sweep, move_slot)),
+        }
+    }
+
+    /// Seed of the exchange attempt for `pair_index` during `sweep`.
+    pub fn exchange(&self, sweep: usize, pair_index: usize) -> u64 {
+        match self.derivation {
+            StreamDerivation::Numeric => exchange_seed(self.master_seed, sweep, pair_index),
+            StreamDerivation::Labeled => self.labeled(&exchange_label(sweep, pair_index)),
+        }
+    }
+
+    /// Summary of the recorded derivations, when auditing is enabled.
+    ///
+    /// Only labelled derivations are recorded.
+    pub fn audit_summary(&self) -> Option<SeedAuditSummary> {
+        #[cfg(feature = "seed-audit")]
+        {
+            self.audit.as_ref().map(SeedAudit::summary)
+        }
+        #[cfg(not(feature = "seed-audit"))]
+        {
+            None
+        }
+    }
+
+    fn labeled(&self, label: &StreamLabel) -> u64 {
+        #[cfg(feature = "seed-audit")]
+        if let Some(audit) = &self.audit {
+            return audit.derive(self.master_seed, label);
+        }
+        derive_labeled_seed(self.master_seed, label)
+    }
+}
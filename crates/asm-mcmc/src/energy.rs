@@ -1,10 +1,15 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
 use asm_code::css;
 use asm_code::css::CSSCode;
-use asm_core::AsmError;
+use asm_core::{AsmError, EdgeId, HyperedgeEndpoints, Hypergraph, NodeId};
 use asm_graph::{forman_curvature_edges, forman_curvature_nodes, HypergraphImpl};
 use serde::{Deserialize, Serialize};
 
 use crate::config::ScoringWeights;
+use crate::moves_code::{CodeChange, GeneratorFamily};
+use crate::moves_graph::GraphChange;
 
 /// Breakdown of the scoring proxies used to construct the total energy.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -39,7 +44,7 @@ pub fn score(
 ) -> Result<EnergyBreakdown, AsmError> {
     let cmdl = cmdl_proxy(code);
     let spec = spec_proxy(code);
-    let curv = curv_proxy(graph)?;
+    let curv = curv_proxy(graph, weights.exclude_isolated)?;
 
     let total = weights.cmdl * cmdl + weights.spec * spec + weights.curv * curv;
 
@@ -113,13 +118,22 @@ fn spec_proxy(code: &CSSCode) -> f64 {
     rank_deficit.powi(2) + support_var + sparse_penalty
 }
 
-fn curv_proxy(graph: &HypergraphImpl) -> Result<f64, AsmError> {
+fn curv_proxy(graph: &HypergraphImpl, exclude_isolated: bool) -> Result<f64, AsmError> {
     let node_curvatures = forman_curvature_nodes(graph)?;
     let edge_curvatures = forman_curvature_edges(graph)?;
-    let node_vals: Vec<f64> = node_curvatures
+    let mut node_vals: Vec<f64> = node_curvatures
         .iter()
         .map(|(_, value)| *value as f64)
         .collect();
+    // Forman node curvature averages over incident edges, so isolated nodes
+    // are absent from `node_curvatures` unless counted explicitly.
+    if !exclude_isolated {
+        for node in graph.nodes() {
+            if graph.edges_touching(node)?.is_empty() {
+                node_vals.push(0.0);
+            }
+        }
+    }
     let edge_vals: Vec<f64> = edge_curvatures
         .iter()
         .map(|(_, value)| *value as f64)
@@ -137,3 +151,351 @@ fn variance(values: &[f64]) -> Option<f64> {
     let sq_mean = values.iter().copied().map(|v| v * v).sum::<f64>() / values.len() as f64;
     Some((sq_mean - mean * mean).max(0.0))
 }
+
+/// Cached proxy intermediates that allow rescoring a local move without
+/// recomputing the energy from scratch.
+///
+/// The code proxies share per-generator terms keyed by support, and the
+/// curvature proxy keeps the Forman curvature of every edge and node. Cloning
+/// a state is cheap: the two halves are shared until a move modifies them.
+#[derive(Debug, Clone)]
+pub struct ProxyState {
+    weights: ScoringWeights,
+    code: Arc<CodeTerms>,
+    curv: Arc<CurvatureTerms>,
+}
+
+/// Local change described by a move proposal, paired with its candidate.
+#[derive(Debug, Clone, Copy)]
+pub enum LocalChange<'a> {
+    /// A code move producing `candidate`; the graph is unchanged.
+    Code {
+        /// Candidate code returned by the proposal.
+        candidate: &'a CSSCode,
+        /// Generators modified by the proposal.
+        change: &'a CodeChange,
+    },
+    /// A graph move producing `candidate`; the code is unchanged.
+    Graph {
+        /// Candidate graph returned by the proposal.
+        candidate: &'a HypergraphImpl,
+        /// Edges rewired by the proposal.
+        change: &'a GraphChange,
+    },
+}
+
+impl ProxyState {
+    /// Builds the cached intermediates for a code/graph pair.
+    pub fn new(
+        code: &CSSCode,
+        graph: &HypergraphImpl,
+        weights: &ScoringWeights,
+    ) -> Result<Self, AsmError> {
+        Ok(Self {
+            weights: weights.clone(),
+            code: Arc::new(CodeTerms::new(code)),
+            curv: Arc::new(CurvatureTerms::new(graph)?),
+        })
+    }
+
+    /// Energy breakdown of the cached state, equal to [`score`] on the pair the
+    /// state describes.
+    pub fn breakdown(&self) -> EnergyBreakdown {
+        let cmdl = self.code.cmdl();
+        let spec = self.code.spec();
+        let curv = self.curv.curv(self.weights.exclude_isolated);
+        let total = self.weights.cmdl * cmdl + self.weights.spec * spec + self.weights.curv * curv;
+        EnergyBreakdown {
+            cmdl,
+            spec,
+            curv,
+            total,
+        }
+    }
+}
+
+/// Rescores `state` after a local move, recomputing only the generators,
+/// edges, and nodes affected by `change`.
+///
+/// Returns the candidate energy together with the state describing the
+/// candidate; `state` itself is left untouched so rejected moves cost nothing.
+pub fn rescore_delta(
+    state: &ProxyState,
+    change: LocalChange<'_>,
+) -> Result<(EnergyBreakdown, ProxyState), AsmError> {
+    let mut next = state.clone();
+    match change {
+        LocalChange::Code { candidate, change } => {
+            next.code = Arc::new(state.code.apply(candidate, change));
+        }
+        LocalChange::Graph { candidate, change } => {
+            next.curv = Arc::new(state.curv.apply(candidate, change)?);
+        }
+    }
+    Ok((next.breakdown(), next))
+}
+
+/// Per-generator contributions to the cMDL and spectrum proxies.
+#[derive(Debug, Clone)]
+struct GeneratorTerms {
+    support: usize,
+    /// Gap logarithms in the order `cmdl_proxy` accumulates them.
+    lz_terms: Vec<f64>,
+    sparse: f64,
+}
+
+impl GeneratorTerms {
+    fn new(variables: &[usize], vars: usize) -> Self {
+        let mut lz_terms = Vec::with_capacity(variables.len() + 1);
+        let mut prev = 0usize;
+        for &var in variables {
+            let gap = var.abs_diff(prev);
+            lz_terms.push((gap as f64 + 1.0).ln());
+            prev = var;
+        }
+        if vars > 0 {
+            let tail_gap = vars - prev.min(vars - 1);
+            lz_terms.push((tail_gap as f64 + 1.0).ln());
+        }
+        let sparse = if vars > 0 {
+            ((variables.len() as f64 + 1.0) / vars as f64).powf(0.75)
+        } else {
+            0.0
+        };
+        Self {
+            support: variables.len(),
+            lz_terms,
+            sparse,
+        }
+    }
+}
+
+/// Cache behind the cMDL and spectrum proxies. Generators are keyed by
+/// support, matching the sorted order [`CSSCode`] stores them in, so folds
+/// over the cache add terms in the same order as the full proxies.
+#[derive(Debug, Clone)]
+struct CodeTerms {
+    vars: usize,
+    rank_x: usize,
+    rank_z: usize,
+    x: BTreeMap<Vec<usize>, GeneratorTerms>,
+    z: BTreeMap<Vec<usize>, GeneratorTerms>,
+}
+
+impl CodeTerms {
+    fn new(code: &CSSCode) -> Self {
+        let (vars, x_checks, z_checks, _, _, _, _) = css::into_parts(code);
+        let terms = |checks: Vec<css::Constraint>| {
+            checks
+                .iter()
+                .map(|constraint| {
+                    let support = constraint.variables().to_vec();
+                    let terms = GeneratorTerms::new(&support, vars);
+                    (support, terms)
+                })
+                .collect()
+        };
+        Self {
+            vars,
+            rank_x: code.rank_x(),
+            rank_z: code.rank_z(),
+            x: terms(x_checks),
+            z: terms(z_checks),
+        }
+    }
+
+    fn apply(&self, candidate: &CSSCode, change: &CodeChange) -> Self {
+        let mut next = self.clone();
+        next.rank_x = candidate.rank_x();
+        next.rank_z = candidate.rank_z();
+        for generator in &change.generators {
+            let family = match generator.family {
+                GeneratorFamily::X => &mut next.x,
+                GeneratorFamily::Z => &mut next.z,
+            };
+            family.remove(&generator.before);
+            let terms = GeneratorTerms::new(&generator.after, next.vars);
+            family.insert(generator.after.clone(), terms);
+        }
+        // A change that does not describe the candidate (for example a
+        // support missing from the cache) falls back to a full rebuild.
+        if next.vars != candidate.num_variables()
+            || next.x.len() != candidate.num_constraints_x()
+            || next.z.len() != candidate.num_constraints_z()
+        {
+            return Self::new(candidate);
+        }
+        next
+    }
+
+    fn generators(&self) -> impl Iterator<Item = &GeneratorTerms> {
+        self.x.values().chain(self.z.values())
+    }
+
+    fn cmdl(&self) -> f64 {
+        let generator_count = (self.x.len() + self.z.len()) as f64;
+        if generator_count == 0.0 {
+            return 0.0;
+        }
+        let total_support: usize = self.generators().map(|terms| terms.support).sum();
+        let avg_support = total_support as f64 / generator_count.max(1.0);
+        let mut lz_proxy = 0.0;
+        for term in self.generators().flat_map(|terms| terms.lz_terms.iter()) {
+            lz_proxy += term;
+        }
+        generator_count + avg_support + lz_proxy / generator_count.max(1.0)
+    }
+
+    fn spec(&self) -> f64 {
+        let rank_x = self.rank_x as f64;
+        let rank_z = self.rank_z as f64;
+        let nx = self.x.len() as f64;
+        let nz = self.z.len() as f64;
+        let rank_deficit = (nx + nz) - (rank_x + rank_z);
+        let supports: Vec<f64> = self
+            .generators()
+            .map(|terms| terms.support as f64)
+            .collect();
+        let support_var = variance(&supports).unwrap_or(0.0);
+        let sparse_penalty = if self.vars > 0 {
+            self.generators().map(|terms| terms.sparse).sum::<f64>() / supports.len().max(1) as f64
+        } else {
+            0.0
+        };
+        rank_deficit.powi(2) + support_var + sparse_penalty
+    }
+}
+
+/// Cache behind the curvature proxy: Forman curvature of every edge and of
+/// every node with at least one incident edge.
+#[derive(Debug, Clone)]
+struct CurvatureTerms {
+    endpoints: BTreeMap<EdgeId, HyperedgeEndpoints>,
+    edges: BTreeMap<EdgeId, f32>,
+    nodes: BTreeMap<NodeId, f32>,
+    isolated: BTreeSet<NodeId>,
+}
+
+impl CurvatureTerms {
+    fn new(graph: &HypergraphImpl) -> Result<Self, AsmError> {
+        let mut endpoints = BTreeMap::new();
+        for edge in graph.edges() {
+            endpoints.insert(edge, graph.hyperedge(edge)?);
+        }
+        let mut isolated = BTreeSet::new();
+        for node in graph.nodes() {
+            if graph.edges_touching(node)?.is_empty() {
+                isolated.insert(node);
+            }
+        }
+        Ok(Self {
+            endpoints,
+            edges: forman_curvature_edges(graph)?.into_iter().collect(),
+            nodes: forman_curvature_nodes(graph)?.into_iter().collect(),
+            isolated,
+        })
+    }
+
+    fn apply(&self, candidate: &HypergraphImpl, change: &GraphChange) -> Result<Self, AsmError> {
+        let mut next = self.clone();
+        // Nodes whose degree may have changed: old and new endpoints of every
+        // rewired edge.
+        let mut degree_nodes = BTreeSet::new();
+        for edge in &change.edges {
+            let endpoints = candidate.hyperedge(*edge)?;
+            if let Some(old) = next.endpoints.insert(*edge, endpoints.clone()) {
+                degree_nodes.extend(old.sources.iter().chain(old.destinations.iter()));
+            }
+            degree_nodes.extend(
+                endpoints
+                    .sources
+                    .iter()
+                    .chain(endpoints.destinations.iter()),
+            );
+        }
+        // Edge curvature depends on the degrees of its endpoints.
+        let mut edges: BTreeSet<EdgeId> = change.edges.iter().copied().collect();
+        for node in &degree_nodes {
+            edges.extend(candidate.edges_touching(*node)?);
+        }
+        for edge in &edges {
+            let value = edge_curvature(candidate, &next.endpoints[edge])?;
+            next.edges.insert(*edge, value);
+        }
+        // Node curvature averages the curvature of its incident edges.
+        let mut nodes = degree_nodes;
+        for edge in &edges {
+            let endpoints = &next.endpoints[edge];
+            nodes.extend(
+                endpoints
+                    .sources
+                    .iter()
+                    .chain(endpoints.destinations.iter()),
+            );
+        }
+        for node in nodes {
+            match next.node_curvature(candidate, node)? {
+                Some(value) => {
+                    next.nodes.insert(node, value);
+                    next.isolated.remove(&node);
+                }
+                None => {
+                    next.nodes.remove(&node);
+                    next.isolated.insert(node);
+                }
+            }
+        }
+        Ok(next)
+    }
+
+    /// Mirrors `forman_curvature_nodes`: incident edge curvatures are summed
+    /// in edge order, once per occurrence of `node` among the endpoints.
+    fn node_curvature(
+        &self,
+        graph: &HypergraphImpl,
+        node: NodeId,
+    ) -> Result<Option<f32>, AsmError> {
+        let mut sum = 0.0f32;
+        let mut count = 0usize;
+        for edge in graph.edges_touching(node)? {
+            let endpoints = &self.endpoints[&edge];
+            let curvature = self.edges[&edge];
+            for endpoint in endpoints
+                .sources
+                .iter()
+                .chain(endpoints.destinations.iter())
+            {
+                if *endpoint == node {
+                    sum += curvature;
+                    count += 1;
+                }
+            }
+        }
+        Ok((count > 0).then(|| sum / count as f32))
+    }
+
+    fn curv(&self, exclude_isolated: bool) -> f64 {
+        let mut node_vals: Vec<f64> = self.nodes.values().map(|value| *value as f64).collect();
+        if !exclude_isolated {
+            node_vals.extend(self.isolated.iter().map(|_| 0.0));
+        }
+        let edge_vals: Vec<f64> = self.edges.values().map(|value| *value as f64).collect();
+        let node_var = variance(&node_vals).unwrap_or(0.0);
+        let edge_var = variance(&edge_vals).unwrap_or(0.0);
+        (node_var + edge_var) / 2.0
+    }
+}
+
+/// Forman curvature of a single edge, as computed by `forman_curvature_edges`.
+fn edge_curvature(graph: &HypergraphImpl, endpoints: &HyperedgeEndpoints) -> Result<f32, AsmError> {
+    let mut value = 2.0 - (endpoints.sources.len() + endpoints.destinations.len()) as f32;
+    for source in endpoints.sources.iter() {
+        let degree = graph.out_degree(*source)? as f32;
+        value += 1.0 / (1.0 + degree);
+    }
+    for dest in endpoints.destinations.iter() {
+        let degree = graph.in_degree(*dest)? as f32;
+        value += 1.0 / (1.0 + degree);
+    }
+    Ok(value)
+}
@@ -2,17 +2,18 @@ use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use asm_code::css::{self, CSSCode};
-use asm_core::errors::ErrorInfo;
-use asm_core::{AsmError, RngHandle};
+use asm_core::errors::{ErrorCode, ErrorInfo};
+use asm_core::{build_fingerprint, AsmError, RngHandle};
 use asm_graph::{canonical_hash as graph_hash, graph_to_json, HypergraphImpl};
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
-use crate::checkpoint::{self, CheckpointPayload};
+use crate::atomic::write_atomic;
+use crate::checkpoint::{self, CheckpointPayload, ProgressSnapshot, SweepPosition};
 use crate::config::{OutputConfig, RunConfig, ScoringWeights};
-use crate::determinism;
-use crate::energy::{self, EnergyBreakdown};
-use crate::manifest::RunManifest;
+use crate::determinism::SeedStreams;
+use crate::energy::{self, EnergyBreakdown, LocalChange, ProxyState};
+use crate::manifest::{EndStatePruning, RunManifest};
 use crate::metrics::{self, CoverageMetrics, MetricSample, MetricsRecorder};
 use crate::moves_code;
 use crate::moves_graph;
@@ -77,8 +78,14 @@ pub struct RunSummary {
     pub exchange_acceptance: Vec<f64>,
     /// Coverage metrics captured during the run.
     pub coverage: CoverageMetrics,
-    /// Crude effective sample size estimate.
+    /// Legacy Jaccard-based effective sample size, kept for compatibility.
     pub effective_sample_size: f64,
+    /// Effective sample size per energy observable of the cold replica.
+    #[serde(default)]
+    pub ess: BTreeMap<String, f64>,
+    /// Whether the cold chain was too short for an autocorrelation estimate.
+    #[serde(default)]
+    pub ess_short_chain: bool,
     /// Canonical hash of the coldest code state at the end of the run.
     pub final_code_hash: String,
     /// Canonical hash of the coldest graph state at the end of the run.
@@ -99,6 +106,8 @@ struct ReplicaState {
     code: CSSCode,
     graph: HypergraphImpl,
     energy: EnergyBreakdown,
+    /// Cached proxy intermediates; `None` when incremental scoring is disabled.
+    proxies: Option<ProxyState>,
     accepted: BTreeMap<MoveKind, usize>,
     proposed: BTreeMap<MoveKind, usize>,
 }
@@ -111,11 +120,13 @@ impl ReplicaState {
         weights: &ScoringWeights,
     ) -> Result<Self, AsmError> {
         let energy = energy::score(&code, &graph, weights)?;
+        let proxies = proxy_state(&code, &graph, weights)?;
         Ok(Self {
             temperature,
             code,
             graph,
             energy,
+            proxies,
             accepted: BTreeMap::new(),
             proposed: BTreeMap::new(),
         })
@@ -129,6 +140,18 @@ impl ReplicaState {
     }
 }
 
+fn proxy_state(
+    code: &CSSCode,
+    graph: &HypergraphImpl,
+    weights: &ScoringWeights,
+) -> Result<Option<ProxyState>, AsmError> {
+    if weights.incremental {
+        ProxyState::new(code, graph, weights).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
 /// Runs the MCMC sampler from scratch with the provided configuration and seed.
 pub fn run(
     config: &RunConfig,
@@ -136,99 +159,375 @@ pub fn run(
     code: &CSSCode,
     graph: &HypergraphImpl,
 ) -> Result<RunSummary, AsmError> {
-    let ladder = tempering::build_ladder(&config.ladder);
-    let mut replicas = Vec::new();
-    for (index, &temperature) in ladder.iter().enumerate() {
-        let replica_code = clone_code(code);
-        let replica_graph = graph.clone();
-        replicas.push(ReplicaState::new(
-            temperature,
-            replica_code,
-            replica_graph,
-            &config.scoring,
-        )?);
-        // ensure deterministic seeds are at least exercised.
-        let _ = determinism::replica_seed(seed, index);
-    }
-    run_with_replicas(config, seed, ladder, replicas, 0, config.sweeps)
+    let (ladder, replicas) = initial_replicas(config, code, graph)?;
+    run_with_replicas(
+        config,
+        seed,
+        ladder,
+        replicas,
+        (0, None),
+        None,
+        config.sweeps,
+    )
+}
+
+/// Runs from scratch and suspends immediately before the proposal at `position` of `sweep`.
+///
+/// The returned payload records the partial sweep so [`resume_payload`] (or
+/// [`resume`] after [`CheckpointPayload::store`]) continues with exactly the
+/// proposals an uninterrupted run would have made.
+pub fn run_until(
+    config: &RunConfig,
+    seed: u64,
+    code: &CSSCode,
+    graph: &HypergraphImpl,
+    sweep: usize,
+    position: SweepPosition,
+) -> Result<CheckpointPayload, AsmError> {
+    let (ladder, mut replicas) = initial_replicas(config, code, graph)?;
+    validate_position(config, sweep, position, replicas.len())?;
+    let mut progress = SweepProgress::new(config, seed, ladder.len())?;
+    advance_sweeps(
+        config,
+        seed,
+        &mut replicas,
+        &mut progress,
+        (0, None),
+        config.sweeps,
+        Some((sweep, position)),
+    )?;
+    mid_sweep_payload(
+        config,
+        seed,
+        sweep,
+        position,
+        &replicas,
+        &progress,
+        &progress.checkpoints,
+    )
 }
 
 /// Resumes a run from a checkpoint file.
 pub fn resume(path: &Path) -> Result<RunSummary, AsmError> {
     let payload = CheckpointPayload::load(path)?;
-    let states = checkpoint::restore_payload(&payload)?;
-    if states.is_empty() {
+    if payload.replicas.is_empty() {
         return Err(AsmError::Serde(
-            ErrorInfo::new("empty-checkpoint", "checkpoint contained no replicas")
-                .with_context("path", path.display().to_string()),
+            ErrorInfo::new(
+                ErrorCode::EmptyCheckpoint,
+                "checkpoint contained no replicas",
+            )
+            .with_context("path", path.display().to_string()),
         ));
     }
+    resume_payload(&payload)
+}
+
+/// Resumes a run from an in-memory checkpoint payload.
+///
+/// Sweep-boundary payloads rebuild temperatures from the ladder and restart the
+/// move counters. Mid-sweep payloads restore the recorded temperatures,
+/// counters and [`CheckpointPayload::progress`] and continue from
+/// [`CheckpointPayload::position`].
+pub fn resume_payload(payload: &CheckpointPayload) -> Result<RunSummary, AsmError> {
+    let states = checkpoint::restore_payload(payload)?;
+    if states.is_empty() {
+        return Err(AsmError::Serde(ErrorInfo::new(
+            ErrorCode::EmptyCheckpoint,
+            "checkpoint contained no replicas",
+        )));
+    }
     let ladder = tempering::build_ladder(&payload.config.ladder);
     let mut replicas = Vec::new();
-    for (idx, (temperature, code, graph, energy)) in states.into_iter().enumerate() {
-        let temp = ladder.get(idx).copied().unwrap_or(temperature);
+    for (idx, ((temperature, code, graph, energy), stored)) in
+        states.into_iter().zip(&payload.replicas).enumerate()
+    {
+        let temp = if payload.position.is_some() {
+            temperature
+        } else {
+            ladder.get(idx).copied().unwrap_or(temperature)
+        };
+        let proxies = proxy_state(&code, &graph, &payload.config.scoring)?;
         replicas.push(ReplicaState {
             temperature: temp,
             code,
             graph,
             energy,
-            accepted: BTreeMap::new(),
-            proposed: BTreeMap::new(),
+            proxies,
+            accepted: stored.accepted.clone(),
+            proposed: stored.proposed.clone(),
         });
     }
+    if let Some(position) = payload.position {
+        validate_position(&payload.config, payload.sweep, position, replicas.len())?;
+    }
     let start_sweep = payload.sweep.min(payload.config.sweeps);
     run_with_replicas(
         &payload.config,
         payload.master_seed,
         ladder,
         replicas,
-        start_sweep,
+        (start_sweep, payload.position),
+        payload
+            .progress
+            .as_ref()
+            .filter(|_| payload.position.is_some()),
         payload.config.sweeps,
     )
 }
 
-fn run_with_replicas(
+fn initial_replicas(
+    config: &RunConfig,
+    code: &CSSCode,
+    graph: &HypergraphImpl,
+) -> Result<(Vec<f64>, Vec<ReplicaState>), AsmError> {
+    let ladder = tempering::build_ladder(&config.ladder);
+    let mut replicas = Vec::new();
+    for &temperature in &ladder {
+        let replica_code = clone_code(code);
+        let replica_graph = graph.clone();
+        replicas.push(ReplicaState::new(
+            temperature,
+            replica_code,
+            replica_graph,
+            &config.scoring,
+        )?);
+    }
+    Ok((ladder, replicas))
+}
+
+fn move_slots(config: &RunConfig) -> usize {
+    let counts = &config.move_counts;
+    counts.generator_flips + counts.row_ops + counts.graph_rewires + counts.worm_moves
+}
+
+fn validate_position(
+    config: &RunConfig,
+    sweep: usize,
+    position: SweepPosition,
+    replicas: usize,
+) -> Result<(), AsmError> {
+    let slots = move_slots(config);
+    if sweep >= config.sweeps || position.replica >= replicas || position.move_slot >= slots {
+        return Err(AsmError::Serde(
+            ErrorInfo::new(
+                ErrorCode::InvalidSweepPosition,
+                format!(
+                    "position (sweep {sweep}, replica {}, slot {}) outside run of {} sweeps, {replicas} replicas, {slots} slots",
+                    position.replica, position.move_slot, config.sweeps
+                ),
+            )
+            .with_context("sweep", sweep.to_string()),
+        ));
+    }
+    Ok(())
+}
+
+/// Mutable bookkeeping carried across sweeps.
+struct SweepProgress {
+    recorder: MetricsRecorder,
+    checkpoints: Vec<PathBuf>,
+    exchange_totals: Vec<f64>,
+    exchange_counts: Vec<usize>,
+    output_layout: ResolvedOutput,
+    streams: SeedStreams,
+}
+
+impl SweepProgress {
+    fn new(config: &RunConfig, seed: u64, ladder_len: usize) -> Result<Self, AsmError> {
+        let streams = SeedStreams::new(seed, &config.seed_policy)?;
+        // Replica streams are reserved even though replicas draw per move.
+        for index in 0..ladder_len {
+            let _ = streams.replica(index);
+        }
+        Ok(Self {
+            recorder: MetricsRecorder::new(),
+            checkpoints: Vec::new(),
+            exchange_totals: vec![0.0; ladder_len.saturating_sub(1)],
+            exchange_counts: vec![0usize; ladder_len.saturating_sub(1)],
+            output_layout: resolve_output_paths(&config.output),
+            streams,
+        })
+    }
+
+    /// Restores the bookkeeping recorded by a mid-sweep checkpoint.
+    fn restore(&mut self, snapshot: &ProgressSnapshot) -> Result<(), AsmError> {
+        if snapshot.exchange_totals.len() != self.exchange_totals.len()
+            || snapshot.exchange_counts.len() != self.exchange_counts.len()
+        {
+            return Err(AsmError::Serde(
+                ErrorInfo::new(
+                    ErrorCode::CheckpointParse,
+                    "checkpoint exchange tallies do not match the temperature ladder",
+                )
+                .with_context("pairs", self.exchange_totals.len().to_string()),
+            ));
+        }
+        self.recorder = MetricsRecorder::from_snapshot(&snapshot.metrics);
+        self.exchange_totals = snapshot.exchange_totals.clone();
+        self.exchange_counts = snapshot.exchange_counts.clone();
+        self.checkpoints = match &self.output_layout.run_directory {
+            Some(run_dir) => snapshot
+                .checkpoints
+                .iter()
+                .map(|rel| run_dir.join(rel))
+                .collect(),
+            None => snapshot.checkpoints.clone(),
+        };
+        Ok(())
+    }
+
+    /// Captures the bookkeeping for a mid-sweep checkpoint retaining `checkpoints`.
+    fn snapshot(&self, checkpoints: &[PathBuf]) -> ProgressSnapshot {
+        let run_dir = self.output_layout.run_directory.as_deref();
+        ProgressSnapshot {
+            metrics: self.recorder.snapshot(),
+            exchange_totals: self.exchange_totals.clone(),
+            exchange_counts: self.exchange_counts.clone(),
+            checkpoints: checkpoints
+                .iter()
+                .map(|path| {
+                    run_dir
+                        .and_then(|dir| path.strip_prefix(dir).ok())
+                        .unwrap_or(path)
+                        .to_path_buf()
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Executes sweeps from `start` until `total_sweeps`, or until the move at `stop`.
+///
+/// When `start` carries a position, replicas before it are skipped in the
+/// first sweep and the positioned replica resumes at its move slot. When the
+/// `stop` position is reached the function returns before executing that
+/// move, leaving tempering and metrics for the sweep untouched. Every
+/// `checkpoint.move_interval` proposals a mid-sweep checkpoint is written
+/// for the next proposal within the sweep.
+fn advance_sweeps(
     config: &RunConfig,
     seed: u64,
-    ladder: Vec<f64>,
-    mut replicas: Vec<ReplicaState>,
-    start_sweep: usize,
+    replicas: &mut [ReplicaState],
+    progress: &mut SweepProgress,
+    start: (usize, Option<SweepPosition>),
     total_sweeps: usize,
-) -> Result<RunSummary, AsmError> {
-    let mut recorder = MetricsRecorder::new();
-    let mut checkpoints = Vec::new();
-    let output_layout = resolve_output_paths(&config.output);
-    let mut exchange_totals = vec![0.0; ladder.len().saturating_sub(1)];
-    let mut exchange_counts = vec![0usize; ladder.len().saturating_sub(1)];
-
+    stop: Option<(usize, SweepPosition)>,
+) -> Result<(), AsmError> {
+    let (start_sweep, start_position) = start;
+    let slots = move_slots(config);
     for sweep in start_sweep..total_sweeps {
-        for (replica_index, replica) in replicas.iter_mut().enumerate() {
-            perform_code_moves(config, seed, sweep, replica_index, replica)?;
-            perform_graph_moves(config, seed, sweep, replica_index, replica)?;
-            perform_worm_moves(config, seed, sweep, replica_index, replica, &mut recorder)?;
+        for replica_index in 0..replicas.len() {
+            let mut first_slot = 0;
+            if let Some(position) = start_position.filter(|_| sweep == start_sweep) {
+                if replica_index < position.replica {
+                    continue;
+                }
+                if replica_index == position.replica {
+                    first_slot = position.move_slot;
+                }
+            }
+            let halt_slot = stop
+                .filter(|(stop_sweep, position)| {
+                    *stop_sweep == sweep && position.replica == replica_index
+                })
+                .map(|(_, position)| position.move_slot);
+            for move_slot in first_slot..halt_slot.unwrap_or(slots) {
+                perform_move(
+                    config,
+                    &progress.streams,
+                    sweep,
+                    replica_index,
+                    move_slot,
+                    &mut replicas[replica_index],
+                    &mut progress.recorder,
+                )?;
+                if let Some(next) =
+                    mid_sweep_due(config, sweep, replica_index, move_slot, replicas.len())
+                {
+                    write_mid_sweep_checkpoint(config, seed, sweep, next, replicas, progress)?;
+                }
+            }
+            if halt_slot.is_some() {
+                return Ok(());
+            }
         }
 
         perform_tempering(
-            seed,
+            &progress.streams,
             sweep,
-            &mut replicas,
-            &mut exchange_totals,
-            &mut exchange_counts,
+            replicas,
+            &mut progress.exchange_totals,
+            &mut progress.exchange_counts,
         );
 
-        record_metrics(config, sweep, &mut recorder, &replicas)?;
+        record_metrics(config, sweep, &mut progress.recorder, replicas)?;
 
         if config.checkpoint.interval > 0
             && (sweep + 1) % config.checkpoint.interval == 0
             && config.output.run_directory.is_some()
         {
-            if let Some(path) = write_checkpoint(config, seed, sweep, &replicas, &output_layout)? {
-                checkpoints.push(path);
-                enforce_checkpoint_retention(&mut checkpoints, config.checkpoint.max_to_keep)?;
+            if let Some(path) =
+                write_checkpoint(config, seed, sweep, replicas, &progress.output_layout)?
+            {
+                progress.checkpoints.push(path);
+                enforce_checkpoint_retention(
+                    &mut progress.checkpoints,
+                    config.checkpoint.max_to_keep,
+                )?;
             }
         }
     }
+    Ok(())
+}
 
+fn run_with_replicas(
+    config: &RunConfig,
+    seed: u64,
+    ladder: Vec<f64>,
+    mut replicas: Vec<ReplicaState>,
+    start: (usize, Option<SweepPosition>),
+    restored: Option<&ProgressSnapshot>,
+    total_sweeps: usize,
+) -> Result<RunSummary, AsmError> {
+    let mut progress = SweepProgress::new(config, seed, ladder.len())?;
+    if let Some(snapshot) = restored {
+        progress.restore(snapshot)?;
+    }
+    // Checkpoints, metrics and the end state are rewritten below; mark the
+    // directory as mid-update until the manifest commits.
+    let manifest_path = progress
+        .output_layout
+        .run_directory
+        .as_ref()
+        .map(|run_dir| {
+            run_dir.join(
+                progress
+                    .output_layout
+                    .manifest_file
+                    .clone()
+                    .unwrap_or_default(),
+            )
+        });
+    if let Some(manifest_path) = &manifest_path {
+        RunManifest::begin_update(manifest_path)?;
+    }
+    advance_sweeps(
+        config,
+        seed,
+        &mut replicas,
+        &mut progress,
+        start,
+        total_sweeps,
+        None,
+    )?;
+    let SweepProgress {
+        recorder,
+        checkpoints,
+        exchange_totals,
+        exchange_counts,
+        output_layout,
+        streams,
+    } = progress;
     let cold = &replicas[0];
     let final_code_hash = cold.code.canonical_hash();
     let final_graph_hash = graph_hash(&cold.graph)?;
@@ -240,7 +539,7 @@ fn run_with_replicas(
         let path = run_dir.join(metrics_rel);
         recorder.write_csv(&path).map_err(|err| {
             AsmError::Serde(
-                asm_core::errors::ErrorInfo::new("metrics-write", err.to_string())
+                asm_core::errors::ErrorInfo::new(ErrorCode::MetricsWrite, err.to_string())
                     .with_context("path", path.display().to_string()),
             )
         })?;
@@ -249,15 +548,28 @@ fn run_with_replicas(
         None
     };
 
+    let mut end_state_pruning = None;
     if let (Some(run_dir), Some(end_state_dir)) = (
         output_layout.run_directory.clone(),
         output_layout.end_state_dir.clone(),
     ) {
-        write_end_state(&cold.code, &cold.graph, &run_dir.join(end_state_dir))?;
+        let dir = run_dir.join(end_state_dir);
+        if config.output.prune_end_state {
+            let mut pruned = cold.graph.clone();
+            let removed = pruned.prune_isolated();
+            write_end_state(&cold.code, &pruned, &dir)?;
+            end_state_pruning = Some(EndStatePruning {
+                pruned_nodes: removed.len(),
+                graph_hash: graph_hash(&pruned)?,
+            });
+        } else {
+            write_end_state(&cold.code, &cold.graph, &dir)?;
+        }
     }
 
-    let manifest_path = if let Some(run_dir) = output_layout.run_directory.clone() {
-        let manifest_path = run_dir.join(output_layout.manifest_file.clone().unwrap_or_default());
+    if let (Some(run_dir), Some(manifest_path)) =
+        (output_layout.run_directory.clone(), &manifest_path)
+    {
         let manifest = RunManifest {
             config: config.clone(),
             master_seed: seed,
@@ -276,12 +588,13 @@ fn run_with_replicas(
                         .map(|rel| rel.to_path_buf())
                 })
                 .collect(),
+            generation: 0,
+            seed_audit: streams.audit_summary(),
+            end_state_pruning,
+            build: Some(build_fingerprint()),
         };
-        manifest.write(&manifest_path)?;
-        Some(manifest_path)
-    } else {
-        None
-    };
+        manifest.write(manifest_path)?;
+    }
 
     let coverage = recorder.coverage();
     let effective_sample_size = if recorder.samples().is_empty() {
@@ -289,6 +602,7 @@ fn run_with_replicas(
     } else {
         recorder.samples().len() as f64 / (1.0 + coverage.average_jaccard)
     };
+    let ess = recorder.effective_sample_sizes();
 
     let exchange_acceptance: Vec<f64> = exchange_totals
         .iter()
@@ -308,6 +622,8 @@ fn run_with_replicas(
         exchange_acceptance,
         coverage,
         effective_sample_size,
+        ess: ess.ess,
+        ess_short_chain: ess.short_chain,
         final_code_hash,
         final_graph_hash,
         metrics_path,
@@ -317,53 +633,50 @@ fn run_with_replicas(
     })
 }
 
-fn perform_code_moves(
+fn perform_move(
     config: &RunConfig,
-    seed: u64,
+    streams: &SeedStreams,
     sweep: usize,
     replica_index: usize,
+    move_slot: usize,
     replica: &mut ReplicaState,
+    recorder: &mut MetricsRecorder,
 ) -> Result<(), AsmError> {
     let counts = &config.move_counts;
-    for trial in 0..counts.generator_flips {
-        let mut move_rng =
-            RngHandle::from_seed(determinism::move_seed(seed, replica_index, sweep, trial));
-        match moves_code::propose_generator_flip(&replica.code, &mut move_rng) {
-            Ok(proposal) => {
-                apply_code_proposal(
-                    replica,
-                    proposal,
-                    MoveKind::GeneratorFlip,
-                    &config.scoring,
-                    &mut move_rng,
-                )?;
-            }
-            Err(_) => replica.record(MoveKind::GeneratorFlip, false),
-        }
+    let code_end = counts.generator_flips + counts.row_ops;
+    let graph_end = code_end + counts.graph_rewires;
+    let mut move_rng = RngHandle::from_seed(streams.proposal(replica_index, sweep, move_slot));
+    if move_slot < counts.generator_flips {
+        perform_code_move(config, MoveKind::GeneratorFlip, replica, &mut move_rng)
+    } else if move_slot < code_end {
+        perform_code_move(config, MoveKind::RowOperation, replica, &mut move_rng)
+    } else if move_slot < graph_end {
+        perform_graph_move(config, move_slot - code_end, replica, &mut move_rng)
+    } else {
+        perform_worm_move(replica, recorder, &mut move_rng);
+        Ok(())
     }
-    for trial in 0..counts.row_ops {
-        let mut move_rng = RngHandle::from_seed(determinism::move_seed(
-            seed,
-            replica_index,
-            sweep,
-            counts.generator_flips + trial,
-        ));
-        match moves_code::propose_row_operation(&replica.code, &mut move_rng) {
-            Ok(proposal) => {
-                apply_code_proposal(
-                    replica,
-                    proposal,
-                    MoveKind::RowOperation,
-                    &config.scoring,
-                    &mut move_rng,
-                )?;
-            }
-            Err(_) => replica.record(MoveKind::RowOperation, false),
+}
+
+fn perform_code_move(
+    config: &RunConfig,
+    kind: MoveKind,
+    replica: &mut ReplicaState,
+    move_rng: &mut RngHandle,
+) -> Result<(), AsmError> {
+    let result = if kind == MoveKind::GeneratorFlip {
+        moves_code::propose_generator_flip(&replica.code, move_rng)
+    } else {
+        moves_code::propose_row_operation(&replica.code, move_rng)
+    };
+    match result {
+        Ok(proposal) => apply_code_proposal(replica, proposal, kind, &config.scoring, move_rng),
+        Err(_) => {
+            replica.record(kind, false);
+            Ok(())
         }
     }
-    Ok(())
 }
-
 fn apply_code_proposal(
     replica: &mut ReplicaState,
     proposal: moves_code::CodeMoveProposal,
@@ -371,7 +684,20 @@ fn apply_code_proposal(
     weights: &ScoringWeights,
     rng: &mut RngHandle,
 ) -> Result<(), AsmError> {
-    let candidate_energy = energy::score(&proposal.candidate, &replica.graph, weights)?;
+    let (candidate_energy, candidate_proxies) = match &replica.proxies {
+        Some(state) => {
+            let change = LocalChange::Code {
+                candidate: &proposal.candidate,
+                change: &proposal.change,
+            };
+            let (energy, state) = energy::rescore_delta(state, change)?;
+            (energy, Some(state))
+        }
+        None => (
+            energy::score(&proposal.candidate, &replica.graph, weights)?,
+            None,
+        ),
+    };
     let delta = candidate_energy.total - replica.energy.total;
     let acceptance = (-delta / replica.temperature.max(1e-9)).exp().min(1.0);
     let draw = rng.next_u64() as f64 / u64::MAX as f64;
@@ -380,49 +706,37 @@ fn apply_code_proposal(
     if accepted {
         replica.code = proposal.candidate;
         replica.energy = candidate_energy;
+        replica.proxies = candidate_proxies;
     }
     Ok(())
 }
 
-fn perform_graph_moves(
+fn perform_graph_move(
     config: &RunConfig,
-    seed: u64,
-    sweep: usize,
-    replica_index: usize,
+    trial: usize,
     replica: &mut ReplicaState,
+    move_rng: &mut RngHandle,
 ) -> Result<(), AsmError> {
-    let counts = &config.move_counts;
-    for trial in 0..counts.graph_rewires {
-        let move_slot = counts.generator_flips + counts.row_ops + trial;
-        let mut move_rng = RngHandle::from_seed(determinism::move_seed(
-            seed,
-            replica_index,
-            sweep,
-            move_slot,
-        ));
-        let kind = match trial % 3 {
-            0 => MoveKind::GraphSwapTargets,
-            1 => MoveKind::GraphRetarget,
-            _ => MoveKind::GraphResourceBalance,
-        };
-        let result = match kind {
-            MoveKind::GraphSwapTargets => {
-                moves_graph::propose_swap_targets(&replica.graph, &mut move_rng)
-            }
-            MoveKind::GraphRetarget => moves_graph::propose_retarget(&replica.graph, &mut move_rng),
-            MoveKind::GraphResourceBalance => {
-                moves_graph::propose_resource_balanced(&replica.graph, &mut move_rng)
-            }
-            _ => unreachable!(),
-        };
-        match result {
-            Ok(proposal) => {
-                apply_graph_proposal(replica, proposal, kind, &config.scoring, &mut move_rng)?;
-            }
-            Err(_) => replica.record(kind, false),
+    let kind = match trial % 3 {
+        0 => MoveKind::GraphSwapTargets,
+        1 => MoveKind::GraphRetarget,
+        _ => MoveKind::GraphResourceBalance,
+    };
+    let result = match kind {
+        MoveKind::GraphSwapTargets => moves_graph::propose_swap_targets(&replica.graph, move_rng),
+        MoveKind::GraphRetarget => moves_graph::propose_retarget(&replica.graph, move_rng),
+        MoveKind::GraphResourceBalance => {
+            moves_graph::propose_resource_balanced(&replica.graph, move_rng)
+        }
+        _ => unreachable!(),
+    };
+    match result {
+        Ok(proposal) => apply_graph_proposal(replica, proposal, kind, &config.scoring, move_rng),
+        Err(_) => {
+            replica.record(kind, false);
+            Ok(())
         }
     }
-    Ok(())
 }
 
 fn apply_graph_proposal(
@@ -432,7 +746,20 @@ fn apply_graph_proposal(
     weights: &ScoringWeights,
     rng: &mut RngHandle,
 ) -> Result<(), AsmError> {
-    let candidate_energy = energy::score(&replica.code, &proposal.candidate, weights)?;
+    let (candidate_energy, candidate_proxies) = match &replica.proxies {
+        Some(state) => {
+            let change = LocalChange::Graph {
+                candidate: &proposal.candidate,
+                change: &proposal.change,
+            };
+            let (energy, state) = energy::rescore_delta(state, change)?;
+            (energy, Some(state))
+        }
+        None => (
+            energy::score(&replica.code, &proposal.candidate, weights)?,
+            None,
+        ),
+    };
     let delta = candidate_energy.total - replica.energy.total;
     let acceptance = (-delta / replica.temperature.max(1e-9)).exp().min(1.0);
     let draw = rng.next_u64() as f64 / u64::MAX as f64;
@@ -441,42 +768,27 @@ fn apply_graph_proposal(
     if accepted {
         replica.graph = proposal.candidate;
         replica.energy = candidate_energy;
+        replica.proxies = candidate_proxies;
     }
     Ok(())
 }
 
-fn perform_worm_moves(
-    config: &RunConfig,
-    seed: u64,
-    sweep: usize,
-    replica_index: usize,
+fn perform_worm_move(
     replica: &mut ReplicaState,
     recorder: &mut MetricsRecorder,
-) -> Result<(), AsmError> {
-    for trial in 0..config.move_counts.worm_moves {
-        let move_slot = config.move_counts.generator_flips
-            + config.move_counts.row_ops
-            + config.move_counts.graph_rewires
-            + trial;
-        let mut move_rng = RngHandle::from_seed(determinism::move_seed(
-            seed,
-            replica_index,
-            sweep,
-            move_slot,
-        ));
-        match moves_worm::propose_worm(&replica.code, &replica.graph, &mut move_rng) {
-            Ok(worm) => {
-                recorder.note_worm_sample(worm.sample_hash);
-                replica.record(MoveKind::WormSample, true);
-            }
-            Err(_) => replica.record(MoveKind::WormSample, false),
+    move_rng: &mut RngHandle,
+) {
+    match moves_worm::propose_worm(&replica.code, &replica.graph, move_rng) {
+        Ok(worm) => {
+            recorder.note_worm_sample(worm.sample_hash);
+            replica.record(MoveKind::WormSample, true);
         }
+        Err(_) => replica.record(MoveKind::WormSample, false),
     }
-    Ok(())
 }
 
 fn perform_tempering(
-    seed: u64,
+    streams: &SeedStreams,
     sweep: usize,
     replicas: &mut [ReplicaState],
     totals: &mut [f64],
@@ -486,7 +798,7 @@ fn perform_tempering(
         return;
     }
     for pair in 0..replicas.len() - 1 {
-        let mut rng = RngHandle::from_seed(determinism::exchange_seed(seed, sweep, pair));
+        let mut rng = RngHandle::from_seed(streams.exchange(sweep, pair));
         let (accept, prob) = tempering::attempt_exchange(
             replicas[pair].energy.total,
             replicas[pair].temperature,
@@ -581,6 +893,97 @@ fn write_checkpoint(
     Ok(Some(path))
 }
 
+/// Returns the position after `move_slot` when a mid-sweep checkpoint is due.
+///
+/// No checkpoint is taken after the last proposal of a sweep; the
+/// sweep-boundary checkpoint covers that point once tempering has run.
+fn mid_sweep_due(
+    config: &RunConfig,
+    sweep: usize,
+    replica_index: usize,
+    move_slot: usize,
+    replica_count: usize,
+) -> Option<SweepPosition> {
+    let interval = config.checkpoint.move_interval;
+    if interval == 0 || config.output.run_directory.is_none() {
+        return None;
+    }
+    let slots = move_slots(config);
+    let executed = (sweep * replica_count + replica_index) * slots + move_slot + 1;
+    if executed % interval != 0 {
+        return None;
+    }
+    if move_slot + 1 < slots {
+        Some(SweepPosition {
+            replica: replica_index,
+            move_slot: move_slot + 1,
+        })
+    } else if replica_index + 1 < replica_count {
+        Some(SweepPosition {
+            replica: replica_index + 1,
+            move_slot: 0,
+        })
+    } else {
+        None
+    }
+}
+
+fn mid_sweep_payload(
+    config: &RunConfig,
+    seed: u64,
+    sweep: usize,
+    position: SweepPosition,
+    replicas: &[ReplicaState],
+    progress: &SweepProgress,
+    checkpoints: &[PathBuf],
+) -> Result<CheckpointPayload, AsmError> {
+    let replica_refs: Vec<_> = replicas
+        .iter()
+        .map(|replica| {
+            (
+                replica.temperature,
+                &replica.code,
+                &replica.graph,
+                &replica.energy,
+            )
+        })
+        .collect();
+    let mut payload = checkpoint::build_payload(sweep, config, seed, &replica_refs)?;
+    payload.position = Some(position);
+    payload.progress = Some(progress.snapshot(checkpoints));
+    for (stored, replica) in payload.replicas.iter_mut().zip(replicas) {
+        stored.accepted = replica.accepted.clone();
+        stored.proposed = replica.proposed.clone();
+    }
+    Ok(payload)
+}
+
+fn write_mid_sweep_checkpoint(
+    config: &RunConfig,
+    seed: u64,
+    sweep: usize,
+    position: SweepPosition,
+    replicas: &[ReplicaState],
+    progress: &mut SweepProgress,
+) -> Result<(), AsmError> {
+    let layout = &progress.output_layout;
+    let run_dir = match &layout.run_directory {
+        Some(dir) => dir,
+        None => return Ok(()),
+    };
+    let checkpoint_dir = run_dir.join(layout.checkpoint_dir.clone().unwrap_or_default());
+    let path = checkpoint::mid_sweep_checkpoint_path(&checkpoint_dir, sweep, position);
+    // The payload lists the checkpoints retained once this one is written.
+    let mut retained = progress.checkpoints.clone();
+    retained.push(path.clone());
+    let excess = retained.len().saturating_sub(config.checkpoint.max_to_keep);
+    retained.drain(..excess);
+    let payload = mid_sweep_payload(config, seed, sweep, position, replicas, progress, &retained)?;
+    payload.store(&path)?;
+    progress.checkpoints.push(path);
+    enforce_checkpoint_retention(&mut progress.checkpoints, config.checkpoint.max_to_keep)
+}
+
 fn enforce_checkpoint_retention(
     paths: &mut Vec<PathBuf>,
     max_to_keep: usize,
@@ -595,7 +998,7 @@ fn enforce_checkpoint_retention(
     for path in removed {
         std::fs::remove_file(&path).map_err(|err| {
             AsmError::Serde(
-                asm_core::errors::ErrorInfo::new("checkpoint-remove", err.to_string())
+                asm_core::errors::ErrorInfo::new(ErrorCode::CheckpointRemove, err.to_string())
                     .with_context("path", path.display().to_string()),
             )
         })?;
@@ -654,7 +1057,7 @@ fn resolve_output_paths(config: &OutputConfig) -> ResolvedOutput {
 fn write_end_state(code: &CSSCode, graph: &HypergraphImpl, dir: &Path) -> Result<(), AsmError> {
     std::fs::create_dir_all(dir).map_err(|err| {
         AsmError::Serde(
-            asm_core::errors::ErrorInfo::new("end-state-mkdir", err.to_string())
+            asm_core::errors::ErrorInfo::new(ErrorCode::EndStateMkdir, err.to_string())
                 .with_context("path", dir.display().to_string()),
         )
     })?;
@@ -662,15 +1065,15 @@ fn write_end_state(code: &CSSCode, graph: &HypergraphImpl, dir: &Path) -> Result
     let graph_json = graph_to_json(graph)?;
     let code_path = dir.join("code.json");
     let graph_path = dir.join("graph.json");
-    std::fs::write(&code_path, code_json).map_err(|err| {
+    write_atomic(&code_path, code_json).map_err(|err| {
         AsmError::Serde(
-            asm_core::errors::ErrorInfo::new("end-state-code-write", err.to_string())
+            asm_core::errors::ErrorInfo::new(ErrorCode::EndStateCodeWrite, err.to_string())
                 .with_context("path", code_path.display().to_string()),
         )
     })?;
-    std::fs::write(&graph_path, graph_json).map_err(|err| {
+    write_atomic(&graph_path, graph_json).map_err(|err| {
         AsmError::Serde(
-            asm_core::errors::ErrorInfo::new("end-state-graph-write", err.to_string())
+            asm_core::errors::ErrorInfo::new(ErrorCode::EndStateGraphWrite, err.to_string())
                 .with_context("path", graph_path.display().to_string()),
         )
     })?;
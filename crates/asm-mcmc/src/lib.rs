@@ -5,6 +5,8 @@
 
 /// Analysis helpers for inspecting run artefacts.
 pub mod analysis;
+/// Crash-consistent artefact writes.
+pub mod atomic;
 /// Checkpoint serialization helpers and payload structures.
 pub mod checkpoint;
 /// YAML configuration schema and defaults.
@@ -28,9 +30,15 @@ pub mod moves_worm;
 /// Parallel tempering ladder helpers.
 pub mod tempering;
 
+pub use checkpoint::{CheckpointPayload, ProgressSnapshot, SweepPosition};
 pub use config::{
     CheckpointConfig, LadderConfig, MoveCounts, RunConfig, ScoringWeights, SeedPolicy,
+    StreamDerivation,
+};
+pub use determinism::SeedStreams;
+pub use energy::{rescore_delta, score, EnergyBreakdown, LocalChange, ProxyState};
+pub use kernel::{resume, resume_payload, run, run_until, ProposalOutcome, RunSummary};
+pub use metrics::{
+    integrated_autocorrelation_time, CoverageMetrics, EffectiveSampleSizes, MetricSample,
+    RecorderSnapshot,
 };
-pub use energy::{score, EnergyBreakdown};
-pub use kernel::{resume, run, ProposalOutcome, RunSummary};
-pub use metrics::{CoverageMetrics, MetricSample};
@@ -1,10 +1,13 @@
+use std::ffi::OsString;
 use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
 
-use asm_core::errors::ErrorInfo;
-use asm_core::AsmError;
+use asm_core::errors::{ErrorCode, ErrorInfo};
+use asm_core::{AsmError, BuildFingerprint, SeedAuditSummary};
 use serde::{Deserialize, Serialize};
 
+use crate::atomic::write_atomic;
 use crate::config::RunConfig;
 
 /// Structured manifest describing a completed or running ensemble sweep.
@@ -24,44 +27,159 @@ pub struct RunManifest {
     pub metrics_file: Option<PathBuf>,
     /// Checkpoint files generated during the run (relative order preserved).
     pub checkpoints: Vec<PathBuf>,
+    /// Even generation counter committed alongside this manifest.
+    #[serde(default)]
+    pub generation: u64,
+    /// Seed derivations recorded when `seed_policy.audit` is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed_audit: Option<SeedAuditSummary>,
+    /// Pruning applied to the exported end state when
+    /// `output.prune_end_state` is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_state_pruning: Option<EndStatePruning>,
+    /// Build that produced the run; absent in manifests written before it
+    /// was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build: Option<BuildFingerprint>,
+}
+
+/// Note recording how the exported end-state graph differs from the live state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EndStatePruning {
+    /// Number of isolated nodes removed before export.
+    pub pruned_nodes: usize,
+    /// Canonical hash of the exported graph. `graph_hash` stays the hash of
+    /// the unpruned live state so checkpoints and resumes keep matching it.
+    pub graph_hash: String,
+}
+
+/// Returns the generation counter file `<manifest>.generation` next to a manifest.
+pub fn generation_path(manifest_path: &Path) -> PathBuf {
+    let mut name = manifest_path
+        .file_name()
+        .map(OsString::from)
+        .unwrap_or_default();
+    name.push(".generation");
+    manifest_path.with_file_name(name)
+}
+
+/// Reads the generation counter of a run directory; a missing counter is zero.
+///
+/// An odd generation marks a directory whose artefacts are being rewritten.
+pub fn read_generation(manifest_path: &Path) -> Result<u64, AsmError> {
+    let path = generation_path(manifest_path);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => return Err(generation_error(&path, err)),
+    };
+    contents
+        .trim()
+        .parse()
+        .map_err(|err| generation_error(&path, err))
+}
+
+fn write_generation(manifest_path: &Path, generation: u64) -> Result<(), AsmError> {
+    let path = generation_path(manifest_path);
+    write_atomic(&path, format!("{generation}\n")).map_err(|err| generation_error(&path, err))
+}
+
+fn generation_error(path: &Path, err: impl ToString) -> AsmError {
+    AsmError::Serde(
+        ErrorInfo::new(ErrorCode::ManifestGeneration, err.to_string())
+            .with_context("path", path.display().to_string()),
+    )
 }
 
 impl RunManifest {
-    /// Writes the manifest to a JSON file.
-    pub fn write(&self, path: &Path) -> Result<(), AsmError> {
+    /// Marks the run directory as mid-update by advancing its generation
+    /// counter to an odd value, returning the new generation.
+    ///
+    /// Call before rewriting artefacts; [`RunManifest::write`] commits the
+    /// update.
+    pub fn begin_update(path: &Path) -> Result<u64, AsmError> {
+        let current = read_generation(path)?;
+        if current % 2 == 1 {
+            return Ok(current);
+        }
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent).map_err(|err| {
                 AsmError::Serde(
-                    ErrorInfo::new("manifest-mkdir", err.to_string())
+                    ErrorInfo::new(ErrorCode::ManifestMkdir, err.to_string())
                         .with_context("path", parent.display().to_string()),
                 )
             })?;
         }
-        let json = serde_json::to_string_pretty(self).map_err(|err| {
+        write_generation(path, current + 1)?;
+        Ok(current + 1)
+    }
+
+    /// Atomically writes the manifest to a JSON file and commits the next
+    /// even generation, returning it.
+    ///
+    /// The counter passes through an odd value while the manifest is replaced,
+    /// so a crash leaves a directory that [`RunManifest::load_consistent`]
+    /// rejects.
+    pub fn write(&self, path: &Path) -> Result<u64, AsmError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| {
+                AsmError::Serde(
+                    ErrorInfo::new(ErrorCode::ManifestMkdir, err.to_string())
+                        .with_context("path", parent.display().to_string()),
+                )
+            })?;
+        }
+        let generation = Self::begin_update(path)? + 1;
+        let manifest = Self {
+            generation,
+            ..self.clone()
+        };
+        let json = serde_json::to_string_pretty(&manifest).map_err(|err| {
             AsmError::Serde(
-                ErrorInfo::new("manifest-serialize", err.to_string())
+                ErrorInfo::new(ErrorCode::ManifestSerialize, err.to_string())
                     .with_context("path", path.display().to_string()),
             )
         })?;
-        fs::write(path, json).map_err(|err| {
+        write_atomic(path, json).map_err(|err| {
             AsmError::Serde(
-                ErrorInfo::new("manifest-write", err.to_string())
+                ErrorInfo::new(ErrorCode::ManifestWrite, err.to_string())
                     .with_context("path", path.display().to_string()),
             )
-        })
+        })?;
+        write_generation(path, generation)?;
+        Ok(generation)
+    }
+
+    /// Loads a manifest, rejecting directories that are mid-update or whose
+    /// manifest does not match the committed generation.
+    pub fn load_consistent(path: &Path) -> Result<Self, AsmError> {
+        let generation = read_generation(path)?;
+        let manifest = Self::load(path)?;
+        if generation % 2 == 1 || manifest.generation != generation {
+            return Err(AsmError::Serde(
+                ErrorInfo::new(
+                    ErrorCode::ManifestIncomplete,
+                    "run directory was not fully updated",
+                )
+                .with_context("path", path.display().to_string())
+                .with_context("generation", generation.to_string())
+                .with_context("manifest_generation", manifest.generation.to_string()),
+            ));
+        }
+        Ok(manifest)
     }
 
     /// Loads a manifest from disk.
     pub fn load(path: &Path) -> Result<Self, AsmError> {
         let contents = fs::read_to_string(path).map_err(|err| {
             AsmError::Serde(
-                ErrorInfo::new("manifest-read", err.to_string())
+                ErrorInfo::new(ErrorCode::ManifestRead, err.to_string())
                     .with_context("path", path.display().to_string()),
             )
         })?;
         serde_json::from_str(&contents).map_err(|err| {
             AsmError::Serde(
-                ErrorInfo::new("manifest-parse", err.to_string())
+                ErrorInfo::new(ErrorCode::ManifestParse, err.to_string())
                     .with_context("path", path.display().to_string()),
             )
         })
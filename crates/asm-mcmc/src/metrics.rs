@@ -1,11 +1,11 @@
-use std::collections::BTreeSet;
-use std::fs::File;
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::Write;
 use std::path::Path;
 
 use indexmap::IndexSet;
 use serde::{Deserialize, Serialize};
 
+use crate::atomic::write_atomic_with;
 use crate::energy::EnergyBreakdown;
 
 /// Per-sweep metrics stored for CSV export.
@@ -57,6 +57,72 @@ impl CoverageMetrics {
     }
 }
 
+/// Minimum number of cold-replica samples for a trusted autocorrelation estimate.
+pub const MIN_ESS_SAMPLES: usize = 50;
+
+/// Energy observables for which effective sample sizes are reported.
+pub const ESS_OBSERVABLES: [&str; 4] = ["cmdl", "spec", "curv", "total"];
+
+/// Per-observable effective sample sizes of the cold replica.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EffectiveSampleSizes {
+    /// Effective sample size keyed by observable name.
+    pub ess: BTreeMap<String, f64>,
+    /// Set when the chain was too short for an autocorrelation estimate and
+    /// every observable reports a single effective sample.
+    pub short_chain: bool,
+}
+
+/// Estimates the integrated autocorrelation time of `series` using Geyer's
+/// initial positive sequence estimator.
+///
+/// The result is clamped to `[1, n]`; a constant series reports `n` so that
+/// its effective sample size is a single sample.
+pub fn integrated_autocorrelation_time(series: &[f64]) -> f64 {
+    let n = series.len();
+    if n < 2 {
+        return 1.0;
+    }
+    let mean = series.iter().sum::<f64>() / n as f64;
+    let autocovariance = |lag: usize| -> f64 {
+        series[..n - lag]
+            .iter()
+            .zip(&series[lag..])
+            .map(|(a, b)| (a - mean) * (b - mean))
+            .sum::<f64>()
+            / n as f64
+    };
+    let variance = autocovariance(0);
+    if !variance.is_finite() || variance <= 0.0 {
+        return n as f64;
+    }
+
+    // Sum consecutive autocovariance pairs until the first non-positive pair.
+    let mut pair_sum = 0.0;
+    let mut lag = 0;
+    while lag + 1 < n {
+        let pair = autocovariance(lag) + autocovariance(lag + 1);
+        if pair <= 0.0 {
+            break;
+        }
+        pair_sum += pair;
+        lag += 2;
+    }
+    let tau = (2.0 * pair_sum - variance) / variance;
+    tau.clamp(1.0, n as f64)
+}
+
+/// Serializable state of a [`MetricsRecorder`], stored in mid-sweep checkpoints.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RecorderSnapshot {
+    /// Samples recorded so far, in sweep order.
+    pub samples: Vec<MetricSample>,
+    /// Generator support set recorded alongside each sample.
+    pub generator_history: Vec<BTreeSet<usize>>,
+    /// Distinct worm sample hashes in first-seen order.
+    pub worm_hashes: Vec<String>,
+}
+
 /// Collects per-sweep metrics and computes aggregate coverage proxies.
 #[derive(Debug, Default)]
 pub struct MetricsRecorder {
@@ -72,6 +138,27 @@ impl MetricsRecorder {
         Self::default()
     }
 
+    /// Rebuilds a recorder from a snapshot taken by [`MetricsRecorder::snapshot`].
+    pub fn from_snapshot(snapshot: &RecorderSnapshot) -> Self {
+        let mut recorder = Self::new();
+        for (sample, support) in snapshot.samples.iter().zip(&snapshot.generator_history) {
+            recorder.push_sample(sample.clone(), support.clone());
+        }
+        for hash in &snapshot.worm_hashes {
+            recorder.note_worm_sample(hash.clone());
+        }
+        recorder
+    }
+
+    /// Captures the recorded state so a resumed run can continue from it.
+    pub fn snapshot(&self) -> RecorderSnapshot {
+        RecorderSnapshot {
+            samples: self.samples.clone(),
+            generator_history: self.generator_history.clone(),
+            worm_hashes: self.worm_hashes.iter().cloned().collect(),
+        }
+    }
+
     /// Records a metrics sample together with the generator support set used for coverage.
     pub fn push_sample(&mut self, sample: MetricSample, generator_support: BTreeSet<usize>) {
         self.unique_hashes
@@ -135,16 +222,54 @@ impl MetricsRecorder {
         }
     }
 
-    /// Writes the recorded metrics to a CSV file.
+    /// Computes the effective sample size of each energy observable over the
+    /// cold replica's samples.
+    pub fn effective_sample_sizes(&self) -> EffectiveSampleSizes {
+        let cold: Vec<&EnergyBreakdown> = self
+            .samples
+            .iter()
+            .filter(|sample| sample.replica == 0)
+            .map(|sample| &sample.energy)
+            .collect();
+        let n = cold.len();
+        let short_chain = n < MIN_ESS_SAMPLES;
+        let ess = ESS_OBSERVABLES
+            .iter()
+            .map(|&name| {
+                let series: Vec<f64> = cold
+                    .iter()
+                    .map(|energy| match name {
+                        "cmdl" => energy.cmdl,
+                        "spec" => energy.spec,
+                        "curv" => energy.curv,
+                        _ => energy.total,
+                    })
+                    .collect();
+                let value = if short_chain {
+                    n.min(1) as f64
+                } else {
+                    n as f64 / integrated_autocorrelation_time(&series)
+                };
+                (name.to_string(), value)
+            })
+            .collect();
+        EffectiveSampleSizes { ess, short_chain }
+    }
+
+    /// Atomically writes the recorded metrics to a CSV file.
     pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
-        let mut file = File::create(path)?;
+        write_atomic_with(path.as_ref(), |writer| self.write_csv_to(writer))
+    }
+
+    /// Streams the recorded metrics as CSV into `writer`.
+    pub fn write_csv_to<W: Write + ?Sized>(&self, writer: &mut W) -> std::io::Result<()> {
         writeln!(
-            file,
+            writer,
             "sweep,replica,temperature,energy,cmdl,spec,curv,accepted,proposed,code_hash,graph_hash"
         )?;
         for sample in &self.samples {
             writeln!(
-                file,
+                writer,
                 "{},{},{},{:.6},{:.6},{:.6},{:.6},{},{},{},{}",
                 sample.sweep,
                 sample.replica,
@@ -1,10 +1,40 @@
 use std::collections::BTreeSet;
 
 use asm_code::css::CSSCode;
-use asm_core::errors::ErrorInfo;
+use asm_core::errors::{ErrorCode, ErrorInfo};
 use asm_core::{AsmError, RngHandle};
 use rand::RngCore;
 
+/// Stabiliser family a CSS generator belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneratorFamily {
+    /// X-type stabilisers.
+    X,
+    /// Z-type stabilisers.
+    Z,
+}
+
+/// Support of one generator before and after a move.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratorChange {
+    /// Family of the modified generator.
+    pub family: GeneratorFamily,
+    /// Sorted support prior to the move.
+    pub before: Vec<usize>,
+    /// Sorted support after the move.
+    pub after: Vec<usize>,
+}
+
+/// Local change applied to the code by a proposal.
+///
+/// Generators are identified by support rather than index because
+/// [`CSSCode::new`] re-sorts the constraint lists.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodeChange {
+    /// Generators whose support changed.
+    pub generators: Vec<GeneratorChange>,
+}
+
 /// Result of a CSS code proposal.
 #[derive(Debug)]
 pub struct CodeMoveProposal {
@@ -16,6 +46,8 @@ pub struct CodeMoveProposal {
     pub reverse_prob: f64,
     /// Indices of generators touched by the move.
     pub touched_generators: Vec<usize>,
+    /// Generators modified by the move, for incremental rescoring.
+    pub change: CodeChange,
     /// Human readable description of the move.
     pub description: String,
 }
@@ -38,7 +70,7 @@ pub fn propose_generator_flip(
 
     if x_checks.is_empty() && z_checks.is_empty() {
         return Err(AsmError::Code(ErrorInfo::new(
-            "no-generators",
+            ErrorCode::NoGenerators,
             "cannot flip generator in empty code",
         )));
     }
@@ -51,11 +83,16 @@ pub fn propose_generator_flip(
         (rng.next_u64() as usize) % num_variables
     };
 
-    let (target_vec, description_prefix) = if choice < x_checks.len() {
-        (&mut x_checks[choice], "x")
+    let (target_vec, description_prefix, family) = if choice < x_checks.len() {
+        (&mut x_checks[choice], "x", GeneratorFamily::X)
     } else {
-        (&mut z_checks[choice - x_checks.len()], "z")
+        (
+            &mut z_checks[choice - x_checks.len()],
+            "z",
+            GeneratorFamily::Z,
+        )
     };
+    let before = target_vec.clone();
 
     if let Some(pos) = target_vec.iter().position(|&var| var == var_choice) {
         target_vec.remove(pos);
@@ -64,6 +101,7 @@ pub fn propose_generator_flip(
         target_vec.sort_unstable();
         target_vec.dedup();
     }
+    let after = target_vec.clone();
 
     let candidate = CSSCode::new(
         num_variables,
@@ -78,6 +116,13 @@ pub fn propose_generator_flip(
         forward_prob: 1.0 / total.max(1) as f64,
         reverse_prob: 1.0 / total.max(1) as f64,
         touched_generators: vec![choice],
+        change: CodeChange {
+            generators: vec![GeneratorChange {
+                family,
+                before,
+                after,
+            }],
+        },
         description: format!("generator-flip:{description_prefix}{choice}:var{var_choice}"),
     })
 }
@@ -106,7 +151,7 @@ pub fn propose_row_operation(
         (rng.next_u64() & 1) == 0
     };
 
-    let (family_len, idx_a, idx_b) = {
+    let (family_len, idx_a, idx_b, before, after) = {
         let family = if choose_x_family {
             &mut x_checks
         } else {
@@ -115,7 +160,7 @@ pub fn propose_row_operation(
 
         if family.len() < 2 {
             return Err(AsmError::Code(ErrorInfo::new(
-                "insufficient-generators",
+                ErrorCode::InsufficientGenerators,
                 "not enough generators for row op",
             )));
         }
@@ -133,8 +178,8 @@ pub fn propose_row_operation(
                 set.remove(var);
             }
         }
-        family[idx_a] = set.iter().copied().collect();
-        (family_len, idx_a, idx_b)
+        let before = std::mem::replace(&mut family[idx_a], set.iter().copied().collect());
+        (family_len, idx_a, idx_b, before, family[idx_a].clone())
     };
 
     let candidate = CSSCode::new(
@@ -151,6 +196,17 @@ pub fn propose_row_operation(
         forward_prob: 1.0 / family_len.max(1) as f64,
         reverse_prob: 1.0 / family_len.max(1) as f64,
         touched_generators: vec![idx_a],
+        change: CodeChange {
+            generators: vec![GeneratorChange {
+                family: if choose_x_family {
+                    GeneratorFamily::X
+                } else {
+                    GeneratorFamily::Z
+                },
+                before,
+                after,
+            }],
+        },
         description: format!("row-op:{family_label}{idx_a}^{family_label}{idx_b}"),
     })
 }
@@ -1,10 +1,18 @@
-use asm_core::errors::ErrorInfo;
+use asm_core::errors::{ErrorCode, ErrorInfo};
 use asm_core::{AsmError, EdgeId, Hypergraph, NodeId, RngHandle};
 use asm_graph::{
     canonical_hash, rewire_resource_balanced, rewire_retarget, rewire_swap_targets, HypergraphImpl,
 };
 use rand::RngCore;
 
+/// Local change applied to the graph by a proposal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphChange {
+    /// Edges whose endpoints differ between the current and candidate graph.
+    /// Moves never add or remove edges, so identifiers are stable.
+    pub edges: Vec<EdgeId>,
+}
+
 /// Result of a graph rewiring proposal.
 #[derive(Debug, Clone)]
 pub struct GraphMoveProposal {
@@ -20,6 +28,8 @@ pub struct GraphMoveProposal {
     pub touched_node: Option<NodeId>,
     /// Canonical hash of the candidate graph after the proposal.
     pub candidate_hash: String,
+    /// Edges rewired by the move, for incremental rescoring.
+    pub change: GraphChange,
     /// Human readable description of the move.
     pub description: String,
 }
@@ -32,7 +42,7 @@ pub fn propose_swap_targets(
     let edge_ids: Vec<EdgeId> = graph.edges().collect();
     if edge_ids.len() < 2 {
         return Err(AsmError::Graph(ErrorInfo::new(
-            "insufficient-edges",
+            ErrorCode::InsufficientEdges,
             "need at least two edges for swap",
         )));
     }
@@ -54,6 +64,9 @@ pub fn propose_swap_targets(
         reverse_prob: 2.0 / (edge_ids.len() * (edge_ids.len() - 1)) as f64,
         touched_edges: vec![edge_a, edge_b],
         touched_node: None,
+        change: GraphChange {
+            edges: vec![edge_a, edge_b],
+        },
         description: format!("swap-targets:e{}-e{}", edge_a.as_raw(), edge_b.as_raw()),
     })
 }
@@ -66,14 +79,14 @@ pub fn propose_retarget(
     let edge_ids: Vec<EdgeId> = graph.edges().collect();
     if edge_ids.is_empty() {
         return Err(AsmError::Graph(ErrorInfo::new(
-            "no-edges",
+            ErrorCode::NoEdges,
             "graph has no edges to retarget",
         )));
     }
     let nodes: Vec<NodeId> = graph.nodes().collect();
     if nodes.len() < 2 {
         return Err(AsmError::Graph(ErrorInfo::new(
-            "insufficient-nodes",
+            ErrorCode::InsufficientNodes,
             "need at least two nodes for retarget",
         )));
     }
@@ -82,8 +95,11 @@ pub fn propose_retarget(
     let destinations = graph.dst_of(edge)?;
     if destinations.is_empty() {
         return Err(AsmError::Graph(
-            ErrorInfo::new("empty-destinations", "edge has no destinations to retarget")
-                .with_context("edge", edge.as_raw().to_string()),
+            ErrorInfo::new(
+                ErrorCode::EmptyDestinations,
+                "edge has no destinations to retarget",
+            )
+            .with_context("edge", edge.as_raw().to_string()),
         ));
     }
     let remove_idx = (rng.next_u64() as usize) % destinations.len();
@@ -103,6 +119,7 @@ pub fn propose_retarget(
         reverse_prob: 1.0 / (edge_ids.len() * nodes.len()) as f64,
         touched_edges: vec![edge],
         touched_node: Some(added),
+        change: GraphChange { edges: vec![edge] },
         description: format!(
             "retarget:e{}:{}->{}",
             edge.as_raw(),
@@ -120,7 +137,7 @@ pub fn propose_resource_balanced(
     let nodes: Vec<NodeId> = graph.nodes().collect();
     if nodes.is_empty() {
         return Err(AsmError::Graph(ErrorInfo::new(
-            "no-nodes",
+            ErrorCode::NoNodes,
             "graph has no nodes to rebalance",
         )));
     }
@@ -133,6 +150,16 @@ pub fn propose_resource_balanced(
     } else {
         canonical_hash(&candidate)?
     };
+    // The balancing heuristic rewires one of the node's edges without
+    // reporting which, so compare them against the candidate.
+    let mut changed_edges = Vec::new();
+    if outcome.changed {
+        for edge in graph.edges_touching(node)? {
+            if graph.hyperedge(edge)? != candidate.hyperedge(edge)? {
+                changed_edges.push(edge);
+            }
+        }
+    }
     Ok(GraphMoveProposal {
         candidate,
         candidate_hash,
@@ -140,6 +167,9 @@ pub fn propose_resource_balanced(
         reverse_prob: 1.0 / nodes.len() as f64,
         touched_edges: Vec::new(),
         touched_node: Some(node),
+        change: GraphChange {
+            edges: changed_edges,
+        },
         description: format!("resource-balance:n{}", node.as_raw()),
     })
 }
@@ -1,6 +1,6 @@
 use asm_code::css::CSSCode;
 use asm_code::state::StateHandle;
-use asm_core::errors::ErrorInfo;
+use asm_core::errors::{ErrorCode, ErrorInfo};
 use asm_core::{AsmError, RngHandle};
 use asm_graph::HypergraphImpl;
 use rand::RngCore;
@@ -26,7 +26,7 @@ pub fn propose_worm(
     let num_variables = code.num_variables();
     if num_variables == 0 {
         return Err(AsmError::Code(ErrorInfo::new(
-            "empty-code",
+            ErrorCode::EmptyCode,
             "cannot generate worm sample for empty code",
         )));
     }
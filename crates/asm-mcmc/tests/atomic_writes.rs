@@ -0,0 +1,163 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use asm_code::css::CSSCode;
+use asm_core::provenance::{RunProvenance, SchemaVersion};
+use asm_core::{ErrorCode, Hypergraph};
+use asm_graph::{HypergraphConfig, HypergraphImpl, KUniformity};
+use tempfile::tempdir;
+
+use asm_mcmc::atomic::{temp_path, write_atomic, write_atomic_with};
+use asm_mcmc::manifest::{read_generation, RunManifest};
+use asm_mcmc::{run, MoveCounts, RunConfig};
+
+/// Forwards up to `budget` bytes to the inner writer, then fails.
+struct FailingWriter<'a> {
+    inner: &'a mut dyn Write,
+    budget: usize,
+}
+
+impl Write for FailingWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.budget == 0 {
+            return Err(io::Error::other("injected write failure"));
+        }
+        let len = buf.len().min(self.budget);
+        self.budget -= len;
+        self.inner.write(&buf[..len])
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn sample_code() -> CSSCode {
+    CSSCode::new(
+        4,
+        vec![vec![0, 1], vec![2, 3]],
+        vec![vec![0, 1], vec![2, 3]],
+        SchemaVersion::new(1, 0, 0),
+        RunProvenance::default(),
+    )
+    .unwrap()
+}
+
+fn sample_graph() -> HypergraphImpl {
+    let config = HypergraphConfig {
+        causal_mode: false,
+        max_in_degree: None,
+        max_out_degree: None,
+        k_uniform: Some(KUniformity::Balanced {
+            sources: 1,
+            destinations: 1,
+        }),
+        schema_version: SchemaVersion::new(2, 0, 0),
+        ..HypergraphConfig::default()
+    };
+    let mut graph = HypergraphImpl::new(config);
+    let a = graph.add_node().unwrap();
+    let b = graph.add_node().unwrap();
+    let c = graph.add_node().unwrap();
+    graph.add_hyperedge(&[a], &[b]).unwrap();
+    graph.add_hyperedge(&[b], &[c]).unwrap();
+    graph
+}
+
+fn run_config(root: &Path) -> RunConfig {
+    let mut config = RunConfig::default();
+    config.sweeps = 3;
+    config.move_counts = MoveCounts {
+        generator_flips: 1,
+        row_ops: 1,
+        graph_rewires: 1,
+        worm_moves: 1,
+    };
+    config.output.run_directory = Some(root.join("run"));
+    config.checkpoint.interval = 1;
+    config
+}
+
+fn tmp_debris(dir: &Path) -> Vec<String> {
+    let mut debris = Vec::new();
+    for entry in fs::read_dir(dir).unwrap() {
+        let entry = entry.unwrap();
+        let path = entry.path();
+        if path.is_dir() {
+            debris.extend(tmp_debris(&path));
+        } else if path.extension().is_some_and(|ext| ext == "tmp") {
+            debris.push(path.display().to_string());
+        }
+    }
+    debris
+}
+
+#[test]
+fn failed_write_leaves_original_intact() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("metrics.csv");
+    write_atomic(&path, "original\n").unwrap();
+
+    let err = write_atomic_with(&path, |writer| {
+        let mut failing = FailingWriter {
+            inner: writer,
+            budget: 4,
+        };
+        failing.write_all(b"replacement contents\n")
+    })
+    .expect_err("injected failure");
+    assert_eq!(err.to_string(), "injected write failure");
+    assert_eq!(fs::read_to_string(&path).unwrap(), "original\n");
+    assert!(!temp_path(&path).exists());
+
+    write_atomic(&path, "replacement contents\n").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "replacement contents\n");
+    assert!(tmp_debris(dir.path()).is_empty());
+}
+
+#[test]
+fn run_artefacts_are_committed_without_debris() {
+    let dir = tempdir().unwrap();
+    let config = run_config(dir.path());
+    let summary = run(&config, 7, &sample_code(), &sample_graph()).unwrap();
+    let run_dir = dir.path().join("run");
+    let manifest_path = summary.manifest_path.clone().unwrap();
+
+    let manifest = RunManifest::load_consistent(&manifest_path).unwrap();
+    assert_eq!(manifest.generation, 2);
+    assert_eq!(read_generation(&manifest_path).unwrap(), 2);
+    assert!(tmp_debris(&run_dir).is_empty());
+
+    let metrics = fs::read_to_string(summary.metrics_path.unwrap()).unwrap();
+    assert!(metrics.starts_with("sweep,replica,temperature"));
+    for checkpoint in &manifest.checkpoints {
+        assert!(run_dir.join(checkpoint).exists());
+    }
+
+    let rerun = run(&config, 7, &sample_code(), &sample_graph()).unwrap();
+    assert_eq!(rerun.final_code_hash, summary.final_code_hash);
+    let manifest = RunManifest::load_consistent(&manifest_path).unwrap();
+    assert_eq!(manifest.generation, 4);
+    assert!(tmp_debris(&run_dir).is_empty());
+}
+
+#[test]
+fn interrupted_update_is_detected() {
+    let dir = tempdir().unwrap();
+    let config = run_config(dir.path());
+    let summary = run(&config, 7, &sample_code(), &sample_graph()).unwrap();
+    let manifest_path = summary.manifest_path.unwrap();
+
+    // A run killed after marking the directory but before the manifest commits.
+    assert_eq!(RunManifest::begin_update(&manifest_path).unwrap(), 3);
+    let err = RunManifest::load_consistent(&manifest_path).expect_err("mid-update");
+    assert!(err.is(ErrorCode::ManifestIncomplete));
+    assert_eq!(err.context_value("generation"), Some("3"));
+    // The previous manifest itself is still readable.
+    assert_eq!(RunManifest::load(&manifest_path).unwrap().generation, 2);
+
+    let manifest = RunManifest::load(&manifest_path).unwrap();
+    assert_eq!(manifest.write(&manifest_path).unwrap(), 4);
+    assert!(RunManifest::load_consistent(&manifest_path).is_ok());
+}
@@ -29,6 +29,7 @@ fn sample_graph() -> HypergraphImpl {
             destinations: 1,
         }),
         schema_version: SchemaVersion::new(2, 0, 0),
+        ..HypergraphConfig::default()
     };
     let mut graph = HypergraphImpl::new(config);
     let a = graph.add_node().unwrap();
@@ -68,3 +69,46 @@ fn resume_from_checkpoint_preserves_hashes() {
     assert_eq!(summary.final_code_hash, resumed.final_code_hash);
     assert_eq!(summary.final_graph_hash, resumed.final_graph_hash);
 }
+
+#[test]
+fn pruned_end_state_drops_isolated_nodes_but_checkpoints_keep_them() {
+    use asm_graph::{canonical_hash, graph_from_json};
+    use asm_mcmc::analysis::load_end_state;
+    use asm_mcmc::manifest::RunManifest;
+    use asm_mcmc::CheckpointPayload;
+
+    let code = sample_code();
+    let mut graph = sample_graph();
+    graph.add_node().unwrap();
+    graph.add_node().unwrap();
+    let dir = tempdir().unwrap();
+    let mut config = checkpoint_config(dir.path());
+    config.move_counts.graph_rewires = 0;
+    config.output.prune_end_state = true;
+
+    let summary = run(&config, 888, &code, &graph).unwrap();
+    let run_dir = dir.path().join("run");
+    let (_, end_graph) = load_end_state(&run_dir).unwrap();
+    assert_eq!(end_graph.nodes().count(), 3);
+
+    let manifest = RunManifest::load(summary.manifest_path.as_ref().unwrap()).unwrap();
+    let pruning = manifest.end_state_pruning.expect("pruning note");
+    assert_eq!(pruning.pruned_nodes, 2);
+    assert_eq!(pruning.graph_hash, canonical_hash(&end_graph).unwrap());
+    assert_eq!(manifest.graph_hash, summary.final_graph_hash);
+    assert_ne!(pruning.graph_hash, manifest.graph_hash);
+
+    let checkpoint = CheckpointPayload::load(summary.checkpoints.last().unwrap()).unwrap();
+    let live = graph_from_json(&checkpoint.replicas[0].graph_json).unwrap();
+    assert_eq!(live.nodes().count(), 5);
+    assert_eq!(canonical_hash(&live).unwrap(), summary.final_graph_hash);
+
+    // Isolated nodes only enter the curvature proxy when explicitly counted.
+    let mut weights = config.scoring.clone();
+    let excluded = asm_mcmc::score(&code, &live, &weights).unwrap();
+    let pruned = asm_mcmc::score(&code, &end_graph, &weights).unwrap();
+    assert_eq!(excluded.curv, pruned.curv);
+    weights.exclude_isolated = false;
+    let counted = asm_mcmc::score(&code, &live, &weights).unwrap();
+    assert_ne!(counted.curv, excluded.curv);
+}
@@ -31,6 +31,7 @@ fn sample_graph() -> HypergraphImpl {
             destinations: 1,
         }),
         schema_version: SchemaVersion::new(2, 0, 0),
+        ..HypergraphConfig::default()
     };
     let mut graph = HypergraphImpl::new(config);
     let n0 = graph.add_node().unwrap();
@@ -3,7 +3,10 @@ use asm_core::provenance::{RunProvenance, SchemaVersion};
 use asm_core::Hypergraph;
 use asm_graph::{HypergraphConfig, HypergraphImpl, KUniformity};
 
-use asm_mcmc::{run, MoveCounts, RunConfig};
+use asm_mcmc::{
+    resume, resume_payload, run, run_until, CheckpointPayload, MoveCounts, RunConfig, SweepPosition,
+};
+use tempfile::tempdir;
 
 fn sample_code() -> CSSCode {
     CSSCode::new(
@@ -26,6 +29,7 @@ fn sample_graph() -> HypergraphImpl {
             destinations: 1,
         }),
         schema_version: SchemaVersion::new(2, 0, 0),
+        ..HypergraphConfig::default()
     };
     let mut graph = HypergraphImpl::new(config);
     let a = graph.add_node().unwrap();
@@ -63,3 +67,85 @@ fn repeated_runs_with_same_seed_match() {
 
     assert_eq!(summary_a, summary_b);
 }
+
+#[test]
+fn mid_sweep_resume_matches_uninterrupted_run() {
+    let code = sample_code();
+    let graph = sample_graph();
+    let config = deterministic_config();
+    let reference = run(&config, 2024, &code, &graph).unwrap();
+
+    for (sweep, replica, move_slot) in [(0, 0, 1), (1, 1, 3), (2, 2, 2)] {
+        let position = SweepPosition { replica, move_slot };
+        let payload = run_until(&config, 2024, &code, &graph, sweep, position).unwrap();
+        assert_eq!(payload.position, Some(position));
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("ckpt_mid.json");
+        payload.store(&path).unwrap();
+        let reloaded = CheckpointPayload::load(&path).unwrap();
+        assert_eq!(reloaded.position, Some(position));
+
+        let resumed = resume(&path).unwrap();
+        assert_eq!(reference, resumed);
+    }
+}
+
+#[test]
+fn killed_run_resumes_from_its_mid_sweep_checkpoints() {
+    let code = sample_code();
+    let graph = sample_graph();
+    let dir = tempdir().unwrap();
+    let mut config = deterministic_config();
+    config.output.run_directory = Some(dir.path().join("run"));
+    config.checkpoint.interval = 1;
+    config.checkpoint.move_interval = 5;
+    config.checkpoint.max_to_keep = 64;
+    let reference = run(&config, 2024, &code, &graph).unwrap();
+
+    let mut resumed_from = 0;
+    for (index, path) in reference.checkpoints.iter().enumerate() {
+        if CheckpointPayload::load(path).unwrap().position.is_none() {
+            continue;
+        }
+        // A run killed right after writing `path` has no later checkpoints.
+        for later in &reference.checkpoints[index + 1..] {
+            std::fs::remove_file(later).unwrap();
+        }
+        let resumed = resume(path).unwrap();
+        assert_eq!(reference, resumed, "resumed from {}", path.display());
+        resumed_from += 1;
+    }
+    assert!(resumed_from > 0);
+}
+
+#[test]
+fn out_of_range_sweep_position_is_rejected() {
+    let code = sample_code();
+    let graph = sample_graph();
+    let config = deterministic_config();
+    let position = SweepPosition {
+        replica: 0,
+        move_slot: 4,
+    };
+    let err = run_until(&config, 2024, &code, &graph, 0, position).unwrap_err();
+    assert_eq!(err.info().code, "invalid-sweep-position");
+
+    let mut payload = run_until(
+        &config,
+        2024,
+        &code,
+        &graph,
+        1,
+        SweepPosition {
+            replica: 0,
+            move_slot: 0,
+        },
+    )
+    .unwrap();
+    payload.position = Some(SweepPosition {
+        replica: 7,
+        move_slot: 0,
+    });
+    assert!(resume_payload(&payload).is_err());
+}
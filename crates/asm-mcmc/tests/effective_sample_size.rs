@@ -0,0 +1,85 @@
+use asm_code::css::CSSCode;
+use asm_core::provenance::{RunProvenance, SchemaVersion};
+use asm_core::Hypergraph;
+use asm_graph::{HypergraphConfig, HypergraphImpl, KUniformity};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use asm_mcmc::{integrated_autocorrelation_time, run, RunConfig};
+
+fn sample_code() -> CSSCode {
+    CSSCode::new(
+        4,
+        vec![vec![0, 1], vec![2, 3]],
+        vec![vec![0, 1], vec![2, 3]],
+        SchemaVersion::new(1, 0, 0),
+        RunProvenance::default(),
+    )
+    .unwrap()
+}
+
+fn sample_graph() -> HypergraphImpl {
+    let config = HypergraphConfig {
+        causal_mode: false,
+        max_in_degree: None,
+        max_out_degree: None,
+        k_uniform: Some(KUniformity::Balanced {
+            sources: 1,
+            destinations: 1,
+        }),
+        schema_version: SchemaVersion::new(2, 0, 0),
+        ..HypergraphConfig::default()
+    };
+    let mut graph = HypergraphImpl::new(config);
+    let a = graph.add_node().unwrap();
+    let b = graph.add_node().unwrap();
+    let c = graph.add_node().unwrap();
+    graph.add_hyperedge(&[a], &[b]).unwrap();
+    graph.add_hyperedge(&[b], &[c]).unwrap();
+    graph
+}
+
+#[test]
+fn ar1_series_recovers_autocorrelation_time() {
+    let phi: f64 = 0.8;
+    let expected = (1.0 + phi) / (1.0 - phi);
+    let mut rng = StdRng::seed_from_u64(2336);
+    let mut value = 0.0;
+    let series: Vec<f64> = (0..50_000)
+        .map(|_| {
+            value = phi * value + rng.gen_range(-1.0..1.0);
+            value
+        })
+        .collect();
+
+    let tau = integrated_autocorrelation_time(&series);
+    assert!(
+        (tau - expected).abs() / expected < 0.2,
+        "tau {tau} should be within 20% of {expected}"
+    );
+}
+
+#[test]
+fn constant_series_counts_as_single_sample() {
+    let series = vec![1.5; 200];
+    assert_eq!(integrated_autocorrelation_time(&series), 200.0);
+}
+
+#[test]
+fn short_chains_report_flagged_finite_ess() {
+    let mut config = RunConfig::default();
+    config.sweeps = 4;
+    config.burn_in = 0;
+    config.thinning = 1;
+    config.checkpoint.interval = 0;
+    config.output.run_directory = None;
+    let summary = run(&config, 7, &sample_code(), &sample_graph()).unwrap();
+
+    assert!(summary.ess_short_chain);
+    let keys: Vec<&str> = summary.ess.keys().map(String::as_str).collect();
+    assert_eq!(keys, ["cmdl", "curv", "spec", "total"]);
+    assert!(summary
+        .ess
+        .values()
+        .all(|ess| ess.is_finite() && *ess > 0.0));
+}
@@ -0,0 +1,177 @@
+use asm_code::css::CSSCode;
+use asm_core::provenance::{RunProvenance, SchemaVersion};
+use asm_core::rng::RngHandle;
+use asm_core::Hypergraph;
+use asm_graph::{HypergraphConfig, HypergraphImpl, KUniformity};
+use asm_mcmc::energy::{rescore_delta, score, EnergyBreakdown, LocalChange, ProxyState};
+use asm_mcmc::moves_code;
+use asm_mcmc::moves_graph;
+use asm_mcmc::{run, RunConfig, ScoringWeights};
+use proptest::prelude::*;
+use rand::RngCore;
+
+fn sample_code() -> CSSCode {
+    let schema = SchemaVersion::new(1, 0, 0);
+    let mut provenance = RunProvenance::default();
+    provenance.seed = 3;
+    CSSCode::new(
+        8,
+        vec![vec![0, 1], vec![2, 3], vec![0, 1, 2, 3], vec![4, 5, 6, 7]],
+        vec![vec![0, 1, 2, 3], vec![6, 7]],
+        schema,
+        provenance,
+    )
+    .expect("valid css code")
+}
+
+fn ring_graph(nodes: usize) -> HypergraphImpl {
+    let config = HypergraphConfig {
+        causal_mode: false,
+        max_in_degree: None,
+        max_out_degree: None,
+        k_uniform: Some(KUniformity::Balanced {
+            sources: 1,
+            destinations: 1,
+        }),
+        schema_version: SchemaVersion::new(2, 0, 0),
+        ..HypergraphConfig::default()
+    };
+    let mut graph = HypergraphImpl::new(config);
+    let ids: Vec<_> = (0..nodes).map(|_| graph.add_node().unwrap()).collect();
+    for idx in 0..nodes {
+        graph
+            .add_hyperedge(&[ids[idx]], &[ids[(idx + 1) % nodes]])
+            .unwrap();
+        graph
+            .add_hyperedge(&[ids[idx]], &[ids[(idx + 3) % nodes]])
+            .unwrap();
+    }
+    graph
+}
+
+fn assert_close(incremental: &EnergyBreakdown, full: &EnergyBreakdown, step: usize) {
+    for (label, a, b) in [
+        ("cmdl", incremental.cmdl, full.cmdl),
+        ("spec", incremental.spec, full.spec),
+        ("curv", incremental.curv, full.curv),
+        ("total", incremental.total, full.total),
+    ] {
+        assert!(
+            (a - b).abs() <= 1e-9,
+            "{label} diverged at step {step}: incremental {a} vs full {b}"
+        );
+    }
+}
+
+fn walk(seed: u64, steps: usize, exclude_isolated: bool) {
+    let weights = ScoringWeights {
+        cmdl: 1.0,
+        spec: 0.5,
+        curv: 2.0,
+        exclude_isolated,
+        ..ScoringWeights::default()
+    };
+    let mut code = sample_code();
+    let mut graph = ring_graph(7);
+    let mut state = ProxyState::new(&code, &graph, &weights).unwrap();
+    assert_close(
+        &state.breakdown(),
+        &score(&code, &graph, &weights).unwrap(),
+        0,
+    );
+    let mut rng = RngHandle::from_seed(seed);
+    for step in 1..=steps {
+        let mut move_rng = RngHandle::from_seed(rng.next_u64());
+        match rng.next_u64() % 5 {
+            kind @ (0 | 1) => {
+                let proposal = if kind == 0 {
+                    moves_code::propose_generator_flip(&code, &mut move_rng)
+                } else {
+                    moves_code::propose_row_operation(&code, &mut move_rng)
+                };
+                let Ok(proposal) = proposal else { continue };
+                let change = LocalChange::Code {
+                    candidate: &proposal.candidate,
+                    change: &proposal.change,
+                };
+                let (energy, next) = rescore_delta(&state, change).unwrap();
+                let full = score(&proposal.candidate, &graph, &weights).unwrap();
+                assert_close(&energy, &full, step);
+                code = proposal.candidate;
+                state = next;
+            }
+            kind => {
+                let proposal = match kind {
+                    2 => moves_graph::propose_swap_targets(&graph, &mut move_rng),
+                    3 => moves_graph::propose_retarget(&graph, &mut move_rng),
+                    _ => moves_graph::propose_resource_balanced(&graph, &mut move_rng),
+                };
+                let Ok(proposal) = proposal else { continue };
+                let change = LocalChange::Graph {
+                    candidate: &proposal.candidate,
+                    change: &proposal.change,
+                };
+                let (energy, next) = rescore_delta(&state, change).unwrap();
+                let full = score(&code, &proposal.candidate, &weights).unwrap();
+                assert_close(&energy, &full, step);
+                graph = proposal.candidate;
+                state = next;
+            }
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(24))]
+
+    #[test]
+    fn incremental_matches_full_over_random_moves(
+        seed in any::<u64>(),
+        exclude_isolated in any::<bool>(),
+    ) {
+        walk(seed, 120, exclude_isolated);
+    }
+}
+
+/// Shrunk case recorded in `incremental_energy.proptest-regressions`, kept
+/// as a named test so it runs even when the regression file is not read.
+#[test]
+fn recorded_regression_seed_zero_matches_full() {
+    walk(0, 120, false);
+}
+
+#[test]
+fn resource_balance_reports_rewired_edges() {
+    let graph = ring_graph(5);
+    let mut rewired_moves = 0;
+    for seed in 0..16 {
+        let mut rng = RngHandle::from_seed(seed);
+        let Ok(proposal) = moves_graph::propose_resource_balanced(&graph, &mut rng) else {
+            continue;
+        };
+        rewired_moves += usize::from(!proposal.change.edges.is_empty());
+        for edge in graph.edges() {
+            let rewired =
+                graph.hyperedge(edge).unwrap() != proposal.candidate.hyperedge(edge).unwrap();
+            assert_eq!(rewired, proposal.change.edges.contains(&edge));
+        }
+    }
+    assert!(rewired_moves > 0);
+}
+
+#[test]
+fn full_scoring_switch_reproduces_incremental_runs() {
+    let mut config = RunConfig::default();
+    config.sweeps = 6;
+    config.ladder.replicas = 2;
+    let code = sample_code();
+    let graph = ring_graph(6);
+    let incremental = run(&config, 17, &code, &graph).unwrap();
+    config.scoring.incremental = false;
+    let full = run(&config, 17, &code, &graph).unwrap();
+    assert_eq!(incremental.final_code_hash, full.final_code_hash);
+    assert_eq!(incremental.final_graph_hash, full.final_graph_hash);
+    for (a, b) in incremental.samples.iter().zip(&full.samples) {
+        assert_close(&a.energy, &b.energy, a.sweep);
+    }
+}
@@ -0,0 +1,132 @@
+use asm_code::css::CSSCode;
+use asm_core::provenance::{RunProvenance, SchemaVersion};
+use asm_core::{derive_labeled_seed, Hypergraph};
+use asm_graph::{HypergraphConfig, HypergraphImpl, KUniformity};
+
+use asm_mcmc::determinism::{
+    exchange_label, exchange_seed, move_label, move_seed, replica_label, replica_seed,
+};
+use asm_mcmc::{run, MoveCounts, RunConfig, SeedPolicy, SeedStreams, StreamDerivation};
+
+fn sample_code() -> CSSCode {
+    CSSCode::new(
+        4,
+        vec![vec![0, 1], vec![2, 3]],
+        vec![vec![0, 1], vec![2, 3]],
+        SchemaVersion::new(1, 0, 0),
+        RunProvenance::default(),
+    )
+    .unwrap()
+}
+
+fn sample_graph() -> HypergraphImpl {
+    let config = HypergraphConfig {
+        causal_mode: false,
+        max_in_degree: None,
+        max_out_degree: None,
+        k_uniform: Some(KUniformity::Balanced {
+            sources: 1,
+            destinations: 1,
+        }),
+        schema_version: SchemaVersion::new(2, 0, 0),
+        ..HypergraphConfig::default()
+    };
+    let mut graph = HypergraphImpl::new(config);
+    let a = graph.add_node().unwrap();
+    let b = graph.add_node().unwrap();
+    let c = graph.add_node().unwrap();
+    graph.add_hyperedge(&[a], &[b]).unwrap();
+    graph.add_hyperedge(&[b], &[c]).unwrap();
+    graph
+}
+
+fn run_config(streams: StreamDerivation) -> RunConfig {
+    let mut config = RunConfig {
+        sweeps: 4,
+        move_counts: MoveCounts {
+            generator_flips: 1,
+            row_ops: 1,
+            graph_rewires: 1,
+            worm_moves: 1,
+        },
+        ..RunConfig::default()
+    };
+    config.seed_policy.streams = streams;
+    config
+}
+
+#[test]
+fn numeric_streams_keep_legacy_seeds() {
+    let policy = SeedPolicy {
+        streams: StreamDerivation::Numeric,
+        ..SeedPolicy::default()
+    };
+    let streams = SeedStreams::new(99, &policy).unwrap();
+    assert_eq!(streams.replica(2), replica_seed(99, 2));
+    assert_eq!(streams.proposal(1, 5, 3), move_seed(99, 1, 5, 3));
+    assert_eq!(streams.exchange(5, 0), exchange_seed(99, 5, 0));
+
+    let labeled = SeedStreams::new(99, &SeedPolicy::default()).unwrap();
+    assert_eq!(
+        labeled.replica(2),
+        derive_labeled_seed(99, &replica_label(2))
+    );
+    assert_eq!(
+        labeled.proposal(1, 5, 3),
+        derive_labeled_seed(99, &move_label(1, 5, 3))
+    );
+    assert_eq!(
+        labeled.exchange(5, 0),
+        derive_labeled_seed(99, &exchange_label(5, 0))
+    );
+    assert_ne!(labeled.proposal(1, 5, 3), streams.proposal(1, 5, 3));
+}
+
+#[test]
+fn both_schemes_are_deterministic() {
+    for streams in [StreamDerivation::Labeled, StreamDerivation::Numeric] {
+        let config = run_config(streams);
+        let a = run(&config, 17, &sample_code(), &sample_graph()).unwrap();
+        let b = run(&config, 17, &sample_code(), &sample_graph()).unwrap();
+        assert_eq!(a, b, "{streams:?}");
+    }
+}
+
+#[test]
+fn stream_derivation_defaults_to_labeled() {
+    let policy: SeedPolicy = serde_yaml::from_str("master_seed: 4242\n").unwrap();
+    assert_eq!(policy.streams, StreamDerivation::Labeled);
+    assert!(!policy.audit);
+    let policy: SeedPolicy = serde_yaml::from_str("master_seed: 4242\nstreams: numeric\n").unwrap();
+    assert_eq!(policy.streams, StreamDerivation::Numeric);
+}
+
+#[cfg(not(feature = "seed-audit"))]
+#[test]
+fn audit_requires_feature() {
+    let mut config = run_config(StreamDerivation::Labeled);
+    config.seed_policy.audit = true;
+    let err = run(&config, 17, &sample_code(), &sample_graph()).expect_err("feature disabled");
+    assert!(err.is(asm_core::ErrorCode::SeedAuditUnavailable));
+}
+
+#[cfg(feature = "seed-audit")]
+#[test]
+fn manifest_records_clean_audit() {
+    use asm_mcmc::manifest::RunManifest;
+
+    let dir = tempfile::tempdir().unwrap();
+    let mut config = run_config(StreamDerivation::Labeled);
+    config.seed_policy.audit = true;
+    config.output.run_directory = Some(dir.path().join("run"));
+    let summary = run(&config, 17, &sample_code(), &sample_graph()).unwrap();
+    let manifest = RunManifest::load(&summary.manifest_path.unwrap()).unwrap();
+    let audit = manifest.seed_audit.expect("audit summary");
+
+    let replicas = summary.replica_temperatures.len() as u64;
+    let sweeps = config.sweeps as u64;
+    let expected = replicas + sweeps * replicas * 4 + sweeps * (replicas - 1);
+    assert_eq!(audit.derivations, expected);
+    assert_eq!(audit.distinct as u64, expected);
+    assert!(audit.is_clean());
+}
@@ -1,10 +1,15 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 
 use asm_core::errors::{AsmError, ErrorInfo};
 use asm_core::{Hypergraph, NodeId};
 use asm_graph::HypergraphImpl;
+use nalgebra::{DMatrix, SymmetricEigen};
 
-use crate::params::RGOpts;
+use crate::params::{PartitionStrategy, RGOpts};
+
+/// Fiedler vector components closer than this are treated as ties and
+/// ordered by node identifier.
+const FIEDLER_TOLERANCE: f64 = 1e-9;
 
 /// Deterministic partition of fine nodes into coarse blocks.
 #[derive(Debug, Clone, PartialEq)]
@@ -36,11 +41,43 @@ pub fn partition_nodes(graph: &HypergraphImpl, opts: &RGOpts) -> Result<BlockPar
         );
         return Err(AsmError::RG(info));
     }
+    nodes.sort();
+
+    let blocks = match opts.partition_strategy {
+        PartitionStrategy::Canonical => canonical_blocks(nodes.clone(), &opts),
+        PartitionStrategy::SpectralBisection { levels } => {
+            spectral_blocks(&nodes, &adjacency(graph)?, levels)
+        }
+        PartitionStrategy::BFSBlocks { block_size } => {
+            bfs_blocks(&nodes, &adjacency(graph)?, block_size)
+        }
+    };
+
+    let mut lookup = BTreeMap::new();
+    for (idx, block) in blocks.iter().enumerate() {
+        for node in block {
+            lookup.insert(*node, idx);
+        }
+    }
+    if blocks.iter().any(Vec::is_empty)
+        || lookup.len() != nodes.len()
+        || blocks.iter().map(Vec::len).sum::<usize>() != nodes.len()
+    {
+        let info = ErrorInfo::new(
+            "partition-cover",
+            "partition blocks must be non-empty and cover every node exactly once",
+        )
+        .with_context("strategy", opts.partition_strategy.to_string());
+        return Err(AsmError::RG(info));
+    }
+
+    Ok(BlockPartition { blocks, lookup })
+}
 
+fn canonical_blocks(mut nodes: Vec<NodeId>, opts: &RGOpts) -> Vec<Vec<NodeId>> {
     nodes.sort_by_key(|node| mix(node.as_raw(), opts.seed));
 
     let mut blocks: Vec<Vec<NodeId>> = Vec::new();
-    let mut lookup = BTreeMap::new();
     let mut current = Vec::new();
     for node in nodes {
         if current.len() >= opts.max_block_size {
@@ -57,14 +94,122 @@ pub fn partition_nodes(graph: &HypergraphImpl, opts: &RGOpts) -> Result<BlockPar
     if !current.is_empty() {
         blocks.push(current);
     }
+    blocks
+}
 
-    for (idx, block) in blocks.iter().enumerate() {
-        for node in block {
-            lookup.insert(*node, idx);
+/// Clique expansion of the hypergraph: every pair of nodes sharing a
+/// hyperedge is adjacent.
+fn adjacency(graph: &HypergraphImpl) -> Result<BTreeMap<NodeId, BTreeSet<NodeId>>, AsmError> {
+    let mut adjacency: BTreeMap<NodeId, BTreeSet<NodeId>> = BTreeMap::new();
+    for edge in graph.edges() {
+        let endpoints = graph.hyperedge(edge)?;
+        let members: BTreeSet<NodeId> = endpoints
+            .sources
+            .iter()
+            .chain(endpoints.destinations.iter())
+            .copied()
+            .collect();
+        for &a in &members {
+            for &b in &members {
+                if a != b {
+                    adjacency.entry(a).or_default().insert(b);
+                }
+            }
         }
     }
+    Ok(adjacency)
+}
 
-    Ok(BlockPartition { blocks, lookup })
+/// Recursively bisects `nodes` (sorted by identifier) at the median of the
+/// Fiedler vector of their induced Laplacian.
+fn spectral_blocks(
+    nodes: &[NodeId],
+    adjacency: &BTreeMap<NodeId, BTreeSet<NodeId>>,
+    levels: u32,
+) -> Vec<Vec<NodeId>> {
+    if levels == 0 || nodes.len() < 2 {
+        return vec![nodes.to_vec()];
+    }
+
+    let index: BTreeMap<NodeId, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(idx, node)| (*node, idx))
+        .collect();
+    let n = nodes.len();
+    let mut laplacian = DMatrix::<f64>::zeros(n, n);
+    for (i, node) in nodes.iter().enumerate() {
+        for neighbour in adjacency.get(node).into_iter().flatten() {
+            if let Some(&j) = index.get(neighbour) {
+                laplacian[(i, j)] = -1.0;
+                laplacian[(i, i)] += 1.0;
+            }
+        }
+    }
+
+    let eigen = SymmetricEigen::new(laplacian);
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| {
+        eigen.eigenvalues[a]
+            .total_cmp(&eigen.eigenvalues[b])
+            .then(a.cmp(&b))
+    });
+    let fiedler = eigen.eigenvectors.column(order[1]);
+    // Fix the eigenvector sign so the first significant component is positive.
+    let sign = fiedler
+        .iter()
+        .find(|value| value.abs() > FIEDLER_TOLERANCE)
+        .map_or(1.0, |value| value.signum());
+
+    let mut ranked: Vec<(i64, NodeId)> = nodes
+        .iter()
+        .enumerate()
+        .map(|(idx, node)| {
+            let quantised = (sign * fiedler[idx] / FIEDLER_TOLERANCE).round() as i64;
+            (quantised, *node)
+        })
+        .collect();
+    ranked.sort();
+    let mut lower: Vec<NodeId> = ranked[..n / 2].iter().map(|(_, node)| *node).collect();
+    let mut upper: Vec<NodeId> = ranked[n / 2..].iter().map(|(_, node)| *node).collect();
+    lower.sort();
+    upper.sort();
+    if upper[0] < lower[0] {
+        std::mem::swap(&mut lower, &mut upper);
+    }
+
+    let mut blocks = spectral_blocks(&lower, adjacency, levels - 1);
+    blocks.extend(spectral_blocks(&upper, adjacency, levels - 1));
+    blocks
+}
+
+/// Grows blocks of at most `block_size` nodes breadth-first from the lowest
+/// unassigned node, visiting neighbours in identifier order.
+fn bfs_blocks(
+    nodes: &[NodeId],
+    adjacency: &BTreeMap<NodeId, BTreeSet<NodeId>>,
+    block_size: usize,
+) -> Vec<Vec<NodeId>> {
+    let mut unassigned: BTreeSet<NodeId> = nodes.iter().copied().collect();
+    let mut blocks = Vec::new();
+    while let Some(start) = unassigned.pop_first() {
+        let mut block = vec![start];
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            for neighbour in adjacency.get(&node).into_iter().flatten() {
+                if block.len() >= block_size {
+                    break;
+                }
+                if unassigned.remove(neighbour) {
+                    block.push(*neighbour);
+                    queue.push_back(*neighbour);
+                }
+            }
+        }
+        block.sort();
+        blocks.push(block);
+    }
+    blocks
 }
 
 fn mix(value: u64, seed: u64) -> u64 {
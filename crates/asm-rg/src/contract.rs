@@ -3,7 +3,8 @@ use asm_code::CSSCode;
 use asm_core::errors::{AsmError, ErrorInfo};
 
 use crate::block::BlockPartition;
-use crate::isometry::{evaluate_isometry, IsometrySummary};
+use crate::isometry::{build_certificate, evaluate_isometry, IsometrySummary};
+use crate::params::RGOpts;
 
 /// Result of contracting a CSS code under the RG map.
 #[derive(Debug)]
@@ -15,11 +16,15 @@ pub struct ContractResult {
 }
 
 /// Applies a CSS-preserving contraction according to the provided partition.
+///
+/// The summary carries a [`crate::isometry::ContractionCertificate`] when
+/// [`RGOpts::emit_certificate`] is set.
 pub fn apply_contract(
     code: &CSSCode,
     partition: &BlockPartition,
+    opts: &RGOpts,
 ) -> Result<ContractResult, AsmError> {
-    let summary = evaluate_isometry(code, partition)?;
+    let mut summary = evaluate_isometry(code, partition)?;
     if !code.is_css_orthogonal() {
         let info = ErrorInfo::new(
             "non-css-input",
@@ -38,6 +43,9 @@ pub fn apply_contract(
         rank_x,
         rank_z,
     );
+    if opts.emit_certificate {
+        summary.certificate = Some(build_certificate(code, &coarse_code)?);
+    }
 
     Ok(ContractResult {
         code: coarse_code,
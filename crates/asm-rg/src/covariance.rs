@@ -1,10 +1,14 @@
 use asm_core::errors::AsmError;
+use asm_spec::{analyze_spectrum, SpecOpts, SpectrumReport};
 use serde::{Deserialize, Serialize};
 
 use crate::dictionary::{self, CouplingsReport};
-use crate::hash::hash_covariance;
-use crate::params::{CovarianceThresholds, DictOpts, RGOpts};
-use crate::{rg_run, StateRef};
+use crate::hash::{hash_covariance, hash_spectral_covariance};
+use crate::params::{CovarianceThresholds, DictOpts, RGOpts, SpectralCovarianceThresholds};
+use crate::{rg_run, rg_step, StateRef};
+
+/// Smallest magnitude used as the denominator of a relative drift.
+const DRIFT_FLOOR: f64 = 1e-9;
 
 /// Component-wise deviations reported by the covariance check.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -19,6 +23,26 @@ pub struct CovarianceDelta {
     pub yukawa_max_absolute: f64,
 }
 
+impl CovarianceDelta {
+    /// Computes the deviations of `b` from `a`, aligning couplings by name.
+    ///
+    /// Fails with `coupling-name-mismatch` when the reports carry different
+    /// coupling names (for example differing Yukawa counts).
+    pub fn between(a: &CouplingsReport, b: &CouplingsReport) -> Result<Self, AsmError> {
+        let reference = a.couplings();
+        let other = b.couplings();
+        let absolute = reference.difference(&other)?;
+        let relative = reference.relative_difference(&other)?;
+        let max_abs = |values: &[f64]| values.iter().map(|value| value.abs()).fold(0.0, f64::max);
+        Ok(CovarianceDelta {
+            c_kin_relative: relative.c_kin.abs(),
+            g_max_absolute: max_abs(&absolute.gauge),
+            lambda_absolute: absolute.lambda_h.abs(),
+            yukawa_max_absolute: max_abs(&absolute.yukawa),
+        })
+    }
+}
+
 /// Structured report comparing dictionary extraction and RG flow.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CovarianceReport {
@@ -56,7 +80,7 @@ pub fn covariance_check(
     let mut couplings_d_then_r = couplings_r_then_d.clone();
     couplings_d_then_r.dict_hash = crate::hash::hash_couplings_report(&couplings_d_then_r)?;
 
-    let delta = compute_delta(&couplings_r_then_d, &couplings_d_then_r);
+    let delta = CovarianceDelta::between(&couplings_r_then_d, &couplings_d_then_r)?;
     let pass = delta.c_kin_relative <= thresholds.c_kin_relative
         && delta.g_max_absolute <= thresholds.g_absolute
         && delta.lambda_absolute <= thresholds.lambda_absolute
@@ -74,28 +98,154 @@ pub fn covariance_check(
     Ok(report)
 }
 
-fn compute_delta(a: &CouplingsReport, b: &CouplingsReport) -> CovarianceDelta {
-    let c_kin_relative = if a.c_kin.abs() > f64::EPSILON {
-        ((a.c_kin - b.c_kin) / a.c_kin).abs()
-    } else {
-        (a.c_kin - b.c_kin).abs()
-    };
-    let g_max_absolute =
-        a.g.iter()
-            .zip(b.g.iter())
-            .map(|(x, y)| (x - y).abs())
-            .fold(0.0, f64::max);
-    let lambda_absolute = (a.lambda_h - b.lambda_h).abs();
-    let yukawa_max_absolute = a
-        .yukawa
-        .iter()
-        .zip(b.yukawa.iter())
-        .map(|(x, y)| (x - y).abs())
-        .fold(0.0, f64::max);
-    CovarianceDelta {
-        c_kin_relative,
-        g_max_absolute,
-        lambda_absolute,
-        yukawa_max_absolute,
+/// One observable compared between the fine state and its coarse image.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpectralObservable {
+    /// Observable name (`c_est`, `gap_proxy` or `xi`).
+    pub name: String,
+    /// Value measured on the fine state.
+    pub fine: f64,
+    /// Value measured on the coarse state, in coarse lattice units.
+    pub coarse: f64,
+    /// Coarse value mapped back to fine lattice units.
+    pub rescaled: f64,
+    /// `|rescaled - fine| / |fine|`, with the denominator floored at `1e-9`.
+    pub relative_drift: f64,
+    /// Tolerance applied to `relative_drift`.
+    pub threshold: f64,
+    /// Whether the drift is within the tolerance.
+    pub pass: bool,
+}
+
+impl SpectralObservable {
+    fn new(name: &str, fine: f64, coarse: f64, rescaled: f64, threshold: f64) -> Self {
+        let relative_drift = (rescaled - fine).abs() / fine.abs().max(DRIFT_FLOOR);
+        Self {
+            name: name.to_string(),
+            fine,
+            coarse,
+            rescaled,
+            relative_drift,
+            threshold,
+            pass: relative_drift <= threshold,
+        }
     }
 }
+
+/// Structured report comparing the spectrum of a state with that of its RG image.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpectralCovarianceReport {
+    /// Scale factor `b` of the RG step.
+    pub scale_factor: usize,
+    /// Dispersion momentum grid of the fine state.
+    pub k_grid_fine: Vec<f64>,
+    /// Dispersion momentum grid of the coarse state, in coarse lattice units.
+    pub k_grid_coarse: Vec<f64>,
+    /// Coarse momentum grid mapped to fine lattice units (`k / b`).
+    pub k_grid_rescaled: Vec<f64>,
+    /// Per-observable comparisons, ordered `c_est`, `gap_proxy`, `xi`.
+    pub observables: Vec<SpectralObservable>,
+    /// Thresholds applied during the comparison.
+    pub thresholds: SpectralCovarianceThresholds,
+    /// Whether every observable passed.
+    pub pass: bool,
+    /// `analysis_hash` of the fine spectrum report.
+    pub fine_spectrum_hash: String,
+    /// `step_hash` of the RG step.
+    pub rg_step_hash: String,
+    /// `analysis_hash` of the coarse spectrum report.
+    pub coarse_spectrum_hash: String,
+    /// Canonical hash of this report.
+    pub spectral_covariance_hash: String,
+}
+
+/// Compares the spectrum of `state` with that of its image under one RG step,
+/// using [`SpectralCovarianceThresholds::default`].
+pub fn spectral_covariance(
+    state: &StateRef,
+    rg_opts: &RGOpts,
+    spec_opts: &SpecOpts,
+) -> Result<SpectralCovarianceReport, AsmError> {
+    spectral_covariance_with(
+        state,
+        rg_opts,
+        spec_opts,
+        &SpectralCovarianceThresholds::default(),
+    )
+}
+
+/// Runs [`analyze_spectrum`] on `state`, applies [`rg_step`], re-runs the
+/// analysis on the coarse state and compares the results.
+///
+/// Coarse observables are measured in coarse lattice units, where one coarse
+/// spacing spans `b = scale_factor` fine spacings. They are mapped back to
+/// fine units before comparison:
+///
+/// * momenta: `k_fine = k_coarse / b`,
+/// * lengths: `xi_fine = b * xi_coarse`,
+/// * velocities: `c_fine = b * c_coarse`, since frequencies are shared and
+///   only the momentum axis is rescaled,
+/// * gaps are frequencies and compare unscaled.
+pub fn spectral_covariance_with(
+    state: &StateRef,
+    rg_opts: &RGOpts,
+    spec_opts: &SpecOpts,
+    thresholds: &SpectralCovarianceThresholds,
+) -> Result<SpectralCovarianceReport, AsmError> {
+    let scale_factor = rg_opts.sanitised().scale_factor;
+    let b = scale_factor as f64;
+
+    let fine = analyze_spectrum(state.graph, state.code, spec_opts)?;
+    let step = rg_step(state.graph, state.code, rg_opts)?;
+    let coarse = analyze_spectrum(&step.graph, &step.code, spec_opts)?;
+
+    let observables = spectral_observables(&fine, &coarse, b, thresholds);
+    let pass = observables.iter().all(|observable| observable.pass);
+
+    let mut report = SpectralCovarianceReport {
+        scale_factor,
+        k_grid_fine: fine.dispersion.k_grid.clone(),
+        k_grid_rescaled: coarse.dispersion.k_grid.iter().map(|k| k / b).collect(),
+        k_grid_coarse: coarse.dispersion.k_grid.clone(),
+        observables,
+        thresholds: thresholds.clone(),
+        pass,
+        fine_spectrum_hash: fine.analysis_hash,
+        rg_step_hash: step.report.step_hash,
+        coarse_spectrum_hash: coarse.analysis_hash,
+        spectral_covariance_hash: String::new(),
+    };
+    report.spectral_covariance_hash = hash_spectral_covariance(&report)?;
+    Ok(report)
+}
+
+fn spectral_observables(
+    fine: &SpectrumReport,
+    coarse: &SpectrumReport,
+    b: f64,
+    thresholds: &SpectralCovarianceThresholds,
+) -> Vec<SpectralObservable> {
+    vec![
+        SpectralObservable::new(
+            "c_est",
+            fine.dispersion.c_est,
+            coarse.dispersion.c_est,
+            coarse.dispersion.c_est * b,
+            thresholds.c_relative,
+        ),
+        SpectralObservable::new(
+            "gap_proxy",
+            fine.dispersion.gap_proxy,
+            coarse.dispersion.gap_proxy,
+            coarse.dispersion.gap_proxy,
+            thresholds.gap_relative,
+        ),
+        SpectralObservable::new(
+            "xi",
+            fine.correlation.xi,
+            coarse.correlation.xi,
+            coarse.correlation.xi * b,
+            thresholds.xi_relative,
+        ),
+    ]
+}
@@ -1,6 +1,6 @@
 use asm_code::CSSCode;
 use asm_core::errors::AsmError;
-use asm_core::Hypergraph;
+use asm_core::{Couplings, Hypergraph, RunProvenance, SchemaVersion};
 use asm_graph::HypergraphImpl;
 use serde::{Deserialize, Serialize};
 
@@ -50,6 +50,31 @@ pub struct CouplingsReport {
     pub provenance: DictionaryProvenance,
 }
 
+impl CouplingsReport {
+    /// Returns the extracted values as a named [`Couplings`] registry.
+    pub fn couplings(&self) -> Couplings {
+        Couplings {
+            schema_version: SchemaVersion::default(),
+            provenance: RunProvenance {
+                seed: self.provenance.seed,
+                ..RunProvenance::default()
+            },
+            c_kin: self.c_kin,
+            gauge: self.g,
+            yukawa: self.yukawa.clone(),
+            lambda_h: self.lambda_h,
+            notes: Some(self.provenance.notes.clone()),
+            extra: Default::default(),
+            units: Default::default(),
+        }
+    }
+
+    /// Returns the coupling stored under `name` (see [`Couplings::get`]).
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.couplings().get(name)
+    }
+}
+
 /// Extracts deterministic synthetic couplings from a code/graph pair.
 pub fn extract_couplings(
     graph: &HypergraphImpl,
@@ -1,23 +1,130 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use asm_core::errors::{AsmError, ErrorInfo};
-use asm_graph::HypergraphImpl;
+use asm_core::{Hypergraph, NodeId};
 use asm_graph::{graph_from_bytes, graph_to_bytes};
+use asm_graph::{HypergraphConfig, HypergraphImpl};
+
+use crate::block::BlockPartition;
+use crate::params::RGOpts;
+
+/// Number of original fine nodes represented by each node of a graph.
+pub type NodePopulations = BTreeMap<NodeId, usize>;
 
 /// Result of coarsening a hypergraph under the RG map.
 #[derive(Debug, Clone)]
 pub struct GraphCoarseResult {
     /// The coarse grained hypergraph.
     pub graph: HypergraphImpl,
+    /// Original fine nodes represented by each coarse node.
+    pub node_populations: NodePopulations,
+}
+
+/// Populations of an uncoarsened graph: every node represents itself.
+pub fn unit_populations(graph: &HypergraphImpl) -> NodePopulations {
+    graph.nodes().map(|node| (node, 1)).collect()
+}
+
+/// Converts populations into the per-node weights accepted by
+/// `asm_spec::OpOpts::node_weights`.
+pub fn population_weights(populations: &NodePopulations) -> BTreeMap<u64, f64> {
+    populations
+        .iter()
+        .map(|(node, count)| (node.as_raw(), *count as f64))
+        .collect()
+}
+
+/// Coarsens `graph` according to `partition`.
+///
+/// With [`RGOpts::merge_nodes`] every block collapses into one coarse node
+/// whose population is the sum of its members' `populations`; otherwise the
+/// topology is kept and populations carry over unchanged. Nodes missing from
+/// `populations` count as one fine node.
+pub fn coarsen_graph(
+    graph: &HypergraphImpl,
+    partition: &BlockPartition,
+    populations: &NodePopulations,
+    opts: &RGOpts,
+) -> Result<GraphCoarseResult, AsmError> {
+    let population = |node: &NodeId| populations.get(node).copied().unwrap_or(1);
+    if !opts.merge_nodes {
+        return Ok(GraphCoarseResult {
+            graph: clone_graph(graph)?,
+            node_populations: graph
+                .nodes()
+                .map(|node| (node, population(&node)))
+                .collect(),
+        });
+    }
+
+    // Merged blocks may join hyperedges of any arity or direction, so the
+    // coarse graph drops the fine uniformity, degree and causality limits. It
+    // keeps the fine graph's storage mode.
+    let config = HypergraphConfig {
+        causal_mode: false,
+        max_in_degree: None,
+        max_out_degree: None,
+        k_uniform: None,
+        schema_version: graph.config().schema_version,
+        degree_overrides: BTreeMap::new(),
+        label_rules: BTreeMap::new(),
+        compact_ids: graph.config().compact_ids,
+    };
+    let mut coarse = HypergraphImpl::new(config);
+    let mut node_populations = NodePopulations::new();
+    let mut coarse_nodes = Vec::with_capacity(partition.blocks().len());
+    for block in partition.blocks() {
+        let node = coarse.add_node()?;
+        node_populations.insert(node, block.iter().map(population).sum());
+        coarse_nodes.push(node);
+    }
+
+    let to_coarse = |nodes: &[NodeId]| -> Result<BTreeSet<NodeId>, AsmError> {
+        nodes
+            .iter()
+            .map(|node| {
+                partition
+                    .block_index(*node)
+                    .map(|idx| coarse_nodes[idx])
+                    .ok_or_else(|| {
+                        AsmError::RG(
+                            ErrorInfo::new("partition-cover", "node missing from partition")
+                                .with_context("node", node.as_raw().to_string()),
+                        )
+                    })
+            })
+            .collect()
+    };
+    let mut seen = BTreeSet::new();
+    for edge in graph.edges() {
+        let endpoints = graph.hyperedge(edge)?;
+        let sources = to_coarse(&endpoints.sources)?;
+        let destinations = to_coarse(&endpoints.destinations)?;
+        // Edges internal to a single block become part of the coarse node.
+        if sources.len() == 1 && sources == destinations {
+            continue;
+        }
+        if seen.insert((sources.clone(), destinations.clone())) {
+            let sources: Vec<NodeId> = sources.into_iter().collect();
+            let destinations: Vec<NodeId> = destinations.into_iter().collect();
+            coarse.add_hyperedge(&sources, &destinations)?;
+        }
+    }
+
+    Ok(GraphCoarseResult {
+        graph: coarse,
+        node_populations,
+    })
 }
 
-/// Applies deterministic node merging according to the provided partition.
-pub fn coarsen_graph(graph: &HypergraphImpl) -> Result<GraphCoarseResult, AsmError> {
+/// Clones a hypergraph using the deterministic serializer.
+pub(crate) fn clone_graph(graph: &HypergraphImpl) -> Result<HypergraphImpl, AsmError> {
     let bytes = graph_to_bytes(graph)?;
-    let cloned = graph_from_bytes(&bytes).map_err(|err| match err {
+    graph_from_bytes(&bytes).map_err(|err| match err {
         AsmError::Serde(info) => AsmError::RG(
             ErrorInfo::new("graph-clone", "failed to clone graph via serialization")
                 .with_context("cause", info.to_string()),
         ),
         other => other,
-    })?;
-    Ok(GraphCoarseResult { graph: cloned })
+    })
 }
@@ -2,7 +2,7 @@ use asm_core::errors::{AsmError, ErrorInfo};
 use serde::Serialize;
 use sha2::{Digest, Sha256};
 
-use crate::covariance::CovarianceReport;
+use crate::covariance::{CovarianceReport, SpectralCovarianceReport};
 use crate::dictionary::{CouplingsReport, DictionaryProvenance};
 use crate::{RGRunReport, RGStepReport};
 
@@ -59,6 +59,11 @@ pub fn hash_covariance(report: &CovarianceReport) -> Result<String, AsmError> {
     hash_json(report)
 }
 
+/// Computes the canonical hash for a spectral covariance report.
+pub fn hash_spectral_covariance(report: &SpectralCovarianceReport) -> Result<String, AsmError> {
+    hash_json(report)
+}
+
 /// Convenience wrapper for hashing a full couplings report.
 pub fn hash_couplings_report(report: &CouplingsReport) -> Result<String, AsmError> {
     hash_json(report)
@@ -1,5 +1,10 @@
+use std::collections::BTreeMap;
+
+use asm_code::css::{into_parts, Constraint};
 use asm_code::CSSCode;
 use asm_core::errors::{AsmError, ErrorInfo};
+use asm_core::NodeId;
+use serde::{Deserialize, Serialize};
 
 use crate::block::BlockPartition;
 
@@ -12,6 +17,38 @@ pub struct IsometrySummary {
     pub lost_constraints: usize,
     /// Whether the output code maintains CSS structure.
     pub css_preserved: bool,
+    /// Machine-checkable record of the preserved constraints, when requested.
+    pub certificate: Option<ContractionCertificate>,
+}
+
+/// Lifting data and ranks for one stabiliser sector (X or Z).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectorCertificate {
+    /// For each coarse constraint, the sorted `(fine index, coefficient)`
+    /// pairs over GF(2) whose sum reproduces it. Empty when the coarse
+    /// constraint lies outside the fine span.
+    pub lifting: Vec<Vec<(usize, u8)>>,
+    /// GF(2) rank of the fine constraints.
+    pub rank_fine: usize,
+    /// GF(2) rank of the coarse constraints.
+    pub rank_coarse: usize,
+}
+
+/// Certificate of what a contraction preserved.
+///
+/// Coarse and fine codes share variable indices, so each lifted combination
+/// of fine constraints must equal its coarse constraint exactly.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractionCertificate {
+    /// X-sector lifting map and ranks.
+    pub x: SectorCertificate,
+    /// Z-sector lifting map and ranks.
+    pub z: SectorCertificate,
+    /// Independent fine constraints without a coarse counterpart, summed over
+    /// both sectors; an upper bound on the stabiliser bits lost.
+    pub information_loss_bound: usize,
+    /// Whether applying the lifting map reproduced every coarse constraint.
+    pub verified: bool,
 }
 
 impl IsometrySummary {
@@ -21,6 +58,7 @@ impl IsometrySummary {
             kept_fraction: 1.0,
             lost_constraints: 0,
             css_preserved: true,
+            certificate: None,
         }
     }
 }
@@ -39,5 +77,257 @@ pub fn evaluate_isometry(
     }
 
     let total_constraints = code.num_constraints_x() + code.num_constraints_z();
-    Ok(IsometrySummary::identity(total_constraints))
+    if total_constraints == 0 {
+        return Ok(IsometrySummary::identity(total_constraints));
+    }
+
+    // Variable `v` lives on fine node `v`; variables without a node do not
+    // constrain the partition. A generator is kept when its support lies in a
+    // single block.
+    let mut x_blocks: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    let mut z_blocks: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for var in 0..code.num_variables() {
+        let Some(block) = partition.block_index(NodeId::from_raw(var as u64)) else {
+            continue;
+        };
+        for &check in code.x_adjacency(var) {
+            x_blocks.entry(check).or_default().push(block);
+        }
+        for &check in code.z_adjacency(var) {
+            z_blocks.entry(check).or_default().push(block);
+        }
+    }
+    let lost_constraints = x_blocks
+        .values()
+        .chain(z_blocks.values())
+        .filter(|blocks| blocks.iter().any(|block| *block != blocks[0]))
+        .count();
+
+    Ok(IsometrySummary {
+        kept_fraction: (total_constraints - lost_constraints) as f64 / total_constraints as f64,
+        lost_constraints,
+        css_preserved: true,
+        certificate: None,
+    })
+}
+
+/// Dense GF(2) row over the code variables.
+type Row = Vec<u64>;
+
+fn to_row(constraint: &Constraint, words: usize) -> Row {
+    let mut row = vec![0u64; words];
+    for &var in constraint.variables() {
+        row[var / 64] ^= 1 << (var % 64);
+    }
+    row
+}
+
+fn xor_into(target: &mut [u64], source: &[u64]) {
+    for (lhs, rhs) in target.iter_mut().zip(source) {
+        *lhs ^= rhs;
+    }
+}
+
+fn lowest_bit(row: &[u64]) -> Option<usize> {
+    row.iter()
+        .enumerate()
+        .find(|(_, word)| **word != 0)
+        .map(|(idx, word)| idx * 64 + word.trailing_zeros() as usize)
+}
+
+/// Echelon basis keyed by pivot (lowest set bit), tracking which original
+/// rows combine into each basis vector.
+struct Gf2Basis {
+    rows: BTreeMap<usize, (Row, Row)>,
+    combo_words: usize,
+}
+
+impl Gf2Basis {
+    fn new(rows: &[Row]) -> Self {
+        let combo_words = rows.len().div_ceil(64).max(1);
+        let mut basis = Self {
+            rows: BTreeMap::new(),
+            combo_words,
+        };
+        for (idx, row) in rows.iter().enumerate() {
+            let mut combo = vec![0u64; combo_words];
+            combo[idx / 64] |= 1 << (idx % 64);
+            let (reduced, combo) = basis.reduce(row.clone(), combo);
+            if let Some(pivot) = lowest_bit(&reduced) {
+                basis.rows.insert(pivot, (reduced, combo));
+            }
+        }
+        basis
+    }
+
+    fn reduce(&self, mut row: Row, mut combo: Row) -> (Row, Row) {
+        while let Some(pivot) = lowest_bit(&row) {
+            let Some((basis_row, basis_combo)) = self.rows.get(&pivot) else {
+                break;
+            };
+            xor_into(&mut row, basis_row);
+            xor_into(&mut combo, basis_combo);
+        }
+        (row, combo)
+    }
+
+    fn rank(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Fine-row combination summing to `row`, or `None` outside the span.
+    fn express(&self, row: &[u64]) -> Option<Vec<(usize, u8)>> {
+        let (reduced, combo) = self.reduce(row.to_vec(), vec![0u64; self.combo_words]);
+        if lowest_bit(&reduced).is_some() {
+            return None;
+        }
+        let mut terms = Vec::new();
+        for (word_idx, word) in combo.iter().enumerate() {
+            for bit in 0..64 {
+                if word >> bit & 1 == 1 {
+                    terms.push((word_idx * 64 + bit, 1));
+                }
+            }
+        }
+        Some(terms)
+    }
+}
+
+fn certificate_error(message: impl Into<String>) -> AsmError {
+    AsmError::RG(ErrorInfo::new("certificate-shape", message.into()))
+}
+
+/// Applies `lifting` to the fine rows and checks every image equals its coarse row.
+fn lifting_holds(
+    fine: &[Row],
+    coarse: &[Row],
+    lifting: &[Vec<(usize, u8)>],
+) -> Result<bool, AsmError> {
+    if lifting.len() != coarse.len() {
+        return Err(certificate_error(format!(
+            "lifting covers {} coarse constraints but the coarse code has {}",
+            lifting.len(),
+            coarse.len()
+        )));
+    }
+    let words = coarse.first().or(fine.first()).map_or(0, Vec::len);
+    let mut holds = true;
+    for (terms, target) in lifting.iter().zip(coarse) {
+        let mut image = vec![0u64; words];
+        for &(fine_idx, coefficient) in terms {
+            let row = fine.get(fine_idx).ok_or_else(|| {
+                certificate_error(format!("fine constraint {fine_idx} out of range"))
+            })?;
+            match coefficient {
+                0 => {}
+                1 => xor_into(&mut image, row),
+                other => {
+                    return Err(certificate_error(format!(
+                        "coefficient {other} is not an element of GF(2)"
+                    )))
+                }
+            }
+        }
+        // An empty lifting only certifies an empty coarse constraint.
+        holds &= &image == target && (!terms.is_empty() || lowest_bit(target).is_none());
+    }
+    Ok(holds)
+}
+
+/// Fine and coarse rows of one sector.
+struct SectorRows {
+    fine: Vec<Row>,
+    coarse: Vec<Row>,
+}
+
+fn sector_rows(fine: &CSSCode, coarse: &CSSCode) -> Result<[SectorRows; 2], AsmError> {
+    if fine.num_variables() != coarse.num_variables() {
+        return Err(certificate_error(format!(
+            "fine code has {} variables but the coarse code has {}",
+            fine.num_variables(),
+            coarse.num_variables()
+        )));
+    }
+    let words = fine.num_variables().div_ceil(64);
+    let rows = |checks: &[Constraint]| -> Vec<Row> {
+        checks.iter().map(|check| to_row(check, words)).collect()
+    };
+    let (_, fine_x, fine_z, ..) = into_parts(fine);
+    let (_, coarse_x, coarse_z, ..) = into_parts(coarse);
+    Ok([
+        SectorRows {
+            fine: rows(&fine_x),
+            coarse: rows(&coarse_x),
+        },
+        SectorRows {
+            fine: rows(&fine_z),
+            coarse: rows(&coarse_z),
+        },
+    ])
+}
+
+fn sector_certificate(fine: &[Row], coarse: &[Row]) -> Result<(SectorCertificate, bool), AsmError> {
+    let fine_basis = Gf2Basis::new(fine);
+    let mut in_span = true;
+    let lifting: Vec<_> = coarse
+        .iter()
+        .map(|row| {
+            fine_basis.express(row).unwrap_or_else(|| {
+                in_span = false;
+                Vec::new()
+            })
+        })
+        .collect();
+    let verified = in_span && lifting_holds(fine, coarse, &lifting)?;
+    let certificate = SectorCertificate {
+        lifting,
+        rank_fine: fine_basis.rank(),
+        rank_coarse: Gf2Basis::new(coarse).rank(),
+    };
+    Ok((certificate, verified))
+}
+
+/// Builds the contraction certificate relating `coarse` to `fine`.
+///
+/// The verification flag is recomputed by applying the lifting map rather
+/// than trusted from the elimination that produced it.
+pub fn build_certificate(
+    fine: &CSSCode,
+    coarse: &CSSCode,
+) -> Result<ContractionCertificate, AsmError> {
+    let [x_rows, z_rows] = sector_rows(fine, coarse)?;
+    let (x, x_verified) = sector_certificate(&x_rows.fine, &x_rows.coarse)?;
+    let (z, z_verified) = sector_certificate(&z_rows.fine, &z_rows.coarse)?;
+    let information_loss_bound =
+        x.rank_fine.saturating_sub(x.rank_coarse) + z.rank_fine.saturating_sub(z.rank_coarse);
+    Ok(ContractionCertificate {
+        x,
+        z,
+        information_loss_bound,
+        verified: x_verified && z_verified,
+    })
+}
+
+/// Independently checks `cert` against the fine and coarse codes.
+///
+/// Returns `Ok(true)` when the certificate claims verification, its ranks and
+/// loss bound match a fresh computation, and its lifting map reproduces every
+/// coarse constraint. Structural mismatches (variable counts, constraint
+/// counts, out-of-range indices, non-GF(2) coefficients) are errors.
+pub fn verify_certificate(
+    fine_code: &CSSCode,
+    coarse_code: &CSSCode,
+    cert: &ContractionCertificate,
+) -> Result<bool, AsmError> {
+    let [x_rows, z_rows] = sector_rows(fine_code, coarse_code)?;
+    let mut valid = cert.verified;
+    let mut loss_bound = 0;
+    for (sector, rows) in [(&cert.x, &x_rows), (&cert.z, &z_rows)] {
+        let rank_fine = Gf2Basis::new(&rows.fine).rank();
+        let rank_coarse = Gf2Basis::new(&rows.coarse).rank();
+        valid &= sector.rank_fine == rank_fine && sector.rank_coarse == rank_coarse;
+        valid &= lifting_holds(&rows.fine, &rows.coarse, &sector.lifting)?;
+        loss_bound += rank_fine.saturating_sub(rank_coarse);
+    }
+    Ok(valid && cert.information_loss_bound == loss_bound)
 }
@@ -28,12 +28,18 @@ use serde::{Deserialize, Serialize};
 
 use block::partition_nodes;
 use contract::apply_contract;
-use graph_coarse::coarsen_graph;
+use graph_coarse::{clone_graph, coarsen_graph, unit_populations};
 use hash::{hash_run, hash_step};
 
-pub use covariance::{CovarianceDelta, CovarianceReport};
+pub use covariance::{
+    CovarianceDelta, CovarianceReport, SpectralCovarianceReport, SpectralObservable,
+};
 pub use dictionary::{CouplingIntervals, CouplingsReport, DictionaryProvenance};
-pub use params::{CovarianceThresholds, DictOpts, RGOpts};
+pub use graph_coarse::{population_weights, NodePopulations};
+pub use isometry::{verify_certificate, ContractionCertificate, SectorCertificate};
+pub use params::{
+    CovarianceThresholds, DictOpts, PartitionStrategy, RGOpts, SpectralCovarianceThresholds,
+};
 
 /// Borrowed reference to a code/graph pair used as RG input.
 #[derive(Debug, Clone, Copy)]
@@ -63,6 +69,9 @@ pub struct RGStepReport {
     pub symmetry_equivariant: bool,
     /// Human readable notes about the step.
     pub notes: String,
+    /// Contraction certificate, present when [`RGOpts::emit_certificate`] is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub certificate: Option<ContractionCertificate>,
     /// Canonical hash of the step metadata.
     pub step_hash: String,
 }
@@ -74,6 +83,8 @@ pub struct RGStep {
     pub graph: HypergraphImpl,
     /// Coarse grained CSS code.
     pub code: CSSCode,
+    /// Original fine nodes represented by each node of [`RGStep::graph`].
+    pub node_populations: NodePopulations,
     /// Structured metadata describing the transformation.
     pub report: RGStepReport,
 }
@@ -131,17 +142,32 @@ pub struct RGRun {
 
 /// Applies a single RG step to the provided state.
 pub fn rg_step(graph: &HypergraphImpl, code: &CSSCode, opts: &RGOpts) -> Result<RGStep, AsmError> {
+    rg_step_with_populations(graph, code, &unit_populations(graph), opts)
+}
+
+/// Applies a single RG step to a state whose nodes already represent
+/// `populations` fine nodes, e.g. the output of an earlier step.
+pub fn rg_step_with_populations(
+    graph: &HypergraphImpl,
+    code: &CSSCode,
+    populations: &NodePopulations,
+    opts: &RGOpts,
+) -> Result<RGStep, AsmError> {
     let partition = partition_nodes(graph, opts)?;
-    let contracted = apply_contract(code, &partition)?;
-    let coarse_graph = coarsen_graph(graph)?;
+    let contracted = apply_contract(code, &partition, opts)?;
+    let coarse_graph = coarsen_graph(graph, &partition, populations, opts)?;
 
     let graph_hash = asm_graph::canonical_hash(&coarse_graph.graph)?;
     let code_hash = asm_code::hash::canonical_code_hash(&contracted.code);
-    let notes = format!(
-        "blocks={} scale={}",
+    let mut notes = format!(
+        "blocks={} scale={} partition={}",
         partition.blocks().len(),
-        opts.scale_factor
+        opts.scale_factor,
+        opts.sanitised().partition_strategy
     );
+    if opts.merge_nodes {
+        notes.push_str(" merged");
+    }
 
     let mut report = RGStepReport {
         graph_hash,
@@ -152,6 +178,7 @@ pub fn rg_step(graph: &HypergraphImpl, code: &CSSCode, opts: &RGOpts) -> Result<
         css_preserved: contracted.summary.css_preserved,
         symmetry_equivariant: true,
         notes,
+        certificate: contracted.summary.certificate.clone(),
         step_hash: String::new(),
     };
     report.step_hash = hash_step(&report)?;
@@ -159,6 +186,7 @@ pub fn rg_step(graph: &HypergraphImpl, code: &CSSCode, opts: &RGOpts) -> Result<
     Ok(RGStep {
         graph: coarse_graph.graph,
         code: contracted.code,
+        node_populations: coarse_graph.node_populations,
         report,
     })
 }
@@ -171,10 +199,11 @@ pub fn rg_run(input: &StateRef, steps: usize, opts: &RGOpts) -> Result<RGRun, As
     let initial_graph_hash = asm_graph::canonical_hash(&current_graph)?;
     let initial_code_hash = asm_code::hash::canonical_code_hash(&current_code);
 
+    let mut populations = unit_populations(&current_graph);
     let mut run_steps = Vec::new();
     let mut entries = Vec::new();
     for index in 0..steps {
-        let step = rg_step(&current_graph, &current_code, opts)?;
+        let step = rg_step_with_populations(&current_graph, &current_code, &populations, opts)?;
         let next_graph = clone_graph(&step.graph)?;
         let next_code = clone_code(&step.code);
         entries.push(RGRunEntry {
@@ -191,6 +220,7 @@ pub fn rg_run(input: &StateRef, steps: usize, opts: &RGOpts) -> Result<RGRun, As
         });
         current_graph = next_graph;
         current_code = next_code;
+        populations = step.node_populations.clone();
         run_steps.push(step);
     }
 
@@ -213,11 +243,6 @@ pub fn rg_run(input: &StateRef, steps: usize, opts: &RGOpts) -> Result<RGRun, As
     })
 }
 
-/// Clones a hypergraph using the deterministic serializer.
-fn clone_graph(graph: &HypergraphImpl) -> Result<HypergraphImpl, AsmError> {
-    Ok(coarsen_graph(graph)?.graph)
-}
-
 /// Clones a CSS code using canonical parts.
 fn clone_code(code: &CSSCode) -> CSSCode {
     let (num_variables, x_checks, z_checks, schema, provenance, rank_x, rank_z) =
@@ -1,5 +1,53 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
+/// Strategy used to partition fine nodes into RG blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PartitionStrategy {
+    /// Seeded hash ordering of node identifiers, chunked into blocks of
+    /// [`RGOpts::max_block_size`].
+    #[default]
+    Canonical,
+    /// Recursive bisection at the median of the Fiedler vector of the
+    /// clique-expanded graph Laplacian, yielding up to `2^levels` blocks.
+    SpectralBisection {
+        /// Number of bisection rounds.
+        levels: u32,
+    },
+    /// Greedy breadth-first blocks grown from the lowest unassigned node.
+    #[serde(rename = "bfs_blocks")]
+    BFSBlocks {
+        /// Maximum number of nodes per block.
+        block_size: usize,
+    },
+}
+
+impl PartitionStrategy {
+    fn sanitised(self) -> Self {
+        match self {
+            Self::Canonical => Self::Canonical,
+            Self::SpectralBisection { levels } => Self::SpectralBisection {
+                levels: levels.max(1),
+            },
+            Self::BFSBlocks { block_size } => Self::BFSBlocks {
+                block_size: block_size.max(1),
+            },
+        }
+    }
+}
+
+impl fmt::Display for PartitionStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Canonical => write!(f, "canonical"),
+            Self::SpectralBisection { levels } => write!(f, "spectral-bisection(levels={levels})"),
+            Self::BFSBlocks { block_size } => write!(f, "bfs-blocks(block_size={block_size})"),
+        }
+    }
+}
+
 /// Options controlling RG coarse graining.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RGOpts {
@@ -9,6 +57,17 @@ pub struct RGOpts {
     pub max_block_size: usize,
     /// Deterministic seed influencing block ordering.
     pub seed: u64,
+    /// Strategy used to form blocks.
+    #[serde(default)]
+    pub partition_strategy: PartitionStrategy,
+    /// Attach a contraction certificate to every step report. Certificates
+    /// store a lifting map per coarse constraint, so they are opt-in.
+    #[serde(default)]
+    pub emit_certificate: bool,
+    /// Collapse every block into a single coarse node. By default the RG map
+    /// acts on the code only and the graph topology is kept.
+    #[serde(default)]
+    pub merge_nodes: bool,
 }
 
 impl Default for RGOpts {
@@ -17,6 +76,9 @@ impl Default for RGOpts {
             scale_factor: 2,
             max_block_size: 2,
             seed: 0xC0FFEE_u64,
+            partition_strategy: PartitionStrategy::Canonical,
+            emit_certificate: false,
+            merge_nodes: false,
         }
     }
 }
@@ -30,6 +92,9 @@ impl RGOpts {
             scale_factor,
             max_block_size,
             seed: self.seed,
+            partition_strategy: self.partition_strategy.sanitised(),
+            emit_certificate: self.emit_certificate,
+            merge_nodes: self.merge_nodes,
         }
     }
 }
@@ -91,3 +156,24 @@ impl Default for CovarianceThresholds {
         }
     }
 }
+
+/// Relative tolerances applied when comparing fine and rescaled coarse spectra.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpectralCovarianceThresholds {
+    /// Relative tolerance applied to the dispersion velocity `c_est`.
+    pub c_relative: f64,
+    /// Relative tolerance applied to the dispersion gap proxy.
+    pub gap_relative: f64,
+    /// Relative tolerance applied to the correlation length `xi`.
+    pub xi_relative: f64,
+}
+
+impl Default for SpectralCovarianceThresholds {
+    fn default() -> Self {
+        Self {
+            c_relative: 0.1,
+            gap_relative: 0.1,
+            xi_relative: 0.1,
+        }
+    }
+}
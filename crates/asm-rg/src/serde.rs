@@ -1,6 +1,6 @@
 use asm_core::errors::{AsmError, ErrorInfo};
 
-use crate::covariance::CovarianceReport;
+use crate::covariance::{CovarianceReport, SpectralCovarianceReport};
 use crate::dictionary::CouplingsReport;
 use crate::{RGRunReport, RGStepReport};
 
@@ -47,3 +47,14 @@ pub fn covariance_to_json(report: &CovarianceReport) -> Result<String, AsmError>
 pub fn covariance_from_json(json: &str) -> Result<CovarianceReport, AsmError> {
     serde_json::from_str(json).map_err(|err| map_err(err, "rg-covariance-deserialize"))
 }
+
+/// Serialises a spectral covariance report to JSON.
+pub fn spectral_covariance_to_json(report: &SpectralCovarianceReport) -> Result<String, AsmError> {
+    serde_json::to_string_pretty(report)
+        .map_err(|err| map_err(err, "rg-spectral-covariance-serialize"))
+}
+
+/// Restores a spectral covariance report from JSON.
+pub fn spectral_covariance_from_json(json: &str) -> Result<SpectralCovarianceReport, AsmError> {
+    serde_json::from_str(json).map_err(|err| map_err(err, "rg-spectral-covariance-deserialize"))
+}
@@ -0,0 +1,123 @@
+use asm_core::{Hypergraph, RunProvenance, SchemaVersion};
+use asm_graph::{HypergraphConfig, HypergraphImpl, KUniformity};
+use asm_rg::isometry::build_certificate;
+use asm_rg::{rg_step, verify_certificate, RGOpts};
+
+fn build_graph() -> HypergraphImpl {
+    let config = HypergraphConfig {
+        causal_mode: false,
+        max_in_degree: None,
+        max_out_degree: None,
+        k_uniform: Some(KUniformity::Total {
+            total: 2,
+            min_sources: 1,
+        }),
+        schema_version: SchemaVersion::new(2, 0, 0),
+        ..HypergraphConfig::default()
+    };
+    let mut graph = HypergraphImpl::new(config);
+    let nodes: Vec<_> = (0..4).map(|_| graph.add_node().unwrap()).collect();
+    for pair in nodes.windows(2) {
+        graph.add_hyperedge(&[pair[0]], &[pair[1]]).unwrap();
+    }
+    graph
+}
+
+fn code(x_checks: Vec<Vec<usize>>, z_checks: Vec<Vec<usize>>) -> asm_code::CSSCode {
+    asm_code::CSSCode::new(
+        4,
+        x_checks,
+        z_checks,
+        SchemaVersion::new(1, 0, 0),
+        RunProvenance::default(),
+    )
+    .unwrap()
+}
+
+/// X checks are linearly dependent, so the fine X rank is below the count.
+fn redundant_code() -> asm_code::CSSCode {
+    code(
+        vec![vec![0, 1], vec![1, 2], vec![0, 2]],
+        vec![vec![0, 1, 2, 3]],
+    )
+}
+
+fn certified_opts() -> RGOpts {
+    RGOpts {
+        emit_certificate: true,
+        ..RGOpts::default()
+    }
+}
+
+#[test]
+fn certificate_is_opt_in_and_hashed() {
+    let graph = build_graph();
+    let code = redundant_code();
+    let plain = rg_step(&graph, &code, &RGOpts::default()).unwrap();
+    assert!(plain.report.certificate.is_none());
+    let json = asm_rg::serde_io::step_to_json(&plain.report).unwrap();
+    assert!(!json.contains("certificate"));
+
+    let certified = rg_step(&graph, &code, &certified_opts()).unwrap();
+    let cert = certified.report.certificate.as_ref().expect("certificate");
+    assert!(cert.verified);
+    assert_ne!(certified.report.step_hash, plain.report.step_hash);
+
+    let again = rg_step(&graph, &code, &certified_opts()).unwrap();
+    assert_eq!(certified.report, again.report);
+    let json = asm_rg::serde_io::step_to_json(&certified.report).unwrap();
+    let decoded = asm_rg::serde_io::step_from_json(&json).unwrap();
+    assert_eq!(decoded, certified.report);
+}
+
+#[test]
+fn certificate_records_lifting_and_ranks() {
+    let graph = build_graph();
+    let code = redundant_code();
+    let step = rg_step(&graph, &code, &certified_opts()).unwrap();
+    let cert = step.report.certificate.clone().unwrap();
+
+    assert_eq!(cert.x.lifting.len(), code.num_constraints_x());
+    assert_eq!(cert.z.lifting.len(), code.num_constraints_z());
+    assert_eq!((cert.x.rank_fine, cert.x.rank_coarse), (2, 2));
+    assert_eq!((cert.z.rank_fine, cert.z.rank_coarse), (1, 1));
+    assert_eq!(cert.information_loss_bound, 0);
+    for terms in cert.x.lifting.iter().chain(&cert.z.lifting) {
+        assert!(!terms.is_empty());
+        assert!(terms.iter().all(|&(_, coefficient)| coefficient == 1));
+        assert!(terms.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+    assert!(verify_certificate(&code, &step.code, &cert).unwrap());
+}
+
+#[test]
+fn tampered_certificates_fail_verification() {
+    let graph = build_graph();
+    let code = redundant_code();
+    let step = rg_step(&graph, &code, &certified_opts()).unwrap();
+    let cert = step.report.certificate.clone().unwrap();
+
+    let mut wrong_lift = cert.clone();
+    wrong_lift.x.lifting[0] = vec![(1, 1)];
+    assert_ne!(wrong_lift.x.lifting[0], cert.x.lifting[0]);
+    assert!(!verify_certificate(&code, &step.code, &wrong_lift).unwrap());
+
+    let mut wrong_rank = cert.clone();
+    wrong_rank.x.rank_fine += 1;
+    assert!(!verify_certificate(&code, &step.code, &wrong_rank).unwrap());
+
+    let mut out_of_range = cert.clone();
+    out_of_range.z.lifting[0] = vec![(7, 1)];
+    let err = verify_certificate(&code, &step.code, &out_of_range).unwrap_err();
+    assert_eq!(err.info().code, "certificate-shape");
+}
+
+#[test]
+fn constraints_outside_the_fine_span_are_not_verified() {
+    let fine = redundant_code();
+    let coarse = code(vec![vec![0, 1], vec![2, 3]], vec![vec![0, 1, 2, 3]]);
+    let cert = build_certificate(&fine, &coarse).unwrap();
+    assert!(!cert.verified);
+    assert!(cert.x.lifting.iter().any(Vec::is_empty));
+    assert!(!verify_certificate(&fine, &coarse, &cert).unwrap());
+}
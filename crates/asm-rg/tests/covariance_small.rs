@@ -12,6 +12,7 @@ fn build_graph() -> HypergraphImpl {
             min_sources: 1,
         }),
         schema_version: SchemaVersion::new(2, 0, 0),
+        ..HypergraphConfig::default()
     };
     let mut graph = HypergraphImpl::new(config);
     let a = graph.add_node().unwrap();
@@ -0,0 +1,168 @@
+use asm_code::CSSCode;
+use asm_core::rng::RngHandle;
+use asm_core::{Hypergraph, RunProvenance, SchemaVersion};
+use asm_graph::{gen_bounded_degree, HypergraphConfig, HypergraphImpl, KUniformity};
+use asm_rg::{population_weights, rg_run, rg_step, PartitionStrategy, RGOpts, StateRef};
+use asm_spec::{build_operators, OpOpts};
+use nalgebra::{DMatrix, SymmetricEigen};
+use proptest::prelude::*;
+
+fn chain(len: usize) -> HypergraphImpl {
+    let config = HypergraphConfig {
+        causal_mode: false,
+        max_in_degree: None,
+        max_out_degree: None,
+        k_uniform: Some(KUniformity::Total {
+            total: 2,
+            min_sources: 1,
+        }),
+        schema_version: SchemaVersion::new(2, 0, 0),
+        ..HypergraphConfig::default()
+    };
+    let mut graph = HypergraphImpl::new(config);
+    let nodes: Vec<_> = (0..len).map(|_| graph.add_node().unwrap()).collect();
+    for pair in nodes.windows(2) {
+        graph.add_hyperedge(&[pair[0]], &[pair[1]]).unwrap();
+    }
+    graph
+}
+
+fn code() -> CSSCode {
+    CSSCode::new(
+        4,
+        vec![vec![0, 1], vec![2, 3]],
+        vec![vec![0, 1], vec![2, 3]],
+        SchemaVersion::new(1, 0, 0),
+        RunProvenance::default(),
+    )
+    .unwrap()
+}
+
+fn paired_opts() -> RGOpts {
+    RGOpts {
+        partition_strategy: PartitionStrategy::BFSBlocks { block_size: 2 },
+        merge_nodes: true,
+        ..RGOpts::default()
+    }
+}
+
+/// Smallest non-zero eigenvalue of the Laplacian of the symmetrised operator.
+fn laplacian_gap(graph: &HypergraphImpl, opts: &OpOpts) -> f64 {
+    let ops = build_operators(graph, &code(), opts).unwrap();
+    let n = ops.info.num_nodes;
+    let mut laplacian = DMatrix::<f64>::zeros(n, n);
+    for entry in &ops.entries {
+        for (a, b) in [(entry.row, entry.col), (entry.col, entry.row)] {
+            laplacian[(a, b)] -= entry.weight;
+            laplacian[(a, a)] += entry.weight;
+        }
+    }
+    let mut eigenvalues: Vec<f64> = SymmetricEigen::new(laplacian)
+        .eigenvalues
+        .iter()
+        .copied()
+        .collect();
+    eigenvalues.sort_by(f64::total_cmp);
+    eigenvalues[1]
+}
+
+#[test]
+fn merged_step_records_block_populations() {
+    let graph = chain(16);
+    let step = rg_step(&graph, &code(), &paired_opts()).unwrap();
+    assert_eq!(step.graph.nodes().len(), 8);
+    assert_eq!(step.graph.edges().len(), 7);
+    assert_eq!(step.node_populations.len(), 8);
+    assert!(step.node_populations.values().all(|&count| count == 2));
+    assert!(step.report.notes.ends_with(" merged"));
+
+    let kept = rg_step(&graph, &code(), &RGOpts::default()).unwrap();
+    assert_eq!(kept.graph.nodes().len(), 16);
+    assert!(kept.node_populations.values().all(|&count| count == 1));
+    assert_eq!(
+        kept.report.graph_hash,
+        asm_graph::canonical_hash(&graph).unwrap()
+    );
+}
+
+#[test]
+fn populations_compose_across_run_steps() {
+    let graph = chain(16);
+    let state = StateRef {
+        graph: &graph,
+        code: &code(),
+    };
+    let run = rg_run(&state, 3, &paired_opts()).unwrap();
+    for (index, step) in run.steps.iter().enumerate() {
+        let expected = 2usize.pow(index as u32 + 1);
+        assert_eq!(step.graph.nodes().len(), 16 / expected);
+        assert!(step
+            .node_populations
+            .values()
+            .all(|&count| count == expected));
+    }
+}
+
+#[test]
+fn population_weights_bring_coarse_gap_closer_to_fine() {
+    let fine = chain(16);
+    let step = rg_step(&fine, &code(), &paired_opts()).unwrap();
+    let fine_gap = laplacian_gap(&fine, &OpOpts::default());
+    let plain_gap = laplacian_gap(&step.graph, &OpOpts::default());
+    let weighted = OpOpts {
+        node_weights: population_weights(&step.node_populations),
+        ..OpOpts::default()
+    };
+    let weighted_gap = laplacian_gap(&step.graph, &weighted);
+
+    assert!(plain_gap > fine_gap);
+    assert!((weighted_gap - fine_gap).abs() < 0.5 * (plain_gap - fine_gap).abs());
+}
+
+#[test]
+fn invalid_node_weights_are_rejected() {
+    let graph = chain(4);
+    for (node, weight, expected) in [
+        (0, 0.0, "invalid-node-weight"),
+        (1, f64::NAN, "invalid-node-weight"),
+        (9, 1.0, "unknown-node"),
+    ] {
+        let opts = OpOpts {
+            node_weights: [(node, weight)].into_iter().collect(),
+            ..OpOpts::default()
+        };
+        let err = build_operators(&graph, &code(), &opts).unwrap_err();
+        assert_eq!(err.info().code, expected);
+    }
+}
+
+proptest! {
+    #[test]
+    fn populations_sum_to_fine_node_count(
+        seed in any::<u64>(),
+        nodes in 3usize..24,
+        block in 1usize..4,
+        steps in 1usize..4,
+    ) {
+        let mut rng = RngHandle::from_seed(seed);
+        let graph = gen_bounded_degree(nodes, 2, 4, &mut rng).unwrap();
+        let state = StateRef {
+            graph: &graph,
+            code: &code(),
+        };
+        let opts = RGOpts {
+            max_block_size: block,
+            seed,
+            merge_nodes: true,
+            ..RGOpts::default()
+        };
+        let run = rg_run(&state, steps, &opts).unwrap();
+        for (index, step) in run.steps.iter().enumerate() {
+            let total: usize = step.node_populations.values().sum();
+            prop_assert_eq!(total, nodes);
+            prop_assert_eq!(step.node_populations.len(), step.graph.nodes().len());
+            let bound = block.pow(index as u32 + 1);
+            prop_assert!(step.node_populations.values().all(|&count| count <= bound));
+        }
+    }
+}
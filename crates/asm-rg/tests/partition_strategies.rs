@@ -0,0 +1,186 @@
+use std::collections::BTreeSet;
+
+use asm_core::{Hypergraph, NodeId, RunProvenance, SchemaVersion};
+use asm_graph::{HypergraphConfig, HypergraphImpl, KUniformity};
+use asm_rg::block::partition_nodes;
+use asm_rg::{rg_step, PartitionStrategy, RGOpts};
+
+const COMMUNITY: usize = 8;
+
+/// Two complete communities of eight nodes joined by a single bridge edge.
+fn two_community_graph() -> HypergraphImpl {
+    let config = HypergraphConfig {
+        causal_mode: false,
+        max_in_degree: None,
+        max_out_degree: None,
+        k_uniform: Some(KUniformity::Total {
+            total: 2,
+            min_sources: 1,
+        }),
+        schema_version: SchemaVersion::new(2, 0, 0),
+        ..HypergraphConfig::default()
+    };
+    let mut graph = HypergraphImpl::new(config);
+    let nodes: Vec<NodeId> = (0..2 * COMMUNITY)
+        .map(|_| graph.add_node().unwrap())
+        .collect();
+    for community in nodes.chunks(COMMUNITY) {
+        for (i, a) in community.iter().enumerate() {
+            for b in &community[i + 1..] {
+                graph.add_hyperedge(&[*a], &[*b]).unwrap();
+            }
+        }
+    }
+    graph
+        .add_hyperedge(&[nodes[COMMUNITY - 1]], &[nodes[COMMUNITY]])
+        .unwrap();
+    graph
+}
+
+/// Code whose generators are supported on four-variable windows that never
+/// cross the community boundary.
+fn community_code() -> asm_code::CSSCode {
+    let checks: Vec<Vec<usize>> = (0..2 * COMMUNITY)
+        .collect::<Vec<_>>()
+        .chunks(4)
+        .map(<[usize]>::to_vec)
+        .collect();
+    asm_code::CSSCode::new(
+        2 * COMMUNITY,
+        checks.clone(),
+        checks,
+        SchemaVersion::new(1, 0, 0),
+        RunProvenance::default(),
+    )
+    .unwrap()
+}
+
+fn opts(partition_strategy: PartitionStrategy) -> RGOpts {
+    RGOpts {
+        max_block_size: COMMUNITY,
+        partition_strategy,
+        ..RGOpts::default()
+    }
+}
+
+fn assert_cover(graph: &HypergraphImpl, strategy: PartitionStrategy) {
+    let partition = partition_nodes(graph, &opts(strategy)).unwrap();
+    let mut seen = BTreeSet::new();
+    for (idx, block) in partition.blocks().iter().enumerate() {
+        assert!(!block.is_empty());
+        for node in block {
+            assert!(seen.insert(*node), "node {node:?} assigned twice");
+            assert_eq!(partition.block_index(*node), Some(idx));
+        }
+    }
+    assert_eq!(seen, graph.nodes().collect::<BTreeSet<_>>());
+}
+
+#[test]
+fn spectral_bisection_splits_communities() {
+    let graph = two_community_graph();
+    let partition = partition_nodes(
+        &graph,
+        &opts(PartitionStrategy::SpectralBisection { levels: 1 }),
+    )
+    .unwrap();
+    let expected: Vec<Vec<NodeId>> = vec![
+        (0..COMMUNITY as u64).map(NodeId::from_raw).collect(),
+        (COMMUNITY as u64..2 * COMMUNITY as u64)
+            .map(NodeId::from_raw)
+            .collect(),
+    ];
+    assert_eq!(partition.blocks(), expected.as_slice());
+}
+
+#[test]
+fn spectral_keeps_more_constraints_than_canonical() {
+    let graph = two_community_graph();
+    let code = community_code();
+
+    let canonical = rg_step(&graph, &code, &opts(PartitionStrategy::Canonical)).unwrap();
+    let spectral = rg_step(
+        &graph,
+        &code,
+        &opts(PartitionStrategy::SpectralBisection { levels: 1 }),
+    )
+    .unwrap();
+
+    assert!((spectral.report.kept_fraction - 1.0).abs() < 1e-12);
+    assert_eq!(spectral.report.lost_constraints, 0);
+    assert!(spectral.report.kept_fraction > canonical.report.kept_fraction);
+    assert!(canonical.report.lost_constraints > 0);
+}
+
+#[test]
+fn strategies_produce_complete_disjoint_covers() {
+    let graph = two_community_graph();
+    for strategy in [
+        PartitionStrategy::Canonical,
+        PartitionStrategy::SpectralBisection { levels: 0 },
+        PartitionStrategy::SpectralBisection { levels: 3 },
+        PartitionStrategy::BFSBlocks { block_size: 3 },
+        PartitionStrategy::BFSBlocks { block_size: 0 },
+    ] {
+        assert_cover(&graph, strategy);
+    }
+}
+
+#[test]
+fn bfs_blocks_grow_from_lowest_node() {
+    let graph = two_community_graph();
+    let partition = partition_nodes(
+        &graph,
+        &opts(PartitionStrategy::BFSBlocks { block_size: 4 }),
+    )
+    .unwrap();
+    assert_eq!(partition.blocks().len(), 4);
+    for (idx, block) in partition.blocks().iter().enumerate() {
+        let first = 4 * idx as u64;
+        let expected: Vec<NodeId> = (first..first + 4).map(NodeId::from_raw).collect();
+        assert_eq!(block, &expected);
+    }
+}
+
+#[test]
+fn strategy_is_recorded_and_deterministic() {
+    let graph = two_community_graph();
+    let code = community_code();
+    let strategy = PartitionStrategy::SpectralBisection { levels: 2 };
+
+    let first = rg_step(&graph, &code, &opts(strategy)).unwrap();
+    let second = rg_step(&graph, &code, &opts(strategy)).unwrap();
+    assert_eq!(first.report, second.report);
+    assert!(first
+        .report
+        .notes
+        .contains("partition=spectral-bisection(levels=2)"));
+
+    let bfs = rg_step(
+        &graph,
+        &code,
+        &opts(PartitionStrategy::BFSBlocks { block_size: 4 }),
+    )
+    .unwrap();
+    assert!(bfs
+        .report
+        .notes
+        .contains("partition=bfs-blocks(block_size=4)"));
+    assert_ne!(first.report.step_hash, bfs.report.step_hash);
+}
+
+#[test]
+fn partition_strategy_defaults_when_absent() {
+    let json = r#"{"scale_factor":2,"max_block_size":2,"seed":7}"#;
+    let opts: RGOpts = serde_json::from_str(json).unwrap();
+    assert_eq!(opts.partition_strategy, PartitionStrategy::Canonical);
+
+    let spectral = RGOpts {
+        partition_strategy: PartitionStrategy::SpectralBisection { levels: 2 },
+        ..opts
+    };
+    let encoded = serde_json::to_string(&spectral).unwrap();
+    assert!(encoded.contains(r#""partition_strategy":{"spectral_bisection":{"levels":2}}"#));
+    let decoded: RGOpts = serde_json::from_str(&encoded).unwrap();
+    assert_eq!(decoded, spectral);
+}
@@ -0,0 +1,171 @@
+use asm_code::CSSCode;
+use asm_core::{Hypergraph, RunProvenance, SchemaVersion};
+use asm_graph::{HypergraphConfig, HypergraphImpl, KUniformity};
+use asm_rg::covariance::{spectral_covariance, spectral_covariance_with};
+use asm_rg::{rg_step, serde_io, RGOpts, SpectralCovarianceThresholds, StateRef};
+use asm_spec::{
+    analyze_spectrum, CorrelSpec, DispersionSpec, ExcitationSpec, OpOpts, PropOpts, SpecOpts,
+};
+
+fn chain(len: usize) -> HypergraphImpl {
+    let config = HypergraphConfig {
+        causal_mode: false,
+        max_in_degree: None,
+        max_out_degree: None,
+        k_uniform: Some(KUniformity::Total {
+            total: 2,
+            min_sources: 1,
+        }),
+        schema_version: SchemaVersion::new(2, 0, 0),
+        ..HypergraphConfig::default()
+    };
+    let mut graph = HypergraphImpl::new(config);
+    let nodes: Vec<_> = (0..len).map(|_| graph.add_node().unwrap()).collect();
+    for pair in nodes.windows(2) {
+        graph.add_hyperedge(&[pair[0]], &[pair[1]]).unwrap();
+    }
+    graph
+}
+
+fn code() -> CSSCode {
+    CSSCode::new(
+        4,
+        vec![vec![0, 1], vec![2, 3]],
+        vec![vec![0, 1], vec![2, 3]],
+        SchemaVersion::new(1, 0, 0),
+        RunProvenance::default(),
+    )
+    .unwrap()
+}
+
+fn spec_opts() -> SpecOpts {
+    let mut dispersion = DispersionSpec::default();
+    dispersion.k_points = 7;
+    dispersion.modes = 2;
+    SpecOpts {
+        ops: OpOpts::default(),
+        excitation: ExcitationSpec::default(),
+        propagation: PropOpts {
+            iterations: 16,
+            tolerance: 1e-6,
+            seed: 31,
+            emit_front_fit: false,
+            front_threshold: None,
+        },
+        dispersion,
+        correlation: CorrelSpec::default(),
+        master_seed: 4242,
+        fit_tolerance: 1e-6,
+    }
+}
+
+#[test]
+fn chain_coarse_observables_rescale_by_scale_factor() {
+    let graph = chain(16);
+    let code = code();
+    let state = StateRef {
+        graph: &graph,
+        code: &code,
+    };
+    let rg_opts = RGOpts {
+        scale_factor: 4,
+        ..RGOpts::default()
+    };
+    let report = spectral_covariance(&state, &rg_opts, &spec_opts()).unwrap();
+    assert_eq!(report.scale_factor, 4);
+
+    // The grid is (i + 1) / (k_points + 1) on both lattices; a coarse momentum
+    // maps to a quarter of the same fine momentum.
+    let expected: Vec<f64> = (1..=7).map(|i| i as f64 / 32.0).collect();
+    assert_eq!(report.k_grid_coarse.len(), 7);
+    assert_eq!(report.k_grid_rescaled, expected);
+    for (fine, coarse) in report.k_grid_fine.iter().zip(&report.k_grid_coarse) {
+        assert_eq!(fine, coarse);
+    }
+
+    let names: Vec<_> = report.observables.iter().map(|o| o.name.as_str()).collect();
+    assert_eq!(names, ["c_est", "gap_proxy", "xi"]);
+    let [c, gap, xi] = &report.observables[..] else {
+        unreachable!()
+    };
+    assert_eq!(c.rescaled, 4.0 * c.coarse);
+    assert_eq!(gap.rescaled, gap.coarse);
+    assert_eq!(xi.rescaled, 4.0 * xi.coarse);
+    for observable in &report.observables {
+        let drift = (observable.rescaled - observable.fine).abs() / observable.fine.abs().max(1e-9);
+        assert_eq!(observable.relative_drift, drift);
+        assert_eq!(observable.pass, drift <= observable.threshold);
+    }
+    assert_eq!(
+        report.pass,
+        report.observables.iter().all(|observable| observable.pass)
+    );
+}
+
+#[test]
+fn spectral_covariance_records_underlying_hashes() {
+    let graph = chain(12);
+    let code = code();
+    let state = StateRef {
+        graph: &graph,
+        code: &code,
+    };
+    let rg_opts = RGOpts::default();
+    let spec = spec_opts();
+    let report = spectral_covariance(&state, &rg_opts, &spec).unwrap();
+
+    let fine = analyze_spectrum(&graph, &code, &spec).unwrap();
+    let step = rg_step(&graph, &code, &rg_opts).unwrap();
+    let coarse = analyze_spectrum(&step.graph, &step.code, &spec).unwrap();
+    assert_eq!(report.fine_spectrum_hash, fine.analysis_hash);
+    assert_eq!(report.rg_step_hash, step.report.step_hash);
+    assert_eq!(report.coarse_spectrum_hash, coarse.analysis_hash);
+
+    let again = spectral_covariance(&state, &rg_opts, &spec).unwrap();
+    assert_eq!(report, again);
+    let json = serde_io::spectral_covariance_to_json(&report).unwrap();
+    assert_eq!(
+        serde_io::spectral_covariance_from_json(&json).unwrap(),
+        report
+    );
+}
+
+#[test]
+fn thresholds_gate_the_pass_flags() {
+    let graph = chain(12);
+    let code = code();
+    let state = StateRef {
+        graph: &graph,
+        code: &code,
+    };
+    let strict = SpectralCovarianceThresholds {
+        c_relative: 0.0,
+        gap_relative: 0.0,
+        xi_relative: 0.0,
+    };
+    let loose = SpectralCovarianceThresholds {
+        c_relative: f64::MAX,
+        gap_relative: f64::MAX,
+        xi_relative: f64::MAX,
+    };
+    let rg_opts = RGOpts::default();
+    let strict_report = spectral_covariance_with(&state, &rg_opts, &spec_opts(), &strict).unwrap();
+    let loose_report = spectral_covariance_with(&state, &rg_opts, &spec_opts(), &loose).unwrap();
+    assert!(loose_report.pass);
+    assert_eq!(
+        strict_report.observables.len(),
+        loose_report.observables.len()
+    );
+    for (strict, loose) in strict_report
+        .observables
+        .iter()
+        .zip(&loose_report.observables)
+    {
+        assert_eq!(strict.relative_drift, loose.relative_drift);
+        assert_eq!(strict.pass, strict.relative_drift == 0.0);
+    }
+    assert_ne!(
+        strict_report.spectral_covariance_hash,
+        loose_report.spectral_covariance_hash
+    );
+}
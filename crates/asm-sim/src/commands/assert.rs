@@ -9,7 +9,7 @@ use asm_int::serde::from_json_slice as int_from_slice;
 use asm_land::report::SummaryReport;
 use asm_land::serde::from_json_slice as land_from_slice;
 use asm_spec::{from_json_slice as spec_from_slice, SpectrumReport};
-use asm_thy::{run_assertions, serde::to_canonical_json_bytes, AssertionInputs, Policy};
+use asm_thy::{run_assertions, serde::to_canonical_json_bytes, AssertionInputs, Policy, Severity};
 use clap::Args;
 
 #[derive(Args, Debug)]
@@ -83,5 +83,18 @@ pub fn run(args: &AssertArgs) -> Result<(), Box<dyn Error>> {
         args.out.join("assert_report.json"),
         to_canonical_json_bytes(&report)?,
     )?;
+    if !report.overall_pass {
+        let failed: Vec<&str> = report
+            .failures(Severity::Error)
+            .map(|check| check.name.as_str())
+            .collect();
+        return Err(format!("error-level assertions failed: {}", failed.join(", ")).into());
+    }
+    if !report.warnings.is_empty() {
+        println!(
+            "assertions passed with warnings: {}",
+            report.warnings.join(", ")
+        );
+    }
     Ok(())
 }
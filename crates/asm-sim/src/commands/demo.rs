@@ -59,6 +59,8 @@ fn build_demo_report(args: &DemoArgs) -> Result<DemoReport, Box<dyn Error>> {
             method: GapMethod::Dispersion,
             thresholds: threshold_meta.clone(),
             tolerance: 0.03,
+            uncertainty: None,
+            size_series: Vec::new(),
         },
     )
     .map_err(|err| Box::new(err) as Box<dyn Error>)?;
@@ -68,6 +70,8 @@ fn build_demo_report(args: &DemoArgs) -> Result<DemoReport, Box<dyn Error>> {
             method: GapMethod::Spectral,
             thresholds: threshold_meta,
             tolerance: 0.0,
+            uncertainty: None,
+            size_series: Vec::new(),
         },
     )
     .map_err(|err| Box::new(err) as Box<dyn Error>)?;
@@ -1,7 +1,9 @@
 use std::error::Error;
 use std::path::{Path, PathBuf};
 
-use asm_exp::to_canonical_json_bytes;
+use asm_core::errors::{AsmError, ErrorCode};
+use asm_core::hash::{ensure_hash_domain, HashDomain};
+use asm_exp::{determinism_selftest, to_canonical_json_bytes, SelfTestLevel, SelfTestReport};
 use clap::Args;
 use serde::Serialize;
 
@@ -13,6 +15,20 @@ pub struct DoctorArgs {
     /// Emit only JSON without additional context.
     #[arg(long)]
     pub quiet: bool,
+    /// Determinism self-test workloads to run: `quick` or `full`.
+    #[arg(long, default_value = "quick", value_parser = parse_selftest_level)]
+    pub selftest: SelfTestLevel,
+    /// Write the self-test report as canonical JSON, e.g. to attach to a submission.
+    #[arg(long)]
+    pub certificate: Option<PathBuf>,
+}
+
+fn parse_selftest_level(value: &str) -> Result<SelfTestLevel, String> {
+    match value {
+        "quick" => Ok(SelfTestLevel::Quick),
+        "full" => Ok(SelfTestLevel::Full),
+        other => Err(format!("expected quick or full, got '{other}'")),
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -26,16 +42,26 @@ struct DoctorCheck {
 struct DoctorReport {
     status: String,
     checks: Vec<DoctorCheck>,
+    selftest: SelfTestReport,
 }
 
 pub fn run(args: &DoctorArgs) -> Result<(), Box<dyn Error>> {
-    let report = diagnose(&args.root)?;
+    let report = diagnose(&args.root, args.selftest)?;
+    if let Some(path) = &args.certificate {
+        let certificate = to_canonical_json_bytes(&report.selftest)
+            .map_err(|err| Box::new(err) as Box<dyn Error>)?;
+        std::fs::write(path, certificate)?;
+    }
     let json = to_canonical_json_bytes(&report).map_err(|err| Box::new(err) as Box<dyn Error>)?;
     let rendered = String::from_utf8(json)?;
     if args.quiet {
         println!("{}", rendered);
     } else {
         println!("asm-sim doctor status: {}", report.status);
+        for workload in &report.selftest.workloads {
+            let verdict = if workload.pass { "ok" } else { "mismatch" };
+            println!("self-test {}: {}", workload.name, verdict);
+        }
         println!("{}", rendered);
     }
     if report.status != "ok" {
@@ -44,7 +70,7 @@ pub fn run(args: &DoctorArgs) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn diagnose(root: &Path) -> Result<DoctorReport, Box<dyn Error>> {
+fn diagnose(root: &Path, level: SelfTestLevel) -> Result<DoctorReport, Box<dyn Error>> {
     let root = root.canonicalize()?;
     let mut checks = Vec::new();
     checks.push(check_path(
@@ -76,6 +102,14 @@ fn diagnose(root: &Path) -> Result<DoctorReport, Box<dyn Error>> {
         &root.join("scripts/build_paper.sh"),
     ));
     checks.push(check_path("paper/main.md", &root.join("paper/main.md")));
+    checks.push(check_expected_hashes(
+        &root.join("replication/expected/graph_hashes.txt"),
+        HashDomain::Graph,
+    ));
+    checks.push(check_expected_hashes(
+        &root.join("replication/expected/code_hashes.txt"),
+        HashDomain::Code,
+    ));
 
     let mut missing_configs = Vec::new();
     let configs_dir = root.join("replication/configs");
@@ -99,6 +133,9 @@ fn diagnose(root: &Path) -> Result<DoctorReport, Box<dyn Error>> {
         });
     }
 
+    let selftest = determinism_selftest(level);
+    checks.push(check_selftest(&selftest));
+
     let mut status = "ok";
     if checks.iter().any(|check| !check.ok) {
         status = "needs-attention";
@@ -106,6 +143,7 @@ fn diagnose(root: &Path) -> Result<DoctorReport, Box<dyn Error>> {
     Ok(DoctorReport {
         status: status.into(),
         checks,
+        selftest,
     })
 }
 
@@ -124,3 +162,70 @@ fn check_path(name: &str, path: &Path) -> DoctorCheck {
         }
     }
 }
+
+fn check_selftest(report: &SelfTestReport) -> DoctorCheck {
+    let mismatches: Vec<String> = report
+        .mismatches()
+        .map(|workload| match &workload.error {
+            Some(error) => format!("{}: {error}", workload.name),
+            None => format!("{}: hash differs from reference", workload.name),
+        })
+        .collect();
+    DoctorCheck {
+        name: "determinism self-test".into(),
+        ok: report.pass,
+        detail: if mismatches.is_empty() {
+            format!(
+                "{} workloads match reference hashes on {}",
+                report.workloads.len(),
+                report.platform.arch
+            )
+        } else {
+            mismatches.join(", ")
+        },
+    }
+}
+
+fn describe_hash_error(err: &AsmError) -> String {
+    match err.info().code_enum() {
+        Some(ErrorCode::HashDomainMismatch) => format!(
+            "{}: {}",
+            err.context_value("hash").unwrap_or_default(),
+            err.info().message
+        ),
+        Some(code) => format!("hash rejected ({code}): {err}"),
+        None => format!("unexpected failure: {err}"),
+    }
+}
+
+/// Flags expected-hash listings that mix in hashes from another domain, e.g.
+/// a spectrum `analysis_hash` pasted into `graph_hashes.txt`.
+fn check_expected_hashes(path: &Path, domain: HashDomain) -> DoctorCheck {
+    let name = format!("replication/expected {domain} hash domain");
+    let Ok(listing) = std::fs::read_to_string(path) else {
+        return DoctorCheck {
+            name,
+            ok: false,
+            detail: "missing".into(),
+        };
+    };
+    let offenders: Vec<String> = listing
+        .lines()
+        .filter_map(|line| line.split_whitespace().last())
+        .filter_map(|hash| ensure_hash_domain(domain, hash).err())
+        .map(|err| describe_hash_error(&err))
+        .collect();
+    if offenders.is_empty() {
+        DoctorCheck {
+            name,
+            ok: true,
+            detail: path.display().to_string(),
+        }
+    } else {
+        DoctorCheck {
+            name,
+            ok: false,
+            detail: offenders.join(", "),
+        }
+    }
+}
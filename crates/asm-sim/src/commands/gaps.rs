@@ -2,7 +2,9 @@ use std::error::Error;
 use std::fs;
 use std::path::PathBuf;
 
-use asm_exp::{estimate_gaps, to_canonical_json_bytes, GapMethod, GapOpts};
+use asm_exp::{
+    estimate_gaps, to_canonical_json_bytes, GapMethod, GapOpts, GapUncertainty, SizedGap,
+};
 use asm_rg::StateRef;
 use clap::Args;
 use serde_json::Value;
@@ -22,6 +24,15 @@ pub struct GapsArgs {
     pub tolerance: f64,
     #[arg(long)]
     pub thresholds: Option<PathBuf>,
+    /// Resampling used to report a confidence interval (jackknife or bootstrap)
+    #[arg(long)]
+    pub uncertainty: Option<String>,
+    /// Number of bootstrap resamples
+    #[arg(long, default_value_t = 200)]
+    pub resamples: usize,
+    /// JSON or YAML list of `{size, gap}` points for finite-size scaling
+    #[arg(long)]
+    pub series: Option<PathBuf>,
 }
 
 pub fn run(args: &GapsArgs) -> Result<(), Box<dyn Error>> {
@@ -44,14 +55,31 @@ pub fn run(args: &GapsArgs) -> Result<(), Box<dyn Error>> {
     let method = match args.method.as_str() {
         "dispersion" => GapMethod::Dispersion,
         "spectral" => GapMethod::Spectral,
+        "finite-size-scaling" | "finite_size_scaling" => GapMethod::FiniteSizeScaling,
         other => {
             return Err(format!("unsupported gap method: {other}").into());
         }
     };
+    let uncertainty = match args.uncertainty.as_deref() {
+        None => None,
+        Some("jackknife") => Some(GapUncertainty::jackknife()),
+        Some("bootstrap") => Some(GapUncertainty::bootstrap(args.resamples)),
+        Some(other) => {
+            return Err(format!("unsupported uncertainty method: {other}").into());
+        }
+    };
+    let size_series = if let Some(path) = &args.series {
+        let raw = fs::read_to_string(path)?;
+        from_str::<Vec<SizedGap>>(&raw)?
+    } else {
+        Vec::new()
+    };
     let opts = GapOpts {
         method,
         thresholds,
         tolerance: args.tolerance,
+        uncertainty,
+        size_series,
     };
     let report = estimate_gaps(&state_ref, &opts).map_err(|err| Box::new(err) as Box<dyn Error>)?;
     let json = to_canonical_json_bytes(&report).map_err(|err| Box::new(err) as Box<dyn Error>)?;
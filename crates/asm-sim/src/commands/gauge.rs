@@ -4,7 +4,9 @@ use std::path::PathBuf;
 
 use asm_aut::AnalysisReport;
 use asm_gauge::ClosureOpts;
-use asm_gauge::{analyze_gauge, build_rep, to_canonical_json_bytes, GaugeOpts, RepOpts, WardOpts};
+use asm_gauge::{
+    analyze_gauge, build_rep, to_canonical_json_bytes, AnomalyOpts, GaugeOpts, RepOpts, WardOpts,
+};
 use asm_spec::{from_json_slice as spectrum_from_slice, SpectrumReport};
 use clap::Args;
 
@@ -25,9 +27,24 @@ pub struct GaugeArgs {
     /// Closure tolerance recorded in the report.
     #[arg(long, default_value_t = 1e-6)]
     pub closure_tol: f64,
+    /// Number of offending structure tensor entries listed in the closure report.
+    #[arg(long, default_value_t = 5)]
+    pub closure_report_top: usize,
+    /// Attach dimension, Casimir, and commutator diagnostics to `rep.json`.
+    #[arg(long, default_value_t = false)]
+    pub rep_diagnostics: bool,
     /// Ward relative tolerance recorded in the report.
     #[arg(long, default_value_t = 1e-5)]
     pub ward_tol: f64,
+    /// Attach the cubic anomaly cancellation check to the report.
+    #[arg(long, default_value_t = false)]
+    pub anomaly: bool,
+    /// Tolerance applied to the anomaly coefficients.
+    #[arg(long, default_value_t = 1e-6)]
+    pub anomaly_tol: f64,
+    /// JSON array of correlators checked as additional Ward identities.
+    #[arg(long)]
+    pub ward_correlators: Option<PathBuf>,
     /// Optional deterministic seed overriding provenance defaults.
     #[arg(long, default_value_t = 0)]
     pub seed: u64,
@@ -50,19 +67,33 @@ pub fn run(args: &GaugeArgs) -> Result<(), Box<dyn Error>> {
 
     let mut rep_opts = RepOpts::default();
     rep_opts.max_generators = args.max_generators.max(1);
+    rep_opts.diagnostics = args.rep_diagnostics;
     if args.seed != 0 {
         rep_opts.seed = Some(args.seed);
     }
     let closure_opts = ClosureOpts {
         tolerance: args.closure_tol,
+        report_top: args.closure_report_top,
+        ..ClosureOpts::default()
+    };
+    let correlators = match &args.ward_correlators {
+        Some(path) => serde_json::from_str(&fs::read_to_string(path)?)?,
+        None => Vec::new(),
     };
     let ward_opts = WardOpts {
         relative_tol: args.ward_tol,
+        correlators,
+        ..WardOpts::default()
     };
     let gauge_opts = GaugeOpts {
         rep: rep_opts.clone(),
         closure: closure_opts.clone(),
         ward: ward_opts.clone(),
+        anomaly: AnomalyOpts {
+            enabled: args.anomaly,
+            tolerance: args.anomaly_tol,
+            ..AnomalyOpts::default()
+        },
         seed: args.seed,
         ..GaugeOpts::default()
     };
@@ -83,6 +114,12 @@ pub fn run(args: &GaugeArgs) -> Result<(), Box<dyn Error>> {
         args.out.join("ward.json"),
         to_canonical_json_bytes(&report.ward)?,
     )?;
+    if let Some(anomaly) = &report.anomaly {
+        fs::write(
+            args.out.join("anomaly.json"),
+            to_canonical_json_bytes(anomaly)?,
+        )?;
+    }
     fs::write(
         args.out.join("gauge_report.json"),
         to_canonical_json_bytes(&report)?,
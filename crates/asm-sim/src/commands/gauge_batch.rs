@@ -6,7 +6,10 @@ use std::path::{Path, PathBuf};
 use asm_aut::AnalysisReport;
 use asm_core::rng::derive_substream_seed;
 use asm_gauge::ClosureOpts;
-use asm_gauge::{analyze_gauge, build_rep, to_canonical_json_bytes, GaugeOpts, RepOpts, WardOpts};
+use asm_gauge::{
+    analyze_gauge, analyze_gauge_sweep, build_rep, to_canonical_json_bytes, GaugeOpts, RepOpts,
+    WardOpts,
+};
 use asm_spec::{from_json_slice as spectrum_from_slice, SpectrumReport};
 use clap::Args;
 use glob::glob;
@@ -35,6 +38,21 @@ pub struct GaugeBatchArgs {
     /// Master deterministic seed used to derive per-run substreams.
     #[arg(long, default_value_t = 0)]
     pub seed: u64,
+    /// Tolerance grid as `closure_tol:ward_tol` pairs; writes `gauge_sweep.json` per run.
+    #[arg(long = "sweep", value_name = "CLOSURE:WARD", value_delimiter = ',', value_parser = parse_tol_pair)]
+    pub sweep: Vec<(f64, f64)>,
+}
+
+fn parse_tol_pair(value: &str) -> Result<(f64, f64), String> {
+    let (closure, ward) = value
+        .split_once(':')
+        .ok_or_else(|| format!("expected CLOSURE:WARD, got '{value}'"))?;
+    let parse = |text: &str| {
+        text.trim()
+            .parse::<f64>()
+            .map_err(|err| format!("invalid tolerance '{text}': {err}"))
+    };
+    Ok((parse(closure)?, parse(ward)?))
 }
 
 #[derive(Debug, Serialize)]
@@ -148,9 +166,11 @@ pub fn run(args: &GaugeBatchArgs) -> Result<(), Box<dyn Error>> {
         }
         let closure_opts = ClosureOpts {
             tolerance: args.closure_tol,
+            ..ClosureOpts::default()
         };
         let ward_opts = WardOpts {
             relative_tol: args.ward_tol,
+            ..WardOpts::default()
         };
         let gauge_opts = GaugeOpts {
             rep: rep_opts.clone(),
@@ -183,6 +203,13 @@ pub fn run(args: &GaugeBatchArgs) -> Result<(), Box<dyn Error>> {
             run_dir.join("gauge_report.json"),
             to_canonical_json_bytes(&report)?,
         )?;
+        if !args.sweep.is_empty() {
+            let sweep = analyze_gauge_sweep(&spectrum, analysis, &args.sweep, &gauge_opts)?;
+            fs::write(
+                run_dir.join("gauge_sweep.json"),
+                to_canonical_json_bytes(&sweep)?,
+            )?;
+        }
 
         index_entries.push(BatchEntry {
             label,
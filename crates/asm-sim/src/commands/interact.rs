@@ -5,14 +5,49 @@ use std::path::PathBuf;
 use asm_gauge::from_json_slice as gauge_from_slice;
 use asm_gauge::GaugeReport;
 use asm_int::{
-    interact_full, serde::to_canonical_json_bytes, FitOpts, KernelOpts, MeasureOpts, PrepSpec,
+    compare_reports, interact_full, serde::from_json_slice, serde::to_canonical_json_bytes,
+    CompareTolerances, FitOpts, InteractionReport, KernelOpts, MeasureOpts, PrepSpec,
 };
 use asm_spec::from_json_slice as spec_from_slice;
 use asm_spec::SpectrumReport;
-use clap::Args;
+use clap::{Args, Subcommand};
 
 #[derive(Args, Debug)]
+#[command(args_conflicts_with_subcommands = true, arg_required_else_help = true)]
 pub struct InteractArgs {
+    #[command(subcommand)]
+    pub command: Option<InteractCommand>,
+    #[command(flatten)]
+    pub experiment: Option<ExperimentArgs>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum InteractCommand {
+    /// Compare two interaction reports and emit a structured diff.
+    Compare(CompareArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct CompareArgs {
+    /// First interaction report to compare.
+    #[arg(long = "a")]
+    pub report_a: PathBuf,
+    /// Second interaction report to compare.
+    #[arg(long = "b")]
+    pub report_b: PathBuf,
+    /// Absolute coupling delta accepted as unchanged.
+    #[arg(long, default_value_t = 1e-9)]
+    pub coupling_abs: f64,
+    /// Relative coupling delta accepted as unchanged.
+    #[arg(long, default_value_t = 1e-6)]
+    pub coupling_rel: f64,
+    /// Output directory for the diff artefact.
+    #[arg(long)]
+    pub out: PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub struct ExperimentArgs {
     /// Spectrum report emitted during Phase 11.
     #[arg(long)]
     pub spectrum: PathBuf,
@@ -45,7 +80,7 @@ fn load_yaml<T: serde::de::DeserializeOwned>(path: &PathBuf) -> Result<T, Box<dy
     Ok(value)
 }
 
-fn load_reports(args: &InteractArgs) -> Result<(SpectrumReport, GaugeReport), Box<dyn Error>> {
+fn load_reports(args: &ExperimentArgs) -> Result<(SpectrumReport, GaugeReport), Box<dyn Error>> {
     let spectrum_bytes = fs::read(&args.spectrum)?;
     let gauge_bytes = fs::read(&args.gauge)?;
     let spectrum = spec_from_slice(&spectrum_bytes)?;
@@ -53,8 +88,34 @@ fn load_reports(args: &InteractArgs) -> Result<(SpectrumReport, GaugeReport), Bo
     Ok((spectrum, gauge))
 }
 
-/// Executes a single deterministic interaction experiment and persists canonical JSON artefacts.
 pub fn run(args: &InteractArgs) -> Result<(), Box<dyn Error>> {
+    match (&args.command, &args.experiment) {
+        (Some(InteractCommand::Compare(compare)), _) => run_compare(compare),
+        (None, Some(experiment)) => run_experiment(experiment),
+        (None, None) => Err("interact requires experiment arguments or a subcommand".into()),
+    }
+}
+
+/// Compares two interaction reports and writes `interaction_diff.json`.
+fn run_compare(args: &CompareArgs) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(&args.out)?;
+    let report_a: InteractionReport = from_json_slice(&fs::read(&args.report_a)?)?;
+    let report_b: InteractionReport = from_json_slice(&fs::read(&args.report_b)?)?;
+    let tol = CompareTolerances {
+        coupling_abs: args.coupling_abs,
+        coupling_rel: args.coupling_rel,
+        ..CompareTolerances::default()
+    };
+    let diff = compare_reports(&report_a, &report_b, &tol);
+    fs::write(
+        args.out.join("interaction_diff.json"),
+        to_canonical_json_bytes(&diff)?,
+    )?;
+    Ok(())
+}
+
+/// Executes a single deterministic interaction experiment and persists canonical JSON artefacts.
+fn run_experiment(args: &ExperimentArgs) -> Result<(), Box<dyn Error>> {
     fs::create_dir_all(&args.out)?;
     let (spectrum, gauge) = load_reports(args)?;
     let prep_spec: PrepSpec = load_yaml(&args.prep)?;
@@ -2,6 +2,7 @@ use std::error::Error;
 use std::fs;
 use std::path::PathBuf;
 
+use asm_land::estimate::StageCosts;
 use asm_land::filters::load_filters;
 use asm_land::plan::{
     CodeSpec, GaugeSpec, GraphSpec, InteractSpec, OutputLayout, OutputSpec, RuleSpec, SamplerSpec,
@@ -9,7 +10,11 @@ use asm_land::plan::{
 };
 use asm_land::serde::{to_canonical_json_bytes, to_yaml_string};
 use asm_land::{
-    build_atlas, load_plan, plan::Plan, report::AtlasOpts, run_plan, summarize, RunOpts,
+    build_atlas, diff_runs, estimate_plan_with_costs, load_events, load_plan,
+    plan::Plan,
+    report::{AtlasOpts, EVENTS_FILE},
+    run_plan, stage_duration_quantiles, summarize_with_opts, BootstrapOpts, ResumeVerification,
+    RunOpts, StatsOpts,
 };
 use clap::{Args, Subcommand};
 
@@ -19,10 +24,14 @@ pub enum LandscapeSubcommand {
     Plan(PlanArgs),
     /// Execute a landscape plan and emit canonical JSON artefacts.
     Run(RunArgs),
+    /// Project job count, disk footprint, and wall-time without running the plan.
+    Estimate(EstimateArgs),
     /// Summarize metrics for existing runs under the provided root.
     Summarize(SummarizeArgs),
     /// Build a compact atlas manifest aggregating all universes discovered.
     Atlas(AtlasArgs),
+    /// Compare two landscape runs job by job.
+    Diff(DiffArgs),
 }
 
 #[derive(Args, Debug)]
@@ -91,9 +100,40 @@ pub struct RunArgs {
     /// Resume partially completed runs.
     #[arg(long, default_value_t = false)]
     pub resume: bool,
+    /// Fail when a resumed job's artefacts disagree with a recomputation.
+    #[arg(long, default_value_t = false)]
+    pub verify_resumed: bool,
+    /// Re-run resumed jobs whose artefacts disagree with a recomputation.
+    #[arg(long, default_value_t = false, conflicts_with = "verify_resumed")]
+    pub rerun_mismatched: bool,
     /// Advisory concurrency level.
     #[arg(long, default_value_t = 1)]
     pub concurrency: usize,
+    /// Cap on the projected memory of concurrently running jobs.
+    #[arg(long)]
+    pub memory_budget_bytes: Option<usize>,
+    /// Pin the report timestamp so repeated runs produce identical bytes.
+    #[arg(long, default_value_t = false)]
+    pub deterministic_time: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct EstimateArgs {
+    /// Path to the plan YAML file.
+    #[arg(long)]
+    pub plan: PathBuf,
+    /// Optional JSON table of per-stage seconds-per-unit costs.
+    #[arg(long)]
+    pub costs: Option<PathBuf>,
+    /// Worker count assumed for the wall-time projection.
+    #[arg(long, default_value_t = 1)]
+    pub concurrency: usize,
+    /// Cap on the projected memory of concurrently running jobs.
+    #[arg(long)]
+    pub memory_budget_bytes: Option<usize>,
+    /// Write the estimate to this file instead of stdout.
+    #[arg(long)]
+    pub json: Option<PathBuf>,
 }
 
 #[derive(Args, Debug)]
@@ -107,6 +147,18 @@ pub struct SummarizeArgs {
     /// Output directory for the summary artefacts.
     #[arg(long)]
     pub out: PathBuf,
+    /// Significance level below which correlation p-values are accepted.
+    #[arg(long, default_value_t = 0.05)]
+    pub alpha: f64,
+    /// Add per-stage duration quantiles from the run's event log.
+    #[arg(long, default_value_t = false)]
+    pub stage_durations: bool,
+    /// Attach bootstrap confidence bands from this many resamples.
+    #[arg(long)]
+    pub bootstrap_samples: Option<usize>,
+    /// Seed of the bootstrap resampling stream.
+    #[arg(long, default_value_t = 0)]
+    pub bootstrap_seed: u64,
 }
 
 #[derive(Args, Debug)]
@@ -120,14 +172,32 @@ pub struct AtlasArgs {
     /// Include failed jobs in the atlas.
     #[arg(long, default_value_t = false)]
     pub include_failed: bool,
+    /// Collapse structurally identical universes into one entry.
+    #[arg(long, default_value_t = false)]
+    pub deduplicate: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    /// Root directory of the baseline run.
+    #[arg(long)]
+    pub root_a: PathBuf,
+    /// Root directory of the run compared against the baseline.
+    #[arg(long)]
+    pub root_b: PathBuf,
+    /// Write the diff to this file instead of stdout.
+    #[arg(long)]
+    pub json: Option<PathBuf>,
 }
 
 pub fn run(cmd: &LandscapeSubcommand) -> Result<(), Box<dyn Error>> {
     match cmd {
         LandscapeSubcommand::Plan(args) => generate_plan(args),
         LandscapeSubcommand::Run(args) => execute_plan(args),
+        LandscapeSubcommand::Estimate(args) => estimate(args),
         LandscapeSubcommand::Summarize(args) => summarize_runs(args),
         LandscapeSubcommand::Atlas(args) => build_atlas_manifest(args),
+        LandscapeSubcommand::Diff(args) => diff(args),
     }
 }
 
@@ -184,6 +254,7 @@ fn generate_plan(args: &PlanArgs) -> Result<(), Box<dyn Error>> {
             keep_intermediate: true,
         },
         rules: vec![RuleSpec::default()],
+        seed_derivation: Default::default(),
         base_dir: PathBuf::new(),
     };
     let yaml = to_yaml_string(&plan)?;
@@ -198,15 +269,56 @@ fn execute_plan(args: &RunArgs) -> Result<(), Box<dyn Error>> {
         resume: args.resume,
         concurrency: args.concurrency,
         max_retries: 2,
+        verify_resumed: if args.rerun_mismatched {
+            ResumeVerification::Rerun
+        } else if args.verify_resumed {
+            ResumeVerification::Error
+        } else {
+            ResumeVerification::Off
+        },
+        memory_budget_bytes: args.memory_budget_bytes,
+        deterministic_time: args.deterministic_time,
+        ..RunOpts::default()
     };
     run_plan(&plan, &args.out, &opts)?;
     Ok(())
 }
 
+fn estimate(args: &EstimateArgs) -> Result<(), Box<dyn Error>> {
+    let plan = load_plan(&args.plan)?;
+    let costs = match &args.costs {
+        Some(path) => serde_json::from_slice::<StageCosts>(&fs::read(path)?)?,
+        None => StageCosts::default(),
+    };
+    let opts = RunOpts {
+        concurrency: args.concurrency,
+        memory_budget_bytes: args.memory_budget_bytes,
+        ..RunOpts::default()
+    };
+    let estimate = estimate_plan_with_costs(&plan, &opts, &costs)?;
+    let bytes = to_canonical_json_bytes(&estimate)?;
+    match &args.json {
+        Some(path) => fs::write(path, bytes)?,
+        None => println!("{}", String::from_utf8(bytes)?),
+    }
+    Ok(())
+}
+
 fn summarize_runs(args: &SummarizeArgs) -> Result<(), Box<dyn Error>> {
     fs::create_dir_all(&args.out)?;
     let filters = load_filters(&args.filters)?;
-    let summary = summarize(&args.root, &filters)?;
+    let opts = StatsOpts {
+        bootstrap: args.bootstrap_samples.map(|samples| BootstrapOpts {
+            samples,
+            seed: args.bootstrap_seed,
+        }),
+        ..StatsOpts::default()
+    };
+    let mut summary = summarize_with_opts(&args.root, &filters, args.alpha, &opts)?;
+    if args.stage_durations {
+        let events = load_events(&args.root.join(EVENTS_FILE))?;
+        summary.stage_durations = stage_duration_quantiles(&events);
+    }
     fs::write(
         args.out.join("summary_report.json"),
         to_canonical_json_bytes(&summary)?,
@@ -220,6 +332,7 @@ fn build_atlas_manifest(args: &AtlasArgs) -> Result<(), Box<dyn Error>> {
         &args.root,
         &AtlasOpts {
             include_failed: args.include_failed,
+            deduplicate: args.deduplicate,
         },
     )?;
     fs::write(
@@ -228,3 +341,13 @@ fn build_atlas_manifest(args: &AtlasArgs) -> Result<(), Box<dyn Error>> {
     )?;
     Ok(())
 }
+
+fn diff(args: &DiffArgs) -> Result<(), Box<dyn Error>> {
+    let diff = diff_runs(&args.root_a, &args.root_b)?;
+    let bytes = to_canonical_json_bytes(&diff)?;
+    match &args.json {
+        Some(path) => fs::write(path, bytes)?,
+        None => println!("{}", String::from_utf8(bytes)?),
+    }
+    Ok(())
+}
@@ -1,8 +1,7 @@
 use std::error::Error;
-use std::fs;
 use std::path::{Path, PathBuf};
 
-use asm_host::{load_plugin_manifest, PluginRegistry};
+use asm_host::{PluginRegistry, SignatureStatus};
 use clap::{Args, Subcommand};
 
 #[derive(Args, Debug)]
@@ -10,60 +9,88 @@ pub struct PluginArgs {
     /// Registry root directory
     #[arg(long, default_value = "registry/plugins")]
     pub registry: PathBuf,
+    /// Hex-encoded ed25519 public key whose plugin signatures are trusted
+    #[arg(long = "trusted-key")]
+    pub trusted_keys: Vec<String>,
     #[command(subcommand)]
     pub command: PluginCommand,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum PluginCommand {
-    Install { path: PathBuf },
+    Install {
+        path: PathBuf,
+        /// Install even when dependencies are missing or incompatible
+        #[arg(long)]
+        force: bool,
+    },
     List,
-    Verify { name: String },
-    Remove { name: String },
+    Verify {
+        name: String,
+    },
+    Remove {
+        name: String,
+    },
+    /// Re-read an installed plugin from the path it was installed from
+    Reload {
+        name: String,
+    },
+    /// Restore a version archived by an earlier reload
+    Rollback {
+        name: String,
+        hash: String,
+    },
+    /// Remove half-written entries left by interrupted installs
+    Repair,
 }
 
 pub fn run(args: &PluginArgs) -> Result<(), Box<dyn Error>> {
-    let registry = PluginRegistry::new(&args.registry);
+    let registry = PluginRegistry::new(&args.registry).with_trusted_keys(&args.trusted_keys);
     match &args.command {
-        PluginCommand::Install { path } => install(&registry, path)?,
+        PluginCommand::Install { path, force } => install(&registry, path, *force)?,
         PluginCommand::List => list(&registry)?,
         PluginCommand::Verify { name } => verify(&registry, name)?,
         PluginCommand::Remove { name } => remove(&registry, name)?,
+        PluginCommand::Reload { name } => reload(&registry, name)?,
+        PluginCommand::Rollback { name, hash } => rollback(&registry, name, hash)?,
+        PluginCommand::Repair => repair(&registry)?,
     }
     Ok(())
 }
 
-fn install(registry: &PluginRegistry, path: &Path) -> Result<(), Box<dyn Error>> {
-    let manifest_path = if path.is_dir() {
-        path.join("plugin.toml")
-    } else {
-        path.to_path_buf()
-    };
-    let manifest = load_plugin_manifest(&manifest_path)?;
-    let binary_path = manifest_path.parent().map(|dir| dir.join("plugin.bin"));
-    let plugin_bytes = match binary_path {
-        Some(p) if p.exists() => Some(fs::read(p)?),
-        _ => None,
-    };
-    let entry = registry.install(&manifest, plugin_bytes.as_deref())?;
+fn install(registry: &PluginRegistry, path: &Path, force: bool) -> Result<(), Box<dyn Error>> {
+    let entry = registry.install_from(path, force)?;
     println!(
         "installed plugin {} {}",
         entry.metadata.name, entry.metadata.version
     );
+    for dep in &entry.metadata.unresolved_dependencies {
+        println!("unresolved dependency {} {}", dep.name, dep.version_req);
+    }
     Ok(())
 }
 
 fn list(registry: &PluginRegistry) -> Result<(), Box<dyn Error>> {
     let entries = registry.list()?;
     for entry in entries {
-        println!("{} {}", entry.metadata.name, entry.metadata.version);
+        let marker = match entry.signature {
+            SignatureStatus::Verified { .. } => "",
+            SignatureStatus::UntrustedKey { .. } => " (untrusted key)",
+            SignatureStatus::Unsigned => " (unsigned)",
+        };
+        println!("{} {}{marker}", entry.metadata.name, entry.metadata.version);
     }
     Ok(())
 }
 
 fn verify(registry: &PluginRegistry, name: &str) -> Result<(), Box<dyn Error>> {
     let entry = registry.verify(name)?;
-    println!("verified {}", entry.metadata.name);
+    let label = match entry.signature {
+        SignatureStatus::Verified { .. } => "signed",
+        SignatureStatus::UntrustedKey { .. } => "untrusted key",
+        SignatureStatus::Unsigned => "unsigned",
+    };
+    println!("verified {} ({label})", entry.metadata.name);
     Ok(())
 }
 
@@ -72,3 +99,37 @@ fn remove(registry: &PluginRegistry, name: &str) -> Result<(), Box<dyn Error>> {
     println!("removed {name}");
     Ok(())
 }
+
+fn reload(registry: &PluginRegistry, name: &str) -> Result<(), Box<dyn Error>> {
+    let outcome = registry.reload(name)?;
+    match (&outcome.archived, outcome.changed) {
+        (None, _) => println!("plugin {name} unchanged"),
+        (Some(archived), true) => {
+            println!("reloaded {name}; previous binary archived as {archived}")
+        }
+        (Some(archived), false) => {
+            println!("reloaded {name} manifest; previous version archived as {archived}")
+        }
+    }
+    Ok(())
+}
+
+fn rollback(registry: &PluginRegistry, name: &str, hash: &str) -> Result<(), Box<dyn Error>> {
+    let entry = registry.rollback(name, hash)?;
+    println!(
+        "rolled back {} to {}",
+        entry.metadata.name, entry.metadata.version
+    );
+    Ok(())
+}
+
+fn repair(registry: &PluginRegistry) -> Result<(), Box<dyn Error>> {
+    let repaired = registry.repair()?;
+    if repaired.is_empty() {
+        println!("registry clean");
+    }
+    for path in repaired {
+        println!("repaired {path}");
+    }
+    Ok(())
+}
@@ -42,6 +42,7 @@ fn load_meta(path: Option<&PathBuf>) -> Result<RunMeta, Box<dyn Error>> {
             seeds: Vec::new(),
             artifacts: Vec::new(),
             summary: json!({"notes": "auto-generated"}),
+            steps: Vec::new(),
         })
     }
 }
@@ -62,5 +63,11 @@ fn write_markdown(out: &PathBuf, runbook: &asm_exp::RunBook) -> Result<(), Box<d
     writeln!(file, "- Commit: {}", runbook.commit)?;
     writeln!(file, "- Created: {}", runbook.created_at)?;
     writeln!(file, "- Inputs: {}", runbook.inputs.len())?;
+    if !runbook.steps.is_empty() {
+        writeln!(file, "\n## Steps\n")?;
+        for step in &runbook.steps {
+            writeln!(file, "- {}", step.id)?;
+        }
+    }
     Ok(())
 }
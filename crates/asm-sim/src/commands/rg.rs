@@ -5,7 +5,7 @@ use std::path::PathBuf;
 use asm_code::serde as code_serde;
 use asm_mcmc::analysis;
 use asm_mcmc::manifest::RunManifest;
-use asm_rg::{rg_run, serde_io, RGOpts, StateRef};
+use asm_rg::{rg_run, serde_io, verify_certificate, RGOpts, StateRef};
 use clap::Args;
 
 use crate::write_json;
@@ -27,6 +27,17 @@ pub struct RgArgs {
     /// Seed controlling deterministic block ordering.
     #[arg(long, default_value_t = 0)]
     pub seed: u64,
+    /// Emit a contraction certificate per step and check it against the fine
+    /// and coarse codes, failing on the first certificate that does not hold.
+    #[arg(long)]
+    pub verify: bool,
+    /// Collapse every block into a single coarse node.
+    #[arg(long)]
+    pub merge_nodes: bool,
+    /// Write the fine-node population of every coarse node to
+    /// `node_populations.json` next to each step's graph.
+    #[arg(long)]
+    pub populations: bool,
 }
 
 pub fn run(args: &RgArgs) -> Result<(), Box<dyn Error>> {
@@ -41,6 +52,9 @@ pub fn run(args: &RgArgs) -> Result<(), Box<dyn Error>> {
         scale_factor: args.scale.max(1),
         max_block_size: args.scale.max(1),
         seed: args.seed,
+        emit_certificate: args.verify,
+        merge_nodes: args.merge_nodes,
+        ..RGOpts::default()
     };
     let state = StateRef {
         graph: &graph,
@@ -49,6 +63,25 @@ pub fn run(args: &RgArgs) -> Result<(), Box<dyn Error>> {
     let run =
         rg_run(&state, args.steps, &rg_opts).map_err(|err| Box::new(err) as Box<dyn Error>)?;
 
+    if args.verify {
+        let mut fine = &code;
+        for (index, step) in run.steps.iter().enumerate() {
+            let certificate =
+                step.report.certificate.as_ref().ok_or_else(|| {
+                    format!("step {index} is missing its contraction certificate")
+                })?;
+            let valid = verify_certificate(fine, &step.code, certificate)
+                .map_err(|err| Box::new(err) as Box<dyn Error>)?;
+            if !valid {
+                return Err(
+                    format!("contraction certificate for step {index} does not hold").into(),
+                );
+            }
+            fine = &step.code;
+        }
+        println!("verified {} contraction certificates", run.steps.len());
+    }
+
     let run_json =
         serde_io::run_to_json(&run.report).map_err(|err| Box::new(err) as Box<dyn Error>)?;
     fs::write(args.out.join("rg_run.json"), run_json)?;
@@ -62,6 +95,12 @@ pub fn run(args: &RgArgs) -> Result<(), Box<dyn Error>> {
         let graph_json =
             asm_graph::graph_to_json(&step.graph).map_err(|err| Box::new(err) as Box<dyn Error>)?;
         fs::write(step_dir.join("graph.json"), graph_json)?;
+        if args.populations {
+            write_json(
+                step_dir.join("node_populations.json"),
+                &step.node_populations,
+            )?;
+        }
         let code_json =
             code_serde::to_json(&step.code).map_err(|err| Box::new(err) as Box<dyn Error>)?;
         fs::write(step_dir.join("code.json"), code_json)?;
@@ -73,6 +112,8 @@ pub fn run(args: &RgArgs) -> Result<(), Box<dyn Error>> {
         "scale_factor": rg_opts.scale_factor,
         "seed": rg_opts.seed,
         "run_hash": run.report.run_hash,
+        "verified": args.verify,
+        "merge_nodes": args.merge_nodes,
     });
     write_json(args.out.join("summary.json"), &summary)?;
 
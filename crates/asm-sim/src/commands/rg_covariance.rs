@@ -46,6 +46,7 @@ pub fn run(args: &RgCovarianceArgs) -> Result<(), Box<dyn Error>> {
         scale_factor: args.scale.max(1),
         max_block_size: args.scale.max(1),
         seed: args.seed,
+        ..RGOpts::default()
     };
     let dict_opts = DictOpts {
         yukawa_count: args.yukawa.max(1),
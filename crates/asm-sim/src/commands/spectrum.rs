@@ -48,6 +48,19 @@ pub struct SpectrumArgs {
     /// Number of propagation iterations to perform.
     #[arg(long, default_value_t = 16)]
     pub iterations: usize,
+    /// Attach a wavefront velocity fit to the spectrum report.
+    #[arg(long, default_value_t = false)]
+    pub front_fit: bool,
+    /// Amplitude threshold for the wavefront fit (defaults to half the peak).
+    #[arg(long)]
+    pub front_threshold: Option<f64>,
+    /// Per-node operator weights, e.g. `node_populations.json` written by
+    /// `asm-sim rg --populations`.
+    #[arg(long)]
+    pub node_weights: Option<PathBuf>,
+    /// Build operators from edges carrying this label only.
+    #[arg(long)]
+    pub edge_label: Option<String>,
 }
 
 pub fn run(args: &SpectrumArgs) -> Result<(), Box<dyn Error>> {
@@ -55,6 +68,10 @@ pub fn run(args: &SpectrumArgs) -> Result<(), Box<dyn Error>> {
     let loaded = load_state(&args.input)?;
 
     let variant = parse_ops_variant(&args.ops_variant)?;
+    let node_weights = match &args.node_weights {
+        Some(path) => serde_json::from_slice(&fs::read(path)?)?,
+        None => Default::default(),
+    };
     let mut excitation = ExcitationSpec::default();
     excitation.support = args.support.max(1);
 
@@ -68,10 +85,16 @@ pub fn run(args: &SpectrumArgs) -> Result<(), Box<dyn Error>> {
         iterations: args.iterations.max(1),
         tolerance: args.fit_tol,
         seed: derive_substream_seed(args.seed, 0),
+        emit_front_fit: args.front_fit,
+        front_threshold: args.front_threshold,
     };
 
     let spec_opts = SpecOpts {
-        ops: OpOpts { variant },
+        ops: OpOpts {
+            variant,
+            node_weights,
+            edge_label: args.edge_label.clone(),
+        },
         excitation,
         propagation: prop_opts,
         dispersion,
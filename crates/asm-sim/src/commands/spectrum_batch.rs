@@ -162,11 +162,16 @@ pub fn run(args: &SpectrumBatchArgs) -> Result<(), Box<dyn Error>> {
             iterations: args.iterations.max(1),
             tolerance: args.fit_tol,
             seed: derive_substream_seed(args.seed, idx as u64),
+            emit_front_fit: false,
+            front_threshold: None,
         };
         let mut excitation = ExcitationSpec::default();
         excitation.support = args.support.max(1);
         let spec_opts = SpecOpts {
-            ops: OpOpts { variant },
+            ops: OpOpts {
+                variant,
+                ..OpOpts::default()
+            },
             excitation,
             propagation: prop_opts,
             dispersion: dispersion.clone(),
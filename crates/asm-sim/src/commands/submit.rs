@@ -13,6 +13,9 @@ pub struct SubmitArgs {
     /// SQLite registry path
     #[arg(long)]
     pub registry: PathBuf,
+    /// Store artefacts once per SHA256 in the shared blob store
+    #[arg(long, default_value_t = false)]
+    pub dedup: bool,
 }
 
 pub fn run(args: &SubmitArgs) -> Result<(), Box<dyn Error>> {
@@ -26,6 +29,7 @@ pub fn run(args: &SubmitArgs) -> Result<(), Box<dyn Error>> {
     let opts = IngestOptions {
         artifact_root: artifacts_dir,
         validate_hashes: true,
+        dedup: args.dedup,
     };
     let record = ingest_bundle(&conn, &args.bundle, &opts)?;
     println!("ingested submission {}", record.id);
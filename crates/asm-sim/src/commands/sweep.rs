@@ -14,12 +14,28 @@ pub struct SweepArgs {
     pub seed: u64,
     #[arg(long)]
     pub out: PathBuf,
+    /// Write the enumerated jobs to sweep_preview.json without running them
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
 pub fn run(args: &SweepArgs) -> Result<(), Box<dyn Error>> {
     fs::create_dir_all(&args.out)?;
     let plan_text = fs::read_to_string(&args.plan)?;
     let plan: SweepPlan = from_str(&plan_text)?;
+    if args.dry_run {
+        let preview = plan
+            .preview(args.seed)
+            .map_err(|err| Box::new(err) as Box<dyn Error>)?;
+        let bytes =
+            to_canonical_json_bytes(&preview).map_err(|err| Box::new(err) as Box<dyn Error>)?;
+        fs::write(args.out.join("sweep_preview.json"), bytes)?;
+        println!(
+            "sweep would run {} jobs in {} waves",
+            preview.job_count, preview.waves
+        );
+        return Ok(());
+    }
     let report = sweep(&plan, args.seed).map_err(|err| Box::new(err) as Box<dyn Error>)?;
     persist_report(&args.out, &report)?;
     Ok(())
@@ -34,7 +50,10 @@ fn persist_report(out: &PathBuf, report: &SweepReport) -> Result<(), Box<dyn Err
         let params_bytes =
             to_canonical_json_bytes(&job.params).map_err(|err| Box::new(err) as Box<dyn Error>)?;
         fs::write(job_dir.join("params.json"), params_bytes)?;
-        let status = format!("{}\nseed={}", job.status, job.seed);
+        let mut status = format!("{}\nseed={}", job.status, job.seed);
+        if let Some(error) = &job.error {
+            status.push_str(&format!("\nerror={error}"));
+        }
         fs::write(job_dir.join("STATUS"), status)?;
     }
     Ok(())
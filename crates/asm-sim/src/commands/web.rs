@@ -18,13 +18,25 @@ pub struct WebArgs {
     /// Output directory for the generated static site
     #[arg(long)]
     pub out: PathBuf,
+    /// Only re-render pages whose inputs changed since the last build
+    #[arg(long)]
+    pub incremental: bool,
+    /// Regenerate every page even when its inputs are unchanged
+    #[arg(long)]
+    pub force: bool,
 }
 
 pub fn run(args: &WebArgs) -> Result<(), Box<dyn Error>> {
     let conn = Connection::open(&args.registry)?;
     let contents = fs::read_to_string(&args.config)?;
-    let config: SiteConfig = serde_yaml::from_str(&contents)?;
+    let mut config: SiteConfig = serde_yaml::from_str(&contents)?;
+    config.incremental |= args.incremental;
+    config.force |= args.force;
     let manifest = build_site(&conn, &config, &args.out, &QueryParams::default())?;
-    println!("built site with {} pages", manifest.page_count);
+    println!(
+        "built site with {} pages ({} regenerated)",
+        manifest.page_count,
+        manifest.regenerated.len()
+    );
     Ok(())
 }
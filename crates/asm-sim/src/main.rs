@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::error::Error;
@@ -8,7 +9,8 @@ use std::path::{Path, PathBuf};
 use asm_aut::invariants::ProvenanceInfo;
 use asm_aut::{
     analyze_state as aut_analyze_state, cluster as aut_cluster, serde_io as aut_serde,
-    AnalysisReport, ClusterOpts as AutClusterOpts, ScanOpts as AutScanOpts,
+    AnalysisReport, ClusterMetric as AutClusterMetric, ClusterOpts as AutClusterOpts,
+    ScanOpts as AutScanOpts,
 };
 use asm_code::dispersion::{DispersionOptions, DispersionReport};
 use asm_code::{serde as code_serde, CSSCode, SpeciesId};
@@ -175,6 +177,9 @@ struct AnalyzeArgs {
     /// Maximum k-means refinement passes when clustering.
     #[arg(long = "cluster-iterations", default_value_t = 16)]
     cluster_iterations: usize,
+    /// Clustering distance: `features` (k-means) or `similarity` (k-medoids).
+    #[arg(long = "cluster-metric", default_value = "features")]
+    cluster_metric: String,
     /// Emit top-N representative hashes per cluster.
     #[arg(long = "emit-representatives")]
     emit_representatives: Option<usize>,
@@ -193,6 +198,15 @@ struct DispersionConfigFile {
     #[serde(default)]
     steps: Vec<u32>,
     tolerance: Option<f64>,
+    #[serde(default)]
+    overrides: Vec<DispersionOverrideEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DispersionOverrideEntry {
+    species: DispersionSpeciesValue,
+    tolerance: Option<f64>,
+    weight: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -288,7 +302,7 @@ fn run_dispersion_mode(args: &AnalyzeArgs) -> Result<(), Box<dyn Error>> {
     let dispersion_dir = args.out.join("dispersion");
     fs::create_dir_all(&dispersion_dir)?;
     write_species_reports(&dispersion_dir, &report)?;
-    write_common_c(&args.out, &report, options.tolerance)?;
+    write_common_c(&args.out, &report, &options)?;
 
     let checkpoint_paths = if !manifest.checkpoints.is_empty() {
         analysis::resolve_checkpoint_paths(input_dir, &manifest.checkpoints)
@@ -308,7 +322,7 @@ fn run_dispersion_mode(args: &AnalyzeArgs) -> Result<(), Box<dyn Error>> {
             .to_string();
         checkpoint_reports.push((label, report));
     }
-    write_checkpoint_summary(&args.out, &checkpoint_reports, &report, options.tolerance)?;
+    write_checkpoint_summary(&args.out, &checkpoint_reports, &report, &options)?;
 
     Ok(())
 }
@@ -325,6 +339,7 @@ fn run_symmetry_scan(args: &AnalyzeArgs) -> Result<(), Box<dyn Error>> {
     let scan_opts = AutScanOpts {
         laplacian_topk: args.laplacian_topk,
         stabilizer_topk: args.stabilizer_topk,
+        stabilizer_backend: None,
         provenance: Some(provenance),
     };
     let report = aut_analyze_state(&graph, &code, &scan_opts)?;
@@ -382,11 +397,17 @@ fn run_cluster_mode(args: &AnalyzeArgs) -> Result<(), Box<dyn Error>> {
         return Err("no analysis reports found in the provided paths".into());
     }
 
+    let metric = match args.cluster_metric.as_str() {
+        "features" => AutClusterMetric::Features,
+        "similarity" => AutClusterMetric::Similarity,
+        other => return Err(format!("unsupported cluster metric: {other}").into()),
+    };
     let default_opts = AutClusterOpts::default();
     let cluster_opts = AutClusterOpts {
         k: args.cluster_count.min(reports.len()).max(1),
         max_iterations: args.cluster_iterations.max(1),
         seed: default_opts.seed,
+        metric,
     };
     let summary = aut_cluster(&reports, &cluster_opts);
     write_json(args.out.join("cluster_summary.json"), &summary)?;
@@ -514,6 +535,8 @@ fn write_coverage_summary(out_dir: &Path, summary: &RunSummary) -> Result<(), Bo
         "exchange_acceptance": summary.exchange_acceptance,
         "exchange_acceptance_mean": exchange_mean,
         "effective_sample_size": summary.effective_sample_size,
+        "ess": summary.ess,
+        "ess_short_chain": summary.ess_short_chain,
     });
     write_json(out_dir.join("coverage_summary.json"), &payload)
 }
@@ -544,6 +567,8 @@ fn load_dispersion_job(
             let options = DispersionOptions {
                 steps,
                 tolerance: config.tolerance.unwrap_or(0.03),
+                per_species_tolerance: config.per_species_tolerance,
+                per_species_weight: config.per_species_weight,
             };
             return Ok((config.species, options));
         }
@@ -562,6 +587,8 @@ struct ParsedDispersionConfig {
     species: Vec<SpeciesId>,
     steps: Option<Vec<u32>>,
     tolerance: Option<f64>,
+    per_species_tolerance: BTreeMap<SpeciesId, f64>,
+    per_species_weight: BTreeMap<SpeciesId, f64>,
 }
 
 fn parse_dispersion_config(contents: &str) -> Result<ParsedDispersionConfig, Box<dyn Error>> {
@@ -582,10 +609,24 @@ fn parse_dispersion_config(contents: &str) -> Result<ParsedDispersionConfig, Box
         Some(set.into_iter().collect())
     };
 
+    let mut per_species_tolerance = BTreeMap::new();
+    let mut per_species_weight = BTreeMap::new();
+    for entry in file.overrides {
+        let species = parse_species_value(entry.species)?;
+        if let Some(tolerance) = entry.tolerance {
+            per_species_tolerance.insert(species, tolerance);
+        }
+        if let Some(weight) = entry.weight {
+            per_species_weight.insert(species, weight);
+        }
+    }
+
     Ok(ParsedDispersionConfig {
         species,
         steps,
         tolerance: file.tolerance,
+        per_species_tolerance,
+        per_species_weight,
     })
 }
 
@@ -625,14 +666,17 @@ fn species_file_stem(species: SpeciesId) -> String {
 fn write_common_c(
     out_dir: &Path,
     report: &DispersionReport,
-    tolerance: f64,
+    options: &DispersionOptions,
 ) -> Result<(), Box<dyn Error>> {
+    let checks = report.check_residuals(options);
     let payload = serde_json::json!({
         "common_c": report.common_c,
         "residuals": report.residuals,
         "diagnostics": report.diagnostics,
         "residual_max": residual_max(report),
-        "tolerance": tolerance,
+        "tolerance": options.tolerance,
+        "species_checks": checks,
+        "pass": checks.iter().all(|check| check.pass),
     });
     write_json(out_dir.join("common_c.json"), &payload)
 }
@@ -641,34 +685,48 @@ fn write_checkpoint_summary(
     out_dir: &Path,
     checkpoints: &[(String, DispersionReport)],
     final_report: &DispersionReport,
-    tolerance: f64,
+    options: &DispersionOptions,
 ) -> Result<(), Box<dyn Error>> {
     let path = out_dir.join("common_c_by_checkpoint.csv");
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
     let mut file = fs::File::create(path)?;
-    writeln!(file, "checkpoint_id,c,residual_max,tolerance")?;
+    writeln!(
+        file,
+        "checkpoint_id,c,residual_max,tolerance,species_failed"
+    )?;
     for (label, report) in checkpoints {
         writeln!(
             file,
-            "{},{:.6},{:.6},{:.6}",
+            "{},{:.6},{:.6},{:.6},{}",
             label,
             report.common_c,
             residual_max(report),
-            tolerance,
+            options.tolerance,
+            species_failed(report, options),
         )?;
     }
     writeln!(
         file,
-        "end_state,{:.6},{:.6},{:.6}",
+        "end_state,{:.6},{:.6},{:.6},{}",
         final_report.common_c,
         residual_max(final_report),
-        tolerance,
+        options.tolerance,
+        species_failed(final_report, options),
     )?;
     Ok(())
 }
 
+/// Number of species whose residual exceeds their own tolerance.
+fn species_failed(report: &DispersionReport, options: &DispersionOptions) -> usize {
+    report
+        .check_residuals(options)
+        .iter()
+        .filter(|check| !check.pass)
+        .count()
+}
+
 fn residual_max(report: &DispersionReport) -> f64 {
     report
         .residuals
@@ -34,6 +34,8 @@ fn make_opts(seed: u64) -> SpecOpts {
             iterations: 16,
             tolerance: 1e-6,
             seed: seed + 1,
+            emit_front_fit: false,
+            front_threshold: None,
         },
         dispersion,
         correlation: CorrelSpec::default(),
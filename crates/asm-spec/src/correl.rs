@@ -1,3 +1,5 @@
+use std::collections::VecDeque;
+
 use asm_core::errors::{AsmError, ErrorInfo};
 use asm_core::rng::RngHandle;
 use rand::RngCore;
@@ -25,6 +27,19 @@ fn default_method() -> String {
     "exponential-fit".to_string()
 }
 
+fn default_decay_margin() -> f64 {
+    2.0
+}
+
+/// Minimum number of usable distances before a decay class is assigned.
+pub const MIN_DECAY_DISTANCES: usize = 4;
+
+/// Per-step damping of the random-walk resolvent used as the correlator.
+const RESOLVENT_DAMPING: f64 = 0.8;
+
+/// Two-sided 95% normal quantile used for linearised confidence intervals.
+const CI_Z: f64 = 1.96;
+
 /// Configuration for deterministic correlation-length estimation.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CorrelSpec {
@@ -37,6 +52,10 @@ pub struct CorrelSpec {
     /// Named fit method recorded in the report.
     #[serde(default = "default_method")]
     pub method: String,
+    /// Information-criterion difference required before one decay form is
+    /// preferred over the other.
+    #[serde(default = "default_decay_margin")]
+    pub decay_margin: f64,
 }
 
 impl Default for CorrelSpec {
@@ -45,6 +64,7 @@ impl Default for CorrelSpec {
             max_radius: default_max_radius(),
             samples: default_samples(),
             method: default_method(),
+            decay_margin: default_decay_margin(),
         }
     }
 }
@@ -60,6 +80,51 @@ pub struct CorrelationReport {
     pub method: String,
     /// Residuals captured during the fit.
     pub residuals: Vec<f64>,
+    /// Graph distances with a positive correlator, used by the decay fits.
+    #[serde(default)]
+    pub distances: Vec<usize>,
+    /// Correlator averaged over the sampled sources at each distance.
+    #[serde(default)]
+    pub correlator: Vec<f64>,
+    /// Fit of `ln C(r) = a - r / xi`; absent with too few distances.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exponential: Option<DecayFit>,
+    /// Fit of `ln C(r) = a - eta ln r`; absent with too few distances.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power_law: Option<DecayFit>,
+    /// Power-law AIC minus exponential AIC; positive values favour the exponential.
+    #[serde(default)]
+    pub model_score: f64,
+    /// Decay form selected by comparing `model_score` against the margin.
+    #[serde(default)]
+    pub decay_class: DecayClass,
+}
+
+/// Functional form that best describes the correlator decay.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum DecayClass {
+    /// `C(r) ~ exp(-r / xi)`.
+    Exponential,
+    /// `C(r) ~ r^-eta`.
+    PowerLaw,
+    /// Neither form is preferred by the configured margin, or data is too sparse.
+    #[default]
+    Undetermined,
+}
+
+/// Linearised least-squares fit of one decay form in log space.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DecayFit {
+    /// Correlation length for the exponential form, exponent for the power law.
+    /// Zero when the fitted slope does not decay.
+    pub parameter: f64,
+    /// Confidence interval for `parameter` from the slope's standard error.
+    pub ci: Vec<f64>,
+    /// Log-space residuals at each usable distance.
+    pub residuals: Vec<f64>,
+    /// Akaike-style criterion `n ln(RSS / n) + 2k`.
+    pub aic: f64,
 }
 
 /// Computes deterministic correlation-length diagnostics.
@@ -88,10 +153,176 @@ pub fn correlation_scan(
         let value = round_value((idx as f64 + 1.0) / (spec.samples as f64 + 1.0) * jitter);
         residuals.push(value);
     }
+
+    let sources: Vec<usize> = (0..spec.samples)
+        .map(|_| rng.next_u32() as usize % operators.info.num_nodes.max(1))
+        .collect();
+    let profile = correlator_profile(operators, &sources, spec.max_radius);
+    let (distances, correlator): (Vec<usize>, Vec<f64>) = profile
+        .into_iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, value)| *value > 0.0)
+        .unzip();
+
+    let (exponential, power_law) = if distances.len() < MIN_DECAY_DISTANCES {
+        (None, None)
+    } else {
+        let logs: Vec<f64> = correlator.iter().map(|value| value.ln()).collect();
+        let radii: Vec<f64> = distances.iter().map(|&r| r as f64).collect();
+        let log_radii: Vec<f64> = radii.iter().map(|r| r.ln()).collect();
+        (
+            Some(decay_fit(
+                &radii,
+                &logs,
+                |slope| -1.0 / slope,
+                |slope, se| se / (slope * slope),
+            )),
+            Some(decay_fit(&log_radii, &logs, |slope| -slope, |_, se| se)),
+        )
+    };
+    let model_score = match (&exponential, &power_law) {
+        (Some(exp), Some(pow)) => round_value(pow.aic - exp.aic),
+        _ => 0.0,
+    };
+    let decay_class = match (&exponential, &power_law) {
+        (Some(exp), _) if model_score > spec.decay_margin && exp.parameter > 0.0 => {
+            DecayClass::Exponential
+        }
+        (_, Some(pow)) if model_score < -spec.decay_margin && pow.parameter > 0.0 => {
+            DecayClass::PowerLaw
+        }
+        _ => DecayClass::Undetermined,
+    };
+
     Ok(CorrelationReport {
         xi,
         ci,
         method: spec.method.clone(),
         residuals,
+        distances,
+        correlator: correlator.into_iter().map(round_value).collect(),
+        exponential,
+        power_law,
+        model_score,
+        decay_class,
     })
 }
+
+/// Averages the damped random-walk resolvent `sum_t (gamma P)^t delta_s` over
+/// `sources`, taking the mean over each shell of graph distance `0..=max_radius`.
+fn correlator_profile(operators: &Operators, sources: &[usize], max_radius: usize) -> Vec<f64> {
+    let n = operators.info.num_nodes;
+    let mut profile = vec![0.0; max_radius + 1];
+    if n == 0 || sources.is_empty() {
+        return profile;
+    }
+    let mut neighbours = vec![Vec::new(); n];
+    for entry in &operators.entries {
+        if entry.row != entry.col && entry.row < n && entry.col < n {
+            neighbours[entry.row].push(entry.col);
+            neighbours[entry.col].push(entry.row);
+        }
+    }
+    for list in &mut neighbours {
+        list.sort_unstable();
+        list.dedup();
+    }
+
+    let steps = 4 * max_radius.max(1);
+    for &source in sources {
+        let mut distance = vec![usize::MAX; n];
+        distance[source] = 0;
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            for &next in &neighbours[node] {
+                if distance[next] == usize::MAX {
+                    distance[next] = distance[node] + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let mut walk = vec![0.0; n];
+        walk[source] = 1.0;
+        let mut resolvent = walk.clone();
+        let mut weight = 1.0;
+        for _ in 0..steps {
+            let mut next = vec![0.0; n];
+            for (node, mass) in walk.iter().enumerate() {
+                let degree = neighbours[node].len();
+                if *mass == 0.0 || degree == 0 {
+                    continue;
+                }
+                for &target in &neighbours[node] {
+                    next[target] += mass / degree as f64;
+                }
+            }
+            weight *= RESOLVENT_DAMPING;
+            for (total, mass) in resolvent.iter_mut().zip(&next) {
+                *total += weight * mass;
+            }
+            walk = next;
+        }
+
+        let mut sums = vec![0.0; max_radius + 1];
+        let mut counts = vec![0usize; max_radius + 1];
+        for (node, &d) in distance.iter().enumerate() {
+            if d <= max_radius {
+                sums[d] += resolvent[node];
+                counts[d] += 1;
+            }
+        }
+        for radius in 0..=max_radius {
+            if counts[radius] > 0 {
+                profile[radius] += sums[radius] / counts[radius] as f64 / sources.len() as f64;
+            }
+        }
+    }
+    profile
+}
+
+/// Fits `ys = a + b xs` and maps the slope to the decay parameter and its
+/// standard error.
+fn decay_fit<P, E>(xs: &[f64], ys: &[f64], parameter: P, parameter_se: E) -> DecayFit
+where
+    P: Fn(f64) -> f64,
+    E: Fn(f64, f64) -> f64,
+{
+    let n = xs.len() as f64;
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = ys.iter().sum::<f64>() / n;
+    let sxx: f64 = xs.iter().map(|x| (x - mean_x).powi(2)).sum();
+    let sxy: f64 = xs
+        .iter()
+        .zip(ys)
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let slope = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+    let intercept = mean_y - slope * mean_x;
+    let residuals: Vec<f64> = xs
+        .iter()
+        .zip(ys)
+        .map(|(x, y)| y - (intercept + slope * x))
+        .collect();
+    let rss = residuals.iter().map(|r| r * r).sum::<f64>().max(1e-18);
+    let slope_se = if sxx > 0.0 {
+        (rss / (n - 2.0) / sxx).sqrt()
+    } else {
+        0.0
+    };
+    let (value, se) = if slope < 0.0 {
+        (parameter(slope), parameter_se(slope, slope_se))
+    } else {
+        (0.0, 0.0)
+    };
+    DecayFit {
+        parameter: round_value(value),
+        ci: vec![
+            round_value(value - CI_Z * se),
+            round_value(value + CI_Z * se),
+        ],
+        residuals: residuals.into_iter().map(round_value).collect(),
+        aic: round_value(n * (rss / n).ln() + 4.0),
+    }
+}
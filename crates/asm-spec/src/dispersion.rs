@@ -13,6 +13,9 @@ fn round_value(value: f64) -> f64 {
     (value * 1e9).round() / 1e9
 }
 
+/// Group velocity of the lowest synthetic mode, in hops per propagation step.
+pub(crate) const GROUP_VELOCITY: f64 = 0.1;
+
 fn default_k_points() -> usize {
     64
 }
@@ -116,7 +119,7 @@ pub fn dispersion_scan(
         let k_start = k_grid.first().copied().unwrap_or(0.0);
         let k_end = k_grid.last().copied().unwrap_or(1.0);
         let omega_start = modes[0].omega;
-        let omega_end = modes[0].omega + (k_end - k_start) * 0.1;
+        let omega_end = modes[0].omega + (k_end - k_start) * GROUP_VELOCITY;
         if (k_end - k_start).abs() < 1e-9 {
             0.0
         } else {
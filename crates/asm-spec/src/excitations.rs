@@ -1,9 +1,18 @@
+use std::collections::BTreeMap;
+
+use asm_code::css::into_parts;
+use asm_code::defect::species_from_pattern;
+use asm_code::{hash::canonical_code_hash, CSSCode, ConstraintKind, SpeciesId};
 use asm_core::errors::{AsmError, ErrorInfo};
-use asm_core::rng::RngHandle;
+use asm_core::rng::{derive_substream_seed, RngHandle};
+use asm_graph::{canonical_hash as graph_hash, HypergraphImpl};
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
-use crate::operators::Operators;
+use crate::dispersion::{dispersion_scan, DispersionSpec};
+use crate::hash::stable_hash_string;
+use crate::operators::{build_operators, OpOpts, Operators, OpsVariant};
+use crate::propagation::{excite_and_propagate, PropOpts};
 
 fn excitation_error(code: &str, message: impl Into<String>) -> AsmError {
     AsmError::Code(ErrorInfo::new(code, message))
@@ -13,8 +22,10 @@ fn default_support() -> usize {
     3
 }
 
+const NORMALISATION_TOLERANCE: f64 = 1e-9;
+
 /// Canonical excitation families supported by the spectrum analysis pipeline.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "kebab-case")]
 pub enum ExcitationKind {
     /// Localised defect probe with minimal support.
@@ -23,6 +34,21 @@ pub enum ExcitationKind {
     PlaneWave,
     /// Low-weight random probe seeded deterministically.
     RandomLowWeight,
+    /// Coherent superposition of component excitations with normalised
+    /// amplitudes; the spec's `support` and `plane_wave_k` are ignored.
+    Superposition(Vec<SuperpositionComponent>),
+}
+
+/// Weighted component of an [`ExcitationKind::Superposition`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SuperpositionComponent {
+    /// Component excitation seeded independently before combination.
+    pub spec: ExcitationSpec,
+    /// Modulus of the complex weight.
+    pub amplitude: f64,
+    /// Phase of the complex weight in radians.
+    #[serde(default)]
+    pub phase: f64,
 }
 
 #[allow(clippy::derivable_impls)]
@@ -119,18 +145,312 @@ fn select_random_low_weight(operators: &Operators, support: usize, seed: u64) ->
     nodes.into_iter().take(support.min(len)).collect()
 }
 
-pub(crate) fn excitation_support(
+fn validate_components(components: &[SuperpositionComponent]) -> Result<(), AsmError> {
+    if components.is_empty() {
+        return Err(excitation_error(
+            "empty-superposition",
+            "superposition excitations require at least one component",
+        ));
+    }
+    let mut norm = 0.0;
+    for component in components {
+        if !component.amplitude.is_finite() || !component.phase.is_finite() {
+            return Err(excitation_error(
+                "invalid-weight",
+                "superposition weights must be finite",
+            ));
+        }
+        norm += component.amplitude * component.amplitude;
+    }
+    if (norm - 1.0).abs() > NORMALISATION_TOLERANCE {
+        return Err(excitation_error(
+            "unnormalised-superposition",
+            format!("squared superposition amplitudes sum to {norm}, expected 1"),
+        ));
+    }
+    Ok(())
+}
+
+/// Combines component supports into a single weighted initial state.
+///
+/// Component `idx` is seeded from substream `idx + 1` of `seed`. Each node
+/// accumulates `amplitude * exp(i * phase)` from every component containing it
+/// and the modulus of the sum becomes the node weight. Nodes are returned in
+/// ascending identifier order.
+fn superposition_profile(
+    operators: &Operators,
+    components: &[SuperpositionComponent],
+    seed: u64,
+) -> Result<Vec<(u64, f64)>, AsmError> {
+    validate_components(components)?;
+    let mut weights: BTreeMap<u64, (f64, f64)> = BTreeMap::new();
+    for (idx, component) in components.iter().enumerate() {
+        let component_seed = derive_substream_seed(seed, idx as u64 + 1);
+        for (node, weight) in excitation_profile(operators, &component.spec, component_seed)? {
+            let entry = weights.entry(node).or_insert((0.0, 0.0));
+            entry.0 += weight * component.amplitude * component.phase.cos();
+            entry.1 += weight * component.amplitude * component.phase.sin();
+        }
+    }
+    Ok(weights
+        .into_iter()
+        .map(|(node, (re, im))| (node, (re * re + im * im).sqrt()))
+        .collect())
+}
+
+/// Returns the excitation support together with the initial weight per node.
+///
+/// Single-family excitations carry unit weight on every selected node.
+pub(crate) fn excitation_profile(
     operators: &Operators,
     spec: &ExcitationSpec,
     seed: u64,
-) -> Result<Vec<u64>, AsmError> {
-    let support = ensure_support_size(operators.node_degrees.len(), spec.support)?;
-    let nodes = match spec.kind {
-        ExcitationKind::LocalDefect => select_local_defect(operators, support),
+) -> Result<Vec<(u64, f64)>, AsmError> {
+    let nodes = match &spec.kind {
+        ExcitationKind::LocalDefect => {
+            let support = ensure_support_size(operators.node_degrees.len(), spec.support)?;
+            select_local_defect(operators, support)
+        }
         ExcitationKind::PlaneWave => {
+            let support = ensure_support_size(operators.node_degrees.len(), spec.support)?;
             select_plane_wave(operators, support, spec.plane_wave_k.unwrap_or(0))
         }
-        ExcitationKind::RandomLowWeight => select_random_low_weight(operators, support, seed),
+        ExcitationKind::RandomLowWeight => {
+            let support = ensure_support_size(operators.node_degrees.len(), spec.support)?;
+            select_random_low_weight(operators, support, seed)
+        }
+        ExcitationKind::Superposition(components) => {
+            return superposition_profile(operators, components, seed);
+        }
+    };
+    Ok(nodes.into_iter().map(|node| (node, 1.0)).collect())
+}
+
+fn round_value(value: f64) -> f64 {
+    (value * 1e9).round() / 1e9
+}
+
+/// Options controlling [`species_scan`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpeciesScanOpts {
+    /// Operator construction options shared by every species.
+    #[serde(default)]
+    pub ops: OpOpts,
+    /// Propagation configuration; each species propagates on a substream of
+    /// `propagation.seed`.
+    pub propagation: PropOpts,
+    /// Dispersion scan providing the band gap shared by all species.
+    #[serde(default)]
+    pub dispersion: DispersionSpec,
+    /// Master seed used to derive the dispersion substream.
+    pub master_seed: u64,
+}
+
+/// Gap estimate for a single defect species.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpeciesGap {
+    /// Catalogued species identifier.
+    pub species: SpeciesId,
+    /// Stabiliser family of the check defining the species (`x` or `z`).
+    pub check_kind: String,
+    /// Index of the defining check within its family.
+    pub check_index: usize,
+    /// Node the excitation was seeded on.
+    pub site: u64,
+    /// Excitation support size.
+    pub support: usize,
+    /// Dispersion gap proxy offset by the mean response amplitude.
+    pub gap_proxy: f64,
+    /// Spread between the largest and smallest response amplitudes.
+    pub bandwidth: f64,
+    /// Hash of the propagated response.
+    pub response_hash: String,
+}
+
+/// Outcome of a species scan.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "status", rename_all = "kebab-case")]
+pub enum SpeciesScanOutcome {
+    /// The code catalogues no defect species.
+    EmptyCatalog,
+    /// Every catalogued species was excited.
+    Scanned {
+        /// Species sorted by ascending gap proxy, ties by species identifier.
+        table: Vec<SpeciesGap>,
+        /// Species carrying the smallest gap proxy.
+        lightest: SpeciesGap,
+    },
+}
+
+/// Provenance metadata bundled with a [`SpeciesScanReport`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpeciesScanProvenance {
+    /// Master deterministic seed for the scan.
+    pub master_seed: u64,
+    /// Base propagation seed.
+    pub propagation_seed: u64,
+    /// Seed used during the dispersion scan.
+    pub dispersion_seed: u64,
+    /// Operator variant used for construction.
+    pub ops_variant: OpsVariant,
+}
+
+/// Per-species excitation gap table for a state.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpeciesScanReport {
+    /// Content addressed hash of the scan.
+    pub scan_hash: String,
+    /// Canonical hash of the input graph.
+    pub graph_hash: String,
+    /// Canonical hash of the input code.
+    pub code_hash: String,
+    /// Hash of the operators shared by all species.
+    pub operators_hash: String,
+    /// Gap proxy of the state-wide dispersion scan.
+    pub dispersion_gap_proxy: f64,
+    /// Scan table, or the empty-catalog marker.
+    pub outcome: SpeciesScanOutcome,
+    /// Provenance information describing deterministic seeds and knobs.
+    pub provenance: SpeciesScanProvenance,
+    /// Rounding granularity used for floats.
+    pub rounding: f64,
+}
+
+impl SpeciesScanReport {
+    /// Returns the minimal-gap species, if any species were scanned.
+    pub fn lightest(&self) -> Option<&SpeciesGap> {
+        match &self.outcome {
+            SpeciesScanOutcome::EmptyCatalog => None,
+            SpeciesScanOutcome::Scanned { lightest, .. } => Some(lightest),
+        }
+    }
+
+    /// Returns the scanned species sorted by gap proxy.
+    pub fn table(&self) -> &[SpeciesGap] {
+        match &self.outcome {
+            SpeciesScanOutcome::EmptyCatalog => &[],
+            SpeciesScanOutcome::Scanned { table, .. } => table,
+        }
+    }
+}
+
+/// Defining check of each catalogued species, mirroring the catalog
+/// construction in `asm-code`.
+fn species_checks(code: &CSSCode) -> BTreeMap<SpeciesId, (ConstraintKind, usize, Vec<usize>)> {
+    let (_, x_checks, z_checks, ..) = into_parts(code);
+    let mut checks = BTreeMap::new();
+    for (kind, family) in [(ConstraintKind::X, x_checks), (ConstraintKind::Z, z_checks)] {
+        for (idx, constraint) in family.iter().enumerate() {
+            let species = species_from_pattern(kind, std::slice::from_ref(&idx));
+            checks.insert(species, (kind, idx, constraint.variables().to_vec()));
+        }
+    }
+    checks
+}
+
+/// Excites every species in `code.species_catalog()` at a canonical site and
+/// tabulates a per-species excitation gap proxy.
+///
+/// Operators and the dispersion scan are shared by all species. Variable `v`
+/// lives on node `v`, and a species is seeded as a plane-wave excitation
+/// starting at the lowest node touched by its defining check (the lowest node
+/// overall when the check touches none), with support equal to the check
+/// weight. The gap proxy adds the mean propagated amplitude to the dispersion
+/// gap proxy.
+pub fn species_scan(
+    graph: &HypergraphImpl,
+    code: &CSSCode,
+    opts: &SpeciesScanOpts,
+) -> Result<SpeciesScanReport, AsmError> {
+    if opts.propagation.seed == 0 {
+        return Err(excitation_error(
+            "missing-propagation-seed",
+            "propagation seed must be provided in SpeciesScanOpts",
+        ));
+    }
+    let operators = build_operators(graph, code, &opts.ops)?;
+    let dispersion_seed = derive_substream_seed(opts.master_seed, 1);
+    let dispersion = dispersion_scan(&operators, &opts.dispersion, dispersion_seed)?;
+
+    let catalog = code.species_catalog();
+    let outcome = if catalog.is_empty() {
+        SpeciesScanOutcome::EmptyCatalog
+    } else {
+        let mut nodes: Vec<u64> = operators
+            .node_degrees
+            .iter()
+            .map(|entry| entry.node)
+            .collect();
+        nodes.sort_unstable();
+        ensure_support_size(nodes.len(), 1)?;
+        let checks = species_checks(code);
+
+        let mut table = Vec::with_capacity(catalog.len());
+        for species in catalog {
+            let Some((kind, check_index, variables)) = checks.get(&species) else {
+                continue;
+            };
+            let position = variables
+                .iter()
+                .filter_map(|var| nodes.binary_search(&(*var as u64)).ok())
+                .min()
+                .unwrap_or(0);
+            let support = variables.len().clamp(1, nodes.len());
+            let spec = ExcitationSpec {
+                kind: ExcitationKind::PlaneWave,
+                support,
+                plane_wave_k: Some(position),
+            };
+            let propagation = PropOpts {
+                seed: derive_substream_seed(opts.propagation.seed, species.as_raw()),
+                ..opts.propagation.clone()
+            };
+            let response = excite_and_propagate(&operators, &spec, &propagation)?;
+            let amplitudes = &response.amplitudes;
+            let mean = amplitudes.iter().sum::<f64>() / amplitudes.len() as f64;
+            let max = amplitudes.iter().copied().fold(f64::MIN, f64::max);
+            let min = amplitudes.iter().copied().fold(f64::MAX, f64::min);
+
+            table.push(SpeciesGap {
+                species,
+                check_kind: match kind {
+                    ConstraintKind::X => "x".to_string(),
+                    ConstraintKind::Z => "z".to_string(),
+                },
+                check_index: *check_index,
+                site: nodes[position],
+                support,
+                gap_proxy: round_value(dispersion.gap_proxy + mean),
+                bandwidth: round_value(max - min),
+                response_hash: response.response_hash,
+            });
+        }
+        table.sort_by(|a, b| {
+            a.gap_proxy
+                .total_cmp(&b.gap_proxy)
+                .then(a.species.cmp(&b.species))
+        });
+        match table.first().cloned() {
+            Some(lightest) => SpeciesScanOutcome::Scanned { table, lightest },
+            None => SpeciesScanOutcome::EmptyCatalog,
+        }
+    };
+
+    let mut report = SpeciesScanReport {
+        scan_hash: String::new(),
+        graph_hash: graph_hash(graph)?,
+        code_hash: canonical_code_hash(code),
+        operators_hash: operators.info.hash.clone(),
+        dispersion_gap_proxy: dispersion.gap_proxy,
+        outcome,
+        provenance: SpeciesScanProvenance {
+            master_seed: opts.master_seed,
+            propagation_seed: opts.propagation.seed,
+            dispersion_seed,
+            ops_variant: opts.ops.variant,
+        },
+        rounding: 1e-9,
     };
-    Ok(nodes)
+    report.scan_hash = stable_hash_string(&report)?;
+    Ok(report)
 }
@@ -7,13 +7,26 @@ pub mod hash;
 pub mod operators;
 pub mod propagation;
 pub mod report;
+pub mod schema;
 pub mod serde;
 
-pub use correl::{correlation_scan, CorrelSpec, CorrelationReport};
+pub use correl::{correlation_scan, CorrelSpec, CorrelationReport, DecayClass, DecayFit};
 pub use dispersion::{dispersion_scan, DispersionMode, DispersionReport, DispersionSpec};
-pub use excitations::{ExcitationKind, ExcitationSpec};
-pub use hash::stable_hash_string;
+pub use excitations::{
+    species_scan, ExcitationKind, ExcitationSpec, SpeciesGap, SpeciesScanOpts, SpeciesScanOutcome,
+    SpeciesScanProvenance, SpeciesScanReport, SuperpositionComponent,
+};
+pub use hash::{stable_hash_string, stable_hash_with_domain};
 pub use operators::{build_operators, OpOpts, OperatorEntry, Operators, OperatorsInfo, OpsVariant};
-pub use propagation::{excite_and_propagate, PropOpts, Response};
-pub use report::{analyze_spectrum, SpecOpts, SpectrumProvenance, SpectrumReport};
-pub use serde::{from_json_slice, to_canonical_json_bytes};
+pub use propagation::{
+    excite_and_propagate, response_spectrum, FrontCrossing, FrontFit, PropOpts, Response,
+    ResponseTrace, SpectralFunction, SpectrumOpts, SpectrumWindow,
+};
+pub use report::{
+    analyze_spectrum, recompute_analysis_hash, SpecOpts, SpectrumProvenance, SpectrumReport,
+};
+pub use schema::{
+    MigrationLog, MigrationStep, SpectrumMigration, LEGACY_SPECTRUM_SCHEMA_VERSION,
+    SPECTRUM_MIGRATIONS, SPECTRUM_SCHEMA_VERSION,
+};
+pub use serde::{from_json_slice, from_json_slice_versioned, to_canonical_json_bytes};
@@ -41,12 +41,24 @@ pub struct OpOpts {
     /// Variant to use when building the operator matrices.
     #[serde(default)]
     pub variant: OpsVariant,
+    /// Per-node weights keyed by raw node id, e.g. RG node populations. An
+    /// entry between nodes `i` and `j` is scaled by `1 / sqrt(w_i * w_j)`;
+    /// nodes without a weight count as `1`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub node_weights: BTreeMap<u64, f64>,
+    /// Restricts construction to edges carrying this label, e.g. to build the
+    /// gauge sector apart from the matter sector. Every node is kept, so
+    /// operators of different sectors share one index space.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edge_label: Option<String>,
 }
 
 impl Default for OpOpts {
     fn default() -> Self {
         Self {
             variant: OpsVariant::Default,
+            node_weights: BTreeMap::new(),
+            edge_label: None,
         }
     }
 }
@@ -76,7 +88,7 @@ pub struct NodeSummary {
 pub struct OperatorsInfo {
     /// Number of nodes in the originating graph.
     pub num_nodes: usize,
-    /// Number of hyperedges present in the graph.
+    /// Number of hyperedges that contributed to the operator.
     pub num_edges: usize,
     /// Number of non-zero entries in the assembled operator.
     pub nnz: usize,
@@ -145,6 +157,40 @@ fn entry_weight(variant: OpsVariant, endpoints: &HyperedgeEndpoints) -> f64 {
     }
 }
 
+/// Resolves `weights` to `1 / sqrt(w)` per operator index, or `None` when no
+/// weights are given so unweighted operators stay bit-identical.
+fn node_scales(
+    node_map: &BTreeMap<u64, usize>,
+    weights: &BTreeMap<u64, f64>,
+) -> Result<Option<Vec<f64>>, AsmError> {
+    if weights.is_empty() {
+        return Ok(None);
+    }
+    let mut scales = vec![1.0; node_map.len()];
+    for (&node, &weight) in weights {
+        let Some(&idx) = node_map.get(&node) else {
+            return Err(AsmError::Graph(
+                ErrorInfo::new(
+                    "unknown-node",
+                    "node weight refers to a node outside the graph",
+                )
+                .with_context("node", node.to_string()),
+            ));
+        };
+        if !(weight.is_finite() && weight > 0.0) {
+            return Err(AsmError::Graph(
+                ErrorInfo::new(
+                    "invalid-node-weight",
+                    "node weights must be finite and positive",
+                )
+                .with_context("node", node.to_string()),
+            ));
+        }
+        scales[idx] = 1.0 / weight.sqrt();
+    }
+    Ok(Some(scales))
+}
+
 /// Builds deterministic sparse operators from the provided state description.
 pub fn build_operators(
     graph: &HypergraphImpl,
@@ -162,15 +208,25 @@ pub fn build_operators(
     for (idx, node) in nodes.iter().enumerate() {
         node_map.insert(node.as_raw(), idx);
     }
+    let scales = node_scales(&node_map, &opts.node_weights)?;
     let mut degrees = vec![0usize; nodes.len()];
     let mut entries = Vec::new();
     let mut edge_count = 0usize;
 
     for edge in graph.edges() {
+        if let Some(label) = &opts.edge_label {
+            if graph.edge_label(edge)? != Some(label.as_str()) {
+                continue;
+            }
+        }
         let endpoints = graph.hyperedge(edge)?;
         let pairs = collect_endpoints(&endpoints, &node_map, &mut degrees)?;
         let weight = entry_weight(opts.variant, &endpoints);
         for (row, col) in pairs {
+            let weight = match &scales {
+                Some(scales) => round_weight(weight * scales[row] * scales[col]),
+                None => weight,
+            };
             entries.push(OperatorEntry { row, col, weight });
         }
         edge_count += 1;
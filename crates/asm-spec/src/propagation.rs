@@ -1,9 +1,14 @@
+use std::collections::{BTreeMap, VecDeque};
+
 use asm_core::errors::{AsmError, ErrorInfo};
 use asm_core::rng::RngHandle;
+use asm_core::Hypergraph;
+use asm_graph::HypergraphImpl;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
-use crate::excitations::{excitation_support, ExcitationSpec};
+use crate::dispersion::GROUP_VELOCITY;
+use crate::excitations::{excitation_profile, ExcitationSpec};
 use crate::hash::stable_hash_string;
 use crate::operators::Operators;
 
@@ -34,6 +39,12 @@ pub struct PropOpts {
     pub tolerance: f64,
     /// Master seed used for deterministic stochastic probes.
     pub seed: u64,
+    /// Attach a [`FrontFit`] to the spectrum report.
+    #[serde(default)]
+    pub emit_front_fit: bool,
+    /// Amplitude threshold for the wavefront; defaults to half the peak amplitude.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub front_threshold: Option<f64>,
 }
 
 impl PropOpts {
@@ -56,6 +67,283 @@ pub struct Response {
     pub iterations: usize,
     /// Convergence tolerance used for the solve.
     pub tolerance: f64,
+    /// Per-step amplitudes of every operator node during propagation.
+    #[serde(default)]
+    pub trace: ResponseTrace,
+}
+
+/// Time-resolved amplitudes recorded by [`excite_and_propagate`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ResponseTrace {
+    /// Node identifiers in operator index order.
+    pub nodes: Vec<u64>,
+    /// Amplitude of every node in `nodes`, one row per step starting at step zero.
+    pub steps: Vec<Vec<f64>>,
+}
+
+impl ResponseTrace {
+    /// Total amplitude summed over all nodes at each step.
+    pub fn totals(&self) -> Vec<f64> {
+        self.steps.iter().map(|row| row.iter().sum()).collect()
+    }
+}
+
+/// Evolves the seeded amplitudes for `iterations` steps on the operator graph.
+///
+/// Each step every node relaxes toward its strongest neighbour at rate
+/// [`GROUP_VELOCITY`], `u' = u + c · max(0, max_w u_w − u)`, so the excitation
+/// spreads as a front whose speed is set by the dynamics rather than assumed.
+fn propagate_trace(
+    operators: &Operators,
+    support: &[u64],
+    amplitudes: &[f64],
+    iterations: usize,
+) -> ResponseTrace {
+    let nodes: Vec<u64> = operators
+        .node_degrees
+        .iter()
+        .map(|entry| entry.node)
+        .collect();
+    let mut neighbours = vec![Vec::new(); nodes.len()];
+    for entry in &operators.entries {
+        if entry.row != entry.col && entry.row < nodes.len() && entry.col < nodes.len() {
+            neighbours[entry.row].push(entry.col);
+            neighbours[entry.col].push(entry.row);
+        }
+    }
+    for list in &mut neighbours {
+        list.sort_unstable();
+        list.dedup();
+    }
+
+    let mut state = vec![0.0; nodes.len()];
+    for (node, amplitude) in support.iter().zip(amplitudes) {
+        if let Some(idx) = nodes.iter().position(|candidate| candidate == node) {
+            state[idx] = *amplitude;
+        }
+    }
+    let mut steps = Vec::with_capacity(iterations + 1);
+    steps.push(state.clone());
+    for _ in 0..iterations {
+        let next: Vec<f64> = state
+            .iter()
+            .zip(&neighbours)
+            .map(|(&value, adjacent)| {
+                let strongest = adjacent.iter().map(|&idx| state[idx]).fold(value, f64::max);
+                round_value(value + GROUP_VELOCITY * (strongest - value))
+            })
+            .collect();
+        steps.push(next.clone());
+        state = next;
+    }
+    ResponseTrace { nodes, steps }
+}
+
+/// First threshold crossing recorded for a node reached by the wavefront.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FrontCrossing {
+    /// Canonical node identifier.
+    pub node: u64,
+    /// Graph distance in hops from the excitation site.
+    pub distance: usize,
+    /// First propagation step at which the amplitude reached the threshold.
+    pub step: usize,
+}
+
+/// Ballistic wavefront fit `distance ≈ velocity · step + intercept`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FrontFit {
+    /// Node the excitation was seeded on.
+    pub source: Option<u64>,
+    /// Amplitude threshold defining the front.
+    pub threshold: f64,
+    /// Peak amplitude of the response.
+    pub max_amplitude: f64,
+    /// Set when the threshold exceeds the peak amplitude.
+    pub threshold_above_max: bool,
+    /// Whether enough distinct crossings were available for a fit.
+    pub fit_defined: bool,
+    /// Fitted front velocity in hops per step.
+    pub velocity: Option<f64>,
+    /// Fitted distance at step zero.
+    pub intercept: Option<f64>,
+    /// Root-mean-square distance residual of the fit.
+    pub residual: Option<f64>,
+    /// Crossings ordered by node identifier.
+    pub crossings: Vec<FrontCrossing>,
+    /// Reachable nodes that never reach the threshold within the run.
+    pub never_crossed: Vec<u64>,
+    /// Nodes unreachable from the excitation site, excluded from the fit.
+    pub disconnected: usize,
+}
+
+fn hop_distances(graph: &HypergraphImpl, source: u64) -> BTreeMap<u64, usize> {
+    let mut adjacency: BTreeMap<u64, Vec<u64>> = BTreeMap::new();
+    for edge in graph.edges() {
+        let Ok(endpoints) = graph.hyperedge(edge) else {
+            continue;
+        };
+        let members: Vec<u64> = endpoints
+            .sources
+            .iter()
+            .chain(endpoints.destinations.iter())
+            .map(|node| node.as_raw())
+            .collect();
+        for &a in &members {
+            for &b in &members {
+                if a != b {
+                    adjacency.entry(a).or_default().push(b);
+                }
+            }
+        }
+    }
+    let mut distances = BTreeMap::new();
+    distances.insert(source, 0usize);
+    let mut queue = VecDeque::from([source]);
+    while let Some(node) = queue.pop_front() {
+        let next = distances[&node] + 1;
+        for &neighbour in adjacency.get(&node).into_iter().flatten() {
+            if let std::collections::btree_map::Entry::Vacant(slot) = distances.entry(neighbour) {
+                slot.insert(next);
+                queue.push_back(neighbour);
+            }
+        }
+    }
+    distances
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        0.5 * (values[mid - 1] + values[mid])
+    } else {
+        values[mid]
+    }
+}
+
+/// Theil–Sen fit of distance against the median crossing step per distance shell.
+fn theil_sen(crossings: &[FrontCrossing]) -> Option<(f64, f64, f64)> {
+    let mut shells: BTreeMap<usize, Vec<f64>> = BTreeMap::new();
+    for crossing in crossings {
+        shells
+            .entry(crossing.distance)
+            .or_default()
+            .push(crossing.step as f64);
+    }
+    let points: Vec<(f64, f64)> = shells
+        .into_iter()
+        .map(|(distance, mut steps)| (median(&mut steps), distance as f64))
+        .collect();
+    let mut slopes = Vec::new();
+    for (i, &(t_i, d_i)) in points.iter().enumerate() {
+        for &(t_j, d_j) in &points[i + 1..] {
+            if (t_j - t_i).abs() > f64::EPSILON {
+                slopes.push((d_j - d_i) / (t_j - t_i));
+            }
+        }
+    }
+    if slopes.is_empty() {
+        return None;
+    }
+    let velocity = median(&mut slopes);
+    let mut offsets: Vec<f64> = points.iter().map(|(t, d)| d - velocity * t).collect();
+    let intercept = median(&mut offsets);
+    let sum_sq: f64 = crossings
+        .iter()
+        .map(|c| {
+            let err = c.distance as f64 - (velocity * c.step as f64 + intercept);
+            err * err
+        })
+        .sum();
+    let residual = (sum_sq / crossings.len() as f64).sqrt();
+    Some((velocity, intercept, residual))
+}
+
+impl Response {
+    /// Fits the ballistic wavefront of the response on `graph`.
+    ///
+    /// The excitation site is the first support node. Each node records the
+    /// first step of [`Response::trace`] at which its amplitude reaches
+    /// `threshold`; the excitation site is pinned to step zero. Nodes missing
+    /// from the trace never cross.
+    pub fn front_fit(&self, graph: &HypergraphImpl, threshold: f64) -> FrontFit {
+        let max_amplitude = self
+            .trace
+            .steps
+            .iter()
+            .flatten()
+            .chain(&self.amplitudes)
+            .copied()
+            .fold(0.0_f64, f64::max);
+        let threshold_above_max = threshold > max_amplitude;
+        let source = self.support.first().copied();
+        let mut fit = FrontFit {
+            source,
+            threshold: round_value(threshold),
+            max_amplitude: round_value(max_amplitude),
+            threshold_above_max,
+            fit_defined: false,
+            velocity: None,
+            intercept: None,
+            residual: None,
+            crossings: Vec::new(),
+            never_crossed: Vec::new(),
+            disconnected: 0,
+        };
+        let Some(source) = source else {
+            fit.disconnected = graph.nodes().len();
+            return fit;
+        };
+
+        let distances = hop_distances(graph, source);
+        let columns: BTreeMap<u64, usize> = self
+            .trace
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, &node)| (node, idx))
+            .collect();
+        for node in graph.nodes() {
+            let node = node.as_raw();
+            let Some(&distance) = distances.get(&node) else {
+                fit.disconnected += 1;
+                continue;
+            };
+            let step = if distance == 0 {
+                Some(0)
+            } else if threshold_above_max {
+                None
+            } else {
+                columns.get(&node).and_then(|&column| {
+                    self.trace
+                        .steps
+                        .iter()
+                        .position(|row| row.get(column).is_some_and(|&value| value >= threshold))
+                })
+            };
+            match step {
+                Some(step) => fit.crossings.push(FrontCrossing {
+                    node,
+                    distance,
+                    step,
+                }),
+                None => fit.never_crossed.push(node),
+            }
+        }
+        fit.crossings.sort_by_key(|crossing| crossing.node);
+        fit.never_crossed.sort_unstable();
+
+        if !threshold_above_max {
+            if let Some((velocity, intercept, residual)) = theil_sen(&fit.crossings) {
+                fit.fit_defined = true;
+                fit.velocity = Some(round_value(velocity));
+                fit.intercept = Some(round_value(intercept));
+                fit.residual = Some(round_value(residual));
+            }
+        }
+        fit
+    }
 }
 
 /// Seeds an excitation and computes a deterministic linear response profile.
@@ -64,8 +352,8 @@ pub fn excite_and_propagate(
     spec: &ExcitationSpec,
     opts: &PropOpts,
 ) -> Result<Response, AsmError> {
-    let support = excitation_support(operators, spec, opts.substream_seed(0))?;
-    if support.is_empty() {
+    let profile = excitation_profile(operators, spec, opts.substream_seed(0))?;
+    if profile.is_empty() {
         return Err(propagation_error(
             "empty-support",
             "excitation produced an empty support set",
@@ -79,15 +367,18 @@ pub fn excite_and_propagate(
         operators.info.avg_degree
     };
     let denom = (opts.iterations as f64).max(1.0);
-    let mut amplitudes = Vec::with_capacity(support.len());
-    for (idx, node) in support.iter().enumerate() {
+    let mut support = Vec::with_capacity(profile.len());
+    let mut amplitudes = Vec::with_capacity(profile.len());
+    for (idx, (node, weight)) in profile.into_iter().enumerate() {
         let jitter = (rng.next_u32() as f64) / (u32::MAX as f64);
         let scaled = ((node + 1) as f64 / denom) + jitter * opts.tolerance;
-        let amplitude = round_value(scaled / base_scale.max(1e-9));
+        let amplitude = round_value(weight * scaled / base_scale.max(1e-9));
         amplitudes.push(amplitude + round_value(idx as f64 * 1e-3));
+        support.push(node);
     }
 
     let response_hash = stable_hash_string(&(support.clone(), &amplitudes))?;
+    let trace = propagate_trace(operators, &support, &amplitudes, opts.iterations);
 
     Ok(Response {
         support,
@@ -95,5 +386,210 @@ pub fn excite_and_propagate(
         response_hash,
         iterations: opts.iterations,
         tolerance: round_value(opts.tolerance),
+        trace,
+    })
+}
+
+fn spectrum_error(code: &str, message: impl Into<String>) -> AsmError {
+    AsmError::Dictionary(ErrorInfo::new(code, message))
+}
+
+fn default_time_step() -> f64 {
+    1.0
+}
+
+fn default_pad_factor() -> usize {
+    2
+}
+
+fn default_max_peaks() -> usize {
+    3
+}
+
+/// Window applied to the response time series before transforming.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SpectrumWindow {
+    /// Hann window `w[n] = 0.5 - 0.5 cos(2 pi n / (N - 1))`.
+    #[default]
+    Hann,
+    /// Rectangular window (no tapering).
+    Rectangular,
+}
+
+/// Options controlling the deterministic frequency-domain transform.
+///
+/// The total amplitude of a [`ResponseTrace`] is treated as uniformly sampled
+/// with spacing `time_step`. The series is mean-subtracted, multiplied by `window`
+/// and then zero-padded at the end to the next power of two that is at least
+/// `pad_factor` times the original length. The padded length fixes the omega
+/// grid, so identical inputs and options always produce identical bins.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpectrumOpts {
+    /// Window applied prior to the transform.
+    #[serde(default)]
+    pub window: SpectrumWindow,
+    /// Sampling interval of the amplitude series.
+    #[serde(default = "default_time_step")]
+    pub time_step: f64,
+    /// Minimum zero-padding multiplier applied to the series length.
+    #[serde(default = "default_pad_factor")]
+    pub pad_factor: usize,
+    /// Maximum number of spectral peaks to report.
+    #[serde(default = "default_max_peaks")]
+    pub max_peaks: usize,
+}
+
+impl Default for SpectrumOpts {
+    fn default() -> Self {
+        Self {
+            window: SpectrumWindow::default(),
+            time_step: default_time_step(),
+            pad_factor: default_pad_factor(),
+            max_peaks: default_max_peaks(),
+        }
+    }
+}
+
+/// Frequency-domain view of a propagated response.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpectralFunction {
+    /// Angular frequency bins covering `[0, pi / time_step]`.
+    pub omega: Vec<f64>,
+    /// Normalised spectral intensity per bin (maximum equals one).
+    pub intensity: Vec<f64>,
+    /// Angular frequencies of detected local maxima ordered by intensity.
+    pub peaks: Vec<f64>,
+    /// Window applied before the transform.
+    pub window: SpectrumWindow,
+    /// Length of the zero-padded series fed to the transform.
+    pub padded_len: usize,
+}
+
+fn window_weight(window: SpectrumWindow, idx: usize, len: usize) -> f64 {
+    match window {
+        SpectrumWindow::Rectangular => 1.0,
+        SpectrumWindow::Hann if len <= 1 => 1.0,
+        SpectrumWindow::Hann => {
+            let phase = 2.0 * std::f64::consts::PI * idx as f64 / (len - 1) as f64;
+            0.5 - 0.5 * phase.cos()
+        }
+    }
+}
+
+/// In-place iterative radix-2 Cooley–Tukey transform; `re.len()` must be a power of two.
+fn fft_radix2(re: &mut [f64], im: &mut [f64]) {
+    let len = re.len();
+    let mut j = 0;
+    for i in 1..len {
+        let mut bit = len >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+    let mut size = 2;
+    while size <= len {
+        let angle = -2.0 * std::f64::consts::PI / size as f64;
+        for start in (0..len).step_by(size) {
+            for k in 0..size / 2 {
+                let (sin, cos) = (angle * k as f64).sin_cos();
+                let (a, b) = (start + k, start + k + size / 2);
+                let t_re = re[b] * cos - im[b] * sin;
+                let t_im = re[b] * sin + im[b] * cos;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+        size <<= 1;
+    }
+}
+
+/// Computes the spectral function `A(omega)` of a propagated response.
+///
+/// The signal is the total amplitude per step of [`Response::trace`], one
+/// sample every `opts.time_step`, transformed with a radix-2 FFT.
+pub fn response_spectrum(
+    response: &Response,
+    opts: &SpectrumOpts,
+) -> Result<SpectralFunction, AsmError> {
+    let signal = response.trace.totals();
+    if signal.is_empty() {
+        return Err(spectrum_error(
+            "empty-response",
+            "cannot transform a response without a time trace",
+        ));
+    }
+    if !(opts.time_step.is_finite() && opts.time_step > 0.0) {
+        return Err(spectrum_error(
+            "invalid-time-step",
+            format!("time step must be positive, got {}", opts.time_step),
+        ));
+    }
+
+    let len = signal.len();
+    let padded_len = (len * opts.pad_factor.max(1)).next_power_of_two();
+    let mean = signal.iter().sum::<f64>() / len as f64;
+    let mut re = vec![0.0; padded_len];
+    let mut im = vec![0.0; padded_len];
+    for (idx, value) in signal.iter().enumerate() {
+        re[idx] = (value - mean) * window_weight(opts.window, idx, len);
+    }
+    fft_radix2(&mut re, &mut im);
+
+    let bins = padded_len / 2 + 1;
+    let omega: Vec<f64> = (0..bins)
+        .map(|k| {
+            round_value(
+                2.0 * std::f64::consts::PI * k as f64 / (padded_len as f64 * opts.time_step),
+            )
+        })
+        .collect();
+    let power: Vec<f64> = (0..bins).map(|k| re[k] * re[k] + im[k] * im[k]).collect();
+
+    let max_power = power.iter().copied().fold(0.0_f64, f64::max);
+    let intensity: Vec<f64> = power
+        .iter()
+        .map(|value| {
+            if max_power > 0.0 {
+                round_value(value / max_power)
+            } else {
+                0.0
+            }
+        })
+        .collect();
+
+    let mut candidates: Vec<usize> = (0..bins)
+        .filter(|&k| {
+            let left = if k == 0 { f64::MIN } else { intensity[k - 1] };
+            let right = intensity.get(k + 1).copied().unwrap_or(f64::MIN);
+            intensity[k] > 0.0 && intensity[k] > left && intensity[k] >= right
+        })
+        .collect();
+    candidates.sort_by(|a, b| {
+        intensity[*b]
+            .partial_cmp(&intensity[*a])
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.cmp(b))
+    });
+    let peaks = candidates
+        .into_iter()
+        .take(opts.max_peaks)
+        .map(|k| omega[k])
+        .collect();
+
+    Ok(SpectralFunction {
+        omega,
+        intensity,
+        peaks,
+        window: opts.window,
+        padded_len,
     })
 }
@@ -1,13 +1,16 @@
 use asm_code::{hash::canonical_code_hash, CSSCode};
 use asm_core::errors::{AsmError, ErrorInfo};
+use asm_core::hash::{HashDomain, HASH_FORMAT};
+use asm_core::provenance::{build_fingerprint, BuildFingerprint};
 use asm_core::rng::derive_substream_seed;
 use asm_graph::{canonical_hash as graph_hash, HypergraphImpl};
 use serde::{Deserialize, Serialize};
 
 use crate::dispersion::{dispersion_scan, DispersionReport, DispersionSpec};
-use crate::hash::stable_hash_string;
+use crate::hash::{is_legacy_hash_format, legacy_hash_format, stable_hash_with_format};
 use crate::operators::{build_operators, OpOpts, Operators, OpsVariant};
-use crate::propagation::{excite_and_propagate, PropOpts};
+use crate::propagation::{excite_and_propagate, FrontFit, PropOpts};
+use crate::schema::{is_legacy_schema_version, legacy_schema_version, SPECTRUM_SCHEMA_VERSION};
 use crate::{correl::CorrelSpec, correl::CorrelationReport};
 
 fn report_error(code: &str, message: impl Into<String>) -> AsmError {
@@ -55,6 +58,12 @@ impl SpecOpts {
 /// Provenance metadata bundled with a [`SpectrumReport`].
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SpectrumProvenance {
+    /// Report schema version; mirrors [`SpectrumReport::schema_version`].
+    #[serde(
+        default = "legacy_schema_version",
+        skip_serializing_if = "is_legacy_schema_version"
+    )]
+    pub schema_version: u32,
     /// Source commit (if available) or package version.
     pub commit: String,
     /// Master deterministic seed for the analysis.
@@ -71,11 +80,27 @@ pub struct SpectrumProvenance {
     pub ops_variant: OpsVariant,
     /// Deterministic hash of the intermediate linear response.
     pub response_hash: String,
+    /// Layout of `analysis_hash`; reports without it carry legacy bare digests.
+    #[serde(
+        default = "legacy_hash_format",
+        skip_serializing_if = "is_legacy_hash_format"
+    )]
+    pub hash_format: u32,
+    /// Build that produced the report; excluded from `analysis_hash`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build: Option<BuildFingerprint>,
 }
 
 /// Deterministic spectrum analysis bundle.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SpectrumReport {
+    /// Schema version the report was written at; reports without it are
+    /// version 1. See [`crate::serde::from_json_slice_versioned`].
+    #[serde(
+        default = "legacy_schema_version",
+        skip_serializing_if = "is_legacy_schema_version"
+    )]
+    pub schema_version: u32,
     /// Content addressed hash of the full analysis artefact.
     pub analysis_hash: String,
     /// Canonical hash of the input graph.
@@ -90,6 +115,9 @@ pub struct SpectrumReport {
     pub correlation: CorrelationReport,
     /// Provenance information describing deterministic seeds and knobs.
     pub provenance: SpectrumProvenance,
+    /// Wavefront fit emitted when [`PropOpts::emit_front_fit`] is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub front_fit: Option<FrontFit>,
 }
 
 fn commit_string() -> String {
@@ -113,6 +141,13 @@ pub fn analyze_spectrum(
     }
     let operators = build_operators(graph, code, &sopts.ops)?;
     let response = excite_and_propagate(&operators, &sopts.excitation, &sopts.propagation)?;
+    let front_fit = sopts.propagation.emit_front_fit.then(|| {
+        let threshold = sopts
+            .propagation
+            .front_threshold
+            .unwrap_or_else(|| 0.5 * response.amplitudes.iter().copied().fold(0.0_f64, f64::max));
+        response.front_fit(graph, threshold)
+    });
     let dispersion = dispersion_scan(&operators, &sopts.dispersion, sopts.dispersion_seed())?;
     let correlation =
         crate::correl::correlation_scan(&operators, &sopts.correlation, sopts.correlation_seed())?;
@@ -124,6 +159,7 @@ pub fn analyze_spectrum(
     let code_hash = canonical_code_hash(code);
 
     let provenance = SpectrumProvenance {
+        schema_version: SPECTRUM_SCHEMA_VERSION,
         commit: commit_string(),
         master_seed: sopts.master_seed,
         propagation_seed: sopts.propagation.seed,
@@ -132,9 +168,12 @@ pub fn analyze_spectrum(
         fit_tolerance: sopts.fit_tolerance,
         ops_variant: sopts.ops.variant,
         response_hash: response.response_hash,
+        hash_format: HASH_FORMAT,
+        build: Some(build_fingerprint()),
     };
 
     let mut report = SpectrumReport {
+        schema_version: SPECTRUM_SCHEMA_VERSION,
         analysis_hash: String::new(),
         graph_hash,
         code_hash,
@@ -142,16 +181,36 @@ pub fn analyze_spectrum(
         dispersion,
         correlation,
         provenance,
+        front_fit,
     };
 
-    report.analysis_hash = stable_hash_string(&(
+    report.analysis_hash = recompute_analysis_hash(&report)?;
+
+    Ok(report)
+}
+
+/// Recomputes `analysis_hash` in the layout recorded by the report provenance.
+///
+/// Reports written before domain separation hash to their original bare
+/// digest, so both forms can be checked after [`crate::from_json_slice`].
+pub fn recompute_analysis_hash(report: &SpectrumReport) -> Result<String, AsmError> {
+    let format = report.provenance.hash_format;
+    let provenance = SpectrumProvenance {
+        build: None,
+        ..report.provenance.clone()
+    };
+    let base = (
         &report.graph_hash,
         &report.code_hash,
         &report.operators.info.hash,
         &report.dispersion,
         &report.correlation,
-        &report.provenance,
-    ))?;
-
-    Ok(report)
+        &provenance,
+    );
+    match &report.front_fit {
+        Some(front_fit) => {
+            stable_hash_with_format(format, HashDomain::Spectrum, &(base, front_fit))
+        }
+        None => stable_hash_with_format(format, HashDomain::Spectrum, &base),
+    }
 }
@@ -0,0 +1,213 @@
+use asm_core::errors::{AsmError, ErrorInfo};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Schema version written by [`crate::analyze_spectrum`].
+pub const SPECTRUM_SCHEMA_VERSION: u32 = 2;
+
+/// Version assumed for reports written before `schema_version` existed.
+pub const LEGACY_SPECTRUM_SCHEMA_VERSION: u32 = 1;
+
+pub(crate) fn legacy_schema_version() -> u32 {
+    LEGACY_SPECTRUM_SCHEMA_VERSION
+}
+
+pub(crate) fn is_legacy_schema_version(version: &u32) -> bool {
+    *version == LEGACY_SPECTRUM_SCHEMA_VERSION
+}
+
+fn schema_error(code: &str, message: impl Into<String>) -> AsmError {
+    AsmError::Serde(ErrorInfo::new(code, message))
+}
+
+/// One forward step of the spectrum report schema.
+#[derive(Debug, Clone, Copy)]
+pub struct SpectrumMigration {
+    pub from: u32,
+    pub to: u32,
+    pub description: &'static str,
+    /// Rewrites the raw report object in place and returns the dotted paths of
+    /// every field it synthesised.
+    pub apply: fn(&mut Map<String, Value>) -> Vec<String>,
+}
+
+/// Ordered migration registry.
+///
+/// Every schema bump adds exactly one step here with `from` equal to the
+/// previous [`SPECTRUM_SCHEMA_VERSION`]. Steps only fill fields that are
+/// absent, so a report that already carries a field keeps its recorded value
+/// and the field is not reported as synthesised.
+pub const SPECTRUM_MIGRATIONS: &[SpectrumMigration] = &[SpectrumMigration {
+    from: 1,
+    to: 2,
+    description: "correlation decay classification",
+    apply: migrate_v1_to_v2,
+}];
+
+/// Synthesises the correlation decay fields introduced in version 2.
+///
+/// Version 1 reports only recorded the exponential fit (`xi`, `ci`, `method`,
+/// `residuals`). Missing fields are filled as follows:
+///
+/// - `correlation.distances`: `[]`, no per-distance data was kept.
+/// - `correlation.correlator`: `[]`, likewise.
+/// - `correlation.model_score`: `0.0`, neither decay form is favoured.
+/// - `correlation.decay_class`: `"undetermined"`, so the report never claims
+///   a power-law or exponential decay it did not measure.
+///
+/// `exponential` and `power_law` stay absent, which already means the fit was
+/// not available.
+fn migrate_v1_to_v2(report: &mut Map<String, Value>) -> Vec<String> {
+    let mut synthesised = Vec::new();
+    if let Some(Value::Object(correlation)) = report.get_mut("correlation") {
+        let defaults = [
+            ("distances", Value::Array(Vec::new())),
+            ("correlator", Value::Array(Vec::new())),
+            ("model_score", Value::from(0.0)),
+            ("decay_class", Value::from("undetermined")),
+        ];
+        for (key, value) in defaults {
+            if !correlation.contains_key(key) {
+                correlation.insert(key.to_string(), value);
+                synthesised.push(format!("correlation.{key}"));
+            }
+        }
+    }
+    synthesised
+}
+
+/// Migration step applied while loading a report.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MigrationStep {
+    pub from: u32,
+    pub to: u32,
+    pub description: String,
+    /// Dotted paths of the fields filled with synthesised defaults.
+    pub synthesised: Vec<String>,
+}
+
+/// Record of the migrations applied by [`crate::serde::from_json_slice_versioned`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MigrationLog {
+    /// Schema version recorded in the input.
+    pub from_version: u32,
+    /// Schema version of the returned report.
+    pub to_version: u32,
+    /// Steps applied in order; empty when the input was already current.
+    pub steps: Vec<MigrationStep>,
+}
+
+impl MigrationLog {
+    /// Returns whether the input was written at an older schema version.
+    pub fn is_migrated(&self) -> bool {
+        self.from_version != self.to_version
+    }
+
+    /// Dotted paths of every synthesised field, in step order.
+    pub fn synthesised(&self) -> impl Iterator<Item = &str> {
+        self.steps
+            .iter()
+            .flat_map(|step| step.synthesised.iter().map(String::as_str))
+    }
+
+    /// Returns whether `field` holds a synthesised default rather than a
+    /// recorded value.
+    pub fn was_synthesised(&self, field: &str) -> bool {
+        self.synthesised().any(|path| path == field)
+    }
+}
+
+fn recorded_version(object: &Map<String, Value>) -> Result<Option<u32>, AsmError> {
+    match object.get("schema_version") {
+        None => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .map(Some)
+            .ok_or_else(|| {
+                schema_error(
+                    "spectrum-schema-invalid",
+                    format!("schema_version {value} is not a valid version"),
+                )
+            }),
+    }
+}
+
+/// Upgrades a raw spectrum report to [`SPECTRUM_SCHEMA_VERSION`].
+pub(crate) fn migrate_report(value: &mut Value) -> Result<MigrationLog, AsmError> {
+    let report = value.as_object_mut().ok_or_else(|| {
+        schema_error(
+            "spectrum-schema-invalid",
+            "spectrum report must be a JSON object",
+        )
+    })?;
+    let top = recorded_version(report)?;
+    let provenance = match report.get("provenance") {
+        Some(Value::Object(provenance)) => recorded_version(provenance)?,
+        _ => None,
+    };
+    if let (Some(top), Some(provenance)) = (top, provenance) {
+        if top != provenance {
+            return Err(AsmError::Serde(
+                ErrorInfo::new(
+                    "spectrum-schema-mismatch",
+                    format!(
+                        "report schema_version {top} differs from provenance schema_version {provenance}"
+                    ),
+                )
+                .with_context("report", top.to_string())
+                .with_context("provenance", provenance.to_string()),
+            ));
+        }
+    }
+    let from_version = top.or(provenance).unwrap_or(LEGACY_SPECTRUM_SCHEMA_VERSION);
+    if from_version > SPECTRUM_SCHEMA_VERSION {
+        return Err(AsmError::Serde(
+            ErrorInfo::new(
+                "spectrum-schema-newer",
+                format!(
+                    "spectrum report schema version {from_version} is newer than supported version {SPECTRUM_SCHEMA_VERSION}"
+                ),
+            )
+            .with_context("found", from_version.to_string())
+            .with_context("supported", SPECTRUM_SCHEMA_VERSION.to_string()),
+        ));
+    }
+    if from_version < LEGACY_SPECTRUM_SCHEMA_VERSION {
+        return Err(schema_error(
+            "spectrum-schema-invalid",
+            format!("spectrum report schema version {from_version} does not exist"),
+        ));
+    }
+
+    let mut steps = Vec::new();
+    let mut version = from_version;
+    while version < SPECTRUM_SCHEMA_VERSION {
+        let step = SPECTRUM_MIGRATIONS
+            .iter()
+            .find(|step| step.from == version)
+            .ok_or_else(|| {
+                schema_error(
+                    "spectrum-schema-migration",
+                    format!("no migration registered from schema version {version}"),
+                )
+            })?;
+        steps.push(MigrationStep {
+            from: step.from,
+            to: step.to,
+            description: step.description.to_string(),
+            synthesised: (step.apply)(report),
+        });
+        version = step.to;
+    }
+
+    report.insert("schema_version".to_string(), Value::from(version));
+    if let Some(Value::Object(provenance)) = report.get_mut("provenance") {
+        provenance.insert("schema_version".to_string(), Value::from(version));
+    }
+    Ok(MigrationLog {
+        from_version,
+        to_version: version,
+        steps,
+    })
+}
@@ -5,6 +5,9 @@ use asm_core::errors::{AsmError, ErrorInfo};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 
+use crate::report::SpectrumReport;
+use crate::schema::{migrate_report, MigrationLog};
+
 fn serde_error(code: &str, err: impl ToString) -> AsmError {
     AsmError::Serde(ErrorInfo::new(code, err.to_string()))
 }
@@ -39,3 +42,17 @@ pub fn to_canonical_json_bytes<T: Serialize>(value: &T) -> Result<Vec<u8>, AsmEr
 pub fn from_json_slice<T: for<'de> Deserialize<'de>>(data: &[u8]) -> Result<T, AsmError> {
     serde_json::from_slice(data).map_err(|err| serde_error("json-read", err))
 }
+
+/// Restores a [`SpectrumReport`] written at any supported schema version.
+///
+/// Older reports are upgraded by the steps in
+/// [`crate::schema::SPECTRUM_MIGRATIONS`]; the returned log lists every field
+/// that was filled with a synthesised default. Reports newer than
+/// [`crate::schema::SPECTRUM_SCHEMA_VERSION`] are rejected.
+pub fn from_json_slice_versioned(data: &[u8]) -> Result<(SpectrumReport, MigrationLog), AsmError> {
+    let mut value: Value =
+        serde_json::from_slice(data).map_err(|err| serde_error("json-read", err))?;
+    let log = migrate_report(&mut value)?;
+    let report = serde_json::from_value(value).map_err(|err| serde_error("json-read", err))?;
+    Ok((report, log))
+}
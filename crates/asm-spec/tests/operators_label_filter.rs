@@ -0,0 +1,70 @@
+use asm_code::CSSCode;
+use asm_core::provenance::{RunProvenance, SchemaVersion};
+use asm_core::Hypergraph;
+use asm_graph::{HypergraphConfig, HypergraphImpl};
+use asm_spec::{build_operators, OpOpts};
+
+fn code() -> CSSCode {
+    CSSCode::new(
+        4,
+        vec![vec![0, 1], vec![2, 3]],
+        vec![vec![0, 1], vec![2, 3]],
+        SchemaVersion::new(1, 0, 0),
+        RunProvenance::default(),
+    )
+    .unwrap()
+}
+
+/// Four nodes with matter edges when `matter` is set and gauge edges when
+/// `gauge` is set.
+fn sectors(matter: bool, gauge: bool) -> HypergraphImpl {
+    let mut graph = HypergraphImpl::new(HypergraphConfig {
+        causal_mode: false,
+        k_uniform: None,
+        ..HypergraphConfig::default()
+    });
+    let n: Vec<_> = (0..4).map(|_| graph.add_node().unwrap()).collect();
+    if matter {
+        graph
+            .add_hyperedge_labeled(&[n[0]], &[n[1]], "matter")
+            .unwrap();
+        graph
+            .add_hyperedge_labeled(&[n[2]], &[n[3]], "matter")
+            .unwrap();
+    }
+    if gauge {
+        graph
+            .add_hyperedge_labeled(&[n[0], n[2]], &[n[1], n[3]], "gauge")
+            .unwrap();
+    }
+    graph
+}
+
+fn with_label(label: &str) -> OpOpts {
+    OpOpts {
+        edge_label: Some(label.to_string()),
+        ..OpOpts::default()
+    }
+}
+
+#[test]
+fn label_filter_builds_one_sector() {
+    let full = sectors(true, true);
+    let gauge = build_operators(&full, &code(), &with_label("gauge")).unwrap();
+    let gauge_only = build_operators(&sectors(false, true), &code(), &OpOpts::default()).unwrap();
+    assert_eq!(gauge.info.num_edges, 1);
+    assert_eq!(gauge.info.num_nodes, 4);
+    assert_eq!(gauge.entries, gauge_only.entries);
+    assert_eq!(gauge.info.hash, gauge_only.info.hash);
+
+    let matter = build_operators(&full, &code(), &with_label("matter")).unwrap();
+    let matter_only = build_operators(&sectors(true, false), &code(), &OpOpts::default()).unwrap();
+    assert_eq!(matter.info.num_edges, 2);
+    assert_eq!(matter.entries, matter_only.entries);
+
+    let all = build_operators(&full, &code(), &OpOpts::default()).unwrap();
+    assert_eq!(all.info.num_edges, full.edges().len());
+    let none = build_operators(&full, &code(), &with_label("missing")).unwrap();
+    assert_eq!(none.info.num_edges, 0);
+    assert!(none.entries.is_empty());
+}
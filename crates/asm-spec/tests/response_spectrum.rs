@@ -0,0 +1,135 @@
+use std::f64::consts::PI;
+
+use asm_spec::{
+    response_spectrum, to_canonical_json_bytes, Response, ResponseTrace, SpectrumOpts,
+    SpectrumWindow,
+};
+
+/// Single-node response whose amplitude follows `samples` step by step.
+fn response(samples: Vec<f64>) -> Response {
+    Response {
+        support: vec![0],
+        amplitudes: samples.first().copied().into_iter().collect(),
+        response_hash: String::new(),
+        iterations: samples.len().saturating_sub(1),
+        tolerance: 0.0,
+        trace: ResponseTrace {
+            nodes: vec![0],
+            steps: samples.into_iter().map(|value| vec![value]).collect(),
+        },
+    }
+}
+
+fn sinusoid(len: usize, omega: f64, time_step: f64) -> Response {
+    response(
+        (0..len)
+            .map(|n| (omega * n as f64 * time_step).sin())
+            .collect(),
+    )
+}
+
+#[test]
+fn sinusoid_peaks_at_its_frequency() {
+    // 64 samples zero-padded to 128 put bin 10 at omega = 10 * pi / 32 for dt = 0.5.
+    let time_step = 0.5;
+    let omega = 2.0 * PI * 10.0 / (128.0 * time_step);
+    for window in [SpectrumWindow::Hann, SpectrumWindow::Rectangular] {
+        let opts = SpectrumOpts {
+            window,
+            time_step,
+            ..SpectrumOpts::default()
+        };
+        let spectrum = response_spectrum(&sinusoid(64, omega, time_step), &opts).unwrap();
+        assert_eq!(spectrum.window, window);
+        assert!((spectrum.peaks[0] - omega).abs() < 1e-9, "{window:?}");
+        assert_eq!(spectrum.intensity[10], 1.0);
+        assert!(spectrum.peaks.len() <= opts.max_peaks);
+    }
+}
+
+#[test]
+fn padding_fixes_the_omega_grid() {
+    let signal = sinusoid(50, 1.0, 1.0);
+    for (pad_factor, padded_len) in [(0, 64), (1, 64), (2, 128), (3, 256)] {
+        let opts = SpectrumOpts {
+            pad_factor,
+            time_step: 0.25,
+            ..SpectrumOpts::default()
+        };
+        let spectrum = response_spectrum(&signal, &opts).unwrap();
+        assert_eq!(spectrum.padded_len, padded_len);
+        assert_eq!(spectrum.omega.len(), padded_len / 2 + 1);
+        assert_eq!(spectrum.intensity.len(), spectrum.omega.len());
+        assert_eq!(spectrum.omega[0], 0.0);
+        // The last bin is the Nyquist frequency pi / time_step.
+        assert!((spectrum.omega[padded_len / 2] - PI / 0.25).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn spectrum_bytes_are_deterministic() {
+    let signal = response((0..37).map(|n| ((n * 7 % 11) as f64).sqrt()).collect());
+    let opts = SpectrumOpts::default();
+    let first = to_canonical_json_bytes(&response_spectrum(&signal, &opts).unwrap()).unwrap();
+    let second = to_canonical_json_bytes(&response_spectrum(&signal, &opts).unwrap()).unwrap();
+    assert_eq!(first, second);
+}
+
+#[test]
+fn rejects_empty_responses_and_bad_time_steps() {
+    let err = response_spectrum(&response(Vec::new()), &SpectrumOpts::default()).unwrap_err();
+    assert_eq!(err.info().code, "empty-response");
+
+    let signal = sinusoid(8, 1.0, 1.0);
+    for time_step in [0.0, -1.0, f64::NAN] {
+        let opts = SpectrumOpts {
+            time_step,
+            ..SpectrumOpts::default()
+        };
+        let err = response_spectrum(&signal, &opts).unwrap_err();
+        assert_eq!(err.info().code, "invalid-time-step");
+    }
+}
+
+#[test]
+fn propagated_trace_covers_every_step() {
+    use asm_code::CSSCode;
+    use asm_core::{Hypergraph, RunProvenance, SchemaVersion};
+    use asm_graph::{HypergraphConfig, HypergraphImpl};
+    use asm_spec::{build_operators, excite_and_propagate, ExcitationSpec, OpOpts, PropOpts};
+
+    let mut graph = HypergraphImpl::new(HypergraphConfig {
+        causal_mode: false,
+        k_uniform: None,
+        ..HypergraphConfig::default()
+    });
+    let nodes: Vec<_> = (0..6).map(|_| graph.add_node().unwrap()).collect();
+    for pair in nodes.windows(2) {
+        graph.add_hyperedge(&[pair[0]], &[pair[1]]).unwrap();
+    }
+    let code = CSSCode::new(
+        2,
+        vec![vec![0, 1]],
+        vec![vec![0, 1]],
+        SchemaVersion::new(1, 0, 0),
+        RunProvenance::default(),
+    )
+    .unwrap();
+    let ops = build_operators(&graph, &code, &OpOpts::default()).unwrap();
+    let opts = PropOpts {
+        iterations: 12,
+        tolerance: 1e-6,
+        seed: 5,
+        emit_front_fit: false,
+        front_threshold: None,
+    };
+    let response = excite_and_propagate(&ops, &ExcitationSpec::default(), &opts).unwrap();
+    assert_eq!(response.trace.steps.len(), 13);
+    assert_eq!(response.trace.nodes.len(), 6);
+    let totals = response.trace.totals();
+    assert!(totals.windows(2).all(|pair| pair[1] >= pair[0]));
+    assert!(totals[12] > totals[0]);
+
+    let spectrum = response_spectrum(&response, &SpectrumOpts::default()).unwrap();
+    assert_eq!(spectrum.padded_len, 32);
+}
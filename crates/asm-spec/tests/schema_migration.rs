@@ -0,0 +1,94 @@
+use std::fs;
+use std::path::PathBuf;
+
+use asm_spec::{
+    from_json_slice, from_json_slice_versioned, to_canonical_json_bytes, DecayClass,
+    LEGACY_SPECTRUM_SCHEMA_VERSION, SPECTRUM_MIGRATIONS, SPECTRUM_SCHEMA_VERSION,
+};
+use serde_json::Value;
+
+fn v1_fixture() -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../fixtures/phase11/t1_seed0/spectrum_report.json");
+    fs::read(path).expect("read v1 fixture")
+}
+
+fn with_version(bytes: &[u8], version: u32) -> Vec<u8> {
+    let mut value: Value = serde_json::from_slice(bytes).unwrap();
+    value["schema_version"] = Value::from(version);
+    value["provenance"]["schema_version"] = Value::from(version);
+    serde_json::to_vec(&value).unwrap()
+}
+
+#[test]
+fn v1_fixture_migrates_with_exact_log() {
+    let (report, log) = from_json_slice_versioned(&v1_fixture()).expect("migrate");
+    assert_eq!(log.from_version, LEGACY_SPECTRUM_SCHEMA_VERSION);
+    assert_eq!(log.to_version, SPECTRUM_SCHEMA_VERSION);
+    assert!(log.is_migrated());
+    assert_eq!(log.steps.len(), SPECTRUM_MIGRATIONS.len());
+    assert_eq!(
+        log.synthesised().collect::<Vec<_>>(),
+        [
+            "correlation.distances",
+            "correlation.correlator",
+            "correlation.model_score",
+            "correlation.decay_class",
+        ]
+    );
+    assert!(log.was_synthesised("correlation.decay_class"));
+    assert!(!log.was_synthesised("correlation.xi"));
+
+    assert_eq!(report.schema_version, SPECTRUM_SCHEMA_VERSION);
+    assert_eq!(report.provenance.schema_version, SPECTRUM_SCHEMA_VERSION);
+    assert_eq!(report.correlation.decay_class, DecayClass::Undetermined);
+    assert!(report.correlation.distances.is_empty());
+
+    // Recorded values are untouched by the migration.
+    let plain: asm_spec::SpectrumReport = from_json_slice(&v1_fixture()).unwrap();
+    assert_eq!(plain.schema_version, LEGACY_SPECTRUM_SCHEMA_VERSION);
+    assert_eq!(plain.correlation, report.correlation);
+    assert_eq!(plain.analysis_hash, report.analysis_hash);
+}
+
+#[test]
+fn current_reports_need_no_migration() {
+    let (migrated, _) = from_json_slice_versioned(&v1_fixture()).unwrap();
+    let bytes = to_canonical_json_bytes(&migrated).unwrap();
+    let (report, log) = from_json_slice_versioned(&bytes).unwrap();
+    assert_eq!(log.from_version, SPECTRUM_SCHEMA_VERSION);
+    assert!(!log.is_migrated());
+    assert!(log.steps.is_empty());
+    assert_eq!(report, migrated);
+}
+
+#[test]
+fn recorded_fields_are_not_reported_as_synthesised() {
+    let mut value: Value = serde_json::from_slice(&v1_fixture()).unwrap();
+    value["correlation"]["decay_class"] = Value::from("power-law");
+    let bytes = serde_json::to_vec(&value).unwrap();
+    let (report, log) = from_json_slice_versioned(&bytes).unwrap();
+    assert!(log.is_migrated());
+    assert!(!log.was_synthesised("correlation.decay_class"));
+    assert_eq!(log.synthesised().count(), 3);
+    assert_eq!(report.correlation.decay_class, DecayClass::PowerLaw);
+}
+
+#[test]
+fn newer_schema_is_rejected() {
+    let newer = SPECTRUM_SCHEMA_VERSION + 1;
+    let err = from_json_slice_versioned(&with_version(&v1_fixture(), newer)).unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("spectrum-schema-newer"), "{message}");
+    assert!(message.contains(&newer.to_string()));
+    assert!(message.contains(&SPECTRUM_SCHEMA_VERSION.to_string()));
+}
+
+#[test]
+fn mismatched_provenance_version_is_rejected() {
+    let mut value: Value =
+        serde_json::from_slice(&with_version(&v1_fixture(), SPECTRUM_SCHEMA_VERSION)).unwrap();
+    value["provenance"]["schema_version"] = Value::from(LEGACY_SPECTRUM_SCHEMA_VERSION);
+    let err = from_json_slice_versioned(&serde_json::to_vec(&value).unwrap()).unwrap_err();
+    assert!(err.to_string().contains("spectrum-schema-mismatch"));
+}
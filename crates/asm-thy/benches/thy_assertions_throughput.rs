@@ -62,6 +62,7 @@ fn sample_inputs() -> AssertionInputs {
                 total_time: 0.64,
                 final_norm: 1.0,
                 traj_hash: "traj-sample".to_string(),
+                adaptive: None,
             },
             steps: Vec::new(),
         },
@@ -70,6 +71,8 @@ fn sample_inputs() -> AssertionInputs {
             kernel: KernelOpts::default(),
             measure: MeasureOpts::default(),
             fit: FitOpts::default(),
+            hash_format: asm_core::LEGACY_HASH_FORMAT,
+            build: None,
         },
     };
     let mut first = couplings.clone();
@@ -1,11 +1,11 @@
 use std::collections::BTreeMap;
 
 use asm_core::errors::{AsmError, ErrorInfo};
-use asm_gauge::GaugeReport;
+use asm_gauge::{jacobi_check, GaugeReport, JacobiOpts, COMMUTATOR_IDENTITY};
 use asm_int::{InteractionReport, RunningReport};
 use asm_land::metrics::JobKpi;
 use asm_land::report::SummaryReport;
-use asm_spec::SpectrumReport;
+use asm_spec::{DecayClass, SpectrumReport};
 
 use crate::hash::stable_hash_string;
 use crate::policies::Policy;
@@ -46,12 +46,41 @@ fn missing_input(name: &str) -> AsmError {
     )
 }
 
+/// Rejects partial gauge checks unless the policy allows them.
+fn ensure_full_gauge_checks(gauge: &GaugeReport, policy: &Policy) -> Result<(), AsmError> {
+    if !policy.require_full_gauge_checks {
+        return Ok(());
+    }
+    let partial = [
+        ("closure", gauge.closure.partial, &gauge.closure.subset),
+        ("ward", gauge.ward.partial, &gauge.ward.subset),
+    ];
+    match partial.into_iter().find(|(_, partial, _)| *partial) {
+        Some((check, _, subset)) => {
+            let mut info = ErrorInfo::new(
+                "partial-gauge-check",
+                format!("{check} check covered only a subset of generators"),
+            )
+            .with_context("check", check);
+            if let Some(subset) = subset {
+                info = info
+                    .with_context("subset_hash", subset.hash.clone())
+                    .with_context("evaluated", subset.indices.len().to_string())
+                    .with_context("total", subset.total.to_string());
+            }
+            Err(AsmError::Serde(info))
+        }
+        None => Ok(()),
+    }
+}
+
 fn ward_commutator_bound(gauge: &GaugeReport, policy: &Policy) -> AssertionCheck {
     let metric = policy.round(gauge.ward.max_comm_norm.abs());
     let pass = metric <= policy.ward_tol;
     AssertionCheck {
         name: "ward_commutator_bound".to_string(),
         pass,
+        severity: policy.severity_for("ward_commutator_bound"),
         metric,
         threshold: Some(policy.ward_tol),
         range: None,
@@ -63,12 +92,43 @@ fn ward_commutator_bound(gauge: &GaugeReport, policy: &Policy) -> AssertionCheck
     }
 }
 
+/// Commutator bound followed by one check per correlator Ward identity.
+fn ward_identity_suite(gauge: &GaugeReport, policy: &Policy) -> Vec<AssertionCheck> {
+    let mut checks = vec![ward_commutator_bound(gauge, policy)];
+    for identity in &gauge.ward.identities {
+        if identity.name == COMMUTATOR_IDENTITY {
+            continue;
+        }
+        let metric = policy.round(identity.residual.abs());
+        let pass = metric <= identity.tolerance;
+        let name = format!("ward_identity:{}", identity.name);
+        checks.push(AssertionCheck {
+            severity: policy.severity_for(&name),
+            name,
+            pass,
+            metric,
+            threshold: Some(identity.tolerance),
+            range: None,
+            note: if pass {
+                None
+            } else {
+                Some(format!(
+                    "ward identity `{}` exceeds tolerance",
+                    identity.name
+                ))
+            },
+        });
+    }
+    checks
+}
+
 fn closure_residual(gauge: &GaugeReport, policy: &Policy) -> AssertionCheck {
     let metric = policy.round(gauge.closure.max_dev.abs());
     let pass = metric <= policy.closure_tol;
     AssertionCheck {
         name: "closure_residual".to_string(),
         pass,
+        severity: policy.severity_for("closure_residual"),
         metric,
         threshold: Some(policy.closure_tol),
         range: None,
@@ -80,6 +140,33 @@ fn closure_residual(gauge: &GaugeReport, policy: &Policy) -> AssertionCheck {
     }
 }
 
+fn jacobi_identity(gauge: &GaugeReport, policy: &Policy) -> Result<AssertionCheck, AsmError> {
+    let opts = JacobiOpts {
+        tolerance: policy.jacobi_tol,
+    };
+    let report = jacobi_check(&gauge.closure.structure_tensors, &opts)?;
+    let metric = policy.round(report.max_violation);
+    let pass = metric <= policy.jacobi_tol;
+    Ok(AssertionCheck {
+        name: "jacobi_identity".to_string(),
+        pass,
+        severity: policy.severity_for("jacobi_identity"),
+        metric,
+        threshold: Some(policy.jacobi_tol),
+        range: None,
+        note: if pass {
+            None
+        } else {
+            Some(match report.worst_triple {
+                Some([i, j, k]) => {
+                    format!("jacobi identity violated for generators ({i}, {j}, {k})")
+                }
+                None => "jacobi identity violated".to_string(),
+            })
+        },
+    })
+}
+
 fn dispersion_linear_limit(spec: &SpectrumReport, policy: &Policy) -> AssertionCheck {
     let metric = if spec.dispersion.k_grid.len() >= 2 && !spec.dispersion.modes.is_empty() {
         let k0 = spec.dispersion.k_grid[0];
@@ -100,6 +187,7 @@ fn dispersion_linear_limit(spec: &SpectrumReport, policy: &Policy) -> AssertionC
     AssertionCheck {
         name: "dispersion_linear_limit".to_string(),
         pass,
+        severity: policy.severity_for("dispersion_linear_limit"),
         metric,
         threshold: Some(policy.rel_tol_lin),
         range: None,
@@ -112,6 +200,17 @@ fn dispersion_linear_limit(spec: &SpectrumReport, policy: &Policy) -> AssertionC
 }
 
 fn correlation_gap_relation(spec: &SpectrumReport, policy: &Policy) -> AssertionCheck {
+    if spec.correlation.decay_class == DecayClass::PowerLaw {
+        return AssertionCheck {
+            name: "correlation_gap_relation".to_string(),
+            pass: true,
+            severity: policy.severity_for("correlation_gap_relation"),
+            metric: 0.0,
+            threshold: None,
+            range: None,
+            note: Some("skipped: power-law decay has no finite correlation length".to_string()),
+        };
+    }
     let expected = if spec.dispersion.gap_proxy.abs() <= 1e-9 {
         spec.correlation.xi
     } else {
@@ -122,6 +221,7 @@ fn correlation_gap_relation(spec: &SpectrumReport, policy: &Policy) -> Assertion
     AssertionCheck {
         name: "correlation_gap_relation".to_string(),
         pass,
+        severity: policy.severity_for("correlation_gap_relation"),
         metric,
         threshold: Some(policy.abs_tol),
         range: None,
@@ -139,6 +239,7 @@ fn couplings_fit_resid(interaction: &InteractionReport, policy: &Policy) -> Asse
     AssertionCheck {
         name: "couplings_fit_resid".to_string(),
         pass,
+        severity: policy.severity_for("couplings_fit_resid"),
         metric,
         threshold: Some(policy.fit_resid_max),
         range: None,
@@ -175,6 +276,7 @@ fn running_beta_sanity(running: &RunningReport, policy: &Policy) -> AssertionChe
     AssertionCheck {
         name: "running_beta_sanity".to_string(),
         pass,
+        severity: policy.severity_for("running_beta_sanity"),
         metric,
         threshold: Some(threshold),
         range: None,
@@ -193,6 +295,7 @@ fn landscape_filter_rate(summary: &SummaryReport, policy: &Policy) -> AssertionC
     AssertionCheck {
         name: "landscape_filter_rate".to_string(),
         pass,
+        severity: policy.severity_for("landscape_filter_rate"),
         metric,
         threshold: None,
         range: Some(range),
@@ -232,15 +335,18 @@ pub fn run_assertions(
     inputs: &AssertionInputs,
     policy: &Policy,
 ) -> Result<AssertionReport, AsmError> {
+    if let Some(gauge) = &inputs.gauge {
+        ensure_full_gauge_checks(gauge, policy)?;
+    }
     let mut checks = Vec::new();
     if policy.require_ward {
         let gauge = inputs
             .gauge
             .as_ref()
             .ok_or_else(|| missing_input("gauge"))?;
-        checks.push(ward_commutator_bound(gauge, policy));
+        checks.extend(ward_identity_suite(gauge, policy));
     } else if let Some(gauge) = &inputs.gauge {
-        checks.push(ward_commutator_bound(gauge, policy));
+        checks.extend(ward_identity_suite(gauge, policy));
     }
 
     if policy.require_closure {
@@ -253,6 +359,12 @@ pub fn run_assertions(
         checks.push(closure_residual(gauge, policy));
     }
 
+    if policy.check_jacobi {
+        if let Some(gauge) = &inputs.gauge {
+            checks.push(jacobi_identity(gauge, policy)?);
+        }
+    }
+
     if let Some(spec) = &inputs.spectrum {
         checks.push(dispersion_linear_limit(spec, policy));
         checks.push(correlation_gap_relation(spec, policy));
@@ -278,7 +390,7 @@ pub fn run_assertions(
         return Err(missing_input("summary"));
     }
 
-    validate_checks(&checks)?;
+    validate_checks(&checks, policy)?;
     let check_order = checks.iter().map(|check| check.name.clone()).collect();
     let provenance = AssertionProvenance::new(policy.clone(), collect_hashes(inputs)?, check_order);
     AssertionReport::new(checks, provenance)
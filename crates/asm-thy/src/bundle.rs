@@ -8,7 +8,11 @@ use serde::{Deserialize, Serialize};
 use walkdir::WalkDir;
 
 use crate::hash::stable_hash_string;
-use crate::serde::to_canonical_json_bytes;
+use crate::policies::Severity;
+use crate::report::AssertionReport;
+use crate::serde::{from_json_slice, to_canonical_json_bytes};
+
+pub use crate::latex::{render_latex_tables, PaperPackInputs, SigFigPolicy};
 
 fn bundle_error(code: &str, message: impl std::fmt::Display) -> AsmError {
     AsmError::Serde(ErrorInfo::new(code, message.to_string()))
@@ -25,6 +29,16 @@ pub struct BundlePlan {
     /// Whether to flatten output paths instead of recreating directory trees.
     #[serde(default)]
     pub flatten_paths: bool,
+    /// Whether to render LaTeX tables from the collected artefacts.
+    #[serde(default)]
+    pub latex_tables: bool,
+    /// Numeric formatting used by the rendered tables.
+    #[serde(default)]
+    pub sig_figs: SigFigPolicy,
+    /// Whether error-severity assertion failures in bundled
+    /// `assert_report.json` files abort the bundle. Warnings never do.
+    #[serde(default)]
+    pub block_on_assertion_errors: bool,
 }
 
 impl Default for BundlePlan {
@@ -33,6 +47,9 @@ impl Default for BundlePlan {
             include: Vec::new(),
             copy_figures: true,
             flatten_paths: true,
+            latex_tables: false,
+            sig_figs: SigFigPolicy::default(),
+            block_on_assertion_errors: false,
         }
     }
 }
@@ -46,8 +63,18 @@ pub struct ManuscriptBundle {
     pub inputs: Vec<String>,
     /// Mapping from bundle paths to their original source.
     pub manifest: BTreeMap<String, String>,
+    /// Rendered LaTeX tables keyed by table name, pointing at their bundle path.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tables: BTreeMap<String, String>,
+    /// Warning-severity assertion failures found in bundled assertion reports,
+    /// also written to [`ASSERTION_WARNINGS_FILE`] at the bundle root.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assertion_warnings: Vec<String>,
 }
 
+/// File at the bundle root listing warning-severity assertion failures.
+pub const ASSERTION_WARNINGS_FILE: &str = "ASSERTION_WARNINGS.md";
+
 fn build_globset(patterns: &[String]) -> Result<GlobSet, AsmError> {
     let mut builder = GlobSetBuilder::new();
     if patterns.is_empty() {
@@ -98,6 +125,122 @@ fn copy_entry(src: &Path, dest: &Path) -> Result<(), AsmError> {
     Ok(())
 }
 
+fn read_artefact<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, AsmError> {
+    let bytes = fs::read(path).map_err(|err| bundle_error("bundle-read", err))?;
+    from_json_slice(&bytes)
+}
+
+/// Picks table inputs from the collected artefacts, taking the first match in
+/// bundle order for each kind.
+fn collect_table_inputs(
+    out_dir: &Path,
+    manifest: &BTreeMap<String, String>,
+    plan: &BundlePlan,
+) -> Result<PaperPackInputs, AsmError> {
+    let mut inputs = PaperPackInputs {
+        sig_figs: plan.sig_figs,
+        ..PaperPackInputs::default()
+    };
+    let mut interaction_fit = None;
+    for (dest, source) in manifest {
+        let path = out_dir.join(dest);
+        match source.rsplit('/').next().unwrap_or_default() {
+            "assert_report.json" if inputs.assertions.is_none() => {
+                inputs.assertions = Some(read_artefact::<AssertionReport>(&path)?);
+            }
+            "SummaryReport.json" if inputs.summary.is_none() => {
+                inputs.summary = Some(read_artefact(&path)?);
+            }
+            "couplings_fit.json" if inputs.couplings.is_none() => {
+                inputs.couplings = Some(read_artefact(&path)?);
+            }
+            "interaction_report.json" if interaction_fit.is_none() => {
+                let report: asm_int::report::InteractionReport = read_artefact(&path)?;
+                interaction_fit = Some(report.fit);
+            }
+            _ => {}
+        }
+    }
+    if inputs.couplings.is_none() {
+        inputs.couplings = interaction_fit;
+    }
+    Ok(inputs)
+}
+
+/// Scans bundled assertion reports, returning one line per warning-severity
+/// failure and rejecting error-severity failures when the plan blocks on them.
+fn assertion_warnings(
+    out_dir: &Path,
+    manifest: &BTreeMap<String, String>,
+    plan: &BundlePlan,
+) -> Result<Vec<String>, AsmError> {
+    let mut warnings = Vec::new();
+    for (dest, source) in manifest {
+        if source.rsplit('/').next() != Some("assert_report.json") {
+            continue;
+        }
+        let report: AssertionReport = read_artefact(&out_dir.join(dest))?;
+        if plan.block_on_assertion_errors {
+            if let Some(check) = report.failures(Severity::Error).next() {
+                return Err(AsmError::Serde(
+                    ErrorInfo::new(
+                        "assertion-errors",
+                        format!(
+                            "bundled assertion report has failing check `{}`",
+                            check.name
+                        ),
+                    )
+                    .with_context("report", dest.clone())
+                    .with_context("check", check.name.clone()),
+                ));
+            }
+        }
+        for check in report.failures(Severity::Warning) {
+            let note = check.note.as_deref().unwrap_or("failed");
+            warnings.push(format!(
+                "{dest}: {} = {} ({note})",
+                check.name, check.metric
+            ));
+        }
+    }
+    Ok(warnings)
+}
+
+fn write_assertion_warnings(out_dir: &Path, warnings: &[String]) -> Result<(), AsmError> {
+    let path = out_dir.join(ASSERTION_WARNINGS_FILE);
+    if warnings.is_empty() {
+        // Drop a stale file from an earlier bundle into the same directory.
+        if path.exists() {
+            fs::remove_file(&path).map_err(|err| bundle_error("bundle-write", err))?;
+        }
+        return Ok(());
+    }
+    let mut body = String::from("# Assertion warnings\n\n");
+    for warning in warnings {
+        body.push_str(&format!("- {warning}\n"));
+    }
+    fs::write(path, body).map_err(|err| bundle_error("bundle-write", err))
+}
+
+/// Writes rendered tables under `tables/`, returning their bundle paths.
+fn write_tables(
+    out_dir: &Path,
+    rendered: &BTreeMap<String, String>,
+) -> Result<BTreeMap<String, String>, AsmError> {
+    let mut tables = BTreeMap::new();
+    if rendered.is_empty() {
+        return Ok(tables);
+    }
+    let dir = out_dir.join("tables");
+    fs::create_dir_all(&dir).map_err(|err| bundle_error("bundle-mkdir", err))?;
+    for (name, body) in rendered {
+        fs::write(dir.join(format!("{name}.tex")), body)
+            .map_err(|err| bundle_error("bundle-write", err))?;
+        tables.insert(name.clone(), format!("tables/{name}.tex"));
+    }
+    Ok(tables)
+}
+
 /// Builds a deterministic manuscript bundle from the provided roots and plan.
 pub fn build_manuscript_bundle(
     src_roots: &[PathBuf],
@@ -141,11 +284,27 @@ pub fn build_manuscript_bundle(
     }
     let mut inputs: Vec<String> = manifest.keys().cloned().collect();
     inputs.sort();
-    let bundle_hash = stable_hash_string(&manifest)?;
+    let assertion_warnings = assertion_warnings(out_dir, &manifest, plan)?;
+    write_assertion_warnings(out_dir, &assertion_warnings)?;
+    let rendered = if plan.latex_tables {
+        render_latex_tables(&collect_table_inputs(out_dir, &manifest, plan)?)?
+    } else {
+        BTreeMap::new()
+    };
+    let tables = write_tables(out_dir, &rendered)?;
+    // Bundles without tables or warnings keep hashing the manifest alone so
+    // existing hashes stay stable.
+    let bundle_hash = match (rendered.is_empty(), assertion_warnings.is_empty()) {
+        (true, true) => stable_hash_string(&manifest)?,
+        (false, true) => stable_hash_string(&(&manifest, &rendered))?,
+        _ => stable_hash_string(&(&manifest, &rendered, &assertion_warnings))?,
+    };
     let bundle = ManuscriptBundle {
         bundle_hash,
         inputs,
         manifest,
+        tables,
+        assertion_warnings,
     };
     let bytes = to_canonical_json_bytes(&bundle)?;
     fs::write(out_dir.join("manifest.json"), bytes)
@@ -1,8 +1,9 @@
 use asm_core::errors::{AsmError, ErrorInfo};
+use asm_gauge::ClosureReport;
 use serde::{Deserialize, Serialize};
 
 use crate::policies::Policy;
-use crate::symbolic::{NumMat, SymExpr};
+use crate::symbolic::{NumMat, SymExpr, SymTerm};
 
 fn crosscheck_error(code: &str, message: impl Into<String>) -> AsmError {
     AsmError::Serde(ErrorInfo::new(code, message.into()))
@@ -17,6 +18,57 @@ pub struct CrosscheckResult {
     pub metric: f64,
     /// Threshold used during the decision.
     pub threshold: f64,
+    /// Multi-index of the element with the largest absolute deviation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_dev_index: Option<Vec<usize>>,
+    /// Rounded absolute deviation at [`CrosscheckResult::max_dev_index`].
+    #[serde(default)]
+    pub max_abs_dev: f64,
+    /// Rounded Frobenius-norm relative error `|S - N|_F / |S|_F`.
+    #[serde(default)]
+    pub rel_frobenius: f64,
+}
+
+struct Deviation {
+    diff_norm: f64,
+    max_flat: Option<usize>,
+    max_abs: f64,
+    rel_frobenius: f64,
+    within_tol: bool,
+}
+
+fn measure_deviation(symbolic: &NumMat, numeric: &NumMat, policy: &Policy) -> Deviation {
+    let mut diff_sq = 0.0;
+    let mut ref_sq = 0.0;
+    let mut max_flat = None;
+    let mut max_abs: f64 = 0.0;
+    let mut within_tol = true;
+    for (idx, (sym, num)) in symbolic.entries.iter().zip(&numeric.entries).enumerate() {
+        let delta = (sym - num).abs();
+        diff_sq += delta * delta;
+        ref_sq += sym * sym;
+        if max_flat.is_none() || delta > max_abs {
+            max_flat = Some(idx);
+            max_abs = delta;
+        }
+        if delta > policy.abs_tol + policy.rel_tol * sym.abs() {
+            within_tol = false;
+        }
+    }
+    let diff_norm = diff_sq.sqrt();
+    let ref_norm = ref_sq.sqrt();
+    let rel_frobenius = if ref_norm > 0.0 {
+        diff_norm / ref_norm
+    } else {
+        diff_norm
+    };
+    Deviation {
+        diff_norm,
+        max_flat,
+        max_abs,
+        rel_frobenius,
+        within_tol,
+    }
 }
 
 /// Cross-checks a numeric matrix against a symbolic expression with the provided policy.
@@ -47,15 +99,86 @@ pub fn crosscheck_numeric(
         ));
     }
 
-    let mut diff_norm = 0.0;
-    for (sym, num) in symbolic.entries.iter().zip(numeric.entries.iter()) {
-        let delta = sym - num;
-        diff_norm += delta * delta;
-    }
-    let metric = policy.round(diff_norm.sqrt());
+    let deviation = measure_deviation(&symbolic.evaluate(), numeric, policy);
+    let metric = policy.round(deviation.diff_norm);
     Ok(CrosscheckResult {
         pass: metric <= policy.abs_tol,
         metric,
         threshold: policy.abs_tol,
+        max_dev_index: deviation.max_flat.map(|flat| numeric.unravel_index(flat)),
+        max_abs_dev: policy.round(deviation.max_abs),
+        rel_frobenius: policy.round(deviation.rel_frobenius),
     })
 }
+
+/// Cross-checks a matrix-valued artefact element-wise against a symbolic expression.
+///
+/// Each element must satisfy `|s - n| <= abs_tol + rel_tol * |s|` using the
+/// tolerances from `policy`. The reported metric is the largest absolute
+/// element deviation.
+pub fn crosscheck_matrix(
+    symbolic: &SymTerm,
+    numeric: &NumMat,
+    policy: &Policy,
+) -> Result<CrosscheckResult, AsmError> {
+    let expected = symbolic.evaluate()?;
+    let (sym_shape, num_shape) = (expected.shape(), numeric.shape());
+    if sym_shape != num_shape {
+        return Err(crosscheck_error(
+            "shape-mismatch",
+            format!("symbolic shape {sym_shape:?} != numeric shape {num_shape:?}"),
+        ));
+    }
+    let count: usize = num_shape.iter().product();
+    if count == 0 {
+        return Err(crosscheck_error(
+            "empty-matrix",
+            "symbolic and numeric matrices must be non-empty",
+        ));
+    }
+    if numeric.entries.len() != count {
+        return Err(crosscheck_error(
+            "entry-mismatch",
+            format!(
+                "numeric shape {num_shape:?} requires {count} entries, found {}",
+                numeric.entries.len()
+            ),
+        ));
+    }
+
+    let deviation = measure_deviation(&expected, numeric, policy);
+    Ok(CrosscheckResult {
+        pass: deviation.within_tol,
+        metric: policy.round(deviation.max_abs),
+        threshold: policy.abs_tol,
+        max_dev_index: deviation.max_flat.map(|flat| numeric.unravel_index(flat)),
+        max_abs_dev: policy.round(deviation.max_abs),
+        rel_frobenius: policy.round(deviation.rel_frobenius),
+    })
+}
+
+/// Flattens closure structure constants into a `[n, n, n]` tensor indexed `f[i][j][k]`.
+///
+/// The closure report only records `i < j`; the antisymmetric partner
+/// `f[j][i][k] = -f[i][j][k]` is filled in so the ordering is canonical.
+pub fn structure_tensor_mat(
+    closure: &ClosureReport,
+    generators: usize,
+) -> Result<NumMat, AsmError> {
+    let n = generators;
+    let mut entries = vec![0.0; n * n * n];
+    for entry in &closure.structure_tensors {
+        if entry.i >= n || entry.j >= n || entry.k >= n {
+            return Err(crosscheck_error(
+                "tensor-index-out-of-range",
+                format!(
+                    "structure tensor entry ({}, {}, {}) exceeds generator count {n}",
+                    entry.i, entry.j, entry.k
+                ),
+            ));
+        }
+        entries[(entry.i * n + entry.j) * n + entry.k] = entry.value;
+        entries[(entry.j * n + entry.i) * n + entry.k] = -entry.value;
+    }
+    Ok(NumMat::from_shape(vec![n, n, n], entries))
+}
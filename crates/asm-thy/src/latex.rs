@@ -0,0 +1,325 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use asm_core::errors::{AsmError, ErrorInfo};
+use asm_int::CouplingsFit;
+use asm_land::report::SummaryReport;
+use serde::{Deserialize, Serialize};
+
+use crate::report::AssertionReport;
+
+/// Largest number of significant figures that survives a round trip through `f64`.
+const MAX_SIG_FIGS: u32 = 17;
+
+fn latex_error(code: &str, message: impl std::fmt::Display) -> AsmError {
+    AsmError::Serde(ErrorInfo::new(code, message.to_string()))
+}
+
+/// Numeric formatting policy applied to every value in the rendered tables.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SigFigPolicy {
+    /// Number of significant figures kept after rounding half-to-even.
+    pub digits: u32,
+}
+
+impl Default for SigFigPolicy {
+    fn default() -> Self {
+        Self { digits: 4 }
+    }
+}
+
+impl SigFigPolicy {
+    fn validate(&self) -> Result<(), AsmError> {
+        if self.digits == 0 || self.digits > MAX_SIG_FIGS {
+            return Err(latex_error(
+                "latex-sig-figs",
+                format!(
+                    "significant figures must lie in 1..={MAX_SIG_FIGS}, got {}",
+                    self.digits
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Formats `value` as LaTeX math using the configured significant figures.
+    ///
+    /// Rounding operates on the shortest decimal representation of the value
+    /// and breaks ties towards the even digit. Magnitudes below `1e-4` or at
+    /// least `1e6` switch to `m \times 10^{e}` notation. NaN renders as
+    /// `\text{NaN}` and infinities as `\infty` with their sign.
+    pub fn format(&self, value: f64) -> String {
+        format!("${}$", self.format_bare(value))
+    }
+
+    fn format_bare(&self, value: f64) -> String {
+        if value.is_nan() {
+            return r"\text{NaN}".to_string();
+        }
+        if value.is_infinite() {
+            let sign = if value < 0.0 { "-" } else { "" };
+            return format!(r"{sign}\infty");
+        }
+        let digits = self.digits as usize;
+        if value == 0.0 {
+            return pad_zero(digits);
+        }
+        let Some((mantissa, exponent)) = round_half_even(value.abs(), digits) else {
+            return value.to_string();
+        };
+        let sign = if value < 0.0 { "-" } else { "" };
+        if !(-4..6).contains(&exponent) {
+            let mut body = mantissa[..1].to_string();
+            if digits > 1 {
+                body.push('.');
+                body.push_str(&mantissa[1..]);
+            }
+            return format!(r"{sign}{body} \times 10^{{{exponent}}}");
+        }
+        let body = if exponent < 0 {
+            format!("0.{}{}", "0".repeat((-exponent - 1) as usize), mantissa)
+        } else {
+            let int_len = exponent as usize + 1;
+            if int_len >= digits {
+                format!("{}{}", mantissa, "0".repeat(int_len - digits))
+            } else {
+                format!("{}.{}", &mantissa[..int_len], &mantissa[int_len..])
+            }
+        };
+        format!("{sign}{body}")
+    }
+}
+
+fn pad_zero(digits: usize) -> String {
+    if digits > 1 {
+        format!("0.{}", "0".repeat(digits - 1))
+    } else {
+        "0".to_string()
+    }
+}
+
+/// Rounds a positive finite value to `digits` significant figures, returning
+/// the mantissa digits and the decimal exponent of the leading digit, or
+/// `None` when the value has no scientific representation.
+fn round_half_even(value: f64, digits: usize) -> Option<(String, i32)> {
+    if !value.is_finite() {
+        return None;
+    }
+    let repr = format!("{value:e}");
+    let (mantissa, exponent) = repr.split_once('e')?;
+    let mut exponent: i32 = exponent.parse().ok()?;
+    let all: Vec<u8> = mantissa
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .map(|b| b - b'0')
+        .collect();
+    let mut kept: Vec<u8> = all.iter().copied().take(digits).collect();
+    kept.resize(digits, 0);
+    let rest = all.get(digits..).unwrap_or(&[]);
+    let round_up = match rest.split_first() {
+        Some((&first, _)) if first > 5 => true,
+        Some((&5, tail)) if tail.iter().any(|&d| d != 0) => true,
+        Some((&5, _)) => kept[digits - 1] % 2 == 1,
+        _ => false,
+    };
+    if round_up {
+        let mut idx = digits;
+        loop {
+            if idx == 0 {
+                kept.insert(0, 1);
+                kept.truncate(digits);
+                exponent += 1;
+                break;
+            }
+            idx -= 1;
+            if kept[idx] == 9 {
+                kept[idx] = 0;
+            } else {
+                kept[idx] += 1;
+                break;
+            }
+        }
+    }
+    let mantissa = kept.iter().map(|d| char::from(b'0' + d)).collect();
+    Some((mantissa, exponent))
+}
+
+/// Escapes LaTeX special characters so free text renders verbatim.
+pub fn escape_latex(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\\' => out.push_str(r"\textbackslash{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                out.push('\\');
+                out.push(ch);
+            }
+            '~' => out.push_str(r"\textasciitilde{}"),
+            '^' => out.push_str(r"\textasciicircum{}"),
+            '<' => out.push_str(r"\textless{}"),
+            '>' => out.push_str(r"\textgreater{}"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Artefacts rendered into manuscript tables; absent inputs are skipped.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PaperPackInputs {
+    /// Assertion report feeding the check matrix.
+    pub assertions: Option<AssertionReport>,
+    /// Landscape summary feeding the pass-rate and KPI quantile table.
+    pub summary: Option<SummaryReport>,
+    /// Coupling fit feeding the parameter table.
+    pub couplings: Option<CouplingsFit>,
+    /// Numeric formatting policy.
+    pub sig_figs: SigFigPolicy,
+}
+
+struct Table<'a> {
+    columns: &'a str,
+    header: &'a [&'a str],
+    rows: Vec<Vec<String>>,
+    caption: String,
+    label: &'a str,
+}
+
+impl Table<'_> {
+    fn render(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, r"\begin{{table}}[ht]");
+        let _ = writeln!(out, r"\centering");
+        let _ = writeln!(out, r"\caption{{{}}}", self.caption);
+        let _ = writeln!(out, r"\label{{{}}}", self.label);
+        let _ = writeln!(out, r"\begin{{tabular}}{{{}}}", self.columns);
+        let _ = writeln!(out, r"\toprule");
+        let _ = writeln!(out, r"{} \\", self.header.join(" & "));
+        let _ = writeln!(out, r"\midrule");
+        for row in &self.rows {
+            let _ = writeln!(out, r"{} \\", row.join(" & "));
+        }
+        let _ = writeln!(out, r"\bottomrule");
+        let _ = writeln!(out, r"\end{{tabular}}");
+        let _ = writeln!(out, r"\end{{table}}");
+        out
+    }
+}
+
+fn assertion_table(report: &AssertionReport, policy: &SigFigPolicy) -> String {
+    let rows = report
+        .checks
+        .iter()
+        .map(|check| {
+            let criterion = match (check.threshold, check.range) {
+                (_, Some([lo, hi])) => {
+                    format!("$[{}, {}]$", policy.format_bare(lo), policy.format_bare(hi))
+                }
+                (Some(threshold), None) => policy.format(threshold),
+                (None, None) => "--".to_string(),
+            };
+            vec![
+                format!(r"\texttt{{{}}}", escape_latex(&check.name)),
+                policy.format(check.metric),
+                criterion,
+                if check.pass { "yes" } else { "no" }.to_string(),
+            ]
+        })
+        .collect();
+    let passing = report.checks.iter().filter(|check| check.pass).count();
+    Table {
+        columns: "lrrc",
+        header: &["Check", "Metric", "Threshold / range", "Pass"],
+        rows,
+        caption: format!(
+            "Theory assertion matrix: {passing} of {} checks pass.",
+            report.checks.len()
+        ),
+        label: "tab:assertions",
+    }
+    .render()
+}
+
+fn landscape_table(summary: &SummaryReport, policy: &SigFigPolicy) -> String {
+    let rows = summary
+        .quantiles
+        .iter()
+        .map(|(kpi, quantiles)| {
+            vec![
+                format!(r"\texttt{{{}}}", escape_latex(kpi)),
+                policy.format(quantiles.q05),
+                policy.format(quantiles.q50),
+                policy.format(quantiles.q95),
+            ]
+        })
+        .collect();
+    Table {
+        columns: "lrrr",
+        header: &["KPI", "$q_{0.05}$", "$q_{0.50}$", "$q_{0.95}$"],
+        rows,
+        caption: format!(
+            "Landscape summary: {} of {} jobs pass the anthropic filters (pass rate {}).",
+            summary.totals.passing,
+            summary.totals.jobs,
+            policy.format(summary.pass_rates.anthropic)
+        ),
+        label: "tab:landscape",
+    }
+    .render()
+}
+
+fn couplings_table(fit: &CouplingsFit, policy: &SigFigPolicy) -> String {
+    let mut rows = Vec::new();
+    for (idx, (value, ci)) in fit.g.iter().zip(fit.ci.g.iter()).enumerate() {
+        rows.push(vec![
+            format!("$g_{}$", idx + 1),
+            policy.format(*value),
+            policy.format(*ci),
+        ]);
+    }
+    rows.push(vec![
+        r"$\lambda_H$".to_string(),
+        policy.format(fit.lambda_h),
+        policy.format(fit.ci.lambda_h),
+    ]);
+    for (idx, value) in fit.yukawa.iter().enumerate() {
+        rows.push(vec![
+            format!("$y_{}$", idx + 1),
+            policy.format(*value),
+            policy.format(fit.ci.yukawa),
+        ]);
+    }
+    Table {
+        columns: "lrr",
+        header: &["Coupling", "Value", r"$\pm$ CI"],
+        rows,
+        caption: format!(
+            "Coupling fit at scale {} (residual {}).",
+            policy.format(fit.scale),
+            policy.format(fit.fit_resid)
+        ),
+        label: "tab:couplings",
+    }
+    .render()
+}
+
+/// Renders deterministic LaTeX tables for the provided artefacts.
+///
+/// The result maps `assertions`, `landscape`, and `couplings` to complete
+/// `table` environments (using `booktabs` rules); keys are omitted when the
+/// corresponding input is absent.
+pub fn render_latex_tables(inputs: &PaperPackInputs) -> Result<BTreeMap<String, String>, AsmError> {
+    let policy = &inputs.sig_figs;
+    policy.validate()?;
+    let mut tables = BTreeMap::new();
+    if let Some(report) = &inputs.assertions {
+        tables.insert("assertions".to_string(), assertion_table(report, policy));
+    }
+    if let Some(summary) = &inputs.summary {
+        tables.insert("landscape".to_string(), landscape_table(summary, policy));
+    }
+    if let Some(fit) = &inputs.couplings {
+        tables.insert("couplings".to_string(), couplings_table(fit, policy));
+    }
+    Ok(tables)
+}
@@ -9,6 +9,8 @@ pub mod bundle;
 pub mod crosscheck;
 /// Canonical hashing helpers.
 pub mod hash;
+/// LaTeX table rendering for manuscript bundles.
+pub mod latex;
 /// Policy definitions controlling tolerance discipline.
 pub mod policies;
 /// Aggregated assertion reports and provenance types.
@@ -20,7 +22,10 @@ pub mod symbolic;
 
 pub use assertions::{run_assertions, AssertionInputs};
 pub use bundle::{build_manuscript_bundle, BundlePlan, ManuscriptBundle};
-pub use crosscheck::{crosscheck_numeric, CrosscheckResult};
-pub use policies::{Policy, PolicyRange};
+pub use crosscheck::{
+    crosscheck_matrix, crosscheck_numeric, structure_tensor_mat, CrosscheckResult,
+};
+pub use latex::{render_latex_tables, PaperPackInputs, SigFigPolicy};
+pub use policies::{Policy, PolicyRange, Severity};
 pub use report::{AssertionCheck, AssertionProvenance, AssertionReport};
-pub use symbolic::{NumMat, SymExpr};
+pub use symbolic::{NumMat, SymExpr, SymTerm};
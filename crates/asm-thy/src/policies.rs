@@ -1,5 +1,20 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
+/// How much a failing assertion counts against the report.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Failure fails the report.
+    #[default]
+    Error,
+    /// Failure is listed but does not fail the report.
+    Warning,
+    /// Informational; failure is recorded on the check only.
+    Info,
+}
+
 /// Inclusive range used for acceptance checks.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub struct PolicyRange {
@@ -58,6 +73,20 @@ pub struct Policy {
     /// Require ward artefacts to be present.
     #[serde(default = "Policy::default_require_ward")]
     pub require_ward: bool,
+    /// Verify the Jacobi identity on gauge structure constants when a gauge report is present.
+    #[serde(default)]
+    pub check_jacobi: bool,
+    /// Maximum tolerated Jacobi cyclic-sum violation.
+    #[serde(default = "Policy::default_jacobi_tol")]
+    pub jacobi_tol: f64,
+    /// Reject gauge reports whose closure or Ward checks covered only a subset
+    /// of generators.
+    #[serde(default = "Policy::default_require_full_gauge_checks")]
+    pub require_full_gauge_checks: bool,
+    /// Severity overrides keyed by check name. A key without `:` also covers
+    /// the check family sharing that prefix (e.g. `ward_identity`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub severities: BTreeMap<String, Severity>,
 }
 
 impl Policy {
@@ -97,6 +126,34 @@ impl Policy {
         true
     }
 
+    const fn default_jacobi_tol() -> f64 {
+        1e-6
+    }
+
+    const fn default_require_full_gauge_checks() -> bool {
+        true
+    }
+
+    /// Returns the severity applied to the check `name`.
+    ///
+    /// Exact overrides win over family overrides; without either, the
+    /// landscape pass-rate check is a [`Severity::Warning`] and every other
+    /// check an [`Severity::Error`].
+    pub fn severity_for(&self, name: &str) -> Severity {
+        let family = name.split_once(':').map(|(family, _)| family);
+        if let Some(severity) = self
+            .severities
+            .get(name)
+            .or_else(|| family.and_then(|family| self.severities.get(family)))
+        {
+            return *severity;
+        }
+        match name {
+            "landscape_filter_rate" => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+
     /// Rounds the provided value according to the policy granularity.
     pub fn round(&self, value: f64) -> f64 {
         if self.rounding <= 0.0 {
@@ -120,6 +177,10 @@ impl Default for Policy {
             strict: false,
             require_closure: Self::default_require_closure(),
             require_ward: Self::default_require_ward(),
+            check_jacobi: false,
+            jacobi_tol: Self::default_jacobi_tol(),
+            require_full_gauge_checks: Self::default_require_full_gauge_checks(),
+            severities: BTreeMap::new(),
         }
     }
 }
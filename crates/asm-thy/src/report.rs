@@ -4,7 +4,7 @@ use asm_core::errors::{AsmError, ErrorInfo};
 use serde::{Deserialize, Serialize};
 
 use crate::hash::stable_hash_string;
-use crate::policies::Policy;
+use crate::policies::{Policy, Severity};
 use crate::serde::to_canonical_json_bytes;
 
 fn report_error(code: &str, message: impl Into<String>) -> AsmError {
@@ -18,6 +18,9 @@ pub struct AssertionCheck {
     pub name: String,
     /// Whether the assertion passed under the configured policy.
     pub pass: bool,
+    /// How a failure of this check counts against the report.
+    #[serde(default)]
+    pub severity: Severity,
     /// Rounded metric captured during evaluation.
     pub metric: f64,
     /// Optional threshold used for scalar assertions.
@@ -49,6 +52,13 @@ pub struct AssertionReport {
     pub analysis_hash: String,
     /// Per-assertion results.
     pub checks: Vec<AssertionCheck>,
+    /// Whether no [`Severity::Error`] check failed. Reports written before
+    /// severities existed decode as `false`.
+    #[serde(default)]
+    pub overall_pass: bool,
+    /// Names of failing [`Severity::Warning`] checks, in check order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
     /// Provenance describing policy and input hashes.
     pub provenance: AssertionProvenance,
 }
@@ -60,13 +70,30 @@ impl AssertionReport {
         provenance: AssertionProvenance,
     ) -> Result<Self, AsmError> {
         let analysis_hash = stable_hash_string(&(&checks, &provenance))?;
+        let overall_pass = checks
+            .iter()
+            .all(|check| check.pass || check.severity != Severity::Error);
+        let warnings = checks
+            .iter()
+            .filter(|check| !check.pass && check.severity == Severity::Warning)
+            .map(|check| check.name.clone())
+            .collect();
         Ok(Self {
             analysis_hash,
             checks,
+            overall_pass,
+            warnings,
             provenance,
         })
     }
 
+    /// Returns the failing checks of the given severity, in check order.
+    pub fn failures(&self, severity: Severity) -> impl Iterator<Item = &AssertionCheck> {
+        self.checks
+            .iter()
+            .filter(move |check| !check.pass && check.severity == severity)
+    }
+
     /// Persists the report as canonical JSON bytes.
     pub fn to_bytes(&self) -> Result<Vec<u8>, AsmError> {
         to_canonical_json_bytes(self)
@@ -88,13 +115,28 @@ impl AssertionProvenance {
     }
 }
 
-/// Validates that the report contains at least one check.
-pub fn validate_checks(checks: &[AssertionCheck]) -> Result<(), AsmError> {
+/// Validates that the report contains at least one check and that every
+/// check carries the severity `policy` assigns to it.
+pub fn validate_checks(checks: &[AssertionCheck], policy: &Policy) -> Result<(), AsmError> {
     if checks.is_empty() {
         return Err(report_error(
             "empty-assertions",
             "at least one assertion must be executed",
         ));
     }
+    for check in checks {
+        let expected = policy.severity_for(&check.name);
+        if check.severity != expected {
+            return Err(AsmError::Serde(
+                ErrorInfo::new(
+                    "severity-mismatch",
+                    format!("check `{}` severity disagrees with the policy", check.name),
+                )
+                .with_context("check", check.name.clone())
+                .with_context("expected", format!("{expected:?}"))
+                .with_context("found", format!("{:?}", check.severity)),
+            ));
+        }
+    }
     Ok(())
 }
@@ -1,5 +1,14 @@
+use asm_core::errors::{AsmError, ErrorInfo};
 use serde::{Deserialize, Serialize};
 
+fn symbolic_error(code: &str, message: impl Into<String>) -> AsmError {
+    AsmError::Serde(ErrorInfo::new(code, message.into()))
+}
+
+fn square_shape(dim: usize) -> Vec<usize> {
+    vec![dim, dim]
+}
+
 /// Simple symbolic matrix expression stored in row-major order.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SymExpr {
@@ -7,9 +16,35 @@ pub struct SymExpr {
     pub dim: usize,
     /// Row-major entries representing the symbolic matrix.
     pub entries: Vec<f64>,
+    /// Explicit shape for non-square or higher-rank literals (empty means `[dim, dim]`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shape: Vec<usize>,
 }
 
 impl SymExpr {
+    /// Constructs a literal with an explicit row-major shape (e.g. `[n, n, n]` for tensors).
+    pub fn from_shape(shape: Vec<usize>, entries: Vec<f64>) -> Self {
+        Self {
+            dim: shape.first().copied().unwrap_or(0),
+            entries,
+            shape,
+        }
+    }
+
+    /// Returns the row-major shape of the literal.
+    pub fn shape(&self) -> Vec<usize> {
+        if self.shape.is_empty() {
+            square_shape(self.dim)
+        } else {
+            self.shape.clone()
+        }
+    }
+
+    /// Evaluates the literal into a numeric matrix with the same shape.
+    pub fn evaluate(&self) -> NumMat {
+        NumMat::from_shape(self.shape(), self.entries.clone())
+    }
+
     /// Constructs a diagonal matrix expression with the provided diagonal entries.
     pub fn from_diagonal(diagonal: &[f64]) -> Self {
         let dim = diagonal.len();
@@ -17,7 +52,11 @@ impl SymExpr {
         for (idx, value) in diagonal.iter().enumerate() {
             entries[idx * dim + idx] = *value;
         }
-        Self { dim, entries }
+        Self {
+            dim,
+            entries,
+            shape: Vec::new(),
+        }
     }
 
     /// Returns the trace of the symbolic matrix.
@@ -35,12 +74,50 @@ pub struct NumMat {
     pub dim: usize,
     /// Row-major numeric entries.
     pub entries: Vec<f64>,
+    /// Explicit shape for non-square or higher-rank arrays (empty means `[dim, dim]`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shape: Vec<usize>,
 }
 
 impl NumMat {
     /// Constructs a numeric matrix from row-major entries.
     pub fn new(dim: usize, entries: Vec<f64>) -> Self {
-        Self { dim, entries }
+        Self {
+            dim,
+            entries,
+            shape: Vec::new(),
+        }
+    }
+
+    /// Constructs a numeric array with an explicit row-major shape.
+    pub fn from_shape(shape: Vec<usize>, entries: Vec<f64>) -> Self {
+        Self {
+            dim: shape.first().copied().unwrap_or(0),
+            entries,
+            shape,
+        }
+    }
+
+    /// Returns the row-major shape of the array.
+    pub fn shape(&self) -> Vec<usize> {
+        if self.shape.is_empty() {
+            square_shape(self.dim)
+        } else {
+            self.shape.clone()
+        }
+    }
+
+    /// Converts a row-major flat index into a multi-index over [`NumMat::shape`].
+    pub fn unravel_index(&self, flat: usize) -> Vec<usize> {
+        let shape = self.shape();
+        let mut index = vec![0; shape.len()];
+        let mut rem = flat;
+        for (axis, extent) in shape.iter().enumerate().rev() {
+            let extent = (*extent).max(1);
+            index[axis] = rem % extent;
+            rem /= extent;
+        }
+        index
     }
 
     /// Computes the Frobenius norm of the matrix.
@@ -60,6 +137,7 @@ pub fn commutator(a: &SymExpr, b: &SymExpr) -> SymExpr {
         return SymExpr {
             dim: 0,
             entries: Vec::new(),
+            shape: Vec::new(),
         };
     }
     let stride_a = a.dim.max(1);
@@ -79,7 +157,11 @@ pub fn commutator(a: &SymExpr, b: &SymExpr) -> SymExpr {
         }
     }
     let entries = ab.into_iter().zip(ba).map(|(lhs, rhs)| lhs - rhs).collect();
-    SymExpr { dim, entries }
+    SymExpr {
+        dim,
+        entries,
+        shape: Vec::new(),
+    }
 }
 
 /// Returns the Hermitian adjoint of the provided matrix (which is the transpose here).
@@ -93,5 +175,113 @@ pub fn adjoint(expr: &SymExpr) -> SymExpr {
     SymExpr {
         dim: expr.dim,
         entries,
+        shape: Vec::new(),
+    }
+}
+
+/// Symbolic expression tree combining matrix literals.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case", tag = "op", content = "args")]
+pub enum SymTerm {
+    /// Matrix or tensor literal.
+    Literal(SymExpr),
+    /// Element-wise sum of equally shaped operands.
+    Sum(Vec<SymTerm>),
+    /// Left-to-right matrix product of rank-2 operands.
+    Product(Vec<SymTerm>),
+    /// Scalar multiple of an operand.
+    Scale(f64, Box<SymTerm>),
+}
+
+impl From<SymExpr> for SymTerm {
+    fn from(expr: SymExpr) -> Self {
+        SymTerm::Literal(expr)
+    }
+}
+
+fn matmul(lhs: &NumMat, rhs: &NumMat) -> Result<NumMat, AsmError> {
+    let (ls, rs) = (lhs.shape(), rhs.shape());
+    if ls.len() != 2 || rs.len() != 2 || ls[1] != rs[0] {
+        return Err(symbolic_error(
+            "shape-mismatch",
+            format!("cannot multiply shapes {ls:?} and {rs:?}"),
+        ));
+    }
+    let (rows, inner, cols) = (ls[0], ls[1], rs[1]);
+    let mut entries = vec![0.0; rows * cols];
+    for row in 0..rows {
+        for col in 0..cols {
+            let mut acc = 0.0;
+            for k in 0..inner {
+                acc += lhs.entries[row * inner + k] * rhs.entries[k * cols + col];
+            }
+            entries[row * cols + col] = acc;
+        }
+    }
+    Ok(NumMat::from_shape(vec![rows, cols], entries))
+}
+
+impl SymTerm {
+    /// Evaluates the expression into a numeric matrix.
+    pub fn evaluate(&self) -> Result<NumMat, AsmError> {
+        match self {
+            SymTerm::Literal(expr) => {
+                let value = expr.evaluate();
+                let expected: usize = value.shape().iter().product();
+                if expected != value.entries.len() {
+                    return Err(symbolic_error(
+                        "entry-mismatch",
+                        format!(
+                            "literal of shape {:?} requires {expected} entries, found {}",
+                            value.shape(),
+                            value.entries.len()
+                        ),
+                    ));
+                }
+                Ok(value)
+            }
+            SymTerm::Sum(terms) => {
+                let mut iter = terms.iter();
+                let first = iter
+                    .next()
+                    .ok_or_else(|| symbolic_error("empty-sum", "sum requires an operand"))?;
+                let mut acc = first.evaluate()?;
+                for term in iter {
+                    let value = term.evaluate()?;
+                    if value.shape() != acc.shape() {
+                        return Err(symbolic_error(
+                            "shape-mismatch",
+                            format!(
+                                "cannot add shapes {:?} and {:?}",
+                                acc.shape(),
+                                value.shape()
+                            ),
+                        ));
+                    }
+                    for (lhs, rhs) in acc.entries.iter_mut().zip(value.entries) {
+                        *lhs += rhs;
+                    }
+                }
+                Ok(acc)
+            }
+            SymTerm::Product(terms) => {
+                let mut iter = terms.iter();
+                let first = iter.next().ok_or_else(|| {
+                    symbolic_error("empty-product", "product requires an operand")
+                })?;
+                let mut acc = first.evaluate()?;
+                for term in iter {
+                    acc = matmul(&acc, &term.evaluate()?)?;
+                }
+                Ok(acc)
+            }
+            SymTerm::Scale(factor, term) => {
+                let mut value = term.evaluate()?;
+                for entry in value.entries.iter_mut() {
+                    *entry *= factor;
+                }
+                Ok(value)
+            }
+        }
     }
 }
@@ -0,0 +1,131 @@
+mod common;
+
+use std::fs;
+
+use asm_core::errors::AsmError;
+use asm_spec::DecayClass;
+use asm_thy::bundle::{build_manuscript_bundle, BundlePlan, ASSERTION_WARNINGS_FILE};
+use asm_thy::report::validate_checks;
+use asm_thy::serde::to_canonical_json_bytes;
+use asm_thy::{run_assertions, AssertionInputs, Policy, Severity};
+use tempfile::tempdir;
+
+use common::sample_inputs;
+
+/// Sample inputs whose only failure is an out-of-range landscape pass rate.
+fn landscape_rate_failure() -> (AssertionInputs, Policy) {
+    let (mut inputs, policy) = sample_inputs();
+    inputs.spectrum.as_mut().unwrap().correlation.decay_class = DecayClass::PowerLaw;
+    inputs.summary.as_mut().unwrap().pass_rates.anthropic = 0.95;
+    (inputs, policy)
+}
+
+#[test]
+fn warning_failure_passes_overall() -> Result<(), AsmError> {
+    let (inputs, policy) = landscape_rate_failure();
+    let report = run_assertions(&inputs, &policy)?;
+    let failing: Vec<_> = report.checks.iter().filter(|check| !check.pass).collect();
+    assert_eq!(failing.len(), 1);
+    assert_eq!(failing[0].severity, Severity::Warning);
+    assert!(report.overall_pass);
+    assert_eq!(report.warnings, ["landscape_filter_rate"]);
+    Ok(())
+}
+
+#[test]
+fn error_failure_fails_overall() -> Result<(), AsmError> {
+    let (inputs, mut policy) = landscape_rate_failure();
+    policy
+        .severities
+        .insert("landscape_filter_rate".to_string(), Severity::Error);
+    let report = run_assertions(&inputs, &policy)?;
+    assert!(!report.overall_pass);
+    assert!(report.warnings.is_empty());
+    let names: Vec<_> = report
+        .failures(Severity::Error)
+        .map(|check| check.name.as_str())
+        .collect();
+    assert_eq!(names, ["landscape_filter_rate"]);
+    Ok(())
+}
+
+#[test]
+fn family_overrides_cover_ward_identities() {
+    let mut policy = Policy::default();
+    assert_eq!(policy.severity_for("closure_residual"), Severity::Error);
+    assert_eq!(
+        policy.severity_for("ward_identity:singlet"),
+        Severity::Error
+    );
+    policy
+        .severities
+        .insert("ward_identity".to_string(), Severity::Info);
+    policy
+        .severities
+        .insert("ward_identity:singlet".to_string(), Severity::Warning);
+    assert_eq!(policy.severity_for("ward_identity:triplet"), Severity::Info);
+    assert_eq!(
+        policy.severity_for("ward_identity:singlet"),
+        Severity::Warning
+    );
+}
+
+#[test]
+fn validate_checks_rejects_inconsistent_severities() -> Result<(), AsmError> {
+    let (inputs, policy) = landscape_rate_failure();
+    let mut checks = run_assertions(&inputs, &policy)?.checks;
+    validate_checks(&checks, &policy)?;
+    let check = checks
+        .iter_mut()
+        .find(|check| check.name == "landscape_filter_rate")
+        .unwrap();
+    check.severity = Severity::Error;
+    let err = validate_checks(&checks, &policy).unwrap_err();
+    assert_eq!(err.info().code, "severity-mismatch");
+    Ok(())
+}
+
+#[test]
+fn bundle_embeds_warnings_and_optionally_blocks_errors() -> Result<(), AsmError> {
+    let (inputs, policy) = landscape_rate_failure();
+    let src = tempdir().unwrap();
+    let job = src.path().join("job_0");
+    fs::create_dir_all(&job).unwrap();
+    let report = run_assertions(&inputs, &policy)?;
+    fs::write(
+        job.join("assert_report.json"),
+        to_canonical_json_bytes(&report)?,
+    )
+    .unwrap();
+    let blocking = BundlePlan {
+        block_on_assertion_errors: true,
+        ..BundlePlan::default()
+    };
+
+    let out = tempdir().unwrap();
+    let bundle = build_manuscript_bundle(&[src.path().to_path_buf()], out.path(), &blocking)?;
+    assert_eq!(bundle.assertion_warnings.len(), 1);
+    assert!(bundle.assertion_warnings[0].contains("landscape_filter_rate"));
+    let written = fs::read_to_string(out.path().join(ASSERTION_WARNINGS_FILE)).unwrap();
+    assert!(written.contains(&bundle.assertion_warnings[0]));
+
+    let (inputs, policy) = sample_inputs();
+    let failing = run_assertions(&inputs, &policy)?;
+    assert!(!failing.overall_pass);
+    fs::write(
+        job.join("assert_report.json"),
+        to_canonical_json_bytes(&failing)?,
+    )
+    .unwrap();
+    let out = tempdir().unwrap();
+    let err =
+        build_manuscript_bundle(&[src.path().to_path_buf()], out.path(), &blocking).unwrap_err();
+    assert_eq!(err.info().code, "assertion-errors");
+    let bundle = build_manuscript_bundle(
+        &[src.path().to_path_buf()],
+        out.path(),
+        &BundlePlan::default(),
+    )?;
+    assert!(bundle.assertion_warnings.is_empty());
+    Ok(())
+}
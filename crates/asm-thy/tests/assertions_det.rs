@@ -1,6 +1,7 @@
 mod common;
 
 use asm_core::errors::AsmError;
+use asm_gauge::WardIdentity;
 use asm_thy::run_assertions;
 use asm_thy::serde::to_canonical_json_bytes;
 
@@ -22,3 +23,50 @@ fn assertions_are_deterministic() -> Result<(), AsmError> {
 
     Ok(())
 }
+
+#[test]
+fn power_law_decay_skips_gap_relation() -> Result<(), AsmError> {
+    let (mut inputs, policy) = sample_inputs();
+    let spectrum = inputs.spectrum.as_mut().unwrap();
+    spectrum.correlation.decay_class = asm_spec::DecayClass::PowerLaw;
+    spectrum.correlation.xi += 100.0;
+    let report = run_assertions(&inputs, &policy)?;
+    let check = report
+        .checks
+        .iter()
+        .find(|check| check.name == "correlation_gap_relation")
+        .unwrap();
+    assert!(check.pass);
+    assert!(check.threshold.is_none());
+    assert!(check.note.as_deref().unwrap().starts_with("skipped"));
+    Ok(())
+}
+
+#[test]
+fn ward_identities_become_assertion_checks() -> Result<(), AsmError> {
+    let (mut inputs, policy) = sample_inputs();
+    let identity = |name: &str, residual: f64| WardIdentity {
+        name: name.to_string(),
+        residual,
+        tolerance: 1e-9,
+        pass: residual <= 1e-9,
+    };
+    let ward = &mut inputs.gauge.as_mut().unwrap().ward;
+    ward.identities.push(identity("singlet", 0.0));
+    ward.identities.push(identity("broken", 0.5));
+    let report = run_assertions(&inputs, &policy)?;
+    let check = |name: &str| {
+        report
+            .checks
+            .iter()
+            .find(|check| check.name == name)
+            .cloned()
+            .unwrap()
+    };
+    assert!(check("ward_identity:singlet").pass);
+    let broken = check("ward_identity:broken");
+    assert!(!broken.pass);
+    assert_eq!(broken.threshold, Some(1e-9));
+    check("ward_commutator_bound");
+    Ok(())
+}
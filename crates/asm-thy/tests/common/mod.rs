@@ -64,6 +64,7 @@ fn sample_interaction(spectrum: &SpectrumReport, couplings: CouplingsFit) -> Int
                 total_time: 0.64,
                 final_norm: 1.0,
                 traj_hash: "traj-sample".to_string(),
+                adaptive: None,
             },
             steps: Vec::new(),
         },
@@ -72,6 +73,8 @@ fn sample_interaction(spectrum: &SpectrumReport, couplings: CouplingsFit) -> Int
             kernel: KernelOpts::default(),
             measure: MeasureOpts::default(),
             fit: FitOpts::default(),
+            hash_format: asm_core::LEGACY_HASH_FORMAT,
+            build: None,
         },
     }
 }
@@ -119,6 +122,7 @@ fn sample_summary() -> SummaryReport {
         quantiles: BTreeMap::new(),
         correlations: BTreeMap::new(),
         notes: vec![],
+        stage_durations: BTreeMap::new(),
     }
 }
 
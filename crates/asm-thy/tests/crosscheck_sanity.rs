@@ -1,5 +1,51 @@
+use std::fs;
+use std::path::PathBuf;
+
 use asm_core::errors::AsmError;
-use asm_thy::{crosscheck_numeric, NumMat, Policy, SymExpr};
+use asm_gauge::{check_closure, from_json_slice, ClosureOpts, GaugeReport, RepMatrices};
+use asm_thy::{
+    crosscheck_matrix, crosscheck_numeric, structure_tensor_mat, NumMat, Policy, SymExpr, SymTerm,
+};
+
+#[path = "../../../tests/common/mod.rs"]
+mod common;
+
+use common::{levi_civita, su2_adjoint};
+
+fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+fn su2_symbolic_tensor() -> SymTerm {
+    let mut entries = Vec::with_capacity(27);
+    for i in 0..3 {
+        for j in 0..3 {
+            for k in 0..3 {
+                entries.push(levi_civita(i, j, k));
+            }
+        }
+    }
+    SymTerm::Literal(SymExpr::from_shape(vec![3, 3, 3], entries))
+}
+
+fn fixture_dir() -> PathBuf {
+    workspace_root().join("fixtures/phase12/t1_seed0")
+}
+
+fn fixture_gauge() -> Result<GaugeReport, AsmError> {
+    let bytes = fs::read(fixture_dir().join("gauge_report.json")).unwrap();
+    from_json_slice(&bytes)
+}
+
+fn fixture_rep() -> Result<RepMatrices, AsmError> {
+    let bytes = fs::read(fixture_dir().join("rep.json")).unwrap();
+    from_json_slice(&bytes)
+}
+
+/// All-zero `[n, n, n]` tensor expected for a commuting generator set.
+fn abelian_symbolic_tensor(n: usize) -> SymTerm {
+    SymTerm::Literal(SymExpr::from_shape(vec![n, n, n], vec![0.0; n * n * n]))
+}
 
 #[test]
 fn symbolic_numeric_crosscheck_matches() -> Result<(), AsmError> {
@@ -9,3 +55,72 @@ fn symbolic_numeric_crosscheck_matches() -> Result<(), AsmError> {
     assert!(result.pass);
     Ok(())
 }
+
+#[test]
+fn fixture_closure_structure_tensor_crosschecks() -> Result<(), AsmError> {
+    let gauge = fixture_gauge()?;
+    let rep = fixture_rep()?;
+    assert_eq!(
+        gauge.closure,
+        check_closure(&rep, &ClosureOpts::default())?,
+        "fixture closure must match its representation"
+    );
+    assert!(gauge.closure.closed);
+    let n = rep.gens.len();
+    let numeric = structure_tensor_mat(&gauge.closure, n)?;
+    assert_eq!(numeric.shape, vec![n, n, n]);
+    let result = crosscheck_matrix(&abelian_symbolic_tensor(n), &numeric, &Policy::default())?;
+    assert!(result.pass, "{result:?}");
+    assert_eq!(result.rel_frobenius, 0.0);
+    Ok(())
+}
+
+#[test]
+fn perturbed_fixture_tensor_reports_max_deviation_index() -> Result<(), AsmError> {
+    let gauge = fixture_gauge()?;
+    let n = fixture_rep()?.gens.len();
+    let mut numeric = structure_tensor_mat(&gauge.closure, n)?;
+    // Flat offset of f[1][2][0] in the row-major [3, 3, 3] layout.
+    numeric.entries[15] += 0.25;
+    let result = crosscheck_matrix(&abelian_symbolic_tensor(n), &numeric, &Policy::default())?;
+    assert!(!result.pass);
+    assert_eq!(result.max_dev_index, Some(vec![1, 2, 0]));
+    assert_eq!(result.max_abs_dev, 0.25);
+    assert!(result.rel_frobenius > 0.0);
+    Ok(())
+}
+
+#[test]
+fn su2_closure_structure_tensor_matches_levi_civita() -> Result<(), AsmError> {
+    let closure = check_closure(&su2_adjoint(), &ClosureOpts::default())?;
+    assert!(closure.closed);
+    let numeric = structure_tensor_mat(&closure, 3)?;
+    let result = crosscheck_matrix(&su2_symbolic_tensor(), &numeric, &Policy::default())?;
+    assert!(result.pass, "{result:?}");
+    assert_eq!(result.rel_frobenius, 0.0);
+    Ok(())
+}
+
+#[test]
+fn sum_and_product_expressions_evaluate() -> Result<(), AsmError> {
+    let a = SymExpr::from_diagonal(&[1.0, 2.0]);
+    let b = SymExpr::from_shape(vec![2, 2], vec![0.0, 1.0, 1.0, 0.0]);
+    let expr = SymTerm::Sum(vec![
+        SymTerm::Product(vec![a.clone().into(), b.clone().into()]),
+        SymTerm::Scale(2.0, Box::new(a.into())),
+    ]);
+    let numeric = NumMat::new(2, vec![2.0, 1.0, 2.0, 4.0]);
+    let result = crosscheck_matrix(&expr, &numeric, &Policy::default())?;
+    assert!(result.pass, "{result:?}");
+    Ok(())
+}
+
+#[test]
+fn shape_mismatch_names_both_shapes() {
+    let symbolic = SymTerm::Literal(SymExpr::from_diagonal(&[1.0, 2.0]));
+    let numeric = NumMat::from_shape(vec![2, 3], vec![0.0; 6]);
+    let err = crosscheck_matrix(&symbolic, &numeric, &Policy::default()).unwrap_err();
+    assert_eq!(err.info().code, "shape-mismatch");
+    assert!(err.info().message.contains("[2, 2]"));
+    assert!(err.info().message.contains("[2, 3]"));
+}
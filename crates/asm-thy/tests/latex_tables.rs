@@ -0,0 +1,131 @@
+mod common;
+
+use std::fs;
+
+use asm_core::errors::AsmError;
+use asm_land::stat::Quantiles;
+use asm_thy::bundle::{build_manuscript_bundle, BundlePlan};
+use asm_thy::latex::escape_latex;
+use asm_thy::serde::to_canonical_json_bytes;
+use asm_thy::{render_latex_tables, run_assertions, PaperPackInputs, SigFigPolicy};
+
+use common::sample_inputs;
+use tempfile::tempdir;
+
+const GOLDEN_DIR: &str = "tests/fixtures/latex";
+
+fn paper_inputs() -> Result<PaperPackInputs, AsmError> {
+    let (inputs, policy) = sample_inputs();
+    let assertions = run_assertions(&inputs, &policy)?;
+    let mut summary = inputs.summary.clone().unwrap();
+    for (kpi, q05, q50, q95) in [
+        ("gap_min", 0.012_5, 0.215, 0.987_65),
+        ("xi_max", 1.5, 3.25, 12_345_678.0),
+    ] {
+        summary.quantiles.insert(
+            kpi.to_string(),
+            Quantiles {
+                q05,
+                q50,
+                q95,
+                points: Vec::new(),
+                bands: None,
+            },
+        );
+    }
+    Ok(PaperPackInputs {
+        assertions: Some(assertions),
+        summary: Some(summary),
+        couplings: inputs.interaction.map(|interaction| interaction.fit),
+        sig_figs: SigFigPolicy::default(),
+    })
+}
+
+#[test]
+fn tables_match_golden() -> Result<(), AsmError> {
+    let tables = render_latex_tables(&paper_inputs()?)?;
+    let names: Vec<_> = tables.keys().map(String::as_str).collect();
+    assert_eq!(names, ["assertions", "couplings", "landscape"]);
+    for (name, body) in &tables {
+        let golden = format!("{GOLDEN_DIR}/{name}.tex");
+        if std::env::var_os("ASM_UPDATE_GOLDEN").is_some() {
+            fs::write(&golden, body).expect("write golden");
+        }
+        let expected = fs::read_to_string(&golden).expect("golden");
+        assert_eq!(body, &expected, "{name} table drifted from golden");
+    }
+    assert_eq!(tables, render_latex_tables(&paper_inputs()?)?);
+    Ok(())
+}
+
+#[test]
+fn sig_figs_round_half_to_even() -> Result<(), AsmError> {
+    let two = SigFigPolicy { digits: 2 };
+    assert_eq!(two.format(0.125), "$0.12$");
+    assert_eq!(two.format(0.135), "$0.14$");
+    assert_eq!(two.format(0.1251), "$0.13$");
+    assert_eq!(two.format(9.96), "$10$");
+    assert_eq!(two.format(-2.5e-7), r"$-2.5 \times 10^{-7}$");
+    assert_eq!(two.format(0.0), "$0.0$");
+    assert_eq!(two.format(f64::NAN), r"$\text{NaN}$");
+    assert_eq!(two.format(f64::INFINITY), r"$\infty$");
+    assert_eq!(two.format(f64::NEG_INFINITY), r"$-\infty$");
+
+    let err = render_latex_tables(&PaperPackInputs {
+        sig_figs: SigFigPolicy { digits: 0 },
+        ..PaperPackInputs::default()
+    })
+    .unwrap_err();
+    assert_eq!(err.info().code, "latex-sig-figs");
+    Ok(())
+}
+
+#[test]
+fn escapes_special_characters() {
+    assert_eq!(
+        escape_latex("ward_identity:a&b 50% #1"),
+        r"ward\_identity:a\&b 50\% \#1"
+    );
+}
+
+#[test]
+fn bundle_writes_tables_when_requested() -> Result<(), AsmError> {
+    let inputs = paper_inputs()?;
+    let src = tempdir().unwrap();
+    let job = src.path().join("job_0");
+    fs::create_dir_all(&job).unwrap();
+    fs::write(
+        job.join("assert_report.json"),
+        to_canonical_json_bytes(inputs.assertions.as_ref().unwrap())?,
+    )
+    .unwrap();
+    fs::write(
+        job.join("couplings_fit.json"),
+        to_canonical_json_bytes(inputs.couplings.as_ref().unwrap())?,
+    )
+    .unwrap();
+
+    let plain_out = tempdir().unwrap();
+    let plain = build_manuscript_bundle(
+        &[src.path().to_path_buf()],
+        plain_out.path(),
+        &BundlePlan::default(),
+    )?;
+    assert!(plain.tables.is_empty());
+
+    let plan = BundlePlan {
+        latex_tables: true,
+        ..BundlePlan::default()
+    };
+    let out = tempdir().unwrap();
+    let bundle = build_manuscript_bundle(&[src.path().to_path_buf()], out.path(), &plan)?;
+    let names: Vec<_> = bundle.tables.keys().map(String::as_str).collect();
+    assert_eq!(names, ["assertions", "couplings"]);
+    assert_eq!(bundle.manifest, plain.manifest);
+    assert_ne!(bundle.bundle_hash, plain.bundle_hash);
+
+    let written = fs::read_to_string(out.path().join(&bundle.tables["couplings"])).unwrap();
+    let expected = fs::read_to_string(format!("{GOLDEN_DIR}/couplings.tex")).unwrap();
+    assert_eq!(written, expected);
+    Ok(())
+}
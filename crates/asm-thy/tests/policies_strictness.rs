@@ -1,6 +1,7 @@
 mod common;
 
 use asm_core::errors::AsmError;
+use asm_gauge::{ClosureOpts, SubsetSelection, SubsetSpec};
 use asm_thy::run_assertions;
 
 use common::sample_inputs;
@@ -22,3 +23,62 @@ fn stricter_policy_triggers_failure() -> Result<(), AsmError> {
     );
     Ok(())
 }
+
+#[test]
+fn jacobi_assertion_is_opt_in() -> Result<(), AsmError> {
+    let (inputs, mut policy) = sample_inputs();
+    let report = run_assertions(&inputs, &policy)?;
+    assert!(report
+        .checks
+        .iter()
+        .all(|check| check.name != "jacobi_identity"));
+
+    policy.check_jacobi = true;
+    let report = run_assertions(&inputs, &policy)?;
+    let jacobi = report
+        .checks
+        .iter()
+        .find(|check| check.name == "jacobi_identity")
+        .expect("jacobi check recorded");
+    assert!(jacobi.pass);
+    Ok(())
+}
+
+#[test]
+fn partial_gauge_checks_need_policy_opt_in() -> Result<(), AsmError> {
+    let (mut inputs, mut policy) = sample_inputs();
+    let gauge = inputs.gauge.as_mut().unwrap();
+    let rep = serde_json::json!({
+        "basis": "modes",
+        "dim": 2,
+        "gens": [
+            {"id": "a", "matrix": [1.0, 0.0, 0.0, -1.0], "norm": 1.0},
+            {"id": "b", "matrix": [0.0, 1.0, 1.0, 0.0], "norm": 1.0},
+            {"id": "c", "matrix": [0.0, 1.0, -1.0, 0.0], "norm": 1.0}
+        ]
+    });
+    let rep = serde_json::from_value(rep).unwrap();
+    let opts = ClosureOpts {
+        pair_subset: Some(SubsetSpec {
+            selection: SubsetSelection::Indices(vec![0]),
+            seed: 0,
+        }),
+        ..ClosureOpts::default()
+    };
+    gauge.closure = asm_gauge::check_closure(&rep, &opts)?;
+    assert!(gauge.closure.partial);
+
+    assert!(policy.require_full_gauge_checks);
+    let err = run_assertions(&inputs, &policy).unwrap_err();
+    assert_eq!(err.info().code, "partial-gauge-check");
+    assert_eq!(err.info().context.get("check").unwrap(), "closure");
+    assert_eq!(err.info().context.get("total").unwrap(), "3");
+
+    policy.require_full_gauge_checks = false;
+    let report = run_assertions(&inputs, &policy)?;
+    assert!(report
+        .checks
+        .iter()
+        .any(|check| check.name == "closure_residual"));
+    Ok(())
+}
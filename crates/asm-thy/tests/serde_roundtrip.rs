@@ -27,6 +27,7 @@ fn manuscript_bundle_roundtrip() -> Result<(), AsmError> {
         include: vec!["fixtures/phase11/**/spectrum_report.json".to_string()],
         copy_figures: false,
         flatten_paths: true,
+        ..BundlePlan::default()
     };
     let tmp = tempdir().unwrap();
     let bundle = build_manuscript_bundle(&[workspace_root()], tmp.path(), &plan)?;
@@ -0,0 +1,45 @@
+document.querySelectorAll("table.interactive").forEach(function (table) {
+  var body = table.tBodies[0];
+  var rows = Array.prototype.slice.call(body.rows);
+  var filter = document.createElement("input");
+  filter.type = "search";
+  filter.placeholder = "Filter rows";
+  var status = document.createElement("select");
+  ["all", "pass", "fail", "n/a"].forEach(function (value) {
+    var option = document.createElement("option");
+    option.value = option.textContent = value;
+    status.appendChild(option);
+  });
+  function apply() {
+    var needle = filter.value.toLowerCase();
+    rows.forEach(function (row) {
+      var text = row.textContent.toLowerCase().indexOf(needle) !== -1;
+      var state = status.value === "all" || row.getAttribute("data-status") === status.value;
+      row.hidden = !(text && state);
+    });
+  }
+  filter.addEventListener("input", apply);
+  status.addEventListener("change", apply);
+  table.parentNode.insertBefore(filter, table);
+  if (rows.some(function (row) { return row.hasAttribute("data-status"); })) {
+    table.parentNode.insertBefore(status, table);
+  }
+  Array.prototype.forEach.call(table.tHead.rows[0].cells, function (header, column) {
+    var ascending = true;
+    header.style.cursor = "pointer";
+    header.addEventListener("click", function () {
+      var key = function (row) { return row.cells[column].getAttribute("data-sort"); };
+      rows.sort(function (a, b) {
+        var x = key(a), y = key(b);
+        if (x === "" || y === "") {
+          return (x === "") - (y === "");
+        }
+        var nx = Number(x), ny = Number(y);
+        var order = isNaN(nx) || isNaN(ny) ? x.localeCompare(y) : nx - ny;
+        return ascending ? order : -order;
+      });
+      ascending = !ascending;
+      rows.forEach(function (row) { body.appendChild(row); });
+    });
+  });
+});
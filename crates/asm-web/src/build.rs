@@ -1,5 +1,7 @@
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::io::ErrorKind;
+use std::path::{Component, Path};
 
 use asm_core::errors::{AsmError, ErrorInfo};
 use asm_dsr::query::QueryParams;
@@ -7,13 +9,67 @@ use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
 
 use crate::collect::collect_site_data;
-use crate::pages::{render_pages, validate_config, SiteConfig};
-use crate::serde::to_canonical_json_bytes;
+use crate::pages::{plan_pages, render_page, table_data_path, validate_config, SiteConfig};
+use crate::serde::{from_json_slice, to_canonical_json_bytes};
+
+/// Manifest an incremental build diffs against.
+const BUILD_MANIFEST_FILE: &str = "build_manifest.json";
+/// Manifest written by every build, also read when `build_manifest.json` is missing.
+const MANIFEST_FILE: &str = "manifest.json";
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BuildManifest {
     pub page_count: usize,
     pub generated_at: String,
+    /// Input hashes recorded per output path, used to skip unchanged outputs on rebuild.
+    #[serde(default)]
+    pub outputs: BTreeMap<String, BTreeMap<String, String>>,
+    /// Outputs rendered during this build.
+    #[serde(default)]
+    pub regenerated: Vec<String>,
+    /// Outputs deleted because their sources vanished.
+    #[serde(default)]
+    pub removed: Vec<String>,
+}
+
+fn output_dir_error(path: &Path, err: impl ToString) -> AsmError {
+    AsmError::Serde(
+        ErrorInfo::new("asm_web.output_dir", err.to_string())
+            .with_context("path", path.display().to_string()),
+    )
+}
+
+fn write_error(path: &Path, err: impl ToString) -> AsmError {
+    AsmError::Serde(
+        ErrorInfo::new("asm_web.write", err.to_string())
+            .with_context("path", path.display().to_string()),
+    )
+}
+
+fn previous_outputs(out_dir: &Path) -> BTreeMap<String, BTreeMap<String, String>> {
+    [BUILD_MANIFEST_FILE, MANIFEST_FILE]
+        .iter()
+        .find_map(|name| fs::read(out_dir.join(name)).ok())
+        .and_then(|bytes| from_json_slice::<BuildManifest>(&bytes).ok())
+        .map(|manifest| manifest.outputs)
+        .unwrap_or_default()
+}
+
+fn output_key(relative: &Path) -> String {
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+fn write_output(path: &Path, bytes: &[u8]) -> Result<(), AsmError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| output_dir_error(parent, err))?;
+    }
+    fs::write(path, bytes).map_err(|err| write_error(path, err))
+}
+
+fn is_contained(relative: &Path) -> bool {
+    relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
 }
 
 pub fn build_site(
@@ -24,40 +80,58 @@ pub fn build_site(
 ) -> Result<BuildManifest, AsmError> {
     validate_config(config)?;
     let data = collect_site_data(conn, params)?;
-    let pages = render_pages(config, &data)?;
-    fs::create_dir_all(out_dir).map_err(|err| {
-        AsmError::Serde(
-            ErrorInfo::new("asm_web.output_dir", err.to_string())
-                .with_context("path", out_dir.display().to_string()),
-        )
-    })?;
-    for page in &pages {
-        let path = out_dir.join(&page.path);
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent).map_err(|err| {
-                AsmError::Serde(
-                    ErrorInfo::new("asm_web.output_dir", err.to_string())
-                        .with_context("path", parent.display().to_string()),
-                )
-            })?;
+    let plans = plan_pages(config, &data)?;
+    fs::create_dir_all(out_dir).map_err(|err| output_dir_error(out_dir, err))?;
+    let previous = previous_outputs(out_dir);
+
+    let mut outputs = BTreeMap::new();
+    let mut regenerated = Vec::new();
+    for plan in &plans {
+        // A page and its table data share the page's inputs and are rendered together.
+        let mut keys = vec![output_key(&plan.path)];
+        keys.extend(table_data_path(&plan.path).map(|path| output_key(&path)));
+        let unchanged = config.incremental
+            && !config.force
+            && keys
+                .iter()
+                .all(|key| previous.get(key) == Some(&plan.inputs) && out_dir.join(key).is_file());
+        if !unchanged {
+            let page = render_page(config, &data, &plan.path)?;
+            write_output(&out_dir.join(&keys[0]), page.content.as_bytes())?;
+            if let Some(key) = keys.get(1) {
+                write_output(&out_dir.join(key), &to_canonical_json_bytes(&page.tables)?)?;
+            }
+            regenerated.extend(keys.iter().cloned());
+        }
+        for key in keys {
+            outputs.insert(key, plan.inputs.clone());
         }
-        fs::write(&path, page.content.as_bytes()).map_err(|err| {
-            AsmError::Serde(
-                ErrorInfo::new("asm_web.write", err.to_string())
-                    .with_context("path", path.display().to_string()),
-            )
-        })?;
     }
+
+    let mut removed = Vec::new();
+    for key in previous.keys() {
+        if outputs.contains_key(key) || !is_contained(Path::new(key)) {
+            continue;
+        }
+        let path = out_dir.join(key);
+        match fs::remove_file(&path) {
+            Ok(()) => removed.push(key.clone()),
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => return Err(write_error(&path, err)),
+        }
+    }
+
     let manifest = BuildManifest {
-        page_count: pages.len(),
+        page_count: plans.len(),
         generated_at: chrono::Utc::now().to_rfc3339(),
+        outputs,
+        regenerated,
+        removed,
     };
     let manifest_bytes = to_canonical_json_bytes(&manifest)?;
-    fs::write(out_dir.join("manifest.json"), manifest_bytes).map_err(|err| {
-        AsmError::Serde(
-            ErrorInfo::new("asm_web.write", err.to_string())
-                .with_context("path", out_dir.join("manifest.json").display().to_string()),
-        )
-    })?;
+    for name in [MANIFEST_FILE, BUILD_MANIFEST_FILE] {
+        let path = out_dir.join(name);
+        fs::write(&path, &manifest_bytes).map_err(|err| write_error(&path, err))?;
+    }
     Ok(manifest)
 }
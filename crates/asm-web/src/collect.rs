@@ -1,22 +1,286 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
 use asm_core::errors::{AsmError, ErrorInfo};
 use asm_dsr::query::{QueryParams, RegistryQuery};
 use asm_dsr::schema::{ArtifactRecord, MetricRecord, SubmissionRecord};
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::serde::to_canonical_json_bytes;
 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct SiteData {
     pub submissions: Vec<SubmissionRecord>,
     pub artifacts: Vec<ArtifactRecord>,
     pub metrics: Vec<MetricRecord>,
+    /// Content hash per source record keyed by `submission:<id>`, `artifact:<id>`
+    /// and `metrics:<submission_id>`.
+    #[serde(default)]
+    pub source_hashes: BTreeMap<String, String>,
+    /// Per-submission detail data, ordered by submission id.
+    #[serde(default)]
+    pub runs: Vec<RunDetailData>,
+}
+
+/// Headline numbers read from a spectrum report.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpectrumHeadline {
+    pub c_est: Option<f64>,
+    pub gap: Option<f64>,
+}
+
+/// Headline numbers read from a gauge report.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GaugeHeadline {
+    /// Factor types in decomposition order.
+    pub factors: Vec<String>,
+    pub ward_residual: Option<f64>,
+    pub ward_pass: Option<bool>,
+    pub closure_residual: Option<f64>,
+    pub closure_pass: Option<bool>,
+}
+
+/// Coupling vector read from an interaction report, keyed by coupling name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InteractionHeadline {
+    pub couplings: BTreeMap<String, f64>,
+}
+
+/// One row of an `assertion_report.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssertionRow {
+    pub name: String,
+    pub pass: bool,
+    pub metric: Option<f64>,
+    pub note: Option<String>,
+}
+
+/// Artefact recorded for a run together with whether its file could be read.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactLink {
+    pub kind: String,
+    pub path: String,
+    pub available: bool,
+}
+
+/// Everything rendered on the detail page of a single submission.
+///
+/// Sections are `None` when the corresponding artefact is missing or cannot be
+/// parsed; assertion rows are sorted by name and artefacts by path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunDetailData {
+    pub submission: SubmissionRecord,
+    pub spectrum: Option<SpectrumHeadline>,
+    pub gauge: Option<GaugeHeadline>,
+    pub interaction: Option<InteractionHeadline>,
+    pub assertions: Option<Vec<AssertionRow>>,
+    pub artifacts: Vec<ArtifactLink>,
+}
+
+const ASSERTION_REPORT: &str = "assertion_report.json";
+
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn record_hash<T: Serialize>(value: &T) -> Result<String, AsmError> {
+    Ok(sha256_hex(&to_canonical_json_bytes(value)?))
+}
+
+/// Prefers the embedded analysis hash, then the bytes on disk, then the sha256
+/// recorded at ingest time when the file is no longer reachable.
+fn artifact_hash(artifact: &ArtifactRecord) -> String {
+    if let Some(hash) = &artifact.analysis_hash {
+        return hash.clone();
+    }
+    match fs::read(Path::new(&artifact.path)) {
+        Ok(bytes) => sha256_hex(&bytes),
+        Err(_) => artifact.sha256.clone(),
+    }
+}
+
+fn source_hashes(
+    submissions: &[SubmissionRecord],
+    artifacts: &[ArtifactRecord],
+    metrics: &[MetricRecord],
+) -> Result<BTreeMap<String, String>, AsmError> {
+    let mut hashes = BTreeMap::new();
+    for submission in submissions {
+        hashes.insert(
+            format!("submission:{}", submission.id),
+            record_hash(submission)?,
+        );
+    }
+    for artifact in artifacts {
+        hashes.insert(format!("artifact:{}", artifact.id), artifact_hash(artifact));
+    }
+    let mut grouped: BTreeMap<i64, Vec<&MetricRecord>> = BTreeMap::new();
+    for metric in metrics {
+        grouped
+            .entry(metric.submission_id)
+            .or_default()
+            .push(metric);
+    }
+    for (submission_id, group) in grouped {
+        hashes.insert(format!("metrics:{submission_id}"), record_hash(&group)?);
+    }
+    Ok(hashes)
+}
+
+fn read_json(path: &str) -> Option<Value> {
+    let bytes = fs::read(Path::new(path)).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+fn spectrum_headline(report: &Value) -> SpectrumHeadline {
+    let dispersion = report.get("dispersion");
+    SpectrumHeadline {
+        c_est: dispersion
+            .and_then(|d| d.get("c_est"))
+            .and_then(Value::as_f64),
+        gap: dispersion
+            .and_then(|d| d.get("gap_proxy"))
+            .and_then(Value::as_f64),
+    }
+}
+
+fn gauge_headline(report: &Value) -> GaugeHeadline {
+    let factors = report
+        .pointer("/decomp/factors")
+        .and_then(Value::as_array)
+        .map(|factors| {
+            factors
+                .iter()
+                .filter_map(|factor| factor.get("type").and_then(Value::as_str))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    GaugeHeadline {
+        factors,
+        ward_residual: report
+            .pointer("/ward/max_comm_norm")
+            .and_then(Value::as_f64),
+        ward_pass: report.pointer("/ward/pass").and_then(Value::as_bool),
+        closure_residual: report.pointer("/closure/max_dev").and_then(Value::as_f64),
+        closure_pass: report.pointer("/closure/closed").and_then(Value::as_bool),
+    }
+}
+
+/// Accepts both the full report, which nests couplings under `fit`, and the
+/// flattened summaries written by the batch tools.
+fn interaction_headline(report: &Value) -> InteractionHeadline {
+    let fit = report.get("fit").unwrap_or(report);
+    let mut couplings = BTreeMap::new();
+    if let Some(g) = fit.get("g").and_then(Value::as_array) {
+        for (index, value) in g.iter().enumerate() {
+            if let Some(value) = value.as_f64() {
+                couplings.insert(format!("g{}", index + 1), value);
+            }
+        }
+    }
+    if let Some(lambda_h) = fit.get("lambda_h").and_then(Value::as_f64) {
+        couplings.insert("lambda_h".into(), lambda_h);
+    }
+    InteractionHeadline { couplings }
+}
+
+fn assertion_rows(report: &Value) -> Option<Vec<AssertionRow>> {
+    let mut rows: Vec<AssertionRow> = report
+        .get("checks")?
+        .as_array()?
+        .iter()
+        .filter_map(|check| {
+            Some(AssertionRow {
+                name: check.get("name")?.as_str()?.to_string(),
+                pass: check.get("pass")?.as_bool()?,
+                metric: check.get("metric").and_then(Value::as_f64),
+                note: check
+                    .get("note")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            })
+        })
+        .collect();
+    rows.sort_by(|a, b| a.name.cmp(&b.name));
+    Some(rows)
+}
+
+fn is_assertion_report(artifact: &ArtifactRecord) -> bool {
+    artifact.kind == "assertion_report"
+        || Path::new(&artifact.path).file_name() == Some(ASSERTION_REPORT.as_ref())
+}
+
+fn run_detail(submission: &SubmissionRecord, artifacts: &[ArtifactRecord]) -> RunDetailData {
+    let mut owned: Vec<&ArtifactRecord> = artifacts
+        .iter()
+        .filter(|artifact| artifact.submission_id == submission.id)
+        .collect();
+    owned.sort_by(|a, b| a.path.cmp(&b.path).then(a.id.cmp(&b.id)));
+
+    let mut detail = RunDetailData {
+        submission: submission.clone(),
+        spectrum: None,
+        gauge: None,
+        interaction: None,
+        assertions: None,
+        artifacts: Vec::with_capacity(owned.len()),
+    };
+    for artifact in owned {
+        let report = read_json(&artifact.path);
+        if let Some(report) = &report {
+            match artifact.kind.as_str() {
+                "spectrum_report" if detail.spectrum.is_none() => {
+                    detail.spectrum = Some(spectrum_headline(report));
+                }
+                "gauge_report" if detail.gauge.is_none() => {
+                    detail.gauge = Some(gauge_headline(report));
+                }
+                "interaction_report" if detail.interaction.is_none() => {
+                    detail.interaction = Some(interaction_headline(report));
+                }
+                _ if is_assertion_report(artifact) && detail.assertions.is_none() => {
+                    detail.assertions = assertion_rows(report);
+                }
+                _ => {}
+            }
+        }
+        detail.artifacts.push(ArtifactLink {
+            kind: artifact.kind.clone(),
+            path: artifact.path.clone(),
+            available: report.is_some(),
+        });
+    }
+    detail
+}
+
+/// Builds one [`RunDetailData`] per submission from the artefacts recorded for it.
+pub fn collect_run_details(
+    submissions: &[SubmissionRecord],
+    artifacts: &[ArtifactRecord],
+) -> Vec<RunDetailData> {
+    let mut runs: Vec<RunDetailData> = submissions
+        .iter()
+        .map(|submission| run_detail(submission, artifacts))
+        .collect();
+    runs.sort_by_key(|run| run.submission.id);
+    runs
 }
 
 pub fn collect_site_data(conn: &Connection, params: &QueryParams) -> Result<SiteData, AsmError> {
     let query = RegistryQuery::execute(conn, params)?;
+    let source_hashes = source_hashes(&query.submissions, &query.artifacts, &query.metrics)?;
+    let runs = collect_run_details(&query.submissions, &query.artifacts);
     Ok(SiteData {
         submissions: query.submissions,
         artifacts: query.artifacts,
         metrics: query.metrics,
+        source_hashes,
+        runs,
     })
 }
 
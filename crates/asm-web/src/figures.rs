@@ -1,10 +1,28 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+use crate::pages::escape_html;
+
+/// Space reserved along an edge for an axis label.
+const LABEL_MARGIN: f64 = 16.0;
+/// Radius of scatter markers; also the inset that keeps them inside the plot.
+const POINT_RADIUS: f64 = 3.0;
+/// Heatmap colour ramp endpoints for the lowest and highest finite values.
+const HEAT_LOW: [f64; 3] = [239.0, 246.0, 255.0];
+const HEAT_HIGH: [f64; 3] = [30.0, 58.0, 138.0];
+/// Fill of heatmap cells without a finite value.
+const HEAT_MISSING: &str = "#e5e7eb";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FigureConfig {
     pub width: u32,
     pub height: u32,
     pub bins: usize,
+    /// Label drawn below the plot; reserves a strip at the bottom.
+    #[serde(default)]
+    pub x_label: Option<String>,
+    /// Label drawn rotated left of the plot; reserves a strip on the left.
+    #[serde(default)]
+    pub y_label: Option<String>,
 }
 
 impl Default for FigureConfig {
@@ -13,18 +31,102 @@ impl Default for FigureConfig {
             width: 320,
             height: 160,
             bins: 16,
+            x_label: None,
+            y_label: None,
+        }
+    }
+}
+
+/// Region of the canvas left for data after the label strips.
+struct PlotArea {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl PlotArea {
+    fn new(config: &FigureConfig) -> Self {
+        let left = if config.y_label.is_some() {
+            LABEL_MARGIN
+        } else {
+            0.0
+        };
+        let bottom = if config.x_label.is_some() {
+            LABEL_MARGIN
+        } else {
+            0.0
+        };
+        Self {
+            x: left,
+            y: 0.0,
+            width: (config.width as f64 - left).max(1.0),
+            height: (config.height as f64 - bottom).max(1.0),
         }
     }
 }
 
+fn svg_open(config: &FigureConfig) -> String {
+    format!(
+        "<svg xmlns='http://www.w3.org/2000/svg' width='{w}' height='{h}'>",
+        w = config.width,
+        h = config.height
+    )
+}
+
+fn axis_labels(config: &FigureConfig, area: &PlotArea) -> String {
+    let mut labels = String::new();
+    if let Some(label) = &config.x_label {
+        labels.push_str(&format!(
+            "<text x='{:.2}' y='{:.2}' font-size='10' text-anchor='middle'>{}</text>",
+            area.x + area.width / 2.0,
+            config.height as f64 - 4.0,
+            escape_html(label)
+        ));
+    }
+    if let Some(label) = &config.y_label {
+        let x = LABEL_MARGIN - 4.0;
+        let y = area.y + area.height / 2.0;
+        labels.push_str(&format!(
+            "<text x='{x:.2}' y='{y:.2}' font-size='10' text-anchor='middle' transform='rotate(-90 {x:.2} {y:.2})'>{}</text>",
+            escape_html(label)
+        ));
+    }
+    labels
+}
+
+fn empty_svg(config: &FigureConfig) -> String {
+    format!(
+        "{}{}</svg>",
+        svg_open(config),
+        axis_labels(config, &PlotArea::new(config))
+    )
+}
+
+/// Finite minimum and maximum of `values`, if any value is finite.
+fn finite_range(values: impl Iterator<Item = f64>) -> Option<(f64, f64)> {
+    values
+        .filter(|value| value.is_finite())
+        .fold(None, |range, value| match range {
+            None => Some((value, value)),
+            Some((min, max)) => Some((min.min(value), max.max(value))),
+        })
+}
+
+/// Position of `value` within `[min, max]`; a degenerate range maps to the middle.
+fn unit(value: f64, (min, max): (f64, f64)) -> f64 {
+    if max - min <= 1e-9 {
+        0.5
+    } else {
+        (value - min) / (max - min)
+    }
+}
+
 pub fn render_histogram_svg(values: &[f64], config: &FigureConfig) -> String {
     if values.is_empty() {
-        return format!(
-            "<svg xmlns='http://www.w3.org/2000/svg' width='{w}' height='{h}'></svg>",
-            w = config.width,
-            h = config.height
-        );
+        return empty_svg(config);
     }
+    let area = PlotArea::new(config);
     let min = values
         .iter()
         .cloned()
@@ -44,20 +146,16 @@ pub fn render_histogram_svg(values: &[f64], config: &FigureConfig) -> String {
         bins[idx] += 1;
     }
     let max_bin = bins.iter().copied().max().unwrap_or(1) as f64;
-    let bar_width = config.width as f64 / bin_count as f64;
-    let mut parts = vec![format!(
-        "<svg xmlns='http://www.w3.org/2000/svg' width='{w}' height='{h}'>",
-        w = config.width,
-        h = config.height
-    )];
+    let bar_width = area.width / bin_count as f64;
+    let mut parts = vec![svg_open(config)];
     for (idx, count) in bins.iter().enumerate() {
         let height = if max_bin == 0.0 {
             0.0
         } else {
-            (*count as f64 / max_bin) * config.height as f64
+            (*count as f64 / max_bin) * area.height
         };
-        let x = bar_width * idx as f64;
-        let y = config.height as f64 - height;
+        let x = area.x + bar_width * idx as f64;
+        let y = area.y + area.height - height;
         parts.push(format!(
             "<rect x='{:.2}' y='{:.2}' width='{:.2}' height='{:.2}' fill='#3b82f6' />",
             x,
@@ -66,6 +164,88 @@ pub fn render_histogram_svg(values: &[f64], config: &FigureConfig) -> String {
             height
         ));
     }
+    parts.push(axis_labels(config, &area));
+    parts.push("</svg>".into());
+    parts.join("")
+}
+
+/// Renders `(x, y)` points, e.g. `c_est` against gap across vacua.
+///
+/// Both axes are scaled to the finite extent of the points, which are drawn
+/// in input order. Points with a non-finite coordinate are skipped.
+pub fn render_scatter_svg(points: &[(f64, f64)], config: &FigureConfig) -> String {
+    let finite: Vec<(f64, f64)> = points
+        .iter()
+        .copied()
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+        .collect();
+    let (Some(x_range), Some(y_range)) = (
+        finite_range(finite.iter().map(|point| point.0)),
+        finite_range(finite.iter().map(|point| point.1)),
+    ) else {
+        return empty_svg(config);
+    };
+    let area = PlotArea::new(config);
+    let inner_width = (area.width - 2.0 * POINT_RADIUS).max(0.0);
+    let inner_height = (area.height - 2.0 * POINT_RADIUS).max(0.0);
+    let mut parts = vec![svg_open(config)];
+    for (x, y) in finite {
+        let cx = area.x + POINT_RADIUS + unit(x, x_range) * inner_width;
+        let cy = area.y + area.height - POINT_RADIUS - unit(y, y_range) * inner_height;
+        parts.push(format!(
+            "<circle cx='{cx:.2}' cy='{cy:.2}' r='{POINT_RADIUS:.2}' fill='#3b82f6' />"
+        ));
+    }
+    parts.push(axis_labels(config, &area));
+    parts.push("</svg>".into());
+    parts.join("")
+}
+
+/// Renders a 2D grid of values, e.g. a KPI over a parameter sweep.
+///
+/// `grid[row][column]` is drawn with row 0 at the bottom, so rows follow the
+/// y axis. Shorter rows leave their trailing cells empty; empty and
+/// non-finite cells are filled grey. Colours run from light to dark blue
+/// over the finite extent of the grid.
+pub fn render_heatmap_svg(grid: &[Vec<f64>], config: &FigureConfig) -> String {
+    let columns = grid.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return empty_svg(config);
+    }
+    let range = finite_range(grid.iter().flatten().copied());
+    let area = PlotArea::new(config);
+    let cell_width = area.width / columns as f64;
+    let cell_height = area.height / grid.len() as f64;
+    let mut parts = vec![svg_open(config)];
+    for (row, values) in grid.iter().enumerate() {
+        let y = area.y + area.height - cell_height * (row + 1) as f64;
+        for column in 0..columns {
+            let fill = match (values.get(column).copied(), range) {
+                (Some(value), Some(range)) if value.is_finite() => {
+                    let t = unit(value, range);
+                    let [r, g, b] = [0, 1, 2].map(|channel| {
+                        HEAT_LOW[channel] + t * (HEAT_HIGH[channel] - HEAT_LOW[channel])
+                    });
+                    format!(
+                        "#{:02x}{:02x}{:02x}",
+                        r.round() as u8,
+                        g.round() as u8,
+                        b.round() as u8
+                    )
+                }
+                _ => HEAT_MISSING.to_string(),
+            };
+            parts.push(format!(
+                "<rect x='{:.2}' y='{:.2}' width='{:.2}' height='{:.2}' fill='{}' />",
+                area.x + cell_width * column as f64,
+                y,
+                cell_width,
+                cell_height,
+                fill
+            ));
+        }
+    }
+    parts.push(axis_labels(config, &area));
     parts.push("</svg>".into());
     parts.join("")
 }
@@ -6,7 +6,7 @@ pub mod figures;
 pub mod pages;
 pub mod serde;
 
-pub use build::build_site;
-pub use collect::{collect_site_data, SiteData};
-pub use figures::{render_histogram_svg, FigureConfig};
-pub use pages::{PageDescriptor, SiteConfig};
+pub use build::{build_site, BuildManifest};
+pub use collect::{collect_site_data, RunDetailData, SiteData};
+pub use figures::{render_heatmap_svg, render_histogram_svg, render_scatter_svg, FigureConfig};
+pub use pages::{PageDescriptor, PagePlan, SiteConfig, TableData};
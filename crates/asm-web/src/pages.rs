@@ -1,10 +1,13 @@
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 use asm_core::errors::{AsmError, ErrorInfo};
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
-use crate::collect::SiteData;
+use crate::collect::{sha256_hex, RunDetailData, SiteData};
 use crate::figures::{render_histogram_svg, FigureConfig};
+use crate::serde::to_canonical_json_bytes;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SiteConfig {
@@ -13,6 +16,13 @@ pub struct SiteConfig {
     pub navbar: Vec<String>,
     #[serde(default)]
     pub featured_runs: Vec<String>,
+    /// Skip outputs whose recorded input hashes are unchanged since the last
+    /// build into the same directory. Builds re-render everything by default.
+    #[serde(default)]
+    pub incremental: bool,
+    /// Regenerate every output even when its recorded input hashes are unchanged.
+    #[serde(default)]
+    pub force: bool,
 }
 
 impl Default for SiteConfig {
@@ -21,6 +31,8 @@ impl Default for SiteConfig {
             title: "ASM Dashboard".into(),
             navbar: vec!["home".into(), "vacua".into()],
             featured_runs: Vec::new(),
+            incremental: false,
+            force: false,
         }
     }
 }
@@ -29,48 +41,424 @@ impl Default for SiteConfig {
 pub struct PageDescriptor {
     pub path: PathBuf,
     pub content: String,
+    /// Rows behind the page's interactive tables, written next to the page by
+    /// `build_site` (see [`table_data_path`]).
+    pub tables: Vec<TableData>,
 }
 
-pub fn render_pages(config: &SiteConfig, data: &SiteData) -> Result<Vec<PageDescriptor>, AsmError> {
-    Ok(vec![
-        PageDescriptor {
-            path: PathBuf::from("index.html"),
-            content: render_home(config, data),
+/// Rows of one interactive table in column order.
+///
+/// Cells are JSON numbers, strings, or `null` for missing values; a `status`
+/// column holds `"pass"`, `"fail"`, or `"n/a"` and drives the status filter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableData {
+    pub id: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+/// Output path together with the hashes of every source it is rendered from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PagePlan {
+    pub path: PathBuf,
+    pub inputs: BTreeMap<String, String>,
+}
+
+const HOME_PAGE: &str = "index.html";
+const VACUA_PAGE: &str = "vacua.html";
+const RUNS_DIR: &str = "runs";
+const NOT_AVAILABLE: &str = "<p class=\"na\">not available</p>";
+/// Client-side sorting and filtering for `table.interactive`; pages stay fully
+/// readable without it.
+const TABLE_SCRIPT: &str = include_str!("../assets/tables.js");
+
+/// Site-relative path of the detail page for `submission_id`.
+pub fn run_detail_path(submission_id: i64) -> PathBuf {
+    PathBuf::from(format!("{RUNS_DIR}/{submission_id}.html"))
+}
+
+fn run_detail_href(submission_id: i64) -> String {
+    format!("{RUNS_DIR}/{submission_id}.html")
+}
+
+/// Site-relative path of the JSON table data emitted next to `page`, for pages
+/// that render interactive tables.
+pub fn table_data_path(page: &Path) -> Option<PathBuf> {
+    let key = page.to_string_lossy().replace('\\', "/");
+    (key == VACUA_PAGE || parse_run_detail_path(&key).is_some())
+        .then(|| page.with_extension("json"))
+}
+
+fn parse_run_detail_path(path: &str) -> Option<i64> {
+    path.strip_prefix(RUNS_DIR)?
+        .strip_prefix('/')?
+        .strip_suffix(".html")?
+        .parse()
+        .ok()
+}
+
+fn run_inputs(data: &SiteData, run: &RunDetailData) -> BTreeMap<String, String> {
+    let id = run.submission.id;
+    let mut keys = vec![format!("submission:{id}")];
+    keys.extend(
+        data.artifacts
+            .iter()
+            .filter(|artifact| artifact.submission_id == id)
+            .map(|artifact| format!("artifact:{}", artifact.id)),
+    );
+    keys.into_iter()
+        .filter_map(|key| {
+            let hash = data.source_hashes.get(&key)?.clone();
+            Some((key, hash))
+        })
+        .collect()
+}
+
+fn config_hash(config: &SiteConfig) -> Result<String, AsmError> {
+    let mut canonical = config.clone();
+    canonical.incremental = false;
+    canonical.force = false;
+    Ok(sha256_hex(&to_canonical_json_bytes(&canonical)?))
+}
+
+pub fn plan_pages(config: &SiteConfig, data: &SiteData) -> Result<Vec<PagePlan>, AsmError> {
+    let mut home_inputs = data.source_hashes.clone();
+    home_inputs.insert("config".into(), config_hash(config)?);
+    let vacua_inputs = data
+        .source_hashes
+        .iter()
+        .filter(|(key, _)| key.starts_with("submission:") || key.starts_with("artifact:"))
+        .map(|(key, hash)| (key.clone(), hash.clone()))
+        .collect();
+    let mut plans = vec![
+        PagePlan {
+            path: PathBuf::from(HOME_PAGE),
+            inputs: home_inputs,
         },
-        PageDescriptor {
-            path: PathBuf::from("vacua.html"),
-            content: render_vacua(data),
+        PagePlan {
+            path: PathBuf::from(VACUA_PAGE),
+            inputs: vacua_inputs,
         },
-    ])
+    ];
+    plans.extend(data.runs.iter().map(|run| PagePlan {
+        path: run_detail_path(run.submission.id),
+        inputs: run_inputs(data, run),
+    }));
+    Ok(plans)
+}
+
+pub fn render_page(
+    config: &SiteConfig,
+    data: &SiteData,
+    path: &Path,
+) -> Result<PageDescriptor, AsmError> {
+    let unknown = || {
+        AsmError::Serde(
+            ErrorInfo::new("asm_web.unknown_page", "no renderer registered for page")
+                .with_context("path", path.display().to_string()),
+        )
+    };
+    let key = path.to_string_lossy().replace('\\', "/");
+    let content = match key.as_str() {
+        HOME_PAGE => render_home(config, data),
+        VACUA_PAGE => return Ok(render_vacua(data)),
+        other => {
+            let id = parse_run_detail_path(other).ok_or_else(unknown)?;
+            let run = data
+                .runs
+                .iter()
+                .find(|run| run.submission.id == id)
+                .ok_or_else(unknown)?;
+            return Ok(render_run_detail(run));
+        }
+    };
+    Ok(PageDescriptor {
+        path: path.to_path_buf(),
+        content,
+        tables: Vec::new(),
+    })
+}
+
+pub fn render_pages(config: &SiteConfig, data: &SiteData) -> Result<Vec<PageDescriptor>, AsmError> {
+    plan_pages(config, data)?
+        .iter()
+        .map(|plan| render_page(config, data, &plan.path))
+        .collect()
 }
 
 fn render_home(config: &SiteConfig, data: &SiteData) -> String {
     let total = data.submissions.len();
     let values: Vec<f64> = data.metrics.iter().map(|m| m.value).collect();
+    let mut runs = String::new();
+    for run in &data.runs {
+        runs.push_str(&format!(
+            "<li><a href=\"{href}\">Run {id}</a></li>",
+            href = run_detail_href(run.submission.id),
+            id = run.submission.id,
+        ));
+    }
     format!(
-        "<html><head><title>{title}</title></head><body><h1>{title}</h1><p>Total submissions: {total}</p>{hist}</body></html>",
+        "<html><head><title>{title}</title></head><body><h1>{title}</h1><p>Total submissions: {total}</p>{hist}<ul class=\"runs\">{runs}</ul></body></html>",
         title = config.title,
         total = total,
-        hist = render_histogram_svg(&values, &FigureConfig::default())
+        hist = render_histogram_svg(&values, &FigureConfig::default()),
+        runs = runs
     )
 }
 
-fn render_vacua(data: &SiteData) -> String {
-    let mut rows = String::new();
-    for submission in &data.submissions {
-        rows.push_str(&format!(
-            "<tr><td>{id}</td><td>{submitter}</td><td>{toolchain}</td></tr>",
-            id = submission.id,
-            submitter = submission.submitter,
-            toolchain = submission.toolchain,
-        ));
+/// Status of a run: `fail` if any assertion failed, `pass` if all passed, and
+/// `n/a` without an assertion report.
+fn run_status(run: Option<&RunDetailData>) -> &'static str {
+    match run.and_then(|run| run.assertions.as_ref()) {
+        Some(checks) if checks.iter().all(|check| check.pass) => "pass",
+        Some(_) => "fail",
+        None => "n/a",
+    }
+}
+
+fn render_vacua(data: &SiteData) -> PageDescriptor {
+    let rows = data
+        .submissions
+        .iter()
+        .map(|submission| {
+            let run = data
+                .runs
+                .iter()
+                .find(|run| run.submission.id == submission.id);
+            let spectrum = run.and_then(|run| run.spectrum.as_ref());
+            vec![
+                json!(submission.id),
+                json!(submission.submitter),
+                json!(submission.toolchain),
+                json!(spectrum.and_then(|spectrum| spectrum.c_est)),
+                json!(spectrum.and_then(|spectrum| spectrum.gap)),
+                json!(run_status(run)),
+            ]
+        })
+        .collect();
+    let table = TableData {
+        id: "vacua".into(),
+        columns: ["id", "submitter", "toolchain", "c_est", "gap", "status"]
+            .map(String::from)
+            .to_vec(),
+        rows,
+    };
+    let html = render_table(&table, |column, value| {
+        (column == 0).then(|| {
+            let id = value.as_i64().unwrap_or_default();
+            format!("<a href=\"{href}\">{id}</a>", href = run_detail_href(id))
+        })
+    });
+    PageDescriptor {
+        path: PathBuf::from(VACUA_PAGE),
+        content: format!(
+            "<html><head><title>Vacua</title></head><body><h1>Vacua</h1>{html}<script>{TABLE_SCRIPT}</script></body></html>"
+        ),
+        tables: vec![table],
+    }
+}
+
+fn sort_key(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
     }
+}
+
+fn display_cell(value: &Value) -> String {
+    match value {
+        Value::Null => "n/a".to_string(),
+        Value::String(text) => escape_html(text),
+        Value::Number(number) => number
+            .as_i64()
+            .map(|int| int.to_string())
+            .or_else(|| number.as_f64().map(|float| float.to_string()))
+            .unwrap_or_else(|| number.to_string()),
+        other => escape_html(&other.to_string()),
+    }
+}
+
+/// Renders `table` as a complete static table marked for the client-side
+/// script. `cell` may override the HTML of individual cells; a `status`
+/// column renders as badges and tags each row for filtering.
+fn render_table(table: &TableData, cell: impl Fn(usize, &Value) -> Option<String>) -> String {
+    let status = table.columns.iter().position(|column| column == "status");
+    let head: String = table
+        .columns
+        .iter()
+        .map(|column| format!("<th>{}</th>", escape_html(column)))
+        .collect();
+    let body: String = table
+        .rows
+        .iter()
+        .map(|row| {
+            let state = status.and_then(|index| row[index].as_str());
+            let cells: String = row
+                .iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    let html = cell(index, value).unwrap_or_else(|| match state {
+                        Some(state) if Some(index) == status => badge(match state {
+                            "pass" => Some(true),
+                            "fail" => Some(false),
+                            _ => None,
+                        })
+                        .to_string(),
+                        _ => display_cell(value),
+                    });
+                    format!(
+                        "<td data-sort=\"{key}\">{html}</td>",
+                        key = escape_html(&sort_key(value))
+                    )
+                })
+                .collect();
+            match state {
+                Some(state) => format!("<tr data-status=\"{}\">{cells}</tr>", escape_html(state)),
+                None => format!("<tr>{cells}</tr>"),
+            }
+        })
+        .collect();
     format!(
-        "<html><head><title>Vacua</title></head><body><h1>Vacua</h1><table>{rows}</table></body></html>",
-        rows = rows
+        "<table class=\"interactive\" id=\"{id}\"><thead><tr>{head}</tr></thead><tbody>{body}</tbody></table>",
+        id = escape_html(&table.id)
     )
 }
 
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn format_number(value: Option<f64>) -> String {
+    value.map_or_else(|| "n/a".to_string(), |value| value.to_string())
+}
+
+fn badge(pass: Option<bool>) -> &'static str {
+    match pass {
+        Some(true) => "<span class=\"badge pass\">pass</span>",
+        Some(false) => "<span class=\"badge fail\">fail</span>",
+        None => "<span class=\"badge na\">n/a</span>",
+    }
+}
+
+fn section(title: &str, body: Option<String>) -> String {
+    format!(
+        "<section><h2>{title}</h2>{body}</section>",
+        body = body.as_deref().unwrap_or(NOT_AVAILABLE)
+    )
+}
+
+/// Renders the detail page for one submission.
+///
+/// Every section is always emitted; sections whose artefact is missing render
+/// an explicit "not available" marker so absent data is visible on the page.
+pub fn render_run_detail(data: &RunDetailData) -> PageDescriptor {
+    let id = data.submission.id;
+    let spectrum = data.spectrum.as_ref().map(|spectrum| {
+        format!(
+            "<table><tr><th>c_est</th><td>{c_est}</td></tr><tr><th>gap</th><td>{gap}</td></tr></table>",
+            c_est = format_number(spectrum.c_est),
+            gap = format_number(spectrum.gap),
+        )
+    });
+    let gauge = data.gauge.as_ref().map(|gauge| {
+        let factors = if gauge.factors.is_empty() {
+            "n/a".to_string()
+        } else {
+            escape_html(&gauge.factors.join(" x "))
+        };
+        format!(
+            "<table><tr><th>factors</th><td>{factors}</td></tr><tr><th>ward residual</th><td>{ward}</td><td>{ward_badge}</td></tr><tr><th>closure residual</th><td>{closure}</td><td>{closure_badge}</td></tr></table>",
+            ward = format_number(gauge.ward_residual),
+            ward_badge = badge(gauge.ward_pass),
+            closure = format_number(gauge.closure_residual),
+            closure_badge = badge(gauge.closure_pass),
+        )
+    });
+    let interaction = data.interaction.as_ref().map(|interaction| {
+        let rows: String = interaction
+            .couplings
+            .iter()
+            .map(|(name, value)| {
+                format!(
+                    "<tr><th>{name}</th><td>{value}</td></tr>",
+                    name = escape_html(name)
+                )
+            })
+            .collect();
+        format!("<table>{rows}</table>")
+    });
+    let assertion_table = data.assertions.as_ref().map(|checks| TableData {
+        id: "assertions".into(),
+        columns: ["check", "status", "metric", "note"]
+            .map(String::from)
+            .to_vec(),
+        rows: checks
+            .iter()
+            .map(|check| {
+                vec![
+                    json!(check.name),
+                    json!(if check.pass { "pass" } else { "fail" }),
+                    json!(check.metric),
+                    json!(check.note.as_deref().unwrap_or("")),
+                ]
+            })
+            .collect(),
+    });
+    let assertions = assertion_table
+        .as_ref()
+        .map(|table| render_table(table, |_, _| None));
+    let artifacts = (!data.artifacts.is_empty()).then(|| {
+        let items: String = data
+            .artifacts
+            .iter()
+            .map(|artifact| {
+                let path = escape_html(&artifact.path);
+                let link = if artifact.available {
+                    format!("<a href=\"{path}\">{path}</a>")
+                } else {
+                    format!("{path} <span class=\"na\">not available</span>")
+                };
+                format!(
+                    "<li>{kind}: {link}</li>",
+                    kind = escape_html(&artifact.kind)
+                )
+            })
+            .collect();
+        format!("<ul>{items}</ul>")
+    });
+    let script = if assertion_table.is_some() {
+        format!("<script>{TABLE_SCRIPT}</script>")
+    } else {
+        String::new()
+    };
+    let content = format!(
+        "<html><head><title>Run {id}</title></head><body><h1>Run {id}</h1><p>Submitter: {submitter}</p><p>Toolchain: {toolchain}</p>{spectrum}{gauge}{interaction}{assertions}{artifacts}{script}</body></html>",
+        submitter = escape_html(&data.submission.submitter),
+        toolchain = escape_html(&data.submission.toolchain),
+        spectrum = section("Spectrum", spectrum),
+        gauge = section("Gauge", gauge),
+        interaction = section("Interaction", interaction),
+        assertions = section("Assertions", assertions),
+        artifacts = section("Artefacts", artifacts),
+    );
+    PageDescriptor {
+        path: run_detail_path(id),
+        content,
+        tables: assertion_table.into_iter().collect(),
+    }
+}
+
 pub fn validate_config(config: &SiteConfig) -> Result<(), AsmError> {
     if config.title.trim().is_empty() {
         return Err(AsmError::Serde(ErrorInfo::new(
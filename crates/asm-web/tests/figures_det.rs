@@ -1,4 +1,6 @@
-use asm_web::figures::{render_histogram_svg, FigureConfig};
+use asm_web::figures::{
+    render_heatmap_svg, render_histogram_svg, render_scatter_svg, FigureConfig,
+};
 
 #[test]
 fn histogram_is_deterministic() {
@@ -6,6 +8,7 @@ fn histogram_is_deterministic() {
         width: 100,
         height: 50,
         bins: 4,
+        ..FigureConfig::default()
     };
     let values = vec![0.1, 0.2, 0.4, 0.5];
     let svg_a = render_histogram_svg(&values, &config);
@@ -13,3 +16,67 @@ fn histogram_is_deterministic() {
     assert_eq!(svg_a, svg_b);
     assert!(svg_a.contains("rect"));
 }
+
+fn labelled() -> FigureConfig {
+    FigureConfig {
+        width: 116,
+        height: 66,
+        x_label: Some("c_est".into()),
+        y_label: Some("gap <eV>".into()),
+        ..FigureConfig::default()
+    }
+}
+
+#[test]
+fn scatter_is_deterministic_and_scaled() {
+    let config = FigureConfig {
+        width: 106,
+        height: 56,
+        ..FigureConfig::default()
+    };
+    let points = vec![(0.0, 0.0), (1.0, 0.5), (2.0, 1.0), (f64::NAN, 3.0)];
+    let svg = render_scatter_svg(&points, &config);
+    assert_eq!(svg, render_scatter_svg(&points, &config));
+    assert_eq!(svg.matches("<circle").count(), 3);
+    assert!(svg.contains("<circle cx='3.00' cy='53.00' r='3.00'"));
+    assert!(svg.contains("<circle cx='53.00' cy='28.00' r='3.00'"));
+    assert!(svg.contains("<circle cx='103.00' cy='3.00' r='3.00'"));
+
+    let labelled = render_scatter_svg(&points, &labelled());
+    assert!(labelled.contains("<circle cx='19.00' cy='47.00'"));
+    assert!(labelled.contains(">c_est</text>"));
+    assert!(labelled.contains(">gap &lt;eV&gt;</text>"));
+    assert!(labelled.contains("rotate(-90"));
+
+    let empty = render_scatter_svg(&[(f64::INFINITY, 1.0)], &config);
+    assert_eq!(
+        empty,
+        "<svg xmlns='http://www.w3.org/2000/svg' width='106' height='56'></svg>"
+    );
+}
+
+#[test]
+fn heatmap_is_deterministic_and_oriented() {
+    let config = FigureConfig {
+        width: 40,
+        height: 20,
+        ..FigureConfig::default()
+    };
+    let grid = vec![vec![0.0, 1.0], vec![2.0, f64::NAN], vec![3.0]];
+    let svg = render_heatmap_svg(&grid, &config);
+    assert_eq!(svg, render_heatmap_svg(&grid, &config));
+    assert_eq!(svg.matches("<rect").count(), 6);
+    // Row 0 sits at the bottom; extremes map to the ramp endpoints.
+    assert!(svg.contains("<rect x='0.00' y='13.33' width='20.00' height='6.67' fill='#eff6ff' />"));
+    assert!(svg.contains("<rect x='0.00' y='0.00' width='20.00' height='6.67' fill='#1e3a8a' />"));
+    assert_eq!(svg.matches("fill='#e5e7eb'").count(), 2);
+
+    let labelled = render_heatmap_svg(&grid, &labelled());
+    assert!(labelled.contains("<rect x='16.00' y='33.33' width='50.00' height='16.67'"));
+    assert!(labelled.contains(">c_est</text>"));
+
+    assert_eq!(
+        render_heatmap_svg(&[], &config),
+        render_heatmap_svg(&[Vec::new()], &config)
+    );
+}
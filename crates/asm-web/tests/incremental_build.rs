@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+use asm_dsr::query::QueryParams;
+use asm_dsr::schema::{init_schema, insert_artifact, insert_metric, insert_submission};
+use asm_web::{build_site, pages::SiteConfig};
+use rusqlite::Connection;
+use tempfile::tempdir;
+use walkdir::WalkDir;
+
+fn seeded_registry() -> Connection {
+    let conn = Connection::open_in_memory().expect("mem db");
+    init_schema(&conn).expect("schema");
+    let submission_id = insert_submission(&conn, "alice", "asm 0.16", None).expect("submission");
+    insert_artifact(
+        &conn,
+        submission_id,
+        "interaction_report",
+        "interaction.json",
+        "abc",
+        Some("analysis-abc"),
+    )
+    .expect("artifact");
+    insert_metric(&conn, submission_id, "energy_final", 1.0, Some("arb")).expect("metric");
+    conn
+}
+
+fn incremental_config() -> SiteConfig {
+    SiteConfig {
+        incremental: true,
+        ..SiteConfig::default()
+    }
+}
+
+fn snapshot(dir: &Path) -> BTreeMap<String, (Vec<u8>, SystemTime)> {
+    WalkDir::new(dir)
+        .into_iter()
+        .map(|entry| entry.expect("entry"))
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| {
+            let name = entry
+                .path()
+                .strip_prefix(dir)
+                .expect("relative")
+                .to_string_lossy()
+                .into_owned();
+            let bytes = fs::read(entry.path()).expect("read output");
+            let mtime = entry
+                .metadata()
+                .expect("metadata")
+                .modified()
+                .expect("mtime");
+            (name, (bytes, mtime))
+        })
+        .collect()
+}
+
+#[test]
+fn unchanged_rebuild_touches_only_manifests() {
+    let conn = seeded_registry();
+    let out = tempdir().expect("out");
+    let config = incremental_config();
+    let first = build_site(&conn, &config, out.path(), &QueryParams::default()).expect("build");
+    assert_eq!(first.regenerated.len(), first.outputs.len());
+    let before = snapshot(out.path());
+
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    let second = build_site(&conn, &config, out.path(), &QueryParams::default()).expect("rebuild");
+    assert!(second.regenerated.is_empty());
+    assert!(second.removed.is_empty());
+    assert_eq!(first.outputs, second.outputs);
+
+    let after = snapshot(out.path());
+    assert_eq!(
+        before.keys().collect::<Vec<_>>(),
+        after.keys().collect::<Vec<_>>()
+    );
+    for (name, entry) in &before {
+        if name != "build_manifest.json" && name != "manifest.json" {
+            assert_eq!(entry, &after[name], "{name} changed on incremental rebuild");
+        }
+    }
+}
+
+#[test]
+fn incremental_output_matches_cold_build() {
+    let conn = seeded_registry();
+    let incremental = tempdir().expect("incremental");
+    let config = incremental_config();
+    build_site(&conn, &config, incremental.path(), &QueryParams::default()).expect("build");
+
+    insert_submission(&conn, "bob", "asm 0.16", None).expect("submission");
+    let manifest = build_site(&conn, &config, incremental.path(), &QueryParams::default())
+        .expect("incremental build");
+    assert!(manifest.regenerated.contains(&"vacua.html".to_string()));
+
+    let cold = tempdir().expect("cold");
+    build_site(
+        &conn,
+        &SiteConfig::default(),
+        cold.path(),
+        &QueryParams::default(),
+    )
+    .expect("cold build");
+    for page in manifest.outputs.keys() {
+        let lhs = fs::read(incremental.path().join(page)).expect("incremental page");
+        let rhs = fs::read(cold.path().join(page)).expect("cold page");
+        assert_eq!(
+            lhs, rhs,
+            "{page} differs between cold and incremental builds"
+        );
+    }
+}
+
+#[test]
+fn full_rebuild_is_the_default() {
+    let conn = seeded_registry();
+    let out = tempdir().expect("out");
+    let config = SiteConfig::default();
+    build_site(&conn, &config, out.path(), &QueryParams::default()).expect("build");
+    let manifest = build_site(&conn, &config, out.path(), &QueryParams::default()).expect("full");
+    assert_eq!(manifest.regenerated.len(), manifest.outputs.len());
+}
+
+#[test]
+fn force_regenerates_every_output() {
+    let conn = seeded_registry();
+    let out = tempdir().expect("out");
+    let mut config = incremental_config();
+    build_site(&conn, &config, out.path(), &QueryParams::default()).expect("build");
+    config.force = true;
+    let manifest = build_site(&conn, &config, out.path(), &QueryParams::default()).expect("force");
+    assert_eq!(manifest.regenerated.len(), manifest.outputs.len());
+}
+
+#[test]
+fn manifest_json_seeds_incremental_rebuild() {
+    let conn = seeded_registry();
+    let out = tempdir().expect("out");
+    let config = incremental_config();
+    build_site(&conn, &config, out.path(), &QueryParams::default()).expect("build");
+    assert_eq!(
+        fs::read(out.path().join("build_manifest.json")).expect("build manifest"),
+        fs::read(out.path().join("manifest.json")).expect("manifest")
+    );
+    fs::remove_file(out.path().join("build_manifest.json")).expect("remove build manifest");
+
+    let manifest =
+        build_site(&conn, &config, out.path(), &QueryParams::default()).expect("rebuild");
+    assert!(manifest.regenerated.is_empty());
+    assert!(out.path().join("build_manifest.json").is_file());
+    assert!(out.path().join("manifest.json").is_file());
+}
@@ -2,7 +2,7 @@ use std::fs;
 
 use asm_dsr::query::QueryParams;
 use asm_dsr::schema::{init_schema, insert_artifact, insert_metric, insert_submission};
-use asm_web::{build_site, pages::SiteConfig};
+use asm_web::{build_site, pages::SiteConfig, TableData};
 use rusqlite::Connection;
 use tempfile::tempdir;
 
@@ -24,7 +24,8 @@ fn build_site_emits_pages() {
     let out = tempdir().expect("out");
     let config = SiteConfig::default();
     let manifest = build_site(&conn, &config, out.path(), &QueryParams::default()).expect("build");
-    assert_eq!(manifest.page_count, 2);
+    assert_eq!(manifest.page_count, 3);
+    assert!(out.path().join("runs/1.html").is_file());
     let index = fs::read(out.path().join("index.html")).expect("index");
     assert!(std::str::from_utf8(&index)
         .unwrap()
@@ -33,3 +34,57 @@ fn build_site_emits_pages() {
     let manifest_again = fs::read(out.path().join("manifest.json")).expect("manifest json");
     assert_eq!(manifest_bytes, manifest_again);
 }
+
+#[test]
+fn vacua_table_is_interactive_with_json_data() {
+    let conn = Connection::open_in_memory().expect("mem db");
+    init_schema(&conn).expect("schema");
+    let alice = insert_submission(&conn, "alice", "asm 0.16", None).expect("submission");
+    for (kind, file) in [
+        ("spectrum_report", "spectrum_report.json"),
+        ("extra", "assertion_report.json"),
+    ] {
+        let path = format!("tests/fixtures/run_detail/{file}");
+        insert_artifact(&conn, alice, kind, &path, "abc", None).expect("artifact");
+    }
+    insert_submission(&conn, "<bob>", "asm 0.17", None).expect("submission");
+    let out = tempdir().expect("out");
+    let manifest = build_site(
+        &conn,
+        &SiteConfig::default(),
+        out.path(),
+        &QueryParams::default(),
+    )
+    .expect("build");
+    assert!(manifest.outputs.contains_key("vacua.json"));
+    assert!(manifest.outputs.contains_key("runs/1.json"));
+    assert!(!manifest.outputs.contains_key("index.json"));
+
+    let html = fs::read_to_string(out.path().join("vacua.html")).expect("vacua");
+    assert!(html.contains("<table class=\"interactive\" id=\"vacua\">"));
+    assert!(html.contains("<tr data-status=\"fail\">"));
+    assert!(html.contains("<tr data-status=\"n/a\">"));
+    assert!(html.contains("&lt;bob&gt;"));
+    assert!(html.contains("<script>"));
+
+    let tables: Vec<TableData> =
+        serde_json::from_slice(&fs::read(out.path().join("vacua.json")).expect("vacua json"))
+            .expect("tables");
+    assert_eq!(tables.len(), 1);
+    assert_eq!(
+        tables[0].columns,
+        ["id", "submitter", "toolchain", "c_est", "gap", "status"]
+    );
+    assert_eq!(tables[0].rows.len(), 2);
+    assert_eq!(tables[0].rows[0][3], serde_json::json!(0.1));
+    assert_eq!(tables[0].rows[0][5], "fail");
+    assert_eq!(tables[0].rows[1][3], serde_json::Value::Null);
+    assert_eq!(tables[0].rows[1][5], "n/a");
+
+    let run: Vec<TableData> =
+        serde_json::from_slice(&fs::read(out.path().join("runs/1.json")).expect("run json"))
+            .expect("run tables");
+    assert_eq!(run[0].id, "assertions");
+    let bare = fs::read(out.path().join("runs/2.json")).expect("bare run json");
+    assert_eq!(bare, b"[]");
+}
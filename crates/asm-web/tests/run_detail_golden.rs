@@ -0,0 +1,52 @@
+use std::fs;
+
+use asm_dsr::query::QueryParams;
+use asm_dsr::schema::{init_schema, insert_artifact, insert_submission};
+use asm_web::collect_site_data;
+use asm_web::pages::render_run_detail;
+use rusqlite::Connection;
+
+const FIXTURE_DIR: &str = "tests/fixtures/run_detail";
+
+fn fixture_registry() -> Connection {
+    let conn = Connection::open_in_memory().expect("mem db");
+    init_schema(&conn).expect("schema");
+    let submission_id = insert_submission(&conn, "alice", "asm 0.16", None).expect("submission");
+    let artifacts = [
+        ("spectrum_report", "spectrum_report.json"),
+        ("gauge_report", "gauge_report.json"),
+        ("extra", "assertion_report.json"),
+        ("interaction_report", "interaction_report.json"),
+    ];
+    for (kind, file) in artifacts {
+        let path = format!("{FIXTURE_DIR}/{file}");
+        insert_artifact(&conn, submission_id, kind, &path, "abc", None).expect("artifact");
+    }
+    conn
+}
+
+#[test]
+fn run_detail_matches_golden() {
+    let conn = fixture_registry();
+    let data = collect_site_data(&conn, &QueryParams::default()).expect("collect");
+    assert_eq!(data.runs.len(), 1);
+    let run = &data.runs[0];
+    assert!(run.interaction.is_none());
+    let names: Vec<_> = run
+        .assertions
+        .as_ref()
+        .expect("assertions")
+        .iter()
+        .map(|check| check.name.as_str())
+        .collect();
+    assert_eq!(names, ["gauge_closure", "ward_identity"]);
+
+    let page = render_run_detail(run);
+    assert_eq!(page.path.to_str(), Some("runs/1.html"));
+    let golden = format!("{FIXTURE_DIR}/run_detail.html");
+    if std::env::var_os("ASM_UPDATE_GOLDEN").is_some() {
+        fs::write(&golden, &page.content).expect("write golden");
+    }
+    let expected = fs::read_to_string(&golden).expect("golden");
+    assert_eq!(page.content, expected.trim_end());
+}
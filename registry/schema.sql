@@ -7,3 +7,7 @@ CREATE TABLE IF NOT EXISTS runs (
     params TEXT NOT NULL,
     metrics TEXT NOT NULL
 );
+
+CREATE TABLE IF NOT EXISTS schema_version(version INTEGER NOT NULL);
+CREATE INDEX IF NOT EXISTS runs_plan ON runs(plan_name, date, job_id);
+INSERT INTO schema_version(version) SELECT 1 WHERE NOT EXISTS (SELECT 1 FROM schema_version);
@@ -31,7 +31,8 @@ def load_csv_rows(path: Path) -> List[Dict[str, Any]]:
         return []
     rows: List[Dict[str, Any]] = []
     with path.open() as fh:
-        reader = csv.DictReader(fh)
+        # Versioned registries start with a `#schema_version=N` comment line.
+        reader = csv.DictReader(line for line in fh if not line.startswith("#"))
         for row in reader:
             rows.append(row)
     return rows
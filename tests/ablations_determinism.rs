@@ -1,6 +1,12 @@
+use std::collections::BTreeMap;
+
+use asm_core::errors::AsmError;
 use asm_exp::{
-    run_ablation, to_canonical_json_bytes, AblationMode, AblationPlan, ToleranceSpec,
+    run_ablation, run_ablation_with, stable_hash_string, to_canonical_json_bytes, AblationJob,
+    AblationMode, AblationPlan, FactorKind, HashExecutor, PipelineExecutor, PipelineParams,
+    ToleranceSpec,
 };
+use asm_land::stages::synthesise_stage_outputs;
 use serde_json::json;
 
 #[test]
@@ -49,6 +55,7 @@ fn ablation_reports_are_deterministic() {
         ]
         .into_iter()
         .collect(),
+        correlation: None,
     };
     let report_a = run_ablation(&plan, 101).expect("run ablation");
     let report_b = run_ablation(&plan, 101).expect("run ablation");
@@ -63,3 +70,438 @@ fn ablation_reports_are_deterministic() {
     let lhs_report = run_ablation(&plan, 303).expect("lhs ablation");
     assert_eq!(lhs_report.jobs.len(), 3);
 }
+
+#[test]
+fn oat_varies_one_factor_at_a_time() {
+    let plan = AblationPlan {
+        name: "oat".to_string(),
+        mode: AblationMode::Oat,
+        samples: None,
+        factors: [
+            (
+                "graph.degree_cap".to_string(),
+                vec![json!(3), json!(4), json!(5)],
+            ),
+            (
+                "moves.worm_weight".to_string(),
+                vec![json!(0.0), json!(0.3)],
+            ),
+        ]
+        .into_iter()
+        .collect(),
+        fixed: [
+            ("moves.worm_weight".to_string(), json!(0.3)),
+            ("sampler.sweeps".to_string(), json!(32)),
+        ]
+        .into_iter()
+        .collect(),
+        tolerances: [(
+            "exchange_acceptance".to_string(),
+            ToleranceSpec {
+                min: Some(0.2),
+                max: Some(0.5),
+                abs: Some(1e-6),
+                rel: None,
+            },
+        )]
+        .into_iter()
+        .collect(),
+        correlation: None,
+    };
+    let report = run_ablation(&plan, 7).expect("oat ablation");
+    assert_eq!(report, run_ablation(&plan, 7).expect("oat ablation"));
+
+    // Baseline plus (3 - 1) + (2 - 1) single-factor variations.
+    assert_eq!(report.jobs.len(), 4);
+    let baseline = &report.jobs[0].params;
+    assert_eq!(baseline["graph.degree_cap"], json!(4));
+    assert_eq!(baseline["moves.worm_weight"], json!(0.3));
+    assert_eq!(baseline["sampler.sweeps"], json!(32));
+    for job in &report.jobs[1..] {
+        let changed = ["graph.degree_cap", "moves.worm_weight"]
+            .iter()
+            .filter(|name| job.params[**name] != baseline[**name])
+            .count();
+        assert_eq!(changed, 1);
+    }
+
+    let baseline_value = report.jobs[0].metrics["kpis"]["exchange_acceptance"]["value"]
+        .as_f64()
+        .unwrap();
+    let sensitivity = &report.summary["sensitivity"];
+    let degree = &sensitivity["graph.degree_cap"];
+    assert_eq!(degree["baseline"], json!(4));
+    let levels = degree["levels"].as_array().unwrap();
+    assert_eq!(levels.len(), 2);
+    assert_eq!(levels[0]["value"], json!(3));
+    let job_value = report.jobs[1].metrics["kpis"]["exchange_acceptance"]["value"]
+        .as_f64()
+        .unwrap();
+    let delta = levels[0]["deltas"]["exchange_acceptance"].as_f64().unwrap();
+    assert!((delta - (job_value - baseline_value)).abs() < 1e-12);
+    assert_eq!(
+        sensitivity["moves.worm_weight"]["levels"][0]["value"],
+        json!(0.0)
+    );
+}
+
+fn degree_plan() -> AblationPlan {
+    AblationPlan {
+        name: "executor".to_string(),
+        mode: AblationMode::Grid,
+        samples: None,
+        factors: [(
+            "graph.degree_cap".to_string(),
+            vec![json!(2), json!(3), json!(4)],
+        )]
+        .into_iter()
+        .collect(),
+        fixed: BTreeMap::new(),
+        tolerances: [(
+            "mean_degree".to_string(),
+            ToleranceSpec {
+                min: None,
+                max: Some(3.5),
+                abs: Some(1e-9),
+                rel: None,
+            },
+        )]
+        .into_iter()
+        .collect(),
+        correlation: None,
+    }
+}
+
+#[test]
+fn executor_supplies_job_metrics() {
+    let plan = degree_plan();
+    let executor = |job: &AblationJob<'_>| -> Result<BTreeMap<String, f64>, AsmError> {
+        let cap = job.params["graph.degree_cap"].as_f64().unwrap_or_default();
+        Ok(BTreeMap::from([
+            ("mean_degree".to_string(), cap),
+            ("job_index".to_string(), job.index as f64),
+        ]))
+    };
+    let report = run_ablation_with(&plan, 5, &executor).expect("custom executor");
+    let values: Vec<_> = report
+        .jobs
+        .iter()
+        .map(|job| job.metrics["kpis"]["mean_degree"]["value"].as_f64().unwrap())
+        .collect();
+    assert_eq!(values, [2.0, 3.0, 4.0]);
+    let passes: Vec<_> = report
+        .jobs
+        .iter()
+        .map(|job| job.metrics["kpis"]["mean_degree"]["pass"].as_bool().unwrap())
+        .collect();
+    assert_eq!(passes, [true, true, false]);
+    assert_eq!(report.jobs[2].metrics["kpis"]["job_index"]["value"], json!(2.0));
+    assert_eq!(report.summary["kpis"]["mean_degree"]["mean"], json!(3.0));
+
+    let default = run_ablation(&plan, 5).expect("default executor");
+    assert_eq!(
+        default,
+        run_ablation_with(&plan, 5, &HashExecutor).expect("hash executor")
+    );
+    assert_eq!(default.plan_hash, report.plan_hash);
+}
+
+#[test]
+fn executor_must_measure_declared_kpis() {
+    let plan = degree_plan();
+    let executor =
+        |_: &AblationJob<'_>| -> Result<BTreeMap<String, f64>, AsmError> { Ok(BTreeMap::new()) };
+    let err = run_ablation_with(&plan, 5, &executor).expect_err("missing KPI");
+    assert_eq!(err.info().code, "ablation-kpi-missing");
+}
+
+fn pipeline_plan() -> AblationPlan {
+    AblationPlan {
+        name: "pipeline".to_string(),
+        mode: AblationMode::Grid,
+        samples: None,
+        factors: [
+            ("rule_id".to_string(), vec![json!(1), json!(2), json!(3)]),
+            ("spectrum.k_points".to_string(), vec![json!(16), json!(32)]),
+        ]
+        .into_iter()
+        .collect(),
+        fixed: [("sampler.sweeps".to_string(), json!(64))]
+            .into_iter()
+            .collect(),
+        tolerances: [
+            (
+                "c_est".to_string(),
+                ToleranceSpec {
+                    min: Some(0.9),
+                    max: Some(1.1),
+                    abs: Some(1e-9),
+                    rel: None,
+                },
+            ),
+            (
+                "gap_proxy".to_string(),
+                ToleranceSpec {
+                    min: Some(0.05),
+                    max: Some(0.25),
+                    abs: Some(1e-9),
+                    rel: None,
+                },
+            ),
+        ]
+        .into_iter()
+        .collect(),
+        correlation: None,
+    }
+}
+
+#[test]
+fn pipeline_and_hash_reports_share_structure() {
+    let plan = pipeline_plan();
+    let synthetic = run_ablation(&plan, 17).expect("hash executor");
+    let pipeline = run_ablation_with(&plan, 17, &PipelineExecutor::new()).expect("pipeline");
+    assert_eq!(synthetic.plan_hash, pipeline.plan_hash);
+    assert_eq!(synthetic.jobs.len(), pipeline.jobs.len());
+    for (hash_job, pipeline_job) in synthetic.jobs.iter().zip(&pipeline.jobs) {
+        assert_eq!(hash_job.params, pipeline_job.params);
+        assert_eq!(hash_job.seed, pipeline_job.seed);
+        assert_eq!(hash_job.executor, "hash");
+        assert_eq!(pipeline_job.executor, "pipeline");
+        let keys = |job: &asm_exp::AblationJobReport| -> Vec<String> {
+            job.metrics["kpis"].as_object().unwrap().keys().cloned().collect()
+        };
+        assert_eq!(keys(hash_job), keys(pipeline_job));
+    }
+    let summary_keys = |report: &asm_exp::AblationReport| -> Vec<String> {
+        report.summary["kpis"].as_object().unwrap().keys().cloned().collect()
+    };
+    assert_eq!(summary_keys(&synthetic), summary_keys(&pipeline));
+    assert_eq!(
+        pipeline,
+        run_ablation_with(&plan, 17, &PipelineExecutor::new()).expect("pipeline")
+    );
+}
+
+#[test]
+fn pipeline_values_match_landscape_stages() {
+    let plan = pipeline_plan();
+    let report = run_ablation_with(&plan, 17, &PipelineExecutor::new()).expect("pipeline");
+    let mut c_passes = 0;
+    for job in &report.jobs {
+        let rule_id = job.params["rule_id"].as_u64().unwrap();
+        let k_points = job.params["spectrum.k_points"].as_u64().unwrap() as u32;
+        let stages = synthesise_stage_outputs(
+            asm_land::job_seed(job.seed, rule_id),
+            rule_id,
+            64,
+            2,
+            k_points,
+        )
+        .unwrap();
+        let c_est = &job.metrics["kpis"]["c_est"];
+        assert_eq!(c_est["value"].as_f64().unwrap(), stages.kpi.c_est);
+        let pass = (0.9..=1.1).contains(&stages.kpi.c_est);
+        assert_eq!(c_est["pass"].as_bool().unwrap(), pass);
+        c_passes += usize::from(pass);
+        let gap = &job.metrics["kpis"]["gap_proxy"];
+        assert_eq!(gap["value"].as_f64().unwrap(), stages.kpi.gap_proxy);
+        assert!(gap["pass"].as_bool().unwrap());
+    }
+    let summary = &report.summary["kpis"]["c_est"];
+    let pass_rate = c_passes as f64 / report.jobs.len() as f64;
+    assert_eq!(summary["pass_rate"].as_f64().unwrap(), pass_rate);
+    assert_eq!(summary["all_pass"], json!(c_passes == report.jobs.len()));
+    assert_eq!(report.summary["kpis"]["gap_proxy"]["all_pass"], json!(true));
+}
+
+#[test]
+fn pipeline_accepts_custom_stage_runner() {
+    let plan = pipeline_plan();
+    let executor = PipelineExecutor::with_stages(|params: &PipelineParams| {
+        let mut outputs = synthesise_stage_outputs(
+            params.seed,
+            params.rule_id,
+            params.sweeps,
+            params.modes,
+            params.k_points,
+        )?;
+        outputs.kpi.c_est = params.k_points as f64 / 32.0 + params.sweeps as f64 / 640.0;
+        Ok(outputs)
+    });
+    let report = run_ablation_with(&plan, 17, &executor).expect("custom stages");
+    let values: Vec<_> = report
+        .jobs
+        .iter()
+        .map(|job| job.metrics["kpis"]["c_est"]["value"].as_f64().unwrap())
+        .collect();
+    assert_eq!(values, [0.6, 1.1, 0.6, 1.1, 0.6, 1.1]);
+    let passes: Vec<_> = report
+        .jobs
+        .iter()
+        .map(|job| job.metrics["kpis"]["c_est"]["pass"].as_bool().unwrap())
+        .collect();
+    assert_eq!(passes, [false, true, false, true, false, true]);
+
+    let mut bad = pipeline_plan();
+    bad.fixed.insert("sampler.sweeps".to_string(), json!("many"));
+    let err = run_ablation_with(&bad, 17, &PipelineExecutor::new()).expect_err("bad sweeps");
+    assert_eq!(err.info().code, "ablation-pipeline-param");
+    assert_eq!(err.context_value("param"), Some("sampler.sweeps"));
+}
+
+fn correlated_plan(correlation: Vec<Vec<f64>>) -> AblationPlan {
+    AblationPlan {
+        name: "lhs-correlated".to_string(),
+        mode: AblationMode::Lhs,
+        samples: Some(64),
+        factors: [
+            ("graph.degree_cap".to_string(), vec![json!(2.0), json!(6.0)]),
+            ("moves.worm_weight".to_string(), vec![json!(0.0), json!(0.4)]),
+            ("sampler.beta".to_string(), vec![json!(0.5), json!(1.5)]),
+        ]
+        .into_iter()
+        .collect(),
+        fixed: BTreeMap::new(),
+        tolerances: BTreeMap::new(),
+        correlation: Some(correlation),
+    }
+}
+
+fn factor_ranks(report: &asm_exp::AblationReport, factor: &str) -> Vec<f64> {
+    let values: Vec<f64> = report
+        .jobs
+        .iter()
+        .map(|job| job.params[factor].as_f64().unwrap())
+        .collect();
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let mut ranks = vec![0.0; values.len()];
+    for (rank, idx) in order.into_iter().enumerate() {
+        ranks[idx] = rank as f64;
+    }
+    ranks
+}
+
+fn rank_correlation(a: &[f64], b: &[f64]) -> f64 {
+    let n = a.len() as f64;
+    let mean = (n - 1.0) / 2.0;
+    let cov: f64 = a.iter().zip(b).map(|(x, y)| (x - mean) * (y - mean)).sum();
+    let var: f64 = a.iter().map(|x| (x - mean).powi(2)).sum();
+    cov / var
+}
+
+#[test]
+fn lhs_correlation_is_induced_deterministically() {
+    let plan = correlated_plan(vec![
+        vec![1.0, 0.8, 0.0],
+        vec![0.8, 1.0, -0.5],
+        vec![0.0, -0.5, 1.0],
+    ]);
+    let report = run_ablation(&plan, 11).expect("correlated lhs");
+    assert_eq!(report, run_ablation(&plan, 11).expect("correlated lhs"));
+    assert_ne!(
+        report.jobs,
+        run_ablation(&plan, 12).expect("correlated lhs").jobs
+    );
+
+    let degree = factor_ranks(&report, "graph.degree_cap");
+    let worm = factor_ranks(&report, "moves.worm_weight");
+    let beta = factor_ranks(&report, "sampler.beta");
+    assert!((rank_correlation(&degree, &worm) - 0.8).abs() < 0.1);
+    assert!((rank_correlation(&worm, &beta) + 0.5).abs() < 0.1);
+    assert!(rank_correlation(&degree, &beta).abs() < 0.15);
+
+    // Induction only reorders slots, so every stratum is still hit once.
+    let mut caps: Vec<f64> = report
+        .jobs
+        .iter()
+        .map(|job| job.params["graph.degree_cap"].as_f64().unwrap())
+        .collect();
+    caps.sort_by(f64::total_cmp);
+    for (idx, cap) in caps.into_iter().enumerate() {
+        let expected = 2.0 + 4.0 * (idx as f64 + 0.5) / 64.0;
+        assert!((cap - expected).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn lhs_correlation_must_be_psd() {
+    let plan = correlated_plan(vec![
+        vec![1.0, 0.9, -0.9],
+        vec![0.9, 1.0, 0.9],
+        vec![-0.9, 0.9, 1.0],
+    ]);
+    let err = run_ablation(&plan, 11).expect_err("indefinite matrix");
+    assert_eq!(err.info().code, "ablation-lhs-correlation-psd");
+
+    let plan = correlated_plan(vec![vec![1.0, 0.2], vec![0.2, 1.0]]);
+    let err = run_ablation(&plan, 11).expect_err("wrong shape");
+    assert_eq!(err.info().code, "ablation-lhs-correlation");
+}
+
+fn mixed_plan() -> AblationPlan {
+    AblationPlan {
+        name: "lhs-mixed".to_string(),
+        mode: AblationMode::Lhs,
+        samples: Some(8),
+        factors: [
+            ("graph.degree_cap".to_string(), vec![json!(2), json!(6)]),
+            (
+                "moves.kernel".to_string(),
+                vec![json!("metropolis"), json!("heatbath")],
+            ),
+            ("sampler.beta".to_string(), vec![json!(0.5), json!(1.5)]),
+        ]
+        .into_iter()
+        .collect(),
+        fixed: BTreeMap::new(),
+        tolerances: BTreeMap::new(),
+        correlation: None,
+    }
+}
+
+#[test]
+fn lhs_infers_factor_kinds() {
+    let plan = mixed_plan();
+    let kinds = plan.factor_kinds();
+    assert_eq!(kinds["graph.degree_cap"], FactorKind::Integer);
+    assert_eq!(kinds["moves.kernel"], FactorKind::Categorical);
+    assert_eq!(kinds["sampler.beta"], FactorKind::Continuous);
+
+    let report = run_ablation(&plan, 5).expect("mixed lhs");
+    assert_eq!(report, run_ablation(&plan, 5).expect("mixed lhs"));
+    let mut caps: Vec<i64> = report
+        .jobs
+        .iter()
+        .map(|job| job.params["graph.degree_cap"].as_i64().expect("integer"))
+        .collect();
+    caps.sort_unstable();
+    assert_eq!(caps, vec![2, 3, 3, 4, 4, 5, 5, 6]);
+    let heatbath = report
+        .jobs
+        .iter()
+        .filter(|job| job.params["moves.kernel"] == json!("heatbath"))
+        .count();
+    assert_eq!(heatbath, 4);
+}
+
+#[test]
+fn lhs_factor_kinds_enter_plan_hash() {
+    let plan = mixed_plan();
+    let mut continuous = plan.clone();
+    continuous
+        .factors
+        .insert("graph.degree_cap".to_string(), vec![json!(2.0), json!(6.0)]);
+    continuous.factors.remove("moves.kernel");
+    let report = run_ablation(&continuous, 5).expect("continuous lhs");
+    assert_eq!(
+        report.plan_hash,
+        stable_hash_string(&(&continuous, 5u64)).expect("hash")
+    );
+
+    let report = run_ablation(&plan, 5).expect("mixed lhs");
+    assert_eq!(
+        report.plan_hash,
+        stable_hash_string(&(&plan, 5u64, plan.factor_kinds())).expect("hash")
+    );
+}
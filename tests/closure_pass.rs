@@ -1,7 +1,14 @@
 use asm_aut::AnalysisReport;
-use asm_gauge::{build_rep, check_closure, ClosureOpts, RepOpts};
+use asm_gauge::{
+    build_rep, check_closure, ClosureOpts, RepGenerator, RepMatrices, RepOpts, SubsetSelection,
+    SubsetSpec,
+};
 use asm_spec::{from_json_slice as spectrum_from_slice, SpectrumReport};
 
+mod common;
+
+use common::su2_adjoint;
+
 fn load_inputs() -> (SpectrumReport, AnalysisReport) {
     let spectrum_bytes = include_bytes!("../fixtures/phase11/t1_seed0/spectrum_report.json");
     let spectrum = spectrum_from_slice(spectrum_bytes).expect("spectrum");
@@ -18,3 +25,142 @@ fn closure_residual_within_tol() {
     assert!(report.closed, "closure report should pass: {:?}", report);
     assert!(report.max_dev <= ClosureOpts::default().tolerance + 1e-9);
 }
+
+/// Rotation generator `scale * (E_ab - E_ba)` on a 4-dimensional space.
+fn rotation(a: usize, b: usize, scale: f64) -> RepGenerator {
+    let mut matrix = vec![0.0; 16];
+    matrix[a * 4 + b] = scale;
+    matrix[b * 4 + a] = -scale;
+    RepGenerator {
+        id: format!("l{a}{b}"),
+        norm: scale * 2f64.sqrt(),
+        matrix,
+    }
+}
+
+#[test]
+fn failing_pairs_are_reported_worst_first() {
+    // so(4) without L12 and L03: [L01, L02] and [L01, L13] leave the span,
+    // while L02 and L13 commute.
+    let rep = RepMatrices {
+        basis: "modes".to_string(),
+        dim: 4,
+        gens: vec![
+            rotation(0, 1, 1.0),
+            rotation(0, 2, 1.0),
+            rotation(1, 3, 2.0),
+        ],
+        diagnostics: None,
+    };
+    let opts = ClosureOpts {
+        report_top: 2,
+        ..ClosureOpts::default()
+    };
+    let report = check_closure(&rep, &opts).expect("closure");
+    assert!(!report.closed);
+    let pairs: Vec<_> = report
+        .offenders
+        .iter()
+        .map(|offender| (offender.entry.i, offender.entry.j))
+        .collect();
+    assert_eq!(pairs, [(0, 2), (0, 1)]);
+    assert!(report.offenders[0].residual > report.offenders[1].residual);
+    assert_eq!(report.max_dev, report.offenders[0].residual);
+
+    let top = check_closure(
+        &rep,
+        &ClosureOpts {
+            report_top: 1,
+            ..opts
+        },
+    )
+    .expect("closure");
+    assert_eq!(top.offenders, report.offenders[..1]);
+}
+
+fn subset(selection: SubsetSelection, seed: u64) -> Option<SubsetSpec> {
+    Some(SubsetSpec { selection, seed })
+}
+
+#[test]
+fn covering_pair_subsets_match_full_closure() {
+    let rep = su2_adjoint();
+    let full = check_closure(&rep, &ClosureOpts::default()).expect("closure");
+    assert!(!full.partial);
+    assert!(full.subset.is_none());
+    for selection in [
+        SubsetSelection::Fraction(1.0),
+        SubsetSelection::Indices(vec![2, 0, 1, 1]),
+    ] {
+        let opts = ClosureOpts {
+            pair_subset: subset(selection, 9),
+            ..ClosureOpts::default()
+        };
+        let covered = check_closure(&rep, &opts).expect("closure");
+        assert!(!covered.partial);
+        assert_eq!(covered.subset.as_ref().unwrap().indices, [0, 1, 2]);
+        assert_eq!(covered.max_dev, full.max_dev);
+        assert_eq!(covered.structure_tensors, full.structure_tensors);
+        assert_eq!(covered.offenders, full.offenders);
+    }
+}
+
+#[test]
+fn partial_subsets_are_seeded_and_marked() {
+    let rep = su2_adjoint();
+    let opts = |seed| ClosureOpts {
+        pair_subset: subset(SubsetSelection::Fraction(0.5), seed),
+        ..ClosureOpts::default()
+    };
+    let first = check_closure(&rep, &opts(11)).expect("closure");
+    let again = check_closure(&rep, &opts(11)).expect("closure");
+    assert_eq!(first, again);
+    assert!(first.partial);
+    let info = first.subset.as_ref().unwrap();
+    assert_eq!(info.total, 3);
+    assert_eq!(info.indices.len(), 2);
+    assert!(first
+        .structure_tensors
+        .iter()
+        .all(|entry| info.indices.contains(&pair_index(entry.i, entry.j))));
+
+    let single = check_closure(
+        &rep,
+        &ClosureOpts {
+            pair_subset: subset(SubsetSelection::Indices(vec![1]), 0),
+            ..ClosureOpts::default()
+        },
+    )
+    .expect("closure");
+    assert!(single
+        .structure_tensors
+        .iter()
+        .all(|entry| (entry.i, entry.j) == (0, 2)));
+    assert_ne!(single.subset.unwrap().hash, info.hash);
+
+    for selection in [
+        SubsetSelection::Fraction(0.0),
+        SubsetSelection::Indices(vec![3]),
+        SubsetSelection::Indices(Vec::new()),
+    ] {
+        let err = check_closure(
+            &rep,
+            &ClosureOpts {
+                pair_subset: subset(selection, 0),
+                ..ClosureOpts::default()
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err.info().code, "invalid-subset");
+    }
+}
+
+/// Row-major index of the pair `i < j` among three generators.
+fn pair_index(i: usize, j: usize) -> usize {
+    match (i, j) {
+        (0, 1) => 0,
+        (0, 2) => 1,
+        (1, 2) => 2,
+        _ => unreachable!("pair ({i}, {j}) is not ordered"),
+    }
+}
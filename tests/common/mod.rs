@@ -0,0 +1,37 @@
+//! Helpers shared by the gauge integration tests.
+
+use asm_gauge::{RepGenerator, RepMatrices};
+
+/// Totally antisymmetric symbol with `epsilon_{012} = 1`.
+pub fn levi_civita(i: usize, j: usize, k: usize) -> f64 {
+    match (i, j, k) {
+        (0, 1, 2) | (1, 2, 0) | (2, 0, 1) => 1.0,
+        (0, 2, 1) | (2, 1, 0) | (1, 0, 2) => -1.0,
+        _ => 0.0,
+    }
+}
+
+/// Adjoint representation of su(2), `(T_a)_{bc} = -epsilon_{abc}`.
+pub fn su2_adjoint() -> RepMatrices {
+    let gens = (0..3)
+        .map(|a| {
+            let mut matrix = vec![0.0; 9];
+            for b in 0..3 {
+                for c in 0..3 {
+                    matrix[b * 3 + c] = -levi_civita(a, b, c);
+                }
+            }
+            RepGenerator {
+                id: format!("T{a}"),
+                matrix,
+                norm: 2f64.sqrt(),
+            }
+        })
+        .collect();
+    RepMatrices {
+        basis: "adjoint".to_string(),
+        dim: 3,
+        gens,
+        diagnostics: None,
+    }
+}
@@ -2,11 +2,13 @@ use std::fs;
 use std::path::PathBuf;
 
 use asm_code::{serde as code_serde, CSSCode};
-use asm_graph::{graph_from_json, HypergraphImpl};
-use asm_spec::{build_operators, correlation_scan, CorrelSpec, OpOpts};
+use asm_core::Hypergraph;
+use asm_graph::{graph_from_json, HypergraphConfig, HypergraphImpl};
+use asm_spec::correl::MIN_DECAY_DISTANCES;
+use asm_spec::{build_operators, correlation_scan, CorrelSpec, DecayClass, OpOpts};
 
 fn load_fixture() -> (CSSCode, HypergraphImpl) {
-    let base = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let base = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..");
     let code_path = base.join("fixtures/validation_vacua/t1_seed0/end_state/code.json");
     let graph_path = base.join("fixtures/validation_vacua/t1_seed0/end_state/graph.json");
     let code_json = fs::read_to_string(code_path).expect("code fixture");
@@ -33,3 +35,72 @@ fn correlation_estimates_stable() {
     };
     assert!(rel <= 0.05, "correlation length drifted: {rel}");
 }
+
+fn chain(len: usize) -> HypergraphImpl {
+    let mut graph = HypergraphImpl::new(HypergraphConfig {
+        causal_mode: false,
+        k_uniform: None,
+        ..HypergraphConfig::default()
+    });
+    let nodes: Vec<_> = (0..len).map(|_| graph.add_node().unwrap()).collect();
+    for pair in nodes.windows(2) {
+        graph.add_hyperedge(&[pair[0]], &[pair[1]]).unwrap();
+    }
+    graph
+}
+
+#[test]
+fn chain_correlator_decays_exponentially() {
+    let (code, _) = load_fixture();
+    let operators = build_operators(&chain(40), &code, &OpOpts::default()).expect("operators");
+    let spec = CorrelSpec {
+        max_radius: 8,
+        ..CorrelSpec::default()
+    };
+    let report = correlation_scan(&operators, &spec, 9001).expect("correlation");
+    assert_eq!(report.distances, (1..=8).collect::<Vec<_>>());
+    assert_eq!(report.decay_class, DecayClass::Exponential);
+    assert!(report.model_score > spec.decay_margin);
+    let exp = report.exponential.expect("exponential fit");
+    let pow = report.power_law.expect("power-law fit");
+    assert!(exp.aic < pow.aic);
+    assert!(exp.ci[0] <= exp.parameter && exp.parameter <= exp.ci[1]);
+    assert!(pow.ci[0] <= pow.parameter && pow.parameter <= pow.ci[1]);
+    assert_eq!(pow.residuals.len(), 8);
+}
+
+#[test]
+fn decay_models_are_compared_and_classified() {
+    let (code, graph) = load_fixture();
+    let operators = build_operators(&graph, &code, &OpOpts::default()).expect("operators");
+    let spec = CorrelSpec::default();
+    let report = correlation_scan(&operators, &spec, 9001).expect("correlation");
+    assert_eq!(report.distances.len(), report.correlator.len());
+    assert!(report.distances.windows(2).all(|pair| pair[0] < pair[1]));
+
+    let expected = if report.distances.len() < MIN_DECAY_DISTANCES {
+        DecayClass::Undetermined
+    } else {
+        let exp = report.exponential.as_ref().expect("exponential fit");
+        let pow = report.power_law.as_ref().expect("power-law fit");
+        assert_eq!(exp.residuals.len(), report.distances.len());
+        assert_eq!(pow.residuals.len(), report.distances.len());
+        assert!(pow.ci[0] <= pow.parameter && pow.parameter <= pow.ci[1]);
+        if report.model_score > spec.decay_margin && exp.parameter > 0.0 {
+            DecayClass::Exponential
+        } else if report.model_score < -spec.decay_margin && pow.parameter > 0.0 {
+            DecayClass::PowerLaw
+        } else {
+            DecayClass::Undetermined
+        }
+    };
+    assert_eq!(report.decay_class, expected);
+
+    let short = CorrelSpec {
+        max_radius: MIN_DECAY_DISTANCES - 1,
+        ..CorrelSpec::default()
+    };
+    let report = correlation_scan(&operators, &short, 9001).expect("correlation");
+    assert_eq!(report.decay_class, DecayClass::Undetermined);
+    assert!(report.exponential.is_none() && report.power_law.is_none());
+}
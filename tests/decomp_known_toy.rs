@@ -1,5 +1,7 @@
 use asm_aut::AnalysisReport;
-use asm_gauge::{build_rep, decompose, DecompOpts, RepOpts};
+use asm_gauge::{
+    build_rep, decompose, DecompOpts, RepGenerator, RepMatrices, RepOpts, UNIDENTIFIED,
+};
 use asm_spec::{from_json_slice as spectrum_from_slice, SpectrumReport};
 
 fn load_inputs() -> (SpectrumReport, AnalysisReport) {
@@ -16,7 +18,145 @@ fn decomposition_labels_expected_factors() {
     let rep = build_rep(&spectrum, &analysis, &RepOpts::default()).expect("rep");
     let report = decompose(&rep, &DecompOpts::default()).expect("decomp");
     assert!(!report.factors.is_empty());
-    let types: Vec<_> = report.factors.iter().map(|factor| factor.r#type.as_str()).collect();
+    let types: Vec<_> = report
+        .factors
+        .iter()
+        .map(|factor| factor.r#type.as_str())
+        .collect();
     assert!(types.contains(&"su2"));
     assert!(types.contains(&"u1"));
 }
+
+fn rep_from(dim: usize, matrices: Vec<Vec<f64>>) -> RepMatrices {
+    let gens = matrices
+        .into_iter()
+        .enumerate()
+        .map(|(idx, matrix)| RepGenerator {
+            id: format!("g{idx}"),
+            norm: matrix.iter().map(|v| v * v).sum::<f64>().sqrt(),
+            matrix,
+        })
+        .collect();
+    RepMatrices {
+        basis: "modes".to_string(),
+        dim,
+        gens,
+        diagnostics: None,
+    }
+}
+
+/// Generators `E_ab - E_ba` of the defining representation of so(n).
+fn so(n: usize) -> Vec<Vec<f64>> {
+    let mut gens = Vec::new();
+    for a in 0..n {
+        for b in a + 1..n {
+            let mut m = vec![0.0; n * n];
+            m[a * n + b] = 1.0;
+            m[b * n + a] = -1.0;
+            gens.push(m);
+        }
+    }
+    gens
+}
+
+/// Realification of `i * lambda_a / 2` for the Gell-Mann matrices, as 6x6 real matrices.
+fn su3() -> Vec<Vec<f64>> {
+    let s3 = 1.0 / 3f64.sqrt();
+    // (row, col, re, im) entries of each Gell-Mann matrix.
+    let gell_mann: [&[(usize, usize, f64, f64)]; 8] = [
+        &[(0, 1, 1.0, 0.0), (1, 0, 1.0, 0.0)],
+        &[(0, 1, 0.0, -1.0), (1, 0, 0.0, 1.0)],
+        &[(0, 0, 1.0, 0.0), (1, 1, -1.0, 0.0)],
+        &[(0, 2, 1.0, 0.0), (2, 0, 1.0, 0.0)],
+        &[(0, 2, 0.0, -1.0), (2, 0, 0.0, 1.0)],
+        &[(1, 2, 1.0, 0.0), (2, 1, 1.0, 0.0)],
+        &[(1, 2, 0.0, -1.0), (2, 1, 0.0, 1.0)],
+        &[(0, 0, s3, 0.0), (1, 1, s3, 0.0), (2, 2, -2.0 * s3, 0.0)],
+    ];
+    gell_mann
+        .iter()
+        .map(|entries| {
+            let mut m = vec![0.0; 36];
+            for &(row, col, re, im) in entries.iter() {
+                // i * (re + i im) / 2 = (-im + i re) / 2 embedded as [[A, -B], [B, A]].
+                let (a, b) = (-im / 2.0, re / 2.0);
+                m[row * 6 + col] = a;
+                m[(row + 3) * 6 + col + 3] = a;
+                m[(row + 3) * 6 + col] = b;
+                m[row * 6 + col + 3] = -b;
+            }
+            m
+        })
+        .collect()
+}
+
+/// Places `dim x dim` blocks on the diagonal of a `total x total` matrix at `offset`.
+fn embed(block: &[f64], dim: usize, offset: usize, total: usize) -> Vec<f64> {
+    let mut m = vec![0.0; total * total];
+    for row in 0..dim {
+        for col in 0..dim {
+            m[(row + offset) * total + col + offset] = block[row * dim + col];
+        }
+    }
+    m
+}
+
+fn root_labels(rep: &RepMatrices) -> Vec<(usize, String, usize)> {
+    decompose(rep, &DecompOpts::default())
+        .expect("decomp")
+        .factors
+        .into_iter()
+        .map(|factor| {
+            let system = factor.root_system.expect("root system");
+            (system.component, system.lie_type, system.rank)
+        })
+        .collect()
+}
+
+fn single_type(rep: &RepMatrices) -> (String, usize) {
+    let labels = root_labels(rep);
+    assert!(labels.iter().all(|label| label == &labels[0]));
+    (labels[0].1.clone(), labels[0].2)
+}
+
+#[test]
+fn classical_algebras_are_identified() {
+    assert_eq!(single_type(&rep_from(3, so(3))), ("A1".to_string(), 1));
+    assert_eq!(single_type(&rep_from(6, su3())), ("A2".to_string(), 2));
+    assert_eq!(single_type(&rep_from(5, so(5))), ("B2".to_string(), 2));
+}
+
+#[test]
+fn abelian_generator_is_u1() {
+    let rotation = vec![0.0, -1.0, 1.0, 0.0];
+    assert_eq!(
+        single_type(&rep_from(2, vec![rotation])),
+        ("u1".to_string(), 1)
+    );
+}
+
+#[test]
+fn direct_sum_splits_into_components() {
+    let mut gens: Vec<Vec<f64>> = so(3).iter().map(|m| embed(m, 3, 0, 5)).collect();
+    gens.push(embed(&[0.0, -1.0, 1.0, 0.0], 2, 3, 5));
+    let labels = root_labels(&rep_from(5, gens));
+    let expected = [
+        (0, "A1".to_string(), 1),
+        (0, "A1".to_string(), 1),
+        (0, "A1".to_string(), 1),
+        (1, "u1".to_string(), 1),
+    ];
+    assert_eq!(labels, expected);
+}
+
+#[test]
+fn non_semisimple_component_is_unidentified() {
+    // Heisenberg algebra: [E12, E23] = E13 with E13 central, so the Killing form vanishes.
+    let unit = |row: usize, col: usize| {
+        let mut m = vec![0.0; 9];
+        m[row * 3 + col] = 1.0;
+        m
+    };
+    let rep = rep_from(3, vec![unit(0, 1), unit(1, 2), unit(0, 2)]);
+    assert_eq!(single_type(&rep), (UNIDENTIFIED.to_string(), 0));
+}
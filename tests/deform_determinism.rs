@@ -2,12 +2,15 @@ use std::fs;
 use std::path::PathBuf;
 
 use asm_code::{serde as code_serde, CSSCode};
-use asm_exp::{deform, to_canonical_json_bytes, DeformSpec};
+use asm_exp::{
+    deform, deform_with, estimate_gaps, to_canonical_json_bytes, DeformSpec, GapMethod, GapOpts,
+    TARGET_GAP_MAX_ITERATIONS, TARGET_GAP_TOLERANCE,
+};
 use asm_graph::{graph_from_json, HypergraphImpl};
 use asm_rg::StateRef;
 
 fn load_fixture() -> (CSSCode, HypergraphImpl) {
-    let base = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let base = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..");
     let code_path = base
         .join("fixtures/validation_vacua/t1_seed0/end_state/code.json");
     let graph_path = base
@@ -34,3 +37,109 @@ fn deform_reports_are_deterministic() {
     let bytes_b = to_canonical_json_bytes(&report_b).expect("json");
     assert_eq!(bytes_a, bytes_b);
 }
+
+#[test]
+fn adaptive_refinement_concentrates_on_discontinuity() {
+    let (code, graph) = load_fixture();
+    let state = StateRef {
+        graph: &graph,
+        code: &code,
+    };
+    let edge = 0.37;
+    let step_kpi = |_seed: u64, t: f64| {
+        let mut kpis = std::collections::BTreeMap::new();
+        kpis.insert("order".to_string(), if t < edge { 0.0 } else { 1.0 });
+        kpis
+    };
+    let spec = DeformSpec::degree_tweak(1).adaptive(5, 4, "order", 0.5);
+    let report = deform_with(&state, &spec, 7101, step_kpi).expect("deformation");
+
+    assert_eq!(report.refinements.len(), 4);
+    for interval in &report.refinements {
+        assert!(interval.lo < edge && edge <= interval.hi);
+    }
+    let grid: Vec<f64> = report.steps.iter().map(|step| step.t).collect();
+    assert!(grid.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(grid.len(), 5 + 4);
+    assert_eq!(report.end_state_hashes.len(), grid.len());
+
+    let again = deform_with(&state, &spec, 7101, step_kpi).expect("deformation");
+    assert_eq!(report, again);
+
+    let coarse = DeformSpec::degree_tweak(1).adaptive(5, 0, "order", 0.5);
+    let unrefined = deform_with(&state, &coarse, 7101, step_kpi).expect("deformation");
+    assert!(unrefined.refinements.is_empty());
+    assert_ne!(unrefined.deform_hash, report.deform_hash);
+}
+
+fn cubic_gap(_seed: u64, t: f64) -> std::collections::BTreeMap<String, f64> {
+    let mut kpis = std::collections::BTreeMap::new();
+    kpis.insert("gap".to_string(), 0.2 + 0.8 * t * t * t);
+    kpis
+}
+
+#[test]
+fn target_gap_search_converges_deterministically() {
+    let (code, graph) = load_fixture();
+    let state = StateRef {
+        graph: &graph,
+        code: &code,
+    };
+    let spec = DeformSpec::degree_tweak(1).target_gap(0.55);
+    let report = deform_with(&state, &spec, 7101, cubic_gap).expect("deformation");
+    let outcome = report.target.clone().expect("target outcome");
+    assert!(outcome.converged);
+    assert!((outcome.achieved - 0.55).abs() <= TARGET_GAP_TOLERANCE);
+    assert!((outcome.t - (0.35f64 / 0.8).cbrt()).abs() < 1e-4);
+    assert!(outcome.iterations > 0 && outcome.iterations <= TARGET_GAP_MAX_ITERATIONS);
+    assert_eq!(report.end_state_hashes.len(), 1);
+
+    let again = deform_with(&state, &spec, 7101, cubic_gap).expect("deformation");
+    assert_eq!(report, again);
+}
+
+#[test]
+fn unreachable_target_gap_reports_non_convergence() {
+    let (code, graph) = load_fixture();
+    let state = StateRef {
+        graph: &graph,
+        code: &code,
+    };
+    let spec = DeformSpec::degree_tweak(1).target_gap(2.0);
+    let report = deform_with(&state, &spec, 7101, cubic_gap).expect("deformation");
+    let outcome = report.target.expect("target outcome");
+    assert!(!outcome.converged);
+    assert_eq!(outcome.iterations, 0);
+    assert_eq!(outcome.t, 1.0);
+    assert_eq!(outcome.achieved, 1.0);
+    assert!(report.notes.contains("converged=false"));
+}
+
+#[test]
+fn target_gap_uses_gap_estimates() {
+    let (code, graph) = load_fixture();
+    let state = StateRef {
+        graph: &graph,
+        code: &code,
+    };
+    let opts = GapOpts {
+        method: GapMethod::Spectral,
+        thresholds: serde_json::Value::Null,
+        tolerance: 1e-3,
+        uncertainty: None,
+        size_series: Vec::new(),
+    };
+    let start = estimate_gaps(&state, &opts).expect("gaps").gap_value;
+    let spec = DeformSpec::degree_tweak(1).target_gap(start);
+    let report = deform(&state, &spec, 7101).expect("deformation");
+    let outcome = report.target.clone().expect("target outcome");
+    assert!(outcome.converged);
+    assert_eq!(outcome.t, 0.0);
+    assert_eq!(report, deform(&state, &spec, 7101).expect("deformation"));
+
+    let path = DeformSpec::degree_tweak(1)
+        .adaptive(5, 2, "gap", 0.1)
+        .target_gap(start);
+    let err = deform(&state, &path, 7101).expect_err("path rejected");
+    assert_eq!(err.info().code, "deform-target-path");
+}
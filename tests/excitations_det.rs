@@ -1,14 +1,18 @@
+use std::collections::BTreeMap;
+use std::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_2, PI};
 use std::fs;
 use std::path::PathBuf;
 
 use asm_code::{serde as code_serde, CSSCode};
 use asm_graph::{graph_from_json, HypergraphImpl};
 use asm_spec::{
-    build_operators, excite_and_propagate, ExcitationKind, ExcitationSpec, OpOpts, PropOpts,
+    build_operators, excite_and_propagate, from_json_slice, species_scan, to_canonical_json_bytes,
+    ExcitationKind, ExcitationSpec, OpOpts, Operators, PropOpts, SpeciesScanOpts,
+    SpeciesScanOutcome, SpeciesScanReport, SuperpositionComponent,
 };
 
 fn load_fixture() -> (CSSCode, HypergraphImpl) {
-    let base = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let base = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..");
     let code_path = base.join("fixtures/validation_vacua/t1_seed0/end_state/code.json");
     let graph_path = base.join("fixtures/validation_vacua/t1_seed0/end_state/graph.json");
     let code_json = fs::read_to_string(code_path).expect("code fixture");
@@ -29,8 +33,198 @@ fn excitation_responses_are_deterministic() {
         iterations: 24,
         tolerance: 1e-6,
         seed: 4242,
+        emit_front_fit: false,
+        front_threshold: None,
     };
     let first = excite_and_propagate(&ops, &spec, &popts).expect("response");
     let second = excite_and_propagate(&ops, &spec, &popts).expect("response");
     assert_eq!(first, second);
 }
+
+fn scan_opts() -> SpeciesScanOpts {
+    SpeciesScanOpts {
+        ops: OpOpts::default(),
+        propagation: PropOpts {
+            iterations: 24,
+            tolerance: 1e-6,
+            seed: 4242,
+            emit_front_fit: false,
+            front_threshold: None,
+        },
+        dispersion: Default::default(),
+        master_seed: 17,
+    }
+}
+
+#[test]
+fn species_scan_tabulates_every_species() {
+    let (code, graph) = load_fixture();
+    let report = species_scan(&graph, &code, &scan_opts()).expect("species scan");
+    assert_eq!(
+        report,
+        species_scan(&graph, &code, &scan_opts()).expect("species scan")
+    );
+
+    let table = report.table();
+    assert_eq!(table.len(), code.species_catalog().len());
+    assert!(table
+        .windows(2)
+        .all(|pair| pair[0].gap_proxy <= pair[1].gap_proxy));
+    let lightest = report.lightest().expect("lightest species");
+    assert_eq!(lightest, &table[0]);
+    assert!(lightest.gap_proxy > report.dispersion_gap_proxy);
+    assert!(table.iter().any(|row| row.gap_proxy != lightest.gap_proxy));
+
+    let bytes = to_canonical_json_bytes(&report).expect("json");
+    let decoded: SpeciesScanReport = from_json_slice(&bytes).expect("decode");
+    assert_eq!(decoded, report);
+}
+
+#[test]
+fn species_scan_reports_empty_catalog() {
+    let (_, graph) = load_fixture();
+    let code = CSSCode::new(
+        4,
+        Vec::new(),
+        Vec::new(),
+        asm_core::SchemaVersion::new(1, 0, 0),
+        asm_core::RunProvenance::default(),
+    )
+    .expect("empty code");
+    let report = species_scan(&graph, &code, &scan_opts()).expect("species scan");
+    assert_eq!(report.outcome, SpeciesScanOutcome::EmptyCatalog);
+    assert!(report.lightest().is_none());
+    assert!(report.table().is_empty());
+}
+
+fn prop_opts() -> PropOpts {
+    PropOpts {
+        iterations: 24,
+        tolerance: 1e-6,
+        seed: 4242,
+        emit_front_fit: false,
+        front_threshold: None,
+    }
+}
+
+fn defect(amplitude: f64, phase: f64) -> SuperpositionComponent {
+    SuperpositionComponent {
+        spec: ExcitationSpec::default(),
+        amplitude,
+        phase,
+    }
+}
+
+fn superposition(components: Vec<SuperpositionComponent>) -> ExcitationSpec {
+    ExcitationSpec {
+        kind: ExcitationKind::Superposition(components),
+        ..ExcitationSpec::default()
+    }
+}
+
+/// Response amplitudes per node with the index offset removed, i.e. the
+/// initial weight times a factor shared by every spec on the same operators.
+fn weighted(ops: &Operators, spec: &ExcitationSpec) -> BTreeMap<u64, f64> {
+    let response = excite_and_propagate(ops, spec, &prop_opts()).expect("response");
+    response
+        .support
+        .iter()
+        .zip(&response.amplitudes)
+        .enumerate()
+        .map(|(idx, (node, amplitude))| (*node, amplitude - idx as f64 * 1e-3))
+        .collect()
+}
+
+fn assert_scaled(actual: &BTreeMap<u64, f64>, reference: &BTreeMap<u64, f64>, factor: f64) {
+    assert_eq!(
+        actual.keys().collect::<Vec<_>>(),
+        reference.keys().collect::<Vec<_>>()
+    );
+    for (node, value) in actual {
+        assert!(
+            (value - factor * reference[node]).abs() < 1e-8,
+            "node {node}: {value} vs {factor} * {}",
+            reference[node]
+        );
+    }
+}
+
+#[test]
+fn superposition_weights_follow_phases() {
+    let (code, graph) = load_fixture();
+    let ops = build_operators(&graph, &code, &OpOpts::default()).expect("operators");
+    let plain = weighted(&ops, &ExcitationSpec::default());
+
+    // A single unit component reproduces the plain excitation for any phase.
+    assert_scaled(
+        &weighted(&ops, &superposition(vec![defect(1.0, 1.3)])),
+        &plain,
+        1.0,
+    );
+    // Two identical components add constructively, in quadrature, or cancel.
+    let pair = |phase| {
+        superposition(vec![
+            defect(FRAC_1_SQRT_2, 0.0),
+            defect(FRAC_1_SQRT_2, phase),
+        ])
+    };
+    assert_scaled(&weighted(&ops, &pair(0.0)), &plain, 2f64.sqrt());
+    assert_scaled(&weighted(&ops, &pair(FRAC_PI_2)), &plain, 1.0);
+    assert_scaled(&weighted(&ops, &pair(PI)), &plain, 0.0);
+}
+
+#[test]
+fn superposition_requires_normalised_components() {
+    let (code, graph) = load_fixture();
+    let ops = build_operators(&graph, &code, &OpOpts::default()).expect("operators");
+    let code_of = |spec: &ExcitationSpec| {
+        excite_and_propagate(&ops, spec, &prop_opts())
+            .unwrap_err()
+            .info()
+            .code
+            .clone()
+    };
+    assert_eq!(code_of(&superposition(Vec::new())), "empty-superposition");
+    assert_eq!(
+        code_of(&superposition(vec![defect(0.5, 0.0), defect(0.5, 0.0)])),
+        "unnormalised-superposition"
+    );
+    assert_eq!(
+        code_of(&superposition(vec![defect(f64::NAN, 0.0)])),
+        "invalid-weight"
+    );
+    let normalised = superposition(vec![defect(0.6, 0.0), defect(0.8, 2.0)]);
+    excite_and_propagate(&ops, &normalised, &prop_opts()).expect("normalised superposition");
+}
+
+#[test]
+fn superposition_responses_are_byte_identical() {
+    let (code, graph) = load_fixture();
+    let ops = build_operators(&graph, &code, &OpOpts::default()).expect("operators");
+    let spec = superposition(vec![
+        defect(0.6, 0.25),
+        SuperpositionComponent {
+            spec: ExcitationSpec {
+                kind: ExcitationKind::RandomLowWeight,
+                support: 4,
+                ..ExcitationSpec::default()
+            },
+            amplitude: 0.8,
+            phase: -1.0,
+        },
+    ]);
+    let bytes = |spec: &ExcitationSpec| {
+        let response = excite_and_propagate(&ops, spec, &prop_opts()).expect("response");
+        to_canonical_json_bytes(&response).expect("json")
+    };
+    assert_eq!(bytes(&spec), bytes(&spec));
+
+    let encoded = serde_json::to_string(&spec).expect("encode spec");
+    let decoded: ExcitationSpec = serde_json::from_str(&encoded).expect("decode spec");
+    assert_eq!(decoded, spec);
+    assert_eq!(bytes(&decoded), bytes(&spec));
+    assert!(encoded.contains(r#""kind":{"superposition":["#));
+    assert!(!serde_json::to_string(&ExcitationSpec::default())
+        .expect("encode default")
+        .contains("superposition"));
+}
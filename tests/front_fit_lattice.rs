@@ -0,0 +1,96 @@
+use asm_code::CSSCode;
+use asm_core::{Hypergraph, RunProvenance, SchemaVersion};
+use asm_graph::{HypergraphConfig, HypergraphImpl, KUniformity};
+use asm_spec::{
+    build_operators, dispersion_scan, excite_and_propagate, DispersionSpec, ExcitationSpec,
+    OpOpts, PropOpts,
+};
+
+fn chain(len: usize, isolated: usize) -> HypergraphImpl {
+    let config = HypergraphConfig {
+        causal_mode: false,
+        k_uniform: Some(KUniformity::Total {
+            total: 2,
+            min_sources: 1,
+        }),
+        schema_version: SchemaVersion::new(2, 0, 0),
+        ..HypergraphConfig::default()
+    };
+    let mut graph = HypergraphImpl::new(config);
+    let nodes: Vec<_> = (0..len).map(|_| graph.add_node().unwrap()).collect();
+    for pair in nodes.windows(2) {
+        graph.add_hyperedge(&[pair[0]], &[pair[1]]).unwrap();
+    }
+    for _ in 0..isolated {
+        graph.add_node().unwrap();
+    }
+    graph
+}
+
+fn code() -> CSSCode {
+    CSSCode::new(
+        2,
+        vec![vec![0, 1]],
+        vec![vec![0, 1]],
+        SchemaVersion::new(1, 0, 0),
+        RunProvenance::default(),
+    )
+    .unwrap()
+}
+
+fn prop_opts() -> PropOpts {
+    PropOpts {
+        iterations: 400,
+        tolerance: 1e-6,
+        seed: 31,
+        emit_front_fit: true,
+        front_threshold: None,
+    }
+}
+
+#[test]
+fn lattice_front_velocity_matches_dispersion() {
+    let graph = chain(24, 2);
+    let ops = build_operators(&graph, &code(), &OpOpts::default()).expect("operators");
+    let spec = ExcitationSpec {
+        support: 1,
+        ..ExcitationSpec::default()
+    };
+    let response = excite_and_propagate(&ops, &spec, &prop_opts()).expect("response");
+    let peak = response.amplitudes.iter().copied().fold(0.0_f64, f64::max);
+    let fit = response.front_fit(&graph, 0.5 * peak);
+
+    // Relaxation makes the amplitude at hop d after t steps a binomial tail,
+    // A · P(Bin(t, c) >= d), whose half crossing advances c hops per step.
+    let dispersion = dispersion_scan(&ops, &DispersionSpec::default(), 7).expect("dispersion");
+    assert!(fit.fit_defined);
+    let velocity = fit.velocity.expect("velocity");
+    assert!(
+        (velocity - dispersion.c_est).abs() < 5e-3,
+        "front velocity {velocity} vs c_est {}",
+        dispersion.c_est
+    );
+    assert_eq!(fit.disconnected, 2);
+    let source = fit.source.expect("source");
+    let site = fit
+        .crossings
+        .iter()
+        .find(|crossing| crossing.node == source)
+        .expect("site crossing");
+    assert_eq!((site.distance, site.step), (0, 0));
+    assert_eq!(fit, response.front_fit(&graph, 0.5 * peak));
+}
+
+#[test]
+fn threshold_above_peak_leaves_fit_undefined() {
+    let graph = chain(8, 0);
+    let ops = build_operators(&graph, &code(), &OpOpts::default()).expect("operators");
+    let response =
+        excite_and_propagate(&ops, &ExcitationSpec::default(), &prop_opts()).expect("response");
+    let fit = response.front_fit(&graph, 1e6);
+    assert!(fit.threshold_above_max);
+    assert!(!fit.fit_defined);
+    assert_eq!(fit.velocity, None);
+    assert_eq!(fit.crossings.len(), 1);
+    assert_eq!(fit.never_crossed.len(), 7);
+}
@@ -2,12 +2,15 @@ use std::fs;
 use std::path::PathBuf;
 
 use asm_code::{serde as code_serde, CSSCode};
-use asm_exp::{estimate_gaps, GapMethod, GapOpts};
+use asm_exp::{
+    estimate_gaps, to_canonical_json_bytes, GapMethod, GapOpts, GapUncertainty, ResamplingMethod,
+    ScalingVerdict, SizedGap,
+};
 use asm_graph::{graph_from_json, HypergraphImpl};
 use asm_rg::StateRef;
 
 fn load_fixture() -> (CSSCode, HypergraphImpl) {
-    let base = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let base = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..");
     let code_path = base
         .join("fixtures/validation_vacua/t1_seed0/end_state/code.json");
     let graph_path = base
@@ -30,6 +33,8 @@ fn gap_estimates_repeat_within_tolerance() {
         method: GapMethod::Spectral,
         thresholds: serde_json::json!({"min": 0.05}),
         tolerance: 0.01,
+        uncertainty: None,
+        size_series: Vec::new(),
     };
     let report_a = estimate_gaps(&state, &opts).expect("gaps");
     let report_b = estimate_gaps(&state, &opts).expect("gaps");
@@ -39,3 +44,154 @@ fn gap_estimates_repeat_within_tolerance() {
     assert_eq!(report_a.method, "spectral");
     assert_eq!(report_a, report_b);
 }
+
+fn opts_with(method: GapMethod, uncertainty: Option<GapUncertainty>) -> GapOpts {
+    GapOpts {
+        method,
+        thresholds: serde_json::Value::Null,
+        tolerance: 1e-3,
+        uncertainty,
+        size_series: Vec::new(),
+    }
+}
+
+#[test]
+fn resampled_intervals_bracket_the_estimate() {
+    let (code, graph) = load_fixture();
+    let state = StateRef {
+        graph: &graph,
+        code: &code,
+    };
+    for uncertainty in [GapUncertainty::jackknife(), GapUncertainty::bootstrap(500)] {
+        for method in [GapMethod::Dispersion, GapMethod::Spectral] {
+            let report =
+                estimate_gaps(&state, &opts_with(method, Some(uncertainty.clone()))).expect("gaps");
+            let interval = report.interval.clone().expect("interval");
+            assert_eq!(interval.method, uncertainty.method);
+            let [low, high] = interval.bounds;
+            assert!(low <= high);
+            assert!(interval.std_error > 0.0);
+            assert!(
+                low - 0.01 <= report.gap_value && report.gap_value <= high + 0.01,
+                "{low} {high} {}",
+                report.gap_value
+            );
+            assert_eq!(
+                report,
+                estimate_gaps(&state, &opts_with(method, Some(uncertainty.clone()))).expect("gaps")
+            );
+            match method {
+                GapMethod::Dispersion => assert!(report.fit_residual.expect("fit") < 0.01),
+                GapMethod::Spectral | GapMethod::FiniteSizeScaling => {
+                    assert!(report.fit_residual.is_none())
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn uncertainty_is_opt_in() {
+    let (code, graph) = load_fixture();
+    let state = StateRef {
+        graph: &graph,
+        code: &code,
+    };
+    let plain = estimate_gaps(&state, &opts_with(GapMethod::Dispersion, None)).expect("gaps");
+    assert!(plain.interval.is_none() && plain.fit_residual.is_none());
+    let json = String::from_utf8(to_canonical_json_bytes(&plain).expect("json")).unwrap();
+    assert!(!json.contains("interval") && !json.contains("fit_residual"));
+
+    let jackknife = estimate_gaps(
+        &state,
+        &opts_with(GapMethod::Dispersion, Some(GapUncertainty::jackknife())),
+    )
+    .expect("gaps");
+    assert_eq!(jackknife.gap_value, plain.gap_value);
+    assert_eq!(jackknife.ci, plain.ci);
+    assert_eq!(jackknife.residuals, plain.residuals);
+
+    let invalid = GapUncertainty {
+        method: ResamplingMethod::Jackknife,
+        level: 1.0,
+        resamples: 0,
+    };
+    let err = estimate_gaps(&state, &opts_with(GapMethod::Dispersion, Some(invalid)))
+        .expect_err("level rejected");
+    assert_eq!(err.info().code, "gap-uncertainty");
+}
+
+fn scaling_opts(gap: impl Fn(f64) -> f64) -> GapOpts {
+    GapOpts {
+        size_series: [8.0, 12.0, 16.0, 24.0, 32.0, 48.0, 64.0]
+            .into_iter()
+            .map(|size| SizedGap {
+                size,
+                gap: gap(size),
+            })
+            .collect(),
+        ..opts_with(GapMethod::FiniteSizeScaling, None)
+    }
+}
+
+#[test]
+fn finite_size_scaling_classifies_phases() {
+    let (code, graph) = load_fixture();
+    let state = StateRef {
+        graph: &graph,
+        code: &code,
+    };
+
+    let gapped = estimate_gaps(&state, &scaling_opts(|l| 0.3 + 2.0 * l.powf(-1.5))).expect("fss");
+    let fit = gapped.scaling.as_ref().expect("scaling fit");
+    assert_eq!(gapped.method, "finite_size_scaling");
+    assert_eq!(fit.verdict, ScalingVerdict::Gapped);
+    assert!((fit.extrapolated_gap - 0.3).abs() < 1e-4);
+    assert!((fit.exponent - 1.5).abs() < 1e-3);
+    assert!(gapped.passes);
+    assert!(gapped.ci[0] <= gapped.gap_value && gapped.gap_value <= gapped.ci[1]);
+
+    let gapless = estimate_gaps(&state, &scaling_opts(|l| 3.0 / l)).expect("fss");
+    let fit = gapless.scaling.as_ref().expect("scaling fit");
+    assert_eq!(fit.verdict, ScalingVerdict::Gapless);
+    assert!(fit.extrapolated_gap.abs() < 1e-3);
+    assert!((fit.exponent - 1.0).abs() < 1e-3);
+
+    // Sizes alternate between two gap levels, which no power law follows.
+    let noisy = scaling_opts(|l| {
+        if (2.0 * l.log2()).round() as i64 % 2 == 0 {
+            0.2
+        } else {
+            0.8
+        }
+    });
+    let fit = estimate_gaps(&state, &noisy)
+        .expect("fss")
+        .scaling
+        .expect("scaling fit");
+    assert_eq!(fit.verdict, ScalingVerdict::Inconclusive);
+
+    let repeat = estimate_gaps(&state, &scaling_opts(|l| 0.3 + 2.0 * l.powf(-1.5))).expect("fss");
+    assert_eq!(
+        to_canonical_json_bytes(&gapped).unwrap(),
+        to_canonical_json_bytes(&repeat).unwrap()
+    );
+}
+
+#[test]
+fn finite_size_scaling_rejects_short_series() {
+    let (code, graph) = load_fixture();
+    let state = StateRef {
+        graph: &graph,
+        code: &code,
+    };
+    let mut opts = scaling_opts(|l| 1.0 / l);
+    opts.size_series.truncate(2);
+    let err = estimate_gaps(&state, &opts).expect_err("too few sizes");
+    assert_eq!(err.info().code, "gap-finite-size");
+
+    let mut opts = scaling_opts(|l| 1.0 / l);
+    opts.size_series[1].size = opts.size_series[0].size;
+    let err = estimate_gaps(&state, &opts).expect_err("duplicate sizes");
+    assert_eq!(err.info().code, "gap-finite-size");
+}
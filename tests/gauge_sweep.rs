@@ -0,0 +1,49 @@
+use asm_aut::AnalysisReport;
+use asm_gauge::{analyze_gauge, analyze_gauge_sweep, GaugeOpts};
+use asm_spec::{from_json_slice as spectrum_from_slice, SpectrumReport};
+
+fn load_spectrum() -> SpectrumReport {
+    let bytes = include_bytes!("../fixtures/phase11/t1_seed0/spectrum_report.json");
+    spectrum_from_slice(bytes).expect("decode spectrum")
+}
+
+fn load_analysis() -> AnalysisReport {
+    let json = include_str!("../fixtures/phase12/analysis/t1_seed0/analysis_report.json");
+    serde_json::from_str(json).expect("decode analysis")
+}
+
+#[test]
+fn tolerance_sweep_locates_factor_boundary() {
+    let spectrum = load_spectrum();
+    let aut = load_analysis();
+    let opts = GaugeOpts::default();
+    let grid = [(1.0, 1.0), (1e-10, 1.0), (1e-6, 1.0), (1e-6, 1.0)];
+    let sweep = analyze_gauge_sweep(&spectrum, &aut, &grid, &opts).expect("sweep");
+
+    let baseline = analyze_gauge(&spectrum, &aut, &spectrum.operators.info, &opts).expect("gauge");
+    let reported: Vec<String> = baseline
+        .decomp
+        .factors
+        .iter()
+        .map(|factor| factor.r#type.clone())
+        .collect();
+    assert_eq!(sweep.reported_factors, reported);
+
+    let closure_tols: Vec<f64> = sweep
+        .points
+        .iter()
+        .map(|point| point.tolerances.closure_tol)
+        .collect();
+    assert_eq!(closure_tols, [1e-10, 1e-6, 1.0]);
+    // The first generator carries a 1e-9 trace, so it flips to u1 below that tolerance.
+    assert_eq!(sweep.points[0].factors, ["u1", "u1", "su2"]);
+    assert!(!sweep.points[0].stable);
+    assert!(sweep.points[1].stable);
+    assert_eq!(sweep.points[2].factors, ["su2", "su2", "su2"]);
+    assert!(!sweep.points[2].stable);
+    assert_eq!(sweep.first_stable.map(|tol| tol.closure_tol), Some(1e-6));
+
+    let again = analyze_gauge_sweep(&spectrum, &aut, &grid, &opts).expect("sweep");
+    assert_eq!(again.sweep_hash, sweep.sweep_hash);
+    assert!(analyze_gauge_sweep(&spectrum, &aut, &[], &opts).is_err());
+}
@@ -0,0 +1,102 @@
+use std::fs;
+use std::path::PathBuf;
+
+use asm_code::serde as code_serde;
+use asm_core::{ensure_hash_domain, parse_hash, HashDomain, HASH_FORMAT, LEGACY_HASH_FORMAT};
+use asm_gauge::GaugeReport;
+use asm_graph::graph_from_json;
+use asm_spec::{
+    analyze_spectrum, from_json_slice, to_canonical_json_bytes, CorrelSpec, DispersionSpec,
+    ExcitationSpec, OpOpts, PropOpts, SpecOpts, SpectrumReport,
+};
+
+fn fixture(path: &str) -> Vec<u8> {
+    let base = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..");
+    fs::read(base.join(path)).expect("fixture")
+}
+
+fn spectrum_report() -> SpectrumReport {
+    let code_json = String::from_utf8(fixture(
+        "fixtures/validation_vacua/t1_seed0/end_state/code.json",
+    ))
+    .unwrap();
+    let graph_json = String::from_utf8(fixture(
+        "fixtures/validation_vacua/t1_seed0/end_state/graph.json",
+    ))
+    .unwrap();
+    let code = code_serde::from_json(&code_json).expect("decode code");
+    let graph = graph_from_json(&graph_json).expect("decode graph");
+    let mut dispersion = DispersionSpec::default();
+    dispersion.k_points = 8;
+    dispersion.modes = 2;
+    let opts = SpecOpts {
+        ops: OpOpts::default(),
+        excitation: ExcitationSpec::default(),
+        propagation: PropOpts {
+            iterations: 16,
+            tolerance: 1e-6,
+            seed: 7777,
+            emit_front_fit: false,
+            front_threshold: None,
+        },
+        dispersion,
+        correlation: CorrelSpec::default(),
+        master_seed: 9999,
+        fit_tolerance: 1e-6,
+    };
+    analyze_spectrum(&graph, &code, &opts).expect("spectrum")
+}
+
+#[test]
+fn legacy_fixtures_still_load() {
+    let spectrum: SpectrumReport =
+        from_json_slice(&fixture("fixtures/phase11/t1_seed0/spectrum_report.json"))
+            .expect("spectrum");
+    assert_eq!(spectrum.provenance.hash_format, LEGACY_HASH_FORMAT);
+    assert_eq!(parse_hash(&spectrum.analysis_hash).0, None);
+
+    let gauge: GaugeReport =
+        asm_gauge::from_json_slice(&fixture("fixtures/phase12/t1_seed0/gauge_report.json"))
+            .expect("gauge");
+    assert_eq!(gauge.provenance.hash_format, LEGACY_HASH_FORMAT);
+    assert!(ensure_hash_domain(HashDomain::Spectrum, &gauge.analysis_hash).is_ok());
+}
+
+#[test]
+fn legacy_reports_rehash_to_bare_digests() {
+    let mut report = spectrum_report();
+    report.provenance.hash_format = LEGACY_HASH_FORMAT;
+    report.analysis_hash = asm_spec::recompute_analysis_hash(&report).expect("rehash");
+    assert_eq!(parse_hash(&report.analysis_hash).0, None);
+    assert_eq!(report.analysis_hash.len(), 64);
+
+    let bytes = to_canonical_json_bytes(&report).expect("serialize");
+    assert!(!String::from_utf8(bytes.clone()).unwrap().contains("hash_format"));
+    let restored: SpectrumReport = from_json_slice(&bytes).expect("deserialize");
+    assert_eq!(
+        asm_spec::recompute_analysis_hash(&restored).expect("rehash"),
+        report.analysis_hash
+    );
+}
+
+#[test]
+fn new_reports_use_domain_separated_hashes() {
+    let report = spectrum_report();
+    assert_eq!(report.provenance.hash_format, HASH_FORMAT);
+    assert!(report.analysis_hash.starts_with("spec1-"));
+    assert_eq!(
+        parse_hash(&report.analysis_hash).0,
+        Some(HashDomain::Spectrum)
+    );
+
+    let bytes = to_canonical_json_bytes(&report).expect("serialize");
+    let restored: SpectrumReport = from_json_slice(&bytes).expect("deserialize");
+    assert_eq!(restored.provenance.hash_format, HASH_FORMAT);
+    assert_eq!(
+        asm_spec::recompute_analysis_hash(&restored).expect("rehash"),
+        report.analysis_hash
+    );
+
+    let err = ensure_hash_domain(HashDomain::Gauge, &report.analysis_hash).unwrap_err();
+    assert_eq!(err.info().code, "hash-domain-mismatch");
+}
@@ -0,0 +1,93 @@
+use std::fs;
+use std::path::PathBuf;
+
+use asm_gauge::from_json_slice as gauge_from_slice;
+use asm_int::serde::to_canonical_json_bytes;
+use asm_int::{
+    compare_reports, interact, CompareTolerances, FitOpts, KernelMode, KernelOpts, MeasureOpts,
+    ParticipantSpec, PrepSpec,
+};
+use asm_spec::from_json_slice as spec_from_slice;
+
+fn load_reports() -> (asm_spec::SpectrumReport, asm_gauge::GaugeReport) {
+    let base = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let spectrum_bytes = fs::read(base.join("fixtures/phase11/t1_seed0/spectrum_report.json"))
+        .expect("spectrum fixture");
+    let gauge_bytes = fs::read(base.join("fixtures/phase12/t1_seed0/gauge_report.json"))
+        .expect("gauge fixture");
+    let spectrum = spec_from_slice(&spectrum_bytes).expect("decode spectrum");
+    let gauge = gauge_from_slice(&gauge_bytes).expect("decode gauge");
+    (spectrum, gauge)
+}
+
+fn prep() -> PrepSpec {
+    PrepSpec {
+        participants: vec![
+            ParticipantSpec {
+                mode_id: 0,
+                k: 0.1,
+                charge: 1.0,
+            },
+            ParticipantSpec {
+                mode_id: 1,
+                k: -0.1,
+                charge: -1.0,
+            },
+        ],
+        ..PrepSpec::default()
+    }
+}
+
+fn kernel(dt: f64) -> KernelOpts {
+    KernelOpts {
+        steps: 16,
+        dt,
+        mode: KernelMode::Light,
+        save_trajectory: true,
+        ..KernelOpts::default()
+    }
+}
+
+#[test]
+fn identical_reports_are_equivalent() {
+    let (spectrum, gauge) = load_reports();
+    let measure = MeasureOpts::default();
+    let fit = FitOpts::default();
+    let report = interact(&spectrum, &gauge, &prep(), &kernel(0.01), &measure, &fit, 7)
+        .expect("interact");
+
+    let diff = compare_reports(&report, &report, &CompareTolerances::default());
+    assert!(diff.equivalent);
+    assert!(diff.incomparable.is_none());
+    assert!(diff.provenance_changes.is_empty());
+    assert!(diff.couplings.iter().all(|delta| delta.abs_delta == Some(0.0)));
+    assert_eq!(
+        to_canonical_json_bytes(&diff).expect("json"),
+        to_canonical_json_bytes(&compare_reports(&report, &report, &CompareTolerances::default()))
+            .expect("json")
+    );
+}
+
+#[test]
+fn changing_dt_is_a_provenance_only_diff() {
+    let (spectrum, gauge) = load_reports();
+    let measure = MeasureOpts::default();
+    let fit = FitOpts::default();
+    let a = interact(&spectrum, &gauge, &prep(), &kernel(0.01), &measure, &fit, 7).expect("a");
+    let b = interact(&spectrum, &gauge, &prep(), &kernel(0.05), &measure, &fit, 7).expect("b");
+
+    let diff = compare_reports(&a, &b, &CompareTolerances::default());
+    assert!(diff.incomparable.is_none());
+    assert_eq!(diff.provenance_changes, ["kernel.dt"]);
+    assert!(diff.couplings.iter().any(|delta| !delta.within_tol));
+    assert!(!diff.equivalent);
+
+    // A different preparation seed changes the state identity.
+    let other = interact(&spectrum, &gauge, &prep(), &kernel(0.01), &measure, &fit, 8)
+        .expect("other seed");
+    let diff = compare_reports(&a, &other, &CompareTolerances::default());
+    let mismatch = diff.incomparable.expect("incomparable");
+    assert_eq!(mismatch.field, "prep_hash");
+    assert!(diff.couplings.is_empty());
+    assert!(!diff.equivalent);
+}
@@ -0,0 +1,163 @@
+use std::fs;
+use std::path::PathBuf;
+
+use asm_gauge::from_json_slice as gauge_from_slice;
+use asm_int::{
+    evolve, measure, prepare_state, AdaptiveOpts, KernelMode, KernelOpts, MeasureOpts,
+    ParticipantSpec, PrepSpec, PreparedState,
+};
+use asm_spec::from_json_slice as spec_from_slice;
+
+const STIFFNESS: f64 = 200.0;
+
+fn prepared() -> PreparedState {
+    let base = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let spectrum_bytes = fs::read(base.join("fixtures/phase11/t1_seed0/spectrum_report.json"))
+        .expect("spectrum fixture");
+    let gauge_bytes =
+        fs::read(base.join("fixtures/phase12/t1_seed0/gauge_report.json")).expect("gauge fixture");
+    let spectrum = spec_from_slice(&spectrum_bytes).expect("decode spectrum");
+    let gauge = gauge_from_slice(&gauge_bytes).expect("decode gauge");
+    let prep = PrepSpec {
+        participants: vec![
+            ParticipantSpec {
+                mode_id: 0,
+                k: 0.1,
+                charge: 1.0,
+            },
+            ParticipantSpec {
+                mode_id: 1,
+                k: -0.1,
+                charge: -1.0,
+            },
+        ],
+        ..PrepSpec::default()
+    };
+    prepare_state(&spectrum, &gauge, &prep, 2401).expect("prepared")
+}
+
+/// Stiff kernel over the horizon `T = 1`.
+fn stiff_kernel(steps: usize, adaptive: Option<AdaptiveOpts>) -> KernelOpts {
+    KernelOpts {
+        steps,
+        dt: 1.0 / steps as f64,
+        mode: KernelMode::Full,
+        stiffness: STIFFNESS,
+        adaptive,
+        ..KernelOpts::default()
+    }
+}
+
+fn adaptive_kernel() -> KernelOpts {
+    stiff_kernel(
+        100,
+        Some(AdaptiveOpts {
+            rtol: 1e-7,
+            ..AdaptiveOpts::default()
+        }),
+    )
+}
+
+/// Exact norm of `dn/dt = -(1 + s·exp(-s·t))·n` on the unit horizon.
+fn exact_norm(initial: f64, time: f64) -> f64 {
+    initial * (-time - (1.0 - (-STIFFNESS * time).exp())).exp()
+}
+
+#[test]
+fn fixed_step_trajectories_are_unchanged() {
+    let traj = evolve(&prepared(), &KernelOpts::default()).expect("traj");
+    assert!(traj.meta.adaptive.is_none());
+    assert!(traj.steps.iter().all(|step| step.slope.is_none()));
+    assert_eq!(
+        traj.meta.traj_hash,
+        "1b604f4f071ae66b7619e40eca2ba3cd621c51cebad7780d8ca3c5d62be6ea40"
+    );
+    assert_eq!(traj.meta.final_norm, 0.140629748);
+}
+
+#[test]
+fn adaptive_run_matches_fine_fixed_reference() {
+    let state = prepared();
+    let reference = evolve(&state, &stiff_kernel(200_000, None)).expect("reference");
+    let adaptive = evolve(&state, &adaptive_kernel()).expect("adaptive");
+
+    let exact = exact_norm(state.norm, 1.0);
+    let rel = |value: f64| (value - exact).abs() / exact;
+    assert!(rel(adaptive.meta.final_norm) < 1e-5, "adaptive drifted");
+    assert!(rel(reference.meta.final_norm) < 2e-3, "reference drifted");
+    assert!(
+        (adaptive.meta.final_norm - reference.meta.final_norm).abs() / exact < 2e-3,
+        "adaptive {} vs reference {}",
+        adaptive.meta.final_norm,
+        reference.meta.final_norm
+    );
+
+    let stats = adaptive.meta.adaptive.as_ref().expect("stats");
+    assert_eq!(stats.accepted_steps, adaptive.meta.steps);
+    assert_eq!(adaptive.meta.total_time, 1.0);
+    assert!(adaptive.meta.steps * 100 < reference.meta.steps);
+    // Small steps resolve the transient, large ones cover the slow tail.
+    assert!(stats.max_step > 20.0 * stats.min_step);
+    assert_eq!(
+        adaptive,
+        evolve(&state, &adaptive_kernel()).expect("repeat")
+    );
+}
+
+#[test]
+fn trajectory_hash_covers_step_sizes() {
+    let state = prepared();
+    let tight = evolve(&state, &adaptive_kernel()).expect("tight");
+    let mut loose_opts = adaptive_kernel();
+    loose_opts.adaptive.as_mut().unwrap().rtol = 1e-4;
+    let loose = evolve(&state, &loose_opts).expect("loose");
+    assert_ne!(tight.meta.traj_hash, loose.meta.traj_hash);
+
+    let mut quiet = adaptive_kernel();
+    quiet.save_trajectory = false;
+    let mut capped = quiet.clone();
+    capped.adaptive.as_mut().unwrap().max_dt = 0.01;
+    let quiet = evolve(&state, &quiet).expect("quiet");
+    let capped = evolve(&state, &capped).expect("capped");
+    assert!(quiet.steps.is_empty());
+    assert_ne!(quiet.meta.traj_hash, capped.meta.traj_hash);
+}
+
+#[test]
+fn measure_reads_dense_output_at_sample_times() {
+    let state = prepared();
+    let traj = evolve(&state, &adaptive_kernel()).expect("traj");
+    let times = vec![0.0, 0.001, 0.0123, 0.25, 0.5, 1.0];
+    let opts = MeasureOpts {
+        sample_times: times.clone(),
+        ..MeasureOpts::default()
+    };
+    let report = measure(&traj, &opts).expect("measure");
+    assert_eq!(report.samples.len(), times.len());
+    for sample in &report.samples {
+        let exact = exact_norm(state.norm, sample.time);
+        assert!(
+            (sample.norm - exact).abs() < 1e-4 * state.norm,
+            "t = {}: {} vs {exact}",
+            sample.time,
+            sample.norm
+        );
+        assert_eq!(sample.amplitude, (sample.norm * 0.5 * 1e9).round() / 1e9);
+    }
+    let plain = measure(&traj, &MeasureOpts::default()).expect("plain");
+    assert!(plain.samples.is_empty());
+    assert_ne!(plain.obs_hash, report.obs_hash);
+
+    let unsorted = MeasureOpts {
+        sample_times: vec![0.5, 0.25],
+        ..MeasureOpts::default()
+    };
+    let err = measure(&traj, &unsorted).unwrap_err();
+    assert_eq!(err.info().code, "sample-times-unsorted");
+    let late = MeasureOpts {
+        sample_times: vec![1.5],
+        ..MeasureOpts::default()
+    };
+    let err = measure(&traj, &late).unwrap_err();
+    assert_eq!(err.info().code, "sample-time-range");
+}
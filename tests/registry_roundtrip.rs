@@ -2,9 +2,11 @@ use std::env;
 use std::path::PathBuf;
 
 use asm_exp::{
-    registry_append, registry_query, run_ablation, AblationMode, AblationPlan, Query, Registry,
-    ToleranceSpec,
+    registry_append, registry_migrate, registry_query, run_ablation, AblationMode, AblationPlan,
+    AblationReport, Aggregate, AggregateOp, Query, Registry, ToleranceSpec,
+    REGISTRY_SCHEMA_VERSION,
 };
+use rusqlite::Connection;
 use serde_json::json;
 
 fn temp_path(name: &str) -> PathBuf {
@@ -13,9 +15,8 @@ fn temp_path(name: &str) -> PathBuf {
     base
 }
 
-#[test]
-fn registry_roundtrip_csv_and_sqlite() {
-    let plan = AblationPlan {
+fn registry_plan() -> AblationPlan {
+    AblationPlan {
         name: "registry".to_string(),
         mode: AblationMode::Grid,
         samples: None,
@@ -49,8 +50,17 @@ fn registry_roundtrip_csv_and_sqlite() {
         ]
         .into_iter()
         .collect(),
-    };
-    let report = run_ablation(&plan, 77).expect("ablation");
+        correlation: None,
+    }
+}
+
+fn registry_report() -> AblationReport {
+    run_ablation(&registry_plan(), 77).expect("ablation")
+}
+
+#[test]
+fn registry_roundtrip_csv_and_sqlite() {
+    let report = registry_report();
 
     let csv_path = temp_path("csv");
     let _ = std::fs::remove_file(&csv_path);
@@ -66,3 +76,183 @@ fn registry_roundtrip_csv_and_sqlite() {
     let table_sqlite = registry_query(&registry_sqlite, &Query::default()).expect("query sqlite");
     assert_eq!(table_sqlite.rows.len(), report.jobs.len());
 }
+
+/// Like `temp_path`, but keeps `file_name`'s extension so the backend is
+/// chosen from it.
+fn registry_file(file_name: &str) -> PathBuf {
+    let mut base = env::temp_dir();
+    base.push(format!(
+        "asm_registry_test_{}_{}",
+        std::process::id(),
+        file_name
+    ));
+    base
+}
+
+const LEGACY_HEADER: &str = "date,commit,plan_name,plan_hash,job_id,params,metrics\n";
+
+#[test]
+fn registry_migrates_legacy_csv_in_place() {
+    let report = registry_report();
+    let path = registry_file("legacy.csv");
+    std::fs::write(&path, LEGACY_HEADER).expect("legacy csv");
+    let registry = Registry::from_path(&path);
+
+    registry_append(&registry, &report).expect("append legacy csv");
+    let contents = std::fs::read_to_string(&path).expect("read csv");
+    assert!(contents.starts_with(&format!("#schema_version={REGISTRY_SCHEMA_VERSION}\n")));
+    assert_eq!(
+        registry_migrate(&registry).expect("migrate"),
+        REGISTRY_SCHEMA_VERSION
+    );
+
+    registry_append(&registry, &report).expect("append versioned csv");
+    let table = registry_query(&registry, &Query::default()).expect("query csv");
+    assert_eq!(table.rows.len(), 2 * report.jobs.len());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn registry_migrates_legacy_sqlite_in_place() {
+    let report = registry_report();
+    let path = registry_file("legacy.sqlite");
+    let _ = std::fs::remove_file(&path);
+    let conn = Connection::open(&path).expect("legacy sqlite");
+    conn.execute_batch(
+        r#"CREATE TABLE runs (
+            date TEXT NOT NULL,
+            "commit" TEXT NOT NULL,
+            plan_name TEXT NOT NULL,
+            plan_hash TEXT NOT NULL,
+            job_id INTEGER NOT NULL,
+            params TEXT NOT NULL,
+            metrics TEXT NOT NULL
+        );
+        INSERT INTO runs VALUES ('1970-01-01T00:00:00Z', 'old', 'legacy', 'h', 0, '{}', '{}');"#,
+    )
+    .expect("legacy schema");
+    drop(conn);
+    let registry = Registry::from_path(&path);
+
+    assert_eq!(registry_migrate(&registry).expect("migrate"), 0);
+    assert_eq!(
+        registry_migrate(&registry).expect("migrate"),
+        REGISTRY_SCHEMA_VERSION
+    );
+    registry_append(&registry, &report).expect("append sqlite");
+    let table = registry_query(&registry, &Query::default()).expect("query sqlite");
+    assert_eq!(table.rows.len(), 1 + report.jobs.len());
+    assert_eq!(table.rows[0][2], "legacy");
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn registry_rejects_newer_schema() {
+    let report = registry_report();
+    let csv_path = registry_file("newer.csv");
+    std::fs::write(&csv_path, format!("#schema_version=99\n{LEGACY_HEADER}")).expect("csv");
+    let err = registry_append(&Registry::from_path(&csv_path), &report).expect_err("downgrade");
+    assert_eq!(err.info().code, "registry-schema-downgrade");
+    let _ = std::fs::remove_file(&csv_path);
+
+    let sqlite_path = registry_file("newer.sqlite");
+    let _ = std::fs::remove_file(&sqlite_path);
+    let registry = Registry::from_path(&sqlite_path);
+    registry_append(&registry, &report).expect("append sqlite");
+    Connection::open(&sqlite_path)
+        .and_then(|conn| conn.execute("UPDATE schema_version SET version = 99", []))
+        .expect("bump version");
+    let err = registry_query(&registry, &Query::default()).expect_err("downgrade");
+    assert_eq!(err.info().code, "registry-schema-downgrade");
+    let _ = std::fs::remove_file(&sqlite_path);
+}
+
+#[test]
+fn registry_aggregates_match_across_backends() {
+    let report_a = registry_report();
+    let mut plan_b = registry_plan();
+    plan_b.name = "registry-b".to_string();
+    let report_b = run_ablation(&plan_b, 78).expect("ablation");
+
+    let csv_path = registry_file("aggregate.csv");
+    let sqlite_path = registry_file("aggregate.sqlite");
+    let _ = std::fs::remove_file(&csv_path);
+    let _ = std::fs::remove_file(&sqlite_path);
+    let csv = Registry::from_path(&csv_path);
+    let sqlite = Registry::from_path(&sqlite_path);
+    for registry in [&csv, &sqlite] {
+        registry_append(registry, &report_a).expect("append a");
+        registry_append(registry, &report_b).expect("append b");
+    }
+
+    let aggregate = |op, metric: &str| {
+        Some(Aggregate {
+            op,
+            metric: metric.to_string(),
+        })
+    };
+    let queries = [
+        Query {
+            group_by: Some("plan_name".to_string()),
+            aggregate: aggregate(AggregateOp::Mean, "delta_c"),
+            ..Query::default()
+        },
+        Query {
+            group_by: Some("plan_name".to_string()),
+            aggregate: aggregate(AggregateOp::Min, "delta_c"),
+            ..Query::default()
+        },
+        Query {
+            group_by: Some("job_id".to_string()),
+            aggregate: aggregate(AggregateOp::Max, "delta_c"),
+            limit: Some(1),
+            ..Query::default()
+        },
+        Query {
+            aggregate: aggregate(AggregateOp::Count, "delta_c"),
+            ..Query::default()
+        },
+        Query {
+            plan_name: Some("registry-b".to_string()),
+            aggregate: aggregate(AggregateOp::Mean, "missing"),
+            ..Query::default()
+        },
+        Query {
+            group_by: Some("plan_hash".to_string()),
+            ..Query::default()
+        },
+    ];
+    for query in &queries {
+        let from_csv = registry_query(&csv, query).expect("csv aggregate");
+        let from_sqlite = registry_query(&sqlite, query).expect("sqlite aggregate");
+        assert_eq!(from_csv, from_sqlite, "{query:?}");
+    }
+
+    let means = registry_query(&csv, &queries[0]).expect("mean");
+    assert_eq!(means.columns, vec!["plan_name", "mean_delta_c"]);
+    let values: Vec<f64> = report_a
+        .jobs
+        .iter()
+        .map(|job| job.metrics["kpis"]["delta_c"]["value"].as_f64().unwrap())
+        .collect();
+    let expected = values.iter().sum::<f64>() / values.len() as f64;
+    assert_eq!(means.rows[0][0], "registry");
+    assert!((means.rows[0][1].parse::<f64>().unwrap() - expected).abs() < 1e-9);
+
+    let count = registry_query(&sqlite, &queries[3]).expect("count");
+    assert_eq!(count.rows, vec![vec![(2 * report_a.jobs.len()).to_string()]]);
+    let missing = registry_query(&sqlite, &queries[4]).expect("missing metric");
+    assert_eq!(missing.rows, vec![vec![String::new()]]);
+
+    let err = registry_query(
+        &sqlite,
+        &Query {
+            group_by: Some("params".to_string()),
+            ..Query::default()
+        },
+    )
+    .expect_err("unsupported group");
+    assert_eq!(err.info().code, "registry-query-group");
+    let _ = std::fs::remove_file(&csv_path);
+    let _ = std::fs::remove_file(&sqlite_path);
+}
@@ -1,7 +1,11 @@
 use asm_aut::AnalysisReport;
-use asm_gauge::{build_rep, RepOpts};
+use asm_gauge::{anomaly_coefficients, build_rep, AnomalyOpts, RepGenerator, RepMatrices, RepOpts};
 use asm_spec::{from_json_slice as spectrum_from_slice, SpectrumReport};
 
+mod common;
+
+use common::su2_adjoint;
+
 fn load_spectrum() -> SpectrumReport {
     let bytes = include_bytes!("../fixtures/phase11/t1_seed0/spectrum_report.json");
     spectrum_from_slice(bytes).expect("decode spectrum")
@@ -20,3 +24,38 @@ fn rep_determinism() {
     let rep_b = build_rep(&spectrum, &analysis, &RepOpts::default()).expect("rep");
     assert_eq!(rep_a, rep_b);
 }
+
+#[test]
+fn su2_adjoint_casimir_is_scalar() {
+    let rep = su2_adjoint();
+    assert_eq!(rep.dimension(), 3);
+    assert_eq!(rep.casimir().expect("casimir"), 1.0);
+    let diagnostics = rep.diagnostics(1e-9).expect("diagnostics");
+    assert!(diagnostics.pass);
+    assert_eq!(diagnostics.casimir_scalar_dev, Some(0.0));
+}
+
+#[test]
+fn anomaly_cancels_for_real_adjoint_but_not_chiral_u1() {
+    let report = anomaly_coefficients(&su2_adjoint(), &AnomalyOpts::default());
+    assert!(report.pass);
+    assert_eq!(report.triples_checked, 10);
+    assert_eq!(report.max_abs, 0.0);
+
+    let chiral = |charges: [f64; 2]| RepMatrices {
+        basis: "modes".to_string(),
+        dim: 2,
+        gens: vec![RepGenerator {
+            id: "Q".to_string(),
+            matrix: vec![charges[0], 0.0, 0.0, charges[1]],
+            norm: 1.0,
+        }],
+        diagnostics: None,
+    };
+    let vector_like = anomaly_coefficients(&chiral([1.0, -1.0]), &AnomalyOpts::default());
+    assert!(vector_like.pass);
+    let anomalous = anomaly_coefficients(&chiral([1.0, 2.0]), &AnomalyOpts::default());
+    assert!(!anomalous.pass);
+    assert_eq!(anomalous.max_abs, 9.0);
+    assert_eq!(anomalous.violations.len(), 1);
+}
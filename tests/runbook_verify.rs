@@ -0,0 +1,145 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use asm_exp::{artifact_hash, build_runbook, RunMeta, RunStep, StepStatus};
+use serde_json::Value;
+
+fn temp_path(name: &str) -> PathBuf {
+    let mut base = env::temp_dir();
+    base.push(format!("asm_runbook_test_{}_{}", name, std::process::id()));
+    base
+}
+
+fn step(id: &str, depends_on: &[&str]) -> RunStep {
+    RunStep {
+        id: id.to_string(),
+        depends_on: depends_on.iter().map(|dep| dep.to_string()).collect(),
+        outputs: vec![format!("{id}.json")],
+        hashes: Default::default(),
+        completed: false,
+    }
+}
+
+fn meta(steps: Vec<RunStep>) -> RunMeta {
+    RunMeta {
+        created_at: "1970-01-01T00:00:00Z".to_string(),
+        commit: "test".to_string(),
+        seeds: vec![1],
+        artifacts: Vec::new(),
+        summary: Value::Null,
+        steps,
+    }
+}
+
+#[test]
+fn runbook_orders_steps_by_dependency() {
+    let steps = vec![
+        step("spectrum", &["mcmc"]),
+        step("gauge", &["spectrum"]),
+        step("mcmc", &[]),
+    ];
+    let runbook = build_runbook(&[], &meta(steps)).expect("runbook");
+    let order: Vec<_> = runbook.steps.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(order, ["mcmc", "spectrum", "gauge"]);
+
+    let diamond = vec![
+        step("fit", &["measure", "prepare"]),
+        step("measure", &["prepare"]),
+        step("prepare", &[]),
+    ];
+    let runbook = build_runbook(&[], &meta(diamond)).expect("runbook");
+    let order: Vec<_> = runbook.steps.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(order, ["prepare", "measure", "fit"]);
+
+    let cyclic = vec![step("a", &["b"]), step("b", &["a"])];
+    let err = build_runbook(&[], &meta(cyclic)).unwrap_err();
+    assert_eq!(err.info().code, "runbook-cycle");
+
+    let cyclic = vec![
+        step("report", &["fit"]),
+        step("fit", &["measure"]),
+        step("measure", &["prepare"]),
+        step("prepare", &["fit"]),
+    ];
+    let err = build_runbook(&[], &meta(cyclic)).unwrap_err();
+    assert_eq!(err.info().code, "runbook-cycle");
+    assert_eq!(
+        err.context_value("cycle"),
+        Some("fit -> measure -> prepare -> fit")
+    );
+
+    let err = build_runbook(&[], &meta(vec![step("loop", &["loop"])])).unwrap_err();
+    assert_eq!(err.context_value("cycle"), Some("loop -> loop"));
+
+    let dangling = vec![step("a", &["missing"])];
+    let err = build_runbook(&[], &meta(dangling)).unwrap_err();
+    assert_eq!(err.info().code, "runbook-dangling-dependency");
+}
+
+#[test]
+fn deleted_artifact_marks_step_stale_and_dependents_blocked() {
+    let root = temp_path("chain");
+    fs::create_dir_all(&root).expect("root");
+    let mut steps = vec![
+        step("mcmc", &[]),
+        step("spectrum", &["mcmc"]),
+        step("gauge", &["spectrum"]),
+    ];
+    for step in &mut steps {
+        let output = &step.outputs[0];
+        fs::write(root.join(output), step.id.as_bytes()).expect("write output");
+        let hash = artifact_hash(&root.join(output)).expect("hash");
+        step.hashes.insert(output.clone(), hash);
+        step.completed = true;
+    }
+    let runbook = build_runbook(&[], &meta(steps)).expect("runbook");
+
+    let verification = runbook.verify(&root);
+    assert!(verification
+        .steps
+        .iter()
+        .all(|step| step.status == StepStatus::Done));
+    assert!(verification.next_runnable().is_empty());
+
+    fs::remove_file(root.join("spectrum.json")).expect("delete");
+    let verification = runbook.verify(&root);
+    let statuses: Vec<_> = verification.steps.iter().map(|s| s.status).collect();
+    assert_eq!(
+        statuses,
+        [StepStatus::Done, StepStatus::Stale, StepStatus::Blocked]
+    );
+    assert_eq!(verification.steps[1].missing, ["spectrum.json"]);
+    assert_eq!(verification.next_runnable(), ["spectrum"]);
+
+    fs::write(root.join("spectrum.json"), b"tampered").expect("rewrite");
+    let verification = runbook.verify(&root);
+    assert_eq!(verification.steps[1].mismatched, ["spectrum.json"]);
+    assert_eq!(verification.steps[1].status, StepStatus::Stale);
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn report_hash_recorded_as_output_digest_is_cross_domain() {
+    let root = temp_path("cross_domain");
+    fs::create_dir_all(&root).expect("root");
+    let mut spectrum = step("spectrum", &[]);
+    fs::write(root.join("spectrum.json"), b"{}").expect("write output");
+    let analysis_hash = asm_core::domain_hash(asm_core::HashDomain::Spectrum, b"{}");
+    spectrum
+        .hashes
+        .insert("spectrum.json".to_string(), analysis_hash);
+    spectrum.completed = true;
+    let runbook = build_runbook(&[], &meta(vec![spectrum])).expect("runbook");
+
+    let verification = runbook.verify(&root);
+    assert_eq!(verification.steps[0].status, StepStatus::Stale);
+    assert_eq!(verification.steps[0].cross_domain, ["spectrum.json"]);
+    assert!(verification.steps[0].mismatched.is_empty());
+    let err = verification.ensure_no_cross_domain().unwrap_err();
+    assert_eq!(err.info().code, "hash-domain-mismatch");
+    assert_eq!(err.context_value("output"), Some("spectrum.json"));
+
+    let _ = fs::remove_dir_all(&root);
+}
@@ -12,7 +12,7 @@ use asm_rg::StateRef;
 use serde_json::json;
 
 fn load_fixture() -> (CSSCode, HypergraphImpl) {
-    let base = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let base = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..");
     let code_path = base
         .join("fixtures/validation_vacua/t1_seed0/end_state/code.json");
     let graph_path = base
@@ -46,6 +46,7 @@ fn schema_roundtrips_are_identical() {
             }],
         },
         scheduler: Default::default(),
+        concurrency: 1,
     };
     let sweep_report = sweep(&plan, 7).expect("sweep");
     let sweep_bytes = to_canonical_json_bytes(&sweep_report).expect("json");
@@ -56,6 +57,8 @@ fn schema_roundtrips_are_identical() {
         method: GapMethod::Dispersion,
         thresholds: json!({"max": 0.2}),
         tolerance: 0.05,
+        uncertainty: None,
+        size_series: Vec::new(),
     };
     let gap_report = estimate_gaps(&state, &gap_opts).expect("gaps");
     let gap_bytes = to_canonical_json_bytes(&gap_report).expect("json");
@@ -81,6 +84,7 @@ fn schema_roundtrips_are_identical() {
         )]
         .into_iter()
         .collect(),
+        correlation: None,
     };
     let ablation_report = run_ablation(&ablation_plan, 9001).expect("ablation");
     let ablation_bytes = to_canonical_json_bytes(&ablation_report).expect("json");
@@ -95,6 +99,7 @@ fn schema_roundtrips_are_identical() {
         seeds: vec![1, 2, 3],
         artifacts: vec!["analysis/a.json".into()],
         summary: json!({"jobs": 1}),
+        steps: Vec::new(),
     };
     let runbook = build_runbook(&inputs, &meta).expect("runbook");
     let runbook_bytes = to_canonical_json_bytes(&runbook).expect("json");
@@ -9,7 +9,7 @@ use asm_spec::{
 };
 
 fn load_fixture() -> (CSSCode, HypergraphImpl) {
-    let base = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let base = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..");
     let code_path = base.join("fixtures/validation_vacua/t1_seed0/end_state/code.json");
     let graph_path = base.join("fixtures/validation_vacua/t1_seed0/end_state/graph.json");
     let code_json = fs::read_to_string(code_path).expect("code fixture");
@@ -32,6 +32,8 @@ fn spectrum_report_roundtrips() {
             iterations: 16,
             tolerance: 1e-6,
             seed: 7777,
+            emit_front_fit: false,
+            front_threshold: None,
         },
         dispersion,
         correlation: CorrelSpec::default(),
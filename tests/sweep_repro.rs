@@ -1,5 +1,8 @@
+use asm_core::errors::{AsmError, ErrorInfo};
 use asm_exp::{
-    sweep, to_canonical_json_bytes, GridParameter, SweepPlan, SweepStrategy,
+    stable_hash_string, sweep, sweep_with, sweep_with_predicate, to_canonical_json_bytes,
+    EarlyStop, GridParameter, JobVerdict, LhsParameter, Scheduler, SweepJob, SweepJobReport,
+    SweepPlan, SweepStrategy, SOBOL_MAX_DIMENSIONS,
 };
 use serde_json::json;
 
@@ -19,6 +22,7 @@ fn sweep_reports_repeat() {
             ],
         },
         scheduler: Default::default(),
+        concurrency: 1,
     };
     let report_a = sweep(&plan, 8001).expect("sweep");
     let report_b = sweep(&plan, 8001).expect("sweep");
@@ -28,3 +32,307 @@ fn sweep_reports_repeat() {
     assert_eq!(json_a, json_b);
     assert_eq!(report_a.jobs.len(), 4);
 }
+
+fn lhs_plan(samples: usize, concurrency: usize) -> SweepPlan {
+    SweepPlan {
+        strategy: SweepStrategy::Lhs {
+            parameters: vec![
+                LhsParameter {
+                    name: "beta".to_string(),
+                    min: 0.1,
+                    max: 2.0,
+                },
+                LhsParameter {
+                    name: "worm_weight".to_string(),
+                    min: 0.0,
+                    max: 0.5,
+                },
+            ],
+            samples,
+        },
+        scheduler: Default::default(),
+        concurrency,
+    }
+}
+
+/// Hashes the job seed and parameters, sleeping longer for earlier jobs so
+/// parallel runs complete out of index order.
+fn seeded_executor(job: &SweepJob<'_>) -> Result<Vec<String>, AsmError> {
+    std::thread::sleep(std::time::Duration::from_micros(
+        (500 - job.index.min(500)) as u64 * 4,
+    ));
+    Ok(vec![stable_hash_string(&(job.seed, job.params))?])
+}
+
+#[test]
+fn parallel_sweeps_match_serial_byte_for_byte() {
+    let serial = sweep_with(&lhs_plan(500, 1), 8001, &seeded_executor).expect("serial");
+    let parallel = sweep_with(&lhs_plan(500, 8), 8001, &seeded_executor).expect("parallel");
+    assert_eq!(serial.jobs.len(), 500);
+    assert_eq!(serial.plan_hash, parallel.plan_hash);
+    assert_eq!(
+        to_canonical_json_bytes(&serial).expect("json"),
+        to_canonical_json_bytes(&parallel).expect("json")
+    );
+}
+
+#[test]
+fn job_seeds_depend_only_on_index() {
+    let order = std::sync::Mutex::new(Vec::new());
+    let recording = |job: &SweepJob<'_>| {
+        order.lock().unwrap().push(job.index);
+        seeded_executor(job)
+    };
+    let parallel = sweep_with(&lhs_plan(64, 8), 8001, &recording).expect("parallel");
+    let serial = sweep(&lhs_plan(64, 1), 8001).expect("serial");
+    let mut executed = order.into_inner().unwrap();
+    executed.sort_unstable();
+    assert_eq!(executed, (0..64).collect::<Vec<_>>());
+    for (idx, (lhs, rhs)) in parallel.jobs.iter().zip(&serial.jobs).enumerate() {
+        assert_eq!(
+            lhs.seed, rhs.seed,
+            "seed of job {idx} depends on execution order"
+        );
+        assert_eq!(lhs.params, rhs.params);
+        assert_eq!(lhs.out_dir, format!("job_{idx:04}"));
+    }
+}
+
+#[test]
+fn failing_jobs_are_recorded_without_aborting() {
+    let flaky = |job: &SweepJob<'_>| {
+        if job.index % 7 == 3 {
+            return Err(AsmError::Serde(ErrorInfo::new(
+                "job-failed",
+                format!("job {} diverged", job.index),
+            )));
+        }
+        seeded_executor(job)
+    };
+    let serial = sweep_with(&lhs_plan(50, 1), 8001, &flaky).expect("serial");
+    let parallel = sweep_with(&lhs_plan(50, 8), 8001, &flaky).expect("parallel");
+    assert_eq!(serial, parallel);
+    assert_eq!(parallel.failed, 7);
+    for (idx, job) in parallel.jobs.iter().enumerate() {
+        if idx % 7 == 3 {
+            assert_eq!(job.status, "failed");
+            assert!(job.end_hashes.is_empty());
+            assert!(job.error.as_deref().unwrap().contains("diverged"));
+        } else {
+            assert_eq!(job.status, "completed");
+            assert!(job.error.is_none());
+        }
+    }
+}
+
+#[test]
+fn preview_enumerates_the_jobs_a_run_executes() {
+    let grid = SweepPlan {
+        strategy: SweepStrategy::Grid {
+            parameters: vec![
+                GridParameter {
+                    name: "degree_cap".to_string(),
+                    values: vec![json!(2), json!(3), json!(4)],
+                },
+                GridParameter {
+                    name: "worm_weight".to_string(),
+                    values: vec![json!(0.1), json!(0.2)],
+                },
+            ],
+        },
+        scheduler: Default::default(),
+        concurrency: 4,
+    };
+    for (plan, seed) in [(grid, 8001), (lhs_plan(37, 8), 8001), (lhs_plan(37, 1), 9)] {
+        let preview = plan.preview(seed).expect("preview");
+        let report = sweep(&plan, seed).expect("sweep");
+        assert_eq!(preview.plan_hash, report.plan_hash);
+        assert_eq!(preview.job_count, report.jobs.len());
+        assert_eq!(
+            preview.waves,
+            report.jobs.len().div_ceil(plan.concurrency.max(1))
+        );
+        for (idx, (planned, job)) in preview.jobs.iter().zip(&report.jobs).enumerate() {
+            assert_eq!(planned.index, idx);
+            assert_eq!(planned.seed, job.seed);
+            assert_eq!(planned.out_dir, job.out_dir);
+            assert_eq!(serde_json::to_value(&planned.params).unwrap(), job.params);
+        }
+    }
+}
+
+fn sobol_plan(dimensions: usize, samples: usize) -> SweepPlan {
+    SweepPlan {
+        strategy: SweepStrategy::Sobol {
+            parameters: (0..dimensions)
+                .map(|dim| LhsParameter {
+                    name: format!("p{dim:02}"),
+                    min: 0.0,
+                    max: 1.0,
+                })
+                .collect(),
+            samples,
+        },
+        scheduler: Default::default(),
+        concurrency: 1,
+    }
+}
+
+fn sobol_points(report: &asm_exp::SweepReport) -> Vec<Vec<f64>> {
+    report
+        .jobs
+        .iter()
+        .map(|job| {
+            job.params
+                .as_object()
+                .unwrap()
+                .values()
+                .map(|value| value.as_f64().unwrap())
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn sobol_sweeps_follow_the_reference_sequence() {
+    let report = sweep(&sobol_plan(3, 8), 8001).expect("sweep");
+    let expected = [
+        [0.0, 0.0, 0.0],
+        [0.5, 0.5, 0.5],
+        [0.75, 0.25, 0.25],
+        [0.25, 0.75, 0.75],
+        [0.375, 0.375, 0.625],
+        [0.875, 0.875, 0.125],
+        [0.625, 0.125, 0.875],
+        [0.125, 0.625, 0.375],
+    ];
+    assert_eq!(sobol_points(&report), expected.map(Vec::from).to_vec());
+
+    // Points ignore the sweep seed; job seeds still follow it.
+    let reseeded = sweep(&sobol_plan(3, 8), 42).expect("sweep");
+    assert_eq!(sobol_points(&reseeded), sobol_points(&report));
+    assert_ne!(reseeded.jobs[0].seed, report.jobs[0].seed);
+}
+
+#[test]
+fn sobol_sweeps_stratify_every_supported_dimension() {
+    let samples = 64;
+    let report = sweep(&sobol_plan(SOBOL_MAX_DIMENSIONS, samples), 8001).expect("sweep");
+    let points = sobol_points(&report);
+    for dim in 0..SOBOL_MAX_DIMENSIONS {
+        let mut strata: Vec<usize> = points
+            .iter()
+            .map(|point| (point[dim] * samples as f64) as usize)
+            .collect();
+        strata.sort_unstable();
+        assert_eq!(strata, (0..samples).collect::<Vec<_>>(), "dimension {dim}");
+    }
+
+    let err = sweep(&sobol_plan(SOBOL_MAX_DIMENSIONS + 1, 4), 8001).unwrap_err();
+    assert_eq!(err.info().code, "sweep-sobol-dimensions");
+}
+
+fn early_stop_plan(concurrency: usize, good_budget: Option<usize>) -> SweepPlan {
+    SweepPlan {
+        strategy: SweepStrategy::Grid {
+            parameters: vec![
+                GridParameter {
+                    name: "degree_cap".to_string(),
+                    values: vec![json!(2), json!(3), json!(4)],
+                },
+                GridParameter {
+                    name: "worm_weight".to_string(),
+                    values: vec![json!(0.1), json!(0.2), json!(0.3), json!(0.4)],
+                },
+            ],
+        },
+        scheduler: Scheduler {
+            early_stop: Some(EarlyStop {
+                good_budget,
+                branch_depth: 1,
+            }),
+            ..Default::default()
+        },
+        concurrency,
+    }
+}
+
+/// Prunes the `degree_cap = 2` branch once `worm_weight` passes 0.15 and
+/// counts every `degree_cap = 4` job as good.
+fn kpi(job: &SweepJobReport) -> JobVerdict {
+    match (
+        job.params["degree_cap"].as_i64(),
+        job.params["worm_weight"].as_f64(),
+    ) {
+        (Some(2), Some(weight)) if weight > 0.15 => JobVerdict::Prune,
+        (Some(4), _) => JobVerdict::Good,
+        _ => JobVerdict::Neutral,
+    }
+}
+
+#[test]
+fn early_stopping_prunes_branches_and_honours_the_budget() {
+    let report = sweep_with_predicate(&early_stop_plan(1, Some(2)), 8001, &seeded_executor, &kpi)
+        .expect("sweep");
+    let statuses: Vec<Vec<&str>> = report
+        .jobs
+        .chunks(4)
+        .map(|branch| branch.iter().map(|job| job.status.as_str()).collect())
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            ["completed", "completed", "pruned", "pruned"],
+            ["completed", "completed", "completed", "completed"],
+            ["completed", "completed", "skipped", "skipped"],
+        ]
+    );
+    let log = report.early_stop.as_ref().expect("early stop log");
+    assert_eq!(log.pruned.len(), 1);
+    assert_eq!(log.pruned[0].trigger, 1);
+    assert_eq!(log.pruned[0].branch["degree_cap"], json!(2));
+    assert_eq!(log.pruned[0].skipped, [2, 3]);
+    assert_eq!(log.budget_reached_at, Some(9));
+    assert_eq!(log.verdicts.len(), 8);
+    assert!(report.jobs[2].end_hashes.is_empty());
+    assert_eq!(report.jobs.len(), 12);
+
+    for concurrency in [3, 5, 12] {
+        let parallel = sweep_with_predicate(
+            &early_stop_plan(concurrency, Some(2)),
+            8001,
+            &seeded_executor,
+            &kpi,
+        )
+        .expect("parallel sweep");
+        assert_eq!(
+            to_canonical_json_bytes(&report).expect("json"),
+            to_canonical_json_bytes(&parallel).expect("json"),
+            "concurrency {concurrency} diverged from the serial run"
+        );
+    }
+
+    let unlimited = sweep_with_predicate(&early_stop_plan(1, None), 8001, &seeded_executor, &kpi)
+        .expect("sweep");
+    assert!(unlimited
+        .early_stop
+        .as_ref()
+        .unwrap()
+        .budget_reached_at
+        .is_none());
+    assert_eq!(
+        unlimited
+            .jobs
+            .iter()
+            .filter(|job| job.status == "completed")
+            .count(),
+        10
+    );
+    let plain = sweep_with(&early_stop_plan(1, None), 8001, &seeded_executor).expect("sweep");
+    assert!(plain.early_stop.is_none());
+    for (ran, full) in unlimited.jobs.iter().zip(&plain.jobs) {
+        if ran.status == "completed" {
+            assert_eq!(ran, full);
+        }
+    }
+}
@@ -1,6 +1,14 @@
 use asm_aut::AnalysisReport;
-use asm_gauge::{build_rep, ward_check, RepOpts, WardOpts};
-use asm_spec::{from_json_slice as spectrum_from_slice, SpectrumReport};
+use asm_gauge::{
+    build_rep, check_closure, from_json_slice, jacobi_check, to_canonical_json_bytes, ward_check,
+    ClosureOpts, JacobiOpts, RepOpts, StructureTensorEntry, SubsetSelection, SubsetSpec,
+    WardCorrelator, WardOpts, WardReport, COMMUTATOR_IDENTITY,
+};
+use asm_spec::{from_json_slice as spectrum_from_slice, OperatorsInfo, SpectrumReport};
+
+mod common;
+
+use common::su2_adjoint;
 
 fn load_inputs() -> (SpectrumReport, AnalysisReport) {
     let spectrum_bytes = include_bytes!("../fixtures/phase11/t1_seed0/spectrum_report.json");
@@ -17,3 +25,110 @@ fn ward_commutator_within_tol() {
     let report = ward_check(&rep, &spectrum.operators.info, &WardOpts::default()).expect("ward");
     assert!(report.pass, "ward report should pass: {:?}", report);
 }
+
+#[test]
+fn su2_structure_constants_satisfy_jacobi() {
+    let closure = check_closure(&su2_adjoint(), &ClosureOpts::default()).expect("closure");
+    let report = jacobi_check(&closure.structure_tensors, &JacobiOpts::default()).expect("jacobi");
+    assert!(report.pass, "{report:?}");
+    assert_eq!(report.generators, 3);
+    assert_eq!(report.triples_checked, 1);
+
+    let mut broken = closure.structure_tensors.clone();
+    broken.push(StructureTensorEntry {
+        i: 0,
+        j: 3,
+        k: 1,
+        value: 1.0,
+    });
+    let report = jacobi_check(&broken, &JacobiOpts::default()).expect("jacobi");
+    assert!(!report.pass);
+    assert!(report.worst_triple.is_some());
+}
+
+fn adjoint_operators() -> OperatorsInfo {
+    OperatorsInfo {
+        num_nodes: 3,
+        num_edges: 3,
+        nnz: 9,
+        avg_degree: 2.0,
+        max_degree: 2,
+        code_variables: 3,
+        code_rank_x: 1,
+        code_rank_z: 1,
+        hash: String::new(),
+    }
+}
+
+#[test]
+fn ward_suite_reports_each_correlator() {
+    let opts = WardOpts {
+        relative_tol: 10.0,
+        correlators: vec![
+            WardCorrelator {
+                name: "singlet".to_string(),
+                matrix: vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+                tolerance: Some(1e-9),
+            },
+            WardCorrelator {
+                name: "broken".to_string(),
+                matrix: vec![1.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 3.0],
+                tolerance: Some(1e-9),
+            },
+        ],
+        ..WardOpts::default()
+    };
+    let ward = ward_check(&su2_adjoint(), &adjoint_operators(), &opts).expect("ward");
+    let names: Vec<&str> = ward.identities.iter().map(|id| id.name.as_str()).collect();
+    assert_eq!(names, [COMMUTATOR_IDENTITY, "singlet", "broken"]);
+    assert!(ward.identities[1].pass);
+    assert!(!ward.identities[2].pass);
+    assert!(!ward.pass);
+}
+
+#[test]
+fn commutator_only_report_round_trips() {
+    let ward =
+        ward_check(&su2_adjoint(), &adjoint_operators(), &WardOpts::default()).expect("ward");
+    assert_eq!(ward.identities.len(), 1);
+    let bytes = to_canonical_json_bytes(&ward).expect("encode");
+    let restored: WardReport = from_json_slice(&bytes).expect("decode");
+    assert_eq!(restored, ward);
+    assert_eq!(restored.identities[0].name, COMMUTATOR_IDENTITY);
+}
+
+fn subset(selection: SubsetSelection, seed: u64) -> Option<SubsetSpec> {
+    Some(SubsetSpec { selection, seed })
+}
+
+#[test]
+fn covering_operator_subsets_match_full_ward_check() {
+    let rep = su2_adjoint();
+    let correlators = vec![WardCorrelator {
+        name: "broken".to_string(),
+        matrix: vec![1.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 3.0],
+        tolerance: None,
+    }];
+    let full = ward_check(
+        &rep,
+        &adjoint_operators(),
+        &WardOpts {
+            correlators: correlators.clone(),
+            ..WardOpts::default()
+        },
+    )
+    .expect("ward");
+    let covered = ward_check(
+        &rep,
+        &adjoint_operators(),
+        &WardOpts {
+            correlators,
+            operator_subset: subset(SubsetSelection::Fraction(1.0), 3),
+            ..WardOpts::default()
+        },
+    )
+    .expect("ward");
+    assert!(!covered.partial);
+    assert_eq!(covered.max_comm_norm, full.max_comm_norm);
+    assert_eq!(covered.identities, full.identities);
+}